@@ -0,0 +1,67 @@
+//! フィード取得のレイテンシ比較ベンチマーク
+//!
+//! リクエスト毎にクライアントを作り直す場合（接続を再利用しない）と、
+//! 1つのクライアントを使い回す場合（ホスト毎に接続を再利用する）の所要時間を比較する。
+//!
+//! `cargo bench --bench feed_fetch`で実行する。
+use datadoggo::infra::api::http::{HttpClient, HttpClientConfig, ReqwestHttpClient};
+use std::time::Instant;
+
+/// ベンチマークで取得するフィード数
+const FEEDS: usize = 200;
+
+/// 同じホストに置くフィード数
+const FEEDS_PER_HOST: usize = 20;
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().expect("tokioランタイムの作成に失敗");
+    runtime.block_on(async {
+        let servers: Vec<httpmock::MockServer> = futures::future::join_all(
+            (0..FEEDS / FEEDS_PER_HOST).map(|_| httpmock::MockServer::start_async()),
+        )
+        .await;
+        for server in &servers {
+            server
+                .mock_async(|when, then| {
+                    when.method(httpmock::Method::GET);
+                    then.status(200)
+                        .body("<rss version=\"2.0\"><channel></channel></rss>");
+                })
+                .await;
+        }
+        let urls: Vec<String> = (0..FEEDS)
+            .map(|i| servers[i % servers.len()].url(format!("/feed/{}.xml", i)))
+            .collect();
+
+        let config = HttpClientConfig::default();
+        let started = Instant::now();
+        for url in &urls {
+            let client = ReqwestHttpClient::with_config(&config).unwrap();
+            fetch(&client, url).await;
+        }
+        let fresh = started.elapsed();
+
+        let shared = ReqwestHttpClient::with_config(&config).unwrap();
+        let started = Instant::now();
+        for url in &urls {
+            fetch(&shared, url).await;
+        }
+        let reused = started.elapsed();
+
+        println!("フィード{}件（{}ホスト）の取得", FEEDS, servers.len());
+        println!("  リクエスト毎に接続: {:?}", fresh);
+        println!("  接続を再利用: {:?}", reused);
+        if let Some(stats) = shared.pool_stats() {
+            for line in stats.format_lines() {
+                println!("  {}", line);
+            }
+        }
+    });
+}
+
+async fn fetch(client: &ReqwestHttpClient, url: &str) {
+    client
+        .fetch(url, 10)
+        .await
+        .expect("ベンチマーク用サーバからの取得に失敗");
+}
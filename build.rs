@@ -0,0 +1,7 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=build.rs");
+    // gRPCサーバ（grpc feature）を有効にした場合のみproto定義からコードを生成する
+    #[cfg(feature = "grpc")]
+    tonic_build::compile_protos("proto/datadoggo.proto")?;
+    Ok(())
+}
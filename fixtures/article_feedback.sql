@@ -0,0 +1,9 @@
+-- 記事フィードバックのテスト用データ
+-- good.example.comは全件取得成功、bad.example.comは半分が取得エラー
+
+INSERT INTO articles (url, status_code, content)
+VALUES
+    ('https://good.example.com/1', 200, '良い記事1'),
+    ('https://good.example.com/2', 200, '良い記事2'),
+    ('https://bad.example.com/1', 200, '微妙な記事'),
+    ('https://bad.example.com/2', 500, '取得エラー: timeout');
@@ -0,0 +1,3 @@
+-- soft delete用の削除日時（NULLなら有効なレコード）
+ALTER TABLE article_links ADD COLUMN deleted_at TIMESTAMPTZ;
+ALTER TABLE articles ADD COLUMN deleted_at TIMESTAMPTZ;
@@ -0,0 +1,11 @@
+-- article_linksへの新規INSERTをNOTIFYで通知する（payloadはurl）
+CREATE OR REPLACE FUNCTION notify_article_link_inserted() RETURNS trigger AS $$
+BEGIN
+    PERFORM pg_notify('article_links_inserted', NEW.url);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER article_links_inserted_notify
+    AFTER INSERT ON article_links
+    FOR EACH ROW EXECUTE FUNCTION notify_article_link_inserted();
@@ -0,0 +1,2 @@
+-- タイトルのキーワード選別による記事取得の対象可否（falseならスクレイプしない）
+ALTER TABLE article_links ADD COLUMN fetch_target BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,7 @@
+-- 読んだ記事への評価（1-5）とメモ（1記事につき1件、再評価時は上書き）
+CREATE TABLE article_feedback (
+    url TEXT PRIMARY KEY REFERENCES articles (url) ON DELETE CASCADE,
+    score SMALLINT NOT NULL CHECK (score BETWEEN 1 AND 5),
+    note TEXT,
+    rated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
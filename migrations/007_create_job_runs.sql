@@ -0,0 +1,16 @@
+-- ワークフロー実行の履歴と実行期間中の統計（前回実行との比較に使う）
+CREATE TABLE job_runs (
+    id BIGSERIAL PRIMARY KEY,
+    job_name TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'running',
+    started_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    finished_at TIMESTAMPTZ,
+    new_links BIGINT NOT NULL DEFAULT 0,
+    fetched_articles BIGINT NOT NULL DEFAULT 0,
+    failed_articles BIGINT NOT NULL DEFAULT 0
+);
+
+CREATE INDEX job_runs_job_name_idx ON job_runs (job_name, id);
+
+-- 実行期間中に追加されたリンクを数えるための登録日時
+ALTER TABLE article_links ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP;
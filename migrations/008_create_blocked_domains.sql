@@ -0,0 +1,6 @@
+-- 法的・ポリシー上クロールしてはいけないドメイン（サブドメインも対象）
+CREATE TABLE blocked_domains (
+    domain TEXT PRIMARY KEY,
+    reason TEXT NOT NULL,
+    blocked_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,4 @@
+-- リンクを収集したフィード（"group/name"、RSS以外から登録されたリンクはNULL）
+ALTER TABLE article_links ADD COLUMN feed TEXT;
+
+CREATE INDEX article_links_feed_idx ON article_links (feed);
@@ -0,0 +1,8 @@
+-- 外部のアクセス解析から取り込んだ記事毎の指標（閲覧数など）
+CREATE TABLE article_external_metrics (
+    url TEXT NOT NULL REFERENCES article_links (url) ON DELETE CASCADE,
+    metric TEXT NOT NULL,
+    value DOUBLE PRECISION NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (url, metric)
+);
@@ -0,0 +1,10 @@
+-- 記事取得の試行履歴（articlesは最新の結果で上書きされるため、試行毎に記録する）
+CREATE TABLE article_fetch_attempts (
+    id BIGSERIAL PRIMARY KEY,
+    url TEXT NOT NULL,
+    status_code INTEGER NOT NULL,
+    content_hash TEXT NOT NULL,
+    attempted_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX article_fetch_attempts_url_idx ON article_fetch_attempts (url, attempted_at);
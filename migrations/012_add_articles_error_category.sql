@@ -0,0 +1,5 @@
+-- 取得エラーの原因カテゴリ（timeout / dns / blocked など、成功記事はNULL）
+ALTER TABLE articles ADD COLUMN error_category TEXT;
+
+CREATE INDEX articles_error_category_idx ON articles (error_category, timestamp)
+    WHERE error_category IS NOT NULL;
@@ -0,0 +1,16 @@
+-- バックログ取得（未削除・取得対象のリンクをpub_date降順）用の部分インデックス
+CREATE INDEX article_links_backlog_idx ON article_links (pub_date DESC, url)
+    WHERE deleted_at IS NULL AND fetch_target;
+
+-- 公開日時の範囲検索・並び替え用
+CREATE INDEX article_links_pub_date_idx ON article_links (pub_date DESC, url);
+
+-- ソース別の集計・絞り込み用
+CREATE INDEX article_links_source_idx ON article_links (source);
+
+-- エラー記事（status_code != 200）の結合・集計用の部分インデックス
+CREATE INDEX articles_error_status_idx ON articles (url, status_code)
+    WHERE status_code <> 200;
+
+-- 取得日時の範囲検索用
+CREATE INDEX articles_timestamp_idx ON articles (timestamp);
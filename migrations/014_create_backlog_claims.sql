@@ -0,0 +1,10 @@
+-- 複数ワーカーでバックログを分担するための処理権（クレーム）
+-- expires_atを過ぎたクレームは他のワーカーが取り直せる
+CREATE TABLE backlog_claims (
+    url TEXT PRIMARY KEY REFERENCES article_links (url) ON DELETE CASCADE,
+    worker_id TEXT NOT NULL,
+    claimed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX backlog_claims_worker_idx ON backlog_claims (worker_id);
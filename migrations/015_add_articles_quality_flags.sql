@@ -0,0 +1,2 @@
+-- 本文の修復できなかった構造の崩れ（unclosed_code_fence / broken_table のカンマ区切り、問題が無ければNULL）
+ALTER TABLE articles ADD COLUMN quality_flags TEXT;
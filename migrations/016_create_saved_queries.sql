@@ -0,0 +1,7 @@
+-- 名前付きで保存した記事の検索条件（ArticleQueryのJSON）
+CREATE TABLE saved_queries (
+    name TEXT PRIMARY KEY,
+    query_json JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,25 @@
+-- URLが変わっても変わらない参照用のID（ULID: 時刻48bit + 乱数80bitのCrockford Base32、26文字）
+CREATE FUNCTION gen_ulid() RETURNS TEXT AS $$
+DECLARE
+    encoding CONSTANT TEXT := '0123456789ABCDEFGHJKMNPQRSTVWXYZ';
+    ts BIGINT := (EXTRACT(EPOCH FROM clock_timestamp()) * 1000)::BIGINT;
+    output TEXT := '';
+BEGIN
+    -- 時刻部（10文字）
+    FOR i IN REVERSE 9..0 LOOP
+        output := output || substr(encoding, ((ts >> (i * 5)) & 31)::INT + 1, 1);
+    END LOOP;
+    -- 乱数部（16文字）
+    FOR i IN 1..16 LOOP
+        output := output || substr(encoding, floor(random() * 32)::INT + 1, 1);
+    END LOOP;
+    RETURN output;
+END;
+$$ LANGUAGE plpgsql VOLATILE;
+
+-- 既存の行にも1行毎に異なるIDが割り当てられる
+ALTER TABLE article_links ADD COLUMN id TEXT NOT NULL DEFAULT gen_ulid();
+CREATE UNIQUE INDEX article_links_id_idx ON article_links (id);
+
+ALTER TABLE articles ADD COLUMN id TEXT NOT NULL DEFAULT gen_ulid();
+CREATE UNIQUE INDEX articles_id_idx ON articles (id);
@@ -0,0 +1,2 @@
+-- デバッグモードで取得した記事の処理ログ（リクエスト・取得試行・パース警告、対象外の記事はNULL）
+ALTER TABLE articles ADD COLUMN debug_log JSONB;
@@ -0,0 +1,8 @@
+-- エラー記事の再処理の管理
+-- retry_count: 連続して取得に失敗した回数（成功すると0に戻す）
+-- next_retry_at: 次に再処理する時刻（NULLは再処理を打ち切った記事。既存の記事はすぐに再処理の対象にする）
+ALTER TABLE articles
+    ADD COLUMN retry_count INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN next_retry_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP;
+
+CREATE INDEX idx_articles_next_retry_at ON articles (next_retry_at) WHERE status_code <> 200;
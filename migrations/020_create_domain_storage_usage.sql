@@ -0,0 +1,7 @@
+-- ドメイン毎の記事本文の保存容量（refresh_domain_storage_usageで定期的に集計し直す）
+CREATE TABLE domain_storage_usage (
+    domain TEXT PRIMARY KEY,
+    article_count BIGINT NOT NULL,
+    content_bytes BIGINT NOT NULL,
+    aggregated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,7 @@
+-- 記事本文の全文検索用のtsvector生成列とGINインデックス
+-- 言語に依存しない'simple'設定で語を分割する（空白・記号区切りのため、日本語は文節単位の語になる）
+ALTER TABLE articles
+    ADD COLUMN content_tsv tsvector
+        GENERATED ALWAYS AS (to_tsvector('simple', content)) STORED;
+
+CREATE INDEX articles_content_tsv_idx ON articles USING GIN (content_tsv);
@@ -0,0 +1,24 @@
+-- 収集処理のシミュレーション（過去の収集のリプレイ）用に記録する取得結果
+-- replay.record_snapshotsを有効にしたワークフローの実行時のみ記録する
+
+-- 取得できたRSSフィードの本文（フィードURL毎、取得の度に追記）
+CREATE TABLE feed_snapshots (
+    id BIGSERIAL PRIMARY KEY,
+    url TEXT NOT NULL,
+    body TEXT NOT NULL,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX feed_snapshots_fetched_at_idx ON feed_snapshots (fetched_at);
+
+-- スクレイパーが返した加工前の本文（markdown）または取得エラー（記事URL毎、取得の度に追記）
+CREATE TABLE raw_scrapes (
+    id BIGSERIAL PRIMARY KEY,
+    url TEXT NOT NULL,
+    markdown TEXT,
+    error TEXT,
+    scraped_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX raw_scrapes_scraped_at_idx ON raw_scrapes (scraped_at);
+CREATE INDEX raw_scrapes_url_idx ON raw_scrapes (url, scraped_at DESC);
@@ -0,0 +1,12 @@
+-- フィード定義（config/feeds.yamlの代わりにDBで管理する場合に使う）
+-- 無効にしたフィードは削除せずenabled = FALSEとして残す
+CREATE TABLE feeds (
+    id BIGSERIAL PRIMARY KEY,
+    "group" TEXT NOT NULL,
+    name TEXT NOT NULL,
+    url TEXT NOT NULL,
+    fallback_urls TEXT[] NOT NULL DEFAULT '{}',
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE ("group", name)
+);
@@ -0,0 +1,9 @@
+-- フィード毎の取得スケジュール
+-- fetch_intervalは取得間隔（分、NULLは毎回取得）。feeds.yamlを使う場合はYAMLに記載する
+ALTER TABLE feeds ADD COLUMN fetch_interval INTEGER CHECK (fetch_interval > 0);
+
+-- フィード（"group/name"）毎の前回の取得日時（feeds.yaml・feedsテーブルのどちらを使う場合も記録する）
+CREATE TABLE feed_fetch_times (
+    feed TEXT PRIMARY KEY,
+    last_fetched_at TIMESTAMPTZ NOT NULL
+);
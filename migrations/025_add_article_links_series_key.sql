@@ -0,0 +1,5 @@
+-- 連載記事をまとめるシリーズキー（config/app.yamlのseries.rulesでURL・タイトルから導出、該当しないリンクはNULL）
+ALTER TABLE article_links ADD COLUMN series_key TEXT;
+
+CREATE INDEX article_links_series_key_idx ON article_links (series_key, pub_date DESC)
+    WHERE series_key IS NOT NULL;
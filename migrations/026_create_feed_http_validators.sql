@@ -0,0 +1,8 @@
+-- フィードURL毎のHTTP条件付きリクエストの検証子（前回の応答のETag / Last-Modified）
+-- フォールバックURLは別のサーバのため、フィードではなくURL毎に記録する
+CREATE TABLE feed_http_validators (
+    url TEXT PRIMARY KEY,
+    etag TEXT,
+    last_modified TEXT,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,18 @@
+-- 本文のSHA-256（取得に成功した記事のみ、重複記事の検出に使う）と、同じ本文を先に保存した記事のURL
+ALTER TABLE articles ADD COLUMN content_hash TEXT, ADD COLUMN duplicate_of TEXT;
+
+UPDATE articles SET content_hash = encode(sha256(convert_to(content, 'UTF8')), 'hex')
+WHERE status_code = 200 AND content <> '';
+
+CREATE INDEX articles_content_hash_idx ON articles (content_hash, timestamp)
+    WHERE content_hash IS NOT NULL;
+
+UPDATE articles a SET duplicate_of = (
+    SELECT o.url FROM articles o
+    WHERE o.content_hash = a.content_hash
+        AND o.deleted_at IS NULL
+        AND (o.timestamp, o.url) < (a.timestamp, a.url)
+    ORDER BY o.timestamp, o.url
+    LIMIT 1
+)
+WHERE a.content_hash IS NOT NULL;
@@ -0,0 +1,11 @@
+-- 記事本文に埋め込まれたツイート・動画の参照（記事の保存時に本文から抽出して記事毎に置き換える）
+CREATE TABLE article_embeds (
+    id BIGSERIAL PRIMARY KEY,
+    url TEXT NOT NULL,
+    -- twitter / youtube
+    embed_type TEXT NOT NULL,
+    embed_url TEXT NOT NULL
+);
+
+CREATE INDEX article_embeds_url_idx ON article_embeds (url);
+CREATE INDEX article_embeds_embed_url_idx ON article_embeds (embed_url);
@@ -0,0 +1,12 @@
+-- RSSの<item>から取り込む記事のメタデータ（概要・著者、フィードに無い場合はNULL）
+ALTER TABLE article_links ADD COLUMN description TEXT;
+ALTER TABLE article_links ADD COLUMN author TEXT;
+
+-- RSSの<item>の<category>（1つのリンクに複数）
+CREATE TABLE article_categories (
+    url TEXT NOT NULL REFERENCES article_links (url) ON DELETE CASCADE,
+    category TEXT NOT NULL,
+    PRIMARY KEY (url, category)
+);
+
+CREATE INDEX article_categories_category_idx ON article_categories (category);
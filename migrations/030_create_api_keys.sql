@@ -0,0 +1,9 @@
+-- RESTサーバのAPIキー（キーそのものは保存せずSHA-256のみ、失効したキーはrevoked_atを記録して残す）
+CREATE TABLE api_keys (
+    name TEXT PRIMARY KEY,
+    key_hash TEXT NOT NULL UNIQUE,
+    -- 1分あたりのリクエスト数の上限（NULLはserver.rate_limit_per_minuteの既定値）
+    rate_limit_per_minute INTEGER,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    revoked_at TIMESTAMPTZ
+);
@@ -0,0 +1,17 @@
+-- 収集処理（フィード・記事の取得など）で発生したエラーの詳細（ログに流れて消えないよう記録する）
+CREATE TABLE collection_errors (
+    id BIGSERIAL PRIMARY KEY,
+    -- feed / article / task
+    entity_type TEXT NOT NULL,
+    -- フィードのキー（group/name）・記事のURL・タスクの処理名
+    entity_key TEXT NOT NULL,
+    error_category TEXT NOT NULL,
+    message TEXT NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- 発生時に実行中だったワークフロー（ワークフロー外の実行はNULL）
+    job_id BIGINT REFERENCES job_runs (id) ON DELETE SET NULL
+);
+
+CREATE INDEX collection_errors_occurred_at_idx ON collection_errors (occurred_at);
+CREATE INDEX collection_errors_entity_idx ON collection_errors (entity_type, entity_key);
+CREATE INDEX collection_errors_job_id_idx ON collection_errors (job_id);
@@ -0,0 +1,19 @@
+-- フィードの取得毎の結果（成功・304・失敗の履歴を後から調査できるよう記録する）
+CREATE TABLE feed_fetch_logs (
+    id BIGSERIAL PRIMARY KEY,
+    -- フィードのキー（group/name）
+    feed TEXT NOT NULL,
+    -- 取得できたURL（失敗した場合はメインURL）
+    url TEXT NOT NULL,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- success / not_modified / failed
+    result TEXT NOT NULL,
+    -- 失敗の原因カテゴリとエラーメッセージ（失敗以外はNULL）
+    error_category TEXT,
+    error_message TEXT,
+    -- フィードから抽出したリンク数
+    link_count INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX feed_fetch_logs_feed_idx ON feed_fetch_logs (feed, fetched_at);
+CREATE INDEX feed_fetch_logs_fetched_at_idx ON feed_fetch_logs (fetched_at);
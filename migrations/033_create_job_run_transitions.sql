@@ -0,0 +1,13 @@
+-- ワークフローの実行の状態遷移の履歴（job_runs.statusの変更毎に1行）
+CREATE TABLE job_run_transitions (
+    id BIGSERIAL PRIMARY KEY,
+    job_id BIGINT NOT NULL REFERENCES job_runs (id) ON DELETE CASCADE,
+    from_state TEXT NOT NULL,
+    to_state TEXT NOT NULL,
+    transitioned_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX job_run_transitions_job_id_idx ON job_run_transitions (job_id, id);
+
+-- 再起動時に未完了の実行を探すための索引
+CREATE INDEX job_runs_unfinished_idx ON job_runs (job_name, id) WHERE finished_at IS NULL;
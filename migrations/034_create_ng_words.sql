@@ -0,0 +1,6 @@
+-- 収集記事の検閲に使うNGワード（config/app.yamlのarticle_fetch.ng_words.wordsと合わせて使う）
+CREATE TABLE ng_words (
+    word TEXT PRIMARY KEY,
+    reason TEXT NOT NULL DEFAULT '',
+    added_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,4 @@
+-- 本文から推定した記事の言語（ISO 639-3、推定できなかった記事はNULL）
+ALTER TABLE articles ADD COLUMN language TEXT;
+
+CREATE INDEX articles_language_idx ON articles (language);
@@ -0,0 +1,2 @@
+-- 記事ページの元のHTML（scraper.raw_htmlを有効にした場合のみ保存し、それ以外はNULL）
+ALTER TABLE articles ADD COLUMN raw_html TEXT;
@@ -0,0 +1,3 @@
+-- フィード毎の記事リンクの期待URLパターン（正規表現、いずれにも一致しないリンクは収集しない）
+-- 空配列は検証しない。feeds.yamlを使う場合はYAMLに記載する
+ALTER TABLE feeds ADD COLUMN url_patterns TEXT[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,4 @@
+-- 取得済み記事を最後に再クロールした日時（再クロールしていない記事はNULLで、articles.timestampを使う）
+-- 本文が変わらなかった場合はtimestampが更新されないため、再クロールの対象判定はこの列で行う
+ALTER TABLE articles ADD COLUMN recrawled_at TIMESTAMPTZ;
+CREATE INDEX articles_recrawl_idx ON articles ((COALESCE(recrawled_at, timestamp))) WHERE status_code = 200;
@@ -0,0 +1,14 @@
+-- 本文を取得できた記事（新規保存、またはエラー記事の取得成功）をNOTIFYで通知する（payloadはurl）
+-- 本文の更新（再クロールなど）は新着ではないため通知しない
+CREATE OR REPLACE FUNCTION notify_article_stored() RETURNS trigger AS $$
+BEGIN
+    IF NEW.status_code = 200 AND (TG_OP = 'INSERT' OR OLD.status_code <> 200) THEN
+        PERFORM pg_notify('articles_stored', NEW.url);
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER articles_stored_notify
+    AFTER INSERT OR UPDATE OF status_code ON articles
+    FOR EACH ROW EXECUTE FUNCTION notify_article_stored();
@@ -0,0 +1,14 @@
+-- ドメイン毎の記事取得バックエンドの対応表（REST APIから変更し、config/app.yamlのscraper.domainsより優先する）
+CREATE TABLE fetch_backends (
+    id BIGSERIAL PRIMARY KEY,
+    -- 対象のドメイン（サブドメインにも適用する）
+    domain_pattern TEXT NOT NULL UNIQUE,
+    -- firecrawl / local / headless
+    backend TEXT NOT NULL CHECK (backend IN ('firecrawl', 'local', 'headless')),
+    -- バックエンドの設定の上書き（localはscraper.local、headlessはscraper.headlessの項目）
+    options JSONB NOT NULL DEFAULT '{}',
+    -- 複数の行に一致する場合は大きい方を優先する（同じ場合はより長いドメイン）
+    priority INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
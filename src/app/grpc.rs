@@ -0,0 +1,534 @@
+use crate::{
+    app::shutdown_signal,
+    core::{
+        api_access::{
+            constant_time_eq, find_api_client, load_configured_api_keys, ApiClient, GrpcConfig,
+            RateLimiter, ServerConfig,
+        },
+        article::{
+            article_query_limits, get_article, search_articles_fulltext, search_articles_page,
+            stream_articles, Article, ArticleCursor, ArticleKey, ArticleQuery, ArticleStatus,
+            DEFAULT_PAGE_SIZE,
+        },
+        search_query::SearchQuery,
+    },
+    server::{API_KEY_HEADER, INGEST_TOKEN_CLIENT},
+};
+use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use sqlx::PgPool;
+use std::collections::HashSet;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{metadata::MetadataMap, Request, Response, Status};
+
+/// proto/datadoggo.protoから生成したメッセージとサービス
+pub mod proto {
+    tonic::include_proto!("datadoggo.v1");
+}
+
+use proto::article_service_server::{ArticleService, ArticleServiceServer};
+
+/// StreamNewArticlesの送信待ちの記事数（クライアントの受信が遅い場合はこの件数で確認を止める）
+const STREAM_BUFFER: usize = 64;
+
+/// StreamNewArticlesが1回の確認でDBから読み込む1ページの件数
+const STREAM_PAGE_SIZE: i64 = 500;
+
+/// 記事配信のgRPCサービス（認証・レート制限はRESTサーバと同じ方式）
+#[derive(Clone)]
+pub struct ArticleGrpcService {
+    pub pool: PgPool,
+    /// `authorization`メタデータのBearerトークンとして受け付けるトークン
+    pub ingest_token: String,
+    /// 設定で管理するAPIキーとそのクライアント（DBのapi_keysより先に照合する）
+    pub api_keys: Arc<Vec<(String, ApiClient)>>,
+    /// クライアント毎の1分あたりのリクエスト数の既定の上限（0は無制限）
+    pub rate_limit_per_minute: u32,
+    pub rate_limiter: Arc<RateLimiter>,
+    /// StreamNewArticlesが新着記事を確認する間隔
+    pub stream_poll_interval: Duration,
+}
+
+impl ArticleGrpcService {
+    /// 環境変数INGEST_TOKENと設定のAPIキー（`server.api_keys`）を読み込んでサービスを作成
+    pub fn from_env(pool: PgPool, server: &ServerConfig, grpc: &GrpcConfig) -> Result<Self> {
+        let ingest_token = env::var("INGEST_TOKEN")
+            .context("認証トークンの環境変数INGEST_TOKENが設定されていません")?;
+        if ingest_token.is_empty() {
+            anyhow::bail!("環境変数INGEST_TOKENが空です");
+        }
+
+        Ok(Self {
+            pool,
+            ingest_token,
+            api_keys: Arc::new(load_configured_api_keys(server)?),
+            rate_limit_per_minute: server.rate_limit_per_minute,
+            rate_limiter: Arc::new(RateLimiter::default()),
+            stream_poll_interval: Duration::from_secs(grpc.stream_poll_interval_secs.max(1)),
+        })
+    }
+
+    /// # 概要
+    /// リクエストのクライアントを認証し、クライアント毎のレート制限を適用する。
+    ///
+    /// `x-api-key`メタデータを設定のAPIキー、DBのapi_keysの順に照合し、
+    /// 無ければ`authorization`メタデータのBearerトークンを`INGEST_TOKEN`と照合する。
+    /// 認証できなければUNAUTHENTICATED、上限を超えたらRESOURCE_EXHAUSTEDを返す。
+    async fn authorize(&self, metadata: &MetadataMap) -> Result<(), Status> {
+        let client = match metadata
+            .get(API_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+        {
+            Some(key) => {
+                let configured = self
+                    .api_keys
+                    .iter()
+                    .find(|(expected, _)| constant_time_eq(key, expected));
+                match configured {
+                    Some((_, client)) => Some(client.clone()),
+                    None => find_api_client(key, &self.pool).await.map_err(|e| {
+                        tracing::error!(error = format!("{:#}", e), "APIキーの照合に失敗");
+                        Status::internal("APIキーの照合に失敗しました")
+                    })?,
+                }
+            }
+            None => metadata
+                .get("authorization")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .filter(|token| constant_time_eq(token, &self.ingest_token))
+                .map(|_| ApiClient {
+                    name: INGEST_TOKEN_CLIENT.to_string(),
+                    rate_limit_per_minute: None,
+                }),
+        };
+        let client = client.ok_or_else(|| Status::unauthenticated("APIキーが不正です"))?;
+
+        let limit = client
+            .rate_limit_per_minute
+            .unwrap_or(self.rate_limit_per_minute);
+        self.rate_limiter
+            .check(&client.name, limit)
+            .map_err(|_| Status::resource_exhausted("リクエスト数の上限を超えました"))
+    }
+}
+
+#[tonic::async_trait]
+impl ArticleService for ArticleGrpcService {
+    async fn search_articles(
+        &self,
+        request: Request<proto::SearchArticlesRequest>,
+    ) -> Result<Response<proto::SearchArticlesResponse>, Status> {
+        self.authorize(request.metadata()).await?;
+        let query = article_query_from_request(request.into_inner())?;
+
+        // 全文検索は関連度順のためカーソルによるページ送りに対応しない
+        let (articles, next_cursor) = if query.full_text.is_some() {
+            let articles = search_articles_fulltext(query, &self.pool)
+                .await
+                .map_err(|e| internal_error("記事の検索に失敗", e))?;
+            (articles, None)
+        } else {
+            let page = search_articles_page(query, &self.pool)
+                .await
+                .map_err(|e| internal_error("記事の検索に失敗", e))?;
+            (page.articles, page.next_cursor)
+        };
+
+        Ok(Response::new(proto::SearchArticlesResponse {
+            articles: articles.into_iter().map(proto::Article::from).collect(),
+            next_cursor: next_cursor.map(|cursor| cursor.to_string()),
+        }))
+    }
+
+    async fn get_article(
+        &self,
+        request: Request<proto::GetArticleRequest>,
+    ) -> Result<Response<proto::Article>, Status> {
+        self.authorize(request.metadata()).await?;
+        let key = request.into_inner().key;
+        if key.trim().is_empty() {
+            return Err(Status::invalid_argument(
+                "記事のIDまたはURLを指定してください",
+            ));
+        }
+
+        match get_article(&ArticleKey::parse(&key), &self.pool).await {
+            Ok(Some(article)) => Ok(Response::new(article.into())),
+            Ok(None) => Err(Status::not_found("記事が見つかりません")),
+            Err(e) => Err(internal_error("記事の取得に失敗", e)),
+        }
+    }
+
+    type StreamNewArticlesStream = ReceiverStream<Result<proto::Article, Status>>;
+
+    async fn stream_new_articles(
+        &self,
+        request: Request<proto::StreamNewArticlesRequest>,
+    ) -> Result<Response<Self::StreamNewArticlesStream>, Status> {
+        self.authorize(request.metadata()).await?;
+        let since = match request.into_inner().since {
+            Some(since) => parse_datetime("since", &since)?,
+            None => Utc::now(),
+        };
+
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        let pool = self.pool.clone();
+        let interval = self.stream_poll_interval;
+        tokio::spawn(async move {
+            let mut watermark = NewArticleWatermark::new(since);
+            loop {
+                match poll_new_articles(&mut watermark, &pool).await {
+                    Ok(articles) => {
+                        for article in articles {
+                            // クライアントが切断した場合は配信をやめる
+                            if tx.send(Ok(article.into())).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        let _ = tx
+                            .send(Err(internal_error("新着記事の確認に失敗", e)))
+                            .await;
+                        return;
+                    }
+                }
+                tokio::select! {
+                    _ = tokio::time::sleep(interval) => {}
+                    _ = tx.closed() => return,
+                }
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+/// 指定アドレスで記事配信のgRPCサーバを起動する（SIGTERM・Ctrl-Cで終了）
+pub async fn serve_grpc(addr: &str, service: ArticleGrpcService) -> Result<()> {
+    let socket_addr = addr
+        .parse()
+        .with_context(|| format!("アドレスの形式が不正です: {}", addr))?;
+    tracing::info!(addr, "gRPCサーバを起動");
+
+    tonic::transport::Server::builder()
+        .add_service(ArticleServiceServer::new(service))
+        .serve_with_shutdown(socket_addr, shutdown_signal())
+        .await
+        .context("gRPCサーバの実行中にエラーが発生しました")
+}
+
+/// # 概要
+/// SearchArticlesの条件を`ArticleQuery`にする。
+///
+/// 日時・処理状態・カーソルの形式、検索構文、件数の上限をここで検証し、不正ならINVALID_ARGUMENTを返す。
+/// 件数を省略した場合は1ページ分（`DEFAULT_PAGE_SIZE`）とする。
+pub fn article_query_from_request(
+    request: proto::SearchArticlesRequest,
+) -> Result<ArticleQuery, Status> {
+    for pattern in [&request.title_pattern, &request.content_query]
+        .into_iter()
+        .flatten()
+    {
+        SearchQuery::parse(pattern).map_err(|e| invalid_argument("検索条件が不正です", e))?;
+    }
+    let limit = article_query_limits()
+        .resolve(Some(request.limit.unwrap_or(DEFAULT_PAGE_SIZE)))
+        .map_err(|e| invalid_argument("件数が不正です", e))?;
+    if request.full_text.is_some() && request.cursor.is_some() {
+        return Err(Status::invalid_argument(
+            "全文検索はカーソルによるページ送りに対応していません",
+        ));
+    }
+
+    Ok(ArticleQuery {
+        url: request.url,
+        link_pattern: request.link_pattern,
+        title_pattern: request.title_pattern,
+        content_query: request.content_query,
+        full_text: request.full_text,
+        pub_date_from: request
+            .pub_date_from
+            .map(|value| parse_datetime("pub_date_from", &value))
+            .transpose()?,
+        pub_date_to: request
+            .pub_date_to
+            .map(|value| parse_datetime("pub_date_to", &value))
+            .transpose()?,
+        article_status: request
+            .article_status
+            .map(|value| value.parse::<ArticleStatus>())
+            .transpose()
+            .map_err(|e| invalid_argument("処理状態が不正です", e))?,
+        language: request.language,
+        limit: Some(limit),
+        after: request
+            .cursor
+            .map(|value| value.parse::<ArticleCursor>())
+            .transpose()
+            .map_err(|e| invalid_argument("カーソルが不正です", e))?,
+        ..Default::default()
+    })
+}
+
+impl From<Article> for proto::Article {
+    fn from(article: Article) -> Self {
+        Self {
+            id: article.id,
+            url: article.url,
+            title: article.title,
+            pub_date: format_datetime(article.pub_date),
+            updated_at: article.updated_at.map(format_datetime),
+            status_code: article.status_code,
+            content: article.content,
+        }
+    }
+}
+
+/// # 概要
+/// StreamNewArticlesで配信済みの位置（本文を取得した日時）。
+///
+/// 同じ日時に取得した記事を取りこぼさないよう、確認は`since`を含めて行い、
+/// `since`と同じ日時の配信済みの記事はURLで除外する。
+#[derive(Debug, Clone)]
+pub struct NewArticleWatermark {
+    since: DateTime<Utc>,
+    /// 取得日時が`since`と同じ配信済みの記事のURL
+    delivered_at_since: HashSet<String>,
+}
+
+impl NewArticleWatermark {
+    pub fn new(since: DateTime<Utc>) -> Self {
+        Self {
+            since,
+            delivered_at_since: HashSet::new(),
+        }
+    }
+
+    /// 配信済みの記事を除き、取得日時の古い順に並べて位置を進める
+    fn advance(&mut self, articles: Vec<Article>) -> Vec<Article> {
+        let mut articles: Vec<Article> = articles
+            .into_iter()
+            .filter(|article| match article.updated_at {
+                Some(updated_at) if updated_at == self.since => {
+                    !self.delivered_at_since.contains(&article.url)
+                }
+                Some(updated_at) => updated_at > self.since,
+                None => false,
+            })
+            .collect();
+        articles.sort_by(|a, b| (a.updated_at, &a.url).cmp(&(b.updated_at, &b.url)));
+
+        for article in &articles {
+            let Some(updated_at) = article.updated_at else {
+                continue;
+            };
+            if updated_at > self.since {
+                self.since = updated_at;
+                self.delivered_at_since.clear();
+            }
+            self.delivered_at_since.insert(article.url.clone());
+        }
+        articles
+    }
+}
+
+/// # 概要
+/// 前回の確認以降に本文を取得できた記事（ゴミ箱の記事を除く）を取得日時の古い順に返す。
+///
+/// 取得し直した記事も新着として返す。
+pub async fn poll_new_articles(
+    watermark: &mut NewArticleWatermark,
+    pool: &PgPool,
+) -> Result<Vec<Article>> {
+    let query = ArticleQuery {
+        updated_from: Some(watermark.since),
+        article_status: Some(ArticleStatus::Success),
+        ..Default::default()
+    };
+    let mut articles = Vec::new();
+    stream_articles(query, STREAM_PAGE_SIZE, pool, |page| {
+        articles.extend(page);
+        Ok(())
+    })
+    .await?;
+    Ok(watermark.advance(articles))
+}
+
+/// RFC 3339形式の日時を解釈する（不正ならINVALID_ARGUMENT）
+fn parse_datetime(field: &str, value: &str) -> Result<DateTime<Utc>, Status> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|datetime| datetime.with_timezone(&Utc))
+        .map_err(|e| {
+            Status::invalid_argument(format!("{}の日時が不正です: {}: {}", field, value, e))
+        })
+}
+
+fn format_datetime(datetime: DateTime<Utc>) -> String {
+    datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+fn invalid_argument(message: &str, error: anyhow::Error) -> Status {
+    Status::invalid_argument(format!("{}: {:#}", message, error))
+}
+
+/// 内部エラーを記録し、詳細を含めないINTERNALを返す
+fn internal_error(message: &str, error: anyhow::Error) -> Status {
+    tracing::error!(error = format!("{:#}", error), "{}", message);
+    Status::internal(format!("{}しました", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tonic::metadata::MetadataValue;
+
+    fn test_service(pool: PgPool) -> ArticleGrpcService {
+        ArticleGrpcService {
+            pool,
+            ingest_token: "test-token".to_string(),
+            api_keys: Arc::new(Vec::new()),
+            rate_limit_per_minute: 0,
+            rate_limiter: Arc::new(RateLimiter::default()),
+            stream_poll_interval: Duration::from_millis(10),
+        }
+    }
+
+    fn authorized<T>(message: T) -> Request<T> {
+        let mut request = Request::new(message);
+        request.metadata_mut().insert(
+            "authorization",
+            MetadataValue::from_static("Bearer test-token"),
+        );
+        request
+    }
+
+    #[test]
+    fn test_article_query_from_request() {
+        let query = article_query_from_request(proto::SearchArticlesRequest {
+            title_pattern: Some("ニュース -速報".to_string()),
+            pub_date_from: Some("2024-01-01T00:00:00+09:00".to_string()),
+            article_status: Some("4xx".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(query.limit, Some(DEFAULT_PAGE_SIZE));
+        assert_eq!(
+            query.pub_date_from.unwrap().to_rfc3339(),
+            "2023-12-31T15:00:00+00:00"
+        );
+        assert!(matches!(
+            query.article_status,
+            Some(ArticleStatus::ClientError)
+        ));
+
+        let invalid = |request: proto::SearchArticlesRequest| {
+            article_query_from_request(request).unwrap_err().code()
+        };
+        assert_eq!(
+            invalid(proto::SearchArticlesRequest {
+                pub_date_to: Some("昨日".to_string()),
+                ..Default::default()
+            }),
+            tonic::Code::InvalidArgument
+        );
+        assert_eq!(
+            invalid(proto::SearchArticlesRequest {
+                limit: Some(i64::MAX),
+                ..Default::default()
+            }),
+            tonic::Code::InvalidArgument
+        );
+        assert_eq!(
+            invalid(proto::SearchArticlesRequest {
+                full_text: Some("rust".to_string()),
+                cursor: Some("2024-01-01T00:00:00Z|https://example.com/a".to_string()),
+                ..Default::default()
+            }),
+            tonic::Code::InvalidArgument
+        );
+
+        println!("✅ gRPCの検索条件の変換テスト成功");
+    }
+
+    #[sqlx::test(fixtures("../../fixtures/article_query_filter.sql"))]
+    async fn test_grpc_article_service(pool: PgPool) -> Result<(), anyhow::Error> {
+        let service = test_service(pool);
+
+        let status = service
+            .search_articles(Request::new(Default::default()))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+
+        let response = service
+            .search_articles(authorized(proto::SearchArticlesRequest {
+                link_pattern: Some("example.com".to_string()),
+                limit: Some(1),
+                ..Default::default()
+            }))
+            .await?
+            .into_inner();
+        assert_eq!(response.articles.len(), 1);
+        let cursor = response.next_cursor.expect("次のページがある");
+        let next = service
+            .search_articles(authorized(proto::SearchArticlesRequest {
+                link_pattern: Some("example.com".to_string()),
+                cursor: Some(cursor),
+                ..Default::default()
+            }))
+            .await?
+            .into_inner();
+        assert_eq!(next.articles.len(), 1);
+        assert_ne!(next.articles[0].url, response.articles[0].url);
+
+        let article = service
+            .get_article(authorized(proto::GetArticleRequest {
+                key: "https://example.com/news1".to_string(),
+            }))
+            .await?
+            .into_inner();
+        assert_eq!(article.status_code, Some(200));
+        assert_eq!(article.content.as_deref(), Some("ニュース1の内容"));
+        let status = service
+            .get_article(authorized(proto::GetArticleRequest {
+                key: "https://example.com/missing".to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+
+        println!("✅ gRPCの記事配信サービステスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("../../fixtures/article_query_filter.sql"))]
+    async fn test_poll_new_articles(pool: PgPool) -> Result<(), anyhow::Error> {
+        let mut watermark = NewArticleWatermark::new(Utc::now() - chrono::Duration::days(1));
+        let articles = poll_new_articles(&mut watermark, &pool).await?;
+        assert_eq!(articles.len(), 2, "取得に成功した記事のみ");
+        assert!(
+            poll_new_articles(&mut watermark, &pool).await?.is_empty(),
+            "配信済みの記事は返さない"
+        );
+
+        sqlx::query!(
+            "UPDATE articles SET timestamp = CURRENT_TIMESTAMP + INTERVAL '1 second' WHERE url = 'https://different.com/news3'"
+        )
+        .execute(&pool)
+        .await?;
+        let articles = poll_new_articles(&mut watermark, &pool).await?;
+        assert_eq!(
+            articles.iter().map(|a| a.url.as_str()).collect::<Vec<_>>(),
+            vec!["https://different.com/news3"],
+            "取得し直した記事は再び配信する"
+        );
+
+        println!("✅ 新着記事の確認テスト成功");
+        Ok(())
+    }
+}
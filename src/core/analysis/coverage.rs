@@ -0,0 +1,356 @@
+use crate::core::circuit_breaker::url_domain;
+use crate::core::rss::UNTITLED;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::{BTreeSet, HashSet};
+use std::ops::Range;
+
+// 類似記事グループの判定条件
+#[derive(Debug, Clone)]
+pub struct CoverageOptions {
+    /// タイトルの比較に使う文字n-gramの長さ
+    pub ngram: usize,
+    /// 同じ出来事とみなすタイトルの類似度（n-gramのJaccard係数）の下限
+    pub similarity_threshold: f64,
+    /// 同じ出来事とみなす公開日時の差の上限
+    pub window: Duration,
+    /// レポートに含めるグループの最小メディア数
+    pub min_media: usize,
+}
+
+impl Default for CoverageOptions {
+    fn default() -> Self {
+        Self {
+            ngram: 3,
+            similarity_threshold: 0.5,
+            window: Duration::hours(48),
+            min_media: 2,
+        }
+    }
+}
+
+// カバレッジ集計の対象記事
+#[derive(Debug, Clone, Serialize)]
+pub struct CoverageArticle {
+    pub url: String,
+    pub title: String,
+    pub pub_date: DateTime<Utc>,
+    /// 報じたメディア（フィードのグループ、フィード以外から登録されたリンクはドメイン）
+    pub media: String,
+}
+
+// 同じ出来事を報じたとみなした記事のグループ
+#[derive(Debug, Clone, Serialize)]
+pub struct CoverageGroup {
+    /// 公開日時の古い順
+    pub articles: Vec<CoverageArticle>,
+    /// 参加メディア（名前順）
+    pub media: Vec<String>,
+}
+
+impl CoverageGroup {
+    /// 参加メディア数
+    pub fn coverage(&self) -> usize {
+        self.media.len()
+    }
+
+    /// 最初に報じた記事のタイトル
+    pub fn representative_title(&self) -> &str {
+        self.articles.first().map_or("", |a| a.title.as_str())
+    }
+}
+
+// カバレッジレポート
+#[derive(Debug, Clone, Serialize)]
+pub struct CoverageReport {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    /// 集計対象の記事数
+    pub total_articles: usize,
+    /// 参加メディア数の多い順
+    pub groups: Vec<CoverageGroup>,
+}
+
+impl CoverageReport {
+    /// 表示用の行に整形する
+    pub fn format_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "期間: {} 〜 {}（記事{}件、複数メディアの出来事{}件）",
+            self.from.to_rfc3339(),
+            self.to.to_rfc3339(),
+            self.total_articles,
+            self.groups.len()
+        )];
+        for group in &self.groups {
+            lines.push(format!(
+                "[{}メディア] {}",
+                group.coverage(),
+                group.representative_title()
+            ));
+            for article in &group.articles {
+                lines.push(format!(
+                    "  {} {}: {}",
+                    article.pub_date.to_rfc3339(),
+                    article.media,
+                    article.title
+                ));
+            }
+        }
+        lines
+    }
+}
+
+/// 既定の判定条件でカバレッジレポートを作成する
+pub async fn coverage_report(
+    date_range: Range<DateTime<Utc>>,
+    pool: &PgPool,
+) -> Result<CoverageReport> {
+    coverage_report_with(date_range, &CoverageOptions::default(), pool).await
+}
+
+/// # 概要
+/// 期間内（`pub_date`が`date_range`内）の記事リンクをタイトルの類似度でグループ化し、
+/// 同じ出来事を何メディアが報じたかを集計する。
+///
+/// 仮タイトルのリンクとゴミ箱のリンクは対象外。
+pub async fn coverage_report_with(
+    date_range: Range<DateTime<Utc>>,
+    options: &CoverageOptions,
+    pool: &PgPool,
+) -> Result<CoverageReport> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT url, title, pub_date, feed FROM article_links
+        WHERE deleted_at IS NULL
+            AND pub_date >= $1 AND pub_date < $2
+            AND title <> $3 AND title <> url
+        ORDER BY pub_date
+        "#,
+        date_range.start,
+        date_range.end,
+        UNTITLED
+    )
+    .fetch_all(pool)
+    .await
+    .context("カバレッジ集計対象の記事リンクの取得に失敗")?;
+
+    let articles: Vec<CoverageArticle> = rows
+        .into_iter()
+        .map(|row| CoverageArticle {
+            media: row
+                .feed
+                .as_deref()
+                .and_then(|feed| feed.split('/').next())
+                .map(str::to_string)
+                .unwrap_or_else(|| url_domain(&row.url)),
+            url: row.url,
+            title: row.title,
+            pub_date: row.pub_date,
+        })
+        .collect();
+
+    let total_articles = articles.len();
+    let groups = group_similar_articles(articles, options)
+        .into_iter()
+        .filter(|group| group.coverage() >= options.min_media)
+        .collect();
+
+    Ok(CoverageReport {
+        from: date_range.start,
+        to: date_range.end,
+        total_articles,
+        groups,
+    })
+}
+
+/// # 概要
+/// タイトルの類似度と公開日時の近さで記事をグループ化する（単連結クラスタリング）。
+///
+/// 公開日時の差が`window`以内で類似度が`similarity_threshold`以上の記事同士を同じグループとし、
+/// 推移的につながる記事もまとめる。グループは参加メディア数・記事数の多い順に返す。
+pub fn group_similar_articles(
+    mut articles: Vec<CoverageArticle>,
+    options: &CoverageOptions,
+) -> Vec<CoverageGroup> {
+    articles.sort_by_key(|a| a.pub_date);
+    let ngrams: Vec<HashSet<String>> = articles
+        .iter()
+        .map(|a| title_ngrams(&normalize_title(&a.title), options.ngram))
+        .collect();
+
+    let mut parents: Vec<usize> = (0..articles.len()).collect();
+    for i in 0..articles.len() {
+        for j in (i + 1)..articles.len() {
+            if articles[j].pub_date - articles[i].pub_date > options.window {
+                break;
+            }
+            if jaccard(&ngrams[i], &ngrams[j]) >= options.similarity_threshold {
+                let (root_i, root_j) = (find_root(&mut parents, i), find_root(&mut parents, j));
+                parents[root_j] = root_i;
+            }
+        }
+    }
+
+    let mut members: Vec<Vec<usize>> = vec![Vec::new(); articles.len()];
+    for i in 0..articles.len() {
+        let root = find_root(&mut parents, i);
+        members[root].push(i);
+    }
+
+    let mut groups: Vec<CoverageGroup> = members
+        .into_iter()
+        .filter(|indices| !indices.is_empty())
+        .map(|indices| {
+            let articles: Vec<CoverageArticle> =
+                indices.iter().map(|&i| articles[i].clone()).collect();
+            let media: BTreeSet<String> = articles.iter().map(|a| a.media.clone()).collect();
+            CoverageGroup {
+                articles,
+                media: media.into_iter().collect(),
+            }
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        b.coverage()
+            .cmp(&a.coverage())
+            .then(b.articles.len().cmp(&a.articles.len()))
+    });
+    groups
+}
+
+fn find_root(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
+
+/// タイトルを比較用に正規化する
+///
+/// 全角英数字を半角にして小文字化し、英数字・かな・漢字以外（空白・記号）を除く。
+pub fn normalize_title(title: &str) -> String {
+    title
+        .chars()
+        .map(|c| match c {
+            // 全角英数字・記号（U+FF01〜U+FF5E）は対応する半角文字に置き換える
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            _ => c,
+        })
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 正規化したタイトル同士の類似度（文字n-gramのJaccard係数、0.0〜1.0）
+pub fn title_similarity(a: &str, b: &str, n: usize) -> f64 {
+    jaccard(
+        &title_ngrams(&normalize_title(a), n),
+        &title_ngrams(&normalize_title(b), n),
+    )
+}
+
+/// 文字n-gramの集合（n文字未満の場合は文字列全体を1つのn-gramとする）
+fn title_ngrams(normalized: &str, n: usize) -> HashSet<String> {
+    let chars: Vec<char> = normalized.chars().collect();
+    let n = n.max(1);
+    if chars.len() <= n {
+        return [normalized.to_string()]
+            .into_iter()
+            .filter(|s| !s.is_empty())
+            .collect();
+    }
+    chars.windows(n).map(|w| w.iter().collect()).collect()
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let intersection = a.intersection(b).count();
+    intersection as f64 / (a.len() + b.len() - intersection) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rss::{store_article_links, ArticleLink};
+
+    fn article(media: &str, title: &str, hours: i64) -> CoverageArticle {
+        let base: DateTime<Utc> = "2025-09-01T00:00:00Z".parse().unwrap();
+        CoverageArticle {
+            url: format!("https://{}.example.com/{}", media, hours),
+            title: title.to_string(),
+            pub_date: base + Duration::hours(hours),
+            media: media.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_title_and_similarity() {
+        assert_eq!(normalize_title("ＡＩ規制法、 成立へ！"), "ai規制法成立へ");
+        assert!(title_similarity("日銀が追加利上げを決定", "日銀、追加利上げを決定", 3) > 0.5);
+        assert!(title_similarity("日銀が追加利上げを決定", "新型スマホを発表", 3) < 0.1);
+        assert_eq!(title_similarity("", "日銀", 3), 0.0);
+    }
+
+    #[test]
+    fn test_group_similar_articles() {
+        let articles = vec![
+            article("bbc", "Fed raises interest rates by 0.25%", 0),
+            article("cbs", "Fed raises interest rates by 0.25 percent", 3),
+            article("nhk", "FED raises interest rates", 5),
+            article("bbc", "New smartphone unveiled", 1),
+            // 日付窓の外の類似記事は別の出来事とする
+            article("cnn", "Fed raises interest rates by 0.25%", 24 * 7),
+        ];
+        let groups = group_similar_articles(articles, &CoverageOptions::default());
+
+        assert_eq!(groups[0].coverage(), 3);
+        assert_eq!(groups[0].media, vec!["bbc", "cbs", "nhk"]);
+        assert_eq!(
+            groups[0].representative_title(),
+            "Fed raises interest rates by 0.25%"
+        );
+        assert_eq!(groups.len(), 3);
+    }
+
+    #[sqlx::test]
+    async fn test_coverage_report(pool: PgPool) -> Result<(), anyhow::Error> {
+        let now = Utc::now();
+        let link = |url: &str, title: &str| ArticleLink {
+            url: url.to_string(),
+            title: title.to_string(),
+            pub_date: now,
+            source: "rss".to_string(),
+            ..Default::default()
+        };
+        store_article_links(
+            &[
+                link("https://a.example.com/1", "大型台風が上陸、交通機関に影響"),
+                link(
+                    "https://b.example.com/1",
+                    "大型台風が上陸 交通機関に影響広がる",
+                ),
+                link("https://a.example.com/2", "新製品を発表"),
+                link("https://c.example.com/1", UNTITLED),
+            ],
+            &pool,
+        )
+        .await?;
+
+        let report =
+            coverage_report(now - Duration::hours(1)..now + Duration::hours(1), &pool).await?;
+        assert_eq!(report.total_articles, 3, "仮タイトルは対象外");
+        assert_eq!(report.groups.len(), 1, "単独メディアの記事は含めない");
+        assert_eq!(
+            report.groups[0].media,
+            vec!["a.example.com", "b.example.com"]
+        );
+
+        println!("✅ カバレッジレポートテスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,16 @@
+pub mod coverage;
+pub mod publish_time;
+
+// 公開APIの再エクスポート
+
+// coverage.rsから
+pub use coverage::{
+    coverage_report, coverage_report_with, group_similar_articles, normalize_title,
+    title_similarity, CoverageArticle, CoverageGroup, CoverageOptions, CoverageReport,
+};
+
+// publish_time.rsから
+pub use publish_time::{
+    bucket_publish_times, format_heatmap_lines, hourly_share, publish_time_histogram,
+    publish_time_histogram_with, HourBucket, PublishTimeTarget,
+};
@@ -0,0 +1,250 @@
+use crate::core::circuit_breaker::url_domain;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, FixedOffset, Timelike, Utc, Weekday};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::ops::Range;
+
+/// 曜日の表示名（月曜始まり）
+const WEEKDAY_LABELS: [&str; 7] = ["月", "火", "水", "木", "金", "土", "日"];
+
+// 公開時刻を集計する対象
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PublishTimeTarget {
+    /// フィード（`Feed::key`、`article_links.feed`と一致するリンク）
+    Feed(String),
+    /// ドメイン（URLのホストが一致するリンク、サブドメインを含む）
+    Domain(String),
+}
+
+impl PublishTimeTarget {
+    /// `group/name`の形ならフィード、それ以外はドメインとして解釈する
+    pub fn parse(feed_or_domain: &str) -> Self {
+        let value = feed_or_domain.trim();
+        if value.contains('/') {
+            Self::Feed(value.to_string())
+        } else {
+            Self::Domain(value.trim_start_matches("www.").to_lowercase())
+        }
+    }
+
+    fn matches_domain(&self, url: &str) -> bool {
+        match self {
+            Self::Feed(_) => true,
+            Self::Domain(domain) => {
+                let host = url_domain(url);
+                let host = host.strip_prefix("www.").unwrap_or(&host);
+                host == domain || host.ends_with(&format!(".{}", domain))
+            }
+        }
+    }
+}
+
+// 曜日・時間帯毎の公開記事数（ヒートマップの1マス）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HourBucket {
+    pub weekday: Weekday,
+    /// 時（0〜23）
+    pub hour: u32,
+    pub count: i64,
+}
+
+/// 既定（UTC）の時間帯で記事の公開時刻を集計する
+pub async fn publish_time_histogram(
+    target: &PublishTimeTarget,
+    date_range: Range<DateTime<Utc>>,
+    pool: &PgPool,
+) -> Result<Vec<HourBucket>> {
+    let utc = FixedOffset::east_opt(0).expect("UTCオフセットは常に有効");
+    publish_time_histogram_with(target, date_range, utc, pool).await
+}
+
+/// # 概要
+/// 期間内（`pub_date`が`date_range`内）の記事リンクの公開時刻を曜日・時間帯毎に集計する。
+///
+/// 曜日・時は`offset`の時間帯で数える。記事の無いマスも含めた月曜0時〜日曜23時の168件を返す。
+/// ゴミ箱のリンクは対象外。
+pub async fn publish_time_histogram_with(
+    target: &PublishTimeTarget,
+    date_range: Range<DateTime<Utc>>,
+    offset: FixedOffset,
+    pool: &PgPool,
+) -> Result<Vec<HourBucket>> {
+    let feed = match target {
+        PublishTimeTarget::Feed(feed) => Some(feed.as_str()),
+        PublishTimeTarget::Domain(_) => None,
+    };
+    let rows = sqlx::query!(
+        r#"
+        SELECT url, pub_date FROM article_links
+        WHERE deleted_at IS NULL
+            AND pub_date >= $1 AND pub_date < $2
+            AND ($3::text IS NULL OR feed = $3)
+        "#,
+        date_range.start,
+        date_range.end,
+        feed
+    )
+    .fetch_all(pool)
+    .await
+    .context("公開時刻の集計対象の記事リンクの取得に失敗")?;
+
+    Ok(bucket_publish_times(
+        rows.iter()
+            .filter(|row| target.matches_domain(&row.url))
+            .map(|row| row.pub_date),
+        offset,
+    ))
+}
+
+/// 公開日時を曜日・時間帯毎に数える（月曜0時〜日曜23時の168件）
+pub fn bucket_publish_times(
+    pub_dates: impl IntoIterator<Item = DateTime<Utc>>,
+    offset: FixedOffset,
+) -> Vec<HourBucket> {
+    let mut counts = [[0i64; 24]; 7];
+    for pub_date in pub_dates {
+        let local = pub_date.with_timezone(&offset);
+        counts[local.weekday().num_days_from_monday() as usize][local.hour() as usize] += 1;
+    }
+
+    let mut buckets = Vec::with_capacity(7 * 24);
+    let mut weekday = Weekday::Mon;
+    for row in counts {
+        for (hour, count) in row.into_iter().enumerate() {
+            buckets.push(HourBucket {
+                weekday,
+                hour: hour as u32,
+                count,
+            });
+        }
+        weekday = weekday.succ();
+    }
+    buckets
+}
+
+/// 時間帯（0〜23時）毎の公開記事の割合（曜日は合算、記事が無ければすべて0.0）
+///
+/// 記事の出やすい時間帯にポーリングを寄せる判断に使う。
+pub fn hourly_share(buckets: &[HourBucket]) -> [f64; 24] {
+    let mut counts = [0i64; 24];
+    for bucket in buckets {
+        if let Some(count) = counts.get_mut(bucket.hour as usize) {
+            *count += bucket.count;
+        }
+    }
+    let total: i64 = counts.iter().sum();
+    let mut shares = [0.0; 24];
+    if total > 0 {
+        for (share, count) in shares.iter_mut().zip(counts) {
+            *share = count as f64 / total as f64;
+        }
+    }
+    shares
+}
+
+/// ヒートマップを表示用の行に整形する（曜日毎に1行、0時〜23時の記事数）
+pub fn format_heatmap_lines(buckets: &[HourBucket]) -> Vec<String> {
+    let header: String = (0..24).map(|hour| format!("{:>4}", hour)).collect();
+    let mut lines = vec![format!("  {}", header)];
+    for (i, label) in WEEKDAY_LABELS.iter().enumerate() {
+        let row: String = buckets
+            .iter()
+            .filter(|b| b.weekday.num_days_from_monday() as usize == i)
+            .map(|b| format!("{:>4}", b.count))
+            .collect();
+        lines.push(format!("{}{}", label, row));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::feed::Feed;
+    use crate::core::rss::{assign_article_links_feed, store_article_links, ArticleLink};
+    use chrono::Duration;
+
+    #[test]
+    fn test_bucket_publish_times() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let pub_dates = [
+            // 2025-09-01は月曜
+            at("2025-09-01T09:15:00Z"),
+            at("2025-09-01T09:45:00Z"),
+            at("2025-09-07T23:00:00Z"),
+        ];
+        let buckets = bucket_publish_times(pub_dates, FixedOffset::east_opt(0).unwrap());
+        assert_eq!(buckets.len(), 168);
+        assert_eq!(buckets[9].weekday, Weekday::Mon);
+        assert_eq!(buckets[9].count, 2);
+        assert_eq!(buckets[167].weekday, Weekday::Sun);
+        assert_eq!(buckets[167].count, 1);
+
+        // JSTでは日曜23時が月曜8時になる
+        let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+        let buckets = bucket_publish_times(pub_dates, jst);
+        assert_eq!(buckets[8].count, 1);
+        assert_eq!(buckets[18].count, 2);
+
+        let shares = hourly_share(&buckets);
+        assert!((shares[18] - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(hourly_share(&[])[0], 0.0);
+        assert_eq!(format_heatmap_lines(&buckets).len(), 8);
+    }
+
+    #[test]
+    fn test_publish_time_target_parse() {
+        assert_eq!(
+            PublishTimeTarget::parse("bbc/world"),
+            PublishTimeTarget::Feed("bbc/world".to_string())
+        );
+        let domain = PublishTimeTarget::parse("www.Example.com");
+        assert_eq!(domain, PublishTimeTarget::Domain("example.com".to_string()));
+        assert!(domain.matches_domain("https://news.example.com/1"));
+        assert!(domain.matches_domain("https://www.example.com/1"));
+        assert!(!domain.matches_domain("https://notexample.com/1"));
+    }
+
+    #[sqlx::test]
+    async fn test_publish_time_histogram(pool: PgPool) -> Result<(), anyhow::Error> {
+        let base: DateTime<Utc> = "2025-09-01T09:00:00Z".parse()?;
+        let link = |url: &str, hours: i64| ArticleLink {
+            url: url.to_string(),
+            title: url.to_string(),
+            pub_date: base + Duration::hours(hours),
+            source: "rss".to_string(),
+            ..Default::default()
+        };
+        let feed_links = vec![
+            link("https://a.example.com/1", 0),
+            link("https://a.example.com/2", 24),
+        ];
+        store_article_links(&feed_links, &pool).await?;
+        store_article_links(&[link("https://b.example.com/1", 0)], &pool).await?;
+        let feed = Feed {
+            group: "a".to_string(),
+            name: "news".to_string(),
+            ..Default::default()
+        };
+        assign_article_links_feed(&feed_links, &feed, &pool).await?;
+
+        let range = base - Duration::days(1)..base + Duration::days(7);
+        let buckets =
+            publish_time_histogram(&PublishTimeTarget::parse("a/news"), range.clone(), &pool)
+                .await?;
+        let counted: Vec<(Weekday, u32, i64)> = buckets
+            .iter()
+            .filter(|b| b.count > 0)
+            .map(|b| (b.weekday, b.hour, b.count))
+            .collect();
+        assert_eq!(counted, vec![(Weekday::Mon, 9, 1), (Weekday::Tue, 9, 1)]);
+
+        let buckets =
+            publish_time_histogram(&PublishTimeTarget::parse("example.com"), range, &pool).await?;
+        assert_eq!(buckets.iter().map(|b| b.count).sum::<i64>(), 3);
+
+        println!("✅ 公開時刻ヒートマップテスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,280 @@
+use crate::infra::compute::calc_hash;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::env;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// レート制限の集計単位
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+// RESTサーバの認証・レート制限の設定（config/app.yamlのserverに対応）
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServerConfig {
+    /// クライアント毎の1分あたりのリクエスト数の上限（キー毎の指定が無い場合、0は無制限）
+    #[serde(default = "default_rate_limit_per_minute")]
+    pub rate_limit_per_minute: u32,
+    /// 設定で管理するAPIキー（キーは環境変数から読む）
+    #[serde(default)]
+    pub api_keys: Vec<ConfiguredApiKey>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            rate_limit_per_minute: default_rate_limit_per_minute(),
+            api_keys: Vec::new(),
+        }
+    }
+}
+
+fn default_rate_limit_per_minute() -> u32 {
+    60
+}
+
+// gRPCサーバの設定（config/app.yamlのgrpcに対応、認証・レート制限はserverの設定を共有する）
+#[derive(Debug, Clone, Deserialize)]
+pub struct GrpcConfig {
+    /// StreamNewArticlesが新着記事を確認する間隔（秒）
+    #[serde(default = "default_stream_poll_interval_secs")]
+    pub stream_poll_interval_secs: u64,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            stream_poll_interval_secs: default_stream_poll_interval_secs(),
+        }
+    }
+}
+
+fn default_stream_poll_interval_secs() -> u64 {
+    5
+}
+
+// 設定で管理するAPIキー
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfiguredApiKey {
+    /// クライアント名（レート制限の単位）
+    pub name: String,
+    /// キーを設定した環境変数の名前
+    pub key_env: String,
+    /// 1分あたりのリクエスト数の上限（省略時は`rate_limit_per_minute`）
+    pub rate_limit_per_minute: Option<u32>,
+}
+
+// 認証されたクライアント
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiClient {
+    pub name: String,
+    /// 1分あたりのリクエスト数の上限（`None`は既定値）
+    pub rate_limit_per_minute: Option<u32>,
+}
+
+// api_keysテーブルの1行（キーそのものは保存しない）
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiKey {
+    pub name: String,
+    pub rate_limit_per_minute: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// # 概要
+/// 設定のAPIキーを環境変数から読み込む。
+///
+/// 環境変数が未設定・空のキーはエラーにする（意図せず認証が無効にならないように）。
+pub fn load_configured_api_keys(config: &ServerConfig) -> Result<Vec<(String, ApiClient)>> {
+    config
+        .api_keys
+        .iter()
+        .map(|entry| {
+            let key = env::var(&entry.key_env).with_context(|| {
+                format!(
+                    "APIキーの環境変数{}が設定されていません: {}",
+                    entry.key_env, entry.name
+                )
+            })?;
+            if key.is_empty() {
+                bail!("APIキーの環境変数{}が空です: {}", entry.key_env, entry.name);
+            }
+            Ok((
+                key,
+                ApiClient {
+                    name: entry.name.clone(),
+                    rate_limit_per_minute: entry.rate_limit_per_minute,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// # 概要
+/// APIキーを発行してapi_keysテーブルに登録し、発行したキーを返す。
+///
+/// キーはDBの`gen_random_uuid`で生成した128文字の16進数で、DBにはSHA-256のみ保存する
+/// （発行時以外にキーを確認する方法は無い）。同じ名前のキーがあればエラー。
+pub async fn create_api_key(
+    name: &str,
+    rate_limit_per_minute: Option<u32>,
+    pool: &PgPool,
+) -> Result<String> {
+    if name.trim().is_empty() {
+        bail!("APIキーの名前が空です");
+    }
+    let key = sqlx::query_scalar!(
+        r#"
+        SELECT replace(
+            gen_random_uuid()::text || gen_random_uuid()::text
+                || gen_random_uuid()::text || gen_random_uuid()::text,
+            '-', ''
+        ) AS "key!"
+        "#
+    )
+    .fetch_one(pool)
+    .await
+    .context("APIキーの生成に失敗")?;
+
+    let rate_limit = rate_limit_per_minute
+        .map(i32::try_from)
+        .transpose()
+        .context("レート制限の値が大きすぎます")?;
+    sqlx::query!(
+        "INSERT INTO api_keys (name, key_hash, rate_limit_per_minute) VALUES ($1, $2, $3)",
+        name,
+        calc_hash(&key, 64),
+        rate_limit
+    )
+    .execute(pool)
+    .await
+    .with_context(|| format!("APIキーの登録に失敗: {}", name))?;
+
+    Ok(key)
+}
+
+/// APIキーを失効させる（失効させた場合は`true`）
+pub async fn revoke_api_key(name: &str, pool: &PgPool) -> Result<bool> {
+    let result = sqlx::query!(
+        "UPDATE api_keys SET revoked_at = CURRENT_TIMESTAMP WHERE name = $1 AND revoked_at IS NULL",
+        name
+    )
+    .execute(pool)
+    .await
+    .with_context(|| format!("APIキーの失効に失敗: {}", name))?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// 登録済みのAPIキーを名前順に取得する（失効したキーを含む）
+pub async fn list_api_keys(pool: &PgPool) -> Result<Vec<ApiKey>> {
+    sqlx::query_as!(
+        ApiKey,
+        "SELECT name, rate_limit_per_minute, created_at, revoked_at FROM api_keys ORDER BY name"
+    )
+    .fetch_all(pool)
+    .await
+    .context("APIキーの一覧の取得に失敗")
+}
+
+/// 失効していないAPIキーのクライアントを取得する（該当しなければ`None`）
+pub async fn find_api_client(key: &str, pool: &PgPool) -> Result<Option<ApiClient>> {
+    let row = sqlx::query!(
+        "SELECT name, rate_limit_per_minute FROM api_keys WHERE key_hash = $1 AND revoked_at IS NULL",
+        calc_hash(key, 64)
+    )
+    .fetch_optional(pool)
+    .await
+    .context("APIキーの照合に失敗")?;
+
+    Ok(row.map(|row| ApiClient {
+        name: row.name,
+        rate_limit_per_minute: row.rate_limit_per_minute.map(|limit| limit.max(0) as u32),
+    }))
+}
+
+/// 比較時間から値を推測されないよう、全バイトを比較する
+pub fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+/// # 概要
+/// クライアント毎のリクエスト数を1分単位の固定ウィンドウで数えるレート制限。
+///
+/// プロセス内のメモリで数えるため、複数のサーバプロセスではプロセス毎の上限になる。
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    /// # 概要
+    /// リクエストを1件数え、上限以内なら`Ok`を返す。
+    ///
+    /// 上限を超えた場合は次のウィンドウまでの待ち時間を`Err`で返す（超過分は数えない）。
+    /// `limit`が0なら制限しない。
+    pub fn check(&self, client: &str, limit: u32) -> std::result::Result<(), Duration> {
+        if limit == 0 {
+            return Ok(());
+        }
+        let now = Instant::now();
+        let mut windows = self.windows.lock().expect("レート制限のロックに失敗");
+        let (started_at, count) = windows.entry(client.to_string()).or_insert((now, 0));
+        if now.duration_since(*started_at) >= RATE_LIMIT_WINDOW {
+            *started_at = now;
+            *count = 0;
+        }
+        if *count >= limit {
+            return Err(RATE_LIMIT_WINDOW.saturating_sub(now.duration_since(*started_at)));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter::default();
+        assert!(limiter.check("a", 2).is_ok());
+        assert!(limiter.check("a", 2).is_ok());
+        let retry_after = limiter.check("a", 2).unwrap_err();
+        assert!(retry_after <= RATE_LIMIT_WINDOW);
+        assert!(limiter.check("b", 2).is_ok(), "クライアント毎に数える");
+        assert!((0..100).all(|_| limiter.check("c", 0).is_ok()), "0は無制限");
+    }
+
+    #[sqlx::test]
+    async fn test_api_key_lifecycle(pool: PgPool) -> Result<(), anyhow::Error> {
+        let key = create_api_key("partner", Some(10), &pool).await?;
+        assert_eq!(key.len(), 128);
+        assert!(create_api_key("partner", None, &pool).await.is_err());
+
+        let client = find_api_client(&key, &pool).await?;
+        assert_eq!(
+            client,
+            Some(ApiClient {
+                name: "partner".to_string(),
+                rate_limit_per_minute: Some(10),
+            })
+        );
+        assert_eq!(find_api_client("unknown", &pool).await?, None);
+
+        assert!(revoke_api_key("partner", &pool).await?);
+        assert!(!revoke_api_key("partner", &pool).await?, "失効済み");
+        assert_eq!(find_api_client(&key, &pool).await?, None);
+        assert!(list_api_keys(&pool).await?[0].revoked_at.is_some());
+
+        println!("✅ APIキー発行・失効テスト成功");
+        Ok(())
+    }
+}
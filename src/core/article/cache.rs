@@ -0,0 +1,273 @@
+use super::link::normalize_article_url;
+use super::model::Article;
+use super::service::{search_articles, store_article_content, ArticleContent, ArticleQuery};
+use crate::core::rss::{store_article_links, ArticleLink};
+use crate::infra::compute::calc_hash;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// 記事検索の読み取りキャッシュの設定
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArticleCacheConfig {
+    /// 検索結果を再利用する秒数
+    #[serde(default = "default_ttl_secs")]
+    pub ttl_secs: u64,
+    /// 保持する検索結果の上限（超えた場合は期限の近いものから捨てる）
+    #[serde(default = "default_max_entries")]
+    pub max_entries: usize,
+}
+
+impl Default for ArticleCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl_secs: default_ttl_secs(),
+            max_entries: default_max_entries(),
+        }
+    }
+}
+
+fn default_ttl_secs() -> u64 {
+    30
+}
+
+fn default_max_entries() -> usize {
+    1000
+}
+
+// キャッシュした検索結果
+#[derive(Debug)]
+struct CacheEntry {
+    query: ArticleQuery,
+    articles: Vec<Article>,
+    expires_at: Instant,
+}
+
+impl CacheEntry {
+    /// `url`の記事リンク・記事内容の書き込みで結果が変わりうるか
+    ///
+    /// 結果に含まれるURLと、URLの完全一致・部分一致の条件に当てはまるURLのみを関連とみなし、
+    /// それ以外の条件の検索は新しいリンクが結果に加わりうるため常に関連とする。
+    fn is_affected_by(&self, url: &str) -> bool {
+        if self.articles.iter().any(|article| article.url == url) {
+            return true;
+        }
+        if let Some(query_url) = &self.query.url {
+            return query_url == url;
+        }
+        if self.query.id.is_some() {
+            return false;
+        }
+        match &self.query.link_pattern {
+            Some(pattern) => url.to_lowercase().contains(&pattern.to_lowercase()),
+            None => true,
+        }
+    }
+}
+
+/// # 概要
+/// 同じ`ArticleQuery`の検索結果をTTLの間メモ化する読み取りキャッシュ。
+///
+/// Web UIなどで同じ検索が短時間に繰り返される場合のDB負荷を下げるために、リポジトリ関数の前段に置く。
+/// キーはクエリ（JSON）のハッシュ。このキャッシュを経由して書き込むと関連する検索結果を無効化する
+/// （別のプロセスによる書き込みはTTLが切れるまで反映されない）。
+#[derive(Debug)]
+pub struct ArticleQueryCache {
+    ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl ArticleQueryCache {
+    pub fn new(config: &ArticleCacheConfig) -> Self {
+        Self {
+            ttl: Duration::from_secs(config.ttl_secs),
+            max_entries: config.max_entries.max(1),
+            entries: Mutex::default(),
+        }
+    }
+
+    /// 記事を検索する（TTL内に同じクエリの結果があればDBに問い合わせない）
+    pub async fn search_articles(
+        &self,
+        query: Option<ArticleQuery>,
+        pool: &PgPool,
+    ) -> Result<Vec<Article>> {
+        let query = query.unwrap_or_default();
+        let key = cache_key(&query)?;
+        let now = Instant::now();
+        if let Some(entry) = self.lock().get(&key) {
+            if entry.expires_at > now {
+                return Ok(entry.articles.clone());
+            }
+        }
+
+        let articles = search_articles(Some(query.clone()), pool).await?;
+        self.insert(key, query, articles.clone());
+        Ok(articles)
+    }
+
+    /// 記事内容を保存し、そのURLに関連する検索結果を無効化する
+    pub async fn store_article_content(
+        &self,
+        article: &ArticleContent,
+        pool: &PgPool,
+    ) -> Result<()> {
+        store_article_content(article, pool).await?;
+        self.invalidate_url(&article.url);
+        Ok(())
+    }
+
+    /// 記事リンクを保存し、それらのURLに関連する検索結果を無効化する
+    pub async fn store_article_links(
+        &self,
+        article_links: &[ArticleLink],
+        pool: &PgPool,
+    ) -> Result<()> {
+        store_article_links(article_links, pool).await?;
+        for link in article_links {
+            self.invalidate_url(&normalize_article_url(&link.url));
+        }
+        Ok(())
+    }
+
+    /// `url`の書き込みで結果が変わりうる検索結果を無効化する
+    pub fn invalidate_url(&self, url: &str) {
+        self.lock().retain(|_, entry| !entry.is_affected_by(url));
+    }
+
+    /// すべての検索結果を無効化する
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// 保持している検索結果の数（期限切れを含む）
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn insert(&self, key: String, query: ArticleQuery, articles: Vec<Article>) {
+        let now = Instant::now();
+        let mut entries = self.lock();
+        if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            entries.retain(|_, entry| entry.expires_at > now);
+            if entries.len() >= self.max_entries {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.expires_at)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(
+            key,
+            CacheEntry {
+                query,
+                articles,
+                expires_at: now + self.ttl,
+            },
+        );
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, CacheEntry>> {
+        self.entries
+            .lock()
+            .expect("記事検索キャッシュのロックに失敗")
+    }
+}
+
+/// クエリのキャッシュキー（JSONのSHA-256）
+fn cache_key(query: &ArticleQuery) -> Result<String> {
+    let json = serde_json::to_string(query).context("検索条件のシリアライズに失敗")?;
+    Ok(calc_hash(&json, 64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn query_for(pattern: &str) -> ArticleQuery {
+        ArticleQuery {
+            link_pattern: Some(pattern.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[sqlx::test(fixtures("../../../fixtures/article_query_filter.sql"))]
+    async fn test_article_query_cache(pool: PgPool) -> Result<(), anyhow::Error> {
+        let cache = ArticleQueryCache::new(&ArticleCacheConfig::default());
+        let query = || Some(query_for("example.com"));
+        let before = cache.search_articles(query(), &pool).await?;
+        cache
+            .search_articles(Some(query_for("different.com")), &pool)
+            .await?;
+        assert_eq!(cache.len(), 2);
+
+        // キャッシュを経由しない書き込みはTTLの間反映されない
+        sqlx::query!("UPDATE article_links SET title = 'DBで変更' WHERE url LIKE '%example.com%'")
+            .execute(&pool)
+            .await?;
+        let cached = cache.search_articles(query(), &pool).await?;
+        assert_eq!(
+            cached.iter().map(|a| &a.title).collect::<Vec<_>>(),
+            before.iter().map(|a| &a.title).collect::<Vec<_>>()
+        );
+
+        // キャッシュを経由した書き込みは、関連する検索結果だけを無効化する
+        let content = ArticleContent {
+            url: before[0].url.clone(),
+            timestamp: Utc::now(),
+            status_code: 200,
+            content: "キャッシュ無効化".to_string(),
+            raw_html: None,
+        };
+        cache.store_article_content(&content, &pool).await?;
+        assert_eq!(cache.len(), 1, "different.comの検索結果は残る");
+        let refreshed = cache.search_articles(query(), &pool).await?;
+        assert!(refreshed.iter().all(|a| a.title == "DBで変更"));
+
+        println!("✅ 記事検索キャッシュテスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("../../../fixtures/article_query_filter.sql"))]
+    async fn test_article_query_cache_expiry(pool: PgPool) -> Result<(), anyhow::Error> {
+        let config = ArticleCacheConfig {
+            ttl_secs: 60,
+            max_entries: 2,
+        };
+        let cache = ArticleQueryCache::new(&config);
+        for pattern in ["example.com", "different.com", "news"] {
+            cache
+                .search_articles(Some(query_for(pattern)), &pool)
+                .await?;
+        }
+        assert_eq!(cache.len(), 2, "上限を超えた分は捨てる");
+
+        // TTLが切れた結果は使わない
+        let config = ArticleCacheConfig {
+            ttl_secs: 0,
+            ..Default::default()
+        };
+        let cache = ArticleQueryCache::new(&config);
+        cache
+            .search_articles(Some(query_for("news")), &pool)
+            .await?;
+        sqlx::query!("DELETE FROM articles").execute(&pool).await?;
+        let articles = cache
+            .search_articles(Some(query_for("news")), &pool)
+            .await?;
+        assert!(articles.iter().all(|a| a.status_code.is_none()));
+        Ok(())
+    }
+}
@@ -0,0 +1,426 @@
+use super::service::{get_article, ArticleKey};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::ops::Range;
+
+// 本文のチャンク分割の設定
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChunkOptions {
+    /// 1チャンクの最大トークン数（`estimate_tokens`による近似値）
+    #[serde(default = "default_max_tokens")]
+    pub max_tokens: usize,
+    /// 前のチャンクの末尾から繰り返すトークン数の上限（見出しを跨いでは繰り返さない）
+    #[serde(default = "default_overlap_tokens")]
+    pub overlap_tokens: usize,
+}
+
+impl Default for ChunkOptions {
+    fn default() -> Self {
+        Self {
+            max_tokens: default_max_tokens(),
+            overlap_tokens: default_overlap_tokens(),
+        }
+    }
+}
+
+fn default_max_tokens() -> usize {
+    512
+}
+
+fn default_overlap_tokens() -> usize {
+    64
+}
+
+// 本文のチャンク（LLMに1回で渡す単位）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Chunk {
+    /// 先頭からの番号（0始まり）
+    pub index: usize,
+    /// チャンクの属する節の見出し（`#`を除いたもの、最初の見出しより前は`None`）
+    pub heading: Option<String>,
+    pub text: String,
+    /// 本文中のバイト位置（`text == content[start..end]`）
+    pub start: usize,
+    pub end: usize,
+    /// `text`の近似トークン数
+    pub approx_tokens: usize,
+}
+
+// 分割の単位（段落・見出し、長い段落は文・文字数で分けたもの）
+#[derive(Debug, Clone)]
+struct Unit {
+    range: Range<usize>,
+    tokens: usize,
+    /// 見出しの行（節の始まり）
+    heading: bool,
+    /// 属する節の見出し
+    section: Option<String>,
+}
+
+/// # 概要
+/// テキストの近似トークン数を返す。
+///
+/// 日本語などASCII以外の文字は1文字1トークン、ASCIIの文字は空白を除いて4文字で1トークンとして数える。
+/// トークナイザに依存しない近似のため、上限には余裕を持たせる。
+pub fn estimate_tokens(text: &str) -> usize {
+    let (ascii, others) =
+        text.chars()
+            .filter(|c| !c.is_whitespace())
+            .fold((0, 0), |(ascii, others), c| {
+                if c.is_ascii() {
+                    (ascii + 1, others)
+                } else {
+                    (ascii, others + 1)
+                }
+            });
+    others + ascii.div_ceil(4)
+}
+
+/// # 概要
+/// 記事の本文（Markdown）をLLMに渡すためのチャンクに分割する。
+///
+/// 段落（空行区切り）単位で`max_tokens`以内に詰め、見出しの行では必ず新しいチャンクを始める。
+/// `max_tokens`を超える段落は文、さらに長い文は文字数で分ける。コードブロックは途中の空行で分けない。
+/// 2つ目以降のチャンクの先頭には、同じ節の前のチャンクの末尾の文を`overlap_tokens`以内で繰り返す。
+pub fn split_article_chunks(content: &str, options: &ChunkOptions) -> Vec<Chunk> {
+    let max_tokens = options.max_tokens.max(1);
+    let units: Vec<Unit> = split_blocks(content)
+        .into_iter()
+        .flat_map(|unit| split_oversized(content, unit, max_tokens))
+        .collect();
+
+    let mut chunks = Vec::new();
+    let mut current: Vec<Unit> = Vec::new();
+    let mut tokens = 0;
+    // 前のチャンクから繰り返した単位の数（見出しの判定に使わない）
+    let mut carried = 0;
+    for unit in units {
+        if current.len() > carried && (unit.heading || tokens + unit.tokens > max_tokens) {
+            chunks.push(build_chunk(
+                content,
+                chunks.len(),
+                &current[carried..],
+                &current,
+            ));
+            current = if unit.heading {
+                Vec::new()
+            } else {
+                overlap_units(
+                    content,
+                    &current,
+                    options
+                        .overlap_tokens
+                        .min(max_tokens - unit.tokens.min(max_tokens)),
+                )
+            };
+            carried = current.len();
+            tokens = current.iter().map(|u| u.tokens).sum();
+        }
+        tokens += unit.tokens;
+        current.push(unit);
+    }
+    if current.len() > carried {
+        chunks.push(build_chunk(
+            content,
+            chunks.len(),
+            &current[carried..],
+            &current,
+        ));
+    }
+    chunks
+}
+
+/// # 概要
+/// IDまたはURLで記事を取得し、本文をチャンクに分割して返す（記事が無ければ`None`）。
+///
+/// 本文の無い記事は空のリストを返す。embeddingや要約など、チャンク単位で処理する用途に使う。
+pub async fn get_article_chunks(
+    key: &ArticleKey,
+    options: &ChunkOptions,
+    pool: &PgPool,
+) -> Result<Option<Vec<Chunk>>> {
+    let article = get_article(key, pool).await?;
+    Ok(article.map(|article| {
+        article
+            .content
+            .as_deref()
+            .map(|content| split_article_chunks(content, options))
+            .unwrap_or_default()
+    }))
+}
+
+fn build_chunk(content: &str, index: usize, own: &[Unit], all: &[Unit]) -> Chunk {
+    let start = all.first().map_or(0, |u| u.range.start);
+    let end = all.last().map_or(0, |u| u.range.end);
+    let text = content[start..end].to_string();
+    Chunk {
+        index,
+        heading: own.first().and_then(|u| u.section.clone()),
+        approx_tokens: estimate_tokens(&text),
+        text,
+        start,
+        end,
+    }
+}
+
+/// 本文を段落・見出しの単位に分ける（空行・空白のみの単位は含めない）
+fn split_blocks(content: &str) -> Vec<Unit> {
+    let mut units = Vec::new();
+    let mut section: Option<String> = None;
+    let mut paragraph: Option<Range<usize>> = None;
+    let mut in_fence = false;
+    let mut offset = 0;
+
+    let flush =
+        |paragraph: &mut Option<Range<usize>>, section: &Option<String>, units: &mut Vec<Unit>| {
+            if let Some(range) = paragraph.take() {
+                units.push(Unit {
+                    tokens: estimate_tokens(&content[range.clone()]),
+                    range,
+                    heading: false,
+                    section: section.clone(),
+                });
+            }
+        };
+
+    for line in content.split_inclusive('\n') {
+        let range = offset..offset + line.trim_end().len();
+        offset += line.len();
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        } else if !in_fence && trimmed.is_empty() {
+            flush(&mut paragraph, &section, &mut units);
+            continue;
+        } else if !in_fence && is_heading(trimmed) {
+            flush(&mut paragraph, &section, &mut units);
+            section = Some(trimmed.trim_start_matches('#').trim().to_string());
+            units.push(Unit {
+                tokens: estimate_tokens(trimmed),
+                range,
+                heading: true,
+                section: section.clone(),
+            });
+            continue;
+        }
+        paragraph = Some(match paragraph {
+            Some(paragraph) => paragraph.start..range.end,
+            None => range.start + (line.len() - line.trim_start().len())..range.end,
+        });
+    }
+    flush(&mut paragraph, &section, &mut units);
+    units
+}
+
+/// ATX形式の見出し（`# `〜`###### `）か
+fn is_heading(line: &str) -> bool {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    (1..=6).contains(&level) && line[level..].starts_with([' ', '\t'])
+}
+
+/// `max_tokens`を超える単位を文、さらに長い文は文字数で分ける
+fn split_oversized(content: &str, unit: Unit, max_tokens: usize) -> Vec<Unit> {
+    if unit.tokens <= max_tokens {
+        return vec![unit];
+    }
+    split_sentences(content, unit.range.clone())
+        .into_iter()
+        .flat_map(|range| split_by_tokens(content, range, max_tokens))
+        .map(|range| Unit {
+            tokens: estimate_tokens(&content[range.clone()]),
+            range,
+            heading: false,
+            section: unit.section.clone(),
+        })
+        .collect()
+}
+
+/// 範囲を文の区切り（句点・感嘆符・疑問符・改行）で分ける（前後の空白は含めない）
+fn split_sentences(content: &str, range: Range<usize>) -> Vec<Range<usize>> {
+    let text = &content[range.clone()];
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let next = chars.peek().map(|&(_, next)| next);
+        let is_end = matches!(c, '。' | '！' | '？' | '\n')
+            || (matches!(c, '.' | '!' | '?') && next.is_none_or(char::is_whitespace));
+        if is_end {
+            sentences.push(start..i + c.len_utf8());
+            start = i + c.len_utf8();
+        }
+    }
+    sentences.push(start..text.len());
+
+    sentences
+        .into_iter()
+        .filter_map(|sentence| {
+            let part = &text[sentence.clone()];
+            let leading = part.len() - part.trim_start().len();
+            let trimmed = part.trim();
+            (!trimmed.is_empty()).then(|| {
+                let start = range.start + sentence.start + leading;
+                start..start + trimmed.len()
+            })
+        })
+        .collect()
+}
+
+/// `max_tokens`を超える範囲を文字の境界で分ける
+fn split_by_tokens(content: &str, range: Range<usize>, max_tokens: usize) -> Vec<Range<usize>> {
+    if estimate_tokens(&content[range.clone()]) <= max_tokens {
+        return vec![range];
+    }
+    let mut pieces = Vec::new();
+    let mut start = range.start;
+    for (i, c) in content[range.clone()].char_indices() {
+        let end = range.start + i + c.len_utf8();
+        if estimate_tokens(&content[start..end]) > max_tokens {
+            let split = range.start + i;
+            if split > start {
+                pieces.push(start..split);
+                start = split;
+            }
+        }
+    }
+    pieces.push(start..range.end);
+    pieces
+}
+
+/// 前のチャンクの末尾から`overlap_tokens`以内の文を取り出す（同じ節の文のみ、チャンク全体は繰り返さない）
+fn overlap_units(content: &str, previous: &[Unit], overlap_tokens: usize) -> Vec<Unit> {
+    let Some(last) = previous.last() else {
+        return Vec::new();
+    };
+    if overlap_tokens == 0 {
+        return Vec::new();
+    }
+    let first_start = previous[0].range.start;
+    let sentences: Vec<Unit> = previous
+        .iter()
+        .filter(|unit| !unit.heading && unit.section == last.section)
+        .flat_map(|unit| {
+            split_sentences(content, unit.range.clone())
+                .into_iter()
+                .map(|range| Unit {
+                    tokens: estimate_tokens(&content[range.clone()]),
+                    range,
+                    heading: false,
+                    section: unit.section.clone(),
+                })
+        })
+        .collect();
+
+    let mut tokens = 0;
+    let mut carried = Vec::new();
+    for sentence in sentences.into_iter().rev() {
+        if tokens + sentence.tokens > overlap_tokens || sentence.range.start == first_start {
+            break;
+        }
+        tokens += sentence.tokens;
+        carried.push(sentence);
+    }
+    carried.reverse();
+    carried
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARTICLE: &str = "\
+リード文です。
+
+# 経済
+
+景気は回復している。物価も上昇した。
+
+```
+コード
+
+ブロック
+```
+
+## 市場
+
+株価が上がった。";
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("日本語"), 3);
+        assert_eq!(estimate_tokens("hello world"), 3, "空白を除いた10文字");
+    }
+
+    #[test]
+    fn test_split_article_chunks_by_heading() {
+        let chunks = split_article_chunks(ARTICLE, &ChunkOptions::default());
+        let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "リード文です。",
+                "# 経済\n\n景気は回復している。物価も上昇した。\n\n```\nコード\n\nブロック\n```",
+                "## 市場\n\n株価が上がった。",
+            ],
+            "見出し毎に分け、コードブロックは分けない"
+        );
+        assert_eq!(chunks[0].heading, None);
+        assert_eq!(chunks[1].heading.as_deref(), Some("経済"));
+        assert_eq!(chunks[2].heading.as_deref(), Some("市場"));
+        for chunk in &chunks {
+            assert_eq!(&ARTICLE[chunk.start..chunk.end], chunk.text);
+        }
+        assert!(split_article_chunks("", &ChunkOptions::default()).is_empty());
+        println!("✅ 見出し単位のチャンク分割テスト成功");
+    }
+
+    #[test]
+    fn test_split_article_chunks_with_overlap() {
+        let content = "一文目です。二文目です。三文目です。\n\n四文目です。";
+        // 1文6トークン
+        let options = ChunkOptions {
+            max_tokens: 12,
+            overlap_tokens: 6,
+        };
+        let chunks = split_article_chunks(content, &options);
+        let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "一文目です。二文目です。",
+                "二文目です。三文目です。",
+                "三文目です。\n\n四文目です。",
+            ]
+        );
+        assert!(chunks.iter().all(|c| c.approx_tokens <= options.max_tokens));
+
+        // 重複なしなら本文を過不足なく分ける（段落を跨いで詰める）
+        let chunks = split_article_chunks(
+            content,
+            &ChunkOptions {
+                max_tokens: 12,
+                overlap_tokens: 0,
+            },
+        );
+        let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec!["一文目です。二文目です。", "三文目です。\n\n四文目です。"]
+        );
+
+        // 文より小さい上限では文字数で分ける
+        let chunks = split_article_chunks(
+            "あいうえおかきくけこ",
+            &ChunkOptions {
+                max_tokens: 4,
+                overlap_tokens: 0,
+            },
+        );
+        let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts, vec!["あいうえ", "おかきく", "けこ"]);
+        println!("✅ 重複付きのチャンク分割テスト成功");
+    }
+}
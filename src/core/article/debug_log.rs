@@ -0,0 +1,245 @@
+use super::pipeline::PipelineReport;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+
+// 記事毎の処理ログを記録するデバッグモードの設定（config/app.yamlのarticle_fetch.debugに対応）
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ArticleDebugConfig {
+    /// 処理ログを記録するURL（完全一致）
+    #[serde(default)]
+    pub urls: Vec<String>,
+    /// 処理ログを記録する記事の割合（0.0〜1.0）
+    #[serde(default)]
+    pub sample_rate: f64,
+}
+
+impl ArticleDebugConfig {
+    /// # 概要
+    /// URLの処理ログを記録するかどうか。
+    ///
+    /// サンプリングはURLのハッシュで判定するため、同じURLは毎回同じ判定になる
+    /// （取得に失敗し続ける記事も再取得のたびに記録される）。
+    pub fn should_trace(&self, url: &str) -> bool {
+        if self.urls.iter().any(|target| target == url) {
+            return true;
+        }
+        if self.sample_rate <= 0.0 {
+            return false;
+        }
+        let hash = Sha256::digest(url.as_bytes());
+        let bucket = u64::from_be_bytes(hash[..8].try_into().expect("ハッシュは8バイト以上"));
+        (bucket as f64 / u64::MAX as f64) < self.sample_rate
+    }
+}
+
+// 処理ログの重要度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DebugLogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+// 処理ログの1行
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DebugLogEntry {
+    pub at: DateTime<Utc>,
+    pub level: DebugLogLevel,
+    /// ログの種類（request / attempt / response / pipeline / parse_warning など）
+    pub kind: String,
+    pub message: String,
+}
+
+// 1記事の処理中に発生したログ（articles.debug_logに保存する）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArticleDebugLog {
+    pub entries: Vec<DebugLogEntry>,
+}
+
+impl ArticleDebugLog {
+    pub fn push(&mut self, level: DebugLogLevel, kind: &str, message: impl Into<String>) {
+        self.entries.push(DebugLogEntry {
+            at: Utc::now(),
+            level,
+            kind: kind.to_string(),
+            message: message.into(),
+        });
+    }
+
+    pub fn info(&mut self, kind: &str, message: impl Into<String>) {
+        self.push(DebugLogLevel::Info, kind, message);
+    }
+
+    pub fn warn(&mut self, kind: &str, message: impl Into<String>) {
+        self.push(DebugLogLevel::Warn, kind, message);
+    }
+
+    pub fn error(&mut self, kind: &str, message: impl Into<String>) {
+        self.push(DebugLogLevel::Error, kind, message);
+    }
+
+    /// 1記事分のパイプラインの統計を記録する（修復した箇所と品質フラグ）
+    pub fn record_pipeline(&mut self, report: &PipelineReport) {
+        for (step, stats) in &report.steps {
+            for (item, count) in stats {
+                self.info("pipeline", format!("[{}] {}: {}件", step, item, count));
+            }
+        }
+        for flag in report.quality_flags.keys() {
+            self.warn("parse_warning", format!("修復できない構造の崩れ: {}", flag));
+        }
+    }
+
+    /// 表示用の行に整形する
+    pub fn format_lines(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .map(|entry| {
+                let level = match entry.level {
+                    DebugLogLevel::Info => "INFO",
+                    DebugLogLevel::Warn => "WARN",
+                    DebugLogLevel::Error => "ERROR",
+                };
+                format!(
+                    "{} {:<5} [{}] {}",
+                    entry.at.to_rfc3339(),
+                    level,
+                    entry.kind,
+                    entry.message
+                )
+            })
+            .collect()
+    }
+}
+
+/// これまでの取得試行の回数（処理ログにリトライであることを記録するため）
+pub async fn count_fetch_attempts(url: &str, pool: &PgPool) -> Result<i64> {
+    sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM article_fetch_attempts WHERE url = $1"#,
+        url
+    )
+    .fetch_one(pool)
+    .await
+    .with_context(|| format!("取得試行回数の集計に失敗: {}", url))
+}
+
+/// # 概要
+/// 処理ログを記事に添付して保存する（前回の処理ログは置き換える）。
+///
+/// 記事が保存されていないURLのログは保存しない。
+pub async fn store_article_debug_logs(
+    logs: &[(String, ArticleDebugLog)],
+    pool: &PgPool,
+) -> Result<()> {
+    if logs.is_empty() {
+        return Ok(());
+    }
+    let urls: Vec<String> = logs.iter().map(|(url, _)| url.clone()).collect();
+    let entries = logs
+        .iter()
+        .map(|(_, log)| serde_json::to_value(log))
+        .collect::<Result<Vec<_>, _>>()
+        .context("処理ログのシリアライズに失敗")?;
+
+    sqlx::query!(
+        r#"
+        UPDATE articles a SET debug_log = t.debug_log
+        FROM UNNEST($1::text[], $2::jsonb[]) AS t(url, debug_log)
+        WHERE a.url = t.url
+        "#,
+        &urls,
+        &entries
+    )
+    .execute(pool)
+    .await
+    .context("処理ログの保存に失敗")?;
+
+    Ok(())
+}
+
+/// 記事に添付された処理ログを取得する（記録していない場合は`None`）
+pub async fn get_article_debug_log(url: &str, pool: &PgPool) -> Result<Option<ArticleDebugLog>> {
+    let debug_log = sqlx::query_scalar!("SELECT debug_log FROM articles WHERE url = $1", url)
+        .fetch_optional(pool)
+        .await
+        .with_context(|| format!("処理ログの取得に失敗: {}", url))?
+        .flatten();
+
+    debug_log
+        .map(|value| serde_json::from_value(value).context("処理ログの形式が不正です"))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::article::{store_article_content, ArticleContent};
+
+    #[test]
+    fn test_should_trace() {
+        let config = ArticleDebugConfig {
+            urls: vec!["https://example.com/target".to_string()],
+            sample_rate: 0.0,
+        };
+        assert!(config.should_trace("https://example.com/target"));
+        assert!(!config.should_trace("https://example.com/other"));
+        assert!(!ArticleDebugConfig::default().should_trace("https://example.com/target"));
+
+        let all = ArticleDebugConfig {
+            sample_rate: 1.0,
+            ..Default::default()
+        };
+        assert!(all.should_trace("https://example.com/other"));
+
+        // サンプリング率に近い割合のURLが対象になり、判定はURL毎に一定
+        let half = ArticleDebugConfig {
+            sample_rate: 0.5,
+            ..Default::default()
+        };
+        let urls: Vec<String> = (0..1000)
+            .map(|i| format!("https://example.com/{}", i))
+            .collect();
+        let traced = urls.iter().filter(|url| half.should_trace(url)).count();
+        assert!((400..600).contains(&traced), "traced: {}", traced);
+        assert!(urls
+            .iter()
+            .all(|url| half.should_trace(url) == half.should_trace(url)));
+    }
+
+    #[sqlx::test]
+    async fn test_store_and_get_article_debug_log(pool: PgPool) -> Result<(), anyhow::Error> {
+        let url = "https://debug.example.com/1";
+        store_article_content(&ArticleContent::error_placeholder(url, "取得エラー"), &pool).await?;
+        assert!(get_article_debug_log(url, &pool).await?.is_none());
+        assert_eq!(count_fetch_attempts(url, &pool).await?, 1);
+
+        let mut log = ArticleDebugLog::default();
+        log.info("request", "取得開始");
+        log.error("response", "取得エラー: timed out");
+        store_article_debug_logs(
+            &[
+                (url.to_string(), log.clone()),
+                // 記事が無いURLは無視される
+                ("https://debug.example.com/none".to_string(), log.clone()),
+            ],
+            &pool,
+        )
+        .await?;
+
+        let stored = get_article_debug_log(url, &pool).await?.unwrap();
+        assert_eq!(stored, log);
+        assert!(stored.format_lines()[1].contains("ERROR [response] 取得エラー"));
+        assert!(
+            get_article_debug_log("https://debug.example.com/none", &pool)
+                .await?
+                .is_none()
+        );
+
+        println!("✅ 記事の処理ログ保存テスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,274 @@
+use super::link::{find_url_start, url_end};
+use super::service::ArticleContent;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::{Connection, PgConnection, PgPool};
+use std::fmt;
+use std::str::FromStr;
+use url::Url;
+
+// 本文に埋め込まれたコンテンツの種類（article_embeds.embed_typeに保存する）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbedType {
+    /// Twitter/Xのツイート
+    Twitter,
+    /// YouTubeの動画
+    Youtube,
+}
+
+impl EmbedType {
+    pub const ALL: [EmbedType; 2] = [Self::Twitter, Self::Youtube];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Twitter => "twitter",
+            Self::Youtube => "youtube",
+        }
+    }
+}
+
+impl fmt::Display for EmbedType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EmbedType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|embed_type| embed_type.as_str() == s)
+            .with_context(|| format!("不明な埋め込みの種類: {}", s))
+    }
+}
+
+// 記事に埋め込まれたコンテンツの参照（article_embedsテーブルの1行）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArticleEmbed {
+    /// 埋め込み元の記事のURL
+    pub url: String,
+    pub embed_type: EmbedType,
+    /// 埋め込まれたツイート・動画の正規化したURL
+    pub embed_url: String,
+}
+
+/// # 概要
+/// 本文中のURLから埋め込まれたツイート・動画を検出する（本文中の出現順、重複は除く）。
+///
+/// ツイートは`https://x.com/<ユーザー>/status/<ID>`、
+/// 動画は`https://www.youtube.com/watch?v=<ID>`の形に揃える。
+/// Markdownのリンクと、埋め込み用HTMLの`href` / `src`属性のどちらも対象にする。
+pub fn extract_embeds(content: &str) -> Vec<(EmbedType, String)> {
+    let mut embeds = Vec::new();
+    let mut rest = content;
+    while let Some(start) = find_url_start(rest) {
+        let candidate = &rest[start..];
+        let end = url_end(candidate);
+        let embed = Url::parse(&candidate[..end])
+            .ok()
+            .and_then(|url| classify_embed(&url));
+        if let Some(embed) = embed {
+            if !embeds.contains(&embed) {
+                embeds.push(embed);
+            }
+        }
+        rest = &candidate[end..];
+    }
+    embeds
+}
+
+/// 埋め込みの対象になるURLであれば種類と正規化したURLを返す
+fn classify_embed(url: &Url) -> Option<(EmbedType, String)> {
+    let host = url.host_str()?;
+    let host = host
+        .strip_prefix("www.")
+        .or_else(|| host.strip_prefix("mobile."))
+        .or_else(|| host.strip_prefix("m."))
+        .unwrap_or(host);
+    let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+
+    match host {
+        "twitter.com" | "x.com" => match segments.as_slice() {
+            [user, "status" | "statuses", id, ..] if id.chars().all(|c| c.is_ascii_digit()) => {
+                Some((
+                    EmbedType::Twitter,
+                    format!("https://x.com/{}/status/{}", user, id),
+                ))
+            }
+            _ => None,
+        },
+        "youtube.com" | "youtube-nocookie.com" => {
+            let id = match segments.as_slice() {
+                ["watch"] => url
+                    .query_pairs()
+                    .find(|(key, _)| key == "v")
+                    .map(|(_, id)| id.into_owned())?,
+                ["embed" | "shorts" | "live", id, ..] => id.to_string(),
+                _ => return None,
+            };
+            youtube_embed(&id)
+        }
+        "youtu.be" => youtube_embed(segments.first()?),
+        _ => None,
+    }
+}
+
+/// YouTubeの動画IDから埋め込みの参照を作る（IDの形式が不正な場合は`None`）
+fn youtube_embed(id: &str) -> Option<(EmbedType, String)> {
+    let valid = id.len() == 11
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then(|| {
+        (
+            EmbedType::Youtube,
+            format!("https://www.youtube.com/watch?v={}", id),
+        )
+    })
+}
+
+/// 保存した記事の本文から埋め込みを抽出して、記事毎にarticle_embedsを置き換える
+///
+/// 取得に失敗した記事は埋め込みを持たないものとして削除する。
+/// 抽出結果は付随情報のため、失敗しても記事の保存自体は成功として扱う。
+/// 呼び出し側のトランザクションを中断させないよう、セーブポイント内で実行する。
+pub(super) async fn record_article_embeds(articles: &[&ArticleContent], conn: &mut PgConnection) {
+    let urls: Vec<String> = articles.iter().map(|a| a.url.clone()).collect();
+    let mut embed_urls: Vec<String> = Vec::new();
+    let mut embed_types: Vec<String> = Vec::new();
+    let mut embedded: Vec<String> = Vec::new();
+    for article in articles.iter().filter(|a| a.is_success()) {
+        for (embed_type, embed_url) in extract_embeds(&article.content) {
+            embed_urls.push(article.url.clone());
+            embed_types.push(embed_type.to_string());
+            embedded.push(embed_url);
+        }
+    }
+
+    let result: Result<()> = async {
+        let mut savepoint = conn.begin().await?;
+        sqlx::query!("DELETE FROM article_embeds WHERE url = ANY($1)", &urls)
+            .execute(&mut *savepoint)
+            .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO article_embeds (url, embed_type, embed_url)
+            SELECT * FROM UNNEST($1::text[], $2::text[], $3::text[])
+            "#,
+            &embed_urls,
+            &embed_types,
+            &embedded
+        )
+        .execute(&mut *savepoint)
+        .await?;
+        savepoint.commit().await?;
+        Ok(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(error = format!("{:#}", e), "埋め込みの記録エラー");
+    }
+}
+
+/// # 概要
+/// 記事に埋め込まれたツイート・動画の参照を本文中の出現順に取得する。
+pub async fn get_embeds(url: &str, pool: &PgPool) -> Result<Vec<ArticleEmbed>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT url, embed_type, embed_url
+        FROM article_embeds
+        WHERE url = $1
+        ORDER BY id
+        "#,
+        url
+    )
+    .fetch_all(pool)
+    .await
+    .context("埋め込みの取得に失敗")?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(ArticleEmbed {
+                url: row.url,
+                embed_type: row.embed_type.parse()?,
+                embed_url: row.embed_url,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::article::service::store_article_contents;
+    use chrono::Utc;
+
+    const CONTENT: &str = r#"# 記事
+
+本文 [ツイート](https://twitter.com/example/status/1234567890?s=20) を引用。
+
+<blockquote class="twitter-tweet"><a href="https://x.com/other/status/42">pic</a></blockquote>
+<iframe src="https://www.youtube.com/embed/dQw4w9WgXcQ?start=10"></iframe>
+
+動画: https://youtu.be/dQw4w9WgXcQ と https://www.youtube.com/watch?v=abcdefghijk&t=1s
+プロフィール https://x.com/example は埋め込みではない。
+"#;
+
+    #[test]
+    fn test_extract_embeds() {
+        let embeds = extract_embeds(CONTENT);
+        assert_eq!(
+            embeds,
+            vec![
+                (
+                    EmbedType::Twitter,
+                    "https://x.com/example/status/1234567890".to_string()
+                ),
+                (
+                    EmbedType::Twitter,
+                    "https://x.com/other/status/42".to_string()
+                ),
+                (
+                    EmbedType::Youtube,
+                    "https://www.youtube.com/watch?v=dQw4w9WgXcQ".to_string()
+                ),
+                (
+                    EmbedType::Youtube,
+                    "https://www.youtube.com/watch?v=abcdefghijk".to_string()
+                ),
+            ]
+        );
+        assert!(extract_embeds("https://www.youtube.com/watch?v=short").is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_store_and_get_embeds(pool: PgPool) -> Result<(), anyhow::Error> {
+        let url = "https://embed.example.com/news/1";
+        let article = |content: &str| ArticleContent {
+            url: url.to_string(),
+            timestamp: Utc::now(),
+            status_code: 200,
+            content: content.to_string(),
+            raw_html: None,
+        };
+        store_article_contents(&[article(CONTENT)], &pool).await?;
+        let embeds = get_embeds(url, &pool).await?;
+        assert_eq!(embeds.len(), 4);
+        assert_eq!(embeds[0].embed_type, EmbedType::Twitter);
+        assert_eq!(
+            embeds[3].embed_url,
+            "https://www.youtube.com/watch?v=abcdefghijk"
+        );
+
+        // 本文が更新されたら埋め込みも置き換える
+        store_article_contents(&[article("埋め込みの無い本文")], &pool).await?;
+        assert!(get_embeds(url, &pool).await?.is_empty());
+
+        println!("✅ 埋め込み抽出・保存テスト成功");
+        Ok(())
+    }
+}
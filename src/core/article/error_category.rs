@@ -0,0 +1,447 @@
+use super::service::{ERROR_STATUS_CODE, ROBOTS_DISALLOWED_STATUS_CODE};
+use crate::types::{format_error_chain, FetchError};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+// 記事取得エラーの原因カテゴリ（articles.error_categoryに保存する）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// 応答待ちのタイムアウト
+    Timeout,
+    /// 名前解決の失敗
+    Dns,
+    /// 接続拒否・切断などの通信エラー
+    Network,
+    /// アクセス拒否・ボット判定（403など）
+    Blocked,
+    /// 有料会員限定などのペイウォール
+    Paywall,
+    /// ページが存在しない（404など）
+    NotFound,
+    /// 応答の解析失敗
+    Parse,
+    /// APIの利用上限・レート制限
+    ApiQuota,
+    /// 上記のいずれにも該当しない
+    Unknown,
+}
+
+/// 判定に使うキーワード（本文を小文字化して部分一致、上から順に判定する）
+const CATEGORY_KEYWORDS: &[(ErrorCategory, &[&str])] = &[
+    (
+        ErrorCategory::ApiQuota,
+        &[
+            "rate limit",
+            "ratelimit",
+            "too many requests",
+            "quota",
+            "insufficient credits",
+            "payment required",
+        ],
+    ),
+    (
+        ErrorCategory::Timeout,
+        &["timeout", "timed out", "deadline exceeded", "タイムアウト"],
+    ),
+    (
+        ErrorCategory::Dns,
+        &[
+            "dns",
+            "failed to lookup address",
+            "name or service not known",
+            "no such host",
+            "nodename nor servname",
+            "名前解決",
+        ],
+    ),
+    (
+        ErrorCategory::Paywall,
+        &[
+            "paywall",
+            "subscribe to continue",
+            "subscribers only",
+            "subscription required",
+            "有料会員",
+            "会員限定",
+        ],
+    ),
+    (
+        ErrorCategory::Blocked,
+        &[
+            "forbidden",
+            "access denied",
+            "captcha",
+            "bot detection",
+            "are you a robot",
+            "収集禁止",
+        ],
+    ),
+    (
+        ErrorCategory::NotFound,
+        &["not found", "404", "見つかりません"],
+    ),
+    (
+        ErrorCategory::Parse,
+        &[
+            "parse",
+            "decode",
+            "deserialize",
+            "invalid json",
+            "unexpected token",
+            "解析に失敗",
+        ],
+    ),
+    (
+        ErrorCategory::Network,
+        &[
+            "connection refused",
+            "connection reset",
+            "connection closed",
+            "broken pipe",
+            "error sending request",
+            "接続",
+        ],
+    ),
+];
+
+impl ErrorCategory {
+    pub const ALL: [ErrorCategory; 9] = [
+        Self::Timeout,
+        Self::Dns,
+        Self::Network,
+        Self::Blocked,
+        Self::Paywall,
+        Self::NotFound,
+        Self::Parse,
+        Self::ApiQuota,
+        Self::Unknown,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Timeout => "timeout",
+            Self::Dns => "dns",
+            Self::Network => "network",
+            Self::Blocked => "blocked",
+            Self::Paywall => "paywall",
+            Self::NotFound => "not_found",
+            Self::Parse => "parse",
+            Self::ApiQuota => "api_quota",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    /// 時間をおいて再試行すれば成功する見込みがある一時的なエラーかどうか
+    ///
+    /// タイムアウト・名前解決・通信エラー・レート制限が該当する。
+    /// 404やアクセス拒否など、再試行しても結果が変わらないエラーは該当しない。
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::Timeout | Self::Dns | Self::Network | Self::ApiQuota
+        )
+    }
+
+    /// # 概要
+    /// 取得結果のステータスコードとエラー本文から原因カテゴリを判定する。
+    ///
+    /// 成功（200）の場合は`None`。判定できないエラーは`Unknown`とする。
+    /// ステータスコードで判別できる場合はそれを優先し、
+    /// 取得処理で500に丸められたエラーは本文のキーワードで判定する。
+    pub fn classify(status_code: i32, message: &str) -> Option<Self> {
+        match status_code {
+            200 => return None,
+            401 | 403 | 451 | ROBOTS_DISALLOWED_STATUS_CODE => return Some(Self::Blocked),
+            402 => return Some(Self::Paywall),
+            404 | 410 => return Some(Self::NotFound),
+            408 | 504 => return Some(Self::Timeout),
+            429 => return Some(Self::ApiQuota),
+            _ => {}
+        }
+
+        let message = message.to_lowercase();
+        let category = CATEGORY_KEYWORDS
+            .iter()
+            .find(|(_, keywords)| keywords.iter().any(|k| message.contains(k)))
+            .map(|(category, _)| *category);
+        Some(category.unwrap_or(Self::Unknown))
+    }
+
+    /// # 概要
+    /// 取得のエラーの種類から原因カテゴリを判定する。
+    ///
+    /// ステータス・タイムアウト・解析の失敗は種類で判別し、
+    /// 送信の失敗などはエラー本文のキーワードで判定する（判定できなければ`Unknown`）。
+    pub fn from_fetch_error(error: &FetchError) -> Self {
+        let status_code = match error {
+            FetchError::Timeout { .. } => return Self::Timeout,
+            FetchError::Parse(_) => return Self::Parse,
+            FetchError::Status { status, .. } => i32::from(*status),
+            _ => ERROR_STATUS_CODE,
+        };
+        Self::classify(status_code, &format_error_chain(error)).unwrap_or(Self::Unknown)
+    }
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ErrorCategory {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.as_str() == s)
+            .with_context(|| format!("不明なエラーカテゴリ: {}", s))
+    }
+}
+
+// エラーカテゴリ毎の集計結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorCategoryCount {
+    pub category: ErrorCategory,
+    pub count: i64,
+    /// 最後にこのカテゴリのエラーが記録された日時
+    pub latest_at: DateTime<Utc>,
+}
+
+/// # 概要
+/// 期間内（`articles.timestamp`が`range`内）のエラー記事をカテゴリ毎に集計する。
+///
+/// 件数の多い順に返す。ゴミ箱の記事は対象外。
+pub async fn aggregate_errors_by_category(
+    range: Range<DateTime<Utc>>,
+    pool: &PgPool,
+) -> Result<Vec<ErrorCategoryCount>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            COALESCE(error_category, 'unknown') AS "category!",
+            COUNT(*) AS "count!",
+            MAX(timestamp) AS "latest_at!"
+        FROM articles
+        WHERE deleted_at IS NULL
+            AND status_code <> 200
+            AND timestamp >= $1 AND timestamp < $2
+        GROUP BY 1
+        ORDER BY 2 DESC, 1
+        "#,
+        range.start,
+        range.end
+    )
+    .fetch_all(pool)
+    .await
+    .context("エラーカテゴリの集計に失敗")?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(ErrorCategoryCount {
+                category: row.category.parse()?,
+                count: row.count,
+                latest_at: row.latest_at,
+            })
+        })
+        .collect()
+}
+
+/// # 概要
+/// カテゴリ未設定のエラー記事を判定し直して保存する（列追加前のデータの補完用）。
+///
+/// # 戻り値
+/// カテゴリを設定した記事数
+pub async fn backfill_error_categories(pool: &PgPool) -> Result<u64> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT url, status_code, content FROM articles
+        WHERE status_code <> 200 AND error_category IS NULL
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .context("カテゴリ未設定のエラー記事の取得に失敗")?;
+
+    if rows.is_empty() {
+        return Ok(0);
+    }
+
+    let (urls, categories): (Vec<String>, Vec<String>) = rows
+        .into_iter()
+        .filter_map(|row| {
+            let category = ErrorCategory::classify(row.status_code, &row.content)?;
+            Some((row.url, category.as_str().to_string()))
+        })
+        .unzip();
+
+    let result = sqlx::query!(
+        r#"
+        UPDATE articles AS a SET error_category = t.category
+        FROM UNNEST($1::text[], $2::text[]) AS t(url, category)
+        WHERE a.url = t.url
+        "#,
+        &urls,
+        &categories
+    )
+    .execute(pool)
+    .await
+    .context("エラーカテゴリの保存に失敗")?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::article::{store_article_content, ArticleContent};
+    use chrono::Duration;
+
+    #[test]
+    fn test_classify_error() {
+        let cases = [
+            (200, "本文", None),
+            (
+                500,
+                "Firecrawl API エラー: operation timed out",
+                Some(ErrorCategory::Timeout),
+            ),
+            (
+                500,
+                "取得エラー: error sending request: dns error: failed to lookup address",
+                Some(ErrorCategory::Dns),
+            ),
+            (
+                500,
+                "Firecrawl API エラー: Rate limit exceeded",
+                Some(ErrorCategory::ApiQuota),
+            ),
+            (
+                500,
+                "Please subscribe to continue reading",
+                Some(ErrorCategory::Paywall),
+            ),
+            (500, "JSONの解析に失敗", Some(ErrorCategory::Parse)),
+            (500, "connection refused", Some(ErrorCategory::Network)),
+            (403, "", Some(ErrorCategory::Blocked)),
+            (
+                999,
+                "robots.txtで取得が禁止されています",
+                Some(ErrorCategory::Blocked),
+            ),
+            (404, "", Some(ErrorCategory::NotFound)),
+            (500, "想定外のエラー", Some(ErrorCategory::Unknown)),
+        ];
+        for (status_code, message, expected) in cases {
+            assert_eq!(
+                ErrorCategory::classify(status_code, message),
+                expected,
+                "{}: {}",
+                status_code,
+                message
+            );
+        }
+
+        for category in ErrorCategory::ALL {
+            assert_eq!(
+                category.as_str().parse::<ErrorCategory>().unwrap(),
+                category
+            );
+        }
+    }
+
+    #[test]
+    fn test_category_from_fetch_error() {
+        let url = "https://example.com/rss".to_string();
+        let cases = [
+            (
+                FetchError::Status {
+                    url: url.clone(),
+                    status: 404,
+                },
+                ErrorCategory::NotFound,
+            ),
+            (
+                FetchError::Status {
+                    url: url.clone(),
+                    status: 503,
+                },
+                ErrorCategory::Unknown,
+            ),
+            (
+                FetchError::Timeout { url: url.clone() },
+                ErrorCategory::Timeout,
+            ),
+            (
+                crate::types::ParseError::Date("2025-13-40".to_string()).into(),
+                ErrorCategory::Parse,
+            ),
+            (
+                FetchError::Unavailable("connection refused".to_string()),
+                ErrorCategory::Network,
+            ),
+        ];
+        for (error, expected) in cases {
+            assert_eq!(
+                ErrorCategory::from_fetch_error(&error),
+                expected,
+                "{}",
+                error
+            );
+        }
+        println!("✅ 取得エラーのカテゴリ判定テスト成功");
+    }
+
+    #[sqlx::test]
+    async fn test_aggregate_errors_by_category(pool: PgPool) -> Result<(), anyhow::Error> {
+        for (url, status_code, content) in [
+            ("https://errors.example.com/1", 500, "operation timed out"),
+            ("https://errors.example.com/2", 500, "request timeout"),
+            ("https://errors.example.com/3", 403, "Forbidden"),
+            ("https://errors.example.com/4", 200, "本文"),
+        ] {
+            let article = ArticleContent {
+                url: url.to_string(),
+                timestamp: Utc::now(),
+                status_code,
+                content: content.to_string(),
+                raw_html: None,
+            };
+            store_article_content(&article, &pool).await?;
+        }
+
+        // 列追加前に保存されたデータを想定して、カテゴリを消してから補完する
+        sqlx::query!(
+            "UPDATE articles SET error_category = NULL WHERE url = 'https://errors.example.com/3'"
+        )
+        .execute(&pool)
+        .await?;
+        assert_eq!(backfill_error_categories(&pool).await?, 1);
+
+        let now = Utc::now();
+        let counts =
+            aggregate_errors_by_category(now - Duration::hours(1)..now + Duration::hours(1), &pool)
+                .await?;
+        let summary: Vec<(ErrorCategory, i64)> =
+            counts.iter().map(|c| (c.category, c.count)).collect();
+        assert_eq!(
+            summary,
+            vec![(ErrorCategory::Timeout, 2), (ErrorCategory::Blocked, 1)]
+        );
+
+        let past =
+            aggregate_errors_by_category(now - Duration::days(2)..now - Duration::days(1), &pool)
+                .await?;
+        assert!(past.is_empty(), "期間外の記事は集計しない");
+
+        println!("✅ エラーカテゴリ集計テスト成功");
+        Ok(())
+    }
+}
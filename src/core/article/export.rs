@@ -0,0 +1,366 @@
+use super::model::Article;
+use super::service::{search_articles, ArticleQuery};
+use crate::core::schema::Versioned;
+use crate::infra::compute::calc_hash;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::io::{Seek, Write};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// ファイル名に使うスラグの最大長（ハッシュ部分を除く）
+const MAX_SLUG_LENGTH: usize = 80;
+
+// Markdownファイル先頭に付与するfront-matter
+#[derive(Debug, Serialize)]
+struct FrontMatter<'a> {
+    title: &'a str,
+    url: &'a str,
+    pub_date: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+    status_code: Option<i32>,
+}
+
+/// # 概要
+/// URLをファイル名として安全なスラグに変換する。
+///
+/// スキームを除いたホストとパスの英数字以外を`-`に置き換え、
+/// 衝突を避けるためURLのハッシュを末尾に付与する。
+///
+/// # Example
+/// `https://example.com/news/Article?id=1` -> `example-com-news-article-id-1-xxxxxxxx`
+pub fn url_to_slug(url: &str) -> String {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+
+    let mut slug = String::new();
+    for c in without_scheme.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.truncate(MAX_SLUG_LENGTH);
+    let slug = slug.trim_end_matches('-');
+
+    let hash = calc_hash(url, 8);
+    if slug.is_empty() {
+        hash
+    } else {
+        format!("{}-{}", slug, hash)
+    }
+}
+
+/// 記事をfront-matter付きのMarkdown文字列に変換する
+pub fn format_article_markdown(article: &Article) -> Result<String> {
+    let front_matter = FrontMatter {
+        title: &article.title,
+        url: &article.url,
+        pub_date: article.pub_date,
+        updated_at: article.updated_at,
+        status_code: article.status_code,
+    };
+    let yaml = serde_yaml::to_string(&front_matter).context("front-matterの生成に失敗")?;
+
+    Ok(format!(
+        "---\n{}---\n\n{}\n",
+        yaml,
+        article.content.as_deref().unwrap_or_default()
+    ))
+}
+
+/// # 概要
+/// 条件に合う記事をMarkdownファイルのZipアーカイブとして書き出す。
+///
+/// 内容が未取得の記事はアーカイブに含めない。
+///
+/// # 戻り値
+/// アーカイブに含めた記事の件数
+pub async fn export_articles_zip<W: Write + Seek>(
+    query: Option<ArticleQuery>,
+    writer: W,
+    pool: &PgPool,
+) -> Result<usize> {
+    let articles = search_articles(query, pool).await?;
+    write_articles_zip(&articles, writer)
+}
+
+/// 記事のリストをZipアーカイブとして書き出す
+pub fn write_articles_zip<W: Write + Seek>(articles: &[Article], writer: W) -> Result<usize> {
+    let mut zip = ZipWriter::new(writer);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut count = 0;
+    for article in articles.iter().filter(|a| a.content.is_some()) {
+        let file_name = format!("{}.md", url_to_slug(&article.url));
+        zip.start_file(file_name.as_str(), options)
+            .context(format!("Zipエントリの作成に失敗: {}", file_name))?;
+        zip.write_all(format_article_markdown(article)?.as_bytes())
+            .context(format!("Zipエントリの書き込みに失敗: {}", file_name))?;
+        count += 1;
+    }
+
+    zip.finish().context("Zipアーカイブの書き出しに失敗")?;
+    Ok(count)
+}
+
+// 記事の書き出し形式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// 1行に1記事のJSON（JSON Lines、`schema_version`付き）
+    #[default]
+    Jsonl,
+    /// ヘッダ付きのCSV
+    Csv,
+    /// front-matter付きのMarkdownを記事毎に連結したもの
+    Markdown,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [Self::Jsonl, Self::Csv, Self::Markdown];
+
+    /// CLIでの名前
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Jsonl => "jsonl",
+            Self::Csv => "csv",
+            Self::Markdown => "markdown",
+        }
+    }
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.as_str() == s)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(ExportFormat::as_str).collect();
+                format!(
+                    "書き出し形式は{}のいずれかを指定してください: {}",
+                    names.join(" / "),
+                    s
+                )
+            })
+    }
+}
+
+/// # 概要
+/// 条件に合う記事（`search_articles`の結果）を指定した形式で書き出す。
+///
+/// Zipアーカイブと異なり、内容が未取得の記事も含める。
+///
+/// # 戻り値
+/// 書き出した記事の件数
+pub async fn export_articles<W: Write>(
+    query: Option<ArticleQuery>,
+    format: ExportFormat,
+    writer: W,
+    pool: &PgPool,
+) -> Result<usize> {
+    let articles = search_articles(query, pool).await?;
+    write_articles(&articles, format, writer)
+}
+
+/// 記事のリストを指定した形式で書き出す
+pub fn write_articles<W: Write>(
+    articles: &[Article],
+    format: ExportFormat,
+    mut writer: W,
+) -> Result<usize> {
+    match format {
+        ExportFormat::Jsonl => {
+            for article in articles {
+                serde_json::to_writer(&mut writer, &Versioned::new(article))
+                    .with_context(|| format!("記事の書き出しに失敗: {}", article.url))?;
+                writer.write_all(b"\n").context("記事の書き出しに失敗")?;
+            }
+        }
+        ExportFormat::Csv => {
+            let mut csv = csv::Writer::from_writer(&mut writer);
+            for article in articles {
+                csv.serialize(article)
+                    .with_context(|| format!("記事の書き出しに失敗: {}", article.url))?;
+            }
+            csv.flush().context("記事の書き出しに失敗")?;
+        }
+        ExportFormat::Markdown => {
+            for (i, article) in articles.iter().enumerate() {
+                if i > 0 {
+                    writer.write_all(b"\n").context("記事の書き出しに失敗")?;
+                }
+                writer
+                    .write_all(format_article_markdown(article)?.as_bytes())
+                    .with_context(|| format!("記事の書き出しに失敗: {}", article.url))?;
+            }
+        }
+    }
+    writer.flush().context("記事の書き出しに失敗")?;
+    Ok(articles.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::SCHEMA_VERSION;
+    use std::io::{Cursor, Read};
+    use zip::ZipArchive;
+
+    fn sample_article(url: &str, content: Option<&str>) -> Article {
+        Article {
+            id: String::new(),
+            url: url.to_string(),
+            title: "テスト: \"記事\"".to_string(),
+            pub_date: "2025-08-26T10:00:00Z".parse().unwrap(),
+            updated_at: None,
+            status_code: content.map(|_| 200),
+            content: content.map(str::to_string),
+            snippet: None,
+        }
+    }
+
+    #[test]
+    fn test_url_to_slug() {
+        let slug = url_to_slug("https://Example.com/news/Article?id=1");
+        assert!(slug.starts_with("example-com-news-article-id-1-"));
+        assert_eq!(slug.len(), "example-com-news-article-id-1-".len() + 8);
+
+        // クエリが異なれば別のファイル名になる
+        assert_ne!(
+            url_to_slug("https://example.com/a?id=1"),
+            url_to_slug("https://example.com/a?id=2")
+        );
+
+        // パス区切りや危険な文字が残らない
+        let slug = url_to_slug("https://example.com/../../etc/passwd");
+        assert!(!slug.contains('/') && !slug.contains('.'));
+
+        // 非ASCIIのみでもハッシュだけのファイル名になる
+        assert_eq!(url_to_slug("日本語").len(), 8);
+
+        // 長いURLは切り詰められる
+        let long_url = format!("https://example.com/{}", "a".repeat(200));
+        assert_eq!(url_to_slug(&long_url).len(), MAX_SLUG_LENGTH + 1 + 8);
+    }
+
+    #[test]
+    fn test_format_article_markdown() -> Result<(), anyhow::Error> {
+        let article = sample_article("https://example.com/a", Some("# 本文"));
+        let markdown = format_article_markdown(&article)?;
+
+        assert!(markdown.starts_with("---\n"));
+        let (front_matter, body) = markdown[4..]
+            .split_once("---\n")
+            .expect("front-matterが閉じられていません");
+        let parsed: serde_yaml::Value = serde_yaml::from_str(front_matter)?;
+        assert_eq!(parsed["title"].as_str(), Some("テスト: \"記事\""));
+        assert_eq!(parsed["url"].as_str(), Some("https://example.com/a"));
+        assert_eq!(parsed["status_code"].as_i64(), Some(200));
+        assert_eq!(body.trim(), "# 本文");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_articles_zip() -> Result<(), anyhow::Error> {
+        let articles = vec![
+            sample_article("https://example.com/a", Some("記事A")),
+            sample_article("https://example.com/b", Some("記事B")),
+            sample_article("https://example.com/unprocessed", None),
+        ];
+
+        let mut buffer = Cursor::new(Vec::new());
+        let count = write_articles_zip(&articles, &mut buffer)?;
+        assert_eq!(count, 2, "内容のない記事は含めないはず");
+
+        let mut archive = ZipArchive::new(buffer)?;
+        assert_eq!(archive.len(), 2);
+
+        let file_name = format!("{}.md", url_to_slug("https://example.com/a"));
+        let mut content = String::new();
+        archive.by_name(&file_name)?.read_to_string(&mut content)?;
+        assert!(content.contains("https://example.com/a"));
+        assert!(content.ends_with("記事A\n"));
+
+        println!("✅ Zipアーカイブ書き出しテスト成功");
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_articles_formats() -> Result<(), anyhow::Error> {
+        let articles = vec![
+            sample_article("https://example.com/a", Some("記事A\n2行目")),
+            sample_article("https://example.com/unprocessed", None),
+        ];
+
+        let mut jsonl = Vec::new();
+        assert_eq!(
+            write_articles(&articles, ExportFormat::Jsonl, &mut jsonl)?,
+            2
+        );
+        let rows: Vec<Versioned<Article>> = String::from_utf8(jsonl)?
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(rows[0].schema_version, SCHEMA_VERSION);
+        assert_eq!(rows[0].data.content.as_deref(), Some("記事A\n2行目"));
+        assert_eq!(rows[1].data.content, None);
+
+        let mut csv = Vec::new();
+        write_articles(&articles, ExportFormat::Csv, &mut csv)?;
+        let mut reader = csv::Reader::from_reader(csv.as_slice());
+        assert_eq!(
+            reader.headers()?.iter().collect::<Vec<_>>(),
+            [
+                "id",
+                "url",
+                "title",
+                "pub_date",
+                "updated_at",
+                "status_code",
+                "content"
+            ]
+        );
+        let records: Vec<csv::StringRecord> = reader.records().collect::<Result<_, _>>()?;
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            &records[0][2], "テスト: \"記事\"",
+            "引用符はエスケープされる"
+        );
+        assert_eq!(&records[0][6], "記事A\n2行目");
+
+        let mut markdown = Vec::new();
+        write_articles(&articles, ExportFormat::Markdown, &mut markdown)?;
+        let markdown = String::from_utf8(markdown)?;
+        assert_eq!(markdown.matches("url: https://example.com/").count(), 2);
+        assert!(markdown.starts_with("---\n"));
+
+        assert_eq!("csv".parse::<ExportFormat>(), Ok(ExportFormat::Csv));
+        assert!("xml".parse::<ExportFormat>().is_err());
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("../../../fixtures/article_basic.sql"))]
+    async fn test_export_articles_zip(pool: PgPool) -> Result<(), anyhow::Error> {
+        let mut buffer = Cursor::new(Vec::new());
+        let count = export_articles_zip(None, &mut buffer, &pool).await?;
+
+        // link1のみ記事内容が存在する
+        assert_eq!(count, 1);
+        let archive = ZipArchive::new(buffer)?;
+        assert_eq!(archive.len(), 1);
+
+        println!("✅ 記事Zipエクスポートテスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+
+/// 言語の推定に使う本文の先頭の文字数（長い記事は先頭だけで判定する）
+const DETECTION_SAMPLE_CHARS: usize = 2000;
+
+/// # 概要
+/// 本文の言語を推定し、ISO 639-3の言語コード（`jpn`・`eng`など）を返す。
+///
+/// 推定の確度が低い場合（短い本文や複数の言語が同程度に混在する本文）は`None`を返す。
+pub fn detect_language(content: &str) -> Option<&'static str> {
+    let sample = match content.char_indices().nth(DETECTION_SAMPLE_CHARS) {
+        Some((end, _)) => &content[..end],
+        None => content,
+    };
+    let info = whatlang::detect(sample)?;
+    info.is_reliable().then(|| info.lang().code())
+}
+
+/// # 概要
+/// 言語が未判定の成功記事の言語を推定して保存する（列追加前のデータの補完用）。
+///
+/// # 戻り値
+/// 言語を設定した記事数
+pub async fn backfill_article_languages(pool: &PgPool) -> Result<u64> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT url, content FROM articles
+        WHERE status_code = 200 AND language IS NULL
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .context("言語が未判定の記事の取得に失敗")?;
+
+    let (urls, languages): (Vec<String>, Vec<String>) = rows
+        .into_iter()
+        .filter_map(|row| {
+            let language = detect_language(&row.content)?;
+            Some((row.url, language.to_string()))
+        })
+        .unzip();
+    if urls.is_empty() {
+        return Ok(0);
+    }
+
+    let result = sqlx::query!(
+        r#"
+        UPDATE articles AS a SET language = t.language
+        FROM UNNEST($1::text[], $2::text[]) AS t(url, language)
+        WHERE a.url = t.url
+        "#,
+        &urls,
+        &languages
+    )
+    .execute(pool)
+    .await
+    .context("記事の言語の保存に失敗")?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(
+            detect_language("今日は東京で新しい図書館が開館し、多くの市民が訪れました。"),
+            Some("jpn")
+        );
+        assert_eq!(
+            detect_language(
+                "The city council approved the new budget on Tuesday after a long debate \
+                 about public transport and housing."
+            ),
+            Some("eng")
+        );
+        assert_eq!(detect_language(""), None);
+        assert_eq!(detect_language("12345"), None);
+
+        println!("✅ 言語推定テスト成功");
+    }
+
+    #[sqlx::test]
+    async fn test_backfill_article_languages(pool: PgPool) -> Result<(), anyhow::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO articles (url, status_code, content) VALUES
+                ('https://example.com/ja', 200, '今日は東京で新しい図書館が開館し、多くの市民が訪れました。'),
+                ('https://example.com/error', 500, '取得エラー: タイムアウト')
+            "#
+        )
+        .execute(&pool)
+        .await?;
+
+        assert_eq!(backfill_article_languages(&pool).await?, 1);
+        let language = sqlx::query_scalar!(
+            "SELECT language FROM articles WHERE url = 'https://example.com/ja'"
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(language.as_deref(), Some("jpn"));
+        assert_eq!(
+            backfill_article_languages(&pool).await?,
+            0,
+            "判定済みは対象外"
+        );
+
+        println!("✅ 記事の言語の補完テスト成功");
+        Ok(())
+    }
+}
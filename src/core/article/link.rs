@@ -0,0 +1,296 @@
+use super::pipeline::{ContentStep, StepStats};
+use url::Url;
+
+/// リダイレクタを連続して展開する最大回数
+const MAX_REDIRECT_DEPTH: usize = 3;
+
+/// 除去対象のトラッキングパラメータ（完全一致）
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "dclid", "gbraid", "wbraid", "msclkid", "yclid", "mc_cid", "mc_eid",
+    "_hsenc", "_hsmi", "igshid", "mkt_tok", "ref_src",
+];
+
+/// 除去対象のトラッキングパラメータ（前方一致）
+const TRACKING_PARAM_PREFIXES: &[&str] = &["utm_"];
+
+/// 既知のリダイレクタ（ホスト, パスの前方一致, 遷移先URLを持つパラメータ）
+const REDIRECTORS: &[(&str, &str, &str)] = &[
+    ("www.google.com", "/url", "q"),
+    ("www.google.com", "/url", "url"),
+    ("l.facebook.com", "/l.php", "u"),
+    ("lm.facebook.com", "/l.php", "u"),
+    ("l.instagram.com", "/", "u"),
+    ("www.youtube.com", "/redirect", "q"),
+    ("out.reddit.com", "/", "url"),
+    ("click.linksynergy.com", "/deeplink", "murl"),
+    ("www.awin1.com", "/cread.php", "ued"),
+];
+
+// 既知のリダイレクタの定義
+#[derive(Debug, Clone)]
+pub struct Redirector {
+    pub host: String,
+    pub path_prefix: String,
+    /// 遷移先URLを持つクエリパラメータ名
+    pub target_param: String,
+}
+
+/// 本文中のリンクからアフィリエイト/トラッキング要素を除去するステップ
+///
+/// - 既知のリダイレクタ（Google, Facebookなど）を遷移先URLに展開する
+/// - `utm_*`などのトラッキングパラメータを除去する
+///
+/// 統計には`removed_param:<名前>`と`expanded_redirect:<ホスト>`の件数を記録する。
+#[derive(Debug, Clone)]
+pub struct TrackingLinkCleaner {
+    pub tracking_params: Vec<String>,
+    pub tracking_param_prefixes: Vec<String>,
+    pub redirectors: Vec<Redirector>,
+}
+
+impl Default for TrackingLinkCleaner {
+    fn default() -> Self {
+        Self {
+            tracking_params: TRACKING_PARAMS.iter().map(|p| p.to_string()).collect(),
+            tracking_param_prefixes: TRACKING_PARAM_PREFIXES
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            redirectors: REDIRECTORS
+                .iter()
+                .map(|(host, path_prefix, target_param)| Redirector {
+                    host: host.to_string(),
+                    path_prefix: path_prefix.to_string(),
+                    target_param: target_param.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TrackingLinkCleaner {
+    fn is_tracking_param(&self, name: &str) -> bool {
+        self.tracking_params.iter().any(|p| p == name)
+            || self
+                .tracking_param_prefixes
+                .iter()
+                .any(|prefix| name.starts_with(prefix.as_str()))
+    }
+
+    /// 既知のリダイレクタであれば遷移先URLを返す
+    fn expand_redirect(&self, url: &Url) -> Option<Url> {
+        let host = url.host_str()?;
+        let redirector = self.redirectors.iter().find(|r| {
+            r.host == host
+                && url.path().starts_with(r.path_prefix.as_str())
+                && url.query_pairs().any(|(key, _)| key == r.target_param)
+        })?;
+        let (_, target) = url
+            .query_pairs()
+            .find(|(key, _)| *key == redirector.target_param)?;
+        let target = Url::parse(&target).ok()?;
+        matches!(target.scheme(), "http" | "https").then_some(target)
+    }
+
+    /// 1つのURLを正規化する。変更がなければ`None`を返す。
+    pub fn clean_url(&self, raw: &str, stats: &mut StepStats) -> Option<String> {
+        let mut url = Url::parse(raw).ok()?;
+        let mut changed = false;
+
+        for _ in 0..MAX_REDIRECT_DEPTH {
+            let Some(target) = self.expand_redirect(&url) else {
+                break;
+            };
+            let key = format!("expanded_redirect:{}", url.host_str().unwrap_or_default());
+            *stats.entry(key).or_default() += 1;
+            url = target;
+            changed = true;
+        }
+
+        if url.query().is_some() {
+            let (removed, kept): (Vec<_>, Vec<_>) = url
+                .query_pairs()
+                .into_owned()
+                .partition(|(key, _)| self.is_tracking_param(key));
+            if !removed.is_empty() {
+                for (key, _) in &removed {
+                    *stats.entry(format!("removed_param:{}", key)).or_default() += 1;
+                }
+                if kept.is_empty() {
+                    url.set_query(None);
+                } else {
+                    url.query_pairs_mut().clear().extend_pairs(&kept);
+                }
+                changed = true;
+            }
+        }
+
+        changed.then(|| url.to_string())
+    }
+}
+
+impl ContentStep for TrackingLinkCleaner {
+    fn name(&self) -> &str {
+        "tracking_link_cleaner"
+    }
+
+    fn apply(&self, content: &str, stats: &mut StepStats) -> String {
+        let mut result = String::with_capacity(content.len());
+        let mut rest = content;
+
+        while let Some(start) = find_url_start(rest) {
+            result.push_str(&rest[..start]);
+            let candidate = &rest[start..];
+            let end = url_end(candidate);
+            let raw = &candidate[..end];
+
+            match self.clean_url(raw, stats) {
+                Some(cleaned) => result.push_str(&cleaned),
+                None => result.push_str(raw),
+            }
+            rest = &candidate[end..];
+        }
+        result.push_str(rest);
+
+        result
+    }
+}
+
+/// # 概要
+/// 記事URLを重複判定用に正規化する。
+///
+/// `TrackingLinkCleaner`によるリダイレクタの展開と`utm_*`などの除去に加えて、
+/// フラグメントとパス末尾の`/`（ルートを除く）を取り除く。
+/// 変更が無い場合や解析できない場合は前後の空白を除いた元の文字列を返す（何度適用しても同じ結果になる）。
+pub fn normalize_article_url(raw: &str) -> String {
+    let raw = raw.trim();
+    let cleaned = TrackingLinkCleaner::default().clean_url(raw, &mut StepStats::new());
+    let mut changed = cleaned.is_some();
+    let Ok(mut url) = Url::parse(cleaned.as_deref().unwrap_or(raw)) else {
+        return raw.to_string();
+    };
+
+    if url.fragment().is_some() {
+        url.set_fragment(None);
+        changed = true;
+    }
+    let path = url.path();
+    if path.len() > 1 && path.ends_with('/') {
+        let trimmed = path.trim_end_matches('/').to_string();
+        url.set_path(&trimmed);
+        changed = true;
+    }
+
+    if changed {
+        url.to_string()
+    } else {
+        raw.to_string()
+    }
+}
+
+/// 文字列中で最初に現れるhttp(s)のURLの開始位置
+pub(super) fn find_url_start(text: &str) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(pos) = text[offset..].find("http") {
+        let start = offset + pos;
+        let tail = &text[start..];
+        if tail.starts_with("https://") || tail.starts_with("http://") {
+            return Some(start);
+        }
+        offset = start + "http".len();
+    }
+    None
+}
+
+/// URL候補の終了位置（Markdownの区切り文字と末尾の句読点は含めない）
+pub(super) fn url_end(candidate: &str) -> usize {
+    let end = candidate
+        .find(|c: char| c.is_whitespace() || matches!(c, ')' | ']' | '>' | '<' | '"' | '\'' | '`'))
+        .unwrap_or(candidate.len());
+    candidate[..end]
+        .trim_end_matches(['.', ',', ';', ':', '!'])
+        .len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(content: &str) -> (String, StepStats) {
+        let mut stats = StepStats::new();
+        let result = TrackingLinkCleaner::default().apply(content, &mut stats);
+        (result, stats)
+    }
+
+    #[test]
+    fn test_remove_tracking_params() {
+        let (result, stats) = apply(
+            "[記事](https://example.com/a?id=1&utm_source=rss&utm_medium=feed&fbclid=abc) を参照",
+        );
+        assert_eq!(result, "[記事](https://example.com/a?id=1) を参照");
+        assert_eq!(stats["removed_param:utm_source"], 1);
+        assert_eq!(stats["removed_param:utm_medium"], 1);
+        assert_eq!(stats["removed_param:fbclid"], 1);
+
+        // 全パラメータが除去された場合はクエリごと消える
+        let (result, _) = apply("https://example.com/b?utm_campaign=x.");
+        assert_eq!(result, "https://example.com/b.");
+    }
+
+    #[test]
+    fn test_expand_redirectors() {
+        let (result, stats) = apply(
+            "<https://www.google.com/url?q=https%3A%2F%2Fnews.example.com%2Fp%3Futm_source%3Dg&sa=D>",
+        );
+        assert_eq!(result, "<https://news.example.com/p>");
+        assert_eq!(stats["expanded_redirect:www.google.com"], 1);
+        assert_eq!(stats["removed_param:utm_source"], 1);
+
+        // http(s)以外の遷移先は展開しない
+        let content = "https://l.facebook.com/l.php?u=javascript%3Aalert(1)";
+        let (result, stats) = apply(content);
+        assert_eq!(result, content);
+        assert!(stats.is_empty());
+    }
+
+    #[test]
+    fn test_untouched_links_keep_original_text() {
+        let content = "トップ https://example.com と http://example.com/path?a=1&b=2 はそのまま";
+        let (result, stats) = apply(content);
+        assert_eq!(result, content);
+        assert!(stats.is_empty());
+    }
+
+    #[test]
+    fn test_normalize_article_url() {
+        let cases = [
+            (
+                "https://example.com/news/1/?utm_source=rss&utm_medium=feed",
+                "https://example.com/news/1",
+            ),
+            (
+                "https://example.com/news/1#comments",
+                "https://example.com/news/1",
+            ),
+            (
+                "https://example.com/news/1?id=2&fbclid=x",
+                "https://example.com/news/1?id=2",
+            ),
+            // ルートの`/`と変更の無いURLはそのまま
+            ("https://example.com/", "https://example.com/"),
+            ("https://example.com", "https://example.com"),
+            (" https://example.com/news/1 ", "https://example.com/news/1"),
+            ("not a url", "not a url"),
+        ];
+        for (raw, expected) in cases {
+            let normalized = normalize_article_url(raw);
+            assert_eq!(normalized, expected, "{}", raw);
+            assert_eq!(
+                normalize_article_url(&normalized),
+                normalized,
+                "冪等: {}",
+                raw
+            );
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use super::pipeline::{ContentStep, StepStats};
+
+/// 品質フラグ: 閉じられていないコードフェンスがある
+pub const FLAG_UNCLOSED_CODE_FENCE: &str = "unclosed_code_fence";
+/// 品質フラグ: 表が崩れている（空白区切りに潰れた表・列数が揃わない表）
+pub const FLAG_BROKEN_TABLE: &str = "broken_table";
+
+/// 空白区切りに潰れた表とみなす連続行数の下限
+const MIN_WHITESPACE_TABLE_ROWS: usize = 3;
+
+/// 本文のコードブロックと表の崩れを修復するステップ
+///
+/// - 閉じられていないコードフェンスを本文末尾で閉じる
+/// - 区切り行（`|---|`）の無いパイプ表に区切り行を補う
+/// - 列数がヘッダより少ない行を空セルで埋める
+///
+/// 統計には修復した箇所の件数（`closed_code_fence`, `inserted_table_delimiter`,
+/// `padded_table_row`）を記録する。修復できない崩れは`quality_flags`で検出する。
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownStructureRepairer;
+
+impl ContentStep for MarkdownStructureRepairer {
+    fn name(&self) -> &str {
+        "markdown_structure"
+    }
+
+    fn apply(&self, content: &str, stats: &mut StepStats) -> String {
+        let mut lines: Vec<String> = Vec::new();
+        let mut fence: Option<String> = None;
+        let mut table: Vec<&str> = Vec::new();
+
+        for line in content.lines() {
+            if fence.is_none() && is_pipe_row(line) {
+                table.push(line);
+                continue;
+            }
+            if !table.is_empty() {
+                lines.extend(repair_table(&table, stats));
+                table.clear();
+            }
+            match &fence {
+                Some(open) if closes_fence(line, open) => fence = None,
+                Some(_) => {}
+                None => fence = opening_fence(line),
+            }
+            lines.push(line.to_string());
+        }
+        if !table.is_empty() {
+            lines.extend(repair_table(&table, stats));
+        }
+        if let Some(open) = fence {
+            lines.push(open);
+            *stats.entry("closed_code_fence".to_string()).or_default() += 1;
+        }
+
+        let mut repaired = lines.join("\n");
+        if content.ends_with('\n') {
+            repaired.push('\n');
+        }
+        repaired
+    }
+
+    fn quality_flags(&self, content: &str) -> Vec<String> {
+        markdown_quality_flags(content)
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// # 概要
+/// 本文のコードブロックと表の崩れを検出し、品質フラグを返す。
+///
+/// 検出する崩れ:
+/// - 閉じられていないコードフェンス
+/// - 列数がヘッダより多い行を含むパイプ表（どの列がずれたか判断できない）
+/// - 2つ以上の連続空白で区切られた、列数の揃った行の連続（パイプが失われて潰れた表）
+pub fn markdown_quality_flags(content: &str) -> Vec<&'static str> {
+    let mut flags = Vec::new();
+    let mut fence: Option<String> = None;
+    let mut table_columns: Option<usize> = None;
+    let mut broken_table = false;
+    let mut whitespace_run: (usize, usize) = (0, 0);
+
+    for line in content.lines() {
+        if let Some(open) = &fence {
+            if closes_fence(line, open) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(open) = opening_fence(line) {
+            fence = Some(open);
+            table_columns = None;
+            whitespace_run = (0, 0);
+            continue;
+        }
+
+        if is_pipe_row(line) {
+            let columns = split_cells(line).len();
+            match table_columns {
+                Some(header) if !is_delimiter_row(line) && columns > header => {
+                    broken_table = true;
+                }
+                Some(_) => {}
+                None => table_columns = Some(columns),
+            }
+            whitespace_run = (0, 0);
+            continue;
+        }
+        table_columns = None;
+
+        let columns = whitespace_columns(line);
+        whitespace_run = match whitespace_run {
+            (count, run_columns) if columns >= 2 && columns == run_columns => (count + 1, columns),
+            _ if columns >= 2 => (1, columns),
+            _ => (0, 0),
+        };
+        if whitespace_run.0 >= MIN_WHITESPACE_TABLE_ROWS {
+            broken_table = true;
+        }
+    }
+
+    if fence.is_some() {
+        flags.push(FLAG_UNCLOSED_CODE_FENCE);
+    }
+    if broken_table {
+        flags.push(FLAG_BROKEN_TABLE);
+    }
+    flags
+}
+
+/// コードフェンスの開始行であれば、閉じるのに使うフェンス文字列を返す
+fn opening_fence(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let length = trimmed.chars().take_while(|c| *c == marker).count();
+    (length >= 3).then(|| marker.to_string().repeat(length))
+}
+
+/// `open`で始まったコードフェンスを閉じる行かどうか
+fn closes_fence(line: &str, open: &str) -> bool {
+    let trimmed = line.trim();
+    let marker = open.chars().next().unwrap_or('`');
+    trimmed.len() >= open.len() && trimmed.chars().all(|c| c == marker)
+}
+
+fn is_pipe_row(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.starts_with('|') && trimmed.len() > 1
+}
+
+/// パイプ表の区切り行（`| --- | :---: |`）かどうか
+fn is_delimiter_row(line: &str) -> bool {
+    let cells = split_cells(line);
+    !cells.is_empty()
+        && cells.iter().all(|cell| {
+            let cell = cell.trim();
+            cell.contains('-') && cell.chars().all(|c| c == '-' || c == ':')
+        })
+}
+
+/// パイプ表の行をセルに分割する（エスケープされた`\|`は区切りとしない）
+fn split_cells(line: &str) -> Vec<String> {
+    let trimmed = line.trim();
+    let inner = trimmed.strip_prefix('|').unwrap_or(trimmed);
+    let inner = match inner.strip_suffix('|') {
+        Some(stripped) if !stripped.ends_with('\\') => stripped,
+        _ => inner,
+    };
+
+    let mut cells = vec![String::new()];
+    let mut escaped = false;
+    for c in inner.chars() {
+        if c == '|' && !escaped {
+            cells.push(String::new());
+        } else if let Some(cell) = cells.last_mut() {
+            cell.push(c);
+        }
+        escaped = c == '\\';
+    }
+    cells
+}
+
+/// 2つ以上の連続空白（またはタブ）で区切った列数（区切りが無ければ1）
+fn whitespace_columns(line: &str) -> usize {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return 0;
+    }
+    trimmed
+        .split('\t')
+        .flat_map(|part| part.split("  "))
+        .filter(|cell| !cell.trim().is_empty())
+        .count()
+}
+
+/// パイプ表の区切り行を補い、列数の足りない行を空セルで埋める
+fn repair_table(rows: &[&str], stats: &mut StepStats) -> Vec<String> {
+    // 1行だけの場合は表とみなさない
+    if rows.len() < 2 {
+        return rows.iter().map(|row| row.to_string()).collect();
+    }
+
+    let header_columns = split_cells(rows[0]).len();
+    let mut repaired = vec![rows[0].to_string()];
+    if !is_delimiter_row(rows[1]) {
+        repaired.push(format!("|{}", " --- |".repeat(header_columns)));
+        *stats
+            .entry("inserted_table_delimiter".to_string())
+            .or_default() += 1;
+    }
+    for row in &rows[1..] {
+        let cells = split_cells(row);
+        if is_delimiter_row(row) || cells.len() >= header_columns {
+            repaired.push(row.to_string());
+            continue;
+        }
+        let mut cells: Vec<String> = cells.iter().map(|cell| cell.trim().to_string()).collect();
+        cells.resize(header_columns, String::new());
+        repaired.push(format!("| {} |", cells.join(" | ")));
+        *stats.entry("padded_table_row".to_string()).or_default() += 1;
+    }
+    repaired
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repair(content: &str) -> (String, StepStats) {
+        let mut stats = StepStats::new();
+        let repaired = MarkdownStructureRepairer.apply(content, &mut stats);
+        (repaired, stats)
+    }
+
+    #[test]
+    fn test_close_unclosed_code_fence() {
+        let content = "# 使い方\n\n````rust\nfn main() {}\n```\n";
+        let (repaired, stats) = repair(content);
+
+        // 短いフェンスでは閉じられないため、開始と同じ長さのフェンスを補う
+        assert_eq!(repaired, "# 使い方\n\n````rust\nfn main() {}\n```\n````\n");
+        assert_eq!(stats["closed_code_fence"], 1);
+        assert!(markdown_quality_flags(content).contains(&FLAG_UNCLOSED_CODE_FENCE));
+        assert!(markdown_quality_flags(&repaired).is_empty());
+
+        // 閉じられているコードブロック内の表記号は修復しない
+        let (unchanged, stats) = repair("```\n| a | b |\n| 1 |\n```");
+        assert_eq!(unchanged, "```\n| a | b |\n| 1 |\n```");
+        assert!(stats.is_empty());
+    }
+
+    #[test]
+    fn test_repair_pipe_table() {
+        let content = "| 言語 | 年 |\n| Rust | 2015 |\n| Go |\n\n本文";
+        let (repaired, stats) = repair(content);
+
+        assert_eq!(
+            repaired,
+            "| 言語 | 年 |\n| --- | --- |\n| Rust | 2015 |\n| Go |  |\n\n本文"
+        );
+        assert_eq!(stats["inserted_table_delimiter"], 1);
+        assert_eq!(stats["padded_table_row"], 1);
+        assert!(markdown_quality_flags(&repaired).is_empty());
+    }
+
+    #[test]
+    fn test_detect_unrepairable_tables() {
+        // 列数がヘッダより多い行は修復できない
+        let extra_cells = "| a | b |\n| --- | --- |\n| 1 | 2 | 3 |";
+        let (repaired, _) = repair(extra_cells);
+        assert_eq!(markdown_quality_flags(&repaired), vec![FLAG_BROKEN_TABLE]);
+
+        // パイプが失われて連続空白で区切られた表
+        let whitespace_table = "名前    価格    在庫\nりんご  100  5\nみかん  80   12\n";
+        assert_eq!(
+            markdown_quality_flags(whitespace_table),
+            vec![FLAG_BROKEN_TABLE]
+        );
+
+        // 通常の文章やエスケープされたパイプは崩れとみなさない
+        assert!(markdown_quality_flags("文章です。  次の文。\n普通の段落\n").is_empty());
+        assert!(markdown_quality_flags("| a \\| b | c |\n| --- | --- |\n| 1 | 2 |").is_empty());
+    }
+}
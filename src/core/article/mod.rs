@@ -1,8 +1,60 @@
+pub mod cache;
+pub mod chunk;
+pub mod debug_log;
+pub mod embed;
+pub mod error_category;
+pub mod export;
+pub mod language;
+pub mod link;
+pub mod markdown;
 pub mod model;
+pub mod pipeline;
+pub mod query_limit;
+pub mod recrawl;
+pub mod reprocess;
 pub mod service;
+pub mod snippet;
+#[cfg(feature = "reader")]
+pub mod subscribe;
 
 // 公開APIの再エクスポート
 
+// cache.rsから
+pub use cache::{ArticleCacheConfig, ArticleQueryCache};
+
+// chunk.rsから
+pub use chunk::{estimate_tokens, get_article_chunks, split_article_chunks, Chunk, ChunkOptions};
+
+// debug_log.rsから
+pub use debug_log::{
+    get_article_debug_log, store_article_debug_logs, ArticleDebugConfig, ArticleDebugLog,
+};
+
+// embed.rsから
+pub use embed::{extract_embeds, get_embeds, ArticleEmbed, EmbedType};
+
+// error_category.rsから
+pub use error_category::{
+    aggregate_errors_by_category, backfill_error_categories, ErrorCategory, ErrorCategoryCount,
+};
+
+// export.rsから
+pub use export::{
+    export_articles, export_articles_zip, format_article_markdown, url_to_slug, write_articles,
+    write_articles_zip, ExportFormat,
+};
+
+// language.rsから
+pub use language::{backfill_article_languages, detect_language};
+
+// link.rsから
+pub use link::{normalize_article_url, Redirector, TrackingLinkCleaner};
+
+// markdown.rsから
+pub use markdown::{
+    markdown_quality_flags, MarkdownStructureRepairer, FLAG_BROKEN_TABLE, FLAG_UNCLOSED_CODE_FENCE,
+};
+
 // model.rsから
 pub use model::{
     count_articles_by_status, count_articles_metadata_by_status, filter_articles_by_status,
@@ -10,10 +62,42 @@ pub use model::{
     Article, ArticleMetadata, ArticleStatus,
 };
 
+// pipeline.rsから
+pub use pipeline::{ContentPipeline, ContentStep, PipelineReport, StepStats};
+
+// query_limit.rsから
+pub use query_limit::{
+    article_query_limits, set_article_query_limits, ArticleQueryLimits, DEFAULT_MAX_SEARCH_LIMIT,
+    DEFAULT_SEARCH_LIMIT,
+};
+
+// recrawl.rsから
+pub use recrawl::{mark_articles_recrawled, search_recrawl_targets, RecrawlPolicy};
+
+// reprocess.rsから
+pub use reprocess::{
+    schedule_article_reprocessing, FetchOutcome, ReprocessPolicy, ReprocessSummary,
+};
+
+// snippet.rsから
+pub use snippet::SnippetOptions;
+
+// subscribe.rsから
+#[cfg(feature = "reader")]
+pub use subscribe::{subscribe_new_articles, NEW_ARTICLES_CHANNEL};
+
 // repository.rsから（統合後）
+#[cfg(feature = "collector")]
 pub use service::{
     fetch_and_store_article, fetch_and_store_article_with_client, get_article_content,
-    get_article_content_with_client, search_article_contents, search_articles,
-    search_backlog_articles_light, store_article_content, ArticleContent, ArticleContentQuery,
-    ArticleQuery,
+    get_article_content_with_client, get_article_content_with_retry,
+};
+pub use service::{
+    get_article, get_article_raw_html, search_article_contents, search_articles,
+    search_articles_fulltext, search_articles_page, search_backlog_articles_light,
+    search_backlog_articles_light_with, store_article_content, store_article_content_with_conn,
+    store_article_contents, store_article_contents_batch, store_article_contents_with_conn,
+    stream_articles, ArticleContent, ArticleContentQuery, ArticleCursor, ArticleKey, ArticleQuery,
+    ArticleWriteBatchConfig, RetryPolicy, SearchArticlesPage, DEFAULT_PAGE_SIZE, ERROR_STATUS_CODE,
+    ROBOTS_DISALLOWED_STATUS_CODE,
 };
@@ -1,26 +1,44 @@
+use crate::core::schema::{rfc3339, rfc3339_option};
+use anyhow::Context;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
 
 // 軽量記事エンティティ（バックログ処理用、contentを除外）
+// JSONのフィールド名・日付形式は外部連携のスキーマ（`core::schema`）として固定する
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "snake_case")]
 pub struct ArticleMetadata {
     pub url: String,
     pub title: String,
+    #[serde(with = "rfc3339")]
     pub pub_date: DateTime<Utc>,
+    #[serde(default, with = "rfc3339_option")]
     pub updated_at: Option<DateTime<Utc>>,
     pub status_code: Option<i32>,
 }
 
 // 記事エンティティ（RSSリンクと記事内容の統合表現）
+// JSONのフィールド名・日付形式は外部連携のスキーマ（`core::schema`）として固定する
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "snake_case")]
 pub struct Article {
+    /// 記事リンクのID（ULID、URLが正規化・変更されても変わらない参照用のキー）
+    pub id: String,
     pub url: String,
     pub title: String,
+    #[serde(with = "rfc3339")]
     pub pub_date: DateTime<Utc>,
+    #[serde(default, with = "rfc3339_option")]
     pub updated_at: Option<DateTime<Utc>>,
     pub status_code: Option<i32>,
     pub content: Option<String>,
+    /// 全文検索でマッチした箇所周辺の抜粋（`ArticleQuery::snippet`を指定した検索のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub snippet: Option<String>,
 }
 
 // 記事の処理状態を表現するenum
@@ -32,6 +50,67 @@ pub enum ArticleStatus {
     Success,
     /// 記事の取得にエラーが発生（status_code != 200）
     Error(i32),
+    /// 4xxのエラー（検索条件用、`get_article_status`は返さない）
+    ClientError,
+    /// 5xxのエラー（検索条件用、`get_article_status`は返さない）
+    ServerError,
+    /// ステータスコードが範囲内（両端を含む）のエラー（検索条件用、`get_article_status`は返さない）
+    ErrorRange(i32, i32),
+}
+
+impl ArticleStatus {
+    /// エラーの条件が一致するステータスコードの範囲（両端を含む）
+    ///
+    /// `Unprocessed` / `Success`は`None`。範囲に200が含まれても成功した記事は一致しない。
+    pub fn error_code_range(&self) -> Option<RangeInclusive<i32>> {
+        match self {
+            Self::Unprocessed | Self::Success => None,
+            Self::Error(code) => Some(*code..=*code),
+            Self::ClientError => Some(400..=499),
+            Self::ServerError => Some(500..=599),
+            Self::ErrorRange(from, to) => Some(*from..=*to),
+        }
+    }
+
+    /// ステータスコード（未処理は`None`）が条件に一致するか
+    pub fn matches_status_code(&self, status_code: Option<i32>) -> bool {
+        match (self, status_code) {
+            (Self::Unprocessed, None) => true,
+            (Self::Success, Some(200)) => true,
+            (_, None) | (_, Some(200)) => false,
+            (status, Some(code)) => status
+                .error_code_range()
+                .is_some_and(|range| range.contains(&code)),
+        }
+    }
+}
+
+impl FromStr for ArticleStatus {
+    type Err = anyhow::Error;
+
+    /// `unprocessed` / `success` / `4xx` / `5xx` / `404` / `400-499`の形式を解釈する
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let s = s.trim();
+        let parse_code = |code: &str| {
+            code.trim()
+                .parse::<i32>()
+                .with_context(|| format!("不明な記事の処理状態: {}", s))
+        };
+        match s.to_lowercase().as_str() {
+            "unprocessed" => Ok(Self::Unprocessed),
+            "success" => Ok(Self::Success),
+            "4xx" => Ok(Self::ClientError),
+            "5xx" => Ok(Self::ServerError),
+            _ => match s.split_once('-') {
+                Some((from, to)) => {
+                    let (from, to) = (parse_code(from)?, parse_code(to)?);
+                    anyhow::ensure!(from <= to, "ステータスコードの範囲が不正です: {}", s);
+                    Ok(Self::ErrorRange(from, to))
+                }
+                None => Ok(Self::Error(parse_code(s)?)),
+            },
+        }
+    }
 }
 
 // 記事の処理状態を判定するメソッド
@@ -84,15 +163,7 @@ pub fn format_backlog_articles_metadata(articles: &[ArticleMetadata]) -> Vec<Str
 pub fn filter_articles_by_status(articles: &[Article], status: ArticleStatus) -> Vec<&Article> {
     articles
         .iter()
-        .filter(|article| match status {
-            ArticleStatus::Unprocessed => article.is_unprocessed(),
-            ArticleStatus::Success => {
-                matches!(article.get_article_status(), ArticleStatus::Success)
-            }
-            ArticleStatus::Error(code) => {
-                matches!(article.get_article_status(), ArticleStatus::Error(c) if c == code)
-            }
-        })
+        .filter(|article| status.matches_status_code(article.status_code))
         .collect()
 }
 
@@ -103,20 +174,7 @@ pub fn filter_articles_metadata_by_status(
 ) -> Vec<&ArticleMetadata> {
     articles
         .iter()
-        .filter(|article| {
-            let article_status = match article.status_code {
-                None => ArticleStatus::Unprocessed,
-                Some(200) => ArticleStatus::Success,
-                Some(code) => ArticleStatus::Error(code),
-            };
-            match status {
-                ArticleStatus::Unprocessed => matches!(article_status, ArticleStatus::Unprocessed),
-                ArticleStatus::Success => matches!(article_status, ArticleStatus::Success),
-                ArticleStatus::Error(code) => {
-                    matches!(article_status, ArticleStatus::Error(c) if c == code)
-                }
-            }
-        })
+        .filter(|article| status.matches_status_code(article.status_code))
         .collect()
 }
 
@@ -130,7 +188,7 @@ pub fn count_articles_by_status(articles: &[Article]) -> (usize, usize, usize) {
         match article.get_article_status() {
             ArticleStatus::Unprocessed => unprocessed += 1,
             ArticleStatus::Success => success += 1,
-            ArticleStatus::Error(_) => error += 1,
+            _ => error += 1,
         }
     }
 
@@ -168,12 +226,14 @@ mod tests {
         fn test_article_status_detection() {
             // 未処理リンクのテスト
             let unprocessed = Article {
+                id: String::new(),
                 url: "https://test.com/unprocessed".to_string(),
                 title: "未処理記事".to_string(),
                 pub_date: Utc::now(),
                 updated_at: None,
                 status_code: None,
                 content: None,
+                snippet: None,
             };
             assert!(matches!(
                 unprocessed.get_article_status(),
@@ -184,12 +244,14 @@ mod tests {
             assert!(unprocessed.is_backlog());
             // 成功記事のテスト
             let success = Article {
+                id: String::new(),
                 url: "https://test.com/success".to_string(),
                 title: "成功記事".to_string(),
                 pub_date: Utc::now(),
                 updated_at: Some(Utc::now()),
                 status_code: Some(200),
                 content: Some("記事内容".to_string()),
+                snippet: None,
             };
             assert!(matches!(
                 success.get_article_status(),
@@ -200,12 +262,14 @@ mod tests {
             assert!(!success.is_backlog());
             // エラー記事のテスト
             let error = Article {
+                id: String::new(),
                 url: "https://test.com/error".to_string(),
                 title: "エラー記事".to_string(),
                 pub_date: Utc::now(),
                 updated_at: Some(Utc::now()),
                 status_code: Some(404),
                 content: Some("エラー内容".to_string()),
+                snippet: None,
             };
             assert!(matches!(
                 error.get_article_status(),
@@ -223,12 +287,14 @@ mod tests {
         fn test_direct_field_access() {
             // 完全版記事のテスト
             let full_article = Article {
+                id: String::new(),
                 url: "https://test.com/full".to_string(),
                 title: "完全版記事".to_string(),
                 pub_date: Utc::now(),
                 updated_at: Some(Utc::now()),
                 status_code: Some(200),
                 content: Some("記事内容".to_string()),
+                snippet: None,
             };
             // 軽量版記事のテスト
             let light_article = ArticleMetadata {
@@ -259,20 +325,24 @@ mod tests {
         fn test_generic_functions() {
             let full_articles = vec![
                 Article {
+                    id: String::new(),
                     url: "https://test.com/success".to_string(),
                     title: "成功記事".to_string(),
                     pub_date: Utc::now(),
                     updated_at: Some(Utc::now()),
                     status_code: Some(200),
                     content: Some("成功内容".to_string()),
+                    snippet: None,
                 },
                 Article {
+                    id: String::new(),
                     url: "https://test.com/error".to_string(),
                     title: "エラー記事".to_string(),
                     pub_date: Utc::now(),
                     updated_at: Some(Utc::now()),
                     status_code: Some(404),
                     content: Some("エラー内容".to_string()),
+                    snippet: None,
                 },
             ];
 
@@ -310,6 +380,20 @@ mod tests {
                 filter_articles_metadata_by_status(&light_articles, ArticleStatus::Success);
             assert_eq!(success_light.len(), 1);
             assert_eq!(success_light[0].title, "成功軽量記事");
+
+            let client_errors =
+                filter_articles_by_status(&full_articles, ArticleStatus::ClientError);
+            assert_eq!(client_errors.len(), 1);
+            assert!(
+                filter_articles_by_status(&full_articles, ArticleStatus::ServerError).is_empty()
+            );
+            let in_range =
+                filter_articles_by_status(&full_articles, ArticleStatus::ErrorRange(100, 499));
+            assert_eq!(
+                in_range.len(),
+                1,
+                "範囲に200が含まれても成功記事は一致しない"
+            );
             // 統計計算のテスト
             let (unprocessed, success, error) = count_articles_by_status(&full_articles);
             assert_eq!((unprocessed, success, error), (0, 1, 1));
@@ -320,5 +404,17 @@ mod tests {
 
             println!("✅ 関数テスト成功");
         }
+
+        #[test]
+        fn test_parse_article_status() {
+            let parse = |s: &str| s.parse::<ArticleStatus>().unwrap().error_code_range();
+            assert_eq!(parse("4xx"), Some(400..=499));
+            assert_eq!(parse("5XX"), Some(500..=599));
+            assert_eq!(parse("404"), Some(404..=404));
+            assert_eq!(parse("500-503"), Some(500..=503));
+            assert_eq!(parse("success"), None);
+            assert!("503-500".parse::<ArticleStatus>().is_err());
+            assert!("error".parse::<ArticleStatus>().is_err());
+        }
     }
 }
@@ -0,0 +1,186 @@
+use super::link::TrackingLinkCleaner;
+use super::markdown::MarkdownStructureRepairer;
+use std::collections::BTreeMap;
+
+/// ステップごとの統計（項目名 -> 件数）
+pub type StepStats = BTreeMap<String, usize>;
+
+/// 記事本文（Markdown）に適用する加工ステップ
+pub trait ContentStep: Send + Sync {
+    /// レポートに表示するステップ名
+    fn name(&self) -> &str;
+
+    /// 本文を加工して返す。統計は`stats`に加算する。
+    fn apply(&self, content: &str, stats: &mut StepStats) -> String;
+
+    /// 全ステップ適用後の本文を検証し、修復できなかった問題の品質フラグを返す
+    fn quality_flags(&self, _content: &str) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// 全ステップ適用後の本文を保存対象から除外する場合、その理由を返す
+    fn exclusion_reason(&self, _content: &str) -> Option<String> {
+        None
+    }
+}
+
+/// 記事本文に加工ステップを順番に適用するパイプライン
+pub struct ContentPipeline {
+    steps: Vec<Box<dyn ContentStep>>,
+}
+
+impl Default for ContentPipeline {
+    /// 標準のステップ構成（トラッキングリンク除去 -> コードブロック・表の修復）
+    fn default() -> Self {
+        Self::new()
+            .with_step(TrackingLinkCleaner::default())
+            .with_step(MarkdownStructureRepairer)
+    }
+}
+
+impl ContentPipeline {
+    /// ステップを持たない空のパイプラインを作成
+    pub fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    /// ステップを末尾に追加する
+    pub fn with_step<S: ContentStep + 'static>(mut self, step: S) -> Self {
+        self.steps.push(Box::new(step));
+        self
+    }
+
+    /// 本文に全ステップを適用し、統計と検証結果を`report`に加算する
+    pub fn run(&self, content: &str, report: &mut PipelineReport) -> String {
+        let mut current = content.to_string();
+        for step in &self.steps {
+            let stats = report.steps.entry(step.name().to_string()).or_default();
+            current = step.apply(&current, stats);
+        }
+        for flag in self
+            .steps
+            .iter()
+            .flat_map(|step| step.quality_flags(&current))
+        {
+            *report.quality_flags.entry(flag).or_default() += 1;
+        }
+        report.processed += 1;
+        current
+    }
+
+    /// 全ステップ適用後の本文を保存対象から除外する理由（最初に除外を判定したステップのもの）
+    pub fn exclusion_reason(&self, content: &str) -> Option<String> {
+        self.steps
+            .iter()
+            .find_map(|step| step.exclusion_reason(content))
+    }
+}
+
+/// パイプラインの実行統計（複数記事分を集計できる）
+#[derive(Debug, Clone, Default)]
+pub struct PipelineReport {
+    /// パイプラインを通した記事数
+    pub processed: usize,
+    pub steps: BTreeMap<String, StepStats>,
+    /// 修復できなかった問題（品質フラグ -> 記事数）
+    pub quality_flags: BTreeMap<String, usize>,
+    /// 保存対象から除外した記事数
+    pub excluded: usize,
+}
+
+impl PipelineReport {
+    /// 別のレポートの統計を加算する
+    pub fn merge(&mut self, other: PipelineReport) {
+        self.processed += other.processed;
+        for (step, stats) in other.steps {
+            let merged = self.steps.entry(step).or_default();
+            for (item, count) in stats {
+                *merged.entry(item).or_default() += count;
+            }
+        }
+        for (flag, count) in other.quality_flags {
+            *self.quality_flags.entry(flag).or_default() += count;
+        }
+        self.excluded += other.excluded;
+    }
+
+    /// レポートを表示用の行に整形する
+    pub fn format_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("処理記事数: {}件", self.processed)];
+        for (step, stats) in &self.steps {
+            if stats.is_empty() {
+                lines.push(format!("[{}] 変更なし", step));
+                continue;
+            }
+            lines.push(format!("[{}]", step));
+            for (item, count) in stats {
+                lines.push(format!("  {}: {}件", item, count));
+            }
+        }
+        if !self.quality_flags.is_empty() {
+            lines.push("[品質フラグ]".to_string());
+            for (flag, count) in &self.quality_flags {
+                lines.push(format!("  {}: {}件", flag, count));
+            }
+        }
+        if self.excluded > 0 {
+            lines.push(format!("保存対象から除外: {}件", self.excluded));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct UppercaseStep;
+
+    impl ContentStep for UppercaseStep {
+        fn name(&self) -> &str {
+            "uppercase"
+        }
+
+        fn apply(&self, content: &str, stats: &mut StepStats) -> String {
+            *stats.entry("converted".to_string()).or_default() += 1;
+            content.to_uppercase()
+        }
+    }
+
+    #[test]
+    fn test_pipeline_runs_steps_and_collects_report() {
+        let pipeline = ContentPipeline::default().with_step(UppercaseStep);
+        let mut report = PipelineReport::default();
+
+        let content = "[link](https://example.com/a?utm_source=x&id=1)";
+        let result = pipeline.run(content, &mut report);
+        pipeline.run("plain", &mut report);
+
+        // ステップは定義順に適用される（リンク除去 -> 大文字化）
+        assert_eq!(result, "[LINK](HTTPS://EXAMPLE.COM/A?ID=1)");
+        assert_eq!(report.processed, 2);
+        assert_eq!(report.steps["uppercase"]["converted"], 2);
+
+        let lines = report.format_lines();
+        assert_eq!(lines[0], "処理記事数: 2件");
+        assert!(lines.iter().any(|line| line.contains("utm_source: 1件")));
+    }
+
+    #[test]
+    fn test_pipeline_reports_quality_flags() {
+        let pipeline = ContentPipeline::default();
+        let mut report = PipelineReport::default();
+
+        // 閉じていないコードフェンスは修復され、崩れた表は品質フラグとして報告される
+        let repaired = pipeline.run("```\nlet x = 1;", &mut report);
+        pipeline.run("| a | b |\n| --- | --- |\n| 1 | 2 | 3 |", &mut report);
+
+        assert_eq!(repaired, "```\nlet x = 1;\n```");
+        assert_eq!(report.steps["markdown_structure"]["closed_code_fence"], 1);
+        assert_eq!(report.quality_flags["broken_table"], 1);
+        assert!(!report.quality_flags.contains_key("unclosed_code_fence"));
+        assert!(report
+            .format_lines()
+            .contains(&"  broken_table: 1件".to_string()));
+    }
+}
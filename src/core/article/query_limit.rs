@@ -0,0 +1,125 @@
+use anyhow::{bail, Result};
+use serde::Deserialize;
+use std::sync::RwLock;
+
+/// `limit`を指定しない検索で返す件数の既定値
+pub const DEFAULT_SEARCH_LIMIT: i64 = 1000;
+
+/// 1回の検索で指定できる件数の上限の既定値
+pub const DEFAULT_MAX_SEARCH_LIMIT: i64 = 10_000;
+
+// 記事検索の件数の制限（config/app.yamlのarticle_queryに対応）
+//
+// limitの指定漏れで全件を読み込んでメモリを圧迫しないよう、検索系の関数は
+// 未指定なら`default_limit`件に絞り、`max_limit`を超える指定はエラーにする。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct ArticleQueryLimits {
+    /// `limit`未指定時に返す件数
+    #[serde(default = "default_limit")]
+    pub default_limit: i64,
+    /// 指定できる件数の上限（超える件数は`stream_articles`でページ毎に取得する）
+    #[serde(default = "default_max_limit")]
+    pub max_limit: i64,
+}
+
+impl Default for ArticleQueryLimits {
+    fn default() -> Self {
+        Self {
+            default_limit: default_limit(),
+            max_limit: default_max_limit(),
+        }
+    }
+}
+
+fn default_limit() -> i64 {
+    DEFAULT_SEARCH_LIMIT
+}
+
+fn default_max_limit() -> i64 {
+    DEFAULT_MAX_SEARCH_LIMIT
+}
+
+impl ArticleQueryLimits {
+    /// # 概要
+    /// 検索に使う件数を決める（未指定なら`default_limit`、ただし`max_limit`を超えない）。
+    ///
+    /// # エラー
+    /// `max_limit`を超える件数を指定した場合はエラー（ページ毎に取得するAPIを案内する）。
+    pub fn resolve(&self, requested: Option<i64>) -> Result<i64> {
+        match requested {
+            Some(limit) if limit > self.max_limit => bail!(
+                "検索件数の上限（{}件）を超えています: {}件。大量の記事はstream_articles（カーソルによるページ毎の取得）を使用してください",
+                self.max_limit,
+                limit
+            ),
+            Some(limit) => Ok(limit),
+            None => Ok(self.default_limit.min(self.max_limit)),
+        }
+    }
+
+    /// # 概要
+    /// `limit`未指定の検索が既定の件数で打ち切られた可能性がある場合に警告する。
+    pub fn warn_if_truncated(&self, requested: Option<i64>, returned: usize) {
+        let limit = self.default_limit.min(self.max_limit);
+        if requested.is_none() && returned as i64 >= limit {
+            tracing::warn!(
+                limit,
+                "limit未指定のため先頭の{}件のみ返しました（続きはstream_articlesで取得してください）",
+                limit
+            );
+        }
+    }
+}
+
+// set_article_query_limitsで登録された制限（未登録なら既定値を使う）
+static ARTICLE_QUERY_LIMITS: RwLock<Option<ArticleQueryLimits>> = RwLock::new(None);
+
+/// 記事検索の件数の制限を置き換える
+///
+/// アプリ起動時に設定ファイルの値を登録するために使う。
+pub fn set_article_query_limits(limits: ArticleQueryLimits) {
+    let mut guard = ARTICLE_QUERY_LIMITS
+        .write()
+        .unwrap_or_else(|e| e.into_inner());
+    *guard = Some(limits);
+}
+
+/// 登録済みの記事検索の件数の制限（未登録なら既定値）
+pub fn article_query_limits() -> ArticleQueryLimits {
+    let guard = ARTICLE_QUERY_LIMITS
+        .read()
+        .unwrap_or_else(|e| e.into_inner());
+    guard.unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_article_query_limit() {
+        let limits = ArticleQueryLimits {
+            default_limit: 50,
+            max_limit: 200,
+        };
+        assert_eq!(limits.resolve(None).unwrap(), 50);
+        assert_eq!(limits.resolve(Some(10)).unwrap(), 10);
+        assert_eq!(limits.resolve(Some(200)).unwrap(), 200);
+        let error = limits.resolve(Some(201)).unwrap_err().to_string();
+        assert!(error.contains("上限（200件）"));
+        assert!(error.contains("stream_articles"));
+
+        let inverted = ArticleQueryLimits {
+            default_limit: 500,
+            max_limit: 100,
+        };
+        assert_eq!(
+            inverted.resolve(None).unwrap(),
+            100,
+            "既定値も上限を超えない"
+        );
+        assert_eq!(ArticleQueryLimits::default().resolve(None).unwrap(), 1000);
+
+        println!("✅ 記事検索の件数制限テスト成功");
+    }
+}
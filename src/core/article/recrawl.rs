@@ -0,0 +1,227 @@
+use crate::core::rss::ArticleLink;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::time::Duration;
+
+// 取得済み記事の再クロールのポリシー（config/app.yamlのarticle_fetch.recrawlに対応）
+//
+// daemonモードで`interval_minutes`毎に、前回の取得（再クロール）から`stale_after_hours`以上経った
+// 公開から`max_article_age_days`以内の記事を最大`max_articles`件取得し直す。
+// 並列度とレート制限は通常のバックログ処理（article_fetch.max_concurrency / requests_per_minute）と別に設定する。
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecrawlPolicy {
+    /// daemonモードで再クロールのループを動かすかどうか
+    #[serde(default)]
+    pub enabled: bool,
+    /// 再クロールの実行間隔（分）
+    #[serde(default = "default_interval_minutes")]
+    pub interval_minutes: u64,
+    /// 1回に再クロールする記事数の上限
+    #[serde(default = "default_max_articles")]
+    pub max_articles: u32,
+    /// 同時に再クロールする記事数の上限
+    #[serde(default = "default_max_concurrency")]
+    pub max_concurrency: usize,
+    /// 再クロールでのスクレイパーへの1分あたりのリクエスト数の上限（省略時は制限しない）
+    #[serde(default)]
+    pub requests_per_minute: Option<u32>,
+    /// 前回の取得からこの時間（時間）以上経った記事を再クロールする
+    #[serde(default = "default_stale_after_hours")]
+    pub stale_after_hours: u32,
+    /// 公開からこの日数以内の記事だけを再クロールする
+    #[serde(default = "default_max_article_age_days")]
+    pub max_article_age_days: u32,
+}
+
+impl Default for RecrawlPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_minutes: default_interval_minutes(),
+            max_articles: default_max_articles(),
+            max_concurrency: default_max_concurrency(),
+            requests_per_minute: None,
+            stale_after_hours: default_stale_after_hours(),
+            max_article_age_days: default_max_article_age_days(),
+        }
+    }
+}
+
+fn default_interval_minutes() -> u64 {
+    60
+}
+
+fn default_max_articles() -> u32 {
+    100
+}
+
+fn default_max_concurrency() -> usize {
+    2
+}
+
+fn default_stale_after_hours() -> u32 {
+    24
+}
+
+fn default_max_article_age_days() -> u32 {
+    7
+}
+
+impl RecrawlPolicy {
+    /// 再クロールの実行間隔（1分未満にはしない）
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_minutes.max(1) * 60)
+    }
+}
+
+/// # 概要
+/// ポリシーに従って再クロールの対象にする記事のリンクを、前回の取得が古い順に返す。
+///
+/// 対象は本文を取得できた（ステータスコード200の）記事で、前回の再クロール
+/// （再クロールしていなければ本文の取得）から`stale_after_hours`以上経ち、
+/// 公開から`max_article_age_days`以内のもの。ゴミ箱に入っているリンクと取得対象外のリンクは除く。
+pub async fn search_recrawl_targets(
+    policy: &RecrawlPolicy,
+    pool: &PgPool,
+) -> Result<Vec<ArticleLink>> {
+    let links = sqlx::query_as!(
+        ArticleLink,
+        r#"
+        SELECT al.url, al.title, al.pub_date, al.source, al.description, al.author,
+            ARRAY(
+                SELECT ac.category FROM article_categories ac
+                WHERE ac.url = al.url ORDER BY ac.category
+            ) AS "categories!"
+        FROM article_links al
+        JOIN articles a ON al.url = a.url
+        WHERE a.status_code = 200
+            AND COALESCE(a.recrawled_at, a.timestamp) < now() - make_interval(hours => $1)
+            AND al.pub_date >= now() - make_interval(days => $2)
+            AND al.deleted_at IS NULL
+            AND al.fetch_target
+        ORDER BY COALESCE(a.recrawled_at, a.timestamp) ASC
+        LIMIT $3
+        "#,
+        policy.stale_after_hours as i32,
+        policy.max_article_age_days as i32,
+        i64::from(policy.max_articles)
+    )
+    .fetch_all(pool)
+    .await
+    .context("再クロール対象の記事の取得に失敗")?;
+
+    Ok(links)
+}
+
+/// 再クロールした記事の`recrawled_at`を現在日時にする（取得に失敗した記事も次の間隔まで対象から外す）
+pub async fn mark_articles_recrawled(urls: &[String], pool: &PgPool) -> Result<u64> {
+    if urls.is_empty() {
+        return Ok(0);
+    }
+    let result = sqlx::query!(
+        "UPDATE articles SET recrawled_at = now() WHERE url = ANY($1)",
+        urls
+    )
+    .execute(pool)
+    .await
+    .context("記事の再クロール日時の更新に失敗")?;
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::article::{store_article_contents, ArticleContent};
+    use crate::core::rss::store_article_links;
+    use chrono::{Duration as ChronoDuration, Utc};
+
+    #[test]
+    fn test_recrawl_policy_config() {
+        let policy: RecrawlPolicy =
+            serde_yaml::from_str("enabled: true\ninterval_minutes: 30\nmax_articles: 20").unwrap();
+        assert!(policy.enabled);
+        assert_eq!(policy.interval(), Duration::from_secs(30 * 60));
+        assert_eq!(policy.max_articles, 20);
+        assert_eq!(policy.max_concurrency, 2, "並列度は既定値");
+        assert!(!RecrawlPolicy::default().enabled);
+
+        println!("✅ 再クロールポリシーの設定テスト成功");
+    }
+
+    #[sqlx::test]
+    async fn test_search_recrawl_targets(pool: PgPool) -> Result<(), anyhow::Error> {
+        let now = Utc::now();
+        let links = vec![
+            ArticleLink {
+                url: "https://test.com/stale".to_string(),
+                title: "古い取得".to_string(),
+                pub_date: now - ChronoDuration::days(1),
+                source: "test".to_string(),
+                ..Default::default()
+            },
+            ArticleLink {
+                url: "https://test.com/fresh".to_string(),
+                title: "最近の取得".to_string(),
+                pub_date: now - ChronoDuration::days(1),
+                source: "test".to_string(),
+                ..Default::default()
+            },
+            ArticleLink {
+                url: "https://test.com/old-article".to_string(),
+                title: "公開が古い記事".to_string(),
+                pub_date: now - ChronoDuration::days(30),
+                source: "test".to_string(),
+                ..Default::default()
+            },
+            ArticleLink {
+                url: "https://test.com/error".to_string(),
+                title: "エラー記事".to_string(),
+                pub_date: now - ChronoDuration::days(1),
+                source: "test".to_string(),
+                ..Default::default()
+            },
+        ];
+        store_article_links(&links, &pool).await?;
+        let articles: Vec<ArticleContent> = links
+            .iter()
+            .map(|link| ArticleContent {
+                url: link.url.clone(),
+                timestamp: now,
+                status_code: if link.url.ends_with("error") {
+                    500
+                } else {
+                    200
+                },
+                content: format!("{}の本文", link.title),
+                raw_html: None,
+            })
+            .collect();
+        store_article_contents(&articles, &pool).await?;
+        sqlx::query!(
+            "UPDATE articles SET timestamp = now() - interval '2 days' WHERE url <> $1",
+            "https://test.com/fresh"
+        )
+        .execute(&pool)
+        .await?;
+
+        let policy = RecrawlPolicy::default();
+        let targets = search_recrawl_targets(&policy, &pool).await?;
+        let urls: Vec<&str> = targets.iter().map(|link| link.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec!["https://test.com/stale"],
+            "取得が古く公開が新しい成功記事のみ"
+        );
+
+        // 再クロールした記事は本文が変わらなくても次の間隔まで対象にしない
+        assert_eq!(
+            mark_articles_recrawled(&[urls[0].to_string()], &pool).await?,
+            1
+        );
+        assert!(search_recrawl_targets(&policy, &pool).await?.is_empty());
+
+        println!("✅ 再クロール対象の検索テスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,352 @@
+use super::error_category::ErrorCategory;
+use super::service::ArticleContent;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+// エラー記事の再処理ポリシー（config/app.yamlのarticle_fetch.reprocessに対応）
+//
+// 再処理の回数は`articles.retry_count`（連続して取得に失敗した回数）で数える。
+// 上限を超えた記事は`next_retry_at`をNULLにしてバックログから外す。
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReprocessPolicy {
+    /// 記事が見つからない（404・410）場合の再処理回数の上限
+    #[serde(default = "default_not_found_max_retries")]
+    pub not_found_max_retries: u32,
+    /// 記事が見つからない場合の再処理の間隔（秒）
+    #[serde(default = "default_not_found_interval_secs")]
+    pub not_found_interval_secs: u64,
+    /// サーバエラー（5xx）・一時的なエラーの再処理回数の上限
+    #[serde(default = "default_server_error_max_retries")]
+    pub server_error_max_retries: u32,
+    /// その他のエラー（アクセス拒否・有料記事・解析失敗など）の再処理回数の上限
+    #[serde(default = "default_other_max_retries")]
+    pub other_max_retries: u32,
+    /// 指数バックオフの初回の間隔（秒）
+    #[serde(default = "default_initial_interval_secs")]
+    pub initial_interval_secs: u64,
+    /// 指数バックオフの間隔の上限（秒）
+    #[serde(default = "default_max_interval_secs")]
+    pub max_interval_secs: u64,
+    /// 再処理毎に間隔を何倍にするか
+    #[serde(default = "default_multiplier")]
+    pub multiplier: f64,
+}
+
+impl Default for ReprocessPolicy {
+    fn default() -> Self {
+        Self {
+            not_found_max_retries: default_not_found_max_retries(),
+            not_found_interval_secs: default_not_found_interval_secs(),
+            server_error_max_retries: default_server_error_max_retries(),
+            other_max_retries: default_other_max_retries(),
+            initial_interval_secs: default_initial_interval_secs(),
+            max_interval_secs: default_max_interval_secs(),
+            multiplier: default_multiplier(),
+        }
+    }
+}
+
+fn default_not_found_max_retries() -> u32 {
+    3
+}
+
+fn default_not_found_interval_secs() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_server_error_max_retries() -> u32 {
+    8
+}
+
+fn default_other_max_retries() -> u32 {
+    3
+}
+
+fn default_initial_interval_secs() -> u64 {
+    10 * 60
+}
+
+fn default_max_interval_secs() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_multiplier() -> f64 {
+    2.0
+}
+
+impl ReprocessPolicy {
+    /// # 概要
+    /// 取得に失敗した記事を次に再処理するまでの間隔を返す。
+    ///
+    /// `retry_count`は今回を含めて連続して失敗した回数（1以上）。
+    /// 成功した記事や、再処理回数の上限を超えた記事は`None`。
+    /// 404・410（取得処理で500に丸められたエラーは本文から判定したカテゴリ）は一定間隔、
+    /// 5xx・一時的なエラーとその他のエラーは指数バックオフで間隔を空ける。
+    pub fn retry_delay(&self, outcome: &FetchOutcome, retry_count: u32) -> Option<Duration> {
+        let category = outcome.error_category?;
+        let retry_count = retry_count.max(1);
+        if category == ErrorCategory::NotFound {
+            return (retry_count <= self.not_found_max_retries)
+                .then(|| seconds(self.not_found_interval_secs));
+        }
+
+        let server_error = category.is_transient()
+            || (outcome.status_code >= 500 && category == ErrorCategory::Unknown);
+        let max_retries = if server_error {
+            self.server_error_max_retries
+        } else {
+            self.other_max_retries
+        };
+        (retry_count <= max_retries).then(|| self.backoff(retry_count - 1))
+    }
+
+    /// 指数バックオフの間隔（`retry`回目の再処理の前、上限で頭打ち）
+    fn backoff(&self, retry: u32) -> Duration {
+        let secs = self.initial_interval_secs as f64 * self.multiplier.max(1.0).powi(retry as i32);
+        seconds(secs.min(self.max_interval_secs as f64) as u64)
+    }
+}
+
+fn seconds(secs: u64) -> Duration {
+    Duration::seconds(secs.min(i64::MAX as u64) as i64)
+}
+
+// 再処理の予定を決めるための記事の取得結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchOutcome {
+    pub url: String,
+    pub status_code: i32,
+    /// 取得エラーの原因カテゴリ（成功した記事は`None`）
+    pub error_category: Option<ErrorCategory>,
+}
+
+impl From<&ArticleContent> for FetchOutcome {
+    fn from(article: &ArticleContent) -> Self {
+        Self {
+            url: article.url.clone(),
+            status_code: article.status_code,
+            error_category: article.error_category(),
+        }
+    }
+}
+
+// 再処理の予定の更新結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReprocessSummary {
+    /// 再処理を予定したエラー記事の数
+    pub scheduled: usize,
+    /// 再処理回数の上限を超えて打ち切ったエラー記事の数
+    pub exhausted: usize,
+}
+
+/// # 概要
+/// 取得結果に応じて記事の`retry_count`と`next_retry_at`を更新する。
+///
+/// 成功した記事は`retry_count`を0に戻し、失敗した記事は`retry_count`を1増やして
+/// ポリシーに従って次の再処理時刻を設定する（上限を超えた場合はNULL）。
+/// 記事が保存されていないURLは無視する。
+pub async fn schedule_article_reprocessing(
+    outcomes: &[FetchOutcome],
+    policy: &ReprocessPolicy,
+    pool: &PgPool,
+) -> Result<ReprocessSummary> {
+    let mut summary = ReprocessSummary::default();
+    if outcomes.is_empty() {
+        return Ok(summary);
+    }
+
+    let urls: Vec<String> = outcomes.iter().map(|o| o.url.clone()).collect();
+    let current: HashMap<String, i32> = sqlx::query!(
+        "SELECT url, retry_count FROM articles WHERE url = ANY($1)",
+        &urls
+    )
+    .fetch_all(pool)
+    .await
+    .context("記事の再処理回数の取得に失敗")?
+    .into_iter()
+    .map(|row| (row.url, row.retry_count))
+    .collect();
+
+    let now = Utc::now();
+    let mut retry_counts: Vec<i32> = Vec::with_capacity(outcomes.len());
+    let mut next_retry_ats: Vec<Option<DateTime<Utc>>> = Vec::with_capacity(outcomes.len());
+    for outcome in outcomes {
+        if outcome.error_category.is_none() {
+            retry_counts.push(0);
+            next_retry_ats.push(None);
+            continue;
+        }
+        let retry_count = current.get(&outcome.url).copied().unwrap_or(0) + 1;
+        let next_retry_at = policy
+            .retry_delay(outcome, retry_count as u32)
+            .map(|delay| now + delay);
+        if current.contains_key(&outcome.url) {
+            match next_retry_at {
+                Some(_) => summary.scheduled += 1,
+                None => summary.exhausted += 1,
+            }
+        }
+        retry_counts.push(retry_count);
+        next_retry_ats.push(next_retry_at);
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE articles a SET retry_count = t.retry_count, next_retry_at = t.next_retry_at
+        FROM UNNEST($1::text[], $2::int[], $3::timestamptz[]) AS t(url, retry_count, next_retry_at)
+        WHERE a.url = t.url
+        "#,
+        &urls,
+        &retry_counts,
+        &next_retry_ats as &[Option<DateTime<Utc>>]
+    )
+    .execute(pool)
+    .await
+    .context("記事の再処理予定の更新に失敗")?;
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::article::store_article_contents;
+    use crate::core::rss::{search_backlog_article_links, store_article_links, ArticleLink};
+
+    fn outcome(status_code: i32, content: &str) -> FetchOutcome {
+        FetchOutcome::from(&ArticleContent {
+            url: "https://test.com/a".to_string(),
+            timestamp: Utc::now(),
+            status_code,
+            content: content.to_string(),
+            raw_html: None,
+        })
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let policy = ReprocessPolicy::default();
+
+        // 404は一定間隔で3回まで
+        let not_found = outcome(404, "Not Found");
+        assert_eq!(policy.retry_delay(&not_found, 1), Some(Duration::days(1)));
+        assert_eq!(policy.retry_delay(&not_found, 3), Some(Duration::days(1)));
+        assert_eq!(policy.retry_delay(&not_found, 4), None);
+        // 500に丸められたエラーも本文で404と判定する
+        let not_found = outcome(500, "Firecrawl API エラー: 404 not found");
+        assert_eq!(policy.retry_delay(&not_found, 4), None);
+
+        // 5xxは指数バックオフ（上限で頭打ち）
+        let server_error = outcome(503, "Service Unavailable");
+        assert_eq!(
+            policy.retry_delay(&server_error, 1),
+            Some(Duration::minutes(10))
+        );
+        assert_eq!(
+            policy.retry_delay(&server_error, 3),
+            Some(Duration::minutes(40))
+        );
+        assert_eq!(
+            policy.retry_delay(&server_error, 8),
+            Some(Duration::minutes(10 * 128))
+        );
+        assert_eq!(policy.retry_delay(&server_error, 9), None);
+        let capped = ReprocessPolicy {
+            max_interval_secs: 30 * 60,
+            ..Default::default()
+        };
+        assert_eq!(
+            capped.retry_delay(&server_error, 3),
+            Some(Duration::minutes(30))
+        );
+
+        // アクセス拒否はその他のエラーとして扱う
+        let blocked = outcome(403, "Forbidden");
+        assert!(policy.retry_delay(&blocked, 3).is_some());
+        assert_eq!(policy.retry_delay(&blocked, 4), None);
+
+        assert_eq!(policy.retry_delay(&outcome(200, "本文"), 1), None);
+    }
+
+    #[sqlx::test]
+    async fn test_schedule_article_reprocessing(pool: PgPool) -> Result<(), anyhow::Error> {
+        let urls = ["https://test.com/404", "https://test.com/503"];
+        let links: Vec<ArticleLink> = urls
+            .iter()
+            .map(|url| ArticleLink {
+                url: url.to_string(),
+                title: "再処理テスト".to_string(),
+                pub_date: Utc::now(),
+                source: "test".to_string(),
+                ..Default::default()
+            })
+            .collect();
+        store_article_links(&links, &pool).await?;
+        let articles = vec![
+            ArticleContent {
+                status_code: 404,
+                ..ArticleContent::error_placeholder(urls[0], "Not Found")
+            },
+            ArticleContent {
+                status_code: 503,
+                ..ArticleContent::error_placeholder(urls[1], "Service Unavailable")
+            },
+        ];
+        store_article_contents(&articles, &pool).await?;
+        assert_eq!(
+            search_backlog_article_links(&pool).await?.len(),
+            2,
+            "予定を設定する前のエラー記事はすぐに再処理する"
+        );
+
+        let policy = ReprocessPolicy {
+            not_found_max_retries: 1,
+            ..Default::default()
+        };
+        let outcomes: Vec<FetchOutcome> = articles.iter().map(FetchOutcome::from).collect();
+        let summary = schedule_article_reprocessing(&outcomes, &policy, &pool).await?;
+        assert_eq!(
+            summary,
+            ReprocessSummary {
+                scheduled: 2,
+                exhausted: 0
+            }
+        );
+        assert!(
+            search_backlog_article_links(&pool).await?.is_empty(),
+            "再処理時刻までバックログに含めない"
+        );
+
+        // 2回目の失敗で404は打ち切り、5xxは再処理時刻を過ぎたら再処理する
+        let summary = schedule_article_reprocessing(&outcomes, &policy, &pool).await?;
+        assert_eq!(summary.exhausted, 1);
+        sqlx::query!("UPDATE articles SET next_retry_at = now() WHERE next_retry_at IS NOT NULL")
+            .execute(&pool)
+            .await?;
+        let backlog = search_backlog_article_links(&pool).await?;
+        assert_eq!(backlog.len(), 1);
+        assert_eq!(backlog[0].url, urls[1]);
+
+        // 成功すると再処理回数を戻す
+        let success = FetchOutcome {
+            url: urls[1].to_string(),
+            status_code: 200,
+            error_category: None,
+        };
+        schedule_article_reprocessing(&[success], &policy, &pool).await?;
+        let row = sqlx::query!(
+            "SELECT retry_count, next_retry_at FROM articles WHERE url = $1",
+            urls[1]
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(row.retry_count, 0);
+        assert!(row.next_retry_at.is_none());
+
+        println!("✅ エラー記事の再処理予定テスト成功");
+        Ok(())
+    }
+}
@@ -1,25 +1,368 @@
+use super::embed::record_article_embeds;
+use super::error_category::ErrorCategory;
+use super::language::detect_language;
+use super::markdown::markdown_quality_flags;
 use super::model::{Article, ArticleMetadata, ArticleStatus};
-use crate::infra::api::firecrawl::{FirecrawlClient, ReqwestFirecrawlClient};
-use anyhow::{Context, Result};
+use super::query_limit::article_query_limits;
+use super::snippet::SnippetOptions;
+#[cfg(feature = "collector")]
+use crate::core::blocklist::ensure_url_allowed;
+use crate::core::rss::update_placeholder_titles_with_executor;
+#[cfg(feature = "collector")]
+use crate::core::scraper::{ScrapeResult, ScraperClient};
+use crate::core::search_query::SearchQuery;
+#[cfg(feature = "collector")]
+use crate::infra::api::firecrawl::ReqwestFirecrawlClient;
+use crate::infra::compute::calc_hash;
+use anyhow::{bail, Context, Result};
 use chrono::{DateTime, Utc};
+#[cfg(feature = "collector")]
+use firecrawl_sdk::document::Document;
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, PgPool};
+use sqlx::{Connection, FromRow, PgConnection, PgPool};
+use std::time::Duration;
 
+/// 取得処理で記録するエラー用のステータスコード
+pub const ERROR_STATUS_CODE: i32 = 500;
+
+/// robots.txtで取得が禁止されていた記事のステータスコード
+pub const ROBOTS_DISALLOWED_STATUS_CODE: i32 = 999;
+
+// articlesテーブルの1行に対応する記事内容（保存・取得の両方で使う）
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct ArticleContent {
     pub url: String,
     pub timestamp: DateTime<Utc>,
     pub status_code: i32,
     pub content: String,
+    /// 記事ページの元のHTML（`scraper.raw_html`を有効にした場合のみ保存する。検索結果には含めない）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub raw_html: Option<String>,
 }
 
-#[derive(Debug, Default)]
+impl ArticleContent {
+    /// スクレイプ結果から保存用の記事内容を作成する
+    #[cfg(feature = "collector")]
+    pub fn from_scrape_result(url: &str, document: Document) -> Self {
+        Self::from_markdown(url, document.markdown)
+    }
+
+    /// スクレイパーが返したマークダウンから保存用の記事内容を作成する
+    pub fn from_markdown(url: &str, markdown: Option<String>) -> Self {
+        Self {
+            url: url.to_string(),
+            timestamp: Utc::now(),
+            status_code: 200,
+            content: markdown.unwrap_or_else(|| "記事内容が取得できませんでした".to_string()),
+            raw_html: None,
+        }
+    }
+
+    /// スクレイパーの取得結果（マークダウンと元のHTML）から保存用の記事内容を作成する
+    #[cfg(feature = "collector")]
+    pub fn from_scrape(url: &str, result: ScrapeResult) -> Self {
+        Self {
+            raw_html: result.html,
+            ..Self::from_markdown(url, result.markdown)
+        }
+    }
+
+    /// 取得に失敗した記事の記録用プレースホルダを作成する
+    ///
+    /// 失敗した記事もstatus_codeを記録しておくことで、バックログで再処理の対象にできる。
+    pub fn error_placeholder(url: &str, message: impl std::fmt::Display) -> Self {
+        Self {
+            url: url.to_string(),
+            timestamp: Utc::now(),
+            status_code: ERROR_STATUS_CODE,
+            content: message.to_string(),
+            raw_html: None,
+        }
+    }
+
+    /// robots.txtで取得が禁止されていた記事の記録用プレースホルダを作成する
+    pub fn robots_disallowed(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            timestamp: Utc::now(),
+            status_code: ROBOTS_DISALLOWED_STATUS_CODE,
+            content: "robots.txtで取得が禁止されています".to_string(),
+            raw_html: None,
+        }
+    }
+
+    /// 正常に取得できた記事かどうか
+    pub fn is_success(&self) -> bool {
+        self.status_code == 200
+    }
+
+    /// 取得エラーの原因カテゴリ（成功した記事は`None`）
+    pub fn error_category(&self) -> Option<ErrorCategory> {
+        ErrorCategory::classify(self.status_code, &self.content)
+    }
+
+    /// 本文の修復できなかった構造の崩れ（カンマ区切り、取得に失敗した記事や問題が無い本文は`None`）
+    pub fn quality_flags(&self) -> Option<String> {
+        if !self.is_success() {
+            return None;
+        }
+        let flags = markdown_quality_flags(&self.content);
+        (!flags.is_empty()).then(|| flags.join(","))
+    }
+
+    /// 本文から推定した言語（ISO 639-3、取得に失敗した記事や推定できない本文は`None`）
+    pub fn language(&self) -> Option<&'static str> {
+        if !self.is_success() {
+            return None;
+        }
+        detect_language(&self.content)
+    }
+
+    /// 重複記事の検出に使う本文のSHA-256（取得に失敗した記事や本文が空の記事は`None`）
+    pub fn content_hash(&self) -> Option<String> {
+        (self.is_success() && !self.content.is_empty()).then(|| calc_hash(&self.content, 64))
+    }
+
+    /// 本文の最初の見出し（H1）を記事タイトルとして取り出す
+    ///
+    /// 取得に失敗した記事や、H1が無い本文では`None`を返す。
+    pub fn extract_title(&self) -> Option<String> {
+        if !self.is_success() {
+            return None;
+        }
+        extract_markdown_h1(&self.content)
+    }
+}
+
+/// Markdownから最初のH1（`# 見出し`または`===`の下線付き見出し）を取り出す
+///
+/// コードブロック内の行は見出しとして扱わない。
+fn extract_markdown_h1(markdown: &str) -> Option<String> {
+    let mut in_code_block = false;
+    let mut previous: Option<&str> = None;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            previous = None;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        if let Some(heading) = trimmed.strip_prefix("# ") {
+            let title = heading.trim().trim_end_matches('#').trim();
+            if !title.is_empty() {
+                return Some(title.to_string());
+            }
+        }
+        if !trimmed.is_empty() && trimmed.chars().all(|c| c == '=') {
+            if let Some(title) = previous.filter(|p| !p.is_empty()) {
+                return Some(title.to_string());
+            }
+        }
+        previous = Some(trimmed);
+    }
+    None
+}
+
+/// 保存した記事の本文から、仮タイトルのままのリンクのタイトルを補完する
+///
+/// 補完は付随処理のため、失敗しても記事の保存自体は成功として扱う。
+/// 呼び出し側のトランザクションを中断させないよう、セーブポイント内で実行する。
+async fn fill_placeholder_titles(articles: &[&ArticleContent], conn: &mut PgConnection) {
+    let titles: Vec<(String, String)> = articles
+        .iter()
+        .filter_map(|article| Some((article.url.clone(), article.extract_title()?)))
+        .collect();
+    if titles.is_empty() {
+        return;
+    }
+
+    let result: Result<()> = async {
+        let mut savepoint = conn.begin().await?;
+        update_placeholder_titles_with_executor(&titles, &mut *savepoint).await?;
+        savepoint.commit().await?;
+        Ok(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(error = format!("{:#}", e), "タイトル補完エラー");
+    }
+}
+
+/// 本文のハッシュが同じ記事を、先に保存された記事の重複として記録する
+///
+/// クエリパラメータ違いのURLなどで同じ記事が別の行に保存された場合に、`duplicate_of`に元の記事のURLを残す。
+/// 本文が変わって重複でなくなった記事は`duplicate_of`を消す。
+/// 記録は付随処理のため、失敗しても記事の保存自体は成功として扱う。
+async fn mark_duplicate_articles(urls: &[String], conn: &mut PgConnection) {
+    let result: Result<Vec<(String, String)>> = async {
+        let mut savepoint = conn.begin().await?;
+        let rows = sqlx::query!(
+            r#"
+            UPDATE articles a SET duplicate_of = (
+                SELECT o.url FROM articles o
+                WHERE o.content_hash = a.content_hash
+                    AND o.deleted_at IS NULL
+                    AND (o.timestamp, o.url) < (a.timestamp, a.url)
+                ORDER BY o.timestamp, o.url
+                LIMIT 1
+            )
+            WHERE a.url = ANY($1)
+            RETURNING a.url, a.duplicate_of
+            "#,
+            urls
+        )
+        .fetch_all(&mut *savepoint)
+        .await?;
+        savepoint.commit().await?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| Some((row.url, row.duplicate_of?)))
+            .collect())
+    }
+    .await;
+
+    match result {
+        Ok(duplicates) => {
+            for (url, original) in duplicates {
+                tracing::info!(url, duplicate_of = original, "重複記事を検出");
+            }
+        }
+        Err(e) => tracing::warn!(error = format!("{:#}", e), "重複記事の検出エラー"),
+    }
+}
+
+/// 記事取得の試行履歴を記録する（本文は変更検出用のハッシュのみ保存）
+///
+/// 履歴は付随情報のため、失敗しても記事の保存自体は成功として扱う。
+/// 呼び出し側のトランザクションを中断させないよう、セーブポイント内で実行する。
+async fn record_fetch_attempts(articles: &[ArticleContent], conn: &mut PgConnection) {
+    let urls: Vec<String> = articles.iter().map(|a| a.url.clone()).collect();
+    let status_codes: Vec<i32> = articles.iter().map(|a| a.status_code).collect();
+    let contents: Vec<String> = articles.iter().map(|a| a.content.clone()).collect();
+
+    let result: Result<()> = async {
+        let mut savepoint = conn.begin().await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO article_fetch_attempts (url, status_code, content_hash)
+            SELECT url, status_code, md5(content)
+            FROM UNNEST($1::text[], $2::int[], $3::text[]) AS t(url, status_code, content)
+            "#,
+            &urls,
+            &status_codes,
+            &contents
+        )
+        .execute(&mut *savepoint)
+        .await?;
+        savepoint.commit().await?;
+        Ok(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(error = format!("{:#}", e), "取得履歴の記録エラー");
+    }
+}
+
+/// 記事の検索条件
+///
+/// 保存済みクエリとしてJSONに保存できる（省略したフィールドは既定値）。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ArticleQuery {
+    /// 記事リンクのID（ULID）の完全一致
+    pub id: Option<String>,
+    /// URLの完全一致
+    pub url: Option<String>,
     pub link_pattern: Option<String>,
+    /// タイトルの検索条件（`SearchQuery`の簡易クエリ構文）
+    pub title_pattern: Option<String>,
+    /// 本文の検索条件（`SearchQuery`の簡易クエリ構文）
+    pub content_query: Option<String>,
+    /// 本文の全文検索の条件（`websearch_to_tsquery`の構文、`search_articles_fulltext`を参照）
+    pub full_text: Option<String>,
     pub pub_date_from: Option<DateTime<Utc>>,
     pub pub_date_to: Option<DateTime<Utc>>,
+    /// 記事本文を取得・更新した日時（`Article::updated_at`）がこの日時以降の記事のみ
+    pub updated_from: Option<DateTime<Utc>>,
     pub article_status: Option<ArticleStatus>,
+    /// 本文から推定した言語（ISO 639-3、例: `jpn`・`eng`）の完全一致
+    pub language: Option<String>,
+    /// 取得する最大件数（未指定は`article_query.default_limit`、`article_query.max_limit`を超えるとエラー）
     pub limit: Option<i64>,
+    /// 先頭から読み飛ばす件数（offsetによるページ送り）
+    pub offset: Option<i64>,
+    /// 指定したカーソルより後の記事から取得する（keysetによるページ送り）
+    pub after: Option<ArticleCursor>,
+    /// ゴミ箱に入っている（soft delete済みの）記事も含めるかどうか
+    pub include_deleted: bool,
+    /// 各記事に全文検索でマッチした箇所周辺の抜粋（`Article::snippet`）を付ける（`full_text`の指定が必要）
+    pub snippet: Option<SnippetOptions>,
+}
+
+/// `search_articles_page`で1ページに返す件数の既定値（`limit`未指定時）
+pub const DEFAULT_PAGE_SIZE: i64 = 100;
+
+/// keysetによるページ送りのカーソル（記事の並び順である`pub_date`・`url`の降順の位置）
+///
+/// 文字列では`<pub_dateのRFC 3339>|<url>`の形式で表す（CLIなどで次ページの指定に使う）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArticleCursor {
+    pub pub_date: DateTime<Utc>,
+    pub url: String,
+}
+
+impl From<&Article> for ArticleCursor {
+    fn from(article: &Article) -> Self {
+        Self {
+            pub_date: article.pub_date,
+            url: article.url.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for ArticleCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}",
+            self.pub_date
+                .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+            self.url
+        )
+    }
+}
+
+impl std::str::FromStr for ArticleCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (pub_date, url) = s
+            .split_once('|')
+            .with_context(|| format!("カーソルの形式が不正です: {}", s))?;
+        let pub_date = DateTime::parse_from_rfc3339(pub_date)
+            .with_context(|| format!("カーソルの日時が不正です: {}", pub_date))?
+            .with_timezone(&Utc);
+        Ok(Self {
+            pub_date,
+            url: url.to_string(),
+        })
+    }
+}
+
+// search_articles_pageの1ページ分の結果
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchArticlesPage {
+    pub articles: Vec<Article>,
+    /// 次のページを取得するカーソル（最後のページでは`None`）
+    pub next_cursor: Option<ArticleCursor>,
 }
 
 #[derive(Debug, Default)]
@@ -28,90 +371,383 @@ pub struct ArticleContentQuery {
     pub timestamp_from: Option<DateTime<Utc>>,
     pub timestamp_to: Option<DateTime<Utc>>,
     pub status_code: Option<i32>,
+    /// 本文から推定した言語（ISO 639-3、例: `jpn`・`eng`）の完全一致
+    pub language: Option<String>,
+    /// 取得する最大件数（未指定は`article_query.default_limit`、`article_query.max_limit`を超えるとエラー）
+    pub limit: Option<i64>,
+    /// ゴミ箱に入っている（soft delete済みの）記事も含めるかどうか
+    pub include_deleted: bool,
 }
 
 /// URLから記事内容を取得してArticleContent構造体に変換する（Firecrawl SDK使用）
+#[cfg(feature = "collector")]
 pub async fn get_article_content(url: &str) -> Result<ArticleContent> {
     let client =
         ReqwestFirecrawlClient::new().context("実際のFirecrawlクライアントの初期化に失敗")?;
     get_article_content_with_client(url, &client).await
 }
 
-/// 指定されたFirecrawlクライアントを使用して記事内容を取得
+/// 指定されたスクレイパーを使用して記事内容を取得
 ///
 /// この関数は依存注入をサポートし、テスト時にモッククライアントを
 /// 注入することでFirecrawl APIへの実際の通信を避けることができます。
+/// 取得に失敗した場合はバックエンド名を付けたエラーメッセージのプレースホルダを返す。
+#[cfg(feature = "collector")]
 pub async fn get_article_content_with_client(
     url: &str,
-    client: &dyn FirecrawlClient,
+    client: &dyn ScraperClient,
 ) -> Result<ArticleContent> {
-    match client.scrape_url(url).await {
-        Ok(result) => Ok(ArticleContent {
-            url: url.to_string(),
-            timestamp: chrono::Utc::now(),
-            status_code: 200,
-            content: result
-                .markdown
-                .unwrap_or_else(|| "記事内容が取得できませんでした".to_string()),
-        }),
-        Err(e) => Ok(ArticleContent {
-            url: url.to_string(),
-            timestamp: chrono::Utc::now(),
-            status_code: 500,
-            content: format!("Firecrawl API エラー: {}", e),
-        }),
+    match client.scrape(url).await {
+        Ok(result) => Ok(ArticleContent::from_scrape(url, result)),
+        Err(e) => Ok(ArticleContent::error_placeholder(
+            url,
+            format!("{} エラー: {}", client.backend_name_for(url), e),
+        )),
+    }
+}
+
+// 取得した記事本文の一括保存の設定（config/app.yamlのarticle_fetch.write_batchに対応）
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArticleWriteBatchConfig {
+    /// 1回にまとめて保存する記事数（この件数に達したら保存する）
+    #[serde(default = "default_write_batch_size")]
+    pub batch_size: usize,
+    /// 件数に達しなくても保存する間隔（ミリ秒）
+    #[serde(default = "default_write_flush_interval_ms")]
+    pub flush_interval_ms: u64,
+}
+
+impl Default for ArticleWriteBatchConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: default_write_batch_size(),
+            flush_interval_ms: default_write_flush_interval_ms(),
+        }
+    }
+}
+
+fn default_write_batch_size() -> usize {
+    50
+}
+
+fn default_write_flush_interval_ms() -> u64 {
+    5000
+}
+
+// 記事取得のリトライ設定（config/app.yamlのarticle_fetch.retryに対応）
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetryPolicy {
+    /// 一時的なエラーで再試行する最大回数（0なら再試行しない）
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// 1回目の再試行までの待ち時間（ミリ秒）
+    #[serde(default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    /// 待ち時間の上限（ミリ秒）
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    /// 再試行毎に待ち時間に掛ける倍率
+    #[serde(default = "default_backoff_multiplier")]
+    pub multiplier: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: default_max_retries(),
+            initial_backoff_ms: default_initial_backoff_ms(),
+            max_backoff_ms: default_max_backoff_ms(),
+            multiplier: default_backoff_multiplier(),
+        }
+    }
+}
+
+fn default_max_retries() -> u32 {
+    2
+}
+
+fn default_initial_backoff_ms() -> u64 {
+    500
+}
+
+fn default_max_backoff_ms() -> u64 {
+    10_000
+}
+
+fn default_backoff_multiplier() -> f64 {
+    2.0
+}
+
+impl RetryPolicy {
+    /// 再試行しない設定
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// `retry`回目（0始まり）の再試行までの待ち時間（指数バックオフ、上限あり）
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let millis = self.initial_backoff_ms as f64 * self.multiplier.max(1.0).powi(retry as i32);
+        Duration::from_millis(millis.min(self.max_backoff_ms as f64) as u64)
+    }
+}
+
+/// # 概要
+/// 一時的なエラー（`ErrorCategory::is_transient`）の場合に指数バックオフで再試行しながら記事内容を取得する。
+///
+/// 再試行しても失敗した場合や、恒久的なエラー（404など）の場合は最後の結果をそのまま返す。
+#[cfg(feature = "collector")]
+pub async fn get_article_content_with_retry(
+    url: &str,
+    client: &dyn ScraperClient,
+    policy: &RetryPolicy,
+) -> Result<ArticleContent> {
+    let mut retry = 0;
+    loop {
+        let article = get_article_content_with_client(url, client).await?;
+        let category = article.error_category();
+        let transient = category.is_some_and(|category| category.is_transient());
+        if !transient || retry >= policy.max_retries {
+            return Ok(article);
+        }
+
+        let backoff = policy.backoff(retry);
+        retry += 1;
+        tracing::warn!(
+            url,
+            retry,
+            backoff_ms = backoff.as_millis() as u64,
+            category = ?category,
+            "記事取得をリトライ"
+        );
+        tokio::time::sleep(backoff).await;
     }
 }
 
 /// 記事内容をデータベースに保存する。
 /// 重複した場合には更新を行う。
 pub async fn store_article_content(article: &ArticleContent, pool: &PgPool) -> Result<()> {
+    let mut conn = pool
+        .acquire()
+        .await
+        .context("データベース接続の取得に失敗しました")?;
+    store_article_content_with_conn(article, &mut conn).await
+}
+
+/// 指定した接続で記事内容を保存する（取得履歴の記録・タイトル補完を含む）。
+///
+/// トランザクション（`&mut *tx`）を渡すと、リンクの保存などと同じトランザクションで
+/// 保存でき、ロールバックすると付随処理も含めて取り消される。
+#[tracing::instrument(skip_all, fields(url = %article.url))]
+pub async fn store_article_content_with_conn(
+    article: &ArticleContent,
+    conn: &mut PgConnection,
+) -> Result<()> {
     sqlx::query!(
         r#"
-        INSERT INTO articles (url, status_code, content)
-        VALUES ($1, $2, $3)
+        INSERT INTO articles (url, status_code, content, error_category, quality_flags, content_hash, language, raw_html)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
         ON CONFLICT (url) DO UPDATE SET 
             status_code = EXCLUDED.status_code,
             content = EXCLUDED.content,
+            error_category = EXCLUDED.error_category,
+            quality_flags = EXCLUDED.quality_flags,
+            content_hash = EXCLUDED.content_hash,
+            language = EXCLUDED.language,
+            raw_html = EXCLUDED.raw_html,
             timestamp = CURRENT_TIMESTAMP
         WHERE (articles.status_code, articles.content)
             IS DISTINCT FROM (EXCLUDED.status_code, EXCLUDED.content)
         "#,
         article.url,
         article.status_code,
-        article.content
+        article.content,
+        article.error_category().map(|category| category.as_str()),
+        article.quality_flags(),
+        article.content_hash(),
+        article.language(),
+        article.raw_html
     )
-    .execute(pool)
+    .execute(&mut *conn)
     .await
     .context("Firecrawl記事のデータベースへの挿入に失敗しました")?;
 
+    record_fetch_attempts(std::slice::from_ref(article), conn).await;
+    mark_duplicate_articles(std::slice::from_ref(&article.url), conn).await;
+    record_article_embeds(&[article], conn).await;
+    fill_placeholder_titles(&[article], conn).await;
     Ok(())
 }
 
+/// 複数の記事内容を1クエリでまとめてデータベースに保存する。
+/// 重複した場合には更新を行い、同じURLが複数含まれる場合は後のものを優先する。
+pub async fn store_article_contents(articles: &[ArticleContent], pool: &PgPool) -> Result<()> {
+    if articles.is_empty() {
+        return Ok(());
+    }
+
+    let mut conn = pool
+        .acquire()
+        .await
+        .context("データベース接続の取得に失敗しました")?;
+    store_article_contents_with_conn(articles, &mut conn).await
+}
+
+/// 指定した接続で複数の記事内容をまとめて保存する（トランザクション内での利用向け）
+#[tracing::instrument(skip_all, fields(count = articles.len()))]
+pub async fn store_article_contents_with_conn(
+    articles: &[ArticleContent],
+    conn: &mut PgConnection,
+) -> Result<()> {
+    if articles.is_empty() {
+        return Ok(());
+    }
+
+    // 同一クエリ内で同じ行を2回更新できないため、URL毎に最後の1件に絞る
+    let mut latest: Vec<&ArticleContent> = Vec::with_capacity(articles.len());
+    for article in articles.iter().rev() {
+        if !latest.iter().any(|a| a.url == article.url) {
+            latest.push(article);
+        }
+    }
+
+    let urls: Vec<String> = latest.iter().map(|a| a.url.clone()).collect();
+    let status_codes: Vec<i32> = latest.iter().map(|a| a.status_code).collect();
+    let contents: Vec<String> = latest.iter().map(|a| a.content.clone()).collect();
+    let error_categories: Vec<Option<String>> = latest
+        .iter()
+        .map(|a| a.error_category().map(|category| category.to_string()))
+        .collect();
+    let quality_flags: Vec<Option<String>> = latest.iter().map(|a| a.quality_flags()).collect();
+    let content_hashes: Vec<Option<String>> = latest.iter().map(|a| a.content_hash()).collect();
+    let languages: Vec<Option<String>> = latest
+        .iter()
+        .map(|a| a.language().map(str::to_string))
+        .collect();
+    let raw_htmls: Vec<Option<String>> = latest.iter().map(|a| a.raw_html.clone()).collect();
+
+    sqlx::query!(
+        r#"
+        INSERT INTO articles (url, status_code, content, error_category, quality_flags, content_hash, language, raw_html)
+        SELECT * FROM UNNEST(
+            $1::text[], $2::int[], $3::text[], $4::text[], $5::text[], $6::text[], $7::text[], $8::text[]
+        )
+        ON CONFLICT (url) DO UPDATE SET
+            status_code = EXCLUDED.status_code,
+            content = EXCLUDED.content,
+            error_category = EXCLUDED.error_category,
+            quality_flags = EXCLUDED.quality_flags,
+            content_hash = EXCLUDED.content_hash,
+            language = EXCLUDED.language,
+            raw_html = EXCLUDED.raw_html,
+            timestamp = CURRENT_TIMESTAMP
+        WHERE (articles.status_code, articles.content)
+            IS DISTINCT FROM (EXCLUDED.status_code, EXCLUDED.content)
+        "#,
+        &urls,
+        &status_codes,
+        &contents,
+        &error_categories as &[Option<String>],
+        &quality_flags as &[Option<String>],
+        &content_hashes as &[Option<String>],
+        &languages as &[Option<String>],
+        &raw_htmls as &[Option<String>]
+    )
+    .execute(&mut *conn)
+    .await
+    .context("記事のデータベースへの一括挿入に失敗しました")?;
+
+    record_fetch_attempts(articles, conn).await;
+    mark_duplicate_articles(&urls, conn).await;
+    record_article_embeds(&latest, conn).await;
+    fill_placeholder_titles(&latest, conn).await;
+    Ok(())
+}
+
+/// # 概要
+/// 大量の記事内容を`batch_size`件ずつ1クエリのUPSERT（`store_article_contents_with_conn`）で保存する。
+///
+/// 接続は全体で1つを使い回し、バッチ毎に1回のround-tripで書き込む。
+/// バッチを跨いで同じURLが含まれる場合も後のものを優先する。
+/// 途中のバッチで失敗した場合、それまでのバッチは保存したままエラーを返す。
+///
+/// # 戻り値
+/// 保存した記事数（入力の件数）
+pub async fn store_article_contents_batch(
+    articles: &[ArticleContent],
+    batch_size: usize,
+    pool: &PgPool,
+) -> Result<usize> {
+    if articles.is_empty() {
+        return Ok(0);
+    }
+
+    let mut conn = pool
+        .acquire()
+        .await
+        .context("データベース接続の取得に失敗しました")?;
+    let batch_size = batch_size.max(1);
+    for (i, batch) in articles.chunks(batch_size).enumerate() {
+        store_article_contents_with_conn(batch, &mut conn)
+            .await
+            .with_context(|| {
+                format!(
+                    "記事の一括保存に失敗（{}件中{}件目から）",
+                    articles.len(),
+                    i * batch_size + 1
+                )
+            })?;
+    }
+    Ok(articles.len())
+}
+
 /// URLから記事を取得してデータベースに保存する統合関数
+/// 収集禁止ドメインのURLはエラーになる。
+#[cfg(feature = "collector")]
 pub async fn fetch_and_store_article(url: &str, pool: &PgPool) -> Result<ArticleContent> {
+    ensure_url_allowed(url, pool).await?;
     let article = get_article_content(url).await?;
     store_article_content(&article, pool).await?;
     Ok(article)
 }
 
 /// 指定されたクライアントを使って記事を取得してデータベースに保存する統合関数（テスト用）
+#[cfg(feature = "collector")]
 pub async fn fetch_and_store_article_with_client(
     url: &str,
-    client: &dyn FirecrawlClient,
+    client: &dyn ScraperClient,
     pool: &PgPool,
 ) -> Result<ArticleContent> {
+    ensure_url_allowed(url, pool).await?;
     let article = get_article_content_with_client(url, client).await?;
     store_article_content(&article, pool).await?;
     Ok(article)
 }
 
+/// URLの記事ページの元のHTMLを取得する（保存していない記事・ゴミ箱の記事は`None`）
+pub async fn get_article_raw_html(url: &str, pool: &PgPool) -> Result<Option<String>> {
+    let raw_html = sqlx::query_scalar!(
+        "SELECT raw_html FROM articles WHERE url = $1 AND deleted_at IS NULL",
+        url
+    )
+    .fetch_optional(pool)
+    .await
+    .with_context(|| format!("記事の元のHTMLの取得に失敗: {}", url))?;
+
+    Ok(raw_html.flatten())
+}
+
 /// 指定されたデータベースプールからArticleContentを取得する。
+/// ゴミ箱に入っている記事は`include_deleted`を指定しない限り除外される。
 pub async fn search_article_contents(
     query: Option<ArticleContentQuery>,
     pool: &PgPool,
 ) -> Result<Vec<ArticleContent>> {
     let query = query.unwrap_or_default();
+    let limits = article_query_limits();
+    let limit = limits.resolve(query.limit)?;
     let mut qb = sqlx::QueryBuilder::<sqlx::Postgres>::new(
         "SELECT url, timestamp, status_code, content FROM articles",
     );
@@ -150,103 +786,409 @@ pub async fn search_article_contents(
             qb.push(" AND ");
         } else {
             qb.push(" WHERE ");
+            has_where = true;
         }
         qb.push("status_code = ").push_bind(status);
     }
 
-    qb.push(" ORDER BY timestamp DESC");
+    if let Some(ref language) = query.language {
+        if has_where {
+            qb.push(" AND ");
+        } else {
+            qb.push(" WHERE ");
+            has_where = true;
+        }
+        qb.push("language = ").push_bind(language.clone());
+    }
+
+    if !query.include_deleted {
+        if has_where {
+            qb.push(" AND ");
+        } else {
+            qb.push(" WHERE ");
+        }
+        qb.push("deleted_at IS NULL");
+    }
+
+    qb.push(" ORDER BY timestamp DESC LIMIT ").push_bind(limit);
 
     let articles = qb
         .build_query_as::<ArticleContent>()
         .fetch_all(pool)
         .await?;
+    limits.warn_if_truncated(query.limit, articles.len());
 
     Ok(articles)
 }
 
-/// RSSリンクと記事の結合情報を取得する
-pub async fn search_articles(query: Option<ArticleQuery>, pool: &PgPool) -> Result<Vec<Article>> {
-    let query = query.unwrap_or_default();
-
-    let mut qb = sqlx::QueryBuilder::<sqlx::Postgres>::new(
-        r#"
+/// 記事検索で取得する列（`Article`に対応、`snippet`は指定した場合のみ追加する）
+const ARTICLE_SEARCH_SELECT: &str = r#"
         SELECT 
+            al.id,
             al.url,
             al.title,
             al.pub_date,
             a.timestamp as updated_at,
             a.status_code,
             a.content
+        "#;
+
+/// 記事検索の結合（`ARTICLE_SEARCH_SELECT`の列の取得元）
+const ARTICLE_SEARCH_FROM: &str = r#"
         FROM article_links al
         LEFT JOIN articles a ON al.url = a.url
-        "#,
-    );
+        "#;
 
-    let mut has_where = false;
+/// 全文検索の設定（`content_tsv`の生成列と同じ設定を使う）
+const FULL_TEXT_CONFIG: &str = "simple";
+
+/// RSSリンクと記事の結合情報を取得する
+/// ゴミ箱に入っているリンクは`include_deleted`を指定しない限り除外される。
+/// 件数は`limit`（未指定は既定の件数）までで、上限を超える件数は`stream_articles`で取得する。
+pub async fn search_articles(query: Option<ArticleQuery>, pool: &PgPool) -> Result<Vec<Article>> {
+    let query = query.unwrap_or_default();
+    let limits = article_query_limits();
+    let limit = limits.resolve(query.limit)?;
+
+    let results = fetch_articles(&query, limit, pool).await?;
+    limits.warn_if_truncated(query.limit, results.len());
+
+    Ok(results)
+}
+
+/// 検索条件に合う記事を`limit`件まで取得する（件数の制限は呼び出し元で確認する）
+async fn fetch_articles(query: &ArticleQuery, limit: i64, pool: &PgPool) -> Result<Vec<Article>> {
+    let mut qb = article_search_builder(query)?;
+
+    // ページ送りで同じ日時の記事が重複・欠落しないよう、URLでも並べる
+    qb.push(" ORDER BY al.pub_date DESC, al.url DESC");
+    push_limit_offset(&mut qb, limit, query.offset);
+
+    let results = qb
+        .build_query_as::<Article>()
+        .fetch_all(pool)
+        .await
+        .context("記事情報の取得に失敗")?;
+
+    Ok(results)
+}
+
+/// # 概要
+/// 記事本文を全文検索し、関連度の高い順に返す。
+///
+/// `full_text`はPostgreSQLの`websearch_to_tsquery`の構文（`"語句"`、`OR`、`-除外`）で指定する。
+/// `content_tsv`のGINインデックスを使うため、`content_query`（ILIKE）より高速に検索できる。
+/// 語は空白・記号で区切られるため、区切りの無い日本語の部分一致には`content_query`を使う。
+/// その他の条件は`search_articles`と同じように絞り込みに使う。
+///
+/// # エラー
+/// `full_text`が未指定・空の場合と、関連度順と両立しない`after`を指定した場合はエラー。
+pub async fn search_articles_fulltext(query: ArticleQuery, pool: &PgPool) -> Result<Vec<Article>> {
+    let full_text = match query.full_text.as_deref().map(str::trim) {
+        Some(text) if !text.is_empty() => text.to_string(),
+        _ => bail!("全文検索の検索語が指定されていません"),
+    };
+    if query.after.is_some() {
+        bail!("全文検索はカーソルによるページ送りに対応していません（offsetを使用してください）");
+    }
+    let limits = article_query_limits();
+    let limit = limits.resolve(query.limit)?;
+
+    let mut qb = article_search_builder(&query)?;
+
+    qb.push(format!(
+        " ORDER BY ts_rank(a.content_tsv, websearch_to_tsquery('{}', ",
+        FULL_TEXT_CONFIG
+    ))
+    .push_bind(full_text)
+    .push(")) DESC, al.pub_date DESC, al.url DESC");
+    push_limit_offset(&mut qb, limit, query.offset);
+
+    let results = qb
+        .build_query_as::<Article>()
+        .fetch_all(pool)
+        .await
+        .context("記事の全文検索に失敗")?;
+    limits.warn_if_truncated(query.limit, results.len());
+
+    Ok(results)
+}
+
+/// 記事検索のSELECT（`snippet`を指定した場合は抜粋の列を含む）と検索条件のWHERE句を作成する
+///
+/// `snippet`には抜粋の基準にする全文検索の検索語（`full_text`）が必要で、無い場合はエラー。
+fn article_search_builder(
+    query: &ArticleQuery,
+) -> Result<sqlx::QueryBuilder<'static, sqlx::Postgres>> {
+    let mut qb = sqlx::QueryBuilder::<sqlx::Postgres>::new(ARTICLE_SEARCH_SELECT);
+    if let Some(ref snippet) = query.snippet {
+        let full_text = query
+            .full_text
+            .as_deref()
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .context("スニペットには全文検索の検索語（full_text）が必要です")?;
+        let options = snippet
+            .headline_options()
+            .context("スニペットの設定が不正")?;
+        qb.push(format!(
+            ", ts_headline('{0}', a.content, websearch_to_tsquery('{0}', ",
+            FULL_TEXT_CONFIG
+        ))
+        .push_bind(full_text.to_string())
+        .push("), ")
+        .push_bind(options)
+        .push(") AS snippet");
+    }
+    qb.push(ARTICLE_SEARCH_FROM);
+    push_article_conditions(&mut qb, query)?;
+    Ok(qb)
+}
+
+/// 検索条件をWHERE句として追加する
+fn push_article_conditions(
+    qb: &mut sqlx::QueryBuilder<'_, sqlx::Postgres>,
+    query: &ArticleQuery,
+) -> Result<()> {
+    let mut separator = " WHERE ";
+    let mut next_condition = |qb: &mut sqlx::QueryBuilder<'_, sqlx::Postgres>| {
+        qb.push(separator);
+        separator = " AND ";
+    };
+
+    if let Some(ref id) = query.id {
+        next_condition(qb);
+        qb.push("al.id = ").push_bind(id.to_uppercase());
+    }
+    if let Some(ref url) = query.url {
+        next_condition(qb);
+        qb.push("al.url = ").push_bind(url.clone());
+    }
     if let Some(ref link_pattern) = query.link_pattern {
-        if !has_where {
-            qb.push(" WHERE ");
-            has_where = true;
-        }
+        next_condition(qb);
         let pattern = format!("%{}%", link_pattern);
         qb.push("al.url ILIKE ").push_bind(pattern);
     }
+    if let Some(ref title_pattern) = query.title_pattern {
+        let title_query =
+            SearchQuery::parse(title_pattern).context("タイトル検索条件の解析に失敗")?;
+        next_condition(qb);
+        title_query.push_condition(qb, "al.title");
+    }
+    if let Some(ref content_query) = query.content_query {
+        let content_query =
+            SearchQuery::parse(content_query).context("本文検索条件の解析に失敗")?;
+        next_condition(qb);
+        // 未取得の記事は本文が空として扱う
+        content_query.push_condition(qb, "COALESCE(a.content, '')");
+    }
+    if let Some(ref full_text) = query.full_text {
+        next_condition(qb);
+        qb.push(format!(
+            "a.content_tsv @@ websearch_to_tsquery('{}', ",
+            FULL_TEXT_CONFIG
+        ))
+        .push_bind(full_text.clone())
+        .push(")");
+    }
     if let Some(pub_date_from) = query.pub_date_from {
-        if has_where {
-            qb.push(" AND ");
-        } else {
-            qb.push(" WHERE ");
-            has_where = true;
-        }
+        next_condition(qb);
         qb.push("al.pub_date >= ").push_bind(pub_date_from);
     }
     if let Some(pub_date_to) = query.pub_date_to {
-        if has_where {
-            qb.push(" AND ");
-        } else {
-            qb.push(" WHERE ");
-            has_where = true;
-        }
+        next_condition(qb);
         qb.push("al.pub_date <= ").push_bind(pub_date_to);
     }
+    if let Some(updated_from) = query.updated_from {
+        next_condition(qb);
+        qb.push("a.timestamp >= ").push_bind(updated_from);
+    }
     if let Some(ref status) = query.article_status {
-        if has_where {
-            qb.push(" AND ");
-        } else {
-            qb.push(" WHERE ");
-        }
+        next_condition(qb);
+        push_status_condition(qb, status);
+    }
+    if let Some(ref language) = query.language {
+        next_condition(qb);
+        qb.push("a.language = ").push_bind(language.clone());
+    }
+    if let Some(ref after) = query.after {
+        next_condition(qb);
+        qb.push("(al.pub_date, al.url) < (")
+            .push_bind(after.pub_date)
+            .push(", ")
+            .push_bind(after.url.clone())
+            .push(")");
+    }
+    if !query.include_deleted {
+        next_condition(qb);
+        qb.push("al.deleted_at IS NULL");
+    }
 
-        match status {
-            ArticleStatus::Unprocessed => {
-                qb.push("a.url IS NULL");
-            }
-            ArticleStatus::Success => {
-                qb.push("a.status_code = 200");
-            }
-            ArticleStatus::Error(code) => {
-                qb.push("a.status_code = ").push_bind(*code);
+    Ok(())
+}
+
+/// 記事の処理状態の条件を追加する（`a`は記事リンクにLEFT JOINしたarticles）
+fn push_status_condition(qb: &mut sqlx::QueryBuilder<'_, sqlx::Postgres>, status: &ArticleStatus) {
+    match status {
+        ArticleStatus::Unprocessed => {
+            qb.push("a.url IS NULL");
+        }
+        ArticleStatus::Success => {
+            qb.push("a.status_code = 200");
+        }
+        ArticleStatus::Error(code) => {
+            qb.push("a.status_code = ").push_bind(*code);
+        }
+        ArticleStatus::ClientError | ArticleStatus::ServerError | ArticleStatus::ErrorRange(..) => {
+            if let Some(range) = status.error_code_range() {
+                qb.push("a.status_code BETWEEN ")
+                    .push_bind(*range.start())
+                    .push(" AND ")
+                    .push_bind(*range.end())
+                    .push(" AND a.status_code != 200");
             }
         }
     }
+}
 
-    qb.push(" ORDER BY al.pub_date DESC");
-    if let Some(limit) = query.limit {
-        qb.push(" LIMIT ").push_bind(limit);
+/// LIMIT・OFFSET句を追加する
+fn push_limit_offset(
+    qb: &mut sqlx::QueryBuilder<'_, sqlx::Postgres>,
+    limit: i64,
+    offset: Option<i64>,
+) {
+    qb.push(" LIMIT ").push_bind(limit);
+    if let Some(offset) = offset {
+        qb.push(" OFFSET ").push_bind(offset);
     }
+}
 
-    let results = qb
-        .build_query_as::<Article>()
-        .fetch_all(pool)
-        .await
-        .context("記事情報の取得に失敗")?;
+/// # 概要
+/// 記事を1ページ分検索し、次のページを取得するカーソルと合わせて返す。
+///
+/// `limit`（未指定は`DEFAULT_PAGE_SIZE`、検索件数の上限まで）を1ページの件数とする。
+/// 返した`next_cursor`を次の検索の`after`に指定すると続きを取得できる。
+/// `offset`による指定も併用できるが、件数の多いページ送りではカーソルを使う。
+pub async fn search_articles_page(
+    query: ArticleQuery,
+    pool: &PgPool,
+) -> Result<SearchArticlesPage> {
+    let page_size = article_query_limits()
+        .resolve(Some(query.limit.unwrap_or(DEFAULT_PAGE_SIZE)))?
+        .max(1);
+    // 1件多く取得して次のページがあるかを判定する
+    let mut articles = fetch_articles(&query, page_size + 1, pool).await?;
+    let has_next = articles.len() as i64 > page_size;
+    articles.truncate(page_size as usize);
+    let next_cursor = if has_next {
+        articles.last().map(ArticleCursor::from)
+    } else {
+        None
+    };
+
+    Ok(SearchArticlesPage {
+        articles,
+        next_cursor,
+    })
+}
 
-    Ok(results)
+/// # 概要
+/// 条件に合う記事をカーソルによるページ送りで`page_size`件ずつ取得し、ページ毎に`on_page`に渡す。
+///
+/// すべての記事を一度にメモリへ読み込まないため、検索件数の上限を超える件数の処理に使う。
+/// `query`の`limit`・`offset`は使わず、`after`を指定した場合はその続きから取得する。
+///
+/// # 戻り値
+/// `on_page`に渡した記事の件数
+pub async fn stream_articles<F>(
+    query: ArticleQuery,
+    page_size: i64,
+    pool: &PgPool,
+    mut on_page: F,
+) -> Result<usize>
+where
+    F: FnMut(Vec<Article>) -> Result<()>,
+{
+    let mut query = ArticleQuery {
+        limit: Some(page_size),
+        offset: None,
+        ..query
+    };
+    let mut total = 0;
+    loop {
+        let page = search_articles_page(query.clone(), pool).await?;
+        total += page.articles.len();
+        if !page.articles.is_empty() {
+            on_page(page.articles)?;
+        }
+        match page.next_cursor {
+            Some(cursor) => query.after = Some(cursor),
+            None => return Ok(total),
+        }
+    }
+}
+
+/// 記事の参照キー（IDまたはURL）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArticleKey {
+    /// 記事リンクのID（ULID）
+    Id(String),
+    Url(String),
+}
+
+impl ArticleKey {
+    /// ULIDの形式（Crockford Base32の26文字）ならID、それ以外はURLとして解釈する
+    pub fn parse(key: &str) -> Self {
+        const CROCKFORD: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+        let is_ulid = key.len() == 26
+            && key
+                .chars()
+                .all(|c| CROCKFORD.contains(c.to_ascii_uppercase()));
+        if is_ulid {
+            ArticleKey::Id(key.to_uppercase())
+        } else {
+            ArticleKey::Url(key.to_string())
+        }
+    }
+}
+
+/// # 概要
+/// IDまたはURLで記事を1件取得する（ゴミ箱の記事も含む）。
+pub async fn get_article(key: &ArticleKey, pool: &PgPool) -> Result<Option<Article>> {
+    let query = match key {
+        ArticleKey::Id(id) => ArticleQuery {
+            id: Some(id.clone()),
+            ..Default::default()
+        },
+        ArticleKey::Url(url) => ArticleQuery {
+            url: Some(url.clone()),
+            ..Default::default()
+        },
+    };
+    let articles = search_articles(
+        Some(ArticleQuery {
+            include_deleted: true,
+            ..query
+        }),
+        pool,
+    )
+    .await?;
+    Ok(articles.into_iter().next())
 }
 
 /// バックログ記事の軽量版を取得する（article_contentを除外し、パフォーマンスを向上）
 pub async fn search_backlog_articles_light(
     pool: &PgPool,
     limit: Option<i64>,
+) -> Result<Vec<ArticleMetadata>> {
+    search_backlog_articles_light_with(pool, None, limit).await
+}
+
+/// 処理状態（`ClientError`で4xxのみなど）で絞り込んでバックログ記事の軽量版を取得する
+pub async fn search_backlog_articles_light_with(
+    pool: &PgPool,
+    status: Option<&ArticleStatus>,
+    limit: Option<i64>,
 ) -> Result<Vec<ArticleMetadata>> {
     let mut qb = sqlx::QueryBuilder::<sqlx::Postgres>::new(
         r#"
@@ -258,10 +1200,16 @@ pub async fn search_backlog_articles_light(
             a.status_code
         FROM article_links al
         LEFT JOIN articles a ON al.url = a.url
-        WHERE a.url IS NULL OR a.status_code != 200
-        ORDER BY al.pub_date DESC
+        WHERE (a.url IS NULL OR a.status_code != 200)
+            AND al.deleted_at IS NULL
+            AND al.fetch_target
         "#,
     );
+    if let Some(status) = status {
+        qb.push(" AND ");
+        push_status_condition(&mut qb, status);
+    }
+    qb.push(" ORDER BY al.pub_date DESC");
     if let Some(limit) = limit {
         qb.push(" LIMIT ").push_bind(limit);
     }
@@ -311,6 +1259,7 @@ mod tests {
                 timestamp: now,
                 status_code,
                 content,
+                raw_html: None,
             })
         }
 
@@ -363,6 +1312,50 @@ mod tests {
     mod pure {
         use super::*;
 
+        #[cfg(feature = "collector")]
+        #[test]
+        fn test_article_content_constructors() {
+            let document = Document {
+                markdown: Some("本文".to_string()),
+                ..Default::default()
+            };
+            let article = ArticleContent::from_scrape_result("https://test.com/a", document);
+            assert_eq!(article.status_code, 200);
+            assert_eq!(article.content, "本文");
+            assert!(article.is_success());
+
+            // markdownが無い場合も成功扱いで代替テキストを保存
+            let article =
+                ArticleContent::from_scrape_result("https://test.com/b", Document::default());
+            assert_eq!(article.content, "記事内容が取得できませんでした");
+
+            let article = ArticleContent::error_placeholder("https://test.com/c", "タイムアウト");
+            assert_eq!(article.status_code, ERROR_STATUS_CODE);
+            assert_eq!(article.content, "タイムアウト");
+            assert!(!article.is_success());
+        }
+
+        #[test]
+        fn test_extract_markdown_h1() {
+            assert_eq!(
+                extract_markdown_h1("![logo](a.png)\n\n# 記事の見出し #\n\n## 小見出し"),
+                Some("記事の見出し".to_string())
+            );
+            assert_eq!(
+                extract_markdown_h1("Setext見出し\n======\n本文"),
+                Some("Setext見出し".to_string())
+            );
+            assert_eq!(
+                extract_markdown_h1("```\n# コメント\n```\n## 小見出しのみ"),
+                None,
+                "コードブロック内とH2は対象外"
+            );
+
+            let article = ArticleContent::error_placeholder("https://test.com/c", "# エラー");
+            assert_eq!(article.extract_title(), None, "取得失敗の記事は対象外");
+        }
+
+        #[cfg(feature = "collector")]
         #[tokio::test]
         async fn test_get_article_content_with_mock() -> Result<(), anyhow::Error> {
             use crate::infra::api::firecrawl::MockFirecrawlClient;
@@ -380,6 +1373,7 @@ mod tests {
             Ok(())
         }
 
+        #[cfg(feature = "collector")]
         #[tokio::test]
         async fn test_get_article_content_with_error_client() -> Result<(), anyhow::Error> {
             use crate::infra::api::firecrawl::MockFirecrawlClient;
@@ -401,6 +1395,94 @@ mod tests {
             println!("✅ エラークライアント処理テスト完了");
             Ok(())
         }
+
+        #[test]
+        fn test_retry_policy_backoff() {
+            let policy = RetryPolicy {
+                max_retries: 5,
+                initial_backoff_ms: 100,
+                max_backoff_ms: 300,
+                multiplier: 2.0,
+            };
+            assert_eq!(policy.backoff(0), Duration::from_millis(100));
+            assert_eq!(policy.backoff(1), Duration::from_millis(200));
+            assert_eq!(
+                policy.backoff(2),
+                Duration::from_millis(300),
+                "上限で頭打ち"
+            );
+            assert_eq!(RetryPolicy::none().max_retries, 0);
+        }
+
+        #[cfg(feature = "collector")]
+        #[tokio::test(start_paused = true)]
+        async fn test_get_article_content_with_retry() -> Result<(), anyhow::Error> {
+            use std::sync::atomic::{AtomicUsize, Ordering};
+
+            // 指定回数だけ失敗してから成功するスクレイパー
+            struct FlakyScraper {
+                error: &'static str,
+                failures: usize,
+                calls: AtomicUsize,
+            }
+
+            #[async_trait::async_trait]
+            impl ScraperClient for FlakyScraper {
+                fn backend_name(&self) -> &'static str {
+                    "テスト"
+                }
+
+                async fn scrape_markdown(
+                    &self,
+                    _url: &str,
+                ) -> Result<Option<String>, crate::types::FetchError> {
+                    if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                        return Err(crate::types::FetchError::Unavailable(
+                            self.error.to_string(),
+                        ));
+                    }
+                    Ok(Some("本文".to_string()))
+                }
+            }
+
+            let policy = RetryPolicy::default();
+
+            // 一時的なエラーは再試行して成功する
+            let flaky = FlakyScraper {
+                error: "connection reset by peer",
+                failures: 2,
+                calls: AtomicUsize::new(0),
+            };
+            let article =
+                get_article_content_with_retry("https://test.com/a", &flaky, &policy).await?;
+            assert!(article.is_success());
+            assert_eq!(flaky.calls.load(Ordering::SeqCst), 3);
+
+            // 再試行回数を超えたら最後のエラーを返す
+            let flaky = FlakyScraper {
+                error: "operation timed out",
+                failures: 10,
+                calls: AtomicUsize::new(0),
+            };
+            let article =
+                get_article_content_with_retry("https://test.com/b", &flaky, &policy).await?;
+            assert!(!article.is_success());
+            assert_eq!(flaky.calls.load(Ordering::SeqCst), 3, "初回 + 再試行2回");
+
+            // 恒久的なエラーは再試行しない
+            let flaky = FlakyScraper {
+                error: "404 not found",
+                failures: 10,
+                calls: AtomicUsize::new(0),
+            };
+            let article =
+                get_article_content_with_retry("https://test.com/c", &flaky, &policy).await?;
+            assert!(!article.is_success());
+            assert_eq!(flaky.calls.load(Ordering::SeqCst), 1);
+
+            println!("✅ 記事取得リトライテスト成功");
+            Ok(())
+        }
     }
 
     mod called {
@@ -414,6 +1496,7 @@ mod tests {
                 timestamp: now,
                 status_code: 200,
                 content: "# Test Article\n\nThis is a test content.".to_string(),
+                raw_html: None,
             };
             store_article_content(&test_article, &pool).await?;
             let count = sqlx::query_scalar!("SELECT COUNT(*) FROM articles")
@@ -425,6 +1508,63 @@ mod tests {
             Ok(())
         }
 
+        #[sqlx::test]
+        async fn test_store_in_transaction(pool: PgPool) -> Result<(), anyhow::Error> {
+            use crate::core::rss::{store_article_links_with_executor, ArticleLink};
+
+            let url = "https://tx.example.com/news/1";
+            let link = ArticleLink {
+                url: url.to_string(),
+                title: url.to_string(),
+                pub_date: Utc::now(),
+                source: "tx".to_string(),
+                ..Default::default()
+            };
+            let article = ArticleContent {
+                url: url.to_string(),
+                timestamp: Utc::now(),
+                status_code: 200,
+                content: "# トランザクション記事\n\n本文".to_string(),
+                raw_html: None,
+            };
+
+            // ロールバックするとリンク・記事・付随処理のすべてが取り消される
+            let mut tx = pool.begin().await?;
+            store_article_links_with_executor(std::slice::from_ref(&link), &mut *tx).await?;
+            store_article_content_with_conn(&article, &mut tx).await?;
+            let title = sqlx::query_scalar!("SELECT title FROM article_links WHERE url = $1", url)
+                .fetch_one(&mut *tx)
+                .await?;
+            assert_eq!(
+                title, "トランザクション記事",
+                "トランザクション内でタイトル補完される"
+            );
+            tx.rollback().await?;
+
+            let count = |table: &'static str| {
+                let pool = pool.clone();
+                async move {
+                    sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM {}", table))
+                        .fetch_one(&pool)
+                        .await
+                }
+            };
+            for table in ["article_links", "articles", "article_fetch_attempts"] {
+                assert_eq!(count(table).await?, 0, "{}が残っている", table);
+            }
+
+            let mut tx = pool.begin().await?;
+            store_article_links_with_executor(std::slice::from_ref(&link), &mut *tx).await?;
+            store_article_content_with_conn(&article, &mut tx).await?;
+            tx.commit().await?;
+            for table in ["article_links", "articles", "article_fetch_attempts"] {
+                assert_eq!(count(table).await?, 1, "{}が保存されていない", table);
+            }
+
+            println!("✅ トランザクション内の保存テスト成功");
+            Ok(())
+        }
+
         #[sqlx::test]
         async fn test_store_duplicate_article_contents(pool: PgPool) -> Result<(), anyhow::Error> {
             let now = Utc::now();
@@ -433,6 +1573,7 @@ mod tests {
                 timestamp: now,
                 status_code: 200,
                 content: "Original content".to_string(),
+                raw_html: None,
             };
             store_article_content(&original_article, &pool).await?;
             let duplicate_article = ArticleContent {
@@ -440,6 +1581,7 @@ mod tests {
                 timestamp: now,
                 status_code: 404,
                 content: "Different content".to_string(),
+                raw_html: None,
             };
             store_article_content(&duplicate_article, &pool).await?;
             let count = sqlx::query_scalar!("SELECT COUNT(*) FROM articles")
@@ -455,6 +1597,189 @@ mod tests {
             Ok(())
         }
 
+        #[sqlx::test]
+        async fn test_store_article_contents_bulk(pool: PgPool) -> Result<(), anyhow::Error> {
+            let article = |url: &str, content: &str| ArticleContent {
+                url: url.to_string(),
+                timestamp: Utc::now(),
+                status_code: 200,
+                content: content.to_string(),
+                raw_html: None,
+            };
+            let articles = vec![
+                article("https://bulk.example.com/1", "old"),
+                article("https://bulk.example.com/2", "second"),
+                article("https://bulk.example.com/1", "new"),
+            ];
+            store_article_contents(&articles, &pool).await?;
+
+            let rows = sqlx::query!("SELECT url, content FROM articles ORDER BY url")
+                .fetch_all(&pool)
+                .await?;
+            assert_eq!(rows.len(), 2, "URLの重複は1件にまとめられるべき");
+            assert_eq!(rows[0].content, "new", "後の記事内容が優先されるべき");
+            assert_eq!(rows[1].content, "second");
+
+            println!("✅ 記事一括保存テスト成功");
+            Ok(())
+        }
+
+        #[sqlx::test]
+        async fn test_store_article_contents_batch(pool: PgPool) -> Result<(), anyhow::Error> {
+            let article = |i: usize, content: &str| ArticleContent {
+                url: format!("https://batch.example.com/{}", i),
+                timestamp: Utc::now(),
+                status_code: 200,
+                content: content.to_string(),
+                raw_html: None,
+            };
+            let mut articles: Vec<ArticleContent> =
+                (0..5).map(|i| article(i, &format!("本文{}", i))).collect();
+            // 別のバッチに含まれる同じURLも後のものを優先する
+            articles.push(article(0, "更新後の本文"));
+
+            let stored = store_article_contents_batch(&articles, 2, &pool).await?;
+            assert_eq!(stored, 6);
+            assert_eq!(store_article_contents_batch(&[], 2, &pool).await?, 0);
+
+            let rows = sqlx::query!("SELECT url, content FROM articles ORDER BY url")
+                .fetch_all(&pool)
+                .await?;
+            assert_eq!(rows.len(), 5);
+            assert_eq!(rows[0].content, "更新後の本文");
+            assert_eq!(rows[4].content, "本文4");
+
+            println!("✅ 記事のバッチ分割保存テスト成功");
+            Ok(())
+        }
+
+        #[sqlx::test]
+        async fn test_store_article_marks_duplicates(pool: PgPool) -> Result<(), anyhow::Error> {
+            let article = |url: &str, content: &str| ArticleContent {
+                url: url.to_string(),
+                timestamp: Utc::now(),
+                status_code: 200,
+                content: content.to_string(),
+                raw_html: None,
+            };
+            let original = "https://dup.example.com/news/1";
+            let copy = "https://dup.example.com/news/1?ref=top";
+            store_article_content(&article(original, "同じ本文"), &pool).await?;
+            store_article_contents(
+                &[
+                    article(copy, "同じ本文"),
+                    article("https://dup.example.com/news/2", "別の本文"),
+                ],
+                &pool,
+            )
+            .await?;
+
+            let duplicates = || {
+                sqlx::query!("SELECT url, content_hash, duplicate_of FROM articles ORDER BY url")
+                    .fetch_all(&pool)
+            };
+            let rows = duplicates().await?;
+            assert_eq!(rows[0].url, original);
+            assert_eq!(rows[0].duplicate_of, None, "先に保存した記事は重複ではない");
+            assert_eq!(rows[1].url, copy);
+            assert_eq!(rows[1].duplicate_of.as_deref(), Some(original));
+            assert_eq!(rows[1].content_hash, Some(calc_hash("同じ本文", 64)));
+            assert_eq!(rows[2].duplicate_of, None);
+
+            // 本文が変わると重複ではなくなる
+            store_article_content(&article(copy, "更新された本文"), &pool).await?;
+            let rows = duplicates().await?;
+            assert_eq!(rows[1].duplicate_of, None);
+
+            println!("✅ 重複記事検出テスト成功");
+            Ok(())
+        }
+
+        #[sqlx::test]
+        async fn test_store_article_quality_flags(pool: PgPool) -> Result<(), anyhow::Error> {
+            let article = |url: &str, status_code: i32, content: &str| ArticleContent {
+                url: url.to_string(),
+                timestamp: Utc::now(),
+                status_code,
+                content: content.to_string(),
+                raw_html: None,
+            };
+            store_article_contents(
+                &[
+                    article("https://quality.example.com/1", 200, "```\ncode"),
+                    article("https://quality.example.com/2", 200, "正常な本文"),
+                    article("https://quality.example.com/3", 500, "```\nエラー"),
+                ],
+                &pool,
+            )
+            .await?;
+
+            let flags: Vec<Option<String>> =
+                sqlx::query_scalar!("SELECT quality_flags FROM articles ORDER BY url")
+                    .fetch_all(&pool)
+                    .await?;
+            assert_eq!(
+                flags,
+                vec![Some("unclosed_code_fence".to_string()), None, None],
+                "品質フラグは成功記事のみ記録する"
+            );
+
+            println!("✅ 品質フラグ保存テスト成功");
+            Ok(())
+        }
+
+        #[sqlx::test]
+        async fn test_store_article_fills_placeholder_title(
+            pool: PgPool,
+        ) -> Result<(), anyhow::Error> {
+            use crate::core::rss::{store_article_links, ArticleLink, UNTITLED};
+
+            let link = |url: &str, title: &str| ArticleLink {
+                url: url.to_string(),
+                title: title.to_string(),
+                pub_date: Utc::now(),
+                source: "manual".to_string(),
+                ..Default::default()
+            };
+            store_article_links(
+                &[
+                    link("https://title.example.com/untitled", UNTITLED),
+                    link(
+                        "https://title.example.com/url",
+                        "https://title.example.com/url",
+                    ),
+                    link("https://title.example.com/rss", "RSSのタイトル"),
+                ],
+                &pool,
+            )
+            .await?;
+
+            let articles: Vec<ArticleContent> = ["untitled", "url", "rss"]
+                .iter()
+                .map(|path| ArticleContent {
+                    url: format!("https://title.example.com/{}", path),
+                    timestamp: Utc::now(),
+                    status_code: 200,
+                    content: format!("# 本文の見出し{}\n\n本文", path),
+                    raw_html: None,
+                })
+                .collect();
+            store_article_contents(&articles, &pool).await?;
+
+            let rows = sqlx::query!("SELECT url, title FROM article_links ORDER BY url")
+                .fetch_all(&pool)
+                .await?;
+            let titles: Vec<&str> = rows.iter().map(|r| r.title.as_str()).collect();
+            assert_eq!(
+                titles,
+                vec!["RSSのタイトル", "本文の見出しuntitled", "本文の見出しurl"],
+                "仮タイトルのみ本文のH1で上書きされるべき"
+            );
+
+            println!("✅ タイトル補完テスト成功");
+            Ok(())
+        }
+
         #[sqlx::test]
         async fn test_search_article_contents(pool: PgPool) -> Result<(), anyhow::Error> {
             let now = Utc::now();
@@ -463,6 +1788,7 @@ mod tests {
                 timestamp: now,
                 status_code: 200,
                 content: "検索テスト記事".to_string(),
+                raw_html: None,
             };
             store_article_content(&test_article, &pool).await?;
 
@@ -478,6 +1804,67 @@ mod tests {
             Ok(())
         }
 
+        #[sqlx::test]
+        async fn test_search_articles_by_language(pool: PgPool) -> Result<(), anyhow::Error> {
+            let article = |url: &str, content: &str| ArticleContent {
+                url: url.to_string(),
+                timestamp: Utc::now(),
+                status_code: 200,
+                content: content.to_string(),
+                raw_html: None,
+            };
+            let articles = [
+                article(
+                    "https://lang.test.com/ja",
+                    "今日は東京で新しい図書館が開館し、多くの市民が訪れました。",
+                ),
+                article(
+                    "https://lang.test.com/en",
+                    "The city council approved the new budget on Tuesday after a long debate \
+                     about public transport and housing.",
+                ),
+            ];
+            store_article_contents(&articles, &pool).await?;
+            for (url, title) in [
+                ("https://lang.test.com/ja", "日本語"),
+                ("https://lang.test.com/en", "English"),
+            ] {
+                sqlx::query!(
+                    "INSERT INTO article_links (url, title, pub_date, source) VALUES ($1, $2, NOW(), 'test')",
+                    url,
+                    title
+                )
+                .execute(&pool)
+                .await?;
+            }
+
+            let contents = search_article_contents(
+                Some(ArticleContentQuery {
+                    language: Some("jpn".to_string()),
+                    ..Default::default()
+                }),
+                &pool,
+            )
+            .await?;
+            let urls: Vec<&str> = contents.iter().map(|a| a.url.as_str()).collect();
+            assert_eq!(urls, vec!["https://lang.test.com/ja"]);
+
+            let english = search_articles(
+                Some(ArticleQuery {
+                    language: Some("eng".to_string()),
+                    ..Default::default()
+                }),
+                &pool,
+            )
+            .await?;
+            assert_eq!(english.len(), 1);
+            assert_eq!(english[0].url, "https://lang.test.com/en");
+
+            println!("✅ 言語による記事検索テスト成功");
+            Ok(())
+        }
+
+        #[cfg(feature = "collector")]
         #[sqlx::test]
         async fn test_fetch_and_store_article_with_mock(pool: PgPool) -> Result<(), anyhow::Error> {
             use crate::infra::api::firecrawl::MockFirecrawlClient;
@@ -501,6 +1888,75 @@ mod tests {
             Ok(())
         }
 
+        #[cfg(feature = "collector")]
+        #[sqlx::test]
+        async fn test_store_article_raw_html(pool: PgPool) -> Result<(), anyhow::Error> {
+            use crate::core::scraper::{DomainRoutingScraper, ScraperBackend, ScraperConfig};
+            use std::collections::HashMap;
+
+            // マークダウンと元のHTMLを返すスクレイパー
+            struct HtmlScraper;
+
+            #[async_trait::async_trait]
+            impl ScraperClient for HtmlScraper {
+                fn backend_name(&self) -> &'static str {
+                    "テスト"
+                }
+
+                async fn scrape_markdown(
+                    &self,
+                    _url: &str,
+                ) -> Result<Option<String>, crate::types::FetchError> {
+                    Ok(Some("# 見出し\n\n本文".to_string()))
+                }
+
+                async fn scrape(
+                    &self,
+                    url: &str,
+                ) -> Result<ScrapeResult, crate::types::FetchError> {
+                    Ok(ScrapeResult {
+                        markdown: self.scrape_markdown(url).await?,
+                        html: Some("<h1>見出し</h1><p>本文</p>".to_string()),
+                    })
+                }
+            }
+
+            let router = |raw_html: bool| {
+                let scrapers: HashMap<ScraperBackend, Box<dyn ScraperClient>> =
+                    HashMap::from([(ScraperBackend::Local, Box::new(HtmlScraper) as _)]);
+                let config = ScraperConfig {
+                    raw_html,
+                    ..Default::default()
+                };
+                DomainRoutingScraper::new(config, ScraperBackend::Local, scrapers).unwrap()
+            };
+
+            let url = "https://html.test.com/article";
+            let article = fetch_and_store_article_with_client(url, &router(true), &pool).await?;
+            assert_eq!(article.content, "# 見出し\n\n本文");
+            assert_eq!(
+                get_article_raw_html(url, &pool).await?.as_deref(),
+                Some("<h1>見出し</h1><p>本文</p>")
+            );
+            let contents = search_article_contents(None, &pool).await?;
+            assert_eq!(contents[0].raw_html, None, "検索結果には含めない");
+
+            let other = "https://html.test.com/other";
+            fetch_and_store_article_with_client(other, &router(false), &pool).await?;
+            assert_eq!(
+                get_article_raw_html(other, &pool).await?,
+                None,
+                "設定が無効なら保存しない"
+            );
+            assert_eq!(
+                get_article_raw_html("https://html.test.com/missing", &pool).await?,
+                None
+            );
+
+            println!("✅ 記事の元のHTMLの保存テスト成功");
+            Ok(())
+        }
+
         #[sqlx::test(fixtures("../../../fixtures/article_query_filter.sql"))]
         async fn test_article_query_filters(pool: PgPool) -> Result<(), anyhow::Error> {
             let query = ArticleQuery {
@@ -525,10 +1981,299 @@ mod tests {
                 "成功記事のみが取得されるべき"
             );
 
+            // エラーのクラス・範囲指定（200を含む範囲でも成功記事は含めない）
+            let by_status = |status: ArticleStatus| ArticleQuery {
+                article_status: Some(status),
+                ..Default::default()
+            };
+            let client_errors =
+                search_articles(Some(by_status(ArticleStatus::ClientError)), &pool).await?;
+            assert_eq!(client_errors.len(), 1);
+            assert_eq!(client_errors[0].status_code, Some(404));
+            assert!(
+                search_articles(Some(by_status(ArticleStatus::ServerError)), &pool)
+                    .await?
+                    .is_empty()
+            );
+            let in_range =
+                search_articles(Some(by_status(ArticleStatus::ErrorRange(200, 404))), &pool)
+                    .await?;
+            assert_eq!(in_range.len(), 1);
+
+            // タイトル・本文の簡易クエリ構文による検索
+            let search = |title: Option<&str>, content: Option<&str>| ArticleQuery {
+                title_pattern: title.map(str::to_string),
+                content_query: content.map(str::to_string),
+                ..Default::default()
+            };
+            let mut urls: Vec<String> =
+                search_articles(Some(search(Some("ニュース -3"), None)), &pool)
+                    .await?
+                    .into_iter()
+                    .map(|a| a.url)
+                    .collect();
+            urls.sort();
+            assert_eq!(
+                urls,
+                vec!["https://example.com/news1", "https://example.com/news2"]
+            );
+
+            let results =
+                search_articles(Some(search(None, Some(r#""1の内容" OR "3の内容""#))), &pool)
+                    .await?;
+            assert_eq!(results.len(), 2, "OR検索で2件ヒットするはず");
+
+            let results =
+                search_articles(Some(search(Some("ニュース1"), Some("エラー"))), &pool).await?;
+            assert!(results.is_empty(), "タイトルと本文の条件はANDで絞り込む");
+
+            assert!(
+                search_articles(Some(search(Some(" "), None)), &pool)
+                    .await
+                    .is_err(),
+                "検索語の無い条件はエラー"
+            );
+
+            // 本文を取得・更新した日時による絞り込み（未取得のリンクは含めない）
+            let updated_from = |from: DateTime<Utc>| ArticleQuery {
+                updated_from: Some(from),
+                ..Default::default()
+            };
+            let updated = search_articles(
+                Some(updated_from(Utc::now() - chrono::Duration::days(1))),
+                &pool,
+            )
+            .await?;
+            assert!(!updated.is_empty());
+            assert!(updated.iter().all(|a| a.updated_at.is_some()));
+            assert!(search_articles(
+                Some(updated_from(Utc::now() + chrono::Duration::hours(1))),
+                &pool
+            )
+            .await?
+            .is_empty());
+
             println!("✅ クエリフィルターテスト成功");
             Ok(())
         }
 
+        #[sqlx::test]
+        async fn test_search_articles_pagination(pool: PgPool) -> Result<(), anyhow::Error> {
+            use crate::core::rss::{store_article_links, ArticleLink};
+
+            // 同じ日時の記事を含む5件（並び順: 4, 3, 2b, 2a, 1）
+            let base = Utc::now();
+            let links: Vec<ArticleLink> = [("1", 3), ("2a", 2), ("2b", 2), ("3", 1), ("4", 0)]
+                .iter()
+                .map(|(name, minutes)| ArticleLink {
+                    url: format!("https://page.example.com/{}", name),
+                    title: format!("記事{}", name),
+                    pub_date: base - chrono::Duration::minutes(*minutes),
+                    source: "test".to_string(),
+                    ..Default::default()
+                })
+                .collect();
+            store_article_links(&links, &pool).await?;
+            let names = |articles: &[Article]| -> Vec<String> {
+                articles
+                    .iter()
+                    .map(|a| a.url.rsplit('/').next().unwrap().to_string())
+                    .collect()
+            };
+
+            // offset
+            let query = ArticleQuery {
+                limit: Some(2),
+                offset: Some(2),
+                ..Default::default()
+            };
+            assert_eq!(
+                names(&search_articles(Some(query), &pool).await?),
+                ["2b", "2a"]
+            );
+
+            // keyset: カーソルを辿ると重複・欠落なく全件を取得できる
+            let mut pages = Vec::new();
+            let mut after = None;
+            loop {
+                let query = ArticleQuery {
+                    limit: Some(2),
+                    after,
+                    ..Default::default()
+                };
+                let page = search_articles_page(query, &pool).await?;
+                pages.push(names(&page.articles));
+                match page.next_cursor {
+                    Some(cursor) => after = Some(cursor),
+                    None => break,
+                }
+            }
+            assert_eq!(pages, vec![vec!["4", "3"], vec!["2b", "2a"], vec!["1"]]);
+
+            // stream_articlesはページ毎に渡し、limit・offsetは使わない
+            let mut streamed = Vec::new();
+            let query = ArticleQuery {
+                limit: Some(1),
+                offset: Some(3),
+                ..Default::default()
+            };
+            let total = stream_articles(query, 2, &pool, |page| {
+                streamed.push(names(&page));
+                Ok(())
+            })
+            .await?;
+            assert_eq!(total, 5);
+            assert_eq!(streamed, pages);
+
+            // 上限を超える件数の指定はエラー（ページ毎の取得へ誘導する）
+            let over_limit = ArticleQuery {
+                limit: Some(article_query_limits().max_limit + 1),
+                ..Default::default()
+            };
+            let error = search_articles(Some(over_limit), &pool)
+                .await
+                .unwrap_err()
+                .to_string();
+            assert!(error.contains("stream_articles"));
+
+            // カーソルは文字列で受け渡しできる
+            let cursor = ArticleCursor {
+                pub_date: base,
+                url: "https://page.example.com/a|b".to_string(),
+            };
+            assert_eq!(cursor.to_string().parse::<ArticleCursor>()?, cursor);
+            assert!("2025-01-01".parse::<ArticleCursor>().is_err());
+
+            println!("✅ 記事検索のページ送りテスト成功");
+            Ok(())
+        }
+
+        #[sqlx::test]
+        async fn test_search_articles_fulltext(pool: PgPool) -> Result<(), anyhow::Error> {
+            sqlx::query(
+                r#"
+                INSERT INTO article_links (url, title, pub_date, source) VALUES
+                    ('https://ft.example.com/1', 'Rust入門', CURRENT_TIMESTAMP - interval '2 hours', 'test'),
+                    ('https://ft.example.com/2', 'Rust特集', CURRENT_TIMESTAMP - interval '1 hour', 'test'),
+                    ('https://ft.example.com/3', 'Go入門', CURRENT_TIMESTAMP, 'test')
+                "#,
+            )
+            .execute(&pool)
+            .await?;
+            sqlx::query(
+                r#"
+                INSERT INTO articles (url, status_code, content) VALUES
+                    ('https://ft.example.com/1', 200, 'Rust ownership and borrowing. Rust is fast.'),
+                    ('https://ft.example.com/2', 200, 'A short note about Rust'),
+                    ('https://ft.example.com/3', 200, 'Go has goroutines')
+                "#,
+            )
+            .execute(&pool)
+            .await?;
+            let full_text = |text: &str| ArticleQuery {
+                full_text: Some(text.to_string()),
+                ..Default::default()
+            };
+            let urls = |articles: Vec<Article>| -> Vec<String> {
+                articles.into_iter().map(|a| a.url).collect()
+            };
+
+            // 関連度順（語の出現が多い記事が先）
+            assert_eq!(
+                urls(search_articles_fulltext(full_text("rust"), &pool).await?),
+                ["https://ft.example.com/1", "https://ft.example.com/2"]
+            );
+            assert_eq!(
+                urls(search_articles_fulltext(full_text("rust -borrowing"), &pool).await?),
+                ["https://ft.example.com/2"]
+            );
+            assert_eq!(
+                urls(search_articles_fulltext(full_text("ownership OR goroutines"), &pool).await?)
+                    .len(),
+                2
+            );
+
+            // search_articlesでは絞り込み条件として使う（並びは公開日時順）
+            assert_eq!(
+                urls(search_articles(Some(full_text("rust")), &pool).await?),
+                ["https://ft.example.com/2", "https://ft.example.com/1"]
+            );
+
+            assert!(search_articles_fulltext(full_text(" "), &pool)
+                .await
+                .is_err());
+            let with_cursor = ArticleQuery {
+                after: Some(ArticleCursor {
+                    pub_date: Utc::now(),
+                    url: "https://ft.example.com/3".to_string(),
+                }),
+                ..full_text("rust")
+            };
+            assert!(search_articles_fulltext(with_cursor, &pool).await.is_err());
+
+            println!("✅ 記事の全文検索テスト成功");
+            Ok(())
+        }
+
+        #[sqlx::test]
+        async fn test_search_articles_snippet(pool: PgPool) -> Result<(), anyhow::Error> {
+            sqlx::query(
+                r#"
+                INSERT INTO article_links (url, title, pub_date, source) VALUES
+                    ('https://snippet.example.com/1', 'Rust入門', CURRENT_TIMESTAMP, 'test')
+                "#,
+            )
+            .execute(&pool)
+            .await?;
+            sqlx::query(
+                r#"
+                INSERT INTO articles (url, status_code, content) VALUES
+                    ('https://snippet.example.com/1', 200, 'The borrow checker makes Rust memory safe without garbage collection.')
+                "#,
+            )
+            .execute(&pool)
+            .await?;
+            let query = ArticleQuery {
+                full_text: Some("rust".to_string()),
+                snippet: Some(SnippetOptions {
+                    start_sel: "[".to_string(),
+                    stop_sel: "]".to_string(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
+
+            let articles = search_articles_fulltext(query.clone(), &pool).await?;
+            let snippet = articles[0].snippet.as_deref().expect("スニペットが付く");
+            assert!(snippet.contains("makes [Rust] memory"), "{}", snippet);
+            let articles = search_articles(Some(query.clone()), &pool).await?;
+            assert!(articles[0].snippet.is_some(), "search_articlesでも付く");
+
+            // 指定しなければ付かず、JSONにも出力しない
+            let plain = search_articles_fulltext(
+                ArticleQuery {
+                    snippet: None,
+                    ..query.clone()
+                },
+                &pool,
+            )
+            .await?;
+            assert!(plain[0].snippet.is_none());
+            assert!(!serde_json::to_string(&plain[0])?.contains("snippet"));
+
+            // 抜粋の基準にする全文検索の検索語が無い場合はエラー
+            let without_full_text = ArticleQuery {
+                full_text: None,
+                ..query
+            };
+            assert!(search_articles(Some(without_full_text), &pool)
+                .await
+                .is_err());
+
+            println!("✅ 検索結果のスニペットテスト成功");
+            Ok(())
+        }
+
         #[sqlx::test(fixtures("../../../fixtures/article_backlog.sql"))]
         async fn test_search_backlog_articles_light(pool: PgPool) -> Result<(), anyhow::Error> {
             use crate::core::article::model::{
@@ -545,6 +2290,17 @@ mod tests {
             assert!(error >= 1);
             assert_eq!(success, 0);
 
+            // エラーのクラスで絞り込む（フィクスチャのエラーは500）
+            let server_errors =
+                search_backlog_articles_light_with(&pool, Some(&ArticleStatus::ServerError), None)
+                    .await?;
+            assert_eq!(server_errors.len(), 1);
+            assert_eq!(server_errors[0].status_code, Some(500));
+            let client_errors =
+                search_backlog_articles_light_with(&pool, Some(&ArticleStatus::ClientError), None)
+                    .await?;
+            assert!(client_errors.is_empty());
+
             println!(
                 "✅ バックログ軽量版テスト成功: {}件",
                 backlog_articles.len()
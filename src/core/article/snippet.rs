@@ -0,0 +1,101 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+// 全文検索の結果に付ける抜粋（スニペット）の設定
+//
+// PostgreSQLの`ts_headline`のオプションに対応する。マッチした語を`start_sel`と`stop_sel`で囲み、
+// `max_fragments`個までの断片を`fragment_delimiter`で繋いで返す。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SnippetOptions {
+    /// 1つの断片の最大語数
+    pub max_words: u32,
+    /// 1つの断片の最小語数（`max_words`未満にする）
+    pub min_words: u32,
+    /// 抜粋する断片の最大数
+    pub max_fragments: u32,
+    /// マッチした語の前に挿入する文字列
+    pub start_sel: String,
+    /// マッチした語の後に挿入する文字列
+    pub stop_sel: String,
+    /// 断片を繋ぐ文字列
+    pub fragment_delimiter: String,
+}
+
+impl Default for SnippetOptions {
+    fn default() -> Self {
+        Self {
+            max_words: 35,
+            min_words: 15,
+            max_fragments: 2,
+            start_sel: "<b>".to_string(),
+            stop_sel: "</b>".to_string(),
+            fragment_delimiter: " ... ".to_string(),
+        }
+    }
+}
+
+impl SnippetOptions {
+    /// # 概要
+    /// `ts_headline`に渡すオプション文字列を作成する。
+    ///
+    /// # エラー
+    /// 語数・断片数が0の場合と、`min_words`が`max_words`以上の場合はエラー
+    pub fn headline_options(&self) -> Result<String> {
+        if self.min_words == 0 || self.max_fragments == 0 {
+            bail!("スニペットの語数と断片数は1以上を指定してください");
+        }
+        if self.min_words >= self.max_words {
+            bail!(
+                "スニペットのmin_words（{}）はmax_words（{}）未満を指定してください",
+                self.min_words,
+                self.max_words
+            );
+        }
+        Ok(format!(
+            "MaxWords={}, MinWords={}, MaxFragments={}, StartSel={}, StopSel={}, FragmentDelimiter={}",
+            self.max_words,
+            self.min_words,
+            self.max_fragments,
+            quote_option(&self.start_sel),
+            quote_option(&self.stop_sel),
+            quote_option(&self.fragment_delimiter)
+        ))
+    }
+}
+
+/// オプションの値を二重引用符で囲む（空白・カンマを含む値のため、引用符は重ねてエスケープする）
+fn quote_option(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snippet_headline_options() {
+        assert_eq!(
+            SnippetOptions::default().headline_options().unwrap(),
+            r#"MaxWords=35, MinWords=15, MaxFragments=2, StartSel="<b>", StopSel="</b>", FragmentDelimiter=" ... ""#
+        );
+
+        let options: SnippetOptions = serde_json::from_str(
+            r#"{"max_words": 10, "min_words": 3, "start_sel": "<mark class=\"hit\">"}"#,
+        )
+        .unwrap();
+        assert_eq!(options.stop_sel, "</b>", "省略したフィールドは既定値");
+        assert!(options
+            .headline_options()
+            .unwrap()
+            .contains(r#"StartSel="<mark class=""hit"">""#));
+
+        let invalid = SnippetOptions {
+            min_words: 35,
+            ..Default::default()
+        };
+        assert!(invalid.headline_options().is_err());
+
+        println!("✅ スニペットのオプションテスト成功");
+    }
+}
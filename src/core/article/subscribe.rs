@@ -0,0 +1,152 @@
+use super::model::ArticleMetadata;
+use anyhow::{Context, Result};
+use futures::stream::{self, Stream};
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+
+/// 本文を取得できた記事を通知するチャンネル名（migrations/039と対応、payloadはurl）
+pub const NEW_ARTICLES_CHANNEL: &str = "articles_stored";
+
+/// # 概要
+/// 本文を取得できた新着記事をLISTEN/NOTIFYで購読し、届いた順に`ArticleMetadata`を返すストリームを作成する。
+///
+/// 記事の保存時（`store_article_content`・一括保存を含む）に、新規の記事かエラー記事の取得成功を
+/// トリガーが通知する。トランザクション内の保存はコミット時に届き、ロールバックされた保存は届かない。
+/// LISTENを開始してから返すため、戻った後に保存された記事は取りこぼさない。
+/// 接続が切れた場合は再接続して購読を続けるが、切断中の通知は届かない。
+/// ゴミ箱に入っている（soft delete済みの）リンクの記事は返さず、受信に失敗した場合はストリームを終える。
+///
+/// # エラー
+/// LISTEN用の接続またはLISTENに失敗した場合
+pub async fn subscribe_new_articles(
+    pool: &PgPool,
+) -> Result<impl Stream<Item = ArticleMetadata> + Send + 'static> {
+    let mut listener = PgListener::connect_with(pool)
+        .await
+        .context("LISTEN用の接続に失敗")?;
+    listener
+        .listen(NEW_ARTICLES_CHANNEL)
+        .await
+        .with_context(|| format!("チャンネルのLISTENに失敗: {}", NEW_ARTICLES_CHANNEL))?;
+
+    Ok(stream::unfold(
+        (listener, pool.clone()),
+        |(mut listener, pool)| async move {
+            loop {
+                let notification = match listener.recv().await {
+                    Ok(notification) => notification,
+                    Err(e) => {
+                        tracing::error!(error = %e, "新着記事の通知の受信に失敗");
+                        return None;
+                    }
+                };
+                match get_new_article_metadata(notification.payload(), &pool).await {
+                    Ok(Some(metadata)) => return Some((metadata, (listener, pool))),
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!(
+                        url = notification.payload(),
+                        error = format!("{:#}", e),
+                        "新着記事の取得に失敗"
+                    ),
+                }
+            }
+        },
+    ))
+}
+
+// 通知されたURLの記事のメタデータ（リンクが無いかゴミ箱に入っている場合はNone）
+async fn get_new_article_metadata(url: &str, pool: &PgPool) -> Result<Option<ArticleMetadata>> {
+    let metadata = sqlx::query_as!(
+        ArticleMetadata,
+        r#"
+        SELECT al.url, al.title, al.pub_date,
+            a.timestamp AS "updated_at?", a.status_code AS "status_code?"
+        FROM articles a
+        JOIN article_links al ON al.url = a.url
+        WHERE a.url = $1 AND al.deleted_at IS NULL
+        "#,
+        url
+    )
+    .fetch_optional(pool)
+    .await
+    .context("新着記事のメタデータの取得に失敗")?;
+    Ok(metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::article::{store_article_content, store_article_contents, ArticleContent};
+    use crate::core::rss::{store_article_links, ArticleLink};
+    use chrono::Utc;
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    fn article(url: &str, status_code: i32) -> ArticleContent {
+        ArticleContent {
+            url: url.to_string(),
+            timestamp: Utc::now(),
+            status_code,
+            content: format!("{}の本文", url),
+            raw_html: None,
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_subscribe_new_articles(pool: PgPool) -> Result<(), anyhow::Error> {
+        let urls = [
+            "https://stream.example.com/1",
+            "https://stream.example.com/2",
+        ];
+        let links: Vec<ArticleLink> = urls
+            .iter()
+            .map(|url| ArticleLink {
+                url: url.to_string(),
+                title: "新着記事".to_string(),
+                pub_date: Utc::now(),
+                source: "test".to_string(),
+                ..Default::default()
+            })
+            .collect();
+        store_article_links(&links, &pool).await?;
+
+        let stream = subscribe_new_articles(&pool).await?;
+        tokio::pin!(stream);
+
+        // エラー記事は通知せず、取得に成功した時点で通知する
+        store_article_content(&article(urls[0], 500), &pool).await?;
+        store_article_content(&article(urls[0], 200), &pool).await?;
+        store_article_contents(&[article(urls[1], 200)], &pool).await?;
+
+        let mut received = Vec::new();
+        for _ in 0..2 {
+            let metadata = tokio::time::timeout(Duration::from_secs(5), stream.next())
+                .await
+                .context("新着記事が届きませんでした")?
+                .context("ストリームが終了しました")?;
+            assert_eq!(metadata.status_code, Some(200));
+            assert_eq!(metadata.title, "新着記事");
+            received.push(metadata.url);
+        }
+        assert_eq!(received, urls);
+
+        // 本文の更新は新着として通知しない
+        store_article_content(
+            &ArticleContent {
+                content: "更新後の本文".to_string(),
+                ..article(urls[0], 200)
+            },
+            &pool,
+        )
+        .await?;
+        assert!(
+            tokio::time::timeout(Duration::from_millis(500), stream.next())
+                .await
+                .is_err(),
+            "更新は通知しない"
+        );
+
+        println!("✅ 新着記事のストリームテスト成功");
+        Ok(())
+    }
+}
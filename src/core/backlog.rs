@@ -0,0 +1,305 @@
+use crate::core::rss::{ArticleLink, BacklogLinkFilter, BacklogOptions};
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+use std::time::Duration;
+
+/// クレームの有効期限（これを過ぎると処理中のワーカーが落ちたとみなし、他のワーカーが取り直せる）
+pub const BACKLOG_CLAIM_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// # 概要
+/// バックログ（未処理かエラーの記事リンク）から最大`batch_size`件の処理権を取得する。
+///
+/// 他のワーカーがクレーム中のリンク（期限切れを除く）と、他のワーカーが同時に
+/// 取得中の行（`FOR UPDATE SKIP LOCKED`）は飛ばすため、複数のワーカーで同じリンクを
+/// 二重に処理しない。処理が終わったら`release_backlog_claims`で解放すること。
+///
+/// # 戻り値
+/// クレームできた記事リンク（pub_dateの新しい順、`claim_backlog_batch_with`では`options.order`の順）
+pub async fn claim_backlog_batch(
+    pool: &PgPool,
+    worker_id: &str,
+    batch_size: i64,
+) -> Result<Vec<ArticleLink>> {
+    claim_backlog_batch_with(
+        pool,
+        worker_id,
+        batch_size,
+        &BacklogLinkFilter::default(),
+        &BacklogOptions::default(),
+    )
+    .await
+}
+
+/// # 概要
+/// 取得元・フィードのグループで絞り込んだバックログの処理権を`options`の順序で取得する
+/// （`article_fetch.backlog` / `backlog_options`に対応）。
+///
+/// 件数は`batch_size`で指定し、`options.limit`は使わない。
+pub async fn claim_backlog_batch_with(
+    pool: &PgPool,
+    worker_id: &str,
+    batch_size: i64,
+    filter: &BacklogLinkFilter,
+    options: &BacklogOptions,
+) -> Result<Vec<ArticleLink>> {
+    claim_backlog_batch_with_timeout(
+        pool,
+        worker_id,
+        batch_size,
+        filter,
+        options,
+        BACKLOG_CLAIM_TIMEOUT,
+    )
+    .await
+}
+
+/// クレームの有効期限を指定してバックログの処理権を取得する
+pub async fn claim_backlog_batch_with_timeout(
+    pool: &PgPool,
+    worker_id: &str,
+    batch_size: i64,
+    filter: &BacklogLinkFilter,
+    options: &BacklogOptions,
+    timeout: Duration,
+) -> Result<Vec<ArticleLink>> {
+    let (priority_feeds, priorities): (Vec<String>, Vec<i32>) = options
+        .feed_priorities
+        .iter()
+        .map(|(feed, priority)| (feed.clone(), *priority))
+        .unzip();
+    let links = sqlx::query_as!(
+        ArticleLink,
+        r#"
+        WITH candidates AS (
+            SELECT al.url
+            FROM article_links al
+            LEFT JOIN articles a ON al.url = a.url
+            LEFT JOIN backlog_claims c ON al.url = c.url
+            LEFT JOIN UNNEST($8::text[], $9::int4[]) AS fp(feed, priority) ON fp.feed = al.feed
+            WHERE (a.url IS NULL OR (a.status_code != 200 AND a.next_retry_at <= now()))
+                AND al.deleted_at IS NULL
+                AND al.fetch_target
+                AND (c.url IS NULL OR c.expires_at < now())
+                AND ($4::text[] IS NULL OR al.source = ANY($4))
+                AND ($5::text[] IS NULL OR NOT (al.source = ANY($5)))
+                AND ($6::text[] IS NULL OR split_part(al.feed, '/', 1) = ANY($6))
+            ORDER BY
+                CASE WHEN $7 = 'feed_priority' THEN COALESCE(fp.priority, 0) END DESC,
+                CASE WHEN $7 = 'oldest' THEN al.pub_date END ASC,
+                al.pub_date DESC
+            LIMIT $2
+            FOR UPDATE OF al SKIP LOCKED
+        ),
+        claimed AS (
+            INSERT INTO backlog_claims (url, worker_id, expires_at)
+            SELECT url, $1, now() + make_interval(secs => $3) FROM candidates
+            ON CONFLICT (url) DO UPDATE SET
+                worker_id = EXCLUDED.worker_id,
+                claimed_at = CURRENT_TIMESTAMP,
+                expires_at = EXCLUDED.expires_at
+            WHERE backlog_claims.expires_at < now()
+            RETURNING url
+        )
+        SELECT al.url, al.title, al.pub_date, al.source, al.description, al.author,
+            ARRAY(
+                SELECT ac.category FROM article_categories ac
+                WHERE ac.url = al.url ORDER BY ac.category
+            ) AS "categories!"
+        FROM article_links al
+        JOIN claimed ON al.url = claimed.url
+        LEFT JOIN UNNEST($8::text[], $9::int4[]) AS fp(feed, priority) ON fp.feed = al.feed
+        ORDER BY
+            CASE WHEN $7 = 'feed_priority' THEN COALESCE(fp.priority, 0) END DESC,
+            CASE WHEN $7 = 'oldest' THEN al.pub_date END ASC,
+            al.pub_date DESC
+        "#,
+        worker_id,
+        batch_size,
+        timeout.as_secs_f64(),
+        filter.sources.as_deref(),
+        filter.exclude_sources.as_deref(),
+        filter.groups.as_deref(),
+        options.order.as_str(),
+        &priority_feeds,
+        &priorities
+    )
+    .fetch_all(pool)
+    .await
+    .with_context(|| format!("バックログのクレームに失敗: {}", worker_id))?;
+
+    Ok(links)
+}
+
+/// # 概要
+/// ワーカーのクレームを解放する（処理完了時・処理を中断する時に呼ぶ）。
+///
+/// # 戻り値
+/// 解放したクレーム数
+pub async fn release_backlog_claims(
+    pool: &PgPool,
+    worker_id: &str,
+    urls: &[String],
+) -> Result<u64> {
+    let result = sqlx::query!(
+        "DELETE FROM backlog_claims WHERE worker_id = $1 AND url = ANY($2)",
+        worker_id,
+        urls
+    )
+    .execute(pool)
+    .await
+    .with_context(|| format!("クレームの解放に失敗: {}", worker_id))?;
+
+    Ok(result.rows_affected())
+}
+
+/// # 概要
+/// 期限切れのクレームを削除する（落ちたワーカーのクレームの掃除用）。
+///
+/// 期限切れのクレームは削除しなくても他のワーカーが取り直せるため、定期的な掃除のみに使う。
+pub async fn release_expired_backlog_claims(pool: &PgPool) -> Result<u64> {
+    let result = sqlx::query!("DELETE FROM backlog_claims WHERE expires_at < now()")
+        .execute(pool)
+        .await
+        .context("期限切れクレームの削除に失敗")?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rss::{store_article_links, BacklogOrder};
+
+    async fn store_backlog(count: usize, pool: &PgPool) -> Result<()> {
+        let links: Vec<ArticleLink> = (0..count)
+            .map(|i| ArticleLink {
+                url: format!("https://claim.example.com/{}", i),
+                title: format!("記事{}", i),
+                pub_date: chrono::Utc::now() - chrono::Duration::minutes(i as i64),
+                source: "test".to_string(),
+                ..Default::default()
+            })
+            .collect();
+        store_article_links(&links, pool).await
+    }
+
+    #[sqlx::test]
+    async fn test_claim_backlog_batch(pool: PgPool) -> Result<(), anyhow::Error> {
+        store_backlog(5, &pool).await?;
+
+        let a = claim_backlog_batch(&pool, "worker-a", 3).await?;
+        let b = claim_backlog_batch(&pool, "worker-b", 3).await?;
+        assert_eq!(a.len(), 3);
+        assert_eq!(b.len(), 2, "worker-aのクレーム分は取得しない");
+        assert!(b.iter().all(|link| !a.iter().any(|l| l.url == link.url)));
+        assert!(claim_backlog_batch(&pool, "worker-c", 3).await?.is_empty());
+
+        // 解放したリンクは再びクレームできる
+        let urls: Vec<String> = a.iter().map(|link| link.url.clone()).collect();
+        assert_eq!(release_backlog_claims(&pool, "worker-b", &urls).await?, 0);
+        assert_eq!(release_backlog_claims(&pool, "worker-a", &urls).await?, 3);
+        assert_eq!(claim_backlog_batch(&pool, "worker-c", 10).await?.len(), 3);
+
+        println!("✅ バックログのクレームテスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_claim_backlog_batch_with_filter(pool: PgPool) -> Result<(), anyhow::Error> {
+        store_backlog(2, &pool).await?;
+        let other = ArticleLink {
+            url: "https://claim.example.com/other".to_string(),
+            title: "別の取得元".to_string(),
+            pub_date: chrono::Utc::now(),
+            source: "other".to_string(),
+            ..Default::default()
+        };
+        store_article_links(&[other], &pool).await?;
+
+        let options = BacklogOptions::default();
+        let excluded = BacklogLinkFilter {
+            exclude_sources: Some(vec!["test".to_string()]),
+            ..Default::default()
+        };
+        let claimed = claim_backlog_batch_with(&pool, "worker-a", 10, &excluded, &options).await?;
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].source, "other");
+
+        let only_test = BacklogLinkFilter {
+            sources: Some(vec!["test".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            claim_backlog_batch_with(&pool, "worker-b", 10, &only_test, &options)
+                .await?
+                .len(),
+            2
+        );
+
+        println!("✅ 絞り込み付きのバックログのクレームテスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_claim_backlog_batch_order(pool: PgPool) -> Result<(), anyhow::Error> {
+        // store_backlogのリンクは番号が大きいほど古い
+        store_backlog(3, &pool).await?;
+        let oldest = BacklogOptions {
+            order: BacklogOrder::Oldest,
+            ..Default::default()
+        };
+        let claimed =
+            claim_backlog_batch_with(&pool, "worker-a", 2, &BacklogLinkFilter::default(), &oldest)
+                .await?;
+        let urls: Vec<&str> = claimed.iter().map(|link| link.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            ["https://claim.example.com/2", "https://claim.example.com/1"],
+            "古い順にクレームする"
+        );
+
+        println!("✅ バックログのクレームの順序テスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_claim_backlog_concurrently(pool: PgPool) -> Result<(), anyhow::Error> {
+        store_backlog(20, &pool).await?;
+
+        let (a, b) = tokio::join!(
+            claim_backlog_batch(&pool, "worker-a", 15),
+            claim_backlog_batch(&pool, "worker-b", 15)
+        );
+        let (a, b) = (a?, b?);
+        assert!(b.iter().all(|link| !a.iter().any(|l| l.url == link.url)));
+        assert!(a.len() + b.len() <= 20);
+
+        println!("✅ 同時クレームの重複なしテスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_expired_claims(pool: PgPool) -> Result<(), anyhow::Error> {
+        store_backlog(2, &pool).await?;
+
+        let stale = claim_backlog_batch_with_timeout(
+            &pool,
+            "crashed",
+            2,
+            &BacklogLinkFilter::default(),
+            &BacklogOptions::default(),
+            Duration::ZERO,
+        )
+        .await?;
+        assert_eq!(stale.len(), 2);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        // 期限切れのクレームは取り直せる
+        let taken = claim_backlog_batch(&pool, "worker-a", 1).await?;
+        assert_eq!(taken.len(), 1);
+        assert_eq!(release_expired_backlog_claims(&pool).await?, 1);
+
+        println!("✅ 期限切れクレームテスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use url::Url;
+
+// blocked_domainsテーブルの1行に対応する収集禁止ドメイン
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BlockedDomain {
+    pub domain: String,
+    pub reason: String,
+    pub blocked_at: DateTime<Utc>,
+}
+
+/// 収集禁止ドメインの一覧（まとめて判定する場合に1回だけ読み込んで使う）
+#[derive(Debug, Clone, Default)]
+pub struct DomainBlocklist {
+    domains: Vec<BlockedDomain>,
+}
+
+impl DomainBlocklist {
+    pub fn new(domains: Vec<BlockedDomain>) -> Self {
+        Self { domains }
+    }
+
+    /// URLが収集禁止ドメイン（またはそのサブドメイン）に該当する場合、該当ドメインを返す
+    ///
+    /// ホストを解析できないURLは判定できないため該当なしとする。
+    pub fn find(&self, url: &str) -> Option<&BlockedDomain> {
+        let host = url_host(url)?;
+        self.domains.iter().find(|blocked| {
+            host == blocked.domain || host.ends_with(&format!(".{}", blocked.domain))
+        })
+    }
+
+    pub fn is_blocked(&self, url: &str) -> bool {
+        self.find(url).is_some()
+    }
+
+    /// 収集禁止ドメインを除いた要素と、除外した件数を返す
+    pub fn retain_allowed<T>(&self, items: Vec<T>, url: impl Fn(&T) -> &str) -> (Vec<T>, usize) {
+        let total = items.len();
+        let allowed: Vec<T> = items
+            .into_iter()
+            .filter(|item| !self.is_blocked(url(item)))
+            .collect();
+        let blocked = total - allowed.len();
+        (allowed, blocked)
+    }
+}
+
+/// ドメインを比較用に正規化する（小文字化・前後の空白と末尾のドットを除去）
+pub fn normalize_domain(domain: &str) -> String {
+    domain.trim().trim_end_matches('.').to_lowercase()
+}
+
+fn url_host(url: &str) -> Option<String> {
+    Url::parse(url).ok()?.host_str().map(normalize_domain)
+}
+
+/// ドメインを収集禁止にする。既に登録済みの場合は理由を更新する。
+pub async fn block_domain(domain: &str, reason: &str, pool: &PgPool) -> Result<()> {
+    let domain = normalize_domain(domain);
+    if domain.is_empty() {
+        bail!("ブロックするドメインが空です");
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO blocked_domains (domain, reason)
+        VALUES ($1, $2)
+        ON CONFLICT (domain) DO UPDATE SET reason = EXCLUDED.reason
+        "#,
+        domain,
+        reason
+    )
+    .execute(pool)
+    .await
+    .with_context(|| format!("ドメインのブロック登録に失敗: {}", domain))?;
+
+    Ok(())
+}
+
+/// # 概要
+/// ドメインの収集禁止を解除する。
+///
+/// # 戻り値
+/// 登録されていたドメインを解除した場合は`true`
+pub async fn unblock_domain(domain: &str, pool: &PgPool) -> Result<bool> {
+    let result = sqlx::query!(
+        "DELETE FROM blocked_domains WHERE domain = $1",
+        normalize_domain(domain)
+    )
+    .execute(pool)
+    .await
+    .with_context(|| format!("ドメインのブロック解除に失敗: {}", domain))?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// 収集禁止ドメインの一覧を読み込む
+pub async fn load_domain_blocklist(pool: &PgPool) -> Result<DomainBlocklist> {
+    let domains = sqlx::query_as!(
+        BlockedDomain,
+        "SELECT domain, reason, blocked_at FROM blocked_domains ORDER BY domain"
+    )
+    .fetch_all(pool)
+    .await
+    .context("収集禁止ドメインの読み込みに失敗")?;
+
+    Ok(DomainBlocklist::new(domains))
+}
+
+/// URLが収集禁止ドメインに該当する場合、該当ドメインを返す
+pub async fn find_blocked_domain(url: &str, pool: &PgPool) -> Result<Option<BlockedDomain>> {
+    let blocklist = load_domain_blocklist(pool).await?;
+    Ok(blocklist.find(url).cloned())
+}
+
+/// # 概要
+/// URLを収集してよいか確認する共通ガード。
+///
+/// リンク収集・記事取得・手動登録・webhook受信の各入口で呼び出し、
+/// 収集禁止ドメインに該当する場合はエラーを返す。
+pub async fn ensure_url_allowed(url: &str, pool: &PgPool) -> Result<()> {
+    if let Some(blocked) = find_blocked_domain(url, pool).await? {
+        bail!(
+            "収集禁止ドメインのURLです: {}（{}: {}）",
+            url,
+            blocked.domain,
+            blocked.reason
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocklist(domains: &[&str]) -> DomainBlocklist {
+        DomainBlocklist::new(
+            domains
+                .iter()
+                .map(|domain| BlockedDomain {
+                    domain: domain.to_string(),
+                    reason: "テスト".to_string(),
+                    blocked_at: Utc::now(),
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_domain_blocklist_matching() {
+        let list = blocklist(&["blocked.com"]);
+
+        assert!(list.is_blocked("https://blocked.com/a"));
+        assert!(
+            list.is_blocked("https://WWW.Blocked.com/a"),
+            "サブドメインも対象"
+        );
+        assert!(
+            !list.is_blocked("https://notblocked.com/a"),
+            "部分一致は対象外"
+        );
+        assert!(!list.is_blocked("https://blocked.com.example.org/a"));
+        assert!(!list.is_blocked("not a url"));
+
+        let (allowed, blocked) =
+            list.retain_allowed(vec!["https://blocked.com/1", "https://ok.com/1"], |url| url);
+        assert_eq!(allowed, vec!["https://ok.com/1"]);
+        assert_eq!(blocked, 1);
+    }
+
+    #[sqlx::test]
+    async fn test_ensure_url_allowed(pool: PgPool) -> Result<(), anyhow::Error> {
+        block_domain(" Blocked.COM ", "利用規約でクロール禁止", &pool).await?;
+
+        ensure_url_allowed("https://ok.example.com/a", &pool).await?;
+        let err = ensure_url_allowed("https://news.blocked.com/a", &pool)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("利用規約でクロール禁止"));
+
+        assert!(unblock_domain("blocked.com", &pool).await?);
+        ensure_url_allowed("https://news.blocked.com/a", &pool).await?;
+        assert!(!unblock_domain("blocked.com", &pool).await?);
+
+        println!("✅ 収集禁止ドメインガードテスト成功");
+        Ok(())
+    }
+}
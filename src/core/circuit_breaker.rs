@@ -0,0 +1,402 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::{BTreeMap, VecDeque};
+use url::Url;
+
+// ドメイン毎のサーキットブレーカの設定
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// 失敗率を計算する直近の取得件数
+    pub window: usize,
+    /// 遮断の判定に必要な最小件数（これ未満では遮断しない）
+    pub min_samples: usize,
+    /// 直近の失敗率がこれ以上になったら遮断する
+    pub failure_threshold: f64,
+    /// 遮断してから試行を再開（half-open）するまでの時間
+    pub open_duration: Duration,
+    /// 状態の復元に使う取得履歴の期間
+    pub history_period: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            window: 20,
+            min_samples: 10,
+            failure_threshold: 0.8,
+            open_duration: Duration::minutes(30),
+            history_period: Duration::days(1),
+        }
+    }
+}
+
+/// サーキットブレーカの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// 通常どおり取得する
+    Closed,
+    /// 取得を停止中
+    Open,
+    /// 停止期間が過ぎ、1件だけ試行して再開するかを判断する
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
+// 1ドメイン分の状態
+#[derive(Debug, Clone, Default)]
+struct DomainCircuit {
+    /// 直近の取得結果（trueが失敗）
+    recent: VecDeque<bool>,
+    /// 遮断した日時（閉じている場合は`None`）
+    opened_at: Option<DateTime<Utc>>,
+    /// half-openの試行を実行中かどうか
+    trial_in_flight: bool,
+    /// 遮断によって取得しなかった件数
+    skipped: usize,
+}
+
+impl DomainCircuit {
+    fn failure_rate(&self) -> f64 {
+        if self.recent.is_empty() {
+            return 0.0;
+        }
+        let failures = self.recent.iter().filter(|failed| **failed).count();
+        failures as f64 / self.recent.len() as f64
+    }
+}
+
+// メトリクス・レポート用のドメイン毎の状態
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitSnapshot {
+    pub domain: String,
+    pub state: CircuitState,
+    pub failure_rate: f64,
+    pub samples: usize,
+    pub opened_at: Option<DateTime<Utc>>,
+    pub skipped: usize,
+}
+
+/// ドメイン毎の取得成功率に基づくサーキットブレーカ
+///
+/// 直近`window`件の失敗率が`failure_threshold`以上になったドメインを遮断（open）し、
+/// `open_duration`経過後に1件だけ試行（half-open）する。試行が成功すれば再開（closed）し、
+/// 失敗すれば再び遮断する。
+#[derive(Debug, Clone)]
+pub struct DomainCircuitBreakers {
+    config: CircuitBreakerConfig,
+    circuits: BTreeMap<String, DomainCircuit>,
+}
+
+impl DomainCircuitBreakers {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            circuits: BTreeMap::new(),
+        }
+    }
+
+    /// 指定日時におけるドメインの状態
+    pub fn state(&self, domain: &str, now: DateTime<Utc>) -> CircuitState {
+        match self.circuits.get(domain) {
+            Some(circuit) => self.circuit_state(circuit, now),
+            None => CircuitState::Closed,
+        }
+    }
+
+    fn circuit_state(&self, circuit: &DomainCircuit, now: DateTime<Utc>) -> CircuitState {
+        match circuit.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if now - opened_at >= self.config.open_duration => {
+                CircuitState::HalfOpen
+            }
+            Some(_) => CircuitState::Open,
+        }
+    }
+
+    /// ドメインの直近の失敗率
+    pub fn failure_rate(&self, domain: &str) -> f64 {
+        self.circuits
+            .get(domain)
+            .map_or(0.0, DomainCircuit::failure_rate)
+    }
+
+    /// ドメインの記事を取得してよいかを判定する（取得しない場合はスキップ件数に数える）
+    ///
+    /// half-openの場合は試行の1件だけを許可する。
+    pub fn allow(&mut self, domain: &str, now: DateTime<Utc>) -> bool {
+        let Some(circuit) = self.circuits.get(domain) else {
+            return true;
+        };
+        let state = self.circuit_state(circuit, now);
+        let circuit = self.circuits.get_mut(domain).expect("存在確認済み");
+        let allowed = match state {
+            CircuitState::Closed => true,
+            CircuitState::Open => false,
+            CircuitState::HalfOpen => !std::mem::replace(&mut circuit.trial_in_flight, true),
+        };
+        if !allowed {
+            circuit.skipped += 1;
+        }
+        allowed
+    }
+
+    /// 取得結果を記録する
+    ///
+    /// # 戻り値
+    /// 状態が変化した場合は変化後の状態
+    pub fn record(
+        &mut self,
+        domain: &str,
+        success: bool,
+        at: DateTime<Utc>,
+    ) -> Option<CircuitState> {
+        let before = self.state(domain, at);
+        let config = &self.config;
+        let circuit = self.circuits.entry(domain.to_string()).or_default();
+
+        match before {
+            CircuitState::Closed => {
+                circuit.recent.push_back(!success);
+                while circuit.recent.len() > config.window.max(1) {
+                    circuit.recent.pop_front();
+                }
+                if circuit.recent.len() >= config.min_samples
+                    && circuit.failure_rate() >= config.failure_threshold
+                {
+                    circuit.opened_at = Some(at);
+                }
+            }
+            CircuitState::HalfOpen => {
+                circuit.trial_in_flight = false;
+                if success {
+                    circuit.recent.clear();
+                    circuit.opened_at = None;
+                } else {
+                    circuit.opened_at = Some(at);
+                }
+            }
+            // 遮断前から実行中だった取得の結果は判定に使わない
+            CircuitState::Open => {}
+        }
+
+        let after = self.state(domain, at);
+        (after != before).then_some(after)
+    }
+
+    /// 全ドメインの状態（失敗率の高い順）
+    pub fn snapshots(&self, now: DateTime<Utc>) -> Vec<CircuitSnapshot> {
+        let mut snapshots: Vec<CircuitSnapshot> = self
+            .circuits
+            .iter()
+            .map(|(domain, circuit)| CircuitSnapshot {
+                domain: domain.clone(),
+                state: self.circuit_state(circuit, now),
+                failure_rate: circuit.failure_rate(),
+                samples: circuit.recent.len(),
+                opened_at: circuit.opened_at,
+                skipped: circuit.skipped,
+            })
+            .collect();
+        snapshots.sort_by(|a, b| b.failure_rate.total_cmp(&a.failure_rate));
+        snapshots
+    }
+
+    /// 遮断中（half-openを含む）のドメイン数
+    pub fn open_count(&self, now: DateTime<Utc>) -> usize {
+        self.circuits
+            .values()
+            .filter(|circuit| self.circuit_state(circuit, now) != CircuitState::Closed)
+            .count()
+    }
+
+    /// 遮断中・スキップのあったドメインを表示用の行に整形する
+    pub fn format_lines(&self, now: DateTime<Utc>) -> Vec<String> {
+        let snapshots: Vec<CircuitSnapshot> = self
+            .snapshots(now)
+            .into_iter()
+            .filter(|s| s.state != CircuitState::Closed || s.skipped > 0)
+            .collect();
+        if snapshots.is_empty() {
+            return Vec::new();
+        }
+
+        let mut lines = vec![format!(
+            "サーキットブレーカ: 遮断中{}ドメイン",
+            self.open_count(now)
+        )];
+        for s in snapshots {
+            let resume = s
+                .opened_at
+                .filter(|_| s.state == CircuitState::Open)
+                .map(|at| {
+                    format!(
+                        "、{}まで停止",
+                        (at + self.config.open_duration).to_rfc3339()
+                    )
+                })
+                .unwrap_or_default();
+            lines.push(format!(
+                "  {}: {}（失敗率{:.0}%/{}件、スキップ{}件{}）",
+                s.domain,
+                s.state.as_str(),
+                s.failure_rate * 100.0,
+                s.samples,
+                s.skipped,
+                resume
+            ));
+        }
+        lines
+    }
+}
+
+/// URLからサーキットブレーカのドメインを取り出す（解析できない場合は空文字）
+pub fn url_domain(url: &str) -> String {
+    Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_lowercase))
+        .unwrap_or_default()
+}
+
+/// # 概要
+/// 取得試行の履歴（`article_fetch_attempts`）を再生してサーキットブレーカの状態を復元する。
+///
+/// 実行毎に状態を作り直しても、前回までの失敗による遮断が引き継がれる。
+/// 各ドメインの直近`window`件（`history_period`以内）のみを使う。
+pub async fn load_domain_circuit_breakers(
+    config: CircuitBreakerConfig,
+    pool: &PgPool,
+) -> Result<DomainCircuitBreakers> {
+    let since = Utc::now() - config.history_period;
+    let attempts = sqlx::query!(
+        r#"
+        SELECT domain AS "domain!", status_code AS "status_code!", attempted_at AS "attempted_at!"
+        FROM (
+            SELECT
+                lower(substring(url from '^[A-Za-z]+://([^/:?#]+)')) AS domain,
+                status_code,
+                attempted_at,
+                id,
+                row_number() OVER (
+                    PARTITION BY lower(substring(url from '^[A-Za-z]+://([^/:?#]+)'))
+                    ORDER BY attempted_at DESC, id DESC
+                ) AS rn
+            FROM article_fetch_attempts
+            WHERE attempted_at >= $1
+        ) t
+        WHERE domain IS NOT NULL AND rn <= $2
+        ORDER BY attempted_at, id
+        "#,
+        since,
+        config.window as i64
+    )
+    .fetch_all(pool)
+    .await
+    .context("取得試行履歴の取得に失敗")?;
+
+    let mut breakers = DomainCircuitBreakers::new(config);
+    for attempt in attempts {
+        breakers.record(
+            &attempt.domain,
+            attempt.status_code == 200,
+            attempt.attempted_at,
+        );
+    }
+    Ok(breakers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::article::{store_article_contents, ArticleContent};
+
+    fn test_config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            window: 5,
+            min_samples: 4,
+            failure_threshold: 0.8,
+            open_duration: Duration::minutes(10),
+            history_period: Duration::days(1),
+        }
+    }
+
+    #[test]
+    fn test_circuit_breaker_transitions() {
+        let mut breakers = DomainCircuitBreakers::new(test_config());
+        let now = Utc::now();
+        let domain = "flaky.example.com";
+
+        // 最小件数に達するまでは遮断しない
+        for _ in 0..3 {
+            assert_eq!(breakers.record(domain, false, now), None);
+        }
+        assert_eq!(
+            breakers.record(domain, false, now),
+            Some(CircuitState::Open)
+        );
+        assert!(!breakers.allow(domain, now));
+        assert!(breakers.allow("other.example.com", now));
+
+        // 停止期間後は1件だけ試行し、失敗すると再び遮断する
+        let later = now + Duration::minutes(11);
+        assert_eq!(breakers.state(domain, later), CircuitState::HalfOpen);
+        assert!(breakers.allow(domain, later));
+        assert!(!breakers.allow(domain, later), "試行は1件のみ");
+        assert_eq!(
+            breakers.record(domain, false, later),
+            Some(CircuitState::Open)
+        );
+
+        // 試行が成功すると再開する
+        let much_later = later + Duration::minutes(11);
+        assert!(breakers.allow(domain, much_later));
+        assert_eq!(
+            breakers.record(domain, true, much_later),
+            Some(CircuitState::Closed)
+        );
+        assert_eq!(breakers.failure_rate(domain), 0.0);
+
+        let snapshot = &breakers.snapshots(much_later)[0];
+        assert_eq!(snapshot.skipped, 2);
+        assert!(breakers
+            .format_lines(much_later)
+            .iter()
+            .any(|line| line.contains("スキップ2件")));
+    }
+
+    #[sqlx::test]
+    async fn test_load_domain_circuit_breakers(pool: PgPool) -> Result<(), anyhow::Error> {
+        let article = |url: &str, status_code: i32| ArticleContent {
+            url: url.to_string(),
+            timestamp: Utc::now(),
+            status_code,
+            content: "内容".to_string(),
+            raw_html: None,
+        };
+        let articles: Vec<ArticleContent> = (0..5)
+            .map(|i| article(&format!("https://down.example.com/{}", i), 500))
+            .chain((0..5).map(|i| article(&format!("https://up.example.com/{}", i), 200)))
+            .collect();
+        store_article_contents(&articles, &pool).await?;
+
+        let breakers = load_domain_circuit_breakers(test_config(), &pool).await?;
+        let now = Utc::now();
+        assert_eq!(breakers.state("down.example.com", now), CircuitState::Open);
+        assert_eq!(breakers.state("up.example.com", now), CircuitState::Closed);
+        assert_eq!(breakers.open_count(now), 1);
+
+        println!("✅ サーキットブレーカ状態復元テスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,309 @@
+use crate::core::article::{ErrorCategory, ERROR_STATUS_CODE};
+use crate::core::feed::Feed;
+use crate::core::rss::classify_feed_error;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::fmt;
+use std::str::FromStr;
+
+/// 検索件数の既定の上限
+const DEFAULT_SEARCH_LIMIT: i64 = 100;
+
+// エラーの発生した対象の種類（collection_errors.entity_typeに保存する）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionEntityType {
+    /// フィード（entity_keyは`Feed::key`）
+    Feed,
+    /// 記事（entity_keyはURL）
+    Article,
+    /// 個別の対象に紐付かないタスクの処理（entity_keyは処理名）
+    Task,
+}
+
+impl CollectionEntityType {
+    pub const ALL: [CollectionEntityType; 3] = [Self::Feed, Self::Article, Self::Task];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Feed => "feed",
+            Self::Article => "article",
+            Self::Task => "task",
+        }
+    }
+}
+
+impl fmt::Display for CollectionEntityType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CollectionEntityType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|entity_type| entity_type.as_str() == s)
+            .with_context(|| format!("不明なエラーの対象: {}", s))
+    }
+}
+
+// 記録するエラー（collection_errorsに追加する1行）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewCollectionError {
+    pub entity_type: CollectionEntityType,
+    pub entity_key: String,
+    pub error_category: ErrorCategory,
+    pub message: String,
+}
+
+impl NewCollectionError {
+    /// フィードの取得・保存のエラー（カテゴリはエラーの種類から判定する）
+    pub fn feed(feed: &Feed, error: &anyhow::Error) -> Self {
+        Self {
+            entity_type: CollectionEntityType::Feed,
+            entity_key: feed.key(),
+            error_category: classify_feed_error(error),
+            message: format!("{:#}", error),
+        }
+    }
+
+    /// 記事の取得の失敗（カテゴリは取得結果から判定したもの）
+    pub fn article(url: &str, error_category: ErrorCategory, message: impl fmt::Display) -> Self {
+        Self {
+            entity_type: CollectionEntityType::Article,
+            entity_key: url.to_string(),
+            error_category,
+            message: message.to_string(),
+        }
+    }
+
+    /// 記事の保存などの処理のエラー（カテゴリはメッセージから判定する）
+    pub fn article_error(url: &str, error: &anyhow::Error) -> Self {
+        let message = format!("{:#}", error);
+        Self {
+            entity_type: CollectionEntityType::Article,
+            entity_key: url.to_string(),
+            error_category: classify_message(&message),
+            message,
+        }
+    }
+
+    /// 個別の対象に紐付かない処理のエラー（カテゴリはメッセージから判定する）
+    pub fn task(name: &str, error: &anyhow::Error) -> Self {
+        let message = format!("{:#}", error);
+        Self {
+            entity_type: CollectionEntityType::Task,
+            entity_key: name.to_string(),
+            error_category: classify_message(&message),
+            message,
+        }
+    }
+}
+
+/// ステータスコードの無いエラーのカテゴリをメッセージのキーワードで判定する
+fn classify_message(message: &str) -> ErrorCategory {
+    ErrorCategory::classify(ERROR_STATUS_CODE, message).unwrap_or(ErrorCategory::Unknown)
+}
+
+// collection_errorsテーブルの1行
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionError {
+    pub id: i64,
+    pub entity_type: CollectionEntityType,
+    pub entity_key: String,
+    pub error_category: ErrorCategory,
+    pub message: String,
+    pub occurred_at: DateTime<Utc>,
+    /// 発生時に実行中だったワークフロー（job_runs.id）
+    pub job_id: Option<i64>,
+}
+
+// エラーの検索条件（指定した条件をすべて満たすエラーを返す）
+#[derive(Debug, Clone, Default)]
+pub struct CollectionErrorQuery {
+    pub entity_type: Option<CollectionEntityType>,
+    /// フィードのキー・記事のURL・タスクの処理名の完全一致
+    pub entity_key: Option<String>,
+    pub error_category: Option<ErrorCategory>,
+    pub job_id: Option<i64>,
+    pub occurred_from: Option<DateTime<Utc>>,
+    pub occurred_to: Option<DateTime<Utc>>,
+    /// 取得する最大件数（省略時は100件）
+    pub limit: Option<i64>,
+}
+
+/// # 概要
+/// 収集処理のエラーをcollection_errorsに記録する。
+///
+/// 実行中のワークフロー（job_runsの未完了の最新の記録）があれば`job_id`に紐付ける。
+/// エラーの記録は付随情報のため、失敗しても呼び出し元の処理は止めずにログに残す。
+pub async fn record_collection_errors(errors: &[NewCollectionError], pool: &PgPool) {
+    if errors.is_empty() {
+        return;
+    }
+    let entity_types: Vec<&str> = errors.iter().map(|e| e.entity_type.as_str()).collect();
+    let entity_keys: Vec<&str> = errors.iter().map(|e| e.entity_key.as_str()).collect();
+    let categories: Vec<&str> = errors.iter().map(|e| e.error_category.as_str()).collect();
+    let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO collection_errors (entity_type, entity_key, error_category, message, job_id)
+        SELECT t.entity_type, t.entity_key, t.error_category, t.message, (
+            SELECT id FROM job_runs WHERE finished_at IS NULL ORDER BY id DESC LIMIT 1
+        )
+        FROM UNNEST($1::text[], $2::text[], $3::text[], $4::text[])
+            AS t(entity_type, entity_key, error_category, message)
+        "#,
+        &entity_types as &[&str],
+        &entity_keys as &[&str],
+        &categories as &[&str],
+        &messages as &[&str]
+    )
+    .execute(pool)
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(
+            errors = errors.len(),
+            error = format!("{:#}", e),
+            "収集エラーの記録エラー"
+        );
+    }
+}
+
+/// # 概要
+/// 記録した収集処理のエラーを新しい順に検索する。
+pub async fn search_errors(
+    query: Option<CollectionErrorQuery>,
+    pool: &PgPool,
+) -> Result<Vec<CollectionError>> {
+    let query = query.unwrap_or_default();
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, entity_type, entity_key, error_category, message, occurred_at, job_id
+        FROM collection_errors
+        WHERE ($1::text IS NULL OR entity_type = $1)
+            AND ($2::text IS NULL OR entity_key = $2)
+            AND ($3::text IS NULL OR error_category = $3)
+            AND ($4::int8 IS NULL OR job_id = $4)
+            AND ($5::timestamptz IS NULL OR occurred_at >= $5)
+            AND ($6::timestamptz IS NULL OR occurred_at < $6)
+        ORDER BY occurred_at DESC, id DESC
+        LIMIT $7
+        "#,
+        query.entity_type.map(|t| t.as_str()),
+        query.entity_key,
+        query.error_category.map(|c| c.as_str()),
+        query.job_id,
+        query.occurred_from,
+        query.occurred_to,
+        query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT)
+    )
+    .fetch_all(pool)
+    .await
+    .context("収集エラーの検索に失敗")?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(CollectionError {
+                id: row.id,
+                entity_type: row.entity_type.parse()?,
+                entity_key: row.entity_key,
+                error_category: row.error_category.parse()?,
+                message: row.message,
+                occurred_at: row.occurred_at,
+                job_id: row.job_id,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::job_run::{finish_job_run, start_job_run, JOB_STATUS_SUCCEEDED};
+
+    #[sqlx::test]
+    async fn test_record_and_search_errors(pool: PgPool) -> Result<(), anyhow::Error> {
+        let feed = Feed {
+            group: "news".to_string(),
+            name: "world".to_string(),
+            ..Default::default()
+        };
+        record_collection_errors(
+            &[NewCollectionError::feed(
+                &feed,
+                &anyhow::anyhow!("connection timed out"),
+            )],
+            &pool,
+        )
+        .await;
+
+        let run = start_job_run("rss_workflow", &pool).await?;
+        record_collection_errors(
+            &[
+                NewCollectionError::article(
+                    "https://example.com/1",
+                    ErrorCategory::NotFound,
+                    "HTTP 404",
+                ),
+                NewCollectionError::task("reprocess", &anyhow::anyhow!("再処理予定の更新に失敗")),
+            ],
+            &pool,
+        )
+        .await;
+        finish_job_run(run.id, JOB_STATUS_SUCCEEDED, &pool).await?;
+
+        let all = search_errors(None, &pool).await?;
+        assert_eq!(all.len(), 3);
+        let feed_error = all
+            .iter()
+            .find(|e| e.entity_type == CollectionEntityType::Feed)
+            .unwrap();
+        assert_eq!(feed_error.entity_key, "news/world");
+        assert_eq!(feed_error.error_category, ErrorCategory::Timeout);
+        assert_eq!(feed_error.job_id, None, "ワークフロー外のエラー");
+
+        let in_job = search_errors(
+            Some(CollectionErrorQuery {
+                job_id: Some(run.id),
+                ..Default::default()
+            }),
+            &pool,
+        )
+        .await?;
+        assert_eq!(in_job.len(), 2);
+
+        let articles = search_errors(
+            Some(CollectionErrorQuery {
+                entity_type: Some(CollectionEntityType::Article),
+                error_category: Some(ErrorCategory::NotFound),
+                ..Default::default()
+            }),
+            &pool,
+        )
+        .await?;
+        assert_eq!(articles.len(), 1);
+        assert_eq!(articles[0].entity_key, "https://example.com/1");
+        assert_eq!(articles[0].message, "HTTP 404");
+
+        let limited = search_errors(
+            Some(CollectionErrorQuery {
+                limit: Some(1),
+                ..Default::default()
+            }),
+            &pool,
+        )
+        .await?;
+        assert_eq!(limited.len(), 1);
+
+        println!("✅ 収集エラー記録・検索テスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,319 @@
+use crate::core::api_access::{GrpcConfig, ServerConfig};
+use crate::core::article::{
+    set_article_query_limits, ArticleDebugConfig, ArticleQueryLimits, ArticleWriteBatchConfig,
+    RecrawlPolicy, ReprocessPolicy, RetryPolicy,
+};
+use crate::core::exit_code::ExitCodePolicy;
+use crate::core::feed::source::FeedSourcesConfig;
+use crate::core::feed::FeedsConfig;
+use crate::core::health::HealthConfig;
+use crate::core::invariant::InvariantConfig;
+use crate::core::ng_word::NgWordConfig;
+use crate::core::replay::ReplayConfig;
+use crate::core::rss::{BacklogLinkFilter, BacklogOptions};
+use crate::core::scraper::ScraperConfig;
+use crate::core::series::SeriesConfig;
+use crate::core::storage_quota::StorageQuotaConfig;
+use crate::core::topic::TopicFilterConfig;
+use crate::infra::api::http::HttpClientConfig;
+use crate::infra::api::robots::RobotsConfig;
+use crate::infra::metrics::MetricsConfig;
+use crate::infra::notify::NotifyConfig;
+use crate::infra::parser::{default_date_formats, set_date_formats, DateFormat};
+use crate::infra::storage::file::{load_yaml_from_file, normalize_path};
+use crate::infra::telemetry::TelemetryConfig;
+use crate::types::ConfigError;
+use chrono::FixedOffset;
+use serde::Deserialize;
+use std::path::Path;
+use std::time::Duration;
+
+/// アプリ設定ファイルのパス
+pub const APP_CONFIG_PATH: &str = "config/app.yaml";
+
+// config/app.yamlの構造に対応するアプリ設定
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AppConfig {
+    #[serde(default)]
+    pub date_parser: DateParserConfig,
+    /// 分散トレースのエクスポータ設定
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+    /// タイトルのキーワードによる記事取得対象の選別
+    #[serde(default)]
+    pub topic_filter: TopicFilterConfig,
+    /// 連載記事をまとめるシリーズキーの抽出ルール
+    #[serde(default)]
+    pub series: SeriesConfig,
+    /// ヘルスチェック（/healthz, /readyz）の設定
+    #[serde(default)]
+    pub health: HealthConfig,
+    /// ワークフローの段階間の不変条件チェック
+    #[serde(default)]
+    pub invariants: InvariantConfig,
+    /// フィード取得に使うHTTPクライアントの接続設定
+    #[serde(default)]
+    pub http: HttpClientConfig,
+    /// 記事本文の並列取得の設定
+    #[serde(default)]
+    pub article_fetch: ArticleFetchConfig,
+    /// 記事本文の取得に使うスクレイパー
+    #[serde(default)]
+    pub scraper: ScraperConfig,
+    /// フィード定義の保存先（feeds.yamlまたはfeedsテーブル）
+    #[serde(default)]
+    pub feeds: FeedsConfig,
+    /// フィードリストの外部ソースとの同期
+    #[serde(default)]
+    pub feed_sources: FeedSourcesConfig,
+    /// 収集処理のシミュレーション（過去の収集のリプレイ）
+    #[serde(default)]
+    pub replay: ReplayConfig,
+    /// RESTサーバのAPIキー認証・レート制限
+    #[serde(default)]
+    pub server: ServerConfig,
+    /// gRPCサーバ（grpc feature）の新着記事の配信間隔
+    #[serde(default)]
+    pub grpc: GrpcConfig,
+    /// ワンショット実行（workflow）の終了コードの決め方
+    #[serde(default)]
+    pub exit_code: ExitCodePolicy,
+    /// ワークフローの完了・致命的エラーのWebhook通知
+    #[serde(default)]
+    pub notify: NotifyConfig,
+    /// 記事検索の既定の件数と上限
+    #[serde(default)]
+    pub article_query: ArticleQueryLimits,
+    /// Prometheusメトリクスの出力（textfile collector向けのファイル）
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+}
+
+// 記事本文の並列取得の設定
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArticleFetchConfig {
+    /// 同時に取得する記事数の上限
+    #[serde(default = "default_max_concurrency")]
+    pub max_concurrency: usize,
+    /// レスポンスタイムとエラー率から並列度を自動調整する（falseなら常に上限の並列度で取得）
+    #[serde(default = "default_true")]
+    pub adaptive: bool,
+    /// スクレイパーへの1分あたりのリクエスト数の上限（省略時は制限しない）
+    pub requests_per_minute: Option<u32>,
+    /// バックログ検索の取得元による絞り込み
+    #[serde(default)]
+    pub backlog: BacklogLinkFilter,
+    /// バックログから1回に処理するリンクの件数と順序
+    #[serde(default)]
+    pub backlog_options: BacklogOptions,
+    /// 一時的なエラーの再試行（指数バックオフ）
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    /// 取得に失敗した記事をバックログで再処理する回数と間隔
+    #[serde(default)]
+    pub reprocess: ReprocessPolicy,
+    /// daemonモードでの取得済み記事の再クロール（並列度・レート制限はバックログ処理と別）
+    #[serde(default)]
+    pub recrawl: RecrawlPolicy,
+    /// 取得した本文をまとめて保存する件数と間隔
+    #[serde(default)]
+    pub write_batch: ArticleWriteBatchConfig,
+    /// ドメイン毎の本文の保存容量の上限
+    #[serde(default)]
+    pub storage_quota: StorageQuotaConfig,
+    /// 記事毎の処理ログを記録するデバッグモード
+    #[serde(default)]
+    pub debug: ArticleDebugConfig,
+    /// robots.txtで禁止されたURLの取得を止める
+    #[serde(default)]
+    pub robots: RobotsConfig,
+    /// 本文にNGワードを含む記事の検出と除外
+    #[serde(default)]
+    pub ng_words: NgWordConfig,
+    /// workerモードでLISTENできない間にバックログをポーリングする間隔（秒）
+    #[serde(default = "default_worker_poll_interval_secs")]
+    pub worker_poll_interval_secs: u64,
+}
+
+impl Default for ArticleFetchConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrency: default_max_concurrency(),
+            adaptive: true,
+            requests_per_minute: None,
+            backlog: BacklogLinkFilter::default(),
+            backlog_options: BacklogOptions::default(),
+            retry: RetryPolicy::default(),
+            reprocess: ReprocessPolicy::default(),
+            recrawl: RecrawlPolicy::default(),
+            write_batch: ArticleWriteBatchConfig::default(),
+            storage_quota: StorageQuotaConfig::default(),
+            debug: ArticleDebugConfig::default(),
+            robots: RobotsConfig::default(),
+            ng_words: NgWordConfig::default(),
+            worker_poll_interval_secs: default_worker_poll_interval_secs(),
+        }
+    }
+}
+
+impl ArticleFetchConfig {
+    /// workerモードのポーリング間隔（1秒未満にはしない）
+    pub fn worker_poll_interval(&self) -> Duration {
+        Duration::from_secs(self.worker_poll_interval_secs.max(1))
+    }
+}
+
+fn default_max_concurrency() -> usize {
+    8
+}
+
+fn default_worker_poll_interval_secs() -> u64 {
+    60
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// 日付パースの設定
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DateParserConfig {
+    /// 追加フォーマットを組み込みフォーマットより先に試すかどうか
+    #[serde(default)]
+    pub prioritize_custom: bool,
+    /// 追加で登録する日付フォーマット（定義順に試す）
+    #[serde(default)]
+    pub formats: Vec<CustomDateFormat>,
+}
+
+// 設定ファイルで追加する日付フォーマット
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomDateFormat {
+    /// strftime形式のパターン（例: "%d.%m.%Y %H:%M"）
+    pub pattern: String,
+    /// パターンにタイムゾーンが含まれない場合のUTCオフセット（例: "+09:00"、省略時はUTC）
+    pub utc_offset: Option<String>,
+}
+
+impl DateParserConfig {
+    /// 組み込みフォーマットと追加フォーマットから、試行順に並んだテーブルを構築する
+    pub fn build_formats(&self) -> Result<Vec<DateFormat>, ConfigError> {
+        let mut custom = Vec::with_capacity(self.formats.len());
+        for format in &self.formats {
+            let offset = match format.utc_offset {
+                Some(ref offset) => offset.parse::<FixedOffset>().map_err(|_| {
+                    ConfigError::invalid(
+                        "date_parser.formats.utc_offset",
+                        format!(
+                            "不正なUTCオフセット: {} (pattern: {})",
+                            offset, format.pattern
+                        ),
+                    )
+                })?,
+                None => FixedOffset::east_opt(0).expect("UTCオフセットは常に有効"),
+            };
+            custom.push(DateFormat::from_pattern(&format.pattern, offset));
+        }
+
+        let builtin = default_date_formats();
+        let formats = if self.prioritize_custom {
+            custom.into_iter().chain(builtin).collect()
+        } else {
+            builtin.into_iter().chain(custom).collect()
+        };
+        Ok(formats)
+    }
+}
+
+/// 指定パスからアプリ設定を読み込む（ファイルが存在しない場合はデフォルト設定）
+pub fn load_app_config_from(file_path: impl AsRef<Path>) -> Result<AppConfig, ConfigError> {
+    let file_path = file_path.as_ref();
+    if !normalize_path(file_path).exists() {
+        return Ok(AppConfig::default());
+    }
+    load_yaml_from_file(file_path).map_err(|source| ConfigError::Load {
+        path: file_path.to_path_buf(),
+        source: Box::new(source),
+    })
+}
+
+/// config/app.yamlからアプリ設定を読み込む
+pub fn load_app_config() -> Result<AppConfig, ConfigError> {
+    load_app_config_from(APP_CONFIG_PATH)
+}
+
+/// アプリ設定をプロセス全体に反映する
+///
+/// 日付パースのフォーマットテーブルを設定内容で置き換える。
+pub fn apply_app_config(config: &AppConfig) -> Result<(), ConfigError> {
+    let formats = config.date_parser.build_formats()?;
+    set_date_formats(formats);
+    set_article_query_limits(config.article_query);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::article::{ArticleDebugConfig, RetryPolicy};
+    use crate::core::feed::source::FeedSourcesConfig;
+    use crate::core::health::HealthConfig;
+    use crate::core::topic::TopicFilterConfig;
+    use crate::infra::parser::parse_date_with_formats;
+
+    fn parse_config(yaml: &str) -> AppConfig {
+        serde_yaml::from_str(yaml).expect("設定YAMLの解析に失敗")
+    }
+
+    #[test]
+    fn test_load_app_config_missing_file() {
+        let config = load_app_config_from("non_existent_app.yaml").unwrap();
+        assert!(config.date_parser.formats.is_empty());
+        assert!(!config.date_parser.prioritize_custom);
+    }
+
+    #[test]
+    fn test_load_app_config_file() {
+        // リポジトリに含まれる設定ファイルが解析できること
+        let config = load_app_config();
+        assert!(config.is_ok(), "config/app.yamlの読み込みに失敗");
+        assert!(config.unwrap().date_parser.build_formats().is_ok());
+    }
+
+    #[test]
+    fn test_build_formats_order() {
+        let builtin_len = default_date_formats().len();
+        let yaml = r#"
+date_parser:
+  formats:
+    - pattern: "%d|%m|%Y %H:%M"
+      utc_offset: "+09:00"
+"#;
+        let mut config = parse_config(yaml);
+
+        // デフォルトでは組み込みフォーマットの後ろに追加される
+        let formats = config.date_parser.build_formats().unwrap();
+        assert_eq!(formats.len(), builtin_len + 1);
+        assert_eq!(formats[0], DateFormat::Rfc2822);
+        let parsed = parse_date_with_formats("10|08|2025 21:30", &formats).unwrap();
+        assert_eq!(parsed.to_rfc3339(), "2025-08-10T12:30:00+00:00");
+
+        // prioritize_customで先頭に追加される
+        config.date_parser.prioritize_custom = true;
+        let formats = config.date_parser.build_formats().unwrap();
+        assert!(matches!(formats[0], DateFormat::Naive { .. }));
+        assert_eq!(formats[1], DateFormat::Rfc2822);
+    }
+
+    #[test]
+    fn test_build_formats_invalid_offset() {
+        let yaml = r#"
+date_parser:
+  formats:
+    - pattern: "%d|%m|%Y"
+      utc_offset: "JST"
+"#;
+        let result = parse_config(yaml).date_parser.build_formats();
+        assert!(result.is_err(), "不正なオフセットでエラーにならなかった");
+    }
+}
@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use thiserror::Error;
+
+/// すべて成功した場合の終了コード
+pub const EXIT_SUCCESS: u8 = 0;
+
+/// すべて失敗した場合の終了コード
+pub const EXIT_FAILURE: u8 = 1;
+
+/// 許容する失敗率を超えて一部が失敗した場合の終了コード
+pub const EXIT_PARTIAL_FAILURE: u8 = 2;
+
+// ワンショット実行の結果の分類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunOutcome {
+    /// 失敗が無い（対象が無かった場合を含む）
+    Success,
+    /// 一部が失敗した
+    PartialFailure,
+    /// すべて失敗した
+    Failure,
+}
+
+impl RunOutcome {
+    /// 対象数と失敗数から結果を分類する
+    pub fn classify(failed: usize, total: usize) -> Self {
+        if failed == 0 {
+            Self::Success
+        } else if failed >= total {
+            Self::Failure
+        } else {
+            Self::PartialFailure
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::PartialFailure => "partial_failure",
+            Self::Failure => "failure",
+        }
+    }
+}
+
+impl fmt::Display for RunOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// 終了コードの決め方（config/app.yamlのexit_codeに対応）
+//
+// 全成功は0、全失敗は1、部分失敗は失敗率が`max_failure_percent`以下なら0、超えたら2を返す。
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ExitCodePolicy {
+    /// 部分失敗を成功（終了コード0）として扱う失敗率の上限（%、0なら1件でも失敗すれば2）
+    #[serde(default)]
+    pub max_failure_percent: f64,
+}
+
+impl ExitCodePolicy {
+    /// 対象数と失敗数から終了コードを決める
+    pub fn exit_code(&self, failed: usize, total: usize) -> u8 {
+        match RunOutcome::classify(failed, total) {
+            RunOutcome::Success => EXIT_SUCCESS,
+            RunOutcome::Failure => EXIT_FAILURE,
+            RunOutcome::PartialFailure => {
+                let failure_percent = failed as f64 / total as f64 * 100.0;
+                if failure_percent <= self.max_failure_percent {
+                    EXIT_SUCCESS
+                } else {
+                    EXIT_PARTIAL_FAILURE
+                }
+            }
+        }
+    }
+}
+
+/// 終了コードを指定して実行を終えるエラー（CLIは`code`をプロセスの終了コードにする）
+#[derive(Debug, Error)]
+#[error("{message}")]
+pub struct ExitStatusError {
+    pub code: u8,
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code_policy() {
+        assert_eq!(RunOutcome::classify(0, 0), RunOutcome::Success);
+        assert_eq!(RunOutcome::classify(0, 5), RunOutcome::Success);
+        assert_eq!(RunOutcome::classify(2, 5), RunOutcome::PartialFailure);
+        assert_eq!(RunOutcome::classify(5, 5), RunOutcome::Failure);
+
+        let strict = ExitCodePolicy::default();
+        assert_eq!(strict.exit_code(0, 10), EXIT_SUCCESS);
+        assert_eq!(strict.exit_code(1, 10), EXIT_PARTIAL_FAILURE);
+        assert_eq!(strict.exit_code(10, 10), EXIT_FAILURE);
+
+        let tolerant = ExitCodePolicy {
+            max_failure_percent: 20.0,
+        };
+        assert_eq!(tolerant.exit_code(2, 10), EXIT_SUCCESS, "20%までは許容");
+        assert_eq!(tolerant.exit_code(3, 10), EXIT_PARTIAL_FAILURE);
+        assert_eq!(
+            tolerant.exit_code(10, 10),
+            EXIT_FAILURE,
+            "全失敗は閾値に関係なく1"
+        );
+
+        println!("✅ 終了コードのポリシーテスト成功");
+    }
+}
@@ -0,0 +1,309 @@
+use crate::core::article::{StepStats, TrackingLinkCleaner};
+use anyhow::{bail, Context, Result};
+use sqlx::PgPool;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Read;
+use std::str::FromStr;
+use url::Url;
+
+/// 取り込むファイルの形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsFormat {
+    /// `url`列と指標毎の数値列を持つCSV（ヘッダ行必須）
+    Csv,
+    /// `url`と指標毎の数値を持つオブジェクトの配列
+    Json,
+}
+
+impl FromStr for MetricsFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => bail!("非対応の形式です: {}", s),
+        }
+    }
+}
+
+// 取り込み元の1行分の指標
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalMetricRecord {
+    pub url: String,
+    pub metrics: BTreeMap<String, f64>,
+}
+
+// 取り込み結果
+#[derive(Debug, Clone, Default)]
+pub struct MetricsIngestReport {
+    /// 読み込んだ行数
+    pub records: usize,
+    /// 記事リンクと突合できた行数
+    pub matched: usize,
+    /// 突合できなかったURL
+    pub unmatched_urls: Vec<String>,
+    /// 保存した(URL, 指標)の組の数
+    pub stored: usize,
+}
+
+/// # 概要
+/// 外部のアクセス解析の指標を読み込み、記事リンクと突合して保存する。
+///
+/// URLはトラッキングパラメータ・スキーム・`www.`・末尾の`/`などの違いを無視して突合する。
+/// 同じ記事に突合された行の指標は合算し、既に保存済みの指標は取り込んだ値で置き換える。
+pub async fn ingest_external_metrics<R: Read>(
+    reader: R,
+    format: MetricsFormat,
+    pool: &PgPool,
+) -> Result<MetricsIngestReport> {
+    let records = parse_metric_records(reader, format)?;
+    let mut report = MetricsIngestReport {
+        records: records.len(),
+        ..Default::default()
+    };
+
+    let link_urls = sqlx::query_scalar!("SELECT url FROM article_links WHERE deleted_at IS NULL")
+        .fetch_all(pool)
+        .await
+        .context("記事リンクの取得に失敗")?;
+    let links_by_key: HashMap<String, String> = link_urls
+        .into_iter()
+        .filter_map(|url| Some((url_match_key(&url)?, url)))
+        .collect();
+
+    let mut merged: BTreeMap<(String, String), f64> = BTreeMap::new();
+    for record in records {
+        let Some(link_url) = url_match_key(&record.url).and_then(|key| links_by_key.get(&key))
+        else {
+            report.unmatched_urls.push(record.url);
+            continue;
+        };
+        report.matched += 1;
+        for (metric, value) in record.metrics {
+            *merged.entry((link_url.clone(), metric)).or_default() += value;
+        }
+    }
+
+    if merged.is_empty() {
+        return Ok(report);
+    }
+
+    let mut urls = Vec::with_capacity(merged.len());
+    let mut metrics = Vec::with_capacity(merged.len());
+    let mut values = Vec::with_capacity(merged.len());
+    for ((url, metric), value) in merged {
+        urls.push(url);
+        metrics.push(metric);
+        values.push(value);
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO article_external_metrics (url, metric, value)
+        SELECT * FROM UNNEST($1::text[], $2::text[], $3::float8[])
+        ON CONFLICT (url, metric) DO UPDATE SET
+            value = EXCLUDED.value,
+            updated_at = CURRENT_TIMESTAMP
+        "#,
+        &urls,
+        &metrics,
+        &values
+    )
+    .execute(pool)
+    .await
+    .context("外部指標の保存に失敗")?;
+
+    report.stored = urls.len();
+    Ok(report)
+}
+
+/// 取り込み元のデータを行毎の指標に変換する
+///
+/// 数値として解釈できない列・空の値は無視する。`url`が無い行はエラーにする。
+pub fn parse_metric_records<R: Read>(
+    reader: R,
+    format: MetricsFormat,
+) -> Result<Vec<ExternalMetricRecord>> {
+    match format {
+        MetricsFormat::Csv => {
+            let mut csv_reader = csv::Reader::from_reader(reader);
+            let headers = csv_reader
+                .headers()
+                .context("CSVのヘッダ行の読み込みに失敗")?
+                .clone();
+            let url_index = headers
+                .iter()
+                .position(|h| h.trim() == "url")
+                .context("CSVにurl列がありません")?;
+
+            csv_reader
+                .records()
+                .enumerate()
+                .map(|(i, row)| {
+                    let row = row.with_context(|| format!("CSVの{}行目の読み込みに失敗", i + 2))?;
+                    let url = row.get(url_index).unwrap_or_default().trim().to_string();
+                    if url.is_empty() {
+                        bail!("CSVの{}行目にurlがありません", i + 2);
+                    }
+                    let metrics = headers
+                        .iter()
+                        .zip(row.iter())
+                        .enumerate()
+                        .filter(|(index, _)| *index != url_index)
+                        .filter_map(|(_, (name, value))| {
+                            Some((name.trim().to_string(), value.trim().parse().ok()?))
+                        })
+                        .collect();
+                    Ok(ExternalMetricRecord { url, metrics })
+                })
+                .collect()
+        }
+        MetricsFormat::Json => {
+            let rows: Vec<serde_json::Map<String, serde_json::Value>> =
+                serde_json::from_reader(reader).context("JSONの読み込みに失敗")?;
+
+            rows.into_iter()
+                .enumerate()
+                .map(|(i, row)| {
+                    let url = row
+                        .get("url")
+                        .and_then(|v| v.as_str())
+                        .map(str::trim)
+                        .filter(|url| !url.is_empty())
+                        .with_context(|| format!("JSONの{}件目にurlがありません", i + 1))?
+                        .to_string();
+                    let metrics = row
+                        .iter()
+                        .filter(|(name, _)| name.as_str() != "url")
+                        .filter_map(|(name, value)| Some((name.clone(), value.as_f64()?)))
+                        .collect();
+                    Ok(ExternalMetricRecord { url, metrics })
+                })
+                .collect()
+        }
+    }
+}
+
+/// URLを突合用のキーに正規化する（解析できない場合は`None`）
+///
+/// トラッキングパラメータとフラグメントを除去し、スキーム・ホストの`www.`・
+/// パス末尾の`/`・クエリパラメータの順序の違いを無視する。
+pub fn url_match_key(raw: &str) -> Option<String> {
+    let cleaned = TrackingLinkCleaner::default()
+        .clean_url(raw.trim(), &mut StepStats::new())
+        .unwrap_or_else(|| raw.trim().to_string());
+    let url = Url::parse(&cleaned).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+
+    let host = url.host_str()?.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    let path = url.path().trim_end_matches('/');
+    let mut query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+    query.sort();
+
+    let mut key = format!("{}{}", host, path);
+    if !query.is_empty() {
+        let query: Vec<String> = query
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        key.push('?');
+        key.push_str(&query.join("&"));
+    }
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rss::{store_article_links, ArticleLink};
+
+    #[test]
+    fn test_url_match_key() {
+        let key = url_match_key("https://example.com/news/1").unwrap();
+        assert_eq!(key, "example.com/news/1");
+        for variant in [
+            "http://www.Example.com/news/1/",
+            "https://example.com/news/1?utm_source=analytics#top",
+        ] {
+            assert_eq!(url_match_key(variant).as_deref(), Some(key.as_str()));
+        }
+        assert_eq!(
+            url_match_key("https://example.com/?b=2&a=1"),
+            url_match_key("https://example.com/?a=1&b=2")
+        );
+        assert_eq!(url_match_key("not a url"), None);
+    }
+
+    #[test]
+    fn test_parse_metric_records() {
+        let csv =
+            "url,page_views,title\nhttps://example.com/1,120,記事1\nhttps://example.com/2,,記事2\n";
+        let records = parse_metric_records(csv.as_bytes(), MetricsFormat::Csv).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].metrics.get("page_views"), Some(&120.0));
+        assert!(records[1].metrics.is_empty(), "空の値と数値以外の列は無視");
+
+        let json = r#"[{"url": "https://example.com/1", "page_views": 3, "avg_seconds": 42.5}]"#;
+        let records = parse_metric_records(json.as_bytes(), MetricsFormat::Json).unwrap();
+        assert_eq!(records[0].metrics.len(), 2);
+
+        assert!(parse_metric_records("page_views\n1\n".as_bytes(), MetricsFormat::Csv).is_err());
+        assert!(
+            parse_metric_records(r#"[{"page_views": 1}]"#.as_bytes(), MetricsFormat::Json).is_err()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_ingest_external_metrics(pool: PgPool) -> Result<(), anyhow::Error> {
+        let links: Vec<ArticleLink> = ["https://example.com/news/1", "https://example.com/news/2"]
+            .iter()
+            .map(|url| ArticleLink {
+                url: url.to_string(),
+                title: "記事".to_string(),
+                pub_date: chrono::Utc::now(),
+                source: "rss".to_string(),
+                ..Default::default()
+            })
+            .collect();
+        store_article_links(&links, &pool).await?;
+
+        let csv = "\
+url,page_views
+https://example.com/news/1,100
+https://www.example.com/news/1/?utm_source=newsletter,20
+http://example.com/news/2,5
+https://unknown.example.com/a,1
+";
+        let report = ingest_external_metrics(csv.as_bytes(), MetricsFormat::Csv, &pool).await?;
+        assert_eq!(report.records, 4);
+        assert_eq!(report.matched, 3);
+        assert_eq!(report.unmatched_urls, vec!["https://unknown.example.com/a"]);
+        assert_eq!(report.stored, 2);
+
+        let value = sqlx::query_scalar!(
+            "SELECT value FROM article_external_metrics WHERE url = $1 AND metric = 'page_views'",
+            "https://example.com/news/1"
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(value, 120.0, "同じ記事の行は合算される");
+
+        // 再取り込みでは置き換えられる
+        let json = r#"[{"url": "https://example.com/news/1", "page_views": 150}]"#;
+        ingest_external_metrics(json.as_bytes(), MetricsFormat::Json, &pool).await?;
+        let value = sqlx::query_scalar!(
+            "SELECT value FROM article_external_metrics WHERE url = $1 AND metric = 'page_views'",
+            "https://example.com/news/1"
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(value, 150.0);
+
+        println!("✅ 外部指標取り込みテスト成功");
+        Ok(())
+    }
+}
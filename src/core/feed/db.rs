@@ -0,0 +1,359 @@
+use super::{feeds_from_map, Feed, FeedMap, FeedQuery, FeedUrlPatterns};
+use crate::infra::parser::parse_article_url;
+use crate::infra::storage::file::load_yaml_from_file;
+use crate::types::{ConfigError, InfraError, StoreError};
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+use std::path::Path;
+
+// feedsテーブルの1行（無効にしたフィードも含む）
+#[derive(Debug, Clone)]
+pub struct FeedRecord {
+    pub feed: Feed,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+// update_feedで変更する項目（Noneの項目は変更しない）
+#[derive(Debug, Clone, Default)]
+pub struct FeedUpdate {
+    pub url: Option<String>,
+    pub fallback_urls: Option<Vec<String>>,
+    pub enabled: Option<bool>,
+    pub fetch_interval: Option<u32>,
+    pub url_patterns: Option<Vec<String>>,
+}
+
+/// group・nameが空でなく、URLがhttp/httpsで、期待URLパターンが正規表現として正しいことを確認する
+fn validate_feed(feed: &Feed) -> Result<(), InfraError> {
+    if feed.group.trim().is_empty() || feed.name.trim().is_empty() {
+        return Err(
+            ConfigError::invalid("feeds", format!("groupとnameは必須です: {}", feed)).into(),
+        );
+    }
+    for url in feed.candidate_urls() {
+        parse_article_url(url)?;
+    }
+    FeedUrlPatterns::for_feed(feed)?;
+    validate_fetch_interval(feed.fetch_interval)
+}
+
+/// 取得間隔は1分以上（DBにはINTEGERで保存する）
+fn validate_fetch_interval(fetch_interval: Option<u32>) -> Result<(), InfraError> {
+    match fetch_interval {
+        Some(minutes) if minutes == 0 || minutes > i32::MAX as u32 => Err(ConfigError::invalid(
+            "fetch_interval",
+            format!("1以上の分数を指定してください: {}", minutes),
+        )
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+/// # 概要
+/// フィードをfeedsテーブルに保存する。
+///
+/// 同じgroup・nameのフィードがあればURL・フォールバックURL・取得間隔・期待URLパターンを更新する（有効・無効は変更しない）。
+pub async fn store_feed(feed: &Feed, pool: &PgPool) -> Result<(), InfraError> {
+    validate_feed(feed)?;
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(StoreError::database("データベース接続の取得に失敗しました"))?;
+    upsert_feed(feed, &mut conn).await?;
+    Ok(())
+}
+
+async fn upsert_feed(feed: &Feed, conn: &mut PgConnection) -> Result<(), StoreError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO feeds ("group", name, url, fallback_urls, fetch_interval, url_patterns)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT ("group", name) DO UPDATE SET
+            url = EXCLUDED.url,
+            fallback_urls = EXCLUDED.fallback_urls,
+            fetch_interval = EXCLUDED.fetch_interval,
+            url_patterns = EXCLUDED.url_patterns
+        "#,
+        feed.group,
+        feed.name,
+        feed.rss_link,
+        &feed.fallback_urls,
+        feed.fetch_interval.map(|minutes| minutes as i32),
+        &feed.url_patterns
+    )
+    .execute(conn)
+    .await
+    .map_err(StoreError::database(format!(
+        "フィードの保存に失敗: {}",
+        feed.key()
+    )))?;
+    Ok(())
+}
+
+/// フィードの一部の項目を変更する（フィードが存在しなければ`false`）
+pub async fn update_feed(
+    group: &str,
+    name: &str,
+    update: &FeedUpdate,
+    pool: &PgPool,
+) -> Result<bool, InfraError> {
+    for url in update
+        .url
+        .iter()
+        .chain(update.fallback_urls.iter().flatten())
+    {
+        parse_article_url(url)?;
+    }
+    if let Some(url_patterns) = &update.url_patterns {
+        FeedUrlPatterns::compile(url_patterns)?;
+    }
+    validate_fetch_interval(update.fetch_interval)?;
+    let result = sqlx::query!(
+        r#"
+        UPDATE feeds SET
+            url = COALESCE($3, url),
+            fallback_urls = COALESCE($4, fallback_urls),
+            enabled = COALESCE($5, enabled),
+            fetch_interval = COALESCE($6, fetch_interval),
+            url_patterns = COALESCE($7, url_patterns)
+        WHERE "group" = $1 AND name = $2
+        "#,
+        group,
+        name,
+        update.url,
+        update.fallback_urls.as_deref(),
+        update.enabled,
+        update.fetch_interval.map(|minutes| minutes as i32),
+        update.url_patterns.as_deref()
+    )
+    .execute(pool)
+    .await
+    .map_err(StoreError::database(format!(
+        "フィードの更新に失敗: {}/{}",
+        group, name
+    )))?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// フィードを無効にする（収集対象から外すが、定義と収集済みのリンクは残す）
+///
+/// フィードが存在しなければ`false`を返す。
+pub async fn disable_feed(group: &str, name: &str, pool: &PgPool) -> Result<bool, StoreError> {
+    let result = sqlx::query!(
+        r#"UPDATE feeds SET enabled = FALSE WHERE "group" = $1 AND name = $2"#,
+        group,
+        name
+    )
+    .execute(pool)
+    .await
+    .map_err(StoreError::database(format!(
+        "フィードの無効化に失敗: {}/{}",
+        group, name
+    )))?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// # 概要
+/// feedsテーブルの有効なフィードを`search_feeds`と同じ条件で絞り込んで返す。
+///
+/// 並びはgroup, nameの順。
+pub async fn search_feeds_db(
+    query: Option<FeedQuery>,
+    pool: &PgPool,
+) -> Result<Vec<Feed>, StoreError> {
+    let query = query.unwrap_or_default();
+    let rows = sqlx::query!(
+        r#"
+        SELECT "group", name, url, fallback_urls, fetch_interval, url_patterns
+        FROM feeds
+        WHERE enabled
+            AND ($1::text IS NULL OR "group" = $1)
+            AND ($2::text IS NULL OR name = $2)
+        ORDER BY "group", name
+        "#,
+        query.group,
+        query.name
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(StoreError::database("フィードの検索に失敗"))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| Feed {
+            group: row.group,
+            name: row.name,
+            rss_link: row.url,
+            fallback_urls: row.fallback_urls,
+            fetch_interval: row.fetch_interval.map(|minutes| minutes as u32),
+            url_patterns: row.url_patterns,
+            last_fetched_at: None,
+        })
+        .collect())
+}
+
+/// feedsテーブルの全フィード（無効にしたフィードを含む、group, nameの順）
+pub async fn list_feed_records(pool: &PgPool) -> Result<Vec<FeedRecord>, StoreError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT "group", name, url, fallback_urls, fetch_interval, url_patterns, enabled, created_at
+        FROM feeds
+        ORDER BY "group", name
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(StoreError::database("フィード一覧の取得に失敗"))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| FeedRecord {
+            feed: Feed {
+                group: row.group,
+                name: row.name,
+                rss_link: row.url,
+                fallback_urls: row.fallback_urls,
+                fetch_interval: row.fetch_interval.map(|minutes| minutes as u32),
+                url_patterns: row.url_patterns,
+                last_fetched_at: None,
+            },
+            enabled: row.enabled,
+            created_at: row.created_at,
+        })
+        .collect())
+}
+
+/// # 概要
+/// feeds.yaml形式のファイルのフィードをfeedsテーブルに取り込み、取り込んだ件数を返す。
+///
+/// 初期データの投入用。全件を検証してから1つのトランザクションで保存し、
+/// 既存のフィードはURL・フォールバックURL・取得間隔・期待URLパターンのみ更新する（無効にしたフィードは無効のまま）。
+pub async fn import_feeds_from_yaml(
+    file_path: impl AsRef<Path>,
+    pool: &PgPool,
+) -> Result<usize, InfraError> {
+    let feed_map: FeedMap = load_yaml_from_file(file_path)?;
+    let feeds = feeds_from_map(feed_map);
+    for feed in &feeds {
+        validate_feed(feed)?;
+    }
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(StoreError::database("トランザクションの開始に失敗しました"))?;
+    for feed in &feeds {
+        upsert_feed(feed, &mut tx).await?;
+    }
+    tx.commit().await.map_err(StoreError::database(
+        "フィードの取り込みのコミットに失敗しました",
+    ))?;
+    Ok(feeds.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(group: &str, name: &str, url: &str) -> Feed {
+        Feed {
+            group: group.to_string(),
+            name: name.to_string(),
+            rss_link: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_feed_crud(pool: PgPool) -> Result<(), anyhow::Error> {
+        store_feed(&feed("news", "top", "https://example.com/top.xml"), &pool).await?;
+        store_feed(
+            &feed("news", "world", "https://example.com/world.xml"),
+            &pool,
+        )
+        .await?;
+        store_feed(&feed("tech", "top", "https://tech.example.com/rss"), &pool).await?;
+        assert!(
+            store_feed(&feed("news", "bad", "ftp://example.com/rss"), &pool)
+                .await
+                .is_err(),
+            "http/https以外のURLは保存しない"
+        );
+
+        let news = search_feeds_db(Some(FeedQuery::from_group("news")), &pool).await?;
+        assert_eq!(
+            news.iter().map(|f| f.name.as_str()).collect::<Vec<_>>(),
+            vec!["top", "world"]
+        );
+
+        // 同じgroup・nameで保存するとURLを更新する
+        store_feed(&feed("news", "top", "https://example.com/top2.xml"), &pool).await?;
+        let update = FeedUpdate {
+            fallback_urls: Some(vec!["https://mirror.example.com/top.xml".to_string()]),
+            ..Default::default()
+        };
+        assert!(update_feed("news", "top", &update, &pool).await?);
+        assert!(!update_feed("news", "missing", &update, &pool).await?);
+        let top = search_feeds_db(
+            Some(FeedQuery {
+                group: Some("news".to_string()),
+                name: Some("top".to_string()),
+            }),
+            &pool,
+        )
+        .await?;
+        assert_eq!(top[0].rss_link, "https://example.com/top2.xml");
+        assert_eq!(
+            top[0].fallback_urls,
+            vec!["https://mirror.example.com/top.xml"]
+        );
+
+        // 期待URLパターンは正規表現として正しいもののみ保存する
+        let update = FeedUpdate {
+            url_patterns: Some(vec![r"^https://example\.com/news/".to_string()]),
+            ..Default::default()
+        };
+        assert!(update_feed("news", "top", &update, &pool).await?);
+        let invalid = FeedUpdate {
+            url_patterns: Some(vec!["(unclosed".to_string()]),
+            ..Default::default()
+        };
+        assert!(update_feed("news", "top", &invalid, &pool).await.is_err());
+        let top = search_feeds_db(Some(FeedQuery::from_group("news")), &pool).await?;
+        assert_eq!(top[0].url_patterns, vec![r"^https://example\.com/news/"]);
+
+        // 無効にしたフィードは検索に含めないが、一覧には残る
+        assert!(disable_feed("news", "world", &pool).await?);
+        assert!(!disable_feed("news", "missing", &pool).await?);
+        assert_eq!(search_feeds_db(None, &pool).await?.len(), 2);
+        let records = list_feed_records(&pool).await?;
+        assert_eq!(records.len(), 3);
+        assert!(
+            !records
+                .iter()
+                .find(|r| r.feed.name == "world")
+                .unwrap()
+                .enabled
+        );
+
+        // 取り込みは無効にしたフィードを有効に戻さない
+        let path = std::env::temp_dir().join(format!("feeds_import_{}.yaml", std::process::id()));
+        std::fs::write(
+            &path,
+            "news:\n  world: https://example.com/world.xml\n  local: https://example.com/local.xml\n",
+        )?;
+        let imported = import_feeds_from_yaml(&path, &pool).await?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(imported, 2);
+        let names: Vec<String> = search_feeds_db(Some(FeedQuery::from_group("news")), &pool)
+            .await?
+            .into_iter()
+            .map(|f| f.name)
+            .collect();
+        assert_eq!(names, vec!["local", "top"]);
+
+        println!("✅ フィードのDB管理テスト成功");
+        Ok(())
+    }
+}
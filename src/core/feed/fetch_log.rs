@@ -0,0 +1,254 @@
+use super::Feed;
+use crate::core::article::ErrorCategory;
+use crate::core::rss::classify_feed_error;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::fmt;
+use std::str::FromStr;
+
+/// 検索件数の既定の上限
+const DEFAULT_SEARCH_LIMIT: i64 = 100;
+
+// フィードの取得結果（feed_fetch_logs.resultに保存する）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedFetchResult {
+    /// 取得してリンクを保存できた
+    Success,
+    /// 304 Not Modifiedのため解析を省略した
+    NotModified,
+    /// 取得・解析・保存に失敗した
+    Failed,
+}
+
+impl FeedFetchResult {
+    pub const ALL: [FeedFetchResult; 3] = [Self::Success, Self::NotModified, Self::Failed];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::NotModified => "not_modified",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl fmt::Display for FeedFetchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FeedFetchResult {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|result| result.as_str() == s)
+            .with_context(|| format!("不明なフィードの取得結果: {}", s))
+    }
+}
+
+// 記録するフィードの取得結果（feed_fetch_logsに追加する1行）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewFeedFetchLog {
+    pub feed: String,
+    pub url: String,
+    pub result: FeedFetchResult,
+    pub error_category: Option<ErrorCategory>,
+    pub error_message: Option<String>,
+    pub link_count: i32,
+}
+
+impl NewFeedFetchLog {
+    /// 取得できたURLと抽出したリンク数
+    pub fn success(feed: &Feed, url: &str, link_count: usize) -> Self {
+        Self {
+            feed: feed.key(),
+            url: url.to_string(),
+            result: FeedFetchResult::Success,
+            error_category: None,
+            error_message: None,
+            link_count: i32::try_from(link_count).unwrap_or(i32::MAX),
+        }
+    }
+
+    /// 304 Not Modifiedが返されたURL
+    pub fn not_modified(feed: &Feed, url: &str) -> Self {
+        Self {
+            result: FeedFetchResult::NotModified,
+            ..Self::success(feed, url, 0)
+        }
+    }
+
+    /// 失敗したURL（取得に失敗した場合はメインURL）とエラー（カテゴリはエラーの種類から判定する）
+    pub fn failed(feed: &Feed, url: &str, link_count: usize, error: &anyhow::Error) -> Self {
+        Self {
+            result: FeedFetchResult::Failed,
+            error_category: Some(classify_feed_error(error)),
+            error_message: Some(format!("{:#}", error)),
+            ..Self::success(feed, url, link_count)
+        }
+    }
+}
+
+// feed_fetch_logsテーブルの1行
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedFetchLog {
+    pub id: i64,
+    pub feed: String,
+    pub url: String,
+    pub fetched_at: DateTime<Utc>,
+    pub result: FeedFetchResult,
+    pub error_category: Option<ErrorCategory>,
+    pub error_message: Option<String>,
+    pub link_count: i32,
+}
+
+// 取得履歴の検索条件（指定した条件をすべて満たす履歴を返す）
+#[derive(Debug, Clone, Default)]
+pub struct FeedFetchLogQuery {
+    /// フィードのキー（group/name）の完全一致
+    pub feed: Option<String>,
+    pub result: Option<FeedFetchResult>,
+    pub fetched_from: Option<DateTime<Utc>>,
+    pub fetched_to: Option<DateTime<Utc>>,
+    /// 取得する最大件数（省略時は100件）
+    pub limit: Option<i64>,
+}
+
+/// # 概要
+/// フィードの取得結果をfeed_fetch_logsに記録する。
+///
+/// 履歴は付随情報のため、記録に失敗しても呼び出し元の処理は止めずにログに残す。
+pub async fn record_feed_fetch_log(log: &NewFeedFetchLog, pool: &PgPool) {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO feed_fetch_logs (feed, url, result, error_category, error_message, link_count)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        log.feed,
+        log.url,
+        log.result.as_str(),
+        log.error_category.map(|c| c.as_str()),
+        log.error_message,
+        log.link_count
+    )
+    .execute(pool)
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(
+            feed = %log.feed,
+            error = format!("{:#}", e),
+            "フィード取得履歴の記録エラー"
+        );
+    }
+}
+
+/// # 概要
+/// フィードの取得履歴を新しい順に検索する。
+pub async fn search_feed_fetch_logs(
+    query: Option<FeedFetchLogQuery>,
+    pool: &PgPool,
+) -> Result<Vec<FeedFetchLog>> {
+    let query = query.unwrap_or_default();
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, feed, url, fetched_at, result, error_category, error_message, link_count
+        FROM feed_fetch_logs
+        WHERE ($1::text IS NULL OR feed = $1)
+            AND ($2::text IS NULL OR result = $2)
+            AND ($3::timestamptz IS NULL OR fetched_at >= $3)
+            AND ($4::timestamptz IS NULL OR fetched_at < $4)
+        ORDER BY fetched_at DESC, id DESC
+        LIMIT $5
+        "#,
+        query.feed,
+        query.result.map(|r| r.as_str()),
+        query.fetched_from,
+        query.fetched_to,
+        query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT)
+    )
+    .fetch_all(pool)
+    .await
+    .context("フィード取得履歴の検索に失敗")?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(FeedFetchLog {
+                id: row.id,
+                feed: row.feed,
+                url: row.url,
+                fetched_at: row.fetched_at,
+                result: row.result.parse()?,
+                error_category: row.error_category.map(|c| c.parse()).transpose()?,
+                error_message: row.error_message,
+                link_count: row.link_count,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    async fn test_record_and_search_feed_fetch_logs(pool: PgPool) -> Result<(), anyhow::Error> {
+        let feed = Feed {
+            group: "news".to_string(),
+            name: "world".to_string(),
+            rss_link: "https://example.com/rss".to_string(),
+            ..Default::default()
+        };
+        let logs = [
+            NewFeedFetchLog::success(&feed, "https://mirror.example.com/rss", 12),
+            NewFeedFetchLog::not_modified(&feed, &feed.rss_link),
+            NewFeedFetchLog::failed(
+                &feed,
+                &feed.rss_link,
+                0,
+                &anyhow::anyhow!("HTTPリクエストがタイムアウト"),
+            ),
+        ];
+        for log in &logs {
+            record_feed_fetch_log(log, &pool).await;
+        }
+
+        let all = search_feed_fetch_logs(None, &pool).await?;
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].result, FeedFetchResult::Failed, "新しい順");
+        assert_eq!(all[0].error_category, Some(ErrorCategory::Timeout));
+        assert_eq!(all[2].url, "https://mirror.example.com/rss");
+        assert_eq!(all[2].link_count, 12);
+        assert_eq!(all[2].error_message, None);
+
+        let failed = search_feed_fetch_logs(
+            Some(FeedFetchLogQuery {
+                feed: Some("news/world".to_string()),
+                result: Some(FeedFetchResult::Failed),
+                ..Default::default()
+            }),
+            &pool,
+        )
+        .await?;
+        assert_eq!(failed.len(), 1);
+
+        let other = search_feed_fetch_logs(
+            Some(FeedFetchLogQuery {
+                feed: Some("news/other".to_string()),
+                ..Default::default()
+            }),
+            &pool,
+        )
+        .await?;
+        assert!(other.is_empty());
+
+        println!("✅ フィード取得履歴の記録・検索テスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,641 @@
+use crate::infra::storage::file::load_yaml_from_file;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::Path;
+
+pub mod db;
+pub mod fetch_log;
+pub mod opml;
+#[cfg(feature = "collector")]
+pub mod source;
+pub mod url_pattern;
+
+pub use db::{
+    disable_feed, import_feeds_from_yaml, list_feed_records, search_feeds_db, store_feed,
+    update_feed, FeedRecord, FeedUpdate,
+};
+pub use fetch_log::{
+    record_feed_fetch_log, search_feed_fetch_logs, FeedFetchLog, FeedFetchLogQuery,
+    FeedFetchResult, NewFeedFetchLog,
+};
+pub use opml::{export_feeds_to_opml, feeds_to_opml, import_feeds_from_opml, parse_opml};
+pub use url_pattern::FeedUrlPatterns;
+
+/// `search_feeds`が読み込むフィード設定ファイルのパス
+pub const FEEDS_PATH: &str = "config/feeds.yaml";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Feed {
+    pub group: String,
+    pub name: String,
+    pub rss_link: String,
+    /// `rss_link`の取得に失敗した場合に順に試すミラーURL（FeedBurnerなど）
+    #[serde(default)]
+    pub fallback_urls: Vec<String>,
+    /// 取得間隔（分）。スケジューリングモードで前回の取得からこの時間が経つまで取得しない（Noneは毎回取得）
+    #[serde(default)]
+    pub fetch_interval: Option<u32>,
+    /// 記事リンクの期待URLパターン（正規表現）。いずれにも一致しないリンクは警告として数えて除外する（空なら検証しない）
+    #[serde(default)]
+    pub url_patterns: Vec<String>,
+    /// 前回フィードを取得できた日時（`load_last_fetched_at`で読み込む）
+    #[serde(default)]
+    pub last_fetched_at: Option<DateTime<Utc>>,
+}
+
+impl Feed {
+    /// フィードを一意に表すキー（"group/name"）
+    pub fn key(&self) -> String {
+        format!("{}/{}", self.group, self.name)
+    }
+
+    /// 取得を試す順のURL（メインURL、フォールバックURLの順）
+    pub fn candidate_urls(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.rss_link.as_str()).chain(self.fallback_urls.iter().map(String::as_str))
+    }
+
+    /// `now`の時点で取得期限が来ているか（取得間隔が未設定、または未取得なら常に期限）
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        match (self.fetch_interval, self.last_fetched_at) {
+            (Some(interval), Some(last_fetched_at)) => {
+                last_fetched_at + Duration::minutes(i64::from(interval)) <= now
+            }
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for Feed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{} ({})", self.group, self.name, self.rss_link)
+    }
+}
+
+// Feed検索のフィルター条件を表す構造体
+#[derive(Debug, Default)]
+pub struct FeedQuery {
+    pub group: Option<String>,
+    pub name: Option<String>,
+}
+
+impl FeedQuery {
+    pub fn from_group(group: &str) -> Self {
+        Self {
+            group: Some(group.to_string()),
+            name: None,
+        }
+    }
+}
+
+// フィード定義の保存先
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedStorage {
+    /// config/feeds.yaml（変更にはデプロイが必要）
+    #[default]
+    Yaml,
+    /// feedsテーブル（運用中に追加・無効化できる）
+    Db,
+}
+
+// フィード定義の設定（config/app.yamlのfeedsに対応）
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FeedsConfig {
+    #[serde(default)]
+    pub storage: FeedStorage,
+    /// 取得期限の来たフィード（fetch_intervalを参照）のみリンクを収集する
+    #[serde(default)]
+    pub scheduled: bool,
+}
+
+// YAMLファイルのフィード1件分（URLのみ、またはフォールバックURL・取得間隔・期待URLパターン付き）
+//
+// ```yaml
+// top: https://example.com/rss.xml
+// world:
+//   url: https://example.com/world.xml
+//   fallback_urls:
+//     - https://feeds.feedburner.com/example-world
+// weekly:
+//   url: https://example.com/weekly.xml
+//   fetch_interval: 1440
+// tech:
+//   url: https://example.com/tech.xml
+//   url_patterns:
+//     - ^https://example\.com/tech/
+// ```
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum FeedEntry {
+    Url(String),
+    WithFallback {
+        url: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        fallback_urls: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        fetch_interval: Option<u32>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        url_patterns: Vec<String>,
+    },
+}
+
+// YAMLファイルの構造に対応する型
+type FeedMap = HashMap<String, HashMap<String, FeedEntry>>;
+
+/// YAMLファイルからフィード情報を読み込み、Feedのベクタとして返す
+pub fn load_feeds_from_yaml(file_path: impl AsRef<Path>) -> Result<Vec<Feed>> {
+    let file_path = file_path.as_ref();
+    let feed_map: FeedMap = load_yaml_from_file(file_path).with_context(|| {
+        format!(
+            "フィードYAMLファイルの読み込みに失敗: {}",
+            file_path.display()
+        )
+    })?;
+    Ok(feeds_from_map(feed_map))
+}
+
+/// feeds.yaml形式の文字列からフィード情報を読み込む
+pub fn parse_feeds_yaml(content: &str) -> Result<Vec<Feed>> {
+    let feed_map: FeedMap = serde_yaml::from_str(content).context("フィードYAMLの解析に失敗")?;
+    Ok(feeds_from_map(feed_map))
+}
+
+/// フィード情報をfeeds.yaml形式の文字列にする（group, nameの順）
+pub fn feeds_to_yaml(feeds: &[Feed]) -> Result<String> {
+    let mut feed_map: BTreeMap<&str, BTreeMap<&str, FeedEntry>> = BTreeMap::new();
+    for feed in feeds {
+        let entry = if feed.fallback_urls.is_empty()
+            && feed.fetch_interval.is_none()
+            && feed.url_patterns.is_empty()
+        {
+            FeedEntry::Url(feed.rss_link.clone())
+        } else {
+            FeedEntry::WithFallback {
+                url: feed.rss_link.clone(),
+                fallback_urls: feed.fallback_urls.clone(),
+                fetch_interval: feed.fetch_interval,
+                url_patterns: feed.url_patterns.clone(),
+            }
+        };
+        feed_map
+            .entry(&feed.group)
+            .or_default()
+            .insert(&feed.name, entry);
+    }
+    serde_yaml::to_string(&feed_map).context("フィードYAMLの生成に失敗")
+}
+
+fn feeds_from_map(feed_map: FeedMap) -> Vec<Feed> {
+    let mut feeds = Vec::new();
+
+    for (group, name_links) in feed_map {
+        for (name, entry) in name_links {
+            let (rss_link, fallback_urls, fetch_interval, url_patterns) = match entry {
+                FeedEntry::Url(url) => (url, Vec::new(), None, Vec::new()),
+                FeedEntry::WithFallback {
+                    url,
+                    fallback_urls,
+                    fetch_interval,
+                    url_patterns,
+                } => (url, fallback_urls, fetch_interval, url_patterns),
+            };
+            feeds.push(Feed {
+                group: group.clone(),
+                name,
+                rss_link,
+                fallback_urls,
+                fetch_interval,
+                url_patterns,
+                last_fetched_at: None,
+            });
+        }
+    }
+    feeds
+}
+
+/// フィード情報を3段階で絞り込み検索する
+/// 1. 絞り込みなし（全件）
+/// 2. groupのみ指定
+/// 3. group & name指定
+///
+/// 内部でfeeds.yamlファイルを読み込み、指定されたクエリでフィルタリングする
+pub fn search_feeds(query: Option<FeedQuery>) -> Result<Vec<Feed>> {
+    let feeds = load_feeds_from_yaml(FEEDS_PATH)?;
+    let query = query.unwrap_or_default();
+
+    let filtered_feeds = feeds
+        .iter()
+        .filter(|feed| {
+            // groupフィルター
+            if let Some(ref group_filter) = query.group {
+                if feed.group != *group_filter {
+                    return false;
+                }
+            }
+
+            // nameフィルター（groupが指定されている場合のみ適用）
+            if let Some(ref name_filter) = query.name {
+                if feed.name != *name_filter {
+                    return false;
+                }
+            }
+
+            true
+        })
+        .cloned()
+        .collect();
+
+    Ok(filtered_feeds)
+}
+
+/// 設定した保存先（feeds.yamlまたはfeedsテーブル）からフィードを絞り込み検索する
+pub async fn search_feeds_from(
+    config: &FeedsConfig,
+    query: Option<FeedQuery>,
+    pool: &PgPool,
+) -> Result<Vec<Feed>> {
+    match config.storage {
+        FeedStorage::Yaml => search_feeds(query),
+        FeedStorage::Db => Ok(search_feeds_db(query, pool).await?),
+    }
+}
+
+/// # 概要
+/// フィード毎の前回の取得日時をfeed_fetch_timesから読み込み、`last_fetched_at`に設定する。
+///
+/// 一度も取得していないフィードは`None`のまま。
+pub async fn load_last_fetched_at(feeds: &mut [Feed], pool: &PgPool) -> Result<()> {
+    let keys: Vec<String> = feeds.iter().map(Feed::key).collect();
+    let rows = sqlx::query!(
+        "SELECT feed, last_fetched_at FROM feed_fetch_times WHERE feed = ANY($1)",
+        &keys
+    )
+    .fetch_all(pool)
+    .await
+    .context("フィードの取得日時の読み込みに失敗")?;
+
+    let fetched_at: HashMap<String, DateTime<Utc>> = rows
+        .into_iter()
+        .map(|row| (row.feed, row.last_fetched_at))
+        .collect();
+    for feed in feeds {
+        feed.last_fetched_at = fetched_at.get(&feed.key()).copied();
+    }
+    Ok(())
+}
+
+/// フィードを取得できた日時を記録する（次回のスケジューリングの基準になる）
+pub async fn record_feed_fetched(
+    feed: &Feed,
+    fetched_at: DateTime<Utc>,
+    pool: &PgPool,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO feed_fetch_times (feed, last_fetched_at)
+        VALUES ($1, $2)
+        ON CONFLICT (feed) DO UPDATE SET last_fetched_at = EXCLUDED.last_fetched_at
+        "#,
+        feed.key(),
+        fetched_at
+    )
+    .execute(pool)
+    .await
+    .with_context(|| format!("フィードの取得日時の記録に失敗: {}", feed.key()))?;
+    Ok(())
+}
+
+// 収集状況の統計付きフィード
+#[derive(Debug, Clone)]
+pub struct FeedWithStats {
+    pub feed: Feed,
+    /// 収集済みリンク数（ゴミ箱のリンクは除く）
+    pub link_count: i64,
+    /// 最も新しいリンクの公開日時
+    pub latest_pub_date: Option<DateTime<Utc>>,
+    /// 記事取得の成功率（0.0-1.0、記事を取得していない場合はNone）
+    pub success_rate: Option<f64>,
+}
+
+/// # 概要
+/// 設定した保存先の全フィードに、収集済みリンク数・最終新着日・記事取得の成功率を付けて返す。
+///
+/// フィードの並びはgroup, nameの順。リンクを収集していないフィードも含める。
+pub async fn list_feeds_with_stats(
+    config: &FeedsConfig,
+    pool: &PgPool,
+) -> Result<Vec<FeedWithStats>> {
+    let mut feeds = search_feeds_from(config, None, pool).await?;
+    feeds.sort_by(|a, b| (&a.group, &a.name).cmp(&(&b.group, &b.name)));
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            al.feed AS "feed!",
+            COUNT(*) AS "link_count!",
+            MAX(al.pub_date) AS latest_pub_date,
+            COUNT(a.url) AS "fetched_count!",
+            COUNT(a.url) FILTER (WHERE a.status_code = 200) AS "success_count!"
+        FROM article_links al
+        LEFT JOIN articles a ON a.url = al.url AND a.deleted_at IS NULL
+        WHERE al.feed IS NOT NULL AND al.deleted_at IS NULL
+        GROUP BY al.feed
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .context("フィード毎の統計の集計に失敗")?;
+
+    let stats: HashMap<String, _> = rows
+        .into_iter()
+        .map(|row| (row.feed.clone(), row))
+        .collect();
+
+    Ok(feeds
+        .into_iter()
+        .map(|feed| match stats.get(&feed.key()) {
+            Some(row) => FeedWithStats {
+                link_count: row.link_count,
+                latest_pub_date: row.latest_pub_date,
+                success_rate: (row.fetched_count > 0)
+                    .then(|| row.success_count as f64 / row.fetched_count as f64),
+                feed,
+            },
+            None => FeedWithStats {
+                feed,
+                link_count: 0,
+                latest_pub_date: None,
+                success_rate: None,
+            },
+        })
+        .collect())
+}
+
+/// 統計付きフィード一覧を表示用のテーブル行に整形する
+pub fn format_feed_stats_table(feeds: &[FeedWithStats]) -> Vec<String> {
+    let keys: Vec<String> = feeds.iter().map(|f| f.feed.key()).collect();
+    let key_width = keys
+        .iter()
+        .map(|key| key.chars().count())
+        .chain(std::iter::once("FEED".len()))
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = vec![format!(
+        "{:<key_width$}  {:>6}  {:<16}  {:>7}",
+        "FEED", "LINKS", "LATEST", "SUCCESS"
+    )];
+    for (feed, key) in feeds.iter().zip(&keys) {
+        let latest = feed
+            .latest_pub_date
+            .map(|date| date.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let success = feed
+            .success_rate
+            .map(|rate| format!("{:.1}%", rate * 100.0))
+            .unwrap_or_else(|| "-".to_string());
+        // 全角文字を含むキーも揃うよう、文字数で余白を計算する
+        let padding = " ".repeat(key_width - key.chars().count());
+        lines.push(format!(
+            "{}{}  {:>6}  {:<16}  {:>7}",
+            key, padding, feed.link_count, latest, success
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_feeds_no_filter() {
+        // 絞り込みなし（全件取得）
+        let result = search_feeds(None);
+        assert!(result.is_ok(), "フィード検索に失敗");
+
+        let feeds = result.unwrap();
+        assert!(!feeds.is_empty(), "フィードが取得されませんでした");
+    }
+
+    #[test]
+    fn test_search_feeds_group_only() {
+        // groupのみ絞り込み
+        let query = FeedQuery {
+            group: Some("bbc".to_string()),
+            name: None,
+        };
+        let result = search_feeds(Some(query));
+        assert!(result.is_ok(), "フィード検索に失敗");
+
+        let feeds = result.unwrap();
+        assert!(!feeds.is_empty(), "bbcグループのフィードが見つかりません");
+        assert!(
+            feeds.iter().all(|f| f.group == "bbc"),
+            "全てbbcグループである必要があります"
+        );
+    }
+
+    #[test]
+    fn test_search_feeds_group_and_name() {
+        // group & name絞り込み
+        let query = FeedQuery {
+            group: Some("bbc".to_string()),
+            name: Some("world".to_string()),
+        };
+        let result = search_feeds(Some(query));
+        assert!(result.is_ok(), "フィード検索に失敗");
+
+        let feeds = result.unwrap();
+        assert_eq!(feeds.len(), 1, "特定のフィードで1件が期待されます");
+        assert_eq!(feeds[0].group, "bbc");
+        assert_eq!(feeds[0].name, "world");
+    }
+
+    #[test]
+    fn test_load_feeds_from_yaml() {
+        // 実際のYAMLファイルからの読み込みテスト（search_feeds経由）
+        let result = search_feeds(None);
+        assert!(result.is_ok(), "YAMLファイルの読み込みに失敗");
+
+        let feeds = result.unwrap();
+        assert!(!feeds.is_empty(), "フィードが読み込まれませんでした");
+
+        // bbcグループが存在することを確認
+        let bbc_feeds: Vec<_> = feeds.iter().filter(|f| f.group == "bbc").collect();
+        assert!(
+            !bbc_feeds.is_empty(),
+            "bbcグループのフィードが見つかりません"
+        );
+
+        println!(
+            "✅ フィードYAML読み込みテスト成功: {}件のフィードを読み込み",
+            feeds.len()
+        );
+    }
+
+    #[test]
+    fn test_load_feeds_with_fallback_urls() {
+        let path = std::env::temp_dir().join(format!("feeds_fallback_{}.yaml", std::process::id()));
+        std::fs::write(
+            &path,
+            "news:\n  top: https://example.com/top.xml\n  world:\n    url: https://example.com/world.xml\n    fallback_urls:\n      - https://mirror.example.com/world.xml\n",
+        )
+        .unwrap();
+
+        let feeds = load_feeds_from_yaml(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let top = feeds.iter().find(|f| f.name == "top").unwrap();
+        assert!(top.fallback_urls.is_empty());
+        let world = feeds.iter().find(|f| f.name == "world").unwrap();
+        assert_eq!(
+            world.candidate_urls().collect::<Vec<_>>(),
+            vec![
+                "https://example.com/world.xml",
+                "https://mirror.example.com/world.xml"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_feed_fetch_interval() {
+        let feeds = parse_feeds_yaml(
+            "news:\n  top: https://example.com/top.xml\n  weekly:\n    url: https://example.com/weekly.xml\n    fetch_interval: 1440\n",
+        )
+        .unwrap();
+        let top = feeds.iter().find(|f| f.name == "top").unwrap();
+        let weekly = feeds.iter().find(|f| f.name == "weekly").unwrap();
+        assert_eq!(top.fetch_interval, None);
+        assert_eq!(weekly.fetch_interval, Some(1440));
+
+        // YAMLに書き戻しても取得間隔を保持する
+        let round_trip = parse_feeds_yaml(&feeds_to_yaml(&feeds).unwrap()).unwrap();
+        let weekly_again = round_trip.iter().find(|f| f.name == "weekly").unwrap();
+        assert_eq!(weekly_again.fetch_interval, Some(1440));
+
+        // 取得間隔が未設定、または未取得のフィードは常に期限
+        let now = Utc::now();
+        assert!(top.is_due(now));
+        assert!(weekly.is_due(now));
+        let fetched = |hours_ago: i64| Feed {
+            last_fetched_at: Some(now - Duration::hours(hours_ago)),
+            ..weekly.clone()
+        };
+        assert!(!fetched(1).is_due(now));
+        assert!(fetched(24).is_due(now));
+        assert!(Feed {
+            last_fetched_at: Some(now),
+            ..top.clone()
+        }
+        .is_due(now));
+        println!("✅ フィードの取得間隔テスト成功");
+    }
+
+    #[test]
+    fn test_feed_search_logic() {
+        // フィード検索ロジックのテスト（外部通信なし）
+        let query = FeedQuery {
+            group: Some("存在しないグループ".to_string()),
+            name: None,
+        };
+
+        let result = search_feeds(Some(query));
+        match result {
+            Ok(feeds) => {
+                assert!(
+                    feeds.is_empty(),
+                    "存在しないグループでフィードが見つからないはず"
+                );
+            }
+            Err(_) => {
+                // ファイル読み込みエラーは許容
+            }
+        }
+
+        println!("✅ フィード検索ロジックテスト完了");
+    }
+
+    #[test]
+    fn test_format_feed_stats_table() {
+        let feed = |group: &str, name: &str| Feed {
+            group: group.to_string(),
+            name: name.to_string(),
+            rss_link: "https://example.com/rss.xml".to_string(),
+            ..Default::default()
+        };
+        let rows = vec![
+            FeedWithStats {
+                feed: feed("bbc", "top"),
+                link_count: 12,
+                latest_pub_date: Some(
+                    DateTime::parse_from_rfc3339("2025-08-10T09:30:00Z")
+                        .unwrap()
+                        .with_timezone(&Utc),
+                ),
+                success_rate: Some(0.75),
+            },
+            FeedWithStats {
+                feed: feed("yahoo", "国内"),
+                link_count: 0,
+                latest_pub_date: None,
+                success_rate: None,
+            },
+        ];
+
+        assert_eq!(
+            format_feed_stats_table(&rows),
+            vec![
+                "FEED       LINKS  LATEST            SUCCESS",
+                "bbc/top       12  2025-08-10 09:30    75.0%",
+                "yahoo/国内       0  -                       -",
+            ]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_list_feeds_with_stats(pool: PgPool) -> Result<(), anyhow::Error> {
+        use crate::core::article::{store_article_content, ArticleContent};
+        use crate::core::rss::{assign_article_links_feed, store_article_links, ArticleLink};
+
+        let target = search_feeds(Some(FeedQuery::from_group("bbc")))?
+            .into_iter()
+            .next()
+            .expect("bbcグループのフィードが必要");
+
+        let links: Vec<ArticleLink> = (1..=2)
+            .map(|i| ArticleLink {
+                url: format!("https://stats.example.com/{}", i),
+                title: format!("記事{}", i),
+                pub_date: Utc::now(),
+                source: "rss".to_string(),
+                ..Default::default()
+            })
+            .collect();
+        store_article_links(&links, &pool).await?;
+        assign_article_links_feed(&links, &target, &pool).await?;
+        store_article_content(
+            &ArticleContent::error_placeholder(&links[0].url, "取得エラー"),
+            &pool,
+        )
+        .await?;
+
+        let feeds = list_feeds_with_stats(&FeedsConfig::default(), &pool).await?;
+        assert_eq!(feeds.len(), search_feeds(None)?.len(), "全フィードを含む");
+
+        let stats = feeds.iter().find(|f| f.feed.key() == target.key()).unwrap();
+        assert_eq!(stats.link_count, 2);
+        assert!(stats.latest_pub_date.is_some());
+        assert_eq!(stats.success_rate, Some(0.0));
+
+        let others = feeds.iter().filter(|f| f.feed.key() != target.key());
+        assert!(others.clone().all(|f| f.link_count == 0));
+
+        println!("✅ フィード統計一覧テスト成功");
+        Ok(())
+    }
+}
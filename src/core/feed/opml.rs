@@ -0,0 +1,320 @@
+use super::Feed;
+use crate::infra::storage::file::{load_text_file, normalize_text, write_file_atomic};
+use anyhow::{bail, Context, Result};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+/// カテゴリ（フィードを含む`<outline>`）の外にあるフィードのgroup
+pub const OPML_DEFAULT_GROUP: &str = "opml";
+
+/// OPMLファイルを読み込み、Feedのベクタとして返す
+pub fn import_feeds_from_opml(file_path: impl AsRef<Path>) -> Result<Vec<Feed>> {
+    let file_path = file_path.as_ref();
+    let content = load_text_file(file_path)
+        .with_context(|| format!("OPMLファイルの読み込みに失敗: {}", file_path.display()))?;
+    parse_opml(&normalize_text(&content))
+        .with_context(|| format!("OPMLファイルの解析に失敗: {}", file_path.display()))
+}
+
+/// # 概要
+/// OPML形式の文字列からフィード情報を読み込む。
+///
+/// `xmlUrl`を持つ`<outline>`をフィード、それを囲む`<outline>`をgroupとして扱う
+/// （入れ子の場合は最も内側のカテゴリ、カテゴリの外のフィードは`OPML_DEFAULT_GROUP`）。
+/// nameは`text`（無ければ`title`、それも無ければURL）で、`/`は`-`に置き換え、
+/// 同じgroupで重複するnameには連番を付ける。
+/// RSSリーダーの書き出したOPMLを想定した簡易的な解析で、`<outline>`以外の要素は読み飛ばす。
+pub fn parse_opml(content: &str) -> Result<Vec<Feed>> {
+    let mut feeds = Vec::new();
+    let mut used_keys = HashSet::new();
+    // 開いている<outline>毎のカテゴリ名（フィードの<outline>はNone）
+    let mut open_outlines: Vec<Option<String>> = Vec::new();
+    let mut found_opml = false;
+
+    let mut rest = content;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        if let Some(comment) = rest.strip_prefix("!--") {
+            let end = comment
+                .find("-->")
+                .context("コメントが閉じられていません")?;
+            rest = &comment[end + 3..];
+            continue;
+        }
+        let end = rest.find('>').context("タグが閉じられていません")?;
+        let tag = rest[..end].trim();
+        rest = &rest[end + 1..];
+
+        if tag == "/outline" {
+            open_outlines.pop();
+            continue;
+        }
+        if tag == "opml" || tag.starts_with("opml ") {
+            found_opml = true;
+            continue;
+        }
+        let Some(body) = tag.strip_prefix("outline") else {
+            continue;
+        };
+        if !body.is_empty() && !body.starts_with(|c: char| c.is_whitespace() || c == '/') {
+            continue;
+        }
+
+        let self_closing = body.ends_with('/');
+        let attributes = parse_attributes(body.trim_end_matches('/'))?;
+        let label = ["text", "title"]
+            .iter()
+            .find_map(|key| attributes.get(*key).filter(|v| !v.trim().is_empty()))
+            .map(|v| v.trim().to_string());
+
+        match attributes.get("xmlurl").map(|url| url.trim()) {
+            Some(url) if !url.is_empty() => {
+                let group = open_outlines
+                    .iter()
+                    .rev()
+                    .find_map(Option::as_deref)
+                    .unwrap_or(OPML_DEFAULT_GROUP);
+                let group = feed_key_part(group);
+                let base = feed_key_part(label.as_deref().unwrap_or(url));
+                let mut name = base.clone();
+                let mut suffix = 2;
+                while !used_keys.insert((group.clone(), name.clone())) {
+                    name = format!("{}-{}", base, suffix);
+                    suffix += 1;
+                }
+                feeds.push(Feed {
+                    group,
+                    name,
+                    rss_link: url.to_string(),
+                    ..Default::default()
+                });
+                if !self_closing {
+                    open_outlines.push(None);
+                }
+            }
+            _ if !self_closing => open_outlines.push(label),
+            _ => {}
+        }
+    }
+
+    if !found_opml {
+        bail!("<opml>要素がありません");
+    }
+    Ok(feeds)
+}
+
+/// group・nameに使えるよう、`/`を`-`に置き換える（キーの区切り文字のため）
+fn feed_key_part(value: &str) -> String {
+    value.trim().replace('/', "-")
+}
+
+/// タグ内の属性を読み込む（属性名は小文字にそろえる）
+fn parse_attributes(body: &str) -> Result<HashMap<String, String>> {
+    let mut attributes = HashMap::new();
+    let mut rest = body.trim_start();
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .with_context(|| format!("属性の値がありません: {}", rest))?;
+        let name = rest[..name_end].to_lowercase();
+        let value_part = rest[name_end..].trim_start();
+        let value_part = value_part
+            .strip_prefix('=')
+            .with_context(|| format!("属性の値がありません: {}", name))?
+            .trim_start();
+        let quote = value_part
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+            .with_context(|| format!("属性の値が引用符で囲まれていません: {}", name))?;
+        let value_part = &value_part[1..];
+        let value_end = value_part
+            .find(quote)
+            .with_context(|| format!("属性の値が閉じられていません: {}", name))?;
+        attributes.insert(name, unescape_xml(&value_part[..value_end]));
+        rest = value_part[value_end + 1..].trim_start();
+    }
+    Ok(attributes)
+}
+
+/// XMLの文字参照・実体参照を展開する（解釈できない参照はそのまま残す）
+fn unescape_xml(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                _ => {
+                    let code = match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => entity.strip_prefix('#')?.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// XMLの属性値として書き出せるようにエスケープする
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// # 概要
+/// フィード情報をOPML 2.0形式の文字列にする（groupをカテゴリとして、group, nameの順）。
+///
+/// OPMLにはフォールバックURL・取得間隔を表す属性が無いため、`rss_link`のみ書き出す。
+pub fn feeds_to_opml(feeds: &[Feed]) -> String {
+    let mut groups: BTreeMap<&str, BTreeMap<&str, &str>> = BTreeMap::new();
+    for feed in feeds {
+        groups
+            .entry(&feed.group)
+            .or_default()
+            .insert(&feed.name, &feed.rss_link);
+    }
+
+    let mut opml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opml version=\"2.0\">\n  <head>\n    <title>datadoggo feeds</title>\n  </head>\n  <body>\n",
+    );
+    for (group, feeds) in groups {
+        let group = escape_xml(group);
+        opml.push_str(&format!(
+            "    <outline text=\"{}\" title=\"{}\">\n",
+            group, group
+        ));
+        for (name, url) in feeds {
+            let name = escape_xml(name);
+            opml.push_str(&format!(
+                "      <outline type=\"rss\" text=\"{}\" title=\"{}\" xmlUrl=\"{}\"/>\n",
+                name,
+                name,
+                escape_xml(url)
+            ));
+        }
+        opml.push_str("    </outline>\n");
+    }
+    opml.push_str("  </body>\n</opml>\n");
+    opml
+}
+
+/// フィード情報をOPMLファイルに書き出す（書き込み途中のファイルは残さない）
+pub fn export_feeds_to_opml(feeds: &[Feed], file_path: impl AsRef<Path>) -> Result<()> {
+    let file_path = file_path.as_ref();
+    write_file_atomic(file_path, &feeds_to_opml(feeds))
+        .with_context(|| format!("OPMLファイルの書き込みに失敗: {}", file_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<opml version="1.0">
+  <head><title>購読リスト</title></head>
+  <body>
+    <!-- <outline text="コメント" xmlUrl="https://comment.example.com/rss"/> -->
+    <outline text="ニュース" title="ニュース">
+      <outline type="rss" text="Top Stories" xmlUrl="https://news.example.com/top.xml" htmlUrl="https://news.example.com/"/>
+      <outline type="rss" title="Q&amp;A" xmlUrl="https://news.example.com/rss?a=1&amp;b=2"></outline>
+      <outline text="Tech">
+        <outline type="rss" text="Top Stories" xmlUrl='https://tech.example.com/feed'/>
+        <outline type="rss" text="Top Stories" xmlUrl="https://tech.example.com/feed2"/>
+      </outline>
+    </outline>
+    <outline type="rss" text="A/B Blog" xmlUrl="https://blog.example.com/atom.xml"/>
+  </body>
+</opml>
+"#;
+
+    #[test]
+    fn test_parse_opml() {
+        let feeds = parse_opml(OPML).unwrap();
+        let keys: Vec<(String, &str)> = feeds
+            .iter()
+            .map(|feed| (feed.key(), feed.rss_link.as_str()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                (
+                    "ニュース/Top Stories".to_string(),
+                    "https://news.example.com/top.xml"
+                ),
+                (
+                    "ニュース/Q&A".to_string(),
+                    "https://news.example.com/rss?a=1&b=2"
+                ),
+                (
+                    "Tech/Top Stories".to_string(),
+                    "https://tech.example.com/feed"
+                ),
+                (
+                    "Tech/Top Stories-2".to_string(),
+                    "https://tech.example.com/feed2"
+                ),
+                (
+                    "opml/A-B Blog".to_string(),
+                    "https://blog.example.com/atom.xml"
+                ),
+            ]
+        );
+
+        assert!(parse_opml("<rss></rss>").is_err(), "OPMLでない文書はエラー");
+        assert!(parse_opml("<opml><outline text=\"x").is_err());
+    }
+
+    #[test]
+    fn test_opml_roundtrip() {
+        let feed = |group: &str, name: &str, url: &str| Feed {
+            group: group.to_string(),
+            name: name.to_string(),
+            rss_link: url.to_string(),
+            ..Default::default()
+        };
+        let feeds = vec![
+            feed("news", "top", "https://example.com/top.xml?a=1&b=2"),
+            feed("blog", "\"quoted\" <name>", "https://blog.example.com/rss"),
+        ];
+        let opml = feeds_to_opml(&feeds);
+        assert!(opml.contains("xmlUrl=\"https://example.com/top.xml?a=1&amp;b=2\""));
+
+        let mut parsed = parse_opml(&opml).unwrap();
+        let mut expected = feeds;
+        parsed.sort_by_key(Feed::key);
+        expected.sort_by_key(Feed::key);
+        assert_eq!(parsed, expected);
+    }
+}
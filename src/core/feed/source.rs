@@ -0,0 +1,486 @@
+use super::{feeds_to_yaml, load_feeds_from_yaml, parse_feeds_yaml, Feed, FEEDS_PATH};
+use crate::infra::api::http::HttpClient;
+use crate::infra::storage::file::{
+    load_text_file, normalize_path, normalize_text, write_file_atomic,
+};
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// リモートのフィードリストを取得する際のタイムアウト（秒）
+const REMOTE_FETCH_TIMEOUT_SECS: u64 = 30;
+
+/// 同期元のHTTPクライアント（デーモンで使い回すため共有する）
+pub type SharedHttpClient = Arc<dyn HttpClient + Send + Sync>;
+
+/// フィードリストの取得元の抽象化
+///
+/// ローカルのYAML、リモートURLのYAML、CSV（Googleスプレッドシートの
+/// CSVエクスポートURLを含む）を同じように扱うためのインターフェース。
+#[async_trait]
+pub trait FeedSource: Send + Sync {
+    /// 表示用の名前（例: "local_yaml:config/feeds.local.yaml"）
+    fn describe(&self) -> String;
+
+    /// フィードリストを取得する
+    async fn load_feeds(&self) -> Result<Vec<Feed>>;
+}
+
+/// ローカルのfeeds.yaml形式のファイル
+pub struct LocalYamlSource {
+    pub path: PathBuf,
+}
+
+#[async_trait]
+impl FeedSource for LocalYamlSource {
+    fn describe(&self) -> String {
+        format!("local_yaml:{}", self.path.display())
+    }
+
+    async fn load_feeds(&self) -> Result<Vec<Feed>> {
+        load_feeds_from_yaml(&self.path)
+    }
+}
+
+/// リモートURLで公開されたfeeds.yaml形式のファイル
+pub struct RemoteUrlSource {
+    pub url: String,
+    client: SharedHttpClient,
+}
+
+impl RemoteUrlSource {
+    pub fn new(url: &str, client: SharedHttpClient) -> Self {
+        Self {
+            url: url.to_string(),
+            client,
+        }
+    }
+}
+
+#[async_trait]
+impl FeedSource for RemoteUrlSource {
+    fn describe(&self) -> String {
+        format!("remote_url:{}", self.url)
+    }
+
+    async fn load_feeds(&self) -> Result<Vec<Feed>> {
+        let content = self
+            .client
+            .fetch(&self.url, REMOTE_FETCH_TIMEOUT_SECS)
+            .await
+            .with_context(|| format!("フィードリストの取得に失敗: {}", self.url))?;
+        parse_feeds_yaml(&normalize_text(&content))
+            .with_context(|| format!("フィードリストが不正です: {}", self.url))
+    }
+}
+
+// CSVの取得先
+pub enum CsvLocation {
+    Path(PathBuf),
+    /// GoogleスプレッドシートはCSVエクスポートのURL（`.../export?format=csv`）を指定する
+    Url(String, SharedHttpClient),
+}
+
+/// `group,name,url,fallback_urls`列を持つCSV（fallback_urlsは空白区切り、省略可）
+pub struct CsvSource {
+    pub location: CsvLocation,
+}
+
+#[async_trait]
+impl FeedSource for CsvSource {
+    fn describe(&self) -> String {
+        match &self.location {
+            CsvLocation::Path(path) => format!("csv:{}", path.display()),
+            CsvLocation::Url(url, _) => format!("csv:{}", url),
+        }
+    }
+
+    async fn load_feeds(&self) -> Result<Vec<Feed>> {
+        let content = match &self.location {
+            CsvLocation::Path(path) => load_text_file(path)?,
+            CsvLocation::Url(url, client) => client
+                .fetch(url, REMOTE_FETCH_TIMEOUT_SECS)
+                .await
+                .with_context(|| format!("フィードリストの取得に失敗: {}", url))?,
+        };
+        parse_feeds_csv(&normalize_text(&content))
+            .with_context(|| format!("CSVが不正です: {}", self.describe()))
+    }
+}
+
+// CSVの1行分
+#[derive(Debug, Deserialize)]
+struct CsvFeedRow {
+    group: String,
+    name: String,
+    url: String,
+    #[serde(default)]
+    fallback_urls: String,
+    /// 取得間隔（分、空欄は毎回取得）
+    #[serde(default)]
+    fetch_interval: Option<u32>,
+}
+
+/// # 概要
+/// `group,name,url,fallback_urls[,fetch_interval]`列のCSVからフィード情報を読み込む。
+///
+/// 前後の空白は取り除き、group・name・urlのいずれかが空の行（スプレッドシートの空行など）は読み飛ばす。
+pub fn parse_feeds_csv(content: &str) -> Result<Vec<Feed>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let mut feeds = Vec::new();
+    for (index, row) in reader.deserialize::<CsvFeedRow>().enumerate() {
+        // ヘッダが1行目のため、データ行はindex+2行目
+        let row = row.with_context(|| format!("CSVの{}行目の解析に失敗", index + 2))?;
+        if row.group.is_empty() || row.name.is_empty() || row.url.is_empty() {
+            continue;
+        }
+        feeds.push(Feed {
+            group: row.group,
+            name: row.name,
+            rss_link: row.url,
+            fallback_urls: row
+                .fallback_urls
+                .split_whitespace()
+                .map(str::to_string)
+                .collect(),
+            fetch_interval: row.fetch_interval,
+            url_patterns: Vec::new(),
+            last_fetched_at: None,
+        });
+    }
+    Ok(feeds)
+}
+
+/// 複数ソースのフィードをマージする
+///
+/// 同じキー（group/name）のフィードは後のソースの設定を優先する。結果はgroup, nameの順。
+pub fn merge_feeds(sources: Vec<Vec<Feed>>) -> Vec<Feed> {
+    let mut merged: BTreeMap<(String, String), Feed> = BTreeMap::new();
+    for feed in sources.into_iter().flatten() {
+        merged.insert((feed.group.clone(), feed.name.clone()), feed);
+    }
+    merged.into_values().collect()
+}
+
+// フィードリスト同期の設定（config/app.yamlのfeed_sourcesに対応）
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeedSourcesConfig {
+    /// 同期元（記載順にマージし、同じフィードは後のソースを優先）
+    #[serde(default)]
+    pub sources: Vec<FeedSourceConfig>,
+    /// マージ結果の書き込み先（`search_feeds`が読み込むファイル）
+    #[serde(default = "default_output")]
+    pub output: PathBuf,
+    /// デーモンモードの同期間隔（秒）
+    #[serde(default = "default_sync_interval_secs")]
+    pub sync_interval_secs: u64,
+}
+
+impl Default for FeedSourcesConfig {
+    fn default() -> Self {
+        Self {
+            sources: Vec::new(),
+            output: default_output(),
+            sync_interval_secs: default_sync_interval_secs(),
+        }
+    }
+}
+
+fn default_output() -> PathBuf {
+    PathBuf::from(FEEDS_PATH)
+}
+
+fn default_sync_interval_secs() -> u64 {
+    3600
+}
+
+// 同期元1件の設定
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeedSourceConfig {
+    LocalYaml {
+        path: PathBuf,
+    },
+    RemoteUrl {
+        url: String,
+    },
+    /// `path`または`url`のどちらか一方を指定する
+    Csv {
+        path: Option<PathBuf>,
+        url: Option<String>,
+    },
+}
+
+impl FeedSourcesConfig {
+    /// 設定から同期元を作成する
+    pub fn build_sources(&self, client: &SharedHttpClient) -> Result<Vec<Box<dyn FeedSource>>> {
+        self.sources
+            .iter()
+            .map(|source| -> Result<Box<dyn FeedSource>> {
+                Ok(match source {
+                    FeedSourceConfig::LocalYaml { path } => {
+                        Box::new(LocalYamlSource { path: path.clone() })
+                    }
+                    FeedSourceConfig::RemoteUrl { url } => {
+                        Box::new(RemoteUrlSource::new(url, client.clone()))
+                    }
+                    FeedSourceConfig::Csv { path, url } => {
+                        let location = match (path, url) {
+                            (Some(path), None) => CsvLocation::Path(path.clone()),
+                            (None, Some(url)) => CsvLocation::Url(url.clone(), client.clone()),
+                            _ => bail!("CSVの同期元にはpathとurlのどちらか一方を指定してください"),
+                        };
+                        Box::new(CsvSource { location })
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+// フィードリスト同期の結果
+#[derive(Debug, Clone, Default)]
+pub struct FeedSyncReport {
+    /// 同期元毎の取得件数（設定順）
+    pub source_counts: Vec<(String, usize)>,
+    /// マージ後のフィード数
+    pub total: usize,
+    /// 追加・削除・URLが変わったフィードのキー
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+}
+
+impl FeedSyncReport {
+    /// 書き込み先の内容が変わったかどうか
+    pub fn has_changes(&self) -> bool {
+        !(self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty())
+    }
+
+    /// 表示用の行に整形する
+    pub fn format_lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .source_counts
+            .iter()
+            .map(|(source, count)| format!("{}: {}件", source, count))
+            .collect();
+        lines.push(format!(
+            "マージ後: {}件（追加{}件、削除{}件、変更{}件）",
+            self.total,
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len()
+        ));
+        for (label, keys) in [
+            ("追加", &self.added),
+            ("削除", &self.removed),
+            ("変更", &self.changed),
+        ] {
+            lines.extend(keys.iter().map(|key| format!("  {}: {}", label, key)));
+        }
+        lines
+    }
+}
+
+/// # 概要
+/// 同期元からフィードリストを取得してマージし、`output`に書き込む。
+///
+/// 1つでも取得に失敗した同期元があれば書き込まずにエラーを返す
+/// （一時的な障害で、その同期元のフィードが削除されないようにするため）。
+/// 内容が変わらない場合は書き込まない。
+pub async fn sync_feeds(sources: &[Box<dyn FeedSource>], output: &Path) -> Result<FeedSyncReport> {
+    if sources.is_empty() {
+        bail!("フィードリストの同期元が設定されていません（config/app.yamlのfeed_sources）");
+    }
+
+    let mut report = FeedSyncReport::default();
+    let mut loaded = Vec::with_capacity(sources.len());
+    for source in sources {
+        let feeds = source
+            .load_feeds()
+            .await
+            .with_context(|| format!("同期元の読み込みに失敗: {}", source.describe()))?;
+        report.source_counts.push((source.describe(), feeds.len()));
+        loaded.push(feeds);
+    }
+    let merged = merge_feeds(loaded);
+
+    let current = if normalize_path(output).exists() {
+        load_feeds_from_yaml(output)?
+    } else {
+        Vec::new()
+    };
+    let current: BTreeMap<String, &Feed> = current.iter().map(|f| (f.key(), f)).collect();
+    let next: BTreeMap<String, &Feed> = merged.iter().map(|f| (f.key(), f)).collect();
+    for (key, feed) in &next {
+        match current.get(key) {
+            None => report.added.push(key.clone()),
+            Some(existing) if existing != feed => report.changed.push(key.clone()),
+            Some(_) => {}
+        }
+    }
+    report.removed = current
+        .keys()
+        .filter(|key| !next.contains_key(*key))
+        .cloned()
+        .collect();
+    report.total = merged.len();
+
+    if report.has_changes() {
+        write_file_atomic(output, &feeds_to_yaml(&merged)?)?;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::api::http::ReqwestHttpClient;
+    use httpmock::prelude::*;
+
+    fn feed(group: &str, name: &str, url: &str) -> Feed {
+        Feed {
+            group: group.to_string(),
+            name: name.to_string(),
+            rss_link: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_feeds_csv() {
+        let csv = "group,name,url,fallback_urls\n\
+                   bbc, top ,https://example.com/top.xml,\n\
+                   ,,,\n\
+                   nhk,world,https://example.com/world.xml,https://m1.example.com/w.xml https://m2.example.com/w.xml\n";
+        let feeds = parse_feeds_csv(csv).unwrap();
+
+        assert_eq!(feeds.len(), 2, "空行は読み飛ばす");
+        assert_eq!(feeds[0], feed("bbc", "top", "https://example.com/top.xml"));
+        assert_eq!(feeds[1].fallback_urls.len(), 2);
+
+        // fallback_urls列は省略できる
+        let feeds = parse_feeds_csv("group,name,url\nbbc,top,https://example.com/top.xml").unwrap();
+        assert_eq!(feeds.len(), 1);
+        assert!(parse_feeds_csv("group,name\nbbc,top").is_err());
+    }
+
+    #[test]
+    fn test_merge_feeds() {
+        let merged = merge_feeds(vec![
+            vec![
+                feed("bbc", "top", "https://old.example.com/top.xml"),
+                feed("cbs", "top", "https://example.com/cbs.xml"),
+            ],
+            vec![feed("bbc", "top", "https://new.example.com/top.xml")],
+        ]);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(
+            merged[0].rss_link, "https://new.example.com/top.xml",
+            "後のソースを優先"
+        );
+        assert_eq!(merged[1].group, "cbs");
+    }
+
+    #[test]
+    fn test_build_sources() {
+        let config: FeedSourcesConfig = serde_yaml::from_str(
+            r#"
+sources:
+  - type: local_yaml
+    path: config/feeds.yaml
+  - type: csv
+    url: https://docs.google.com/spreadsheets/d/xxx/export?format=csv
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.output, Path::new(FEEDS_PATH));
+
+        let client: SharedHttpClient = Arc::new(ReqwestHttpClient::new());
+        let sources = config.build_sources(&client).unwrap();
+        assert_eq!(sources[0].describe(), "local_yaml:config/feeds.yaml");
+        assert!(sources[1]
+            .describe()
+            .starts_with("csv:https://docs.google.com/"));
+
+        let invalid = FeedSourcesConfig {
+            sources: vec![FeedSourceConfig::Csv {
+                path: None,
+                url: None,
+            }],
+            ..Default::default()
+        };
+        assert!(invalid.build_sources(&client).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sync_feeds() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/feeds.yaml");
+            then.status(200)
+                .body("bbc:\n  top: https://remote.example.com/top.xml\n");
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/sheet.csv");
+            then.status(200)
+                .body("group,name,url\nnhk,news,https://example.com/nhk.xml\n");
+        });
+
+        let dir = std::env::temp_dir().join(format!("feed_sync_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let local = dir.join("local.yaml");
+        std::fs::write(
+            &local,
+            "bbc:\n  top: https://local.example.com/top.xml\n  world: https://example.com/world.xml\n",
+        )
+        .unwrap();
+        let output = dir.join("feeds.yaml");
+        let output = output.as_path();
+
+        let client: SharedHttpClient = Arc::new(ReqwestHttpClient::new());
+        let config = FeedSourcesConfig {
+            sources: vec![
+                FeedSourceConfig::LocalYaml {
+                    path: local.clone(),
+                },
+                FeedSourceConfig::RemoteUrl {
+                    url: server.url("/feeds.yaml"),
+                },
+                FeedSourceConfig::Csv {
+                    path: None,
+                    url: Some(server.url("/sheet.csv")),
+                },
+            ],
+            output: output.to_path_buf(),
+            ..Default::default()
+        };
+        let sources = config.build_sources(&client).unwrap();
+
+        let report = sync_feeds(&sources, output).await.unwrap();
+        assert_eq!(report.total, 3);
+        assert_eq!(report.added.len(), 3);
+        let feeds = load_feeds_from_yaml(output).unwrap();
+        let top = feeds.iter().find(|f| f.key() == "bbc/top").unwrap();
+        assert_eq!(top.rss_link, "https://remote.example.com/top.xml");
+
+        // 内容が変わらなければ変更なし
+        let report = sync_feeds(&sources, output).await.unwrap();
+        assert!(!report.has_changes());
+
+        // 取得に失敗した同期元があれば書き込まない
+        std::fs::remove_file(&local).unwrap();
+        assert!(sync_feeds(&sources, output).await.is_err());
+        assert_eq!(load_feeds_from_yaml(output).unwrap().len(), 3);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        println!("✅ フィードリスト同期テスト成功");
+    }
+}
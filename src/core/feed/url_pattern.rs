@@ -0,0 +1,105 @@
+use super::Feed;
+use crate::types::ConfigError;
+use regex::Regex;
+
+/// # 概要
+/// フィードの`url_patterns`をコンパイルした、リンクの期待URLパターン。
+///
+/// いずれかのパターンに一致するリンクのみ収集する。相対URLや別ドメインの広告URLなど、
+/// フィードが返す想定外のリンクを保存前に除外するために使う。パターンが無い場合はすべて許可する。
+#[derive(Debug, Clone, Default)]
+pub struct FeedUrlPatterns {
+    patterns: Vec<Regex>,
+}
+
+impl FeedUrlPatterns {
+    /// # 概要
+    /// 正規表現のパターンをコンパイルする。
+    ///
+    /// # エラー
+    /// 正規表現として不正なパターンがある場合は設定エラー
+    pub fn compile<S: AsRef<str>>(patterns: &[S]) -> Result<Self, ConfigError> {
+        let patterns = patterns
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern.as_ref()).map_err(|e| {
+                    ConfigError::invalid(
+                        "url_patterns",
+                        format!("正規表現が不正です: {}: {}", pattern.as_ref(), e),
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { patterns })
+    }
+
+    /// フィードに設定された期待URLパターン
+    pub fn for_feed(feed: &Feed) -> Result<Self, ConfigError> {
+        Self::compile(&feed.url_patterns)
+    }
+
+    /// パターンが1つも無いかどうか（無ければ検証しない）
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// URLがいずれかのパターンに一致するか（パターンが無ければ常に`true`）
+    pub fn matches(&self, url: &str) -> bool {
+        self.is_empty() || self.patterns.iter().any(|pattern| pattern.is_match(url))
+    }
+
+    /// パターンに一致する要素と、一致しなかった要素のURLを返す
+    pub fn retain_matching<T>(
+        &self,
+        items: Vec<T>,
+        url: impl Fn(&T) -> &str,
+    ) -> (Vec<T>, Vec<String>) {
+        let mut rejected = Vec::new();
+        let matched = items
+            .into_iter()
+            .filter(|item| {
+                let matches = self.matches(url(item));
+                if !matches {
+                    rejected.push(url(item).to_string());
+                }
+                matches
+            })
+            .collect();
+        (matched, rejected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feed_url_patterns() {
+        let patterns = FeedUrlPatterns::compile(&[
+            r"^https://www\.example\.com/news/",
+            r"^https://example\.com/articles/\d+$",
+        ])
+        .unwrap();
+        assert!(patterns.matches("https://www.example.com/news/2024/01/story"));
+        assert!(patterns.matches("https://example.com/articles/123"));
+        assert!(!patterns.matches("/news/relative-link"));
+        assert!(!patterns.matches("https://ads.example.net/click?id=1"));
+
+        let links = vec![
+            "https://www.example.com/news/a",
+            "/news/b",
+            "https://ads.example.net/c",
+        ];
+        let (matched, rejected) = patterns.retain_matching(links, |link| link);
+        assert_eq!(matched, vec!["https://www.example.com/news/a"]);
+        assert_eq!(rejected, vec!["/news/b", "https://ads.example.net/c"]);
+
+        let empty = FeedUrlPatterns::compile::<String>(&[]).unwrap();
+        assert!(empty.matches("/relative"), "パターンが無ければ検証しない");
+
+        let error = FeedUrlPatterns::compile(&["(unclosed"]).unwrap_err();
+        assert!(error.to_string().contains("(unclosed"));
+
+        println!("✅ フィードの期待URLパターンテスト成功");
+    }
+}
@@ -0,0 +1,254 @@
+use crate::core::feed::Feed;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use std::collections::HashMap;
+use url::Url;
+
+/// 評価スコアの範囲
+pub const MIN_SCORE: i16 = 1;
+pub const MAX_SCORE: i16 = 5;
+
+/// フィード健全性スコアに占めるフィードバックの重み（残りは記事取得の成功率）
+pub const FEEDBACK_WEIGHT: f64 = 0.3;
+
+// ドメイン毎のフィードバック集計
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DomainFeedbackStats {
+    pub domain: String,
+    pub rated_count: i64,
+    pub average_score: f64,
+}
+
+// フィードの健全性（記事取得の成功率とフィードバックから算出）
+#[derive(Debug, Clone)]
+pub struct FeedHealth {
+    pub feed: Feed,
+    /// 記事取得の成功率（0.0-1.0、記事が無い場合はNone）
+    pub fetch_success_rate: Option<f64>,
+    pub feedback: Option<DomainFeedbackStats>,
+    /// 健全性スコア（0.0-1.0、判断材料が無い場合はNone）
+    pub health_score: Option<f64>,
+}
+
+/// # 概要
+/// 記事に1-5の評価とメモを記録する。既に評価済みの場合は上書きする。
+///
+/// 評価できるのは保存済み（articlesテーブルに存在する）記事のみ。
+pub async fn rate_article(url: &str, score: i16, note: Option<&str>, pool: &PgPool) -> Result<()> {
+    if !(MIN_SCORE..=MAX_SCORE).contains(&score) {
+        bail!(
+            "評価は{}から{}の範囲で指定してください: {}",
+            MIN_SCORE,
+            MAX_SCORE,
+            score
+        );
+    }
+
+    let exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM articles WHERE url = $1 AND deleted_at IS NULL) AS "exists!""#,
+        url
+    )
+    .fetch_one(pool)
+    .await
+    .context("記事の存在確認に失敗")?;
+    if !exists {
+        bail!("評価対象の記事が見つかりません: {}", url);
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO article_feedback (url, score, note)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (url) DO UPDATE SET
+            score = EXCLUDED.score,
+            note = EXCLUDED.note,
+            rated_at = CURRENT_TIMESTAMP
+        "#,
+        url,
+        score,
+        note
+    )
+    .execute(pool)
+    .await
+    .context("記事の評価の保存に失敗")?;
+
+    Ok(())
+}
+
+/// 記事の評価をドメイン毎に集計し、評価件数の多い順に返す
+pub async fn get_feedback_stats_by_domain(pool: &PgPool) -> Result<Vec<DomainFeedbackStats>> {
+    let stats = sqlx::query_as!(
+        DomainFeedbackStats,
+        r#"
+        SELECT
+            lower(substring(f.url from '^[A-Za-z]+://([^/:?#]+)')) AS "domain!",
+            COUNT(*) AS "rated_count!",
+            AVG(f.score)::float8 AS "average_score!"
+        FROM article_feedback f
+        JOIN articles a ON a.url = f.url
+        WHERE a.deleted_at IS NULL
+        GROUP BY 1
+        ORDER BY 2 DESC, 1
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .context("ドメイン毎の評価の集計に失敗")?;
+
+    Ok(stats)
+}
+
+/// 記事取得の成功率とフィードバックから健全性スコア（0.0-1.0）を算出する
+///
+/// フィードバックが無い場合は成功率のみ、成功率が無い場合は評価のみで算出する。
+pub fn feed_health_score(
+    fetch_success_rate: Option<f64>,
+    feedback: Option<&DomainFeedbackStats>,
+) -> Option<f64> {
+    // 1-5の平均評価を0.0-1.0に正規化する
+    let feedback_rate =
+        feedback.map(|f| (f.average_score - MIN_SCORE as f64) / (MAX_SCORE - MIN_SCORE) as f64);
+
+    match (fetch_success_rate, feedback_rate) {
+        (Some(success), Some(rating)) => {
+            Some(success * (1.0 - FEEDBACK_WEIGHT) + rating * FEEDBACK_WEIGHT)
+        }
+        (Some(success), None) => Some(success),
+        (None, Some(rating)) => Some(rating),
+        (None, None) => None,
+    }
+}
+
+/// # 概要
+/// フィード毎の健全性を算出する。
+///
+/// フィードのRSSリンクと同じドメインの記事を、そのフィードの記事として集計する。
+pub async fn get_feed_health(feeds: &[Feed], pool: &PgPool) -> Result<Vec<FeedHealth>> {
+    let success_rates: HashMap<String, f64> = sqlx::query!(
+        r#"
+        SELECT
+            lower(substring(url from '^[A-Za-z]+://([^/:?#]+)')) AS "domain!",
+            (COUNT(*) FILTER (WHERE status_code = 200))::float8 / COUNT(*) AS "success_rate!"
+        FROM articles
+        WHERE deleted_at IS NULL
+        GROUP BY 1
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .context("ドメイン毎の記事取得成功率の集計に失敗")?
+    .into_iter()
+    .map(|row| (row.domain, row.success_rate))
+    .collect();
+
+    let feedback: HashMap<String, DomainFeedbackStats> = get_feedback_stats_by_domain(pool)
+        .await?
+        .into_iter()
+        .map(|stats| (stats.domain.clone(), stats))
+        .collect();
+
+    Ok(feeds
+        .iter()
+        .map(|feed| {
+            let domain = feed_domain(feed);
+            let fetch_success_rate = success_rates.get(&domain).copied();
+            let feedback = feedback.get(&domain).cloned();
+            FeedHealth {
+                feed: feed.clone(),
+                fetch_success_rate,
+                health_score: feed_health_score(fetch_success_rate, feedback.as_ref()),
+                feedback,
+            }
+        })
+        .collect())
+}
+
+/// フィードのRSSリンクのドメイン（解析できない場合は空文字）
+fn feed_domain(feed: &Feed) -> String {
+    Url::parse(&feed.rss_link)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_lowercase))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(average_score: f64) -> DomainFeedbackStats {
+        DomainFeedbackStats {
+            domain: "example.com".to_string(),
+            rated_count: 1,
+            average_score,
+        }
+    }
+
+    #[test]
+    fn test_feed_health_score() {
+        assert_eq!(feed_health_score(None, None), None);
+        assert_eq!(feed_health_score(Some(0.8), None), Some(0.8));
+        assert_eq!(feed_health_score(None, Some(&stats(5.0))), Some(1.0));
+
+        // 成功率1.0でも評価が最低なら重みの分だけ下がる
+        let score = feed_health_score(Some(1.0), Some(&stats(1.0))).unwrap();
+        assert!((score - (1.0 - FEEDBACK_WEIGHT)).abs() < 1e-9);
+    }
+
+    #[sqlx::test(fixtures("../../fixtures/article_feedback.sql"))]
+    async fn test_rate_article(pool: PgPool) -> Result<(), anyhow::Error> {
+        rate_article("https://good.example.com/1", 3, None, &pool).await?;
+        // 再評価は上書きされる
+        rate_article("https://good.example.com/1", 5, Some("とても良い"), &pool).await?;
+        rate_article("https://good.example.com/2", 4, None, &pool).await?;
+        rate_article("https://bad.example.com/1", 1, Some("釣りタイトル"), &pool).await?;
+
+        assert!(rate_article("https://good.example.com/1", 6, None, &pool)
+            .await
+            .is_err());
+        assert!(
+            rate_article("https://unknown.example.com/", 3, None, &pool)
+                .await
+                .is_err(),
+            "未保存の記事は評価できない"
+        );
+
+        let stats = get_feedback_stats_by_domain(&pool).await?;
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].domain, "good.example.com");
+        assert_eq!(stats[0].rated_count, 2);
+        assert!((stats[0].average_score - 4.5).abs() < 1e-9);
+        assert_eq!(stats[1].domain, "bad.example.com");
+        assert!((stats[1].average_score - 1.0).abs() < 1e-9);
+
+        println!("✅ 記事評価テスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("../../fixtures/article_feedback.sql"))]
+    async fn test_get_feed_health(pool: PgPool) -> Result<(), anyhow::Error> {
+        rate_article("https://bad.example.com/1", 1, None, &pool).await?;
+
+        let feed = |name: &str, rss_link: &str| Feed {
+            group: "test".to_string(),
+            name: name.to_string(),
+            rss_link: rss_link.to_string(),
+            ..Default::default()
+        };
+        let feeds = vec![
+            feed("good", "https://good.example.com/rss.xml"),
+            feed("bad", "https://bad.example.com/rss.xml"),
+            feed("new", "https://new.example.com/rss.xml"),
+        ];
+        let health = get_feed_health(&feeds, &pool).await?;
+
+        assert_eq!(health[0].health_score, Some(1.0));
+        // 成功率0.5、評価は最低
+        let bad = health[1].health_score.unwrap();
+        assert!((bad - 0.5 * (1.0 - FEEDBACK_WEIGHT)).abs() < 1e-9);
+        assert_eq!(health[2].health_score, None, "記事も評価も無いフィード");
+
+        println!("✅ フィード健全性テスト成功");
+        Ok(())
+    }
+}
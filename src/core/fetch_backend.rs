@@ -0,0 +1,374 @@
+use crate::core::blocklist::normalize_domain;
+use crate::core::scraper::{ScraperBackend, ScraperConfig};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
+use url::Url;
+
+// fetch_backendsテーブルの1行に対応する、ドメイン毎の記事取得バックエンドの指定
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchBackendRule {
+    pub id: i64,
+    /// 対象のドメイン（サブドメインにも適用する）
+    pub domain_pattern: String,
+    pub backend: ScraperBackend,
+    /// バックエンドの設定の上書き（`FetchBackendInput::options`を参照）
+    pub options: Value,
+    /// 複数の行に一致する場合は大きい方を優先する（同じ場合はより長いドメイン）
+    pub priority: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FetchBackendRule {
+    /// URLのホストが対象のドメイン（またはそのサブドメイン）か
+    pub fn matches(&self, url: &str) -> bool {
+        let Some(host) = Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(normalize_domain))
+        else {
+            return false;
+        };
+        host == self.domain_pattern
+            || host
+                .strip_suffix(self.domain_pattern.as_str())
+                .is_some_and(|prefix| prefix.ends_with('.'))
+    }
+
+    /// バックエンドの設定を上書きしない（既定のスクレイパーを使える）か
+    pub fn has_default_options(&self) -> bool {
+        is_default_options(&self.options)
+    }
+
+    /// `base`にこの行の`options`を重ねた、スクレイパーの作成に使う設定
+    pub fn scraper_config(&self, base: &ScraperConfig) -> Result<ScraperConfig> {
+        backend_scraper_config(self.backend, &self.options, base)
+    }
+}
+
+// POST /fetch-backends・PUT /fetch-backends/{id} のリクエストボディ
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FetchBackendInput {
+    pub domain_pattern: String,
+    pub backend: ScraperBackend,
+    /// バックエンドの設定の上書き（JSONオブジェクト、localは`scraper.local`、headlessは`scraper.headless`の項目。
+    /// 省略した項目はconfig/app.yamlの値を使い、firecrawlには指定できない）
+    #[serde(default = "default_options")]
+    pub options: Value,
+    #[serde(default)]
+    pub priority: i32,
+}
+
+fn default_options() -> Value {
+    Value::Object(Default::default())
+}
+
+impl FetchBackendInput {
+    /// # 概要
+    /// ドメインを正規化し、`options`がバックエンドの設定として読み込めるか確認する。
+    ///
+    /// # エラー
+    /// ドメインが空の場合と、`options`がバックエンドの設定として不正な場合はエラー
+    pub fn validate(mut self) -> Result<Self> {
+        self.domain_pattern = normalize_domain(&self.domain_pattern);
+        if self.domain_pattern.is_empty() {
+            bail!("記事取得バックエンドのドメインが空です");
+        }
+        if self.options.is_null() {
+            self.options = default_options();
+        }
+        backend_scraper_config(self.backend, &self.options, &ScraperConfig::default())?;
+        Ok(self)
+    }
+}
+
+fn is_default_options(options: &Value) -> bool {
+    options.is_null() || options.as_object().is_some_and(|fields| fields.is_empty())
+}
+
+/// `base`のバックエンドの設定に`options`を重ねた設定を作る
+fn backend_scraper_config(
+    backend: ScraperBackend,
+    options: &Value,
+    base: &ScraperConfig,
+) -> Result<ScraperConfig> {
+    let mut config = base.clone();
+    if is_default_options(options) {
+        return Ok(config);
+    }
+    match backend {
+        ScraperBackend::Firecrawl => bail!("firecrawlにはoptionsを指定できません"),
+        ScraperBackend::Local => config.local = apply_options(&base.local, options)?,
+        ScraperBackend::Headless => config.headless = apply_options(&base.headless, options)?,
+    }
+    Ok(config)
+}
+
+/// 設定の一部のフィールドを`options`の値で置き換える
+fn apply_options<T: Serialize + DeserializeOwned>(base: &T, options: &Value) -> Result<T> {
+    let Value::Object(options) = options else {
+        bail!("記事取得バックエンドのoptionsはJSONオブジェクトで指定してください");
+    };
+    let mut merged =
+        serde_json::to_value(base).context("バックエンドの設定のシリアライズに失敗")?;
+    if let Value::Object(fields) = &mut merged {
+        fields.extend(options.clone());
+    }
+    serde_json::from_value(merged).context("記事取得バックエンドのoptionsが不正です")
+}
+
+/// # 概要
+/// 記事取得バックエンドの対応表を、振り分けで優先する順（`priority`の降順、同じ場合は長いドメイン順）に返す。
+pub async fn list_fetch_backends(pool: &PgPool) -> Result<Vec<FetchBackendRule>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, domain_pattern, backend, options, priority, created_at, updated_at
+        FROM fetch_backends
+        ORDER BY priority DESC, length(domain_pattern) DESC, id
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .context("記事取得バックエンドの対応表の取得に失敗")?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(FetchBackendRule {
+                backend: parse_backend(&row.backend)?,
+                id: row.id,
+                domain_pattern: row.domain_pattern,
+                options: row.options,
+                priority: row.priority,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })
+        })
+        .collect()
+}
+
+/// # 概要
+/// 記事取得バックエンドの対応表に行を追加する。
+///
+/// # エラー
+/// 入力が不正な場合と、同じドメインの行が既にある場合（一意制約違反）はエラー
+pub async fn create_fetch_backend(
+    input: FetchBackendInput,
+    pool: &PgPool,
+) -> Result<FetchBackendRule> {
+    let input = input.validate()?;
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO fetch_backends (domain_pattern, backend, options, priority)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id, created_at, updated_at
+        "#,
+        input.domain_pattern,
+        input.backend.as_str(),
+        input.options,
+        input.priority
+    )
+    .fetch_one(pool)
+    .await
+    .with_context(|| format!("記事取得バックエンドの追加に失敗: {}", input.domain_pattern))?;
+
+    Ok(FetchBackendRule {
+        id: row.id,
+        domain_pattern: input.domain_pattern,
+        backend: input.backend,
+        options: input.options,
+        priority: input.priority,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    })
+}
+
+/// # 概要
+/// 記事取得バックエンドの対応表の行を置き換える。
+///
+/// # 戻り値
+/// 更新した行（`id`の行が無ければ`None`）
+///
+/// # エラー
+/// 入力が不正な場合と、同じドメインの行が他にある場合（一意制約違反）はエラー
+pub async fn update_fetch_backend(
+    id: i64,
+    input: FetchBackendInput,
+    pool: &PgPool,
+) -> Result<Option<FetchBackendRule>> {
+    let input = input.validate()?;
+    let row = sqlx::query!(
+        r#"
+        UPDATE fetch_backends
+        SET domain_pattern = $2, backend = $3, options = $4, priority = $5,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        RETURNING created_at, updated_at
+        "#,
+        id,
+        input.domain_pattern,
+        input.backend.as_str(),
+        input.options,
+        input.priority
+    )
+    .fetch_optional(pool)
+    .await
+    .with_context(|| format!("記事取得バックエンドの更新に失敗: {}", id))?;
+
+    Ok(row.map(|row| FetchBackendRule {
+        id,
+        domain_pattern: input.domain_pattern,
+        backend: input.backend,
+        options: input.options,
+        priority: input.priority,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    }))
+}
+
+/// 記事取得バックエンドの対応表の行を削除する（削除した場合は`true`）
+pub async fn delete_fetch_backend(id: i64, pool: &PgPool) -> Result<bool> {
+    let result = sqlx::query!("DELETE FROM fetch_backends WHERE id = $1", id)
+        .execute(pool)
+        .await
+        .with_context(|| format!("記事取得バックエンドの削除に失敗: {}", id))?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+fn parse_backend(backend: &str) -> Result<ScraperBackend> {
+    backend
+        .parse()
+        .map_err(|e: String| anyhow::anyhow!(e))
+        .context("記事取得バックエンドの対応表のbackendが不正です")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn input(domain: &str, backend: ScraperBackend, options: Value) -> FetchBackendInput {
+        FetchBackendInput {
+            domain_pattern: domain.to_string(),
+            backend,
+            options,
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn test_fetch_backend_input_validation() {
+        let valid = input(" News.Example.COM. ", ScraperBackend::Local, json!({}))
+            .validate()
+            .unwrap();
+        assert_eq!(valid.domain_pattern, "news.example.com", "ドメインを正規化");
+
+        assert!(input(" ", ScraperBackend::Local, json!({}))
+            .validate()
+            .is_err());
+        assert!(
+            input(
+                "example.com",
+                ScraperBackend::Firecrawl,
+                json!({"timeout_secs": 10})
+            )
+            .validate()
+            .is_err(),
+            "firecrawlにはoptionsを指定できない"
+        );
+        assert!(input(
+            "example.com",
+            ScraperBackend::Local,
+            json!({"timeout_secs": "x"})
+        )
+        .validate()
+        .is_err());
+        assert!(input("example.com", ScraperBackend::Local, json!([1]))
+            .validate()
+            .is_err());
+
+        // 省略した項目は元の設定の値を使う
+        let base = ScraperConfig {
+            local: crate::infra::api::readability::ReadabilityClientConfig {
+                timeout_secs: 30,
+                user_agent: "custom-agent".to_string(),
+            },
+            ..Default::default()
+        };
+        let config =
+            backend_scraper_config(ScraperBackend::Local, &json!({"timeout_secs": 5}), &base)
+                .unwrap();
+        assert_eq!(config.local.timeout_secs, 5);
+        assert_eq!(config.local.user_agent, "custom-agent");
+
+        println!("✅ 記事取得バックエンドの入力検証テスト成功");
+    }
+
+    #[sqlx::test]
+    async fn test_fetch_backends_crud(pool: PgPool) -> Result<(), anyhow::Error> {
+        let local = create_fetch_backend(
+            input(
+                "example.com",
+                ScraperBackend::Local,
+                json!({"timeout_secs": 10}),
+            ),
+            &pool,
+        )
+        .await?;
+        let headless = create_fetch_backend(
+            input("spa.example.com", ScraperBackend::Headless, json!({})),
+            &pool,
+        )
+        .await?;
+        assert!(
+            create_fetch_backend(
+                input("Example.com", ScraperBackend::Firecrawl, json!({})),
+                &pool
+            )
+            .await
+            .is_err(),
+            "同じドメインは追加できない"
+        );
+
+        // 長いドメインを優先し、priorityが大きい行はさらに優先する
+        let domains = |rules: Vec<FetchBackendRule>| -> Vec<String> {
+            rules.into_iter().map(|rule| rule.domain_pattern).collect()
+        };
+        assert_eq!(
+            domains(list_fetch_backends(&pool).await?),
+            ["spa.example.com", "example.com"]
+        );
+        let updated = update_fetch_backend(
+            local.id,
+            FetchBackendInput {
+                priority: 10,
+                ..input("example.com", ScraperBackend::Local, json!({}))
+            },
+            &pool,
+        )
+        .await?
+        .expect("更新した行");
+        assert_eq!(updated.options, json!({}));
+        let rules = list_fetch_backends(&pool).await?;
+        assert_eq!(domains(rules.clone()), ["example.com", "spa.example.com"]);
+        assert!(rules[0].matches("https://app.spa.example.com/1"));
+        assert!(!rules[0].matches("https://notexample.com/1"));
+        assert!(update_fetch_backend(
+            0,
+            input("other.com", ScraperBackend::Local, json!({})),
+            &pool
+        )
+        .await?
+        .is_none());
+
+        assert!(delete_fetch_backend(headless.id, &pool).await?);
+        assert!(!delete_fetch_backend(headless.id, &pool).await?);
+        assert_eq!(domains(list_fetch_backends(&pool).await?), ["example.com"]);
+
+        println!("✅ 記事取得バックエンドの対応表のCRUDテスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,285 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// DB接続確認の待ち時間の上限
+const DATABASE_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+// ヘルスチェックの設定（config/app.yamlのhealthに対応）
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HealthConfig {
+    /// ワーカーモードでヘルスチェック用のHTTPサーバを待ち受けるアドレス（省略時は起動しない）
+    pub listen_addr: Option<String>,
+    /// readinessの判定条件
+    #[serde(default)]
+    pub readiness: ReadinessConfig,
+}
+
+// readinessの判定条件
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReadinessConfig {
+    /// DBに接続できることを要求する
+    #[serde(default = "default_true")]
+    pub require_database: bool,
+    /// アプリ設定を読み込み済みであることを要求する
+    #[serde(default = "default_true")]
+    pub require_config: bool,
+    /// 直近の処理サイクルが成功していることを要求する（サイクルの無いサーバモードでは無視）
+    #[serde(default = "default_true")]
+    pub require_recent_cycle: bool,
+    /// 最後の成功からこの秒数を過ぎたサイクルは直近とみなさない（省略時は経過時間を問わない）
+    pub max_cycle_age_secs: Option<u64>,
+}
+
+impl Default for ReadinessConfig {
+    fn default() -> Self {
+        Self {
+            require_database: true,
+            require_config: true,
+            require_recent_cycle: true,
+            max_cycle_age_secs: None,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// 処理サイクルの最新の結果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CycleStatus {
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_failure_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+/// 処理サイクルの結果を記録する（ワーカーとヘルスチェックで共有する）
+#[derive(Debug, Clone, Default)]
+pub struct CycleTracker {
+    status: Arc<Mutex<CycleStatus>>,
+}
+
+impl CycleTracker {
+    pub fn record_success(&self) {
+        let mut status = self.status.lock().expect("サイクル状態のロックに失敗");
+        status.last_success_at = Some(Utc::now());
+    }
+
+    pub fn record_failure(&self, error: &anyhow::Error) {
+        let mut status = self.status.lock().expect("サイクル状態のロックに失敗");
+        status.last_failure_at = Some(Utc::now());
+        status.last_error = Some(format!("{:#}", error));
+    }
+
+    pub fn status(&self) -> CycleStatus {
+        self.status
+            .lock()
+            .expect("サイクル状態のロックに失敗")
+            .clone()
+    }
+}
+
+/// ヘルスチェックの判定に使う状態
+#[derive(Debug, Clone)]
+pub struct HealthState {
+    pub pool: PgPool,
+    pub config: ReadinessConfig,
+    /// 処理サイクルを持たないプロセス（サーバモード）では`None`
+    pub cycles: Option<CycleTracker>,
+    config_loaded: Arc<AtomicBool>,
+}
+
+impl HealthState {
+    pub fn new(pool: PgPool, config: ReadinessConfig, cycles: Option<CycleTracker>) -> Self {
+        Self {
+            pool,
+            config,
+            cycles,
+            config_loaded: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// アプリ設定の読み込み・反映が完了したことを記録する
+    pub fn mark_config_loaded(&self) {
+        self.config_loaded.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_config_loaded(&self) -> bool {
+        self.config_loaded.load(Ordering::Relaxed)
+    }
+}
+
+// readinessの個別の判定結果
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessCheck {
+    pub name: &'static str,
+    pub ok: bool,
+    pub detail: String,
+}
+
+// readinessの判定結果
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessReport {
+    pub ready: bool,
+    pub checks: Vec<ReadinessCheck>,
+}
+
+/// # 概要
+/// 設定された条件でreadinessを判定する。
+///
+/// 無効にした条件は判定結果に含めない。
+pub async fn check_readiness(state: &HealthState) -> ReadinessReport {
+    let mut checks = Vec::new();
+
+    if state.config.require_database {
+        let result = tokio::time::timeout(
+            DATABASE_CHECK_TIMEOUT,
+            sqlx::query("SELECT 1").execute(&state.pool),
+        )
+        .await;
+        let (ok, detail) = match result {
+            Ok(Ok(_)) => (true, "接続可能".to_string()),
+            Ok(Err(e)) => (false, format!("接続エラー: {}", e)),
+            Err(_) => (false, "接続確認がタイムアウトしました".to_string()),
+        };
+        checks.push(ReadinessCheck {
+            name: "database",
+            ok,
+            detail,
+        });
+    }
+
+    if state.config.require_config {
+        let ok = state.is_config_loaded();
+        checks.push(ReadinessCheck {
+            name: "config",
+            ok,
+            detail: if ok {
+                "読み込み済み"
+            } else {
+                "未読み込み"
+            }
+            .to_string(),
+        });
+    }
+
+    if let (true, Some(cycles)) = (state.config.require_recent_cycle, &state.cycles) {
+        let max_age = state
+            .config
+            .max_cycle_age_secs
+            .map(|secs| chrono::Duration::seconds(secs as i64));
+        let (ok, detail) = evaluate_cycle(&cycles.status(), max_age, Utc::now());
+        checks.push(ReadinessCheck {
+            name: "cycle",
+            ok,
+            detail,
+        });
+    }
+
+    ReadinessReport {
+        ready: checks.iter().all(|check| check.ok),
+        checks,
+    }
+}
+
+/// 直近のサイクルが成功しているかを判定する
+fn evaluate_cycle(
+    status: &CycleStatus,
+    max_age: Option<chrono::Duration>,
+    now: DateTime<Utc>,
+) -> (bool, String) {
+    let Some(success_at) = status.last_success_at else {
+        return match &status.last_error {
+            Some(error) => (false, format!("サイクル失敗: {}", error)),
+            None => (false, "サイクル未完了".to_string()),
+        };
+    };
+    if let Some(failure_at) = status.last_failure_at.filter(|at| *at > success_at) {
+        return (
+            false,
+            format!(
+                "直近のサイクルが失敗（{}）: {}",
+                failure_at.to_rfc3339(),
+                status.last_error.as_deref().unwrap_or_default()
+            ),
+        );
+    }
+    if let Some(max_age) = max_age.filter(|max_age| now - success_at > *max_age) {
+        return (
+            false,
+            format!(
+                "最後の成功から{}秒以上経過（{}）",
+                max_age.num_seconds(),
+                success_at.to_rfc3339()
+            ),
+        );
+    }
+    (true, format!("最終成功: {}", success_at.to_rfc3339()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_cycle() {
+        let now = Utc::now();
+        let minutes_ago = |m: i64| Some(now - chrono::Duration::minutes(m));
+
+        let (ok, _) = evaluate_cycle(&CycleStatus::default(), None, now);
+        assert!(!ok, "未実行は準備未完了");
+
+        let succeeded = CycleStatus {
+            last_success_at: minutes_ago(5),
+            last_failure_at: minutes_ago(10),
+            last_error: Some("タイムアウト".to_string()),
+        };
+        assert!(evaluate_cycle(&succeeded, None, now).0, "失敗後に成功した");
+        assert!(!evaluate_cycle(&succeeded, Some(chrono::Duration::minutes(1)), now).0);
+
+        let failed = CycleStatus {
+            last_success_at: minutes_ago(10),
+            last_failure_at: minutes_ago(5),
+            last_error: Some("タイムアウト".to_string()),
+        };
+        let (ok, detail) = evaluate_cycle(&failed, None, now);
+        assert!(!ok);
+        assert!(detail.contains("タイムアウト"));
+    }
+
+    #[sqlx::test]
+    async fn test_check_readiness(pool: PgPool) -> Result<(), anyhow::Error> {
+        let cycles = CycleTracker::default();
+        let state = HealthState::new(pool, ReadinessConfig::default(), Some(cycles.clone()));
+
+        let report = check_readiness(&state).await;
+        assert!(!report.ready, "設定未読み込み・サイクル未完了");
+        assert!(report.checks.iter().any(|c| c.name == "database" && c.ok));
+
+        state.mark_config_loaded();
+        cycles.record_success();
+        assert!(check_readiness(&state).await.ready);
+
+        cycles.record_failure(&anyhow::anyhow!("バックログ処理エラー"));
+        assert!(!check_readiness(&state).await.ready);
+
+        // 条件を無効にすると判定に含めない
+        let lenient = HealthState {
+            config: ReadinessConfig {
+                require_recent_cycle: false,
+                ..Default::default()
+            },
+            ..state.clone()
+        };
+        let report = check_readiness(&lenient).await;
+        assert!(report.ready);
+        assert!(report.checks.iter().all(|c| c.name != "cycle"));
+
+        println!("✅ readiness判定テスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,304 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+/// 違反の詳細に含めるURLの最大件数
+const MAX_SAMPLE_URLS: i64 = 5;
+
+// ワークフローの段階（段階の終了時に対応する不変条件を検証する）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkflowStage {
+    /// 段階1: RSSフィードからリンクを取得
+    CollectLinks,
+    /// 段階2: 未処理のリンクから記事内容を取得
+    CollectArticles,
+}
+
+// 段階間で成り立つべき不変条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Invariant {
+    /// 保存済みリンク数が記事取得対象数以上である
+    LinksCoverFetchTargets,
+    /// 実行中に取得に成功した記事には本文がある
+    SuccessHasContent,
+    /// 実行中に取得した記事には対応するリンクがある
+    ArticlesHaveLinks,
+}
+
+impl Invariant {
+    pub const ALL: [Invariant; 3] = [
+        Invariant::LinksCoverFetchTargets,
+        Invariant::SuccessHasContent,
+        Invariant::ArticlesHaveLinks,
+    ];
+
+    /// 設定ファイルでの名前
+    pub fn name(&self) -> &'static str {
+        match self {
+            Invariant::LinksCoverFetchTargets => "links_cover_fetch_targets",
+            Invariant::SuccessHasContent => "success_has_content",
+            Invariant::ArticlesHaveLinks => "articles_have_links",
+        }
+    }
+
+    /// 検証する段階
+    pub fn stage(&self) -> WorkflowStage {
+        match self {
+            Invariant::LinksCoverFetchTargets => WorkflowStage::CollectLinks,
+            Invariant::SuccessHasContent | Invariant::ArticlesHaveLinks => {
+                WorkflowStage::CollectArticles
+            }
+        }
+    }
+}
+
+// 不変条件の違反時の動作
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViolationAction {
+    /// 警告を表示してワークフローを継続する
+    #[default]
+    Warn,
+    /// ワークフローを中断する（実行記録は失敗になる）
+    Abort,
+}
+
+// 不変条件チェックの設定（config/app.yamlのinvariantsに対応）
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct InvariantConfig {
+    #[serde(default)]
+    pub on_violation: ViolationAction,
+    /// 個別のチェックのON/OFF
+    #[serde(default)]
+    pub checks: InvariantChecks,
+}
+
+// 個別のチェックのON/OFF（省略時はすべてON）
+#[derive(Debug, Clone, Deserialize)]
+pub struct InvariantChecks {
+    #[serde(default = "default_true")]
+    pub links_cover_fetch_targets: bool,
+    #[serde(default = "default_true")]
+    pub success_has_content: bool,
+    #[serde(default = "default_true")]
+    pub articles_have_links: bool,
+}
+
+impl Default for InvariantChecks {
+    fn default() -> Self {
+        Self {
+            links_cover_fetch_targets: true,
+            success_has_content: true,
+            articles_have_links: true,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl InvariantConfig {
+    pub fn is_enabled(&self, invariant: Invariant) -> bool {
+        match invariant {
+            Invariant::LinksCoverFetchTargets => self.checks.links_cover_fetch_targets,
+            Invariant::SuccessHasContent => self.checks.success_has_content,
+            Invariant::ArticlesHaveLinks => self.checks.articles_have_links,
+        }
+    }
+}
+
+// 不変条件の違反
+#[derive(Debug, Clone, Serialize)]
+pub struct InvariantViolation {
+    pub invariant: Invariant,
+    pub message: String,
+}
+
+/// # 概要
+/// 段階の終了時に、その段階に対応する有効な不変条件を検証する。
+///
+/// 記事に関する条件は`since`（ワークフローの開始時刻）以降に取得・更新された記事のみを対象とする。
+///
+/// # 戻り値
+/// 違反した不変条件（違反が無ければ空）
+pub async fn check_invariants(
+    stage: WorkflowStage,
+    since: DateTime<Utc>,
+    config: &InvariantConfig,
+    pool: &PgPool,
+) -> Result<Vec<InvariantViolation>> {
+    let mut violations = Vec::new();
+    for invariant in Invariant::ALL
+        .into_iter()
+        .filter(|i| i.stage() == stage && config.is_enabled(*i))
+    {
+        let message = match invariant {
+            Invariant::LinksCoverFetchTargets => check_links_cover_fetch_targets(pool).await?,
+            Invariant::SuccessHasContent => check_success_has_content(since, pool).await?,
+            Invariant::ArticlesHaveLinks => check_articles_have_links(since, pool).await?,
+        };
+        if let Some(message) = message {
+            violations.push(InvariantViolation { invariant, message });
+        }
+    }
+    Ok(violations)
+}
+
+async fn check_links_cover_fetch_targets(pool: &PgPool) -> Result<Option<String>> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM article_links WHERE deleted_at IS NULL) AS "links!",
+            (
+                SELECT COUNT(*) FROM article_links al
+                LEFT JOIN articles a ON al.url = a.url
+                WHERE (a.url IS NULL OR a.status_code != 200)
+                    AND al.deleted_at IS NULL
+                    AND al.fetch_target
+            ) AS "fetch_targets!"
+        "#
+    )
+    .fetch_one(pool)
+    .await
+    .context("リンク数と記事取得対象数の集計に失敗")?;
+
+    Ok((row.links < row.fetch_targets).then(|| {
+        format!(
+            "記事取得対象数が保存リンク数を超えています: 対象{}件 / リンク{}件",
+            row.fetch_targets, row.links
+        )
+    }))
+}
+
+async fn check_success_has_content(since: DateTime<Utc>, pool: &PgPool) -> Result<Option<String>> {
+    let urls = sqlx::query_scalar!(
+        r#"
+        SELECT url FROM articles
+        WHERE timestamp >= $1 AND status_code = 200
+            AND btrim(content) = ''
+        ORDER BY url
+        LIMIT $2
+        "#,
+        since,
+        MAX_SAMPLE_URLS
+    )
+    .fetch_all(pool)
+    .await
+    .context("本文の無い成功記事の検索に失敗")?;
+
+    Ok((!urls.is_empty()).then(|| format!("本文の無い成功記事があります: {}", urls.join(", "))))
+}
+
+async fn check_articles_have_links(since: DateTime<Utc>, pool: &PgPool) -> Result<Option<String>> {
+    let urls = sqlx::query_scalar!(
+        r#"
+        SELECT a.url FROM articles a
+        LEFT JOIN article_links al ON a.url = al.url
+        WHERE a.timestamp >= $1 AND al.url IS NULL
+        ORDER BY a.url
+        LIMIT $2
+        "#,
+        since,
+        MAX_SAMPLE_URLS
+    )
+    .fetch_all(pool)
+    .await
+    .context("リンクの無い記事の検索に失敗")?;
+
+    Ok((!urls.is_empty()).then(|| format!("リンクの無い記事があります: {}", urls.join(", "))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rss::{store_article_links, ArticleLink};
+
+    #[test]
+    fn test_invariant_config() {
+        let config: InvariantConfig = serde_yaml::from_str(
+            r#"
+on_violation: abort
+checks:
+  success_has_content: false
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.on_violation, ViolationAction::Abort);
+        assert!(!config.is_enabled(Invariant::SuccessHasContent));
+        assert!(
+            config.is_enabled(Invariant::ArticlesHaveLinks),
+            "省略時はON"
+        );
+        assert_eq!(
+            InvariantConfig::default().on_violation,
+            ViolationAction::Warn
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_check_invariants(pool: PgPool) -> Result<(), anyhow::Error> {
+        let since = Utc::now();
+        store_article_links(
+            &[ArticleLink {
+                url: "https://invariant.example.com/1".to_string(),
+                title: "記事".to_string(),
+                pub_date: since,
+                source: "test".to_string(),
+                ..Default::default()
+            }],
+            &pool,
+        )
+        .await?;
+        let config = InvariantConfig::default();
+        assert!(
+            check_invariants(WorkflowStage::CollectLinks, since, &config, &pool)
+                .await?
+                .is_empty()
+        );
+
+        // 本文が空の成功記事と、リンクの無い記事を保存する
+        sqlx::query!(
+            r#"
+            INSERT INTO articles (url, status_code, content) VALUES
+                ('https://invariant.example.com/1', 200, '  '),
+                ('https://orphan.example.com/1', 500, '')
+            "#
+        )
+        .execute(&pool)
+        .await?;
+        let violations =
+            check_invariants(WorkflowStage::CollectArticles, since, &config, &pool).await?;
+        let invariants: Vec<Invariant> = violations.iter().map(|v| v.invariant).collect();
+        assert_eq!(
+            invariants,
+            vec![Invariant::SuccessHasContent, Invariant::ArticlesHaveLinks]
+        );
+        assert!(violations[1].message.contains("orphan.example.com"));
+
+        // 無効にしたチェックは検証しない
+        let lenient = InvariantConfig {
+            checks: InvariantChecks {
+                success_has_content: false,
+                articles_have_links: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(
+            check_invariants(WorkflowStage::CollectArticles, since, &lenient, &pool)
+                .await?
+                .is_empty()
+        );
+
+        // 以前に取得した記事は対象外
+        let violations =
+            check_invariants(WorkflowStage::CollectArticles, Utc::now(), &config, &pool).await?;
+        assert!(violations.is_empty());
+
+        println!("✅ 不変条件チェックテスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,597 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use std::fmt;
+use std::str::FromStr;
+
+/// 実行状態（job_runs.statusの値、`JobState::as_str`と同じ）
+pub const JOB_STATUS_RUNNING: &str = "running";
+pub const JOB_STATUS_SUCCEEDED: &str = "succeeded";
+pub const JOB_STATUS_FAILED: &str = "failed";
+
+// ワークフローの実行の状態（job_runs.statusに保存する）
+//
+// running → collecting_links → fetching_articles → succeededの順に進み、
+// 完了前のどの状態からもfailedに遷移できる。段階を持たないジョブはrunningから直接完了する。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    /// 開始した（段階に入る前）
+    Running,
+    /// 段階1: RSSフィードからリンクを収集中
+    CollectingLinks,
+    /// 段階2: 記事本文を取得中
+    FetchingArticles,
+    /// 完了
+    Succeeded,
+    /// 失敗
+    Failed,
+}
+
+impl JobState {
+    pub const ALL: [JobState; 5] = [
+        Self::Running,
+        Self::CollectingLinks,
+        Self::FetchingArticles,
+        Self::Succeeded,
+        Self::Failed,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => JOB_STATUS_RUNNING,
+            Self::CollectingLinks => "collecting_links",
+            Self::FetchingArticles => "fetching_articles",
+            Self::Succeeded => JOB_STATUS_SUCCEEDED,
+            Self::Failed => JOB_STATUS_FAILED,
+        }
+    }
+
+    /// 完了した状態か（以降の遷移は無い）
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Succeeded | Self::Failed)
+    }
+
+    /// `next`に遷移できるか（同じ状態への遷移はできない）
+    pub fn can_transition_to(&self, next: JobState) -> bool {
+        match self {
+            Self::Running => next != Self::Running,
+            Self::CollectingLinks => matches!(next, Self::FetchingArticles | Self::Failed),
+            Self::FetchingArticles => next.is_terminal(),
+            Self::Succeeded | Self::Failed => false,
+        }
+    }
+}
+
+impl fmt::Display for JobState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for JobState {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|state| state.as_str() == s)
+            .with_context(|| format!("不明なジョブの状態: {}", s))
+    }
+}
+
+// job_run_transitionsテーブルの1行（状態遷移の履歴）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JobRunTransition {
+    pub from_state: JobState,
+    pub to_state: JobState,
+    pub transitioned_at: DateTime<Utc>,
+}
+
+// job_runsテーブルの1行に対応するワークフローの実行記録
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct JobRun {
+    pub id: i64,
+    pub job_name: String,
+    pub status: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// 実行期間中に追加された記事リンク数
+    pub new_links: i64,
+    /// 実行期間中に取得に成功した記事数
+    pub fetched_articles: i64,
+    /// 実行期間中に取得に失敗した記事数
+    pub failed_articles: i64,
+}
+
+impl JobRun {
+    /// 現在の状態
+    pub fn state(&self) -> Result<JobState> {
+        self.status.parse()
+    }
+
+    /// 記事取得のエラー率（0.0-1.0、記事を取得していない場合は0.0）
+    pub fn error_rate(&self) -> f64 {
+        let total = self.fetched_articles + self.failed_articles;
+        if total == 0 {
+            0.0
+        } else {
+            self.failed_articles as f64 / total as f64
+        }
+    }
+}
+
+// 2つの実行記録の比較結果（job_bからjob_aを引いた差分）
+#[derive(Debug, Clone)]
+pub struct RunComparison {
+    pub job_a: JobRun,
+    pub job_b: JobRun,
+    pub new_links_diff: i64,
+    pub fetched_articles_diff: i64,
+    pub failed_articles_diff: i64,
+    pub error_rate_diff: f64,
+}
+
+impl RunComparison {
+    /// 2つの実行記録の差分を算出する（job_aを基準とする）
+    pub fn new(job_a: JobRun, job_b: JobRun) -> Self {
+        Self {
+            new_links_diff: job_b.new_links - job_a.new_links,
+            fetched_articles_diff: job_b.fetched_articles - job_a.fetched_articles,
+            failed_articles_diff: job_b.failed_articles - job_a.failed_articles,
+            error_rate_diff: job_b.error_rate() - job_a.error_rate(),
+            job_a,
+            job_b,
+        }
+    }
+
+    /// サマリー表示用の行を返す
+    pub fn format_lines(&self) -> Vec<String> {
+        vec![
+            format!(
+                "新規リンク: {}件（{:+}件）",
+                self.job_b.new_links, self.new_links_diff
+            ),
+            format!(
+                "記事取得成功: {}件（{:+}件）",
+                self.job_b.fetched_articles, self.fetched_articles_diff
+            ),
+            format!(
+                "記事取得失敗: {}件（{:+}件）",
+                self.job_b.failed_articles, self.failed_articles_diff
+            ),
+            format!(
+                "エラー率: {:.1}%（{:+.1}pt）",
+                self.job_b.error_rate() * 100.0,
+                self.error_rate_diff * 100.0
+            ),
+        ]
+    }
+}
+
+/// ワークフローの実行開始を記録する
+pub async fn start_job_run(job_name: &str, pool: &PgPool) -> Result<JobRun> {
+    let run = sqlx::query_as!(
+        JobRun,
+        r#"
+        INSERT INTO job_runs (job_name, status)
+        VALUES ($1, $2)
+        RETURNING id, job_name, status, started_at, finished_at,
+            new_links, fetched_articles, failed_articles
+        "#,
+        job_name,
+        JOB_STATUS_RUNNING
+    )
+    .fetch_one(pool)
+    .await
+    .context("ジョブ実行記録の作成に失敗")?;
+
+    Ok(run)
+}
+
+/// # 概要
+/// ワークフローの実行終了を記録する（`status`はsucceededまたはfailed）。
+///
+/// 実行期間中に追加されたリンク数と、取得・更新された記事の成功/失敗数を集計して保存する。
+/// 状態遷移の検証は`transition_job_run`と同じ。
+pub async fn finish_job_run(job_id: i64, status: &str, pool: &PgPool) -> Result<JobRun> {
+    let state: JobState = status.parse()?;
+    if !state.is_terminal() {
+        bail!("完了の状態ではありません: {}", state);
+    }
+    transition_job_run(job_id, state, pool).await
+}
+
+/// # 概要
+/// ワークフローの実行の状態を`next`に遷移させ、遷移をjob_run_transitionsに記録する。
+///
+/// 現在の状態から遷移できない場合（完了済み・段階の逆戻りなど）はエラーにして状態を変えない。
+/// 完了の状態への遷移では`finished_at`と実行期間中の統計も保存する。
+pub async fn transition_job_run(job_id: i64, next: JobState, pool: &PgPool) -> Result<JobRun> {
+    let mut tx = pool.begin().await.context("トランザクションの開始に失敗")?;
+    let current = sqlx::query_scalar!(
+        "SELECT status FROM job_runs WHERE id = $1 FOR UPDATE",
+        job_id
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .with_context(|| format!("ジョブ実行記録の取得に失敗: {}", job_id))?;
+    let current: JobState = current.parse()?;
+    if !current.can_transition_to(next) {
+        bail!(
+            "無効な状態遷移: {} -> {}（job_id={}）",
+            current,
+            next,
+            job_id
+        );
+    }
+
+    let run = if next.is_terminal() {
+        sqlx::query_as!(
+            JobRun,
+            r#"
+            UPDATE job_runs r SET
+                status = $2,
+                finished_at = CURRENT_TIMESTAMP,
+                new_links = (
+                    SELECT COUNT(*) FROM article_links WHERE created_at >= r.started_at
+                ),
+                fetched_articles = (
+                    SELECT COUNT(*) FROM articles
+                    WHERE timestamp >= r.started_at AND status_code = 200
+                ),
+                failed_articles = (
+                    SELECT COUNT(*) FROM articles
+                    WHERE timestamp >= r.started_at AND status_code <> 200
+                )
+            WHERE r.id = $1
+            RETURNING id, job_name, status, started_at, finished_at,
+                new_links, fetched_articles, failed_articles
+            "#,
+            job_id,
+            next.as_str()
+        )
+        .fetch_one(&mut *tx)
+        .await
+    } else {
+        sqlx::query_as!(
+            JobRun,
+            r#"
+            UPDATE job_runs SET status = $2 WHERE id = $1
+            RETURNING id, job_name, status, started_at, finished_at,
+                new_links, fetched_articles, failed_articles
+            "#,
+            job_id,
+            next.as_str()
+        )
+        .fetch_one(&mut *tx)
+        .await
+    }
+    .with_context(|| format!("ジョブ実行記録の更新に失敗: {}", job_id))?;
+
+    sqlx::query!(
+        "INSERT INTO job_run_transitions (job_id, from_state, to_state) VALUES ($1, $2, $3)",
+        job_id,
+        current.as_str(),
+        next.as_str()
+    )
+    .execute(&mut *tx)
+    .await
+    .with_context(|| format!("状態遷移の記録に失敗: {}", job_id))?;
+
+    tx.commit()
+        .await
+        .context("トランザクションのコミットに失敗")?;
+    Ok(run)
+}
+
+// フィードのグループに限定した実行期間中の統計
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GroupRunStats {
+    /// 実行期間中に追加されたグループの記事リンク数
+    pub new_links: i64,
+    /// 実行期間中に取得に成功したグループの記事数
+    pub fetched_articles: i64,
+    /// 実行期間中に取得に失敗したグループの記事数
+    pub failed_articles: i64,
+}
+
+/// # 概要
+/// `since`以降に追加・取得された、フィードのグループ`group`のリンクと記事の件数を数える。
+///
+/// 実行記録の統計は期間中のすべてのリンク・記事を数えるため、複数のグループを並行して
+/// 実行した場合にグループ毎の件数を求めるのに使う（リンクの`feed`が`group/name`のもの）。
+pub async fn count_group_run_stats(
+    group: &str,
+    since: DateTime<Utc>,
+    pool: &PgPool,
+) -> Result<GroupRunStats> {
+    let stats = sqlx::query_as!(
+        GroupRunStats,
+        r#"
+        SELECT
+            (
+                SELECT COUNT(*) FROM article_links
+                WHERE created_at >= $2 AND split_part(feed, '/', 1) = $1
+            ) AS "new_links!",
+            (
+                SELECT COUNT(*) FROM articles a JOIN article_links al ON al.url = a.url
+                WHERE a.timestamp >= $2 AND a.status_code = 200
+                    AND split_part(al.feed, '/', 1) = $1
+            ) AS "fetched_articles!",
+            (
+                SELECT COUNT(*) FROM articles a JOIN article_links al ON al.url = a.url
+                WHERE a.timestamp >= $2 AND a.status_code <> 200
+                    AND split_part(al.feed, '/', 1) = $1
+            ) AS "failed_articles!"
+        "#,
+        group,
+        since
+    )
+    .fetch_one(pool)
+    .await
+    .with_context(|| format!("グループの実行統計の取得に失敗: {}", group))?;
+    Ok(stats)
+}
+
+/// 実行の状態遷移の履歴を古い順に取得する
+pub async fn list_job_run_transitions(job_id: i64, pool: &PgPool) -> Result<Vec<JobRunTransition>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT from_state, to_state, transitioned_at FROM job_run_transitions
+        WHERE job_id = $1
+        ORDER BY id
+        "#,
+        job_id
+    )
+    .fetch_all(pool)
+    .await
+    .with_context(|| format!("状態遷移の履歴の取得に失敗: {}", job_id))?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(JobRunTransition {
+                from_state: row.from_state.parse()?,
+                to_state: row.to_state.parse()?,
+                transitioned_at: row.transitioned_at,
+            })
+        })
+        .collect()
+}
+
+/// # 概要
+/// 同じジョブ名の未完了の実行記録を古い順に取得する。
+///
+/// プロセスの停止（クラッシュ）で完了を記録できなかった実行を、再起動後に検出して再開するために使う。
+/// 同じジョブ名を複数のプロセスで並行して実行しない前提（実行中の他のプロセスの記録も含まれる）。
+pub async fn find_unfinished_job_runs(job_name: &str, pool: &PgPool) -> Result<Vec<JobRun>> {
+    sqlx::query_as!(
+        JobRun,
+        r#"
+        SELECT id, job_name, status, started_at, finished_at,
+            new_links, fetched_articles, failed_articles
+        FROM job_runs
+        WHERE job_name = $1 AND finished_at IS NULL
+        ORDER BY id
+        "#,
+        job_name
+    )
+    .fetch_all(pool)
+    .await
+    .with_context(|| format!("未完了のジョブ実行記録の取得に失敗: {}", job_name))
+}
+
+/// 指定IDの実行記録を取得する
+pub async fn get_job_run(job_id: i64, pool: &PgPool) -> Result<JobRun> {
+    let run = sqlx::query_as!(
+        JobRun,
+        r#"
+        SELECT id, job_name, status, started_at, finished_at,
+            new_links, fetched_articles, failed_articles
+        FROM job_runs WHERE id = $1
+        "#,
+        job_id
+    )
+    .fetch_one(pool)
+    .await
+    .with_context(|| format!("ジョブ実行記録の取得に失敗: {}", job_id))?;
+
+    Ok(run)
+}
+
+/// 同じジョブ名で、指定した実行より前に完了した直近の実行記録を取得する
+pub async fn find_previous_job_run(run: &JobRun, pool: &PgPool) -> Result<Option<JobRun>> {
+    let previous = sqlx::query_as!(
+        JobRun,
+        r#"
+        SELECT id, job_name, status, started_at, finished_at,
+            new_links, fetched_articles, failed_articles
+        FROM job_runs
+        WHERE job_name = $1 AND id < $2 AND finished_at IS NOT NULL
+        ORDER BY id DESC
+        LIMIT 1
+        "#,
+        run.job_name,
+        run.id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("前回のジョブ実行記録の取得に失敗")?;
+
+    Ok(previous)
+}
+
+/// 2つの実行記録の統計を比較する（job_aを基準とした差分を返す）
+pub async fn compare_job_runs(job_a: i64, job_b: i64, pool: &PgPool) -> Result<RunComparison> {
+    let run_a = get_job_run(job_a, pool).await?;
+    let run_b = get_job_run(job_b, pool).await?;
+    Ok(RunComparison::new(run_a, run_b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::article::{store_article_content, ArticleContent};
+    use crate::core::rss::{store_article_links, ArticleLink};
+
+    async fn store_fixture(prefix: &str, links: usize, failed: usize, pool: &PgPool) -> Result<()> {
+        let article_links: Vec<ArticleLink> = (0..links)
+            .map(|i| ArticleLink {
+                url: format!("https://job.example.com/{}/{}", prefix, i),
+                title: format!("記事{}", i),
+                pub_date: Utc::now(),
+                source: "test".to_string(),
+                ..Default::default()
+            })
+            .collect();
+        store_article_links(&article_links, pool).await?;
+
+        for (i, link) in article_links.iter().enumerate() {
+            let article = if i < failed {
+                ArticleContent::error_placeholder(&link.url, "取得エラー")
+            } else {
+                ArticleContent {
+                    url: link.url.clone(),
+                    timestamp: Utc::now(),
+                    status_code: 200,
+                    content: "本文".to_string(),
+                    raw_html: None,
+                }
+            };
+            store_article_content(&article, pool).await?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_comparison_format() {
+        let run = |new_links, fetched_articles, failed_articles| JobRun {
+            id: 1,
+            job_name: "rss_workflow".to_string(),
+            status: JOB_STATUS_SUCCEEDED.to_string(),
+            started_at: Utc::now(),
+            finished_at: None,
+            new_links,
+            fetched_articles,
+            failed_articles,
+        };
+        let comparison = RunComparison::new(run(10, 9, 1), run(4, 2, 2));
+
+        assert_eq!(
+            comparison.format_lines(),
+            vec![
+                "新規リンク: 4件（-6件）",
+                "記事取得成功: 2件（-7件）",
+                "記事取得失敗: 2件（+1件）",
+                "エラー率: 50.0%（+40.0pt）",
+            ]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_compare_job_runs(pool: PgPool) -> Result<(), anyhow::Error> {
+        let first = start_job_run("rss_workflow", &pool).await?;
+        store_fixture("first", 4, 0, &pool).await?;
+        let first = finish_job_run(first.id, JOB_STATUS_SUCCEEDED, &pool).await?;
+        assert_eq!(first.new_links, 4);
+        assert_eq!(first.fetched_articles, 4);
+
+        let second = start_job_run("rss_workflow", &pool).await?;
+        store_fixture("second", 2, 1, &pool).await?;
+        let second = finish_job_run(second.id, JOB_STATUS_SUCCEEDED, &pool).await?;
+
+        let previous = find_previous_job_run(&second, &pool).await?;
+        assert_eq!(previous.map(|r| r.id), Some(first.id));
+
+        let comparison = compare_job_runs(first.id, second.id, &pool).await?;
+        assert_eq!(comparison.new_links_diff, -2);
+        assert_eq!(comparison.fetched_articles_diff, -3);
+        assert_eq!(comparison.failed_articles_diff, 1);
+        assert!((comparison.error_rate_diff - 0.5).abs() < 1e-9);
+
+        println!("✅ ジョブ実行比較テスト成功");
+        Ok(())
+    }
+
+    #[test]
+    fn test_job_state_transitions() {
+        use JobState::*;
+        assert!(Running.can_transition_to(CollectingLinks));
+        assert!(Running.can_transition_to(Succeeded), "段階を持たないジョブ");
+        assert!(CollectingLinks.can_transition_to(FetchingArticles));
+        assert!(
+            !CollectingLinks.can_transition_to(Succeeded),
+            "段階を飛ばせない"
+        );
+        assert!(
+            !FetchingArticles.can_transition_to(CollectingLinks),
+            "逆戻りできない"
+        );
+        assert!(FetchingArticles.can_transition_to(Failed));
+        assert!(JobState::ALL
+            .into_iter()
+            .all(|next| !Succeeded.can_transition_to(next) && !Failed.can_transition_to(next)));
+        assert!(!Running.can_transition_to(Running));
+        for state in JobState::ALL {
+            assert_eq!(state.as_str().parse::<JobState>().unwrap(), state);
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_transition_job_run(pool: PgPool) -> Result<(), anyhow::Error> {
+        let run = start_job_run("rss_workflow", &pool).await?;
+        transition_job_run(run.id, JobState::CollectingLinks, &pool).await?;
+        let run = transition_job_run(run.id, JobState::FetchingArticles, &pool).await?;
+        assert_eq!(run.state()?, JobState::FetchingArticles);
+        assert!(run.finished_at.is_none());
+
+        // 再起動後に未完了の実行として検出できる
+        let unfinished = find_unfinished_job_runs("rss_workflow", &pool).await?;
+        assert_eq!(
+            unfinished.iter().map(|r| r.id).collect::<Vec<_>>(),
+            vec![run.id]
+        );
+
+        // 無効な遷移は拒否して状態を変えない
+        assert!(transition_job_run(run.id, JobState::CollectingLinks, &pool)
+            .await
+            .is_err());
+        assert!(finish_job_run(run.id, JOB_STATUS_RUNNING, &pool)
+            .await
+            .is_err());
+        assert_eq!(
+            get_job_run(run.id, &pool).await?.state()?,
+            JobState::FetchingArticles
+        );
+
+        let run = finish_job_run(run.id, JOB_STATUS_SUCCEEDED, &pool).await?;
+        assert!(run.finished_at.is_some());
+        assert!(
+            finish_job_run(run.id, JOB_STATUS_FAILED, &pool)
+                .await
+                .is_err(),
+            "完了済み"
+        );
+        assert!(find_unfinished_job_runs("rss_workflow", &pool)
+            .await?
+            .is_empty());
+
+        let transitions: Vec<(JobState, JobState)> = list_job_run_transitions(run.id, &pool)
+            .await?
+            .into_iter()
+            .map(|t| (t.from_state, t.to_state))
+            .collect();
+        assert_eq!(
+            transitions,
+            vec![
+                (JobState::Running, JobState::CollectingLinks),
+                (JobState::CollectingLinks, JobState::FetchingArticles),
+                (JobState::FetchingArticles, JobState::Succeeded),
+            ]
+        );
+
+        println!("✅ ジョブ状態遷移テスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,894 @@
+use crate::core::article::{search_articles, Article, ArticleQuery};
+use crate::core::rss::UNTITLED;
+use crate::core::storage_quota::refresh_domain_storage_usage;
+use crate::infra::compute::calc_hash;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+use std::io::Write;
+use url::Url;
+
+// データ品質チェックの閾値（件数がこれを超えたら警告する）
+#[derive(Debug, Clone)]
+pub struct QualityThresholds {
+    /// タイトルが空（または仮タイトル）のリンク数の上限
+    pub max_empty_titles: i64,
+    /// 本文が短すぎる成功記事数の上限
+    pub max_short_contents: i64,
+    /// 公開日時が異常なリンク数の上限
+    pub max_date_anomalies: i64,
+    /// これより短い本文を「短すぎる」とみなす文字数
+    pub min_content_chars: i32,
+    /// 現在時刻からこれ以上未来の公開日時を異常とみなす
+    pub max_future_skew: Duration,
+    /// これより古い公開日時を異常とみなす
+    pub oldest_valid_date: DateTime<Utc>,
+}
+
+impl Default for QualityThresholds {
+    fn default() -> Self {
+        Self {
+            max_empty_titles: 10,
+            max_short_contents: 10,
+            max_date_anomalies: 0,
+            min_content_chars: 100,
+            max_future_skew: Duration::days(1),
+            oldest_valid_date: Utc.with_ymd_and_hms(1995, 1, 1, 0, 0, 0).unwrap(),
+        }
+    }
+}
+
+// データ品質チェックの結果
+#[derive(Debug, Clone)]
+pub struct QualityReport {
+    pub thresholds: QualityThresholds,
+    /// タイトルが空（または仮タイトル）のリンク数
+    pub empty_title_links: i64,
+    /// 本文が`min_content_chars`文字未満の成功記事数
+    pub short_content_articles: i64,
+    /// 公開日時が未来すぎる・古すぎるリンク数
+    pub date_anomalies: i64,
+}
+
+impl QualityReport {
+    /// 閾値を超えた指標の警告メッセージを返す（問題が無ければ空）
+    pub fn warnings(&self) -> Vec<String> {
+        let t = &self.thresholds;
+        let checks = [
+            (
+                "タイトル空のリンク",
+                self.empty_title_links,
+                t.max_empty_titles,
+            ),
+            (
+                "本文が短い成功記事",
+                self.short_content_articles,
+                t.max_short_contents,
+            ),
+            (
+                "日付異常のリンク",
+                self.date_anomalies,
+                t.max_date_anomalies,
+            ),
+        ];
+        checks
+            .iter()
+            .filter(|(_, count, max)| count > max)
+            .map(|(name, count, max)| {
+                format!("{}が閾値を超えています: {}件（上限{}件）", name, count, max)
+            })
+            .collect()
+    }
+
+    /// レポートを表示用の行に整形する
+    pub fn format_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("タイトル空のリンク: {}件", self.empty_title_links),
+            format!(
+                "本文{}文字未満の成功記事: {}件",
+                self.thresholds.min_content_chars, self.short_content_articles
+            ),
+            format!("日付異常のリンク: {}件", self.date_anomalies),
+        ];
+        lines.extend(self.warnings().into_iter().map(|w| format!("⚠ {}", w)));
+        lines
+    }
+}
+
+/// 既定の閾値でデータ品質チェックを行う
+pub async fn run_quality_checks(pool: &PgPool) -> Result<QualityReport> {
+    run_quality_checks_with(QualityThresholds::default(), pool).await
+}
+
+/// # 概要
+/// 収集済みデータの品質指標を計算する。ゴミ箱のレコードは対象外。
+///
+/// - タイトルが空白のみ、または仮タイトル（`UNTITLED`）のリンク数
+/// - 本文が`min_content_chars`文字未満の成功記事数
+/// - 公開日時が`max_future_skew`以上未来、または`oldest_valid_date`より古いリンク数
+pub async fn run_quality_checks_with(
+    thresholds: QualityThresholds,
+    pool: &PgPool,
+) -> Result<QualityReport> {
+    let future_limit = Utc::now() + thresholds.max_future_skew;
+
+    let links = sqlx::query!(
+        r#"
+        SELECT
+            COUNT(*) FILTER (WHERE btrim(title) = '' OR title = $1) AS "empty_titles!",
+            COUNT(*) FILTER (WHERE pub_date > $2 OR pub_date < $3) AS "date_anomalies!"
+        FROM article_links
+        WHERE deleted_at IS NULL
+        "#,
+        UNTITLED,
+        future_limit,
+        thresholds.oldest_valid_date
+    )
+    .fetch_one(pool)
+    .await
+    .context("記事リンクの品質指標の集計に失敗")?;
+
+    let short_content_articles = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) AS "count!" FROM articles
+        WHERE deleted_at IS NULL AND status_code = 200 AND char_length(content) < $1
+        "#,
+        thresholds.min_content_chars
+    )
+    .fetch_one(pool)
+    .await
+    .context("記事の品質指標の集計に失敗")?;
+
+    Ok(QualityReport {
+        thresholds,
+        empty_title_links: links.empty_titles,
+        short_content_articles,
+        date_anomalies: links.date_anomalies,
+    })
+}
+
+/// 固有名詞を除去した箇所に入れる文字列
+pub const PROPER_NOUN_PLACEHOLDER: &str = "[固有名詞]";
+
+// 匿名化エクスポートの設定
+#[derive(Debug, Clone, Default)]
+pub struct AnonymizeOptions {
+    /// 仮名のハッシュに混ぜる値（空だとドメイン名の総当たりで仮名から元に戻せてしまう）
+    pub salt: String,
+    /// タイトル・本文から固有名詞とみられる語を除去するかどうか
+    pub strip_proper_nouns: bool,
+}
+
+// 仮名から元の値に戻すための対応表（データセットとは別に保管する）
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AnonymizationMapping {
+    /// 仮名のドメイン -> 元のドメイン
+    pub domains: BTreeMap<String, String>,
+    /// 仮名のURL -> 元のURL
+    pub urls: BTreeMap<String, String>,
+}
+
+impl AnonymizationMapping {
+    /// 仮名のURLを元のURLに戻す
+    pub fn restore_url(&self, pseudonym: &str) -> Option<&str> {
+        self.urls.get(pseudonym).map(String::as_str)
+    }
+
+    /// 対応表をJSONとして書き出す
+    pub fn write_json<W: Write>(&self, writer: W) -> Result<()> {
+        serde_json::to_writer_pretty(writer, self).context("対応表の書き出しに失敗")
+    }
+}
+
+// 匿名化エクスポートの結果
+#[derive(Debug, Clone, Default)]
+pub struct AnonymizeReport {
+    /// 書き出した記事数
+    pub articles: usize,
+    pub mapping: AnonymizationMapping,
+}
+
+// データセットの1行分（JSON Lines）
+#[derive(Debug, Serialize)]
+struct AnonymizedArticle {
+    url: String,
+    domain: String,
+    title: String,
+    pub_date: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+    status_code: Option<i32>,
+    content: Option<String>,
+}
+
+// URL・ドメインを仮名に置き換え、対応表を記録する
+struct Anonymizer<'a> {
+    options: &'a AnonymizeOptions,
+    mapping: AnonymizationMapping,
+}
+
+impl<'a> Anonymizer<'a> {
+    fn domain(&mut self, host: &str) -> String {
+        let pseudonym = format!(
+            "d{}",
+            calc_hash(&format!("{}{}", self.options.salt, host), 12)
+        );
+        self.mapping
+            .domains
+            .insert(pseudonym.clone(), host.to_string());
+        pseudonym
+    }
+
+    fn url(&mut self, url: &str) -> (String, String) {
+        let host = Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_lowercase))
+            .unwrap_or_default();
+        let domain = self.domain(&host);
+        let pseudonym = format!(
+            "https://{}.invalid/{}",
+            domain,
+            calc_hash(&format!("{}{}", self.options.salt, url), 16)
+        );
+        self.mapping.urls.insert(pseudonym.clone(), url.to_string());
+        (pseudonym, domain)
+    }
+
+    /// テキスト中のURLを仮名に置き換え、設定に応じて固有名詞を除去する
+    fn text(&mut self, text: &str) -> String {
+        let mut masked = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest
+            .find("http://")
+            .into_iter()
+            .chain(rest.find("https://"))
+            .min()
+        {
+            masked.push_str(&rest[..start]);
+            let len = rest[start..]
+                .find(|c: char| {
+                    c.is_whitespace() || matches!(c, ')' | ']' | '>' | '"' | '\'' | '）')
+                })
+                .unwrap_or(rest.len() - start);
+            // 文末の句読点はURLに含めない
+            let url = rest[start..start + len].trim_end_matches(['.', ',', '。', '、']);
+            masked.push_str(&self.url(url).0);
+            rest = &rest[start + url.len()..];
+        }
+        masked.push_str(rest);
+
+        if self.options.strip_proper_nouns {
+            strip_proper_nouns(&masked)
+        } else {
+            masked
+        }
+    }
+}
+
+/// # 概要
+/// 条件に合う記事を、URL・ドメインを仮名化したJSON Lines形式で書き出す（外部共有用）。
+///
+/// 記事のURLは`https://<ドメインの仮名>.invalid/<URLのハッシュ>`に置き換え、
+/// タイトル・本文中のURLも同じ規則で置き換える。仮名から元に戻すための対応表は
+/// 戻り値の`mapping`に入るので、`write_json`でデータセットとは別のファイルに保存すること。
+pub async fn export_anonymized<W: Write>(
+    query: Option<ArticleQuery>,
+    options: &AnonymizeOptions,
+    writer: W,
+    pool: &PgPool,
+) -> Result<AnonymizeReport> {
+    let articles = search_articles(query, pool).await?;
+    write_anonymized(&articles, options, writer)
+}
+
+/// 記事のリストを仮名化してJSON Lines形式で書き出す
+pub fn write_anonymized<W: Write>(
+    articles: &[Article],
+    options: &AnonymizeOptions,
+    mut writer: W,
+) -> Result<AnonymizeReport> {
+    let mut anonymizer = Anonymizer {
+        options,
+        mapping: AnonymizationMapping::default(),
+    };
+
+    for article in articles {
+        let (url, domain) = anonymizer.url(&article.url);
+        let record = AnonymizedArticle {
+            url,
+            domain,
+            title: anonymizer.text(&article.title),
+            pub_date: article.pub_date,
+            updated_at: article.updated_at,
+            status_code: article.status_code,
+            content: article.content.as_deref().map(|c| anonymizer.text(c)),
+        };
+        serde_json::to_writer(&mut writer, &record)
+            .with_context(|| format!("匿名化した記事の書き出しに失敗: {}", article.url))?;
+        writer
+            .write_all(b"\n")
+            .context("匿名化した記事の書き出しに失敗")?;
+    }
+    writer.flush().context("匿名化した記事の書き出しに失敗")?;
+
+    Ok(AnonymizeReport {
+        articles: articles.len(),
+        mapping: anonymizer.mapping,
+    })
+}
+
+/// # 概要
+/// テキストから固有名詞とみられる語を`PROPER_NOUN_PLACEHOLDER`に置き換える。
+///
+/// 形態素解析は行わず、文頭以外で大文字から始まる英単語（2文字以上）を固有名詞とみなす
+/// 簡易的な判定のため、和文中の人名・組織名などは除去できない。
+/// 連続する固有名詞（`Tim Cook`など）はまとめて1つに置き換える。
+pub fn strip_proper_nouns(text: &str) -> String {
+    let joined_placeholder = format!("{} ", PROPER_NOUN_PLACEHOLDER);
+    let is_word_char = |c: char| c.is_ascii_alphanumeric() || c == '\'' || c == '-';
+
+    let mut result = String::with_capacity(text.len());
+    let mut sentence_start = true;
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if !c.is_ascii_alphanumeric() {
+            result.push(c);
+            if matches!(c, '.' | '!' | '?' | '。' | '！' | '？' | '\n') {
+                sentence_start = true;
+            } else if c.is_alphanumeric() {
+                sentence_start = false;
+            }
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+
+        let len = rest.find(|c: char| !is_word_char(c)).unwrap_or(rest.len());
+        let word = &rest[..len];
+        let capitalized = word.len() >= 2 && word.starts_with(|c: char| c.is_ascii_uppercase());
+        if capitalized && !sentence_start {
+            if result.ends_with(&joined_placeholder) {
+                result.pop();
+            } else {
+                result.push_str(PROPER_NOUN_PLACEHOLDER);
+            }
+        } else {
+            result.push_str(word);
+        }
+        sentence_start = false;
+        rest = &rest[len..];
+    }
+    result
+}
+
+/// 旧スキーマから移行したリンクの取得元（`source`）
+pub const LEGACY_SOURCE: &str = "legacy";
+
+// 旧スキーマからのデータ移行の設定
+#[derive(Debug, Clone)]
+pub struct LegacyMigrationOptions {
+    /// 旧テーブル（rss_articles / firecrawl_articles）のあるスキーマ
+    pub legacy_schema: String,
+    /// 移行後に旧テーブルと照合する件数（リンク・記事それぞれ）
+    pub sample_size: i64,
+    /// 移行と検証だけ行い、最後にロールバックする
+    pub dry_run: bool,
+}
+
+impl Default for LegacyMigrationOptions {
+    fn default() -> Self {
+        Self {
+            legacy_schema: "public".to_string(),
+            sample_size: 20,
+            dry_run: false,
+        }
+    }
+}
+
+// 旧スキーマからのデータ移行の結果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MigrationReport {
+    /// rss_articlesの行数
+    pub legacy_links: i64,
+    /// firecrawl_articlesの行数
+    pub legacy_articles: i64,
+    /// URLが空・公開日時が無いため移行しなかったrss_articlesの行数
+    pub invalid_links: i64,
+    /// URLが空のため移行しなかったfirecrawl_articlesの行数
+    pub invalid_articles: i64,
+    /// 同じURLの行をまとめたrss_articlesの行数（公開日時が新しい行を残す）
+    pub duplicate_links: i64,
+    /// 同じURLの行をまとめたfirecrawl_articlesの行数（取得成功・取得日時が新しい行を残す）
+    pub duplicate_articles: i64,
+    /// article_linksに追加したリンク数
+    pub links_inserted: u64,
+    /// articlesに追加した記事数
+    pub articles_inserted: u64,
+    /// リンクの無い記事のために仮タイトルで追加したリンク数（`links_inserted`に含む）
+    pub placeholder_links: u64,
+    /// 新スキーマに既にあったため変更しなかった件数（リンク・記事の合計）
+    pub already_present: u64,
+    /// 照合したサンプル数
+    pub samples_checked: usize,
+    /// 検証で見つかった問題（件数の不一致・サンプルの内容の不一致）
+    pub issues: Vec<String>,
+    /// 移行をコミットしたかどうか（検証に失敗した場合とdry runはロールバックする）
+    pub committed: bool,
+}
+
+impl MigrationReport {
+    /// 検証で問題が見つからなかったか
+    pub fn is_verified(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// レポートを表示用の行に整形する
+    pub fn format_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!(
+                "rss_articles: {}件（不正{}件、重複{}件） -> リンク追加{}件（うち仮タイトル{}件）",
+                self.legacy_links,
+                self.invalid_links,
+                self.duplicate_links,
+                self.links_inserted,
+                self.placeholder_links
+            ),
+            format!(
+                "firecrawl_articles: {}件（不正{}件、重複{}件） -> 記事追加{}件",
+                self.legacy_articles,
+                self.invalid_articles,
+                self.duplicate_articles,
+                self.articles_inserted
+            ),
+            format!("移行済みのため変更せず: {}件", self.already_present),
+            format!("サンプル照合: {}件", self.samples_checked),
+        ];
+        lines.extend(self.issues.iter().map(|issue| format!("⚠ {}", issue)));
+        lines.push(if self.committed {
+            "移行をコミットしました".to_string()
+        } else {
+            "移行をロールバックしました".to_string()
+        });
+        lines
+    }
+}
+
+/// # 概要
+/// 旧スキーマ（rss_articles / firecrawl_articles）のデータをarticle_links / articlesに移行する。
+///
+/// 旧テーブルは次の列を持つものとする。
+/// - `rss_articles(link, title, pub_date)`
+/// - `firecrawl_articles(url, status_code, markdown, scraped_at)`
+///
+/// URLは前後の空白を除いて同一視し、重複はリンクなら公開日時が新しい行、記事なら取得に成功した行・
+/// 取得日時が新しい行を残す。新スキーマに既にあるURLは変更せず、リンクの無い記事には仮タイトル
+/// （`UNTITLED`）のリンクを追加する。移行したリンクの`source`は`LEGACY_SOURCE`。
+///
+/// 1つのトランザクションで移行し、旧テーブルの全URLが新スキーマにあるか（件数突合）と、
+/// 追加した行から`sample_size`件ずつ無作為に選んだ内容が旧テーブルと一致するか（サンプル照合）を検証する。
+/// 問題が見つかった場合と`dry_run`の場合はロールバックし、`committed`を`false`にして返す。
+/// コミットした場合はドメイン毎の保存容量を集計し直す。
+pub async fn migrate_legacy_data(
+    pool: &PgPool,
+    options: &LegacyMigrationOptions,
+) -> Result<MigrationReport> {
+    let schema = &options.legacy_schema;
+    if schema.is_empty()
+        || !schema
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        bail!("スキーマ名は英小文字・数字・_のみ使用できます: {}", schema);
+    }
+
+    let mut tx = pool.begin().await.context("トランザクションの開始に失敗")?;
+    let mut report = MigrationReport::default();
+
+    // 重複を解決した旧データ（トランザクション終了時に削除される）
+    sqlx::query(&format!(
+        r#"
+        CREATE TEMP TABLE legacy_links ON COMMIT DROP AS
+        SELECT DISTINCT ON (btrim(link))
+            btrim(link) AS url,
+            COALESCE(NULLIF(btrim(title), ''), $1) AS title,
+            pub_date
+        FROM {schema}.rss_articles
+        WHERE btrim(link) <> '' AND pub_date IS NOT NULL
+        ORDER BY btrim(link), pub_date DESC
+        "#
+    ))
+    .bind(UNTITLED)
+    .execute(&mut *tx)
+    .await
+    .context("rss_articlesの読み込みに失敗")?;
+    sqlx::query(&format!(
+        r#"
+        CREATE TEMP TABLE legacy_articles ON COMMIT DROP AS
+        SELECT DISTINCT ON (btrim(url))
+            btrim(url) AS url,
+            status_code,
+            COALESCE(markdown, '') AS content,
+            COALESCE(scraped_at, now()) AS timestamp
+        FROM {schema}.firecrawl_articles
+        WHERE btrim(url) <> ''
+        ORDER BY btrim(url), (status_code = 200) DESC, scraped_at DESC NULLS LAST
+        "#
+    ))
+    .execute(&mut *tx)
+    .await
+    .context("firecrawl_articlesの読み込みに失敗")?;
+
+    let (legacy_links, valid_links, deduped_links) =
+        sqlx::query_as::<_, (i64, i64, i64)>(&format!(
+            r#"
+            SELECT
+                COUNT(*),
+                COUNT(*) FILTER (WHERE btrim(link) <> '' AND pub_date IS NOT NULL),
+                (SELECT COUNT(*) FROM legacy_links)
+            FROM {schema}.rss_articles
+            "#
+        ))
+        .fetch_one(&mut *tx)
+        .await
+        .context("rss_articlesの件数の集計に失敗")?;
+    let (legacy_articles, valid_articles, deduped_articles) =
+        sqlx::query_as::<_, (i64, i64, i64)>(&format!(
+            r#"
+            SELECT
+                COUNT(*),
+                COUNT(*) FILTER (WHERE btrim(url) <> ''),
+                (SELECT COUNT(*) FROM legacy_articles)
+            FROM {schema}.firecrawl_articles
+            "#
+        ))
+        .fetch_one(&mut *tx)
+        .await
+        .context("firecrawl_articlesの件数の集計に失敗")?;
+    report.legacy_links = legacy_links;
+    report.invalid_links = legacy_links - valid_links;
+    report.duplicate_links = valid_links - deduped_links;
+    report.legacy_articles = legacy_articles;
+    report.invalid_articles = legacy_articles - valid_articles;
+    report.duplicate_articles = valid_articles - deduped_articles;
+
+    let inserted_links: Vec<String> = sqlx::query_scalar(
+        r#"
+        INSERT INTO article_links (url, title, pub_date, source)
+        SELECT url, title, pub_date, $1 FROM legacy_links
+        ON CONFLICT (url) DO NOTHING
+        RETURNING url
+        "#,
+    )
+    .bind(LEGACY_SOURCE)
+    .fetch_all(&mut *tx)
+    .await
+    .context("記事リンクの移行に失敗")?;
+    let placeholder_links = sqlx::query(
+        r#"
+        INSERT INTO article_links (url, title, pub_date, source)
+        SELECT url, $1, timestamp, $2 FROM legacy_articles
+        ON CONFLICT (url) DO NOTHING
+        "#,
+    )
+    .bind(UNTITLED)
+    .bind(LEGACY_SOURCE)
+    .execute(&mut *tx)
+    .await
+    .context("リンクの無い記事のリンクの追加に失敗")?
+    .rows_affected();
+    let inserted_articles: Vec<String> = sqlx::query_scalar(
+        r#"
+        INSERT INTO articles (url, timestamp, status_code, content)
+        SELECT url, timestamp, status_code, content FROM legacy_articles
+        ON CONFLICT (url) DO NOTHING
+        RETURNING url
+        "#,
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .context("記事の移行に失敗")?;
+    report.links_inserted = inserted_links.len() as u64 + placeholder_links;
+    report.placeholder_links = placeholder_links;
+    report.articles_inserted = inserted_articles.len() as u64;
+    report.already_present = (deduped_links as u64 - inserted_links.len() as u64)
+        + (deduped_articles as u64 - report.articles_inserted);
+
+    // 件数突合: 旧テーブルの全URLが新スキーマにあること
+    let (missing_links, missing_articles) = sqlx::query_as::<_, (i64, i64)>(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM legacy_links l
+                WHERE NOT EXISTS (SELECT 1 FROM article_links al WHERE al.url = l.url)),
+            (SELECT COUNT(*) FROM legacy_articles l
+                WHERE NOT EXISTS (SELECT 1 FROM articles a WHERE a.url = l.url)
+                    OR NOT EXISTS (SELECT 1 FROM article_links al WHERE al.url = l.url))
+        "#,
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .context("移行件数の突合に失敗")?;
+    if missing_links > 0 {
+        report.issues.push(format!(
+            "article_linksに無いリンクが{}件あります",
+            missing_links
+        ));
+    }
+    if missing_articles > 0 {
+        report.issues.push(format!(
+            "articles（またはリンク）に無い記事が{}件あります",
+            missing_articles
+        ));
+    }
+
+    // サンプル照合: 追加した行の内容が旧テーブルと一致すること
+    let link_samples = sqlx::query_as::<_, (String, bool)>(
+        r#"
+        SELECT l.url, (al.title, al.pub_date) IS NOT DISTINCT FROM (l.title, l.pub_date)
+        FROM legacy_links l
+        JOIN article_links al ON al.url = l.url
+        WHERE l.url = ANY($1)
+        ORDER BY random()
+        LIMIT $2
+        "#,
+    )
+    .bind(&inserted_links)
+    .bind(options.sample_size)
+    .fetch_all(&mut *tx)
+    .await
+    .context("記事リンクのサンプル照合に失敗")?;
+    let article_samples = sqlx::query_as::<_, (String, bool)>(
+        r#"
+        SELECT l.url, (a.status_code, a.content) IS NOT DISTINCT FROM (l.status_code, l.content)
+        FROM legacy_articles l
+        JOIN articles a ON a.url = l.url
+        WHERE l.url = ANY($1)
+        ORDER BY random()
+        LIMIT $2
+        "#,
+    )
+    .bind(&inserted_articles)
+    .bind(options.sample_size)
+    .fetch_all(&mut *tx)
+    .await
+    .context("記事のサンプル照合に失敗")?;
+    report.samples_checked = link_samples.len() + article_samples.len();
+    report.issues.extend(
+        link_samples
+            .iter()
+            .chain(&article_samples)
+            .filter(|(_, matched)| !matched)
+            .map(|(url, _)| format!("移行前後で内容が一致しません: {}", url)),
+    );
+
+    if report.is_verified() && !options.dry_run {
+        tx.commit().await.context("移行のコミットに失敗")?;
+        report.committed = true;
+        if report.articles_inserted > 0 {
+            refresh_domain_storage_usage(pool).await?;
+        }
+    } else {
+        tx.rollback().await.context("移行のロールバックに失敗")?;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::article::{store_article_content, ArticleContent};
+    use crate::core::rss::{store_article_links, ArticleLink};
+
+    #[test]
+    fn test_strip_proper_nouns() {
+        assert_eq!(
+            strip_proper_nouns("Apple CEO Tim Cook said the iPhone sold well. Sales rose."),
+            "Apple [固有名詞] said the iPhone sold well. Sales rose.",
+            "文頭の語は対象外、連続する固有名詞はまとめる"
+        );
+        assert_eq!(
+            strip_proper_nouns("昨日、Microsoftが発表した。"),
+            "昨日、[固有名詞]が発表した。"
+        );
+    }
+
+    #[test]
+    fn test_write_anonymized() {
+        let article = |url: &str, content: &str| Article {
+            id: String::new(),
+            url: url.to_string(),
+            title: "Rust 2.0 released by Ferris".to_string(),
+            pub_date: Utc::now(),
+            updated_at: None,
+            status_code: Some(200),
+            content: Some(content.to_string()),
+            snippet: None,
+        };
+        let articles = [
+            article(
+                "https://news.example.com/a",
+                "詳細は https://news.example.com/b を参照。",
+            ),
+            article("https://news.example.com/b", "本文"),
+        ];
+        let options = AnonymizeOptions {
+            salt: "secret".to_string(),
+            strip_proper_nouns: true,
+        };
+
+        let mut output = Vec::new();
+        let report = write_anonymized(&articles, &options, &mut output).unwrap();
+        assert_eq!(report.articles, 2);
+        assert_eq!(report.mapping.domains.len(), 1);
+        assert_eq!(report.mapping.urls.len(), 2);
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(!output.contains("example.com"), "元のドメインが残っている");
+        let rows: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(rows[0]["title"], "Rust 2.0 released by [固有名詞]");
+
+        // 本文中のURLは記事のURLと同じ仮名になり、対応表で元に戻せる
+        let second_url = rows[1]["url"].as_str().unwrap();
+        assert!(rows[0]["content"].as_str().unwrap().contains(second_url));
+        assert_eq!(
+            report.mapping.restore_url(second_url),
+            Some("https://news.example.com/b")
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_run_quality_checks(pool: PgPool) -> Result<(), anyhow::Error> {
+        let link = |url: &str, title: &str, pub_date: DateTime<Utc>| ArticleLink {
+            url: url.to_string(),
+            title: title.to_string(),
+            pub_date,
+            source: "test".to_string(),
+            ..Default::default()
+        };
+        let now = Utc::now();
+        store_article_links(
+            &[
+                link("https://quality.example.com/ok", "正常な記事", now),
+                link("https://quality.example.com/untitled", UNTITLED, now),
+                link("https://quality.example.com/blank", "  ", now),
+                link(
+                    "https://quality.example.com/future",
+                    "未来の記事",
+                    now + Duration::days(30),
+                ),
+                link(
+                    "https://quality.example.com/epoch",
+                    "古すぎる記事",
+                    Utc.timestamp_opt(0, 0).unwrap(),
+                ),
+            ],
+            &pool,
+        )
+        .await?;
+
+        let article = |url: &str, status_code: i32, content: String| ArticleContent {
+            url: url.to_string(),
+            timestamp: now,
+            status_code,
+            content,
+            raw_html: None,
+        };
+        for a in [
+            article("https://quality.example.com/ok", 200, "本文".repeat(100)),
+            article(
+                "https://quality.example.com/untitled",
+                200,
+                "短い".to_string(),
+            ),
+            article(
+                "https://quality.example.com/blank",
+                500,
+                "エラー".to_string(),
+            ),
+        ] {
+            store_article_content(&a, &pool).await?;
+        }
+
+        let report = run_quality_checks(&pool).await?;
+        assert_eq!(report.empty_title_links, 2);
+        assert_eq!(report.short_content_articles, 1, "エラー記事は対象外");
+        assert_eq!(report.date_anomalies, 2);
+
+        // 日付異常のみ既定の閾値（0件）を超える
+        let warnings = report.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("日付異常"));
+
+        println!("✅ データ品質チェックテスト成功");
+        Ok(())
+    }
+
+    async fn create_legacy_tables(pool: &PgPool) -> Result<(), anyhow::Error> {
+        sqlx::raw_sql(
+            r#"
+            CREATE TABLE rss_articles (link TEXT, title TEXT, pub_date TIMESTAMPTZ);
+            CREATE TABLE firecrawl_articles (
+                url TEXT, status_code INTEGER, markdown TEXT, scraped_at TIMESTAMPTZ
+            );
+            INSERT INTO rss_articles VALUES
+                ('https://legacy.example.com/a', '古いタイトル', '2024-01-01T00:00:00Z'),
+                (' https://legacy.example.com/a ', '新しいタイトル', '2024-01-02T00:00:00Z'),
+                ('https://legacy.example.com/b', NULL, '2024-01-03T00:00:00Z'),
+                ('https://legacy.example.com/c', '公開日時なし', NULL),
+                ('', '空のURL', '2024-01-04T00:00:00Z');
+            INSERT INTO firecrawl_articles VALUES
+                ('https://legacy.example.com/a', 500, 'エラー', '2024-01-05T00:00:00Z'),
+                ('https://legacy.example.com/a', 200, '本文A', '2024-01-02T00:00:00Z'),
+                ('https://legacy.example.com/orphan', 200, '本文', '2024-01-06T00:00:00Z');
+            "#,
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_migrate_legacy_data(pool: PgPool) -> Result<(), anyhow::Error> {
+        create_legacy_tables(&pool).await?;
+
+        // dry runは検証だけ行い、新スキーマを変更しない
+        let options = LegacyMigrationOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+        let report = migrate_legacy_data(&pool, &options).await?;
+        assert!(report.is_verified(), "{:?}", report.issues);
+        assert!(!report.committed);
+        let links = sqlx::query_scalar!("SELECT COUNT(*) FROM article_links")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(links, Some(0));
+
+        let report = migrate_legacy_data(&pool, &LegacyMigrationOptions::default()).await?;
+        assert!(report.committed);
+        assert_eq!((report.legacy_links, report.invalid_links), (5, 2));
+        assert_eq!(report.duplicate_links, 1);
+        assert_eq!((report.legacy_articles, report.duplicate_articles), (3, 1));
+        assert_eq!((report.links_inserted, report.placeholder_links), (3, 1));
+        assert_eq!(report.articles_inserted, 2);
+        assert_eq!(report.samples_checked, 4);
+
+        // 重複は公開日時が新しいリンク・取得に成功した記事を残す
+        let title = sqlx::query_scalar!(
+            "SELECT title FROM article_links WHERE url = 'https://legacy.example.com/a'"
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(title, "新しいタイトル");
+        let status_code = sqlx::query_scalar!(
+            "SELECT status_code FROM articles WHERE url = 'https://legacy.example.com/a'"
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(status_code, 200);
+        let orphan = sqlx::query_scalar!(
+            "SELECT title FROM article_links WHERE url = 'https://legacy.example.com/orphan'"
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(orphan, UNTITLED);
+
+        // 再実行しても既存の行は変更しない
+        let report = migrate_legacy_data(&pool, &LegacyMigrationOptions::default()).await?;
+        assert_eq!((report.links_inserted, report.articles_inserted), (0, 0));
+        assert_eq!(report.already_present, 4);
+        assert!(report.committed);
+
+        // 不正なスキーマ名はエラー
+        let options = LegacyMigrationOptions {
+            legacy_schema: "public; DROP TABLE articles".to_string(),
+            ..Default::default()
+        };
+        assert!(migrate_legacy_data(&pool, &options).await.is_err());
+
+        println!("✅ 旧スキーマからのデータ移行テスト成功");
+        Ok(())
+    }
+}
@@ -1,3 +1,33 @@
+pub mod analysis;
+pub mod api_access;
 pub mod article;
+pub mod backlog;
+pub mod blocklist;
+pub mod circuit_breaker;
+pub mod collection_error;
+#[cfg(feature = "collector")]
+pub mod config;
+pub mod exit_code;
+pub mod external_metrics;
 pub mod feed;
+pub mod feedback;
+#[cfg(feature = "collector")]
+pub mod fetch_backend;
+pub mod health;
+pub mod invariant;
+pub mod job_run;
+pub mod maintenance;
+pub mod ng_word;
+#[cfg(feature = "collector")]
+pub mod replay;
 pub mod rss;
+pub mod saved_query;
+pub mod schema;
+#[cfg(feature = "collector")]
+pub mod scraper;
+pub mod search_query;
+pub mod series;
+pub mod storage_quota;
+pub mod timeline;
+pub mod topic;
+pub mod trash;
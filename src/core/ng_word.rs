@@ -0,0 +1,253 @@
+use crate::core::article::{ContentStep, StepStats};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+/// NGワードを含むため保存対象から除外した記事のステータスコード（451 Unavailable For Legal Reasons）
+pub const NG_WORD_EXCLUDED_STATUS_CODE: i32 = 451;
+
+/// NGワードを含む記事に付ける品質フラグ
+pub const FLAG_NG_WORD: &str = "ng_word";
+
+// NGワードを含む記事の扱い
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NgWordAction {
+    /// 保存した上で品質フラグを付ける
+    #[default]
+    Flag,
+    /// 本文を保存せず、ステータスコード451で記録する
+    Exclude,
+}
+
+impl NgWordAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Flag => "flag",
+            Self::Exclude => "exclude",
+        }
+    }
+}
+
+// NGワードの設定（config/app.yamlのarticle_fetch.ng_wordsに対応）
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NgWordConfig {
+    /// NGワード（ng_wordsテーブルの語と合わせて使う。英字は大文字・小文字を区別しない）
+    #[serde(default)]
+    pub words: Vec<String>,
+    /// NGワードを含む記事の扱い
+    #[serde(default)]
+    pub action: NgWordAction,
+}
+
+// ng_wordsテーブルの1行に対応するNGワード
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct NgWord {
+    pub word: String,
+    pub reason: String,
+    pub added_at: DateTime<Utc>,
+}
+
+/// # 概要
+/// 本文にNGワードを含む記事を検出するパイプラインのステップ。
+///
+/// 本文は変更せず、ヒットした語毎の記事数を統計に、NGワードを含む記事を品質フラグ`ng_word`に記録する。
+/// `action`が`exclude`の場合は、NGワードを含む記事を保存対象から除外する。
+#[derive(Debug, Clone, Default)]
+pub struct NgWordFilter {
+    /// 比較用に正規化したNGワード（重複なし）
+    words: Vec<String>,
+    action: NgWordAction,
+}
+
+impl NgWordFilter {
+    pub fn new<I, S>(words: I, action: NgWordAction) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut normalized: Vec<String> = words
+            .into_iter()
+            .map(|word| normalize_ng_word(word.as_ref()))
+            .filter(|word| !word.is_empty())
+            .collect();
+        normalized.sort();
+        normalized.dedup();
+        Self {
+            words: normalized,
+            action,
+        }
+    }
+
+    /// NGワードが1つも無いかどうか
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// 本文に含まれるNGワード（正規化した語、辞書順）
+    pub fn find_hits(&self, content: &str) -> Vec<&str> {
+        let content = content.to_lowercase();
+        self.words
+            .iter()
+            .filter(|word| content.contains(word.as_str()))
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+impl ContentStep for NgWordFilter {
+    fn name(&self) -> &str {
+        "ng_word"
+    }
+
+    fn apply(&self, content: &str, stats: &mut StepStats) -> String {
+        for word in self.find_hits(content) {
+            *stats.entry(word.to_string()).or_default() += 1;
+        }
+        content.to_string()
+    }
+
+    fn quality_flags(&self, content: &str) -> Vec<String> {
+        if self.find_hits(content).is_empty() {
+            return Vec::new();
+        }
+        vec![FLAG_NG_WORD.to_string()]
+    }
+
+    fn exclusion_reason(&self, content: &str) -> Option<String> {
+        if self.action != NgWordAction::Exclude {
+            return None;
+        }
+        let hits = self.find_hits(content);
+        (!hits.is_empty()).then(|| format!("NGワードを含むため保存しません: {}", hits.join(", ")))
+    }
+}
+
+/// NGワードを比較用に正規化する（前後の空白を除去して小文字化）
+pub fn normalize_ng_word(word: &str) -> String {
+    word.trim().to_lowercase()
+}
+
+/// NGワードを登録する。既に登録済みの場合は理由を更新する。
+pub async fn add_ng_word(word: &str, reason: &str, pool: &PgPool) -> Result<()> {
+    let word = normalize_ng_word(word);
+    if word.is_empty() {
+        bail!("登録するNGワードが空です");
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO ng_words (word, reason)
+        VALUES ($1, $2)
+        ON CONFLICT (word) DO UPDATE SET reason = EXCLUDED.reason
+        "#,
+        word,
+        reason
+    )
+    .execute(pool)
+    .await
+    .with_context(|| format!("NGワードの登録に失敗: {}", word))?;
+
+    Ok(())
+}
+
+/// # 概要
+/// NGワードの登録を削除する。
+///
+/// # 戻り値
+/// 登録されていた語を削除した場合は`true`
+pub async fn remove_ng_word(word: &str, pool: &PgPool) -> Result<bool> {
+    let result = sqlx::query!(
+        "DELETE FROM ng_words WHERE word = $1",
+        normalize_ng_word(word)
+    )
+    .execute(pool)
+    .await
+    .with_context(|| format!("NGワードの削除に失敗: {}", word))?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// ng_wordsテーブルに登録したNGワードの一覧
+pub async fn list_ng_words(pool: &PgPool) -> Result<Vec<NgWord>> {
+    sqlx::query_as!(
+        NgWord,
+        "SELECT word, reason, added_at FROM ng_words ORDER BY word"
+    )
+    .fetch_all(pool)
+    .await
+    .context("NGワードの読み込みに失敗")
+}
+
+/// 設定ファイルとng_wordsテーブルのNGワードを合わせたフィルタを読み込む
+pub async fn load_ng_word_filter(config: &NgWordConfig, pool: &PgPool) -> Result<NgWordFilter> {
+    let stored = list_ng_words(pool).await?;
+    let words = config
+        .words
+        .iter()
+        .map(String::as_str)
+        .chain(stored.iter().map(|word| word.word.as_str()));
+    Ok(NgWordFilter::new(words, config.action))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::article::{ContentPipeline, PipelineReport};
+
+    #[test]
+    fn test_ng_word_filter_step() {
+        let filter = NgWordFilter::new([" Spoiler ", "禁止語", "spoiler", ""], NgWordAction::Flag);
+        assert_eq!(
+            filter.find_hits("今日のSPOILERと禁止語"),
+            vec!["spoiler", "禁止語"]
+        );
+
+        let pipeline = ContentPipeline::new().with_step(filter);
+        let mut report = PipelineReport::default();
+        let content = pipeline.run("禁止語を含む本文", &mut report);
+        pipeline.run("問題の無い本文", &mut report);
+
+        assert_eq!(content, "禁止語を含む本文", "本文は変更しない");
+        assert_eq!(report.steps["ng_word"]["禁止語"], 1);
+        assert!(!report.steps["ng_word"].contains_key("spoiler"));
+        assert_eq!(report.quality_flags[FLAG_NG_WORD], 1);
+        assert_eq!(
+            pipeline.exclusion_reason(&content),
+            None,
+            "flagでは除外しない"
+        );
+
+        let exclude =
+            ContentPipeline::new().with_step(NgWordFilter::new(["禁止語"], NgWordAction::Exclude));
+        assert_eq!(
+            exclude.exclusion_reason("禁止語を含む本文").as_deref(),
+            Some("NGワードを含むため保存しません: 禁止語")
+        );
+        assert_eq!(exclude.exclusion_reason("問題の無い本文"), None);
+
+        println!("✅ NGワードフィルタのステップテスト成功");
+    }
+
+    #[sqlx::test]
+    async fn test_load_ng_word_filter(pool: PgPool) -> Result<(), anyhow::Error> {
+        add_ng_word(" Leak ", "テスト", &pool).await?;
+        assert!(add_ng_word("  ", "空", &pool).await.is_err());
+
+        let config = NgWordConfig {
+            words: vec!["禁止語".to_string()],
+            action: NgWordAction::Exclude,
+        };
+        let filter = load_ng_word_filter(&config, &pool).await?;
+        assert_eq!(filter.find_hits("LEAKした禁止語"), vec!["leak", "禁止語"]);
+
+        assert!(remove_ng_word("LEAK", &pool).await?);
+        assert!(!remove_ng_word("leak", &pool).await?);
+        let filter = load_ng_word_filter(&NgWordConfig::default(), &pool).await?;
+        assert!(filter.is_empty());
+
+        println!("✅ NGワードの登録・読み込みテスト成功");
+        Ok(())
+    }
+}
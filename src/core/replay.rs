@@ -0,0 +1,660 @@
+use crate::core::scraper::{ScrapeResult, ScraperClient};
+use crate::infra::api::http::{CacheValidators, ConditionalFetch, HttpClient, HttpPoolStats};
+use crate::types::{format_error_chain, FetchError};
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::sync::Mutex;
+
+// 収集処理のシミュレーションの設定（config/app.yamlのreplayに対応）
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ReplayConfig {
+    /// ワークフローの実行時にフィード本文とスクレイプ結果を記録する（リプレイの入力になる）
+    #[serde(default)]
+    pub record_snapshots: bool,
+}
+
+// リプレイする期間（両端を含む）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ReplayRange {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+}
+
+impl ReplayRange {
+    pub fn new(from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Self> {
+        if from > to {
+            bail!("リプレイ期間の開始が終了より後です: {} > {}", from, to);
+        }
+        Ok(Self { from, to })
+    }
+}
+
+// feed_snapshotsテーブルの1行に対応するフィード本文の記録
+#[derive(Debug, Clone, FromRow)]
+pub struct FeedSnapshot {
+    pub id: i64,
+    pub url: String,
+    pub body: String,
+    pub fetched_at: DateTime<Utc>,
+}
+
+// raw_scrapesテーブルの1行に対応するスクレイプ結果の記録
+#[derive(Debug, Clone, FromRow)]
+pub struct RawScrape {
+    pub id: i64,
+    pub url: String,
+    /// スクレイパーが返した加工前の本文（本文が得られなかった場合やエラーの場合は`None`）
+    pub markdown: Option<String>,
+    /// 取得エラーのメッセージ（成功した場合は`None`）
+    pub error: Option<String>,
+    pub scraped_at: DateTime<Utc>,
+}
+
+/// 取得できたフィード本文を記録する
+pub async fn store_feed_snapshot(url: &str, body: &str, pool: &PgPool) -> Result<()> {
+    sqlx::query!(
+        "INSERT INTO feed_snapshots (url, body) VALUES ($1, $2)",
+        url,
+        body
+    )
+    .execute(pool)
+    .await
+    .with_context(|| format!("フィード本文の記録に失敗: {}", url))?;
+    Ok(())
+}
+
+/// スクレイパーの結果（加工前の本文またはエラー）を記録する
+pub async fn store_raw_scrape(
+    url: &str,
+    result: &Result<Option<String>, FetchError>,
+    pool: &PgPool,
+) -> Result<()> {
+    let (markdown, error) = match result {
+        Ok(markdown) => (markdown.clone(), None),
+        Err(e) => (None, Some(format_error_chain(e))),
+    };
+    sqlx::query!(
+        "INSERT INTO raw_scrapes (url, markdown, error) VALUES ($1, $2, $3)",
+        url,
+        markdown,
+        error
+    )
+    .execute(pool)
+    .await
+    .with_context(|| format!("スクレイプ結果の記録に失敗: {}", url))?;
+    Ok(())
+}
+
+/// 期間内に記録したフィード本文を記録順に取得する
+pub async fn list_feed_snapshots(range: &ReplayRange, pool: &PgPool) -> Result<Vec<FeedSnapshot>> {
+    sqlx::query_as!(
+        FeedSnapshot,
+        r#"
+        SELECT id, url, body, fetched_at FROM feed_snapshots
+        WHERE fetched_at BETWEEN $1 AND $2
+        ORDER BY fetched_at, id
+        "#,
+        range.from,
+        range.to
+    )
+    .fetch_all(pool)
+    .await
+    .context("フィード本文の記録の取得に失敗")
+}
+
+/// 期間内に記録したスクレイプ結果をURL毎に最新の1件ずつ取得する
+///
+/// 再試行などで同じURLを複数回取得した場合は、最後の結果が保存された記事に対応する。
+pub async fn list_raw_scrapes(range: &ReplayRange, pool: &PgPool) -> Result<Vec<RawScrape>> {
+    sqlx::query_as!(
+        RawScrape,
+        r#"
+        SELECT DISTINCT ON (url) id, url, markdown, error, scraped_at FROM raw_scrapes
+        WHERE scraped_at BETWEEN $1 AND $2
+        ORDER BY url, scraped_at DESC, id DESC
+        "#,
+        range.from,
+        range.to
+    )
+    .fetch_all(pool)
+    .await
+    .context("スクレイプ結果の記録の取得に失敗")
+}
+
+/// 取得できたフィード本文を`feed_snapshots`に記録するHTTPクライアント
+///
+/// `pool`が`None`の場合は記録せずにそのまま取得する。記録の失敗は表示だけして取得結果を返す。
+pub struct SnapshotRecordingHttpClient<H: HttpClient> {
+    inner: H,
+    pool: Option<PgPool>,
+}
+
+impl<H: HttpClient> SnapshotRecordingHttpClient<H> {
+    pub fn new(inner: H, pool: Option<PgPool>) -> Self {
+        Self { inner, pool }
+    }
+}
+
+#[async_trait]
+impl<H: HttpClient + Send + Sync> HttpClient for SnapshotRecordingHttpClient<H> {
+    async fn fetch(&self, url: &str, timeout_secs: u64) -> Result<String, FetchError> {
+        let result = self.inner.fetch(url, timeout_secs).await;
+        if let (Some(pool), Ok(body)) = (&self.pool, &result) {
+            if let Err(e) = store_feed_snapshot(url, body, pool).await {
+                tracing::warn!(
+                    url,
+                    error = format!("{:#}", e),
+                    "フィードスナップショットの記録に失敗"
+                );
+            }
+        }
+        result
+    }
+
+    async fn fetch_conditional(
+        &self,
+        url: &str,
+        timeout_secs: u64,
+        validators: &CacheValidators,
+    ) -> Result<ConditionalFetch, FetchError> {
+        if self.pool.is_none() {
+            return self
+                .inner
+                .fetch_conditional(url, timeout_secs, validators)
+                .await;
+        }
+        // 記録中は本文を残すため条件を付けずに取得する
+        let body = self.fetch(url, timeout_secs).await?;
+        Ok(ConditionalFetch::Modified {
+            body,
+            validators: CacheValidators::default(),
+        })
+    }
+
+    fn pool_stats(&self) -> Option<HttpPoolStats> {
+        self.inner.pool_stats()
+    }
+}
+
+/// スクレイパーの結果を`raw_scrapes`に記録するスクレイパー
+///
+/// `pool`が`None`の場合は記録せずにそのまま取得する。記録の失敗は表示だけして取得結果を返す。
+pub struct RawScrapeRecordingScraper<S: ScraperClient> {
+    inner: S,
+    pool: Option<PgPool>,
+}
+
+impl<S: ScraperClient> RawScrapeRecordingScraper<S> {
+    pub fn new(inner: S, pool: Option<PgPool>) -> Self {
+        Self { inner, pool }
+    }
+
+    async fn record(&self, url: &str, result: &Result<Option<String>, FetchError>) {
+        if let Some(pool) = &self.pool {
+            if let Err(e) = store_raw_scrape(url, result, pool).await {
+                tracing::warn!(
+                    url,
+                    error = format!("{:#}", e),
+                    "スクレイプ結果の記録に失敗"
+                );
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<S: ScraperClient> ScraperClient for RawScrapeRecordingScraper<S> {
+    fn backend_name(&self) -> &'static str {
+        self.inner.backend_name()
+    }
+
+    fn backend_name_for(&self, url: &str) -> &'static str {
+        self.inner.backend_name_for(url)
+    }
+
+    async fn scrape_markdown(&self, url: &str) -> Result<Option<String>, FetchError> {
+        let result = self.inner.scrape_markdown(url).await;
+        self.record(url, &result).await;
+        result
+    }
+
+    /// 記録するのはマークダウンのみ（元のHTMLは記録しない）
+    async fn scrape(&self, url: &str) -> Result<ScrapeResult, FetchError> {
+        let (result, html) = match self.inner.scrape(url).await {
+            Ok(ScrapeResult { markdown, html }) => (Ok(markdown), html),
+            Err(e) => (Err(e), None),
+        };
+        self.record(url, &result).await;
+        result.map(|markdown| ScrapeResult { markdown, html })
+    }
+}
+
+/// 記録したフィード本文を返すHTTPクライアント（リプレイ用）
+///
+/// URL毎に記録順で1件ずつ返し、返し終わったURLや記録の無いURLはエラーにする
+/// （記録時にメインURLの取得に失敗していれば、同じようにフォールバックURLが使われる）。
+pub struct SnapshotReplayHttpClient {
+    bodies: Mutex<HashMap<String, VecDeque<String>>>,
+}
+
+impl SnapshotReplayHttpClient {
+    pub fn new(snapshots: Vec<FeedSnapshot>) -> Self {
+        let mut bodies: HashMap<String, VecDeque<String>> = HashMap::new();
+        for snapshot in snapshots {
+            bodies
+                .entry(snapshot.url)
+                .or_default()
+                .push_back(snapshot.body);
+        }
+        Self {
+            bodies: Mutex::new(bodies),
+        }
+    }
+
+    /// まだ返していない記録があるかどうか
+    pub fn has_remaining(&self, url: &str) -> bool {
+        self.bodies
+            .lock()
+            .expect("フィード本文の記録のロックに失敗")
+            .get(url)
+            .is_some_and(|bodies| !bodies.is_empty())
+    }
+
+    /// まだ返していない記録の数
+    pub fn remaining(&self) -> usize {
+        self.bodies
+            .lock()
+            .expect("フィード本文の記録のロックに失敗")
+            .values()
+            .map(VecDeque::len)
+            .sum()
+    }
+}
+
+#[async_trait]
+impl HttpClient for SnapshotReplayHttpClient {
+    async fn fetch(&self, url: &str, _timeout_secs: u64) -> Result<String, FetchError> {
+        self.bodies
+            .lock()
+            .expect("フィード本文の記録のロックに失敗")
+            .get_mut(url)
+            .and_then(VecDeque::pop_front)
+            .ok_or_else(|| {
+                FetchError::Unavailable(format!("記録されたフィード本文がありません: {}", url))
+            })
+    }
+}
+
+/// 記録したスクレイプ結果を返すスクレイパー（リプレイ用）
+pub struct RawScrapeReplayScraper {
+    scrapes: HashMap<String, RawScrape>,
+}
+
+impl RawScrapeReplayScraper {
+    pub fn new(scrapes: Vec<RawScrape>) -> Self {
+        Self {
+            scrapes: scrapes
+                .into_iter()
+                .map(|scrape| (scrape.url.clone(), scrape))
+                .collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl ScraperClient for RawScrapeReplayScraper {
+    fn backend_name(&self) -> &'static str {
+        "記録済みのスクレイプ結果"
+    }
+
+    async fn scrape_markdown(&self, url: &str) -> Result<Option<String>, FetchError> {
+        match self.scrapes.get(url) {
+            Some(RawScrape {
+                error: Some(error), ..
+            }) => Err(FetchError::Unavailable(error.clone())),
+            Some(scrape) => Ok(scrape.markdown.clone()),
+            None => Err(FetchError::Unavailable(format!(
+                "記録されたスクレイプ結果がありません: {}",
+                url
+            ))),
+        }
+    }
+}
+
+/// 記事の取得結果（ステータスコードと本文）をURL毎に取得する
+pub async fn load_article_results(
+    urls: &[String],
+    pool: &PgPool,
+) -> Result<HashMap<String, (i32, String)>> {
+    let rows = sqlx::query!(
+        "SELECT url, status_code, content FROM articles WHERE url = ANY($1)",
+        urls
+    )
+    .fetch_all(pool)
+    .await
+    .context("記事の取得結果の読み込みに失敗")?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.url, (row.status_code, row.content)))
+        .collect())
+}
+
+// 本番またはシミュレーションの収集結果（比較に使うリンクと記事）
+#[derive(Debug, Clone, Default)]
+pub struct CollectionResult {
+    /// 収集したリンクのURL
+    pub links: BTreeSet<String>,
+    /// スクレイプ結果を記録したURLの記事の取得結果（URL -> (ステータスコード, 本文)）
+    pub articles: HashMap<String, (i32, String)>,
+}
+
+// リプレイの入力にした記録の件数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ReplayInputs {
+    /// フィード本文の記録数
+    pub feed_snapshots: usize,
+    /// 現在のフィード設定のどのフィードにも該当せず、リプレイしなかったフィード本文の記録数
+    pub unmatched_snapshots: usize,
+    /// スクレイプ結果の記録数（URL毎）
+    pub raw_scrapes: usize,
+}
+
+// 本番またはシミュレーションの収集結果の集計
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SimulationStats {
+    /// 収集したリンク数
+    pub links: usize,
+    /// スクレイプ結果を記録した記事のうち、保存された記事数
+    pub articles: usize,
+    /// そのうち取得に成功した記事数
+    pub succeeded: usize,
+}
+
+impl SimulationStats {
+    /// 記事取得の成功率（0.0-1.0、記事が無い場合は0.0）
+    pub fn success_rate(&self) -> f64 {
+        if self.articles == 0 {
+            0.0
+        } else {
+            self.succeeded as f64 / self.articles as f64
+        }
+    }
+
+    fn from_result(result: &CollectionResult) -> Self {
+        Self {
+            links: result.links.len(),
+            articles: result.articles.len(),
+            succeeded: result
+                .articles
+                .values()
+                .filter(|(status_code, _)| *status_code == 200)
+                .count(),
+        }
+    }
+}
+
+// 本番とシミュレーションで本文が異なった記事
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContentDiff {
+    pub url: String,
+    /// 本番の本文の文字数
+    pub baseline_chars: usize,
+    /// シミュレーションの本文の文字数
+    pub simulated_chars: usize,
+}
+
+// 収集処理のシミュレーション結果（本番の収集結果との差分）
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationReport {
+    pub range: ReplayRange,
+    /// シミュレーションに使った一時スキーマ名（終了時に削除済み）
+    pub schema: String,
+    pub inputs: ReplayInputs,
+    /// 本番の収集結果
+    pub baseline: SimulationStats,
+    /// 現在の実装で再実行した収集結果
+    pub simulated: SimulationStats,
+    /// 本番では収集されていないリンク
+    pub links_added: Vec<String>,
+    /// 本番では期間内に収集されたが、再実行では収集されなかったリンク
+    pub links_missing: Vec<String>,
+    /// 本番と再実行の両方で取得に成功し、本文が一致した記事数
+    pub content_unchanged: usize,
+    /// 本番と再実行の両方で取得に成功し、本文が異なった記事
+    pub content_changed: Vec<ContentDiff>,
+}
+
+impl SimulationReport {
+    /// 本番とシミュレーションの収集結果を比較してレポートを作成する
+    ///
+    /// `baseline.links`は本番で期間内に収集されたリンク、`known_links`は再実行で収集したリンクの
+    /// うち本番にも存在するリンク。記事は両方ともスクレイプ結果を記録したURLに限って比較する。
+    pub fn compare(
+        range: ReplayRange,
+        schema: String,
+        inputs: ReplayInputs,
+        baseline: CollectionResult,
+        simulated: CollectionResult,
+        known_links: &BTreeSet<String>,
+    ) -> Self {
+        let mut content_unchanged = 0;
+        let mut content_changed: Vec<ContentDiff> = baseline
+            .articles
+            .iter()
+            .filter_map(|(url, (status_code, baseline_content))| {
+                let (simulated_status, simulated_content) = simulated.articles.get(url)?;
+                if *status_code != 200 || *simulated_status != 200 {
+                    return None;
+                }
+                if baseline_content == simulated_content {
+                    content_unchanged += 1;
+                    return None;
+                }
+                Some(ContentDiff {
+                    url: url.clone(),
+                    baseline_chars: baseline_content.chars().count(),
+                    simulated_chars: simulated_content.chars().count(),
+                })
+            })
+            .collect();
+        content_changed.sort_by(|a, b| a.url.cmp(&b.url));
+
+        Self {
+            range,
+            schema,
+            inputs,
+            baseline: SimulationStats::from_result(&baseline),
+            simulated: SimulationStats::from_result(&simulated),
+            links_added: simulated.links.difference(known_links).cloned().collect(),
+            links_missing: baseline
+                .links
+                .difference(&simulated.links)
+                .cloned()
+                .collect(),
+            content_unchanged,
+            content_changed,
+        }
+    }
+
+    /// 表示用の行に整形する
+    pub fn format_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!(
+                "期間: {} 〜 {}",
+                self.range.from.to_rfc3339(),
+                self.range.to.to_rfc3339()
+            ),
+            format!(
+                "入力: フィード本文{}件（設定に無いフィード{}件） / スクレイプ結果{}件",
+                self.inputs.feed_snapshots,
+                self.inputs.unmatched_snapshots,
+                self.inputs.raw_scrapes
+            ),
+            format!(
+                "リンク数: 本番{}件 → 再実行{}件（追加{}件 / 欠落{}件）",
+                self.baseline.links,
+                self.simulated.links,
+                self.links_added.len(),
+                self.links_missing.len()
+            ),
+            format!(
+                "成功率: 本番{:.1}%（{}/{}） → 再実行{:.1}%（{}/{}）",
+                self.baseline.success_rate() * 100.0,
+                self.baseline.succeeded,
+                self.baseline.articles,
+                self.simulated.success_rate() * 100.0,
+                self.simulated.succeeded,
+                self.simulated.articles
+            ),
+            format!(
+                "本文: 一致{}件 / 差分あり{}件",
+                self.content_unchanged,
+                self.content_changed.len()
+            ),
+        ];
+        lines.extend(self.content_changed.iter().map(|diff| {
+            format!(
+                "  {}（{}文字 → {}文字）",
+                diff.url, diff.baseline_chars, diff.simulated_chars
+            )
+        }));
+        lines.extend(self.links_added.iter().map(|url| format!("  + {}", url)));
+        lines.extend(self.links_missing.iter().map(|url| format!("  - {}", url)));
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[sqlx::test]
+    async fn test_record_and_replay_clients(pool: PgPool) -> Result<(), anyhow::Error> {
+        use crate::infra::api::{firecrawl::MockFirecrawlClient, http::MockHttpClient};
+
+        let started = Utc::now();
+        let http = SnapshotRecordingHttpClient::new(
+            MockHttpClient::new_success().with_failing_url("https://feed.example.com/down"),
+            Some(pool.clone()),
+        );
+        let feed = http.fetch("https://feed.example.com/rss", 30).await?;
+        assert!(http
+            .fetch("https://feed.example.com/down", 30)
+            .await
+            .is_err());
+        let scraper = RawScrapeRecordingScraper::new(
+            MockFirecrawlClient::new_success("記録する本文"),
+            Some(pool.clone()),
+        );
+        scraper.scrape_markdown("https://a.example.com/1").await?;
+        let failing = RawScrapeRecordingScraper::new(
+            MockFirecrawlClient::new_error("API障害"),
+            Some(pool.clone()),
+        );
+        assert!(failing
+            .scrape_markdown("https://a.example.com/2")
+            .await
+            .is_err());
+
+        let range = ReplayRange::new(started, Utc::now())?;
+        let snapshots = list_feed_snapshots(&range, &pool).await?;
+        assert_eq!(snapshots.len(), 1, "取得できたフィードだけを記録する");
+        let scrapes = list_raw_scrapes(&range, &pool).await?;
+        assert_eq!(scrapes.len(), 2);
+        assert!(scrapes[1].error.as_deref().unwrap().contains("API障害"));
+
+        // 記録した結果をそのまま再生する
+        let replay_http = SnapshotReplayHttpClient::new(snapshots);
+        assert!(replay_http.has_remaining("https://feed.example.com/rss"));
+        assert_eq!(
+            replay_http
+                .fetch("https://feed.example.com/rss", 30)
+                .await?,
+            feed
+        );
+        assert!(replay_http
+            .fetch("https://feed.example.com/rss", 30)
+            .await
+            .is_err());
+        assert_eq!(replay_http.remaining(), 0);
+
+        let replay_scraper = RawScrapeReplayScraper::new(scrapes);
+        let markdown = replay_scraper
+            .scrape_markdown("https://a.example.com/1")
+            .await?;
+        assert!(markdown.unwrap().contains("記録する本文"));
+        assert!(replay_scraper
+            .scrape_markdown("https://a.example.com/2")
+            .await
+            .is_err());
+        assert!(replay_scraper
+            .scrape_markdown("https://a.example.com/3")
+            .await
+            .is_err());
+
+        assert!(ReplayRange::new(started, started - Duration::hours(1)).is_err());
+
+        println!("✅ 取得結果の記録・再生テスト成功");
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulation_report_compare() {
+        let now = Utc::now();
+        let range = ReplayRange::new(now - Duration::hours(1), now).unwrap();
+        let set = |urls: &[&str]| -> BTreeSet<String> {
+            urls.iter().map(|url| url.to_string()).collect()
+        };
+        let articles = |rows: &[(&str, i32, &str)]| -> HashMap<String, (i32, String)> {
+            rows.iter()
+                .map(|(url, status, content)| (url.to_string(), (*status, content.to_string())))
+                .collect()
+        };
+
+        let baseline = CollectionResult {
+            links: set(&["a", "b", "c"]),
+            articles: articles(&[("a", 200, "本文"), ("b", 200, "本文"), ("c", 500, "エラー")]),
+        };
+        let simulated = CollectionResult {
+            links: set(&["a", "b", "d"]),
+            articles: articles(&[
+                ("a", 200, "本文"),
+                ("b", 200, "加工後の本文"),
+                ("c", 200, "本文"),
+            ]),
+        };
+        let report = SimulationReport::compare(
+            range,
+            "replay_test".to_string(),
+            ReplayInputs::default(),
+            baseline,
+            simulated,
+            &set(&["a", "b"]),
+        );
+
+        assert_eq!(report.links_added, ["d"]);
+        assert_eq!(report.links_missing, ["c"]);
+        assert_eq!(report.baseline.succeeded, 2);
+        assert_eq!(report.simulated.succeeded, 3);
+        assert!((report.simulated.success_rate() - 1.0).abs() < f64::EPSILON);
+        assert_eq!(report.content_unchanged, 1);
+        assert_eq!(
+            report.content_changed,
+            vec![ContentDiff {
+                url: "b".to_string(),
+                baseline_chars: 2,
+                simulated_chars: 6,
+            }]
+        );
+        assert!(report
+            .format_lines()
+            .iter()
+            .any(|line| line.contains("差分あり1件")));
+    }
+}
@@ -1,54 +1,484 @@
+use crate::core::article::{normalize_article_url, ErrorCategory, ERROR_STATUS_CODE};
 use crate::core::feed::Feed;
-use crate::infra::api::http::HttpClient;
-use crate::infra::parser::{parse_channel_from_xml_str, parse_date};
+use crate::core::schema::rfc3339;
+#[cfg(feature = "collector")]
+use crate::infra::api::http::{CacheValidators, ConditionalFetch, HttpClient};
+#[cfg(feature = "collector")]
+use crate::infra::parser::parse_channel_from_xml_str;
+use crate::infra::parser::parse_date;
+use crate::types::{FetchError, ParseError};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rss::Channel;
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, PgPool};
+use sqlx::{Acquire, FromRow, PgExecutor, PgPool, Postgres};
+#[cfg(feature = "collector")]
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+use url::Url;
+
+/// タイトルが取得できなかったリンクに付ける仮タイトル
+pub const UNTITLED: &str = "タイトルなし";
 
 // 記事のリンク情報を格納する構造体（<item>要素のみ対象）
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+// JSONのフィールド名・日付形式は外部連携のスキーマ（`core::schema`）として固定する
+#[derive(Debug, Clone, Default, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "snake_case")]
 pub struct ArticleLink {
     pub url: String,
     pub title: String,
+    #[serde(with = "rfc3339")]
     pub pub_date: DateTime<Utc>,
     pub source: String,
+    /// 記事の概要（<description>）
+    #[serde(default)]
+    #[sqlx(default)]
+    pub description: Option<String>,
+    /// 記事の著者（<author>、無ければ<dc:creator>）
+    #[serde(default)]
+    #[sqlx(default)]
+    pub author: Option<String>,
+    /// 記事のカテゴリ（<category>、article_categoriesに保存する）
+    #[serde(default)]
+    #[sqlx(default)]
+    pub categories: Vec<String>,
 }
 
 // RSSのチャンネルから<item>要素のリンク情報を抽出する関数
+//
+// 相対URL・スキームなしURLはチャンネルの<link>を基準に絶対URLへ解決する（解決できない<item>は除外する）
 pub fn get_article_links_from_channel(channel: &Channel) -> Vec<ArticleLink> {
-    channel
-        .items()
-        .iter()
-        .filter_map(|item| {
-            let link = item.link()?;
-            let pub_date_str = item.pub_date()?;
-            let parsed_date = parse_date(pub_date_str).ok()?;
-
-            Some(ArticleLink {
-                url: link.to_string(),
-                title: item.title().unwrap_or("タイトルなし").to_string(),
-                pub_date: parsed_date,
-                source: "rss".to_string(),
-            })
-        })
-        .collect()
+    get_article_links_from_channel_with_issues(channel).0
+}
+
+/// # 概要
+/// RSSのチャンネルから<item>要素のリンク情報を抽出し、リンクにできなかった<item>要素を`ParseIssue`として返す。
+///
+/// `//example.com/a`や`/news/a.html`のような不完全なURLはチャンネルの<link>（base URL）で絶対URLへ解決する。
+/// チャンネルの<link>が無いなどで解決できないURLの<item>は、<link>・<pubDate>の欠落と同様に`ParseIssue`になる。
+pub fn get_article_links_from_channel_with_issues(
+    channel: &Channel,
+) -> (Vec<ArticleLink>, Vec<ParseIssue>) {
+    let base = Url::parse(channel.link().trim()).ok();
+    let mut links = Vec::new();
+    let mut issues = Vec::new();
+    for item in channel.items() {
+        match article_link_from_item(item, base.as_ref()) {
+            Ok(link) => links.push(link),
+            Err(issue) => issues.push(issue),
+        }
+    }
+    (links, issues)
+}
+
+// リンクにできなかった<item>要素（フィードの解析の警告）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseIssue {
+    /// 対象の<item>要素（タイトル、無ければ<link>の値）
+    pub item: String,
+    /// リンクにできなかった理由
+    pub message: String,
+}
+
+impl ParseIssue {
+    fn new(item: &str, message: impl Into<String>) -> Self {
+        Self {
+            item: item.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.message, self.item)
+    }
+}
+
+// <item>要素をリンク情報に変換する（変換できない場合は理由を返す）
+fn article_link_from_item(
+    item: &rss::Item,
+    base: Option<&Url>,
+) -> std::result::Result<ArticleLink, ParseIssue> {
+    let label = item.title().or(item.link()).unwrap_or(UNTITLED);
+    let link = item
+        .link()
+        .map(str::trim)
+        .filter(|link| !link.is_empty())
+        .ok_or_else(|| ParseIssue::new(label, "<link>がありません"))?;
+    let link = resolve_item_url(link, base).map_err(|message| ParseIssue::new(label, message))?;
+    let pub_date_str = item
+        .pub_date()
+        .ok_or_else(|| ParseIssue::new(label, "<pubDate>がありません"))?;
+    let parsed_date = parse_date(pub_date_str)
+        .map_err(|e| ParseIssue::new(label, format!("<pubDate>を解析できません（{}）", e)))?;
+
+    let author = item.author().or_else(|| {
+        item.dublin_core_ext()
+            .and_then(|dc| dc.creators().first())
+            .map(String::as_str)
+    });
+    let mut categories: Vec<String> = Vec::new();
+    for category in item.categories() {
+        let name = category.name().trim();
+        if !name.is_empty() && !categories.iter().any(|c| c == name) {
+            categories.push(name.to_string());
+        }
+    }
+
+    Ok(ArticleLink {
+        url: normalize_article_url(&link),
+        title: item.title().unwrap_or(UNTITLED).to_string(),
+        pub_date: parsed_date,
+        source: "rss".to_string(),
+        description: non_empty(item.description()),
+        author: non_empty(author),
+        categories,
+    })
+}
+
+// <link>の値を絶対URLにする（絶対URLはそのまま、相対URL・スキームなしURLはbase URLで解決する）
+fn resolve_item_url(link: &str, base: Option<&Url>) -> std::result::Result<String, String> {
+    match Url::parse(link) {
+        Ok(_) => Ok(link.to_string()),
+        Err(url::ParseError::RelativeUrlWithoutBase) => {
+            let base = base.ok_or_else(|| {
+                format!(
+                    "相対URLを解決できません（チャンネルの<link>がありません）: {}",
+                    link
+                )
+            })?;
+            base.join(link)
+                .map(String::from)
+                .map_err(|e| format!("相対URLを解決できません（{}）: {}", e, link))
+        }
+        Err(e) => Err(format!("<link>のURLが不正です（{}）: {}", e, link)),
+    }
+}
+
+// 空白のみの値は無いものとして扱う
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+// フィードのプレビュー（DBには保存しない）
+#[derive(Debug, Clone, Default)]
+pub struct FeedPreview {
+    /// チャンネルのタイトル
+    pub channel_title: String,
+    /// <item>要素の数
+    pub item_count: usize,
+    /// 抽出できたリンク（フィード内の順）
+    pub links: Vec<ArticleLink>,
+    /// リンクにできなかった<item>要素の警告
+    pub warnings: Vec<ParseIssue>,
+}
+
+impl FeedPreview {
+    /// 表示用の行に整形する（リンクは先頭から`limit`件）
+    pub fn format_lines(&self, limit: usize) -> Vec<String> {
+        let mut lines = vec![format!(
+            "{}: {}件中{}件のリンクを抽出",
+            self.channel_title,
+            self.item_count,
+            self.links.len()
+        )];
+        lines.extend(self.links.iter().take(limit).map(|link| {
+            format!(
+                "  {} {}\n    {}",
+                link.pub_date.format("%Y-%m-%d %H:%M"),
+                link.title,
+                link.url
+            )
+        }));
+        if self.links.len() > limit {
+            lines.push(format!("  ...ほか{}件", self.links.len() - limit));
+        }
+        lines.extend(self.warnings.iter().map(|w| format!("⚠ {}", w)));
+        lines
+    }
+}
+
+/// RSSのチャンネルからリンクを抽出し、抽出できなかった<item>要素を警告として返す
+pub fn preview_article_links(channel: &Channel) -> FeedPreview {
+    let (links, warnings) = get_article_links_from_channel_with_issues(channel);
+    FeedPreview {
+        channel_title: channel.title().to_string(),
+        item_count: channel.items().len(),
+        links,
+        warnings,
+    }
+}
+
+/// # 概要
+/// フィードのURLを取得・解析してリンクを抽出する（DBには書き込まない）。
+///
+/// フィードを追加する前に、どんな記事が取れるかを確認するために使う。
+#[cfg(feature = "collector")]
+pub async fn preview_feed<H: HttpClient>(client: &H, url: &str) -> Result<FeedPreview> {
+    let xml_content = client.fetch(url, 30).await?;
+    let channel = parse_channel_from_xml_str(&xml_content).context("XMLの解析に失敗")?;
+    Ok(preview_article_links(&channel))
 }
 
-/// feedからarticle_linkのリストを取得する
+// フィード取得に使ったURLの統計
+#[derive(Debug, Clone, Default)]
+pub struct FeedFetchStats {
+    /// メインURLで取得できたフィード数
+    pub primary: usize,
+    /// フォールバックURLで取得できたフィード（フィードのキー, 取得できたURL）
+    pub fallbacks: Vec<(String, String)>,
+    /// すべてのURLで取得に失敗したフィード（フィードのキー）
+    pub failed: Vec<String>,
+    /// 取得期限前のため取得しなかったフィード数（スケジューリングモード）
+    pub not_due: usize,
+    /// 304 Not Modifiedのため解析を省略したフィード数（条件付きリクエスト）
+    pub not_modified: usize,
+    /// 期待URLパターン（`Feed::url_patterns`）に一致せず除外したリンク（フィードのキー, 件数）
+    pub unexpected_links: Vec<(String, usize)>,
+}
+
+impl FeedFetchStats {
+    /// 他の集計結果を加算する（フィード毎に並行して集計した結果をまとめる）
+    pub fn merge(&mut self, other: FeedFetchStats) {
+        self.primary += other.primary;
+        self.fallbacks.extend(other.fallbacks);
+        self.failed.extend(other.failed);
+        self.not_due += other.not_due;
+        self.not_modified += other.not_modified;
+        self.unexpected_links.extend(other.unexpected_links);
+    }
+
+    /// 表示用の行に整形する
+    pub fn format_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "メインURLで取得: {}件 / フォールバックで取得: {}件 / 取得失敗: {}件",
+            self.primary,
+            self.fallbacks.len(),
+            self.failed.len()
+        )];
+        lines.extend(
+            self.fallbacks
+                .iter()
+                .map(|(feed, url)| format!("  {} -> {}", feed, url)),
+        );
+        if self.not_due > 0 {
+            lines.push(format!("取得期限前のため取得せず: {}件", self.not_due));
+        }
+        if self.not_modified > 0 {
+            lines.push(format!("更新なし（304）: {}件", self.not_modified));
+        }
+        if !self.unexpected_links.is_empty() {
+            let total: usize = self.unexpected_links.iter().map(|(_, count)| count).sum();
+            lines.push(format!("期待URLパターン外のため除外: {}件", total));
+            lines.extend(
+                self.unexpected_links
+                    .iter()
+                    .map(|(feed, count)| format!("  {}: {}件", feed, count)),
+            );
+        }
+        lines
+    }
+}
+
+/// # 概要
+/// feedからarticle_linkのリストを取得する。
+///
+/// メインURLの取得（または解析）に失敗した場合は`fallback_urls`を順に試し、
+/// どのURLで取得できたかを`stats`に記録する。
+#[cfg(feature = "collector")]
 pub async fn get_article_links_from_feed<H: HttpClient>(
     client: &H,
     feed: &Feed,
+    stats: &mut FeedFetchStats,
 ) -> Result<Vec<ArticleLink>> {
-    let xml_content = client
-        .fetch(&feed.rss_link, 30)
-        .await
-        .context(format!("RSSフィードの取得に失敗: {}", feed))?;
-    let channel = parse_channel_from_xml_str(&xml_content).context("XMLの解析に失敗")?;
-    let article_links = get_article_links_from_channel(&channel);
+    match get_article_links_from_feed_conditional(client, feed, &HashMap::new(), stats).await? {
+        FeedFetch::Modified { links, .. } => Ok(links),
+        FeedFetch::NotModified { url } => Err(anyhow::anyhow!(
+            "検証子を付けずに304が返されました: {}",
+            url
+        )),
+    }
+}
 
-    Ok(article_links)
+// フィードの条件付き取得の結果
+#[cfg(feature = "collector")]
+#[derive(Debug)]
+pub enum FeedFetch {
+    /// 前回から更新されていた（取得できたURLと、次回の条件付きリクエストに使う検証子）
+    Modified {
+        links: Vec<ArticleLink>,
+        url: String,
+        validators: CacheValidators,
+    },
+    /// 304 Not Modifiedが返されたため解析を省略した
+    NotModified { url: String },
+}
+
+/// # 概要
+/// 前回の検証子（URL毎のETag / Last-Modified）を付けてfeedからarticle_linkのリストを取得する。
+///
+/// 304が返された場合は解析せずに`FeedFetch::NotModified`を返す。
+/// フォールバックの扱いは`get_article_links_from_feed`と同じ。
+#[cfg(feature = "collector")]
+pub async fn get_article_links_from_feed_conditional<H: HttpClient>(
+    client: &H,
+    feed: &Feed,
+    validators: &HashMap<String, CacheValidators>,
+    stats: &mut FeedFetchStats,
+) -> Result<FeedFetch> {
+    let no_validators = CacheValidators::default();
+    let mut errors = Vec::new();
+    let mut category = None;
+    for (i, url) in feed.candidate_urls().enumerate() {
+        let result = async {
+            let url_validators = validators.get(url).unwrap_or(&no_validators);
+            let fetched = match client.fetch_conditional(url, 30, url_validators).await? {
+                ConditionalFetch::Modified { body, validators } => {
+                    let channel = parse_channel_from_xml_str(&body).context("XMLの解析に失敗")?;
+                    let (links, issues) = get_article_links_from_channel_with_issues(&channel);
+                    for issue in &issues {
+                        tracing::warn!(feed = %feed.key(), url = %url, issue = %issue, "リンクにできない<item>を除外");
+                    }
+                    FeedFetch::Modified {
+                        links,
+                        url: url.to_string(),
+                        validators,
+                    }
+                }
+                ConditionalFetch::NotModified => FeedFetch::NotModified {
+                    url: url.to_string(),
+                },
+            };
+            Ok::<_, anyhow::Error>(fetched)
+        }
+        .await;
+
+        match result {
+            Ok(fetched) => {
+                if i == 0 {
+                    stats.primary += 1;
+                } else {
+                    stats.fallbacks.push((feed.key(), url.to_string()));
+                }
+                if matches!(fetched, FeedFetch::NotModified { .. }) {
+                    stats.not_modified += 1;
+                }
+                return Ok(fetched);
+            }
+            Err(e) => {
+                // 原因はメインURLの失敗で代表する
+                category.get_or_insert_with(|| classify_feed_error(&e));
+                errors.push(format!("{}: {:#}", url, e));
+            }
+        }
+    }
+
+    stats.failed.push(feed.key());
+    Err(anyhow::Error::new(FeedFetchFailure {
+        category: category.unwrap_or(ErrorCategory::Unknown),
+        message: errors.join(" / "),
+    }))
+    .context(format!("RSSフィードの取得に失敗: {}", feed))
+}
+
+/// フィードのすべてのURLの取得に失敗したエラー（原因のカテゴリを保持する）
+#[derive(Debug)]
+pub struct FeedFetchFailure {
+    /// 失敗の原因（404・タイムアウト・解析の失敗など）
+    pub category: ErrorCategory,
+    /// URL毎の失敗の内容
+    pub message: String,
+}
+
+impl fmt::Display for FeedFetchFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for FeedFetchFailure {}
+
+/// # 概要
+/// フィードの取得・保存のエラーの原因カテゴリを判定する。
+///
+/// `FeedFetchFailure`・`FetchError`・`ParseError`を含むエラーはその種類で判別し、
+/// それ以外はエラー本文のキーワードで判定する。
+pub fn classify_feed_error(error: &anyhow::Error) -> ErrorCategory {
+    for cause in error.chain() {
+        if let Some(failure) = cause.downcast_ref::<FeedFetchFailure>() {
+            return failure.category;
+        }
+        if let Some(fetch_error) = cause.downcast_ref::<FetchError>() {
+            return ErrorCategory::from_fetch_error(fetch_error);
+        }
+        if cause.is::<ParseError>() {
+            return ErrorCategory::Parse;
+        }
+    }
+    ErrorCategory::classify(ERROR_STATUS_CODE, &format!("{:#}", error))
+        .unwrap_or(ErrorCategory::Unknown)
+}
+
+/// # 概要
+/// フィードのURL毎に記録した検証子（ETag / Last-Modified）を読み込む。
+///
+/// 記録の無いURLは含めない。
+#[cfg(feature = "collector")]
+pub async fn load_feed_validators(
+    feed: &Feed,
+    pool: &PgPool,
+) -> Result<HashMap<String, CacheValidators>> {
+    let urls: Vec<String> = feed.candidate_urls().map(str::to_string).collect();
+    let rows = sqlx::query!(
+        "SELECT url, etag, last_modified FROM feed_http_validators WHERE url = ANY($1)",
+        &urls
+    )
+    .fetch_all(pool)
+    .await
+    .with_context(|| format!("フィードの検証子の読み込みに失敗: {}", feed.key()))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let validators = CacheValidators {
+                etag: row.etag,
+                last_modified: row.last_modified,
+            };
+            (row.url, validators)
+        })
+        .collect())
+}
+
+/// フィードのURLの検証子を記録する（次回の取得で`If-None-Match` / `If-Modified-Since`に使う）
+#[cfg(feature = "collector")]
+pub async fn store_feed_validators(
+    url: &str,
+    validators: &CacheValidators,
+    pool: &PgPool,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO feed_http_validators (url, etag, last_modified, updated_at)
+        VALUES ($1, $2, $3, now())
+        ON CONFLICT (url) DO UPDATE SET
+            etag = EXCLUDED.etag,
+            last_modified = EXCLUDED.last_modified,
+            updated_at = EXCLUDED.updated_at
+        "#,
+        url,
+        validators.etag,
+        validators.last_modified
+    )
+    .execute(pool)
+    .await
+    .with_context(|| format!("フィードの検証子の記録に失敗: {}", url))?;
+    Ok(())
 }
 
 /// # 概要
@@ -56,51 +486,351 @@ pub async fn get_article_links_from_feed<H: HttpClient>(
 ///
 /// # Note
 /// sqlxの推奨パターンに従い、sqlx::query!マクロを使用してコンパイル時安全性を確保しています。
+#[tracing::instrument(skip_all, fields(count = article_links.len()))]
 pub async fn store_article_links(article_links: &[ArticleLink], pool: &PgPool) -> Result<()> {
+    store_article_links_with_executor(article_links, pool).await
+}
+
+/// 任意の接続（トランザクション・接続・プール）で記事リンクを保存する。
+///
+/// `&mut *tx`を渡すと、呼び出し側のトランザクションに含めて保存できる。
+/// 概要・著者が無いリンクは既存の値を残し、カテゴリを持つリンクはカテゴリを置き換える。
+pub async fn store_article_links_with_executor(
+    article_links: &[ArticleLink],
+    conn: impl Acquire<'_, Database = Postgres>,
+) -> Result<()> {
     if article_links.is_empty() {
         return Ok(());
     }
 
+    // URLを正規化し、同じURLになったリンクは先に現れた1件にまとめる
+    // （同一クエリ内で同じ行を2回更新できないため）
+    let mut seen = HashSet::new();
+    let article_links: Vec<ArticleLink> = article_links
+        .iter()
+        .filter_map(|link| {
+            let url = normalize_article_url(&link.url);
+            seen.insert(url.clone()).then(|| ArticleLink {
+                url,
+                ..link.clone()
+            })
+        })
+        .collect();
+
     // 配列として渡すためのデータ準備
     let urls: Vec<String> = article_links.iter().map(|r| r.url.clone()).collect();
     let titles: Vec<String> = article_links.iter().map(|r| r.title.clone()).collect();
     let pub_dates: Vec<DateTime<Utc>> = article_links.iter().map(|r| r.pub_date).collect();
     let sources: Vec<String> = article_links.iter().map(|r| r.source.clone()).collect();
+    let descriptions: Vec<Option<String>> = article_links
+        .iter()
+        .map(|r| r.description.clone())
+        .collect();
+    let authors: Vec<Option<String>> = article_links.iter().map(|r| r.author.clone()).collect();
+
+    let mut tx = conn.begin().await.context("トランザクションの開始に失敗")?;
 
     // バルクUPSERT処理
     sqlx::query!(
         r#"
-        INSERT INTO article_links (url, title, pub_date, source)
-        SELECT * FROM UNNEST($1::text[], $2::text[], $3::timestamptz[], $4::text[])
+        INSERT INTO article_links (url, title, pub_date, source, description, author)
+        SELECT * FROM UNNEST(
+            $1::text[], $2::text[], $3::timestamptz[], $4::text[], $5::text[], $6::text[]
+        )
         ON CONFLICT (url) DO UPDATE SET
             title = EXCLUDED.title,
             pub_date = EXCLUDED.pub_date,
-            source = EXCLUDED.source
-        WHERE (article_links.title, article_links.pub_date, article_links.source)
-            IS DISTINCT FROM (EXCLUDED.title, EXCLUDED.pub_date, EXCLUDED.source)
+            source = EXCLUDED.source,
+            description = COALESCE(EXCLUDED.description, article_links.description),
+            author = COALESCE(EXCLUDED.author, article_links.author)
+        WHERE (
+            article_links.title,
+            article_links.pub_date,
+            article_links.source,
+            article_links.description,
+            article_links.author
+        ) IS DISTINCT FROM (
+            EXCLUDED.title,
+            EXCLUDED.pub_date,
+            EXCLUDED.source,
+            COALESCE(EXCLUDED.description, article_links.description),
+            COALESCE(EXCLUDED.author, article_links.author)
+        )
         "#,
         &urls,
         &titles,
         &pub_dates,
-        &sources
+        &sources,
+        &descriptions as &[Option<String>],
+        &authors as &[Option<String>]
     )
-    .execute(pool)
+    .execute(&mut *tx)
     .await
     .context("記事リンクのバルクUPSERT処理に失敗しました")?;
 
+    // カテゴリを持つリンクのカテゴリを置き換える
+    let categorized: Vec<String> = article_links
+        .iter()
+        .filter(|r| !r.categories.is_empty())
+        .map(|r| r.url.clone())
+        .collect();
+    if !categorized.is_empty() {
+        let (category_urls, categories): (Vec<String>, Vec<String>) = article_links
+            .iter()
+            .flat_map(|r| r.categories.iter().map(|c| (r.url.clone(), c.clone())))
+            .unzip();
+        sqlx::query!(
+            "DELETE FROM article_categories WHERE url = ANY($1)",
+            &categorized
+        )
+        .execute(&mut *tx)
+        .await
+        .context("記事カテゴリの削除に失敗しました")?;
+        sqlx::query!(
+            r#"
+            INSERT INTO article_categories (url, category)
+            SELECT * FROM UNNEST($1::text[], $2::text[])
+            ON CONFLICT DO NOTHING
+            "#,
+            &category_urls,
+            &categories
+        )
+        .execute(&mut *tx)
+        .await
+        .context("記事カテゴリの保存に失敗しました")?;
+    }
+
+    tx.commit()
+        .await
+        .context("記事リンクの保存のコミットに失敗")?;
+    Ok(())
+}
+
+/// # 概要
+/// 記事リンクを1件保存する。既に同じURLが存在する場合は何もしない。
+///
+/// 外部から投入されたリンクで、RSS由来の既存リンクの情報を上書きしないために使う。
+///
+/// # 戻り値
+/// 新規に登録された場合は`true`
+pub async fn store_article_link_if_absent(
+    article_link: &ArticleLink,
+    pool: &PgPool,
+) -> Result<bool> {
+    store_article_link_if_absent_with_executor(article_link, pool).await
+}
+
+/// 任意のエグゼキュータで記事リンクを1件保存する（既存のURLは変更しない）
+pub async fn store_article_link_if_absent_with_executor(
+    article_link: &ArticleLink,
+    executor: impl PgExecutor<'_>,
+) -> Result<bool> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO article_links (url, title, pub_date, source, description, author)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (url) DO NOTHING
+        "#,
+        article_link.url,
+        article_link.title,
+        article_link.pub_date,
+        article_link.source,
+        article_link.description,
+        article_link.author
+    )
+    .execute(executor)
+    .await
+    .context("記事リンクの登録に失敗しました")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// # 概要
+/// 記事リンクに収集元のフィード（`Feed::key`）を記録する。
+///
+/// 既に別のフィードが記録されているリンクは、最初に収集したフィードのままにする。
+pub async fn assign_article_links_feed(
+    article_links: &[ArticleLink],
+    feed: &Feed,
+    pool: &PgPool,
+) -> Result<()> {
+    assign_article_links_feed_with_executor(article_links, feed, pool).await
+}
+
+/// 任意のエグゼキュータで記事リンクに収集元のフィードを記録する
+pub async fn assign_article_links_feed_with_executor(
+    article_links: &[ArticleLink],
+    feed: &Feed,
+    executor: impl PgExecutor<'_>,
+) -> Result<()> {
+    if article_links.is_empty() {
+        return Ok(());
+    }
+
+    let urls: Vec<String> = article_links.iter().map(|l| l.url.clone()).collect();
+    sqlx::query!(
+        "UPDATE article_links SET feed = $2 WHERE url = ANY($1) AND feed IS NULL",
+        &urls,
+        feed.key()
+    )
+    .execute(executor)
+    .await
+    .context("記事リンクへのフィードの記録に失敗しました")?;
+
     Ok(())
 }
 
+/// # 概要
+/// 仮タイトルのままの記事リンクのタイトルを補完する。
+///
+/// 既存タイトルが仮タイトル（`UNTITLED`）かURLそのものの場合のみ上書きし、
+/// RSSなどで取得済みのタイトルは変更しない。
+///
+/// # 戻り値
+/// タイトルを更新した件数
+pub async fn update_placeholder_titles(titles: &[(String, String)], pool: &PgPool) -> Result<u64> {
+    update_placeholder_titles_with_executor(titles, pool).await
+}
+
+/// 任意のエグゼキュータで仮タイトルのままの記事リンクのタイトルを補完する
+pub async fn update_placeholder_titles_with_executor(
+    titles: &[(String, String)],
+    executor: impl PgExecutor<'_>,
+) -> Result<u64> {
+    if titles.is_empty() {
+        return Ok(0);
+    }
+
+    let urls: Vec<String> = titles.iter().map(|(url, _)| url.clone()).collect();
+    let new_titles: Vec<String> = titles.iter().map(|(_, title)| title.clone()).collect();
+
+    let result = sqlx::query!(
+        r#"
+        UPDATE article_links al SET title = t.title
+        FROM UNNEST($1::text[], $2::text[]) AS t(url, title)
+        WHERE al.url = t.url
+            AND (al.title = $3 OR al.title = al.url OR btrim(al.title) = '')
+            AND al.title <> t.title
+        "#,
+        &urls,
+        &new_titles,
+        UNTITLED
+    )
+    .execute(executor)
+    .await
+    .context("記事リンクのタイトル補完に失敗しました")?;
+
+    Ok(result.rows_affected())
+}
+
 // 記事のフィルター条件を表す構造体
 #[derive(Debug, Default)]
 pub struct ArticleLinkQuery {
     pub link_pattern: Option<String>,
     pub pub_date_from: Option<DateTime<Utc>>,
     pub pub_date_to: Option<DateTime<Utc>>,
+    /// 取得元（`source`）のいずれかに一致するリンクに絞り込む
+    pub sources: Option<Vec<String>>,
+    /// 取得元（`source`）のいずれかに一致するリンクを除外する
+    pub exclude_sources: Option<Vec<String>>,
+    /// カテゴリ（`categories`）のいずれかが付いたリンクに絞り込む
+    pub categories: Option<Vec<String>>,
+    /// ゴミ箱に入っている（soft delete済みの）リンクも含めるかどうか
+    pub include_deleted: bool,
+}
+
+// バックログ検索の絞り込み条件（config/app.yamlのarticle_fetch.backlogに対応）
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BacklogLinkFilter {
+    /// 取得元（`source`）のいずれかに一致するリンクだけを処理する
+    #[serde(default)]
+    pub sources: Option<Vec<String>>,
+    /// 取得元（`source`）のいずれかに一致するリンクは処理しない
+    #[serde(default)]
+    pub exclude_sources: Option<Vec<String>>,
+    /// フィードのグループ（`feed`の`group/name`の`group`）のいずれかに一致するリンクだけを処理する
+    #[serde(default)]
+    pub groups: Option<Vec<String>>,
+}
+
+// バックログから処理するリンクの順序
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BacklogOrder {
+    /// 公開日時の新しい順
+    #[default]
+    Newest,
+    /// 公開日時の古い順
+    Oldest,
+    /// フィードの優先度の高い順（同じ優先度なら新しい順）
+    FeedPriority,
+}
+
+impl BacklogOrder {
+    pub const ALL: [BacklogOrder; 3] = [Self::Newest, Self::Oldest, Self::FeedPriority];
+
+    /// 設定ファイル・CLIでの名前
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Newest => "newest",
+            Self::Oldest => "oldest",
+            Self::FeedPriority => "feed_priority",
+        }
+    }
+}
+
+impl fmt::Display for BacklogOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BacklogOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|order| order.as_str() == s)
+            .ok_or_else(|| {
+                format!(
+                    "不明なバックログの順序: {}（newest・oldest・feed_priority のいずれか）",
+                    s
+                )
+            })
+    }
+}
+
+// バックログから1回に処理するリンクの件数と順序（config/app.yamlのarticle_fetch.backlog_optionsに対応）
+#[derive(Debug, Clone, Deserialize)]
+pub struct BacklogOptions {
+    /// 1回に取得するリンクの上限
+    #[serde(default = "default_backlog_limit")]
+    pub limit: u32,
+    #[serde(default)]
+    pub order: BacklogOrder,
+    /// フィード（`Feed::key`）毎の優先度（大きいほど先に処理する、未指定のフィードは0）
+    #[serde(default)]
+    pub feed_priorities: HashMap<String, i32>,
+}
+
+impl Default for BacklogOptions {
+    fn default() -> Self {
+        Self {
+            limit: default_backlog_limit(),
+            order: BacklogOrder::default(),
+            feed_priorities: HashMap::new(),
+        }
+    }
+}
+
+fn default_backlog_limit() -> u32 {
+    100
 }
 
 /// # 概要
 /// 指定されたデータベースプールから記事リンクを取得する。
+/// ゴミ箱に入っているリンクは`include_deleted`を指定しない限り除外される。
 pub async fn search_article_links(
     query: Option<ArticleLinkQuery>,
     pool: &PgPool,
@@ -111,17 +841,32 @@ pub async fn search_article_links(
     let article_links = sqlx::query_as!(
         ArticleLink,
         r#"
-        SELECT url, title, pub_date, source
-        FROM article_links
+        SELECT al.url, al.title, al.pub_date, al.source, al.description, al.author,
+            ARRAY(
+                SELECT ac.category FROM article_categories ac
+                WHERE ac.url = al.url ORDER BY ac.category
+            ) AS "categories!"
+        FROM article_links al
         WHERE
-            ($1::text IS NULL OR url ILIKE '%' || $1 || '%')
-            AND ($2::timestamptz IS NULL OR pub_date >= $2)
-            AND ($3::timestamptz IS NULL OR pub_date <= $3)
-        ORDER BY pub_date DESC
+            ($1::text IS NULL OR al.url ILIKE '%' || $1 || '%')
+            AND ($2::timestamptz IS NULL OR al.pub_date >= $2)
+            AND ($3::timestamptz IS NULL OR al.pub_date <= $3)
+            AND ($4::text[] IS NULL OR al.source = ANY($4))
+            AND ($5::text[] IS NULL OR NOT (al.source = ANY($5)))
+            AND ($6 OR al.deleted_at IS NULL)
+            AND ($7::text[] IS NULL OR EXISTS (
+                SELECT 1 FROM article_categories ac
+                WHERE ac.url = al.url AND ac.category = ANY($7)
+            ))
+        ORDER BY al.pub_date DESC
         "#,
         query.link_pattern,
         query.pub_date_from,
-        query.pub_date_to
+        query.pub_date_to,
+        query.sources.as_deref(),
+        query.exclude_sources.as_deref(),
+        query.include_deleted,
+        query.categories.as_deref()
     )
     .fetch_all(pool)
     .await?;
@@ -130,17 +875,65 @@ pub async fn search_article_links(
 }
 
 /// 未処理かエラーの記事リンクを取得する
+/// キーワード選別で記事取得の対象外になったリンクは除外される。
 pub async fn search_backlog_article_links(pool: &PgPool) -> Result<Vec<ArticleLink>> {
+    search_backlog_article_links_with(&BacklogLinkFilter::default(), pool).await
+}
+
+/// 取得元で絞り込んで未処理かエラーの記事リンクを新しい順に最大100件取得する
+pub async fn search_backlog_article_links_with(
+    filter: &BacklogLinkFilter,
+    pool: &PgPool,
+) -> Result<Vec<ArticleLink>> {
+    search_backlog_article_links_with_options(filter, &BacklogOptions::default(), pool).await
+}
+
+/// # 概要
+/// 取得元で絞り込んだ未処理かエラーの記事リンクを、`options`の順序で最大`limit`件取得する。
+///
+/// `FeedPriority`ではリンクの`feed`を`feed_priorities`で優先度に変換して並べる
+/// （フィードの無いリンクと未指定のフィードは優先度0）。
+#[tracing::instrument(skip_all, fields(limit = options.limit, order = %options.order))]
+pub async fn search_backlog_article_links_with_options(
+    filter: &BacklogLinkFilter,
+    options: &BacklogOptions,
+    pool: &PgPool,
+) -> Result<Vec<ArticleLink>> {
+    let (priority_feeds, priorities): (Vec<String>, Vec<i32>) = options
+        .feed_priorities
+        .iter()
+        .map(|(feed, priority)| (feed.clone(), *priority))
+        .unzip();
     let links = sqlx::query_as!(
         ArticleLink,
         r#"
-        SELECT al.url, al.title, al.pub_date, al.source
+        SELECT al.url, al.title, al.pub_date, al.source, al.description, al.author,
+            ARRAY(
+                SELECT ac.category FROM article_categories ac
+                WHERE ac.url = al.url ORDER BY ac.category
+            ) AS "categories!"
         FROM article_links al
         LEFT JOIN articles a ON al.url = a.url
-        WHERE a.url IS NULL OR a.status_code != 200
-        ORDER BY al.pub_date DESC
-        LIMIT 100
-        "#
+        LEFT JOIN UNNEST($4::text[], $5::int4[]) AS fp(feed, priority) ON fp.feed = al.feed
+        WHERE (a.url IS NULL OR (a.status_code != 200 AND a.next_retry_at <= now()))
+            AND al.deleted_at IS NULL
+            AND al.fetch_target
+            AND ($1::text[] IS NULL OR al.source = ANY($1))
+            AND ($2::text[] IS NULL OR NOT (al.source = ANY($2)))
+            AND ($7::text[] IS NULL OR split_part(al.feed, '/', 1) = ANY($7))
+        ORDER BY
+            CASE WHEN $3 = 'feed_priority' THEN COALESCE(fp.priority, 0) END DESC,
+            CASE WHEN $3 = 'oldest' THEN al.pub_date END ASC,
+            al.pub_date DESC
+        LIMIT $6
+        "#,
+        filter.sources.as_deref(),
+        filter.exclude_sources.as_deref(),
+        options.order.as_str(),
+        &priority_feeds,
+        &priorities,
+        i64::from(options.limit),
+        filter.groups.as_deref()
     )
     .fetch_all(pool)
     .await
@@ -149,6 +942,46 @@ pub async fn search_backlog_article_links(pool: &PgPool) -> Result<Vec<ArticleLi
     Ok(links)
 }
 
+/// # 概要
+/// 指定したURLのリンクがバックログの処理対象であれば返す（新着通知を受けたリンクの確認用）。
+///
+/// キーワード選別で対象外にしたリンク（`fetch_target`が`false`）、ゴミ箱のリンク、
+/// 取得済み・再処理待ちのリンクと`filter`に合わないリンクは`None`を返す。
+pub async fn find_backlog_article_link(
+    url: &str,
+    filter: &BacklogLinkFilter,
+    pool: &PgPool,
+) -> Result<Option<ArticleLink>> {
+    let link = sqlx::query_as!(
+        ArticleLink,
+        r#"
+        SELECT al.url, al.title, al.pub_date, al.source, al.description, al.author,
+            ARRAY(
+                SELECT ac.category FROM article_categories ac
+                WHERE ac.url = al.url ORDER BY ac.category
+            ) AS "categories!"
+        FROM article_links al
+        LEFT JOIN articles a ON al.url = a.url
+        WHERE al.url = $1
+            AND (a.url IS NULL OR (a.status_code != 200 AND a.next_retry_at <= now()))
+            AND al.deleted_at IS NULL
+            AND al.fetch_target
+            AND ($2::text[] IS NULL OR al.source = ANY($2))
+            AND ($3::text[] IS NULL OR NOT (al.source = ANY($3)))
+            AND ($4::text[] IS NULL OR split_part(al.feed, '/', 1) = ANY($4))
+        "#,
+        url,
+        filter.sources.as_deref(),
+        filter.exclude_sources.as_deref(),
+        filter.groups.as_deref()
+    )
+    .fetch_optional(pool)
+    .await
+    .context("記事リンクの取得に失敗")?;
+
+    Ok(link)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,6 +1031,10 @@ mod tests {
                             <title>Test Article 1</title>
                             <link>http://example.com/article1</link>
                             <description>Test article 1 description</description>
+                            <author>writer@example.com (Writer)</author>
+                            <category>Tech</category>
+                            <category> AI </category>
+                            <category>Tech</category>
                             <pubDate>Sun, 10 Aug 2025 12:00:00 +0000</pubDate>
                         </item>
                         <item>
@@ -217,6 +1054,19 @@ mod tests {
             assert_eq!(article_links[0].url, "http://example.com/article1");
             assert_eq!(article_links[1].title, "Test Article 2");
             assert_eq!(article_links[1].url, "http://example.com/article2");
+
+            // メタデータ（カテゴリは前後の空白を除き、重複をまとめる）
+            assert_eq!(
+                article_links[0].description.as_deref(),
+                Some("Test article 1 description")
+            );
+            assert_eq!(
+                article_links[0].author.as_deref(),
+                Some("writer@example.com (Writer)")
+            );
+            assert_eq!(article_links[0].categories, vec!["Tech", "AI"]);
+            assert_eq!(article_links[1].author, None);
+            assert!(article_links[1].categories.is_empty());
         }
 
         #[test]
@@ -244,12 +1094,201 @@ mod tests {
                 );
             }
         }
+
+        #[test]
+        fn test_preview_article_links_with_warnings() {
+            let xml: &str = r#"
+                <rss version="2.0">
+                    <channel>
+                        <title>Preview Feed</title>
+                        <link>http://example.com</link>
+                        <description>Preview</description>
+                        <item>
+                            <title>Valid</title>
+                            <link>http://example.com/valid</link>
+                            <pubDate>Sun, 10 Aug 2025 12:00:00 +0000</pubDate>
+                        </item>
+                        <item>
+                            <title>No Date</title>
+                            <link>http://example.com/no-date</link>
+                        </item>
+                        <item>
+                            <title>Bad Date</title>
+                            <link>http://example.com/bad-date</link>
+                            <pubDate>someday</pubDate>
+                        </item>
+                    </channel>
+                </rss>
+                "#;
+            let channel = parse_channel_from_xml_str(xml).expect("Failed to parse test RSS");
+            let preview = preview_article_links(&channel);
+
+            assert_eq!(preview.channel_title, "Preview Feed");
+            assert_eq!(preview.item_count, 3);
+            assert_eq!(preview.links.len(), 1);
+            assert_eq!(preview.warnings.len(), 2);
+            assert!(preview.warnings[0]
+                .to_string()
+                .contains("<pubDate>がありません: No Date"));
+            assert_eq!(preview.warnings[1].item, "Bad Date");
+
+            let lines = preview.format_lines(10);
+            assert_eq!(lines[0], "Preview Feed: 3件中1件のリンクを抽出");
+            assert!(lines[1].contains("http://example.com/valid"));
+            assert_eq!(lines.len(), 4);
+        }
+
+        #[test]
+        fn test_resolve_relative_item_urls() {
+            let xml: &str = r#"
+                <rss version="2.0">
+                    <channel>
+                        <title>Relative Feed</title>
+                        <link>https://example.com/news/</link>
+                        <description>Relative</description>
+                        <item>
+                            <title>Scheme Relative</title>
+                            <link>//cdn.example.com/a</link>
+                            <pubDate>Sun, 10 Aug 2025 12:00:00 +0000</pubDate>
+                        </item>
+                        <item>
+                            <title>Root Relative</title>
+                            <link>/news/a.html</link>
+                            <pubDate>Sun, 10 Aug 2025 12:00:00 +0000</pubDate>
+                        </item>
+                        <item>
+                            <title>Path Relative</title>
+                            <link>b.html</link>
+                            <pubDate>Sun, 10 Aug 2025 12:00:00 +0000</pubDate>
+                        </item>
+                        <item>
+                            <title>Absolute</title>
+                            <link>http://other.example.com/c</link>
+                            <pubDate>Sun, 10 Aug 2025 12:00:00 +0000</pubDate>
+                        </item>
+                    </channel>
+                </rss>
+                "#;
+            let channel = parse_channel_from_xml_str(xml).expect("Failed to parse test RSS");
+            let (links, issues) = get_article_links_from_channel_with_issues(&channel);
+            let urls: Vec<&str> = links.iter().map(|link| link.url.as_str()).collect();
+            assert_eq!(
+                urls,
+                vec![
+                    "https://cdn.example.com/a",
+                    "https://example.com/news/a.html",
+                    "https://example.com/news/b.html",
+                    "http://other.example.com/c",
+                ]
+            );
+            assert!(issues.is_empty());
+
+            // チャンネルの<link>が無ければ相対URLは解決できない
+            let xml = xml.replace("<link>https://example.com/news/</link>", "");
+            let channel = parse_channel_from_xml_str(&xml).expect("Failed to parse test RSS");
+            let (links, issues) = get_article_links_from_channel_with_issues(&channel);
+            assert_eq!(links.len(), 1, "絶対URLのみ抽出");
+            assert_eq!(issues.len(), 3);
+            assert_eq!(issues[0].item, "Scheme Relative");
+            assert!(issues[0].message.contains("相対URLを解決できません"));
+            assert_eq!(get_article_links_from_channel(&channel).len(), 1);
+
+            println!("✅ 相対URLの解決テスト成功");
+        }
     }
 
     // データベース保存機能のテスト
     mod save_tests {
         use super::*;
 
+        #[sqlx::test]
+        async fn test_save_links_normalizes_urls(pool: PgPool) -> Result<(), anyhow::Error> {
+            let link = |url: &str, title: &str| ArticleLink {
+                title: title.to_string(),
+                url: url.to_string(),
+                pub_date: "2025-08-26T10:00:00Z".parse().unwrap(),
+                source: "test".to_string(),
+                ..Default::default()
+            };
+            store_article_links(
+                &[
+                    link("https://norm.example.com/news/1/?utm_source=rss", "最初"),
+                    link("https://norm.example.com/news/1#top", "同じ記事"),
+                ],
+                &pool,
+            )
+            .await?;
+            store_article_links(&[link("https://norm.example.com/news/1", "更新")], &pool).await?;
+
+            let rows = sqlx::query!("SELECT url, title FROM article_links")
+                .fetch_all(&pool)
+                .await?;
+            assert_eq!(
+                rows.len(),
+                1,
+                "正規化後に同じURLのリンクは1件にまとめられるべき"
+            );
+            assert_eq!(rows[0].url, "https://norm.example.com/news/1");
+            assert_eq!(rows[0].title, "更新");
+
+            println!("✅ リンクURL正規化テスト成功");
+            Ok(())
+        }
+
+        #[sqlx::test]
+        async fn test_save_links_metadata(pool: PgPool) -> Result<(), anyhow::Error> {
+            let link = |url: &str, categories: &[&str]| ArticleLink {
+                url: url.to_string(),
+                title: url.to_string(),
+                pub_date: "2025-08-26T10:00:00Z".parse().unwrap(),
+                source: "rss".to_string(),
+                description: Some("概要".to_string()),
+                author: Some("著者".to_string()),
+                categories: categories.iter().map(|c| c.to_string()).collect(),
+            };
+            store_article_links(
+                &[
+                    link("https://meta.example.com/1", &["政治", "経済"]),
+                    link("https://meta.example.com/2", &["スポーツ"]),
+                ],
+                &pool,
+            )
+            .await?;
+
+            let by_category = |categories: &[&str]| ArticleLinkQuery {
+                categories: Some(categories.iter().map(|c| c.to_string()).collect()),
+                ..Default::default()
+            };
+            let links = search_article_links(Some(by_category(&["経済"])), &pool).await?;
+            assert_eq!(links.len(), 1);
+            assert_eq!(links[0].url, "https://meta.example.com/1");
+            assert_eq!(links[0].description.as_deref(), Some("概要"));
+            assert_eq!(links[0].author.as_deref(), Some("著者"));
+            assert_eq!(links[0].categories, vec!["政治", "経済"]);
+
+            // メタデータの無い更新では既存の値を残し、カテゴリは新しいものに置き換える
+            let update = ArticleLink {
+                description: None,
+                author: None,
+                ..link("https://meta.example.com/2", &[])
+            };
+            store_article_links(&[update], &pool).await?;
+            store_article_links(&[link("https://meta.example.com/1", &["国際"])], &pool).await?;
+
+            let links =
+                search_article_links(Some(by_category(&["スポーツ", "国際"])), &pool).await?;
+            assert_eq!(links.len(), 2);
+            let second = links.iter().find(|l| l.url.ends_with("/2")).unwrap();
+            assert_eq!(second.description.as_deref(), Some("概要"));
+            assert_eq!(second.categories, vec!["スポーツ"]);
+            assert!(search_article_links(Some(by_category(&["政治"])), &pool)
+                .await?
+                .is_empty());
+
+            println!("✅ 記事メタデータ保存テスト成功");
+            Ok(())
+        }
+
         #[sqlx::test]
         async fn test_save_links_to_db(pool: PgPool) -> Result<(), anyhow::Error> {
             // テスト用リンクデータを作成（必須フィールドのみ）
@@ -259,18 +1298,21 @@ mod tests {
                     url: "https://test.example.com/article1".to_string(),
                     pub_date: "2025-08-26T10:00:00Z".parse().unwrap(),
                     source: "test".to_string(),
+                    ..Default::default()
                 },
                 ArticleLink {
                     title: "Test Article 2".to_string(),
                     url: "https://test.example.com/article2".to_string(),
                     pub_date: "2025-08-26T11:00:00Z".parse().unwrap(),
                     source: "test".to_string(),
+                    ..Default::default()
                 },
                 ArticleLink {
                     title: "異なるドメイン記事".to_string(),
                     url: "https://different.domain.com/post".to_string(),
                     pub_date: "2025-08-26T12:00:00Z".parse().unwrap(),
                     source: "test".to_string(),
+                    ..Default::default()
                 },
             ];
 
@@ -298,6 +1340,7 @@ mod tests {
                 url: "https://test.example.com/article1".to_string(), // fixtureと同じリンク
                 pub_date: "2025-08-26T13:00:00Z".parse().unwrap(),
                 source: "test".to_string(),
+                ..Default::default()
             };
 
             // 重複記事を保存しようとする
@@ -329,18 +1372,21 @@ mod tests {
                     url: "https://test.example.com/article1".to_string(), // fixtureと同じリンク
                     pub_date: "2025-08-26T14:00:00Z".parse().unwrap(),
                     source: "test".to_string(),
+                    ..Default::default()
                 },
                 ArticleLink {
                     title: "新規記事1".to_string(),
                     url: "https://test.example.com/new-article1".to_string(), // 新しいリンク
                     pub_date: "2025-08-26T15:00:00Z".parse().unwrap(),
                     source: "test".to_string(),
+                    ..Default::default()
                 },
                 ArticleLink {
                     title: "新規記事2".to_string(),
                     url: "https://another.domain.com/article".to_string(), // 異なるドメイン
                     pub_date: "2025-08-26T16:00:00Z".parse().unwrap(),
                     source: "test".to_string(),
+                    ..Default::default()
                 },
             ];
 
@@ -356,9 +1402,41 @@ mod tests {
 
             Ok(())
         }
+
+        #[sqlx::test(fixtures("../../fixtures/rss.sql"))]
+        async fn test_store_article_link_if_absent(pool: PgPool) -> Result<(), anyhow::Error> {
+            let new_link = ArticleLink {
+                title: "Webhook記事".to_string(),
+                url: "https://webhook.example.com/new".to_string(),
+                pub_date: "2025-08-26T17:00:00Z".parse().unwrap(),
+                source: "webhook".to_string(),
+                ..Default::default()
+            };
+            assert!(store_article_link_if_absent(&new_link, &pool).await?);
+
+            // fixtureと同じURLは既存情報を上書きしない
+            let existing_link = ArticleLink {
+                url: "https://test.example.com/article1".to_string(),
+                ..new_link.clone()
+            };
+            assert!(!store_article_link_if_absent(&existing_link, &pool).await?);
+
+            let source = sqlx::query_scalar!(
+                "SELECT source FROM article_links WHERE url = $1",
+                "https://test.example.com/article1"
+            )
+            .fetch_one(&pool)
+            .await?;
+            assert_ne!(source, "webhook", "既存リンクのsourceが上書きされました");
+
+            println!("✅ 記事リンク単体登録テスト成功");
+
+            Ok(())
+        }
     }
 
     // HTTPクライアントを使用したフィード取得テスト
+    #[cfg(feature = "collector")]
     mod feed_fetch_tests {
         use super::*;
         use crate::infra::api::http::MockHttpClient;
@@ -372,9 +1450,15 @@ mod tests {
                 group: "test".to_string(),
                 name: "テストフィード".to_string(),
                 rss_link: "https://example.com/rss.xml".to_string(),
+                ..Default::default()
             };
 
-            let result = get_article_links_from_feed(&mock_client, &test_feed).await;
+            let result = get_article_links_from_feed(
+                &mock_client,
+                &test_feed,
+                &mut FeedFetchStats::default(),
+            )
+            .await;
 
             assert!(result.is_ok(), "RSSフィードの取得が失敗");
 
@@ -433,9 +1517,15 @@ mod tests {
                 group: "test".to_string(),
                 name: "エラーテストフィード".to_string(),
                 rss_link: "https://example.com/error.xml".to_string(),
+                ..Default::default()
             };
 
-            let result = get_article_links_from_feed(&error_client, &test_feed).await;
+            let result = get_article_links_from_feed(
+                &error_client,
+                &test_feed,
+                &mut FeedFetchStats::default(),
+            )
+            .await;
 
             assert!(result.is_err(), "エラーが発生するべき");
             let error_msg = result.unwrap_err().to_string();
@@ -446,6 +1536,99 @@ mod tests {
             println!("✅ HTTPモック使用のエラーハンドリングテスト完了");
             Ok(())
         }
+
+        #[tokio::test]
+        async fn test_get_article_links_with_fallback() -> Result<(), anyhow::Error> {
+            let client = MockHttpClient::new_success()
+                .with_failing_url("https://example.com/rss.xml")
+                .with_failing_url("https://mirror1.example.com/rss.xml");
+            let feed = Feed {
+                group: "test".to_string(),
+                name: "ミラー付きフィード".to_string(),
+                rss_link: "https://example.com/rss.xml".to_string(),
+                fallback_urls: vec![
+                    "https://mirror1.example.com/rss.xml".to_string(),
+                    "https://mirror2.example.com/rss.xml".to_string(),
+                ],
+                ..Default::default()
+            };
+
+            let mut stats = FeedFetchStats::default();
+            let links = get_article_links_from_feed(&client, &feed, &mut stats).await?;
+            assert_eq!(links.len(), 3);
+            assert_eq!(stats.primary, 0);
+            assert_eq!(
+                stats.fallbacks,
+                vec![(
+                    "test/ミラー付きフィード".to_string(),
+                    "https://mirror2.example.com/rss.xml".to_string()
+                )]
+            );
+
+            // すべてのURLで失敗した場合はエラー
+            let feed = Feed {
+                fallback_urls: vec!["https://mirror1.example.com/rss.xml".to_string()],
+                ..feed
+            };
+            let err = get_article_links_from_feed(&client, &feed, &mut stats)
+                .await
+                .unwrap_err();
+            assert!(format!("{:#}", err).contains("mirror1.example.com"));
+            assert_eq!(stats.failed, vec!["test/ミラー付きフィード".to_string()]);
+
+            println!("✅ フォールバックURLでのフィード取得テスト完了");
+            Ok(())
+        }
+
+        #[tokio::test]
+        async fn test_feed_fetch_failure_category() -> Result<(), anyhow::Error> {
+            let server = httpmock::MockServer::start_async().await;
+            server
+                .mock_async(|when, then| {
+                    when.method(httpmock::Method::GET).path("/missing.xml");
+                    then.status(404).body("<html>Not Found</html>");
+                })
+                .await;
+            server
+                .mock_async(|when, then| {
+                    when.method(httpmock::Method::GET).path("/broken.xml");
+                    then.status(200).body("<html>RSSではない</html>");
+                })
+                .await;
+
+            let client = crate::infra::api::http::ReqwestHttpClient::new();
+            let fetch_category = |path: &str| {
+                let feed = Feed {
+                    group: "test".to_string(),
+                    name: path.to_string(),
+                    rss_link: server.url(path),
+                    ..Default::default()
+                };
+                let client = &client;
+                async move {
+                    let err =
+                        get_article_links_from_feed(client, &feed, &mut FeedFetchStats::default())
+                            .await
+                            .unwrap_err();
+                    classify_feed_error(&err)
+                }
+            };
+            // 404の本文をRSSとして解析せずにステータスで判別する
+            assert_eq!(
+                fetch_category("/missing.xml").await,
+                ErrorCategory::NotFound
+            );
+            assert_eq!(fetch_category("/broken.xml").await, ErrorCategory::Parse);
+
+            let timeout = anyhow::Error::new(FetchError::Timeout {
+                url: server.url("/slow.xml"),
+            })
+            .context("フィード取得エラー");
+            assert_eq!(classify_feed_error(&timeout), ErrorCategory::Timeout);
+
+            println!("✅ フィード取得失敗の原因判定テスト完了");
+            Ok(())
+        }
     }
 
     // データベース取得機能のテスト
@@ -482,6 +1665,7 @@ mod tests {
                 link_pattern: None,
                 pub_date_from: Some(parse_date("2025-01-15T00:00:00Z")?),
                 pub_date_to: Some(parse_date("2025-01-15T00:00:01Z")?),
+                ..Default::default()
             };
             let article_links_start =
                 search_article_links(Some(filter_start_boundary), &pool).await?;
@@ -496,6 +1680,7 @@ mod tests {
                 link_pattern: None,
                 pub_date_from: Some(parse_date("2025-01-15T23:59:58Z")?),
                 pub_date_to: Some(parse_date("2025-01-15T23:59:59Z")?),
+                ..Default::default()
             };
             let article_links_end = search_article_links(Some(filter_end_boundary), &pool).await?;
             assert_eq!(article_links_end.len(), 1);
@@ -509,6 +1694,7 @@ mod tests {
                 link_pattern: None,
                 pub_date_from: Some(parse_date("2025-01-15T00:00:00Z")?),
                 pub_date_to: Some(parse_date("2025-01-15T23:59:59Z")?),
+                ..Default::default()
             };
             let article_links_day = search_article_links(Some(filter_full_day), &pool).await?;
             let day_links: Vec<&str> = article_links_day.iter().map(|a| a.url.as_str()).collect();
@@ -562,6 +1748,129 @@ mod tests {
             Ok(())
         }
 
+        #[sqlx::test]
+        async fn test_source_filtering(pool: PgPool) -> Result<(), anyhow::Error> {
+            let links: Vec<ArticleLink> = ["bbc", "cnn", "reuters"]
+                .iter()
+                .enumerate()
+                .map(|(i, source)| ArticleLink {
+                    url: format!("https://{}.example.com/{}", source, i),
+                    title: format!("{}の記事", source),
+                    pub_date: Utc::now() - chrono::Duration::minutes(i as i64),
+                    source: source.to_string(),
+                    ..Default::default()
+                })
+                .collect();
+            store_article_links(&links, &pool).await?;
+            let sources_of = |links: &[ArticleLink]| -> Vec<String> {
+                links.iter().map(|l| l.source.clone()).collect()
+            };
+
+            let query = ArticleLinkQuery {
+                sources: Some(vec!["bbc".to_string(), "reuters".to_string()]),
+                ..Default::default()
+            };
+            let found = search_article_links(Some(query), &pool).await?;
+            assert_eq!(sources_of(&found), vec!["bbc", "reuters"]);
+
+            let query = ArticleLinkQuery {
+                exclude_sources: Some(vec!["bbc".to_string()]),
+                ..Default::default()
+            };
+            let found = search_article_links(Some(query), &pool).await?;
+            assert_eq!(sources_of(&found), vec!["cnn", "reuters"]);
+
+            // バックログ検索にも同じ条件で絞り込める
+            let filter = BacklogLinkFilter {
+                sources: Some(vec!["cnn".to_string(), "reuters".to_string()]),
+                exclude_sources: Some(vec!["reuters".to_string()]),
+                ..Default::default()
+            };
+            let backlog = search_backlog_article_links_with(&filter, &pool).await?;
+            assert_eq!(sources_of(&backlog), vec!["cnn"]);
+            assert_eq!(search_backlog_article_links(&pool).await?.len(), 3);
+
+            println!("✅ 取得元フィルタテスト成功");
+            Ok(())
+        }
+
+        #[sqlx::test]
+        async fn test_backlog_options(pool: PgPool) -> Result<(), anyhow::Error> {
+            // news/lowの記事の方が新しい
+            let link = |feed: &str, i: i64| ArticleLink {
+                url: format!("https://{}.example.com/{}", feed, i),
+                title: format!("{}の記事{}", feed, i),
+                pub_date: Utc::now() - chrono::Duration::hours(i),
+                source: "rss".to_string(),
+                ..Default::default()
+            };
+            let low = vec![link("low", 0), link("low", 1)];
+            let high = vec![link("high", 2), link("high", 3)];
+            store_article_links(&[low.clone(), high.clone()].concat(), &pool).await?;
+            for (links, name) in [(&low, "low"), (&high, "high")] {
+                let feed = Feed {
+                    group: "news".to_string(),
+                    name: name.to_string(),
+                    ..Default::default()
+                };
+                assign_article_links_feed(links, &feed, &pool).await?;
+            }
+            let urls_of = |links: &[ArticleLink]| -> Vec<String> {
+                links.iter().map(|l| l.url.clone()).collect()
+            };
+            let filter = BacklogLinkFilter::default();
+
+            let options = BacklogOptions {
+                limit: 3,
+                ..Default::default()
+            };
+            let found = search_backlog_article_links_with_options(&filter, &options, &pool).await?;
+            assert_eq!(urls_of(&found), urls_of(&[&low[..], &high[..1]].concat()));
+
+            let options = BacklogOptions {
+                limit: 2,
+                order: BacklogOrder::Oldest,
+                ..Default::default()
+            };
+            let found = search_backlog_article_links_with_options(&filter, &options, &pool).await?;
+            assert_eq!(
+                urls_of(&found),
+                vec![high[1].url.clone(), high[0].url.clone()]
+            );
+
+            let options = BacklogOptions {
+                limit: 10,
+                order: BacklogOrder::FeedPriority,
+                feed_priorities: HashMap::from([("news/high".to_string(), 10)]),
+            };
+            let found = search_backlog_article_links_with_options(&filter, &options, &pool).await?;
+            assert_eq!(urls_of(&found), urls_of(&[high, low].concat()));
+
+            // フィードのグループで絞り込める
+            let options = BacklogOptions::default();
+            let grouped = |group: &str| BacklogLinkFilter {
+                groups: Some(vec![group.to_string()]),
+                ..Default::default()
+            };
+            let found =
+                search_backlog_article_links_with_options(&grouped("news"), &options, &pool)
+                    .await?;
+            assert_eq!(found.len(), 4);
+            let found =
+                search_backlog_article_links_with_options(&grouped("other"), &options, &pool)
+                    .await?;
+            assert!(found.is_empty());
+
+            assert_eq!(
+                "feed_priority".parse::<BacklogOrder>(),
+                Ok(BacklogOrder::FeedPriority)
+            );
+            assert!("priority".parse::<BacklogOrder>().is_err());
+
+            println!("✅ バックログの件数・順序テスト成功");
+            Ok(())
+        }
+
         #[sqlx::test]
         async fn test_search_backlog_article_links_empty(
             pool: PgPool,
@@ -0,0 +1,211 @@
+use crate::core::article::{search_articles, Article, ArticleQuery};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::PgPool;
+
+// saved_queriesテーブルの1行に対応する保存済みクエリ
+#[derive(Debug, Clone, Serialize)]
+pub struct SavedQuery {
+    pub name: String,
+    pub query: ArticleQuery,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// # 概要
+/// 記事の検索条件を名前付きで保存する。同じ名前のクエリがあれば上書きする。
+pub async fn save_query(name: &str, query: &ArticleQuery, pool: &PgPool) -> Result<()> {
+    if name.trim().is_empty() {
+        bail!("保存済みクエリの名前が空です");
+    }
+    let query_json = serde_json::to_value(query).context("検索条件のシリアライズに失敗")?;
+    sqlx::query!(
+        r#"
+        INSERT INTO saved_queries (name, query_json)
+        VALUES ($1, $2)
+        ON CONFLICT (name) DO UPDATE SET
+            query_json = EXCLUDED.query_json,
+            updated_at = CURRENT_TIMESTAMP
+        "#,
+        name,
+        query_json
+    )
+    .execute(pool)
+    .await
+    .with_context(|| format!("保存済みクエリの保存に失敗: {}", name))?;
+
+    Ok(())
+}
+
+/// 名前を指定して保存済みクエリを取得する（存在しなければ`None`）
+pub async fn get_saved_query(name: &str, pool: &PgPool) -> Result<Option<SavedQuery>> {
+    let row = sqlx::query!(
+        "SELECT name, query_json, created_at, updated_at FROM saved_queries WHERE name = $1",
+        name
+    )
+    .fetch_optional(pool)
+    .await
+    .with_context(|| format!("保存済みクエリの取得に失敗: {}", name))?;
+
+    row.map(|row| {
+        Ok(SavedQuery {
+            query: parse_query(&row.name, row.query_json)?,
+            name: row.name,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    })
+    .transpose()
+}
+
+/// 保存済みクエリの一覧（名前順）
+pub async fn list_saved_queries(pool: &PgPool) -> Result<Vec<SavedQuery>> {
+    let rows = sqlx::query!(
+        "SELECT name, query_json, created_at, updated_at FROM saved_queries ORDER BY name"
+    )
+    .fetch_all(pool)
+    .await
+    .context("保存済みクエリ一覧の取得に失敗")?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(SavedQuery {
+                query: parse_query(&row.name, row.query_json)?,
+                name: row.name,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })
+        })
+        .collect()
+}
+
+/// 保存済みクエリを削除する（削除した場合は`true`）
+pub async fn delete_saved_query(name: &str, pool: &PgPool) -> Result<bool> {
+    let result = sqlx::query!("DELETE FROM saved_queries WHERE name = $1", name)
+        .execute(pool)
+        .await
+        .with_context(|| format!("保存済みクエリの削除に失敗: {}", name))?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// # 概要
+/// 保存済みクエリで記事を検索する。
+///
+/// `overrides`（`ArticleQuery`のフィールドを持つJSONオブジェクト）を指定すると、
+/// 含まれるフィールドだけを保存済みの条件から置き換えて検索する（例: `{"limit": 10}`）。
+pub async fn run_saved_query(
+    name: &str,
+    overrides: Option<&Value>,
+    pool: &PgPool,
+) -> Result<Vec<Article>> {
+    let saved = get_saved_query(name, pool)
+        .await?
+        .with_context(|| format!("保存済みクエリが見つかりません: {}", name))?;
+    let query = match overrides {
+        Some(overrides) => apply_overrides(&saved.query, overrides)?,
+        None => saved.query,
+    };
+    search_articles(Some(query), pool).await
+}
+
+/// 検索条件の一部のフィールドを置き換える
+pub fn apply_overrides(query: &ArticleQuery, overrides: &Value) -> Result<ArticleQuery> {
+    let Value::Object(overrides) = overrides else {
+        bail!("上書きする検索条件はJSONオブジェクトで指定してください");
+    };
+    let mut merged = serde_json::to_value(query).context("検索条件のシリアライズに失敗")?;
+    if let Value::Object(fields) = &mut merged {
+        fields.extend(overrides.clone());
+    }
+    serde_json::from_value(merged).context("上書きする検索条件が不正です")
+}
+
+fn parse_query(name: &str, query_json: Value) -> Result<ArticleQuery> {
+    serde_json::from_value(query_json)
+        .with_context(|| format!("保存済みクエリの検索条件が不正です: {}", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::article::{store_article_content, ArticleContent};
+    use crate::core::rss::{store_article_links, ArticleLink};
+    use serde_json::json;
+
+    #[test]
+    fn test_apply_overrides() {
+        let query = ArticleQuery {
+            title_pattern: Some("rust".to_string()),
+            limit: Some(100),
+            ..Default::default()
+        };
+
+        let merged =
+            apply_overrides(&query, &json!({"limit": 5, "include_deleted": true})).unwrap();
+        assert_eq!(merged.title_pattern.as_deref(), Some("rust"));
+        assert_eq!(merged.limit, Some(5));
+        assert!(merged.include_deleted);
+
+        // nullで条件を外せる
+        let merged = apply_overrides(&query, &json!({"title_pattern": null})).unwrap();
+        assert!(merged.title_pattern.is_none());
+
+        assert!(apply_overrides(&query, &json!({"unknown": 1})).is_err());
+        assert!(apply_overrides(&query, &json!([1])).is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_run_saved_query(pool: PgPool) -> Result<(), anyhow::Error> {
+        let links: Vec<ArticleLink> = ["Rust 2.0 released", "Rust tips", "Go news"]
+            .iter()
+            .enumerate()
+            .map(|(i, title)| ArticleLink {
+                url: format!("https://saved.example.com/{}", i),
+                title: title.to_string(),
+                pub_date: Utc::now() - chrono::Duration::minutes(i as i64),
+                source: "test".to_string(),
+                ..Default::default()
+            })
+            .collect();
+        store_article_links(&links, &pool).await?;
+        store_article_content(
+            &ArticleContent {
+                url: links[0].url.clone(),
+                timestamp: Utc::now(),
+                status_code: 200,
+                content: "本文".to_string(),
+                raw_html: None,
+            },
+            &pool,
+        )
+        .await?;
+
+        let query: ArticleQuery = serde_json::from_value(json!({
+            "title_pattern": "rust",
+            "article_status": "Success"
+        }))?;
+        save_query("rust-success", &query, &pool).await?;
+
+        let articles = run_saved_query("rust-success", None, &pool).await?;
+        assert_eq!(articles.len(), 1);
+        assert_eq!(articles[0].url, links[0].url);
+
+        let overrides = json!({"article_status": null});
+        let articles = run_saved_query("rust-success", Some(&overrides), &pool).await?;
+        assert_eq!(articles.len(), 2, "状態の条件を外すと未処理の記事も含む");
+
+        // 同じ名前で保存すると上書きされる
+        save_query("rust-success", &ArticleQuery::default(), &pool).await?;
+        assert_eq!(run_saved_query("rust-success", None, &pool).await?.len(), 3);
+        assert_eq!(list_saved_queries(&pool).await?.len(), 1);
+
+        assert!(delete_saved_query("rust-success", &pool).await?);
+        assert!(run_saved_query("rust-success", None, &pool).await.is_err());
+
+        println!("✅ 保存済みクエリテスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// 外部連携用JSON（記事・記事リンク）のスキーマのバージョン
+///
+/// フィールドの削除・改名・型の変更など、互換性の無い変更をする場合に上げる。
+/// フィールドの追加だけなら上げない（読み込み側は未知のフィールドを無視する）。
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// # 概要
+/// スキーマのバージョンを付けて外部連携用JSONに書き出す値（`{"schema_version": 1, ...}`）。
+///
+/// 読み込み時は`schema_version`の無いJSON（バージョン導入前の出力）をバージョン1として扱い、
+/// 対応するバージョンより新しいJSONはエラーにする。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Versioned<T> {
+    #[serde(
+        default = "first_schema_version",
+        deserialize_with = "deserialize_schema_version"
+    )]
+    pub schema_version: u32,
+    #[serde(flatten)]
+    pub data: T,
+}
+
+impl<T> Versioned<T> {
+    /// 現在のスキーマのバージョンを付ける
+    pub fn new(data: T) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            data,
+        }
+    }
+}
+
+fn first_schema_version() -> u32 {
+    1
+}
+
+fn deserialize_schema_version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    let version = u32::deserialize(deserializer)?;
+    if version == 0 || version > SCHEMA_VERSION {
+        return Err(D::Error::custom(format!(
+            "対応していないスキーマのバージョン: {}（1〜{}に対応）",
+            version, SCHEMA_VERSION
+        )));
+    }
+    Ok(version)
+}
+
+/// # 概要
+/// 日時をRFC3339（UTC、マイクロ秒までの固定桁、例: `2025-08-26T10:00:00.000000Z`）で読み書きする。
+///
+/// `#[serde(with = "crate::core::schema::rfc3339")]`で使う。
+/// 読み込みはタイムゾーン付きの任意のRFC3339を受け付け、UTCに変換する。
+pub mod rfc3339 {
+    use chrono::{DateTime, SecondsFormat, Utc};
+    use serde::de::Error as _;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &DateTime<Utc>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format(value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DateTime<Utc>, D::Error> {
+        parse(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+
+    pub(super) fn format(value: &DateTime<Utc>) -> String {
+        value.to_rfc3339_opts(SecondsFormat::Micros, true)
+    }
+
+    pub(super) fn parse(value: &str) -> Result<DateTime<Utc>, String> {
+        DateTime::parse_from_rfc3339(value)
+            .map(|date| date.with_timezone(&Utc))
+            .map_err(|e| format!("RFC3339の日時として解釈できません: {}（{}）", value, e))
+    }
+}
+
+/// `rfc3339`の`Option`版（`None`は`null`）
+pub mod rfc3339_option {
+    use chrono::{DateTime, Utc};
+    use serde::de::Error as _;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<DateTime<Utc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_some(&super::rfc3339::format(value)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<Utc>>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|value| super::rfc3339::parse(&value).map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::article::{Article, ArticleMetadata};
+    use crate::core::rss::ArticleLink;
+    use chrono::{DateTime, Utc};
+
+    // スキーマのバージョン1で書き出したJSON（フィールド名・日付形式を変えたらテストが失敗する）
+    const ARTICLE_GOLDEN: &str = include_str!("../../fixtures/schema/article_v1.json");
+    const ARTICLE_LINK_GOLDEN: &str = include_str!("../../fixtures/schema/article_link_v1.json");
+    const ARTICLE_METADATA_GOLDEN: &str =
+        include_str!("../../fixtures/schema/article_metadata_v1.json");
+
+    fn at(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    fn sample_article() -> Article {
+        Article {
+            id: "01J8ZQ3V4K5M6N7P8Q9R0S1T2U".to_string(),
+            url: "https://example.com/news/1".to_string(),
+            title: "テスト記事".to_string(),
+            pub_date: at("2025-08-26T10:00:00Z"),
+            updated_at: Some(at("2025-08-26T10:30:15.123456Z")),
+            status_code: Some(200),
+            content: Some("本文".to_string()),
+            snippet: None,
+        }
+    }
+
+    fn sample_article_link() -> ArticleLink {
+        ArticleLink {
+            url: "https://example.com/news/1".to_string(),
+            title: "テスト記事".to_string(),
+            pub_date: at("2025-08-26T10:00:00Z"),
+            source: "example".to_string(),
+            description: Some("概要".to_string()),
+            author: None,
+            categories: vec!["経済".to_string()],
+        }
+    }
+
+    fn sample_article_metadata() -> ArticleMetadata {
+        ArticleMetadata {
+            url: "https://example.com/news/1".to_string(),
+            title: "テスト記事".to_string(),
+            pub_date: at("2025-08-26T10:00:00Z"),
+            updated_at: None,
+            status_code: Some(404),
+        }
+    }
+
+    /// 書き出したJSONがgolden fileと一致し、golden fileを読み込むと元の値に戻ることを確認する
+    fn assert_golden<T>(value: T, golden: &str)
+    where
+        T: Serialize + for<'de> Deserialize<'de> + std::fmt::Debug,
+    {
+        let expected: serde_json::Value = serde_json::from_str(golden).unwrap();
+        let actual = serde_json::to_value(Versioned::new(&value)).unwrap();
+        assert_eq!(actual, expected, "golden fileとスキーマが一致しない");
+
+        let parsed: Versioned<T> = serde_json::from_str(golden).unwrap();
+        assert_eq!(parsed.schema_version, SCHEMA_VERSION);
+        assert_eq!(format!("{:?}", parsed.data), format!("{:?}", value));
+    }
+
+    #[test]
+    fn test_schema_golden_files() {
+        assert_golden(sample_article(), ARTICLE_GOLDEN);
+        assert_golden(sample_article_link(), ARTICLE_LINK_GOLDEN);
+        assert_golden(sample_article_metadata(), ARTICLE_METADATA_GOLDEN);
+        println!("✅ スキーマ互換性テスト成功");
+    }
+
+    #[test]
+    fn test_versioned_compatibility() {
+        // バージョン導入前の出力（schema_version無し・秒までの日時）も読み込める
+        let legacy = r#"{"url":"https://example.com/news/1","title":"テスト記事","pub_date":"2025-08-26T19:00:00+09:00","updated_at":null,"status_code":404}"#;
+        let parsed: Versioned<ArticleMetadata> = serde_json::from_str(legacy).unwrap();
+        assert_eq!(parsed.schema_version, 1);
+        assert_eq!(parsed.data.pub_date, at("2025-08-26T10:00:00Z"));
+
+        // 対応していない新しいバージョンはエラー
+        let newer = legacy.replacen('{', r#"{"schema_version":2,"#, 1);
+        assert!(serde_json::from_str::<Versioned<ArticleMetadata>>(&newer).is_err());
+        assert!(serde_json::from_str::<ArticleMetadata>(
+            &legacy.replace("2025-08-26T19:00:00+09:00", "2025/08/26")
+        )
+        .is_err());
+        println!("✅ スキーマ後方互換テスト成功");
+    }
+}
@@ -0,0 +1,613 @@
+use crate::core::fetch_backend::{list_fetch_backends, FetchBackendRule};
+#[cfg(feature = "headless")]
+use crate::infra::api::headless::HeadlessBrowserClient;
+use crate::infra::api::{
+    firecrawl::{FirecrawlClient, ReqwestFirecrawlClient},
+    headless::HeadlessBrowserConfig,
+    readability::{ReadabilityClient, ReadabilityClientConfig},
+};
+use crate::types::FetchError;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use url::Url;
+
+// スクレイパーの取得結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScrapeResult {
+    /// 記事本文のマークダウン（本文が得られなかった場合は`None`）
+    pub markdown: Option<String>,
+    /// 記事ページの元のHTML（取得しない設定や、HTMLを返せないバックエンドでは`None`）
+    pub html: Option<String>,
+}
+
+/// 記事本文を取得するスクレイパーの抽象化
+///
+/// Firecrawl API（`FirecrawlClient`を実装する型すべて）とローカルスクレイパー
+/// （`ReadabilityClient`）を同じように扱うためのインターフェース。
+#[async_trait]
+pub trait ScraperClient: Send + Sync {
+    /// エラーメッセージに付けるバックエンド名
+    fn backend_name(&self) -> &'static str;
+
+    /// `url`の取得に使うバックエンド名（ドメイン毎にバックエンドを切り替える場合に上書きする）
+    fn backend_name_for(&self, _url: &str) -> &'static str {
+        self.backend_name()
+    }
+
+    /// URLの記事本文をマークダウンで返す（本文が得られなかった場合は`None`）
+    async fn scrape_markdown(&self, url: &str) -> Result<Option<String>, FetchError>;
+
+    /// URLの記事本文をマークダウンと元のHTMLで返す（既定の実装はHTMLを返さない）
+    async fn scrape(&self, url: &str) -> Result<ScrapeResult, FetchError> {
+        Ok(ScrapeResult {
+            markdown: self.scrape_markdown(url).await?,
+            html: None,
+        })
+    }
+}
+
+#[async_trait]
+impl<F: FirecrawlClient> ScraperClient for F {
+    fn backend_name(&self) -> &'static str {
+        "Firecrawl API"
+    }
+
+    async fn scrape_markdown(&self, url: &str) -> Result<Option<String>, FetchError> {
+        Ok(self.scrape_url(url).await?.markdown)
+    }
+
+    async fn scrape(&self, url: &str) -> Result<ScrapeResult, FetchError> {
+        let document = self.scrape_url(url).await?;
+        Ok(ScrapeResult {
+            markdown: document.markdown,
+            html: document.raw_html,
+        })
+    }
+}
+
+#[async_trait]
+impl ScraperClient for ReadabilityClient {
+    fn backend_name(&self) -> &'static str {
+        "ローカルスクレイパー"
+    }
+
+    async fn scrape_markdown(&self, url: &str) -> Result<Option<String>, FetchError> {
+        Ok(Some(self.fetch_markdown(url).await?))
+    }
+
+    async fn scrape(&self, url: &str) -> Result<ScrapeResult, FetchError> {
+        let (markdown, html) = self.fetch_markdown_with_html(url).await?;
+        Ok(ScrapeResult {
+            markdown: Some(markdown),
+            html: Some(html),
+        })
+    }
+}
+
+#[cfg(feature = "headless")]
+#[async_trait]
+impl ScraperClient for HeadlessBrowserClient {
+    fn backend_name(&self) -> &'static str {
+        "ヘッドレスブラウザ"
+    }
+
+    async fn scrape_markdown(&self, url: &str) -> Result<Option<String>, FetchError> {
+        Ok(Some(self.fetch_markdown(url).await?))
+    }
+
+    async fn scrape(&self, url: &str) -> Result<ScrapeResult, FetchError> {
+        let (markdown, html) = self.fetch_markdown_with_html(url).await?;
+        Ok(ScrapeResult {
+            markdown: Some(markdown),
+            html: Some(html),
+        })
+    }
+}
+
+// 記事本文の取得に使うバックエンド
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScraperBackend {
+    /// Firecrawl API（クレジット課金）
+    #[default]
+    Firecrawl,
+    /// reqwest + readabilityによるローカルスクレイパー
+    Local,
+    /// ヘッドレスChromium + readability（JavaScriptで描画するページ向け、`headless`フィーチャが必要）
+    Headless,
+}
+
+impl ScraperBackend {
+    pub const ALL: [ScraperBackend; 3] = [Self::Firecrawl, Self::Local, Self::Headless];
+
+    /// 設定ファイル・CLIでの名前
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Firecrawl => "firecrawl",
+            Self::Local => "local",
+            Self::Headless => "headless",
+        }
+    }
+}
+
+impl fmt::Display for ScraperBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ScraperBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|backend| backend.as_str() == s)
+            .ok_or_else(|| {
+                format!(
+                    "不明なスクレイパー: {}（firecrawl・local・headless のいずれか）",
+                    s
+                )
+            })
+    }
+}
+
+// スクレイパーの設定（config/app.yamlのscraperに対応）
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScraperConfig {
+    /// 記事本文の取得に使うバックエンド
+    #[serde(default)]
+    pub backend: ScraperBackend,
+    /// ローカルスクレイパーの設定
+    #[serde(default)]
+    pub local: ReadabilityClientConfig,
+    /// ヘッドレスブラウザの設定
+    #[serde(default)]
+    pub headless: HeadlessBrowserConfig,
+    /// ドメイン毎に使うバックエンド（サブドメインにも適用し、より長いドメインを優先）
+    #[serde(default)]
+    pub domains: HashMap<String, ScraperBackend>,
+    /// マークダウンに加えて記事ページの元のHTMLも取得し、articles.raw_htmlに保存する
+    #[serde(default)]
+    pub raw_html: bool,
+    /// ドメイン毎のバックエンドの対応表（fetch_backendsテーブル）を読み込み直す間隔（秒）
+    #[serde(default = "default_fetch_backends_refresh_secs")]
+    pub fetch_backends_refresh_secs: u64,
+}
+
+impl Default for ScraperConfig {
+    fn default() -> Self {
+        Self {
+            backend: ScraperBackend::default(),
+            local: ReadabilityClientConfig::default(),
+            headless: HeadlessBrowserConfig::default(),
+            domains: HashMap::new(),
+            raw_html: false,
+            fetch_backends_refresh_secs: default_fetch_backends_refresh_secs(),
+        }
+    }
+}
+
+fn default_fetch_backends_refresh_secs() -> u64 {
+    60
+}
+
+impl ScraperConfig {
+    /// `url`の取得に使うバックエンドを返す（`domains`に無いドメインは`default`）
+    pub fn backend_for(&self, url: &str, default: ScraperBackend) -> ScraperBackend {
+        let Ok(parsed) = Url::parse(url) else {
+            return default;
+        };
+        let Some(host) = parsed.host_str() else {
+            return default;
+        };
+        self.domains
+            .iter()
+            .filter(|(domain, _)| {
+                host == domain.as_str()
+                    || host
+                        .strip_suffix(domain.as_str())
+                        .is_some_and(|prefix| prefix.ends_with('.'))
+            })
+            .max_by_key(|(domain, _)| domain.len())
+            .map(|(_, backend)| *backend)
+            .unwrap_or(default)
+    }
+}
+
+/// ドメイン毎にバックエンドを切り替えるスクレイパー
+///
+/// `with_fetch_backends`で指定したfetch_backendsテーブルの行に一致するドメインはその行のバックエンド、
+/// `ScraperConfig::domains`に記載したドメインは指定のバックエンド、それ以外は既定のバックエンドで取得する。
+pub struct DomainRoutingScraper {
+    config: ScraperConfig,
+    default: ScraperBackend,
+    scrapers: HashMap<ScraperBackend, Arc<dyn ScraperClient>>,
+    fetch_backends: Option<FetchBackendTable>,
+}
+
+/// fetch_backendsテーブルから読み込んだ振り分けの状態
+struct FetchBackendTable {
+    pool: PgPool,
+    refresh_interval: Duration,
+    resolved: RwLock<Arc<ResolvedFetchBackends>>,
+    /// 最後に読み込んだ日時（読み込み中は他のタスクは待たずに前回の対応表を使う）
+    loaded_at: tokio::sync::Mutex<Option<Instant>>,
+}
+
+#[derive(Default)]
+struct ResolvedFetchBackends {
+    /// 優先する順の対応表の行と、その行で使うスクレイパー
+    rules: Vec<(FetchBackendRule, Arc<dyn ScraperClient>)>,
+    /// `options`付きの行と、既定では作成していないバックエンドの行のために作成したスクレイパー
+    /// （キーはバックエンドと`options`のJSON、読み込み直しても同じ設定なら使い回す）
+    created: HashMap<(ScraperBackend, String), Arc<dyn ScraperClient>>,
+}
+
+impl DomainRoutingScraper {
+    /// 作成済みのスクレイパーから作成する（使うバックエンドすべてが`scrapers`に必要）
+    pub fn new(
+        config: ScraperConfig,
+        default: ScraperBackend,
+        scrapers: HashMap<ScraperBackend, Box<dyn ScraperClient>>,
+    ) -> Result<Self, FetchError> {
+        let required = std::iter::once(default).chain(config.domains.values().copied());
+        for backend in required {
+            if !scrapers.contains_key(&backend) {
+                return Err(FetchError::Unavailable(format!(
+                    "スクレイパー {} が作成されていません",
+                    backend
+                )));
+            }
+        }
+        Ok(Self {
+            config,
+            default,
+            scrapers: scrapers
+                .into_iter()
+                .map(|(backend, scraper)| (backend, Arc::from(scraper)))
+                .collect(),
+            fetch_backends: None,
+        })
+    }
+
+    /// # 概要
+    /// 既定のバックエンドと`config.domains`で使うバックエンドのスクレイパーを作成する。
+    ///
+    /// 使わないバックエンドは作成しない（ヘッドレスブラウザは使う場合のみ起動する）。
+    pub async fn from_config(
+        config: &ScraperConfig,
+        default: ScraperBackend,
+    ) -> Result<Self, FetchError> {
+        let mut scrapers: HashMap<ScraperBackend, Box<dyn ScraperClient>> = HashMap::new();
+        let required = std::iter::once(default).chain(config.domains.values().copied());
+        for backend in required {
+            if scrapers.contains_key(&backend) {
+                continue;
+            }
+            scrapers.insert(backend, create_scraper(backend, config).await?);
+        }
+        Self::new(config.clone(), default, scrapers)
+    }
+
+    /// # 概要
+    /// fetch_backendsテーブルの対応表を振り分けに使う（`config.domains`より優先する）。
+    ///
+    /// 対応表は`reload_fetch_backends`で読み込み、以降は取得時に`refresh_interval`毎に読み込み直す。
+    pub fn with_fetch_backends(mut self, pool: PgPool, refresh_interval: Duration) -> Self {
+        self.fetch_backends = Some(FetchBackendTable {
+            pool,
+            refresh_interval,
+            resolved: RwLock::default(),
+            loaded_at: tokio::sync::Mutex::new(None),
+        });
+        self
+    }
+
+    /// # 概要
+    /// fetch_backendsテーブルの対応表を読み込み直し、振り分けに使う行数を返す。
+    ///
+    /// 行のスクレイパーを作成できない場合（`headless`フィーチャ無しでのheadlessなど）はその行を使わない。
+    pub async fn reload_fetch_backends(&self) -> anyhow::Result<usize> {
+        let Some(table) = &self.fetch_backends else {
+            return Ok(0);
+        };
+        let mut loaded_at = table.loaded_at.lock().await;
+        let count = self.load_fetch_backends(table).await?;
+        *loaded_at = Some(Instant::now());
+        Ok(count)
+    }
+
+    /// 前回の読み込みから`refresh_interval`以上経っていれば対応表を読み込み直す（失敗しても前回の対応表を使う）
+    async fn refresh_fetch_backends(&self) {
+        let Some(table) = &self.fetch_backends else {
+            return;
+        };
+        let Ok(mut loaded_at) = table.loaded_at.try_lock() else {
+            return;
+        };
+        if loaded_at.is_some_and(|at| at.elapsed() < table.refresh_interval) {
+            return;
+        }
+        if let Err(e) = self.load_fetch_backends(table).await {
+            tracing::warn!(
+                error = format!("{:#}", e),
+                "記事取得バックエンドの対応表の読み込みエラー"
+            );
+        }
+        *loaded_at = Some(Instant::now());
+    }
+
+    async fn load_fetch_backends(&self, table: &FetchBackendTable) -> anyhow::Result<usize> {
+        let rules = list_fetch_backends(&table.pool).await?;
+        let previous = Arc::clone(&table.resolved.read().unwrap_or_else(|e| e.into_inner()));
+        let mut resolved = ResolvedFetchBackends::default();
+        for rule in rules {
+            let scraper = self
+                .resolve_rule_scraper(&rule, &previous.created, &mut resolved.created)
+                .await;
+            match scraper {
+                Ok(scraper) => resolved.rules.push((rule, scraper)),
+                Err(e) => tracing::warn!(
+                    domain = %rule.domain_pattern,
+                    backend = rule.backend.as_str(),
+                    error = format!("{:#}", e),
+                    "記事取得バックエンドのスクレイパーの作成エラー（この行は使わない）"
+                ),
+            }
+        }
+        let count = resolved.rules.len();
+        *table.resolved.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(resolved);
+        Ok(count)
+    }
+
+    /// 対応表の行で使うスクレイパー（`options`が無ければ作成済みのバックエンドのスクレイパーを使う）
+    async fn resolve_rule_scraper(
+        &self,
+        rule: &FetchBackendRule,
+        previous: &HashMap<(ScraperBackend, String), Arc<dyn ScraperClient>>,
+        created: &mut HashMap<(ScraperBackend, String), Arc<dyn ScraperClient>>,
+    ) -> anyhow::Result<Arc<dyn ScraperClient>> {
+        if rule.has_default_options() {
+            if let Some(scraper) = self.scrapers.get(&rule.backend) {
+                return Ok(Arc::clone(scraper));
+            }
+        }
+        let key = (rule.backend, rule.options.to_string());
+        let scraper = match created.get(&key).or_else(|| previous.get(&key)) {
+            Some(scraper) => Arc::clone(scraper),
+            None => {
+                let config = rule.scraper_config(&self.config)?;
+                Arc::from(create_scraper(rule.backend, &config).await?)
+            }
+        };
+        created.insert(key, Arc::clone(&scraper));
+        Ok(scraper)
+    }
+
+    fn scraper_for(&self, url: &str) -> Arc<dyn ScraperClient> {
+        if let Some(table) = &self.fetch_backends {
+            let resolved = Arc::clone(&table.resolved.read().unwrap_or_else(|e| e.into_inner()));
+            if let Some((_, scraper)) = resolved.rules.iter().find(|(rule, _)| rule.matches(url)) {
+                return Arc::clone(scraper);
+            }
+        }
+        let backend = self.config.backend_for(url, self.default);
+        Arc::clone(&self.scrapers[&backend])
+    }
+}
+
+/// バックエンドのスクレイパーを作成する（バックエンドの設定は`config`を使う）
+async fn create_scraper(
+    backend: ScraperBackend,
+    config: &ScraperConfig,
+) -> Result<Box<dyn ScraperClient>, FetchError> {
+    let scraper: Box<dyn ScraperClient> =
+        match backend {
+            ScraperBackend::Firecrawl => {
+                Box::new(ReqwestFirecrawlClient::new()?.with_raw_html(config.raw_html))
+            }
+            ScraperBackend::Local => Box::new(ReadabilityClient::with_config(&config.local)?),
+            #[cfg(feature = "headless")]
+            ScraperBackend::Headless => {
+                Box::new(HeadlessBrowserClient::launch(&config.headless).await?)
+            }
+            #[cfg(not(feature = "headless"))]
+            ScraperBackend::Headless => return Err(FetchError::Unavailable(
+                "ヘッドレスブラウザを使うには`headless`フィーチャを有効にしてビルドしてください"
+                    .to_string(),
+            )),
+        };
+    Ok(scraper)
+}
+
+#[async_trait]
+impl ScraperClient for DomainRoutingScraper {
+    fn backend_name(&self) -> &'static str {
+        self.scrapers[&self.default].backend_name()
+    }
+
+    fn backend_name_for(&self, url: &str) -> &'static str {
+        self.scraper_for(url).backend_name()
+    }
+
+    async fn scrape_markdown(&self, url: &str) -> Result<Option<String>, FetchError> {
+        self.refresh_fetch_backends().await;
+        self.scraper_for(url).scrape_markdown(url).await
+    }
+
+    /// 元のHTMLは`raw_html`を有効にした場合のみ返す
+    async fn scrape(&self, url: &str) -> Result<ScrapeResult, FetchError> {
+        self.refresh_fetch_backends().await;
+        let mut result = self.scraper_for(url).scrape(url).await?;
+        if !self.config.raw_html {
+            result.html = None;
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::api::firecrawl::MockFirecrawlClient;
+
+    #[test]
+    fn test_scraper_config() {
+        let config: ScraperConfig = serde_yaml::from_str(
+            r#"
+backend: local
+local:
+  timeout_secs: 10
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.backend, ScraperBackend::Local);
+        assert_eq!(config.local.timeout_secs, 10);
+        assert_eq!(ScraperConfig::default().backend, ScraperBackend::Firecrawl);
+
+        assert_eq!("local".parse(), Ok(ScraperBackend::Local));
+        assert!("selenium".parse::<ScraperBackend>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_domain_routing_scraper() {
+        let config: ScraperConfig = serde_yaml::from_str(
+            r#"
+domains:
+  spa.example.com: headless
+  example.com: local
+"#,
+        )
+        .unwrap();
+        let backend = |url| config.backend_for(url, ScraperBackend::Firecrawl);
+        assert_eq!(
+            backend("https://news.example.org/1"),
+            ScraperBackend::Firecrawl
+        );
+        assert_eq!(backend("https://example.com/1"), ScraperBackend::Local);
+        assert_eq!(backend("https://blog.example.com/1"), ScraperBackend::Local);
+        assert_eq!(
+            backend("https://app.spa.example.com/1"),
+            ScraperBackend::Headless,
+            "より長いドメインを優先"
+        );
+        assert_eq!(
+            backend("https://notexample.com/1"),
+            ScraperBackend::Firecrawl,
+            "ドメインの途中では一致しない"
+        );
+
+        let scrapers = |backends: &[(ScraperBackend, &str)]| {
+            backends
+                .iter()
+                .map(|(backend, content)| {
+                    let scraper: Box<dyn ScraperClient> =
+                        Box::new(MockFirecrawlClient::new_success(content));
+                    (*backend, scraper)
+                })
+                .collect::<HashMap<_, _>>()
+        };
+        assert!(
+            DomainRoutingScraper::new(
+                config.clone(),
+                ScraperBackend::Firecrawl,
+                scrapers(&[(ScraperBackend::Firecrawl, "既定")]),
+            )
+            .is_err(),
+            "使うバックエンドのスクレイパーが必要"
+        );
+        let router = DomainRoutingScraper::new(
+            config,
+            ScraperBackend::Firecrawl,
+            scrapers(&[
+                (ScraperBackend::Firecrawl, "既定"),
+                (ScraperBackend::Local, "ローカル"),
+                (ScraperBackend::Headless, "ブラウザ"),
+            ]),
+        )
+        .unwrap();
+        for (url, expected) in [
+            ("https://news.example.org/1", "既定"),
+            ("https://blog.example.com/1", "ローカル"),
+            ("https://spa.example.com/1", "ブラウザ"),
+        ] {
+            assert_eq!(
+                router.scrape_markdown(url).await.unwrap(),
+                Some(expected.to_string())
+            );
+        }
+        println!("✅ ドメイン別スクレイパーテスト成功");
+    }
+
+    #[sqlx::test]
+    async fn test_domain_routing_with_fetch_backends(pool: PgPool) -> Result<(), anyhow::Error> {
+        use crate::core::fetch_backend::{
+            create_fetch_backend, update_fetch_backend, FetchBackendInput,
+        };
+
+        let scrapers: HashMap<ScraperBackend, Box<dyn ScraperClient>> = HashMap::from([
+            (
+                ScraperBackend::Firecrawl,
+                Box::new(MockFirecrawlClient::new_success("既定")) as Box<dyn ScraperClient>,
+            ),
+            (
+                ScraperBackend::Local,
+                Box::new(MockFirecrawlClient::new_success("ローカル")) as Box<dyn ScraperClient>,
+            ),
+        ]);
+        // 間隔0で取得の度に対応表を読み込み直す
+        let router = DomainRoutingScraper::new(
+            ScraperConfig::default(),
+            ScraperBackend::Firecrawl,
+            scrapers,
+        )?
+        .with_fetch_backends(pool.clone(), Duration::ZERO);
+        let input = |backend| FetchBackendInput {
+            domain_pattern: "example.com".to_string(),
+            backend,
+            options: serde_json::json!({}),
+            priority: 0,
+        };
+        let url = "https://news.example.com/1";
+
+        assert_eq!(router.reload_fetch_backends().await?, 0);
+        assert_eq!(router.scrape_markdown(url).await?, Some("既定".to_string()));
+
+        let rule = create_fetch_backend(input(ScraperBackend::Local), &pool).await?;
+        assert_eq!(
+            router.scrape_markdown(url).await?,
+            Some("ローカル".to_string()),
+            "追加した行を次の取得から使う"
+        );
+        assert_eq!(
+            router.scrape_markdown("https://other.org/1").await?,
+            Some("既定".to_string())
+        );
+
+        update_fetch_backend(rule.id, input(ScraperBackend::Firecrawl), &pool).await?;
+        assert_eq!(router.scrape_markdown(url).await?, Some("既定".to_string()));
+
+        println!("✅ 記事取得バックエンドの対応表による振り分けテスト成功");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_firecrawl_client_as_scraper() {
+        let scraper: &dyn ScraperClient = &MockFirecrawlClient::new_success("本文");
+        assert_eq!(scraper.backend_name(), "Firecrawl API");
+        assert_eq!(
+            scraper
+                .scrape_markdown("https://example.com")
+                .await
+                .unwrap(),
+            Some("本文".to_string())
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use anyhow::{bail, Result};
+use sqlx::{Postgres, QueryBuilder};
+
+// 検索語（部分一致、大文字小文字は区別しない）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchTerm {
+    pub text: String,
+    /// `-`付きの除外語かどうか
+    pub negated: bool,
+}
+
+/// 簡易クエリ構文を解析した検索条件
+///
+/// - スペース区切りの語はすべて含む（AND）
+/// - `OR`で区切った条件のいずれかを満たす（ANDより優先度が低い）
+/// - `-語`は含まないものに限る
+/// - `"フレーズ"`はスペースを含めて1語として扱う
+///
+/// 例: `rust "async runtime" -tokio OR 非同期` は
+/// 「rustと"async runtime"を含みtokioを含まない」または「非同期を含む」。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// OR条件の各項（各項は検索語のAND）
+    pub groups: Vec<Vec<SearchTerm>>,
+}
+
+impl SearchQuery {
+    /// 簡易クエリ構文を解析する
+    ///
+    /// 閉じていない`"`は行末までをフレーズとして扱う。
+    /// 検索語が1つも無い場合はエラーを返す。
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut groups = Vec::new();
+        let mut current = Vec::new();
+        let mut chars = input.chars().peekable();
+
+        loop {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            let Some(&first) = chars.peek() else {
+                break;
+            };
+
+            let negated = first == '-';
+            if negated {
+                chars.next();
+            }
+
+            let (text, quoted) = if chars.peek() == Some(&'"') {
+                chars.next();
+                let phrase: String = chars.by_ref().take_while(|c| *c != '"').collect();
+                (phrase, true)
+            } else {
+                let mut word = String::new();
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                    word.push(c);
+                }
+                (word, false)
+            };
+
+            if !quoted && !negated && text == "OR" {
+                if !current.is_empty() {
+                    groups.push(std::mem::take(&mut current));
+                }
+                continue;
+            }
+            if text.trim().is_empty() {
+                // 単独の`-`や空のフレーズは無視する
+                continue;
+            }
+            current.push(SearchTerm { text, negated });
+        }
+        if !current.is_empty() {
+            groups.push(current);
+        }
+
+        if groups.is_empty() {
+            bail!("検索語が指定されていません: {:?}", input);
+        }
+        Ok(Self { groups })
+    }
+
+    /// 検索条件をSQLの条件式として追加する
+    ///
+    /// `column`はNULLにならない式を渡す（NULLの場合、除外語の判定が常に偽になるため）。
+    pub fn push_condition(&self, qb: &mut QueryBuilder<'_, Postgres>, column: &str) {
+        qb.push("(");
+        for (i, group) in self.groups.iter().enumerate() {
+            if i > 0 {
+                qb.push(" OR ");
+            }
+            qb.push("(");
+            for (j, term) in group.iter().enumerate() {
+                if j > 0 {
+                    qb.push(" AND ");
+                }
+                let operator = if term.negated {
+                    " NOT ILIKE "
+                } else {
+                    " ILIKE "
+                };
+                qb.push(column)
+                    .push(operator)
+                    .push_bind(format!("%{}%", escape_like(&term.text)));
+            }
+            qb.push(")");
+        }
+        qb.push(")");
+    }
+}
+
+/// LIKEのワイルドカード文字をエスケープする（PostgreSQLの既定のエスケープ文字`\`を使用）
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(text: &str, negated: bool) -> SearchTerm {
+        SearchTerm {
+            text: text.to_string(),
+            negated,
+        }
+    }
+
+    #[test]
+    fn test_parse_search_query() {
+        let query = SearchQuery::parse(r#" rust "async runtime"  -tokio OR 非同期 "#).unwrap();
+        assert_eq!(
+            query.groups,
+            vec![
+                vec![
+                    term("rust", false),
+                    term("async runtime", false),
+                    term("tokio", true),
+                ],
+                vec![term("非同期", false)],
+            ]
+        );
+
+        // フレーズ内のORは演算子ではない、小文字のorは通常の語
+        let query = SearchQuery::parse(r#"-"this OR that" or"#).unwrap();
+        assert_eq!(
+            query.groups,
+            vec![vec![term("this OR that", true), term("or", false)]]
+        );
+
+        // 先頭・末尾・連続するORは無視、閉じていないフレーズは行末まで
+        let query = SearchQuery::parse(r#"OR a OR OR "b c"#).unwrap();
+        assert_eq!(
+            query.groups,
+            vec![vec![term("a", false)], vec![term("b c", false)]]
+        );
+
+        assert!(SearchQuery::parse("  ").is_err());
+        assert!(SearchQuery::parse("OR - \"\"").is_err());
+    }
+
+    #[test]
+    fn test_push_condition() {
+        let query = SearchQuery::parse("a -b OR 100%").unwrap();
+        let mut qb = QueryBuilder::<Postgres>::new("SELECT 1 WHERE ");
+        query.push_condition(&mut qb, "title");
+        assert_eq!(
+            qb.sql(),
+            "SELECT 1 WHERE ((title ILIKE $1 AND title NOT ILIKE $2) OR (title ILIKE $3))"
+        );
+        assert_eq!(escape_like("100%_\\"), "100\\%\\_\\\\");
+    }
+}
@@ -0,0 +1,272 @@
+use crate::core::rss::ArticleLink;
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use sqlx::{PgExecutor, PgPool};
+
+/// シリーズキーを導出する正規表現（設定の読み込み時に検証する）
+#[derive(Debug, Clone)]
+pub struct SeriesPattern(Regex);
+
+impl TryFrom<String> for SeriesPattern {
+    type Error = regex::Error;
+
+    fn try_from(pattern: String) -> Result<Self, Self::Error> {
+        Regex::new(&pattern).map(Self)
+    }
+}
+
+impl<'de> Deserialize<'de> for SeriesPattern {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Self::try_from(pattern).map_err(serde::de::Error::custom)
+    }
+}
+
+// シリーズキーの照合対象
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeriesTarget {
+    #[default]
+    Url,
+    Title,
+}
+
+// 連載記事をまとめるシリーズキーの抽出ルール
+//
+// ```yaml
+// - pattern: '^https://example\.com/(?P<key>column/[a-z-]+)/\d+'
+// - target: title
+//   pattern: '^(?P<key>.+?)（第\d+回）'
+// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct SeriesRule {
+    /// 照合する項目（既定はURL）
+    #[serde(default)]
+    pub target: SeriesTarget,
+    /// 名前付きグループ`key`、なければ最初のグループ、グループが無ければ一致した部分をシリーズキーにする
+    pub pattern: SeriesPattern,
+}
+
+impl SeriesRule {
+    fn series_key(&self, link: &ArticleLink) -> Option<String> {
+        let text = match self.target {
+            SeriesTarget::Url => &link.url,
+            SeriesTarget::Title => &link.title,
+        };
+        let captures = self.pattern.0.captures(text)?;
+        let key = captures
+            .name("key")
+            .or_else(|| captures.get(1))
+            .or_else(|| captures.get(0))?
+            .as_str()
+            .trim();
+        (!key.is_empty()).then(|| key.to_string())
+    }
+}
+
+// config/app.yamlのseriesに対応する設定
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SeriesConfig {
+    /// 抽出ルール（記載順に照合し、最初に一致したルールのキーを使う）
+    #[serde(default)]
+    pub rules: Vec<SeriesRule>,
+}
+
+impl SeriesConfig {
+    /// 記事リンクのシリーズキーを導出する（どのルールにも一致しなければNone）
+    pub fn series_key(&self, link: &ArticleLink) -> Option<String> {
+        self.rules.iter().find_map(|rule| rule.series_key(link))
+    }
+}
+
+/// # 概要
+/// 記事リンクのシリーズキー（series_key）を抽出ルールで導出して記録する。
+///
+/// どのルールにも一致しないリンクは変更しない。
+///
+/// # 戻り値
+/// シリーズキーを導出できたリンクの件数
+pub async fn mark_series_keys(
+    article_links: &[ArticleLink],
+    config: &SeriesConfig,
+    pool: &PgPool,
+) -> Result<usize> {
+    mark_series_keys_with_executor(article_links, config, pool).await
+}
+
+/// 任意のエグゼキュータ（トランザクション・接続・プール）でシリーズキーを記録する
+pub async fn mark_series_keys_with_executor(
+    article_links: &[ArticleLink],
+    config: &SeriesConfig,
+    executor: impl PgExecutor<'_>,
+) -> Result<usize> {
+    let (urls, keys): (Vec<String>, Vec<String>) = article_links
+        .iter()
+        .filter_map(|link| Some((link.url.clone(), config.series_key(link)?)))
+        .unzip();
+    if urls.is_empty() {
+        return Ok(0);
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE article_links al SET series_key = t.series_key
+        FROM UNNEST($1::text[], $2::text[]) AS t(url, series_key)
+        WHERE al.url = t.url AND al.series_key IS DISTINCT FROM t.series_key
+        "#,
+        &urls,
+        &keys
+    )
+    .execute(executor)
+    .await
+    .context("シリーズキーの更新に失敗")?;
+
+    Ok(urls.len())
+}
+
+/// # 概要
+/// 保存済みの全記事リンク（ゴミ箱を除く）にシリーズキーを導出し直す。
+///
+/// 抽出ルールを追加・変更した後、既存のリンクに反映するために使う。
+/// ルールに一致しなくなったリンクのシリーズキーは残す。
+pub async fn backfill_series_keys(config: &SeriesConfig, pool: &PgPool) -> Result<usize> {
+    let links = sqlx::query_as!(
+        ArticleLink,
+        r#"
+        SELECT al.url, al.title, al.pub_date, al.source, al.description, al.author,
+            ARRAY(
+                SELECT ac.category FROM article_categories ac
+                WHERE ac.url = al.url ORDER BY ac.category
+            ) AS "categories!"
+        FROM article_links al
+        WHERE al.deleted_at IS NULL
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .context("記事リンクの取得に失敗")?;
+
+    mark_series_keys(&links, config, pool).await
+}
+
+/// # 概要
+/// シリーズキーが一致する記事リンク（ゴミ箱を除く）を公開日時の新しい順に返す。
+///
+/// `latest_only`がtrueなら最新話の1件のみ返す。
+pub async fn search_series(
+    series_key: &str,
+    latest_only: bool,
+    pool: &PgPool,
+) -> Result<Vec<ArticleLink>> {
+    let links = sqlx::query_as!(
+        ArticleLink,
+        r#"
+        SELECT al.url, al.title, al.pub_date, al.source, al.description, al.author,
+            ARRAY(
+                SELECT ac.category FROM article_categories ac
+                WHERE ac.url = al.url ORDER BY ac.category
+            ) AS "categories!"
+        FROM article_links al
+        WHERE al.series_key = $1 AND al.deleted_at IS NULL
+        ORDER BY al.pub_date DESC
+        LIMIT CASE WHEN $2::bool THEN 1 END
+        "#,
+        series_key,
+        latest_only
+    )
+    .fetch_all(pool)
+    .await
+    .with_context(|| format!("シリーズの記事リンクの検索に失敗: {}", series_key))?;
+
+    Ok(links)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rss::store_article_links;
+    use chrono::{Duration, Utc};
+
+    fn link(url: &str, title: &str, days_ago: i64) -> ArticleLink {
+        ArticleLink {
+            url: url.to_string(),
+            title: title.to_string(),
+            pub_date: Utc::now() - Duration::days(days_ago),
+            source: "rss".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn parse_config(yaml: &str) -> SeriesConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_series_key() {
+        let config = parse_config(
+            r#"
+rules:
+  - pattern: '^https://example\.com/(?P<key>column/[a-z-]+)/\d+'
+  - target: title
+    pattern: '^(.+?)（第\d+回）'
+"#,
+        );
+        let column = link(
+            "https://example.com/column/rust-life/12",
+            "Rustのある生活",
+            0,
+        );
+        assert_eq!(
+            config.series_key(&column).as_deref(),
+            Some("column/rust-life")
+        );
+        let titled = link("https://example.com/news/1", "週刊ニュース（第3回）", 0);
+        assert_eq!(config.series_key(&titled).as_deref(), Some("週刊ニュース"));
+        assert_eq!(
+            config.series_key(&link("https://example.com/news/2", "単発記事", 0)),
+            None
+        );
+
+        // 不正な正規表現は設定の読み込み時にエラーにする
+        assert!(serde_yaml::from_str::<SeriesConfig>("rules:\n  - pattern: '('\n").is_err());
+        println!("✅ シリーズキーの導出テスト成功");
+    }
+
+    #[sqlx::test]
+    async fn test_search_series(pool: PgPool) -> Result<(), anyhow::Error> {
+        let config =
+            parse_config("rules:\n  - pattern: '^https://example\\.com/(column/[a-z-]+)/\\d+'\n");
+        let links = vec![
+            link("https://example.com/column/rust-life/1", "第1回", 14),
+            link("https://example.com/column/rust-life/2", "第2回", 7),
+            link("https://example.com/column/rust-life/3", "第3回", 0),
+            link("https://example.com/news/1", "単発記事", 0),
+        ];
+        store_article_links(&links, &pool).await?;
+        assert_eq!(mark_series_keys(&links, &config, &pool).await?, 3);
+
+        let episodes = search_series("column/rust-life", false, &pool).await?;
+        assert_eq!(
+            episodes
+                .iter()
+                .map(|l| l.title.as_str())
+                .collect::<Vec<_>>(),
+            vec!["第3回", "第2回", "第1回"],
+            "新しい順に返す"
+        );
+        let latest = search_series("column/rust-life", true, &pool).await?;
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].title, "第3回");
+
+        // ルールを追加したら既存のリンクに反映できる
+        let config = parse_config(
+            "rules:\n  - pattern: '^https://example\\.com/(column/[a-z-]+)/\\d+'\n  - pattern: '^https://example\\.com/(news)/'\n",
+        );
+        assert_eq!(backfill_series_keys(&config, &pool).await?, 4);
+        assert_eq!(search_series("news", true, &pool).await?.len(), 1);
+
+        println!("✅ シリーズ検索テスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,420 @@
+use crate::core::article::ArticleContent;
+use crate::core::blocklist::normalize_domain;
+use crate::core::circuit_breaker::url_domain;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use std::collections::HashMap;
+
+/// 保存容量の上限を超えて本文を保存しなかった記事のステータスコード（507 Insufficient Storage）
+pub const QUOTA_EXCEEDED_STATUS_CODE: i32 = 507;
+
+/// 抜粋保存した本文の末尾に付ける注記
+const EXCERPT_NOTE: &str = "\n\n…（保存容量の上限のため抜粋）";
+
+// 保存容量の上限を超えた記事の扱い
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaAction {
+    /// 本文の先頭だけを保存する
+    #[default]
+    Excerpt,
+    /// 本文を保存せず、ステータスコード507で記録する
+    Reject,
+}
+
+impl QuotaAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Excerpt => "excerpt",
+            Self::Reject => "reject",
+        }
+    }
+}
+
+// 1ドメイン分の保存容量の上限
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DomainQuota {
+    /// 本文の累計保存容量の上限（バイト）
+    pub max_bytes: i64,
+    /// 上限を超えた記事の扱い
+    #[serde(default)]
+    pub on_exceed: QuotaAction,
+    /// 抜粋保存する本文の文字数
+    #[serde(default = "default_excerpt_chars")]
+    pub excerpt_chars: usize,
+}
+
+fn default_excerpt_chars() -> usize {
+    2000
+}
+
+// ドメイン毎の保存容量クォータの設定（config/app.yamlのarticle_fetch.storage_quotaに対応）
+#[derive(Debug, Clone, Deserialize)]
+pub struct StorageQuotaConfig {
+    /// ドメイン毎の上限（サブドメインも合算して対象にする）
+    #[serde(default)]
+    pub domains: HashMap<String, DomainQuota>,
+    /// `domains`に無いドメインの上限（ドメイン毎に適用、省略時は制限しない）
+    #[serde(default)]
+    pub default: Option<DomainQuota>,
+    /// 使用量を集計し直す間隔（秒）
+    #[serde(default = "default_refresh_interval_secs")]
+    pub refresh_interval_secs: u64,
+}
+
+impl Default for StorageQuotaConfig {
+    fn default() -> Self {
+        Self {
+            domains: HashMap::new(),
+            default: None,
+            refresh_interval_secs: default_refresh_interval_secs(),
+        }
+    }
+}
+
+fn default_refresh_interval_secs() -> u64 {
+    3600
+}
+
+impl StorageQuotaConfig {
+    /// クォータを設定していないかどうか
+    pub fn is_empty(&self) -> bool {
+        self.domains.is_empty() && self.default.is_none()
+    }
+
+    /// ドメインに適用するクォータと、使用量を合算するキー（設定したドメインまたはドメイン自身）
+    pub fn quota_for(&self, domain: &str) -> Option<(String, &DomainQuota)> {
+        let domain = normalize_domain(domain);
+        let configured = self.domains.iter().find(|(key, _)| {
+            let key = normalize_domain(key);
+            domain == key || domain.ends_with(&format!(".{}", key))
+        });
+        match configured {
+            Some((key, quota)) => Some((normalize_domain(key), quota)),
+            None => self.default.as_ref().map(|quota| (domain, quota)),
+        }
+    }
+}
+
+// domain_storage_usageテーブルの1行に対応するドメイン毎の保存容量
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DomainStorageUsage {
+    pub domain: String,
+    pub article_count: i64,
+    pub content_bytes: i64,
+    pub aggregated_at: DateTime<Utc>,
+}
+
+/// # 概要
+/// 記事本文の保存容量をドメイン毎に集計し直して`domain_storage_usage`に保存する。
+///
+/// ゴミ箱の記事も容量を使っているため集計に含める。集計したドメイン数を返す。
+pub async fn refresh_domain_storage_usage(pool: &PgPool) -> Result<usize> {
+    let mut tx = pool.begin().await.context("トランザクションの開始に失敗")?;
+    sqlx::query!("DELETE FROM domain_storage_usage")
+        .execute(&mut *tx)
+        .await
+        .context("保存容量の集計結果の削除に失敗")?;
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO domain_storage_usage (domain, article_count, content_bytes)
+        SELECT domain, COUNT(*), COALESCE(SUM(octet_length(content)), 0)
+        FROM (
+            SELECT lower(substring(url from '^[A-Za-z]+://([^/:?#]+)')) AS domain, content
+            FROM articles
+        ) t
+        WHERE domain IS NOT NULL
+        GROUP BY domain
+        "#
+    )
+    .execute(&mut *tx)
+    .await
+    .context("ドメイン毎の保存容量の集計に失敗")?;
+    tx.commit()
+        .await
+        .context("保存容量の集計結果の保存に失敗")?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+/// 集計済みのドメイン毎の保存容量（容量の多い順）
+pub async fn list_domain_storage_usage(pool: &PgPool) -> Result<Vec<DomainStorageUsage>> {
+    sqlx::query_as!(
+        DomainStorageUsage,
+        r#"
+        SELECT domain, article_count, content_bytes, aggregated_at
+        FROM domain_storage_usage
+        ORDER BY content_bytes DESC, domain
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .context("ドメイン毎の保存容量の取得に失敗")
+}
+
+/// 保存容量を最後に集計した日時（未集計は`None`）
+pub async fn latest_storage_usage_aggregated_at(pool: &PgPool) -> Result<Option<DateTime<Utc>>> {
+    sqlx::query_scalar!("SELECT MAX(aggregated_at) FROM domain_storage_usage")
+        .fetch_one(pool)
+        .await
+        .context("保存容量の集計日時の取得に失敗")
+}
+
+/// 収集中の記事に保存容量のクォータを適用する
+///
+/// 開始時点の集計済み使用量に、この実行で保存した本文の容量を加算して判定する
+/// （既存の記事を更新した場合も加算するため、次の集計までは多めに見積もる）。
+#[derive(Debug, Clone, Default)]
+pub struct DomainStorageQuotas {
+    config: StorageQuotaConfig,
+    /// 使用量を合算するキー毎の保存容量（バイト）
+    usage: HashMap<String, i64>,
+    excerpted: usize,
+    rejected: usize,
+}
+
+impl DomainStorageQuotas {
+    /// ドメイン毎の保存容量からクォータの判定を作成する
+    pub fn new(config: StorageQuotaConfig, usage: &[DomainStorageUsage]) -> Self {
+        let mut totals: HashMap<String, i64> = HashMap::new();
+        for row in usage {
+            if let Some((key, _)) = config.quota_for(&row.domain) {
+                *totals.entry(key).or_default() += row.content_bytes;
+            }
+        }
+        Self {
+            config,
+            usage: totals,
+            ..Default::default()
+        }
+    }
+
+    /// # 概要
+    /// 保存する記事にクォータを適用する。
+    ///
+    /// 成功した記事の本文で上限を超える場合、`on_exceed`に従って本文を抜粋するか、
+    /// 本文を保存せずステータスコード507の記録に置き換える。上限内の記事はそのまま返す。
+    pub fn apply(&mut self, article: ArticleContent) -> ArticleContent {
+        if !article.is_success() {
+            return article;
+        }
+        let Some((key, quota)) = self.config.quota_for(&url_domain(&article.url)) else {
+            return article;
+        };
+        let used = self.usage.entry(key.clone()).or_default();
+        let bytes = article.content.len() as i64;
+        if *used + bytes <= quota.max_bytes {
+            *used += bytes;
+            return article;
+        }
+
+        match quota.on_exceed {
+            QuotaAction::Excerpt => {
+                let mut content: String =
+                    article.content.chars().take(quota.excerpt_chars).collect();
+                content.push_str(EXCERPT_NOTE);
+                *used += content.len() as i64;
+                self.excerpted += 1;
+                ArticleContent {
+                    content,
+                    raw_html: None,
+                    ..article
+                }
+            }
+            QuotaAction::Reject => {
+                self.rejected += 1;
+                ArticleContent {
+                    status_code: QUOTA_EXCEEDED_STATUS_CODE,
+                    content: format!(
+                        "保存容量の上限を超えたため本文を保存しません（{}: {} / {}バイト）",
+                        key, used, quota.max_bytes
+                    ),
+                    raw_html: None,
+                    ..article
+                }
+            }
+        }
+    }
+
+    /// この実行で抜粋保存した記事数と保存を拒否した記事数
+    pub fn counts(&self) -> (usize, usize) {
+        (self.excerpted, self.rejected)
+    }
+}
+
+/// # 概要
+/// 設定したクォータの判定を、集計済みの使用量から作成する。
+///
+/// 最後の集計から`refresh_interval_secs`以上経っている（または未集計の）場合は集計し直す。
+/// クォータを設定していない場合は集計しない。
+pub async fn load_domain_storage_quotas(
+    config: &StorageQuotaConfig,
+    pool: &PgPool,
+) -> Result<DomainStorageQuotas> {
+    if config.is_empty() {
+        return Ok(DomainStorageQuotas::default());
+    }
+    let interval = Duration::seconds(config.refresh_interval_secs as i64);
+    let stale = match latest_storage_usage_aggregated_at(pool).await? {
+        Some(aggregated_at) => Utc::now() - aggregated_at >= interval,
+        None => true,
+    };
+    if stale {
+        refresh_domain_storage_usage(pool).await?;
+    }
+    let usage = list_domain_storage_usage(pool).await?;
+    Ok(DomainStorageQuotas::new(config.clone(), &usage))
+}
+
+/// ドメイン毎の保存容量を表示用の行にする（クォータを設定したドメインは上限も表示）
+pub fn format_storage_usage_lines(
+    usage: &[DomainStorageUsage],
+    config: &StorageQuotaConfig,
+) -> Vec<String> {
+    let Some(aggregated_at) = usage.iter().map(|row| row.aggregated_at).max() else {
+        return vec!["保存容量は未集計です".to_string()];
+    };
+    let mut lines = vec![format!("保存容量（集計: {}）", aggregated_at.to_rfc3339())];
+    for row in usage {
+        let quota = match config.quota_for(&row.domain) {
+            Some((key, quota)) if key == row.domain => format!(
+                " / 上限 {}バイト（{}）",
+                quota.max_bytes,
+                quota.on_exceed.as_str()
+            ),
+            Some((key, quota)) => format!(" / {}の上限 {}バイト", key, quota.max_bytes),
+            None => String::new(),
+        };
+        lines.push(format!(
+            "  {}: {}件 {}バイト{}",
+            row.domain, row.article_count, row.content_bytes, quota
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::article::store_article_contents;
+
+    fn config() -> StorageQuotaConfig {
+        let quota = |max_bytes, on_exceed| DomainQuota {
+            max_bytes,
+            on_exceed,
+            excerpt_chars: 3,
+        };
+        StorageQuotaConfig {
+            domains: HashMap::from([
+                (
+                    "long.example.com".to_string(),
+                    quota(10, QuotaAction::Excerpt),
+                ),
+                (
+                    "huge.example.com".to_string(),
+                    quota(10, QuotaAction::Reject),
+                ),
+            ]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_quota_for() {
+        let config = config();
+        assert_eq!(
+            config.quota_for("news.long.example.com").unwrap().0,
+            "long.example.com",
+            "サブドメインは設定したドメインに合算する"
+        );
+        assert!(config.quota_for("other.example.com").is_none());
+
+        let config = StorageQuotaConfig {
+            default: Some(DomainQuota {
+                max_bytes: 100,
+                on_exceed: QuotaAction::Reject,
+                excerpt_chars: 10,
+            }),
+            ..config
+        };
+        assert_eq!(
+            config.quota_for("Other.Example.com").unwrap().0,
+            "other.example.com"
+        );
+    }
+
+    #[test]
+    fn test_apply_quota() {
+        let usage = vec![DomainStorageUsage {
+            domain: "www.long.example.com".to_string(),
+            article_count: 1,
+            content_bytes: 4,
+            aggregated_at: Utc::now(),
+        }];
+        let mut quotas = DomainStorageQuotas::new(config(), &usage);
+        let article = |url: &str, content: &str| ArticleContent {
+            content: content.to_string(),
+            ..ArticleContent::from_markdown(url, None)
+        };
+
+        // 集計済みの4バイト + 6バイトは上限内
+        let stored = quotas.apply(article("https://long.example.com/1", "abcdef"));
+        assert_eq!(stored.content, "abcdef");
+        // 上限を超えると抜粋
+        let stored = quotas.apply(article("https://long.example.com/2", "abcdef"));
+        assert_eq!(stored.content, format!("abc{}", EXCERPT_NOTE));
+        assert!(stored.is_success());
+
+        // 保存拒否
+        let stored = quotas.apply(article("https://huge.example.com/1", "01234567890"));
+        assert_eq!(stored.status_code, QUOTA_EXCEEDED_STATUS_CODE);
+        assert!(stored.content.contains("huge.example.com"));
+
+        // クォータの無いドメインと失敗した記事はそのまま
+        let stored = quotas.apply(article("https://other.example.com/1", "01234567890"));
+        assert_eq!(stored.content, "01234567890");
+        let failed = ArticleContent::error_placeholder("https://huge.example.com/2", "エラー");
+        assert_eq!(quotas.apply(failed.clone()).content, failed.content);
+
+        assert_eq!(quotas.counts(), (1, 1));
+    }
+
+    #[sqlx::test]
+    async fn test_refresh_domain_storage_usage(pool: PgPool) -> Result<(), anyhow::Error> {
+        assert!(latest_storage_usage_aggregated_at(&pool).await?.is_none());
+        let articles = vec![
+            ArticleContent::from_markdown("https://a.example.com/1", Some("12345".to_string())),
+            ArticleContent::from_markdown("https://A.example.com/2", Some("123".to_string())),
+            ArticleContent::from_markdown("https://b.example.com/1", Some("1".to_string())),
+        ];
+        store_article_contents(&articles, &pool).await?;
+
+        assert_eq!(refresh_domain_storage_usage(&pool).await?, 2);
+        let usage = list_domain_storage_usage(&pool).await?;
+        assert_eq!(usage[0].domain, "a.example.com");
+        assert_eq!(usage[0].article_count, 2);
+        assert_eq!(usage[0].content_bytes, 8);
+        assert!(
+            format_storage_usage_lines(&usage, &StorageQuotaConfig::default())[1]
+                .contains("a.example.com: 2件 8バイト")
+        );
+
+        // 集計から間隔が経っていなければ集計し直さない
+        store_article_contents(
+            &[ArticleContent::from_markdown(
+                "https://c.example.com/1",
+                Some("1".to_string()),
+            )],
+            &pool,
+        )
+        .await?;
+        let quotas = load_domain_storage_quotas(&config(), &pool).await?;
+        assert_eq!(quotas.counts(), (0, 0));
+        assert_eq!(list_domain_storage_usage(&pool).await?.len(), 2);
+
+        println!("✅ ドメイン毎の保存容量集計テスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+use crate::core::blocklist::{find_blocked_domain, BlockedDomain};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+// 記事リンクの登録情報
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LinkRecord {
+    pub title: String,
+    pub pub_date: DateTime<Utc>,
+    pub source: String,
+    /// 登録元のフィード（`グループ/名前`、フィード以外から登録された場合は`None`）
+    pub feed: Option<String>,
+    pub fetch_target: bool,
+    pub created_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+// 記事の現在の状態
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ArticleState {
+    pub status_code: i32,
+    pub timestamp: DateTime<Utc>,
+    pub content_chars: i32,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+// 記事取得の1回分の試行
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FetchAttempt {
+    pub status_code: i32,
+    /// 本文のハッシュ（本文の変更検出用）
+    pub content_hash: String,
+    pub attempted_at: DateTime<Utc>,
+}
+
+/// URLの処理履歴をまとめたもの
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UrlTimeline {
+    pub url: String,
+    /// 記事リンクの登録情報（未登録の場合は`None`）
+    pub link: Option<LinkRecord>,
+    /// 記事の現在の状態（未取得の場合は`None`）
+    pub article: Option<ArticleState>,
+    /// 取得の試行履歴（古い順）
+    pub attempts: Vec<FetchAttempt>,
+    /// 該当する収集禁止ドメイン
+    pub blocked_by: Option<BlockedDomain>,
+}
+
+impl UrlTimeline {
+    /// ステータスコードが変化した試行（最初の試行を含む）
+    pub fn status_changes(&self) -> Vec<&FetchAttempt> {
+        let mut previous: Option<i32> = None;
+        self.attempts
+            .iter()
+            .filter(|attempt| {
+                let changed = previous != Some(attempt.status_code);
+                previous = Some(attempt.status_code);
+                changed
+            })
+            .collect()
+    }
+
+    /// 本文が変化した成功試行（最初の成功を含む）を記事のリビジョンとして返す
+    pub fn revisions(&self) -> Vec<&FetchAttempt> {
+        let mut previous: Option<&str> = None;
+        self.attempts
+            .iter()
+            .filter(|attempt| attempt.status_code == 200)
+            .filter(|attempt| {
+                let changed = previous != Some(attempt.content_hash.as_str());
+                previous = Some(attempt.content_hash.as_str());
+                changed
+            })
+            .collect()
+    }
+
+    /// 表示用の行に整形する（出来事は時系列順）
+    pub fn format_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("URL: {}", self.url)];
+        if let Some(blocked) = &self.blocked_by {
+            lines.push(format!(
+                "⚠ 収集禁止ドメイン: {}（{}）",
+                blocked.domain, blocked.reason
+            ));
+        }
+
+        match &self.link {
+            Some(link) => {
+                lines.push(format!("タイトル: {}", link.title));
+                lines.push(format!(
+                    "ソース: {}（フィード: {}）",
+                    link.source,
+                    link.feed.as_deref().unwrap_or("-")
+                ));
+                lines.push(format!("公開日時: {}", link.pub_date.to_rfc3339()));
+                if !link.fetch_target {
+                    lines.push("取得対象外（キーワード選別）".to_string());
+                }
+            }
+            None => lines.push("記事リンクは登録されていません".to_string()),
+        }
+        match &self.article {
+            Some(article) => lines.push(format!(
+                "現在の状態: status {}（本文{}文字、{}更新）",
+                article.status_code,
+                article.content_chars,
+                article.timestamp.to_rfc3339()
+            )),
+            None => lines.push("現在の状態: 未取得".to_string()),
+        }
+        lines.push(format!(
+            "取得試行: {}回（リビジョン{}件）",
+            self.attempts.len(),
+            self.revisions().len()
+        ));
+
+        let mut events: Vec<(DateTime<Utc>, String)> = Vec::new();
+        if let Some(link) = &self.link {
+            events.push((link.created_at, format!("リンク登録（{}）", link.source)));
+            if let Some(deleted_at) = link.deleted_at {
+                events.push((deleted_at, "リンクをゴミ箱へ移動".to_string()));
+            }
+        }
+        let revisions = self.revisions();
+        for attempt in &self.attempts {
+            let mut event = format!("取得試行 status {}", attempt.status_code);
+            if revisions.iter().any(|r| std::ptr::eq(*r, attempt)) {
+                event.push_str("（本文更新）");
+            }
+            events.push((attempt.attempted_at, event));
+        }
+        if let Some(deleted_at) = self.article.as_ref().and_then(|a| a.deleted_at) {
+            events.push((deleted_at, "記事をゴミ箱へ移動".to_string()));
+        }
+        events.sort_by_key(|(at, _)| *at);
+
+        lines.push("履歴:".to_string());
+        lines.extend(
+            events
+                .into_iter()
+                .map(|(at, event)| format!("  {} {}", at.to_rfc3339(), event)),
+        );
+        lines
+    }
+}
+
+/// # 概要
+/// URLのリンク登録情報・現在の記事の状態・取得試行の履歴をまとめて取得する。
+///
+/// ゴミ箱に入っているレコードも含める。
+pub async fn get_url_timeline(url: &str, pool: &PgPool) -> Result<UrlTimeline> {
+    let link = sqlx::query_as!(
+        LinkRecord,
+        r#"
+        SELECT title, pub_date, source, feed, fetch_target, created_at, deleted_at
+        FROM article_links WHERE url = $1
+        "#,
+        url
+    )
+    .fetch_optional(pool)
+    .await
+    .context("記事リンクの取得に失敗")?;
+
+    let article = sqlx::query_as!(
+        ArticleState,
+        r#"
+        SELECT status_code, timestamp, char_length(content) AS "content_chars!", deleted_at
+        FROM articles WHERE url = $1
+        "#,
+        url
+    )
+    .fetch_optional(pool)
+    .await
+    .context("記事の取得に失敗")?;
+
+    let attempts = sqlx::query_as!(
+        FetchAttempt,
+        r#"
+        SELECT status_code, content_hash, attempted_at
+        FROM article_fetch_attempts WHERE url = $1
+        ORDER BY attempted_at, id
+        "#,
+        url
+    )
+    .fetch_all(pool)
+    .await
+    .context("取得試行履歴の取得に失敗")?;
+
+    Ok(UrlTimeline {
+        url: url.to_string(),
+        link,
+        article,
+        attempts,
+        blocked_by: find_blocked_domain(url, pool).await?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::article::{store_article_content, ArticleContent};
+    use crate::core::rss::{store_article_links, ArticleLink};
+
+    #[sqlx::test]
+    async fn test_get_url_timeline(pool: PgPool) -> Result<(), anyhow::Error> {
+        let url = "https://timeline.example.com/news/1";
+        store_article_links(
+            &[ArticleLink {
+                url: url.to_string(),
+                title: "履歴テスト".to_string(),
+                pub_date: Utc::now(),
+                source: "rss".to_string(),
+                ..Default::default()
+            }],
+            &pool,
+        )
+        .await?;
+
+        let article = |status_code: i32, content: &str| ArticleContent {
+            url: url.to_string(),
+            timestamp: Utc::now(),
+            status_code,
+            content: content.to_string(),
+            raw_html: None,
+        };
+        for a in [
+            article(500, "タイムアウト"),
+            article(200, "本文v1"),
+            article(200, "本文v1"),
+            article(200, "本文v2"),
+        ] {
+            store_article_content(&a, &pool).await?;
+        }
+
+        let timeline = get_url_timeline(url, &pool).await?;
+        assert_eq!(timeline.link.as_ref().unwrap().title, "履歴テスト");
+        assert_eq!(timeline.article.as_ref().unwrap().status_code, 200);
+        assert_eq!(timeline.attempts.len(), 4);
+        assert_eq!(timeline.status_changes().len(), 2);
+        assert_eq!(timeline.revisions().len(), 2, "同じ本文の再取得は数えない");
+        assert!(timeline
+            .format_lines()
+            .iter()
+            .any(|line| line.contains("取得試行: 4回（リビジョン2件）")));
+
+        let unknown = get_url_timeline("https://timeline.example.com/unknown", &pool).await?;
+        assert!(unknown.link.is_none() && unknown.article.is_none());
+        assert!(unknown.attempts.is_empty());
+
+        println!("✅ URL処理履歴テスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+use crate::core::feed::Feed;
+use crate::core::rss::ArticleLink;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use sqlx::{PgExecutor, PgPool};
+use std::collections::HashMap;
+
+// タイトルのキーワードによる記事取得対象の選別ルール
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TopicFilter {
+    /// いずれかを含むタイトルのみ対象にする（空なら全件対象）
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// いずれかを含むタイトルは対象外にする（includeより優先）
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl TopicFilter {
+    /// タイトルが記事取得の対象かどうかを判定する（大文字小文字は区別しない）
+    pub fn is_target(&self, title: &str) -> bool {
+        let title = title.to_lowercase();
+        let contains = |keyword: &String| title.contains(&keyword.to_lowercase());
+
+        if self.exclude.iter().any(contains) {
+            return false;
+        }
+        self.include.is_empty() || self.include.iter().any(contains)
+    }
+
+    /// 別のルールのキーワードを追加したルールを返す
+    fn merged(&self, other: &TopicFilter) -> TopicFilter {
+        TopicFilter {
+            include: self.include.iter().chain(&other.include).cloned().collect(),
+            exclude: self.exclude.iter().chain(&other.exclude).cloned().collect(),
+        }
+    }
+}
+
+// config/app.yamlのtopic_filterに対応する設定
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TopicFilterConfig {
+    /// 全フィード共通のルール
+    #[serde(flatten)]
+    pub global: TopicFilter,
+    /// フィード毎の追加ルール（キーは"group"または"group/name"）
+    #[serde(default)]
+    pub feeds: HashMap<String, TopicFilter>,
+}
+
+impl TopicFilterConfig {
+    /// フィードに適用するルールを返す
+    ///
+    /// 共通ルールに、グループ単位・フィード単位のルールのキーワードを順に追加する。
+    pub fn filter_for(&self, feed: &Feed) -> TopicFilter {
+        let feed_key = format!("{}/{}", feed.group, feed.name);
+        [feed.group.as_str(), feed_key.as_str()]
+            .iter()
+            .filter_map(|key| self.feeds.get(*key))
+            .fold(self.global.clone(), |filter, extra| filter.merged(extra))
+    }
+}
+
+/// # 概要
+/// 記事リンクの記事取得対象フラグ（fetch_target）をタイトルの評価結果で更新する。
+///
+/// # 戻り値
+/// 対象外と判定されたリンクの件数
+pub async fn mark_fetch_targets(
+    article_links: &[ArticleLink],
+    filter: &TopicFilter,
+    pool: &PgPool,
+) -> Result<usize> {
+    mark_fetch_targets_with_executor(article_links, filter, pool).await
+}
+
+/// 任意のエグゼキュータ（トランザクション・接続・プール）で記事取得対象フラグを更新する
+pub async fn mark_fetch_targets_with_executor(
+    article_links: &[ArticleLink],
+    filter: &TopicFilter,
+    executor: impl PgExecutor<'_>,
+) -> Result<usize> {
+    if article_links.is_empty() {
+        return Ok(0);
+    }
+
+    let urls: Vec<String> = article_links.iter().map(|l| l.url.clone()).collect();
+    let targets: Vec<bool> = article_links
+        .iter()
+        .map(|l| filter.is_target(&l.title))
+        .collect();
+
+    sqlx::query!(
+        r#"
+        UPDATE article_links al SET fetch_target = t.fetch_target
+        FROM UNNEST($1::text[], $2::bool[]) AS t(url, fetch_target)
+        WHERE al.url = t.url AND al.fetch_target IS DISTINCT FROM t.fetch_target
+        "#,
+        &urls,
+        &targets
+    )
+    .execute(executor)
+    .await
+    .context("記事取得対象フラグの更新に失敗")?;
+
+    Ok(targets.iter().filter(|target| !**target).count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rss::{search_backlog_article_links, store_article_links};
+
+    fn filter(include: &[&str], exclude: &[&str]) -> TopicFilter {
+        TopicFilter {
+            include: include.iter().map(|k| k.to_string()).collect(),
+            exclude: exclude.iter().map(|k| k.to_string()).collect(),
+        }
+    }
+
+    fn feed(group: &str, name: &str) -> Feed {
+        Feed {
+            group: group.to_string(),
+            name: name.to_string(),
+            rss_link: "https://example.com/rss.xml".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_topic_filter_is_target() {
+        // ルールなしは全件対象
+        assert!(TopicFilter::default().is_target("なんでも"));
+
+        let f = filter(&["AI", "Rust"], &["広告"]);
+        assert!(f.is_target("New rust release"));
+        assert!(f.is_target("生成AIの最新動向"));
+        assert!(!f.is_target("天気予報"));
+        assert!(!f.is_target("【広告】AIセミナーのご案内"), "excludeが優先");
+    }
+
+    #[test]
+    fn test_topic_filter_config_for_feed() {
+        let config: TopicFilterConfig = serde_yaml::from_str(
+            r#"
+include: ["AI"]
+feeds:
+  tech:
+    include: ["Rust"]
+  tech/blog:
+    exclude: ["PR"]
+"#,
+        )
+        .unwrap();
+
+        let global = config.filter_for(&feed("news", "top"));
+        assert_eq!(global.include, vec!["AI"]);
+        assert!(global.exclude.is_empty());
+
+        let blog = config.filter_for(&feed("tech", "blog"));
+        assert_eq!(blog.include, vec!["AI", "Rust"]);
+        assert_eq!(blog.exclude, vec!["PR"]);
+    }
+
+    #[sqlx::test]
+    async fn test_mark_fetch_targets(pool: PgPool) -> Result<(), anyhow::Error> {
+        let links: Vec<ArticleLink> = ["Rustの新機能", "今日の天気"]
+            .iter()
+            .enumerate()
+            .map(|(i, title)| ArticleLink {
+                url: format!("https://topic.example.com/{}", i),
+                title: title.to_string(),
+                pub_date: chrono::Utc::now(),
+                source: "test".to_string(),
+                ..Default::default()
+            })
+            .collect();
+        store_article_links(&links, &pool).await?;
+
+        let skipped = mark_fetch_targets(&links, &filter(&["Rust"], &[]), &pool).await?;
+        assert_eq!(skipped, 1);
+
+        // 対象外のリンクはバックログに含まれない
+        let backlog = search_backlog_article_links(&pool).await?;
+        let urls: Vec<&str> = backlog.iter().map(|l| l.url.as_str()).collect();
+        assert_eq!(urls, vec!["https://topic.example.com/0"]);
+
+        println!("✅ 記事取得対象フラグ更新テスト成功");
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+/// ゴミ箱に入ったレコードを保持する日数
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+
+// ゴミ箱に入っている記事リンク
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TrashedArticleLink {
+    pub url: String,
+    pub title: String,
+    pub pub_date: DateTime<Utc>,
+    pub source: String,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// # 概要
+/// 指定URLの記事リンクと記事をゴミ箱に入れる（soft delete）。
+///
+/// 物理削除は行わず`deleted_at`に削除日時を記録する。
+/// 既にゴミ箱にあるレコードの削除日時は更新しない。
+///
+/// # 戻り値
+/// いずれかのテーブルで削除対象が見つかった場合は`true`
+pub async fn soft_delete(url: &str, pool: &PgPool) -> Result<bool> {
+    let mut tx = pool.begin().await.context("トランザクションの開始に失敗")?;
+
+    let links = sqlx::query!(
+        r#"
+        UPDATE article_links SET deleted_at = CURRENT_TIMESTAMP
+        WHERE url = $1 AND deleted_at IS NULL
+        "#,
+        url
+    )
+    .execute(&mut *tx)
+    .await
+    .context("記事リンクのsoft deleteに失敗")?;
+
+    let articles = sqlx::query!(
+        r#"
+        UPDATE articles SET deleted_at = CURRENT_TIMESTAMP
+        WHERE url = $1 AND deleted_at IS NULL
+        "#,
+        url
+    )
+    .execute(&mut *tx)
+    .await
+    .context("記事のsoft deleteに失敗")?;
+
+    tx.commit()
+        .await
+        .context("トランザクションのコミットに失敗")?;
+
+    Ok(links.rows_affected() + articles.rows_affected() > 0)
+}
+
+/// # 概要
+/// ゴミ箱に入っている指定URLの記事リンクと記事を元に戻す。
+///
+/// # 戻り値
+/// いずれかのテーブルで復元対象が見つかった場合は`true`
+pub async fn restore(url: &str, pool: &PgPool) -> Result<bool> {
+    let mut tx = pool.begin().await.context("トランザクションの開始に失敗")?;
+
+    let links = sqlx::query!(
+        "UPDATE article_links SET deleted_at = NULL WHERE url = $1 AND deleted_at IS NOT NULL",
+        url
+    )
+    .execute(&mut *tx)
+    .await
+    .context("記事リンクの復元に失敗")?;
+
+    let articles = sqlx::query!(
+        "UPDATE articles SET deleted_at = NULL WHERE url = $1 AND deleted_at IS NOT NULL",
+        url
+    )
+    .execute(&mut *tx)
+    .await
+    .context("記事の復元に失敗")?;
+
+    tx.commit()
+        .await
+        .context("トランザクションのコミットに失敗")?;
+
+    Ok(links.rows_affected() + articles.rows_affected() > 0)
+}
+
+/// ゴミ箱に入っている記事リンクを削除日時の新しい順に取得する
+pub async fn search_trash(pool: &PgPool) -> Result<Vec<TrashedArticleLink>> {
+    let links = sqlx::query_as!(
+        TrashedArticleLink,
+        r#"
+        SELECT url, title, pub_date, source, deleted_at AS "deleted_at!"
+        FROM article_links
+        WHERE deleted_at IS NOT NULL
+        ORDER BY deleted_at DESC
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .context("ゴミ箱の記事リンク取得に失敗")?;
+
+    Ok(links)
+}
+
+/// # 概要
+/// ゴミ箱に入ってから`older_than`以上経過したレコードを物理削除する。
+///
+/// # 戻り値
+/// 物理削除した行数（article_linksとarticlesの合計）
+pub async fn empty_trash(older_than: Duration, pool: &PgPool) -> Result<u64> {
+    let threshold = Utc::now() - older_than;
+    let mut tx = pool.begin().await.context("トランザクションの開始に失敗")?;
+
+    let articles = sqlx::query!("DELETE FROM articles WHERE deleted_at <= $1", threshold)
+        .execute(&mut *tx)
+        .await
+        .context("ゴミ箱の記事の物理削除に失敗")?;
+
+    let links = sqlx::query!(
+        "DELETE FROM article_links WHERE deleted_at <= $1",
+        threshold
+    )
+    .execute(&mut *tx)
+    .await
+    .context("ゴミ箱の記事リンクの物理削除に失敗")?;
+
+    tx.commit()
+        .await
+        .context("トランザクションのコミットに失敗")?;
+
+    Ok(articles.rows_affected() + links.rows_affected())
+}
+
+/// 保持期間（`TRASH_RETENTION_DAYS`）を過ぎたゴミ箱のレコードを物理削除する
+pub async fn empty_expired_trash(pool: &PgPool) -> Result<u64> {
+    empty_trash(Duration::days(TRASH_RETENTION_DAYS), pool).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::article::{search_article_contents, search_articles, ArticleQuery};
+    use crate::core::rss::{search_article_links, search_backlog_article_links, ArticleLinkQuery};
+
+    #[sqlx::test(fixtures("../../fixtures/article_basic.sql"))]
+    async fn test_soft_delete_excludes_from_search(pool: PgPool) -> Result<(), anyhow::Error> {
+        let deleted = soft_delete("https://test.com/link1", &pool).await?;
+        assert!(deleted, "削除対象が見つかるべきです");
+
+        // 物理削除はされていない
+        let count = sqlx::query_scalar!("SELECT COUNT(*) FROM article_links")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(count, Some(2));
+
+        // 各検索からはデフォルトで除外される
+        let links = search_article_links(None, &pool).await?;
+        assert!(links.iter().all(|l| l.url != "https://test.com/link1"));
+        let articles = search_articles(None, &pool).await?;
+        assert!(articles.iter().all(|a| a.url != "https://test.com/link1"));
+        let contents = search_article_contents(None, &pool).await?;
+        assert!(
+            contents.is_empty(),
+            "削除済みの記事内容は除外されるべきです"
+        );
+
+        // include_deletedを指定すると含まれる
+        let query = ArticleQuery {
+            include_deleted: true,
+            ..Default::default()
+        };
+        let articles = search_articles(Some(query), &pool).await?;
+        assert!(articles.iter().any(|a| a.url == "https://test.com/link1"));
+
+        // 2回目の削除は対象なし
+        assert!(!soft_delete("https://test.com/link1", &pool).await?);
+
+        println!("✅ soft deleteの検索除外テスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("../../fixtures/article_basic.sql"))]
+    async fn test_soft_delete_excludes_from_backlog(pool: PgPool) -> Result<(), anyhow::Error> {
+        // link2は未処理のためバックログ対象
+        let backlog = search_backlog_article_links(&pool).await?;
+        assert_eq!(backlog.len(), 1);
+
+        soft_delete("https://test.com/link2", &pool).await?;
+
+        let backlog = search_backlog_article_links(&pool).await?;
+        assert!(
+            backlog.is_empty(),
+            "ゴミ箱のリンクは処理対象外であるべきです"
+        );
+
+        println!("✅ soft deleteのバックログ除外テスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("../../fixtures/article_basic.sql"))]
+    async fn test_restore(pool: PgPool) -> Result<(), anyhow::Error> {
+        soft_delete("https://test.com/link1", &pool).await?;
+        let trash = search_trash(&pool).await?;
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].url, "https://test.com/link1");
+
+        let restored = restore("https://test.com/link1", &pool).await?;
+        assert!(restored, "復元対象が見つかるべきです");
+        assert!(search_trash(&pool).await?.is_empty());
+
+        let query = ArticleLinkQuery {
+            link_pattern: Some("link1".to_string()),
+            ..Default::default()
+        };
+        let links = search_article_links(Some(query), &pool).await?;
+        assert_eq!(links.len(), 1, "復元後は検索対象に戻るべきです");
+        let contents = search_article_contents(None, &pool).await?;
+        assert_eq!(contents.len(), 1, "記事内容も復元されるべきです");
+
+        // ゴミ箱にないURLの復元は対象なし
+        assert!(!restore("https://test.com/link2", &pool).await?);
+
+        println!("✅ ゴミ箱からの復元テスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("../../fixtures/article_basic.sql"))]
+    async fn test_empty_trash(pool: PgPool) -> Result<(), anyhow::Error> {
+        soft_delete("https://test.com/link1", &pool).await?;
+        soft_delete("https://test.com/link2", &pool).await?;
+        // link1だけ保持期間を過ぎた状態にする
+        sqlx::query!(
+            "UPDATE article_links SET deleted_at = $1 WHERE url = 'https://test.com/link1'",
+            Utc::now() - Duration::days(TRASH_RETENTION_DAYS + 1)
+        )
+        .execute(&pool)
+        .await?;
+        sqlx::query!(
+            "UPDATE articles SET deleted_at = $1 WHERE url = 'https://test.com/link1'",
+            Utc::now() - Duration::days(TRASH_RETENTION_DAYS + 1)
+        )
+        .execute(&pool)
+        .await?;
+
+        let removed = empty_expired_trash(&pool).await?;
+        assert_eq!(
+            removed, 2,
+            "link1のリンクと記事の2行が物理削除されるべきです"
+        );
+
+        let trash = search_trash(&pool).await?;
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].url, "https://test.com/link2");
+
+        // 期間0を指定するとゴミ箱の全件が削除される
+        let removed = empty_trash(Duration::zero(), &pool).await?;
+        assert_eq!(removed, 1);
+        let count = sqlx::query_scalar!("SELECT COUNT(*) FROM article_links")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(count, Some(0));
+
+        println!("✅ ゴミ箱の物理削除テスト成功");
+        Ok(())
+    }
+}
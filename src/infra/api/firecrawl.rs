@@ -1,55 +1,103 @@
-use anyhow::{Context, Result};
+use crate::infra::metrics::metrics;
+use crate::types::FetchError;
 use async_trait::async_trait;
-use firecrawl_sdk::{document::Document, FirecrawlApp};
+use firecrawl_sdk::{
+    document::Document,
+    scrape::{ScrapeFormats, ScrapeOptions},
+    FirecrawlApp,
+};
 
 /// Firecrawl APIの抽象化プロトコル
 ///
 /// このプロトコルは、実際のFirecrawl APIとモック実装の両方を
 /// 統一的に扱えるようにするためのインターフェースです。
+/// RESTサーバのハンドラから共有できるよう`Send + Sync`を要求します。
 #[async_trait]
-pub trait FirecrawlClient {
+pub trait FirecrawlClient: Send + Sync {
     /// URLをスクレイピングして結果を返す
     ///
     /// # Arguments
     /// * `url` - スクレイピング対象のURL
-    async fn scrape_url(&self, url: &str) -> Result<Document>;
+    async fn scrape_url(&self, url: &str) -> Result<Document, FetchError>;
 }
 
 /// 実際のFirecrawl APIを使用する実装
 pub struct ReqwestFirecrawlClient {
     firecrawl_app: FirecrawlApp,
+    /// マークダウンに加えて元のHTML（`rawHtml`）も取得するかどうか
+    raw_html: bool,
 }
 
 impl ReqwestFirecrawlClient {
     /// デフォルトのFirecrawl設定で新しいクライアントを作成
-    pub fn new() -> Result<Self> {
+    pub fn new() -> Result<Self, FetchError> {
         // NOTE: APIキーはダミーであり、httpの使用もlocalhostを用いるので警告不要
-        let firecrawl_app = FirecrawlApp::new_selfhosted("http://localhost:13002", Some("fc-test"))
-            .context("Firecrawl SDKの初期化に失敗")?;
-
-        Ok(Self { firecrawl_app })
+        Self::new_with_config("http://localhost:13002", Some("fc-test"))
     }
 
     /// カスタム設定でFirecrawlクライアントを作成
-    pub fn new_with_config(base_url: &str, api_key: Option<&str>) -> Result<Self> {
-        let firecrawl_app = FirecrawlApp::new_selfhosted(base_url, api_key)
-            .context("Firecrawl SDKの初期化に失敗")?;
+    pub fn new_with_config(base_url: &str, api_key: Option<&str>) -> Result<Self, FetchError> {
+        let firecrawl_app =
+            FirecrawlApp::new_selfhosted(base_url, api_key).map_err(|e| FetchError::Client {
+                client: "Firecrawl SDK",
+                source: e.to_string().into(),
+            })?;
+
+        Ok(Self {
+            firecrawl_app,
+            raw_html: false,
+        })
+    }
 
-        Ok(Self { firecrawl_app })
+    /// 元のHTMLも取得するかどうかを設定する
+    pub fn with_raw_html(mut self, raw_html: bool) -> Self {
+        self.raw_html = raw_html;
+        self
     }
 }
 
 #[async_trait]
 impl FirecrawlClient for ReqwestFirecrawlClient {
-    async fn scrape_url(&self, url: &str) -> Result<Document> {
+    #[tracing::instrument(skip_all, fields(url = %url))]
+    async fn scrape_url(&self, url: &str) -> Result<Document, FetchError> {
+        let options = self.raw_html.then(|| ScrapeOptions {
+            formats: Some(vec![ScrapeFormats::Markdown, ScrapeFormats::RawHTML]),
+            ..Default::default()
+        });
+        // 所要時間はエラーの場合も含めて記録する（タイマーのドロップ時に記録される）
+        let _timer = metrics().firecrawl_latency.start_timer();
         self.firecrawl_app
-            .scrape_url(url, None)
+            .scrape_url(url, options)
             .await
-            .map_err(|e| anyhow::anyhow!("Firecrawl API エラー: {}", e))
+            .map_err(|e| FetchError::Api {
+                service: "Firecrawl API",
+                message: e.to_string(),
+            })
+    }
+}
+
+/// モックが返すスクレイプ結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockScrapeResult {
+    /// 指定したマークダウンを本文として返す
+    Success(String),
+    /// 指定したメッセージのエラーを返す
+    Error(String),
+}
+
+impl MockScrapeResult {
+    pub fn success(markdown: &str) -> Self {
+        Self::Success(markdown.to_string())
+    }
+
+    pub fn error(message: &str) -> Self {
+        Self::Error(message.to_string())
     }
 }
 
 /// テスト用のモック実装
+///
+/// `with_response`でURL毎の結果を登録でき、未登録のURLには既定の結果を返す。
 pub struct MockFirecrawlClient {
     /// モック時に返すマークダウン内容
     pub mock_content: String,
@@ -57,6 +105,8 @@ pub struct MockFirecrawlClient {
     pub simulate_success: bool,
     /// エラー時に返すメッセージ
     pub error_message: Option<String>,
+    /// URL（末尾`*`は前方一致のパターン）毎に登録した結果
+    responses: Vec<(String, MockScrapeResult)>,
 }
 
 impl MockFirecrawlClient {
@@ -66,6 +116,7 @@ impl MockFirecrawlClient {
             mock_content: mock_content.to_string(),
             simulate_success: true,
             error_message: None,
+            responses: Vec::new(),
         }
     }
 
@@ -75,24 +126,78 @@ impl MockFirecrawlClient {
             mock_content: String::new(),
             simulate_success: false,
             error_message: Some(error_message.to_string()),
+            responses: Vec::new(),
+        }
+    }
+
+    /// URL毎の結果を登録する
+    ///
+    /// `url`の末尾が`*`の場合は前方一致のパターンとして扱う。
+    /// 完全一致を優先し、複数のパターンに一致する場合は最も長いパターンを使う。
+    pub fn with_response(mut self, url: &str, result: MockScrapeResult) -> Self {
+        self.responses.push((url.to_string(), result));
+        self
+    }
+
+    /// 未登録のURLに返す結果を設定する
+    pub fn with_default(mut self, result: MockScrapeResult) -> Self {
+        match result {
+            MockScrapeResult::Success(markdown) => {
+                self.mock_content = markdown;
+                self.simulate_success = true;
+                self.error_message = None;
+            }
+            MockScrapeResult::Error(message) => {
+                self.mock_content = String::new();
+                self.simulate_success = false;
+                self.error_message = Some(message);
+            }
+        }
+        self
+    }
+
+    /// URLに対して返す結果を決める
+    fn resolve(&self, url: &str) -> MockScrapeResult {
+        if let Some((_, result)) = self.responses.iter().find(|(key, _)| key == url) {
+            return result.clone();
+        }
+        let by_pattern = self
+            .responses
+            .iter()
+            .filter_map(|(key, result)| Some((key.strip_suffix('*')?, result)))
+            .filter(|(prefix, _)| url.starts_with(prefix))
+            .max_by_key(|(prefix, _)| prefix.len());
+        if let Some((_, result)) = by_pattern {
+            return result.clone();
+        }
+
+        if self.simulate_success {
+            MockScrapeResult::Success(self.mock_content.clone())
+        } else {
+            MockScrapeResult::Error(
+                self.error_message
+                    .clone()
+                    .unwrap_or_else(|| "Mock error".to_string()),
+            )
         }
     }
 }
 
 #[async_trait]
 impl FirecrawlClient for MockFirecrawlClient {
-    async fn scrape_url(&self, _url: &str) -> Result<Document> {
-        if self.simulate_success {
+    async fn scrape_url(&self, url: &str) -> Result<Document, FetchError> {
+        match self.resolve(url) {
             // 成功時のモックレスポンス
-            Ok(Document {
-                markdown: Some(self.mock_content.clone()),
+            MockScrapeResult::Success(markdown) => Ok(Document {
+                markdown: Some(markdown),
                 // 他のフィールドをデフォルト値で埋める
                 ..Default::default()
-            })
-        } else {
+            }),
             // エラー時のレスポンス
-            let error_msg = self.error_message.as_deref().unwrap_or("Mock error");
-            Err(anyhow::anyhow!("モックエラー: {}", error_msg))
+            MockScrapeResult::Error(message) => Err(FetchError::Unavailable(format!(
+                "モックエラー: {}",
+                message
+            ))),
         }
     }
 }
@@ -125,10 +230,53 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("テストエラー"));
     }
 
+    #[tokio::test]
+    async fn test_mock_client_responses_by_url() {
+        let mock_client = MockFirecrawlClient::new_success("既定の内容")
+            .with_response("https://example.com/a", MockScrapeResult::success("記事A"))
+            .with_response("https://example.com/*", MockScrapeResult::error("403"))
+            .with_response(
+                "https://example.com/ok/*",
+                MockScrapeResult::success("許可された記事"),
+            );
+
+        let markdown = |result: Result<Document, FetchError>| result.unwrap().markdown.unwrap();
+        assert_eq!(
+            markdown(mock_client.scrape_url("https://example.com/a").await),
+            "記事A",
+            "完全一致を優先"
+        );
+        assert_eq!(
+            markdown(mock_client.scrape_url("https://example.com/ok/1").await),
+            "許可された記事",
+            "長いパターンを優先"
+        );
+        assert!(mock_client
+            .scrape_url("https://example.com/b")
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("403"));
+        assert_eq!(
+            markdown(mock_client.scrape_url("https://other.com/").await),
+            "既定の内容"
+        );
+
+        // 未登録URLの挙動を失敗に変更
+        let mock_client = mock_client.with_default(MockScrapeResult::error("未登録"));
+        assert!(mock_client.scrape_url("https://other.com/").await.is_err());
+        assert!(mock_client
+            .scrape_url("https://example.com/a")
+            .await
+            .is_ok());
+    }
+
     /// 軽量オンラインテスト - 実際のFirecrawlAPIへの基本接続確認
     #[cfg(feature = "online")]
     #[tokio::test]
     async fn test_firecrawl_online_basic() -> Result<(), anyhow::Error> {
+        use anyhow::Context;
+
         // httpbin.orgを使った軽量な接続テスト
         let client =
             ReqwestFirecrawlClient::new().context("Firecrawlクライアントの初期化に失敗")?;
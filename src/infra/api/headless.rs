@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[cfg(feature = "headless")]
+use crate::infra::api::readability::extract_markdown;
+#[cfg(feature = "headless")]
+use crate::types::FetchError;
+#[cfg(feature = "headless")]
+use chromiumoxide::browser::{Browser, BrowserConfig};
+#[cfg(feature = "headless")]
+use futures::StreamExt;
+#[cfg(feature = "headless")]
+use std::time::Duration;
+
+// ヘッドレスブラウザの設定（config/app.yamlのscraper.headlessに対応）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeadlessBrowserConfig {
+    /// ページの読み込みのタイムアウト（秒）
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// 読み込み完了後、JavaScriptの描画を待つ時間（ミリ秒）
+    #[serde(default = "default_render_wait_ms")]
+    pub render_wait_ms: u64,
+    /// Chrome/Chromiumの実行ファイル（省略時はPATHなどから探す）
+    pub chrome_executable: Option<PathBuf>,
+}
+
+impl Default for HeadlessBrowserConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: default_timeout_secs(),
+            render_wait_ms: default_render_wait_ms(),
+            chrome_executable: None,
+        }
+    }
+}
+
+fn default_timeout_secs() -> u64 {
+    60
+}
+
+fn default_render_wait_ms() -> u64 {
+    1000
+}
+
+/// ヘッドレスChromium（chromiumoxide）でページを描画し、readabilityで本文を抽出するクライアント
+///
+/// JavaScriptで本文を描画するページをFirecrawl APIを使わずに取得する。
+/// ブラウザは作成時に1つ起動し、ページ毎にタブを開いて閉じる（`headless`フィーチャが必要）。
+#[cfg(feature = "headless")]
+pub struct HeadlessBrowserClient {
+    browser: Browser,
+    handler: tokio::task::JoinHandle<()>,
+    timeout: Duration,
+    render_wait: Duration,
+}
+
+#[cfg(feature = "headless")]
+impl HeadlessBrowserClient {
+    /// 設定を指定してブラウザを起動する
+    pub async fn launch(config: &HeadlessBrowserConfig) -> Result<Self, FetchError> {
+        let timeout = Duration::from_secs(config.timeout_secs);
+        let mut builder = BrowserConfig::builder().request_timeout(timeout);
+        if let Some(path) = &config.chrome_executable {
+            builder = builder.chrome_executable(path);
+        }
+        let browser_config = builder.build().map_err(|e| FetchError::Client {
+            client: "ヘッドレスブラウザ",
+            source: e.into(),
+        })?;
+        let (browser, mut events) =
+            Browser::launch(browser_config)
+                .await
+                .map_err(|e| FetchError::Client {
+                    client: "ヘッドレスブラウザ",
+                    source: e.into(),
+                })?;
+        // ブラウザとのCDP通信はイベントを読み続けている間だけ進む
+        let handler = tokio::spawn(async move {
+            while let Some(event) = events.next().await {
+                if event.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            browser,
+            handler,
+            timeout,
+            render_wait: Duration::from_millis(config.render_wait_ms),
+        })
+    }
+
+    /// 記事ページを描画し、本文をマークダウンで返す
+    pub async fn fetch_markdown(&self, url: &str) -> Result<String, FetchError> {
+        let (markdown, _) = self.fetch_markdown_with_html(url).await?;
+        Ok(markdown)
+    }
+
+    /// 記事ページを描画し、本文のマークダウンと描画後のHTMLを返す
+    #[tracing::instrument(skip_all, fields(url = %url))]
+    pub async fn fetch_markdown_with_html(
+        &self,
+        url: &str,
+    ) -> Result<(String, String), FetchError> {
+        let html = tokio::time::timeout(self.timeout, self.render_html(url))
+            .await
+            .map_err(|_| FetchError::Timeout {
+                url: url.to_string(),
+            })??;
+        let markdown = extract_markdown(&html, url)?;
+        Ok((markdown, html))
+    }
+
+    async fn render_html(&self, url: &str) -> Result<String, FetchError> {
+        let request_error = |e: chromiumoxide::error::CdpError| FetchError::Request {
+            url: url.to_string(),
+            source: e.into(),
+        };
+        let page = self.browser.new_page(url).await.map_err(request_error)?;
+        let html = async {
+            page.wait_for_navigation().await?;
+            tokio::time::sleep(self.render_wait).await;
+            page.content().await
+        }
+        .await
+        .map_err(|e| FetchError::Body {
+            url: url.to_string(),
+            source: e.into(),
+        });
+        if let Err(e) = page.close().await {
+            tracing::warn!(error = %e, "タブを閉じられませんでした");
+        }
+        html
+    }
+}
+
+#[cfg(feature = "headless")]
+impl Drop for HeadlessBrowserClient {
+    fn drop(&mut self) {
+        self.handler.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_headless_browser_config() {
+        let config: HeadlessBrowserConfig =
+            serde_yaml::from_str("timeout_secs: 20\nchrome_executable: /usr/bin/chromium\n")
+                .unwrap();
+        assert_eq!(config.timeout_secs, 20);
+        assert_eq!(config.render_wait_ms, 1000);
+        assert_eq!(
+            config.chrome_executable.as_deref(),
+            Some(std::path::Path::new("/usr/bin/chromium"))
+        );
+    }
+}
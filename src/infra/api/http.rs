@@ -1,33 +1,396 @@
 use crate::infra::compute::generate_mock_rss_id;
-use anyhow::{Context, Result};
+use crate::types::FetchError;
 use async_trait::async_trait;
-use reqwest::Client;
-use std::time::Duration;
+use reqwest::{
+    header::{CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    Client, StatusCode, Version,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 /// HTTPクライアントの抽象化トレイト
 ///
 /// このトレイトは、実際のHTTP通信とモック実装の両方を
 /// 統一的に扱えるようにするためのインターフェースです。
 #[async_trait]
-pub trait HttpClient {
+pub trait HttpClient: Send + Sync {
     /// 指定されたURLからテキストを取得する
     ///
     /// # Arguments
     /// * `url` - 取得対象のURL
     /// * `timeout_secs` - タイムアウト時間（秒）
-    async fn fetch(&self, url: &str, timeout_secs: u64) -> Result<String>;
+    async fn fetch(&self, url: &str, timeout_secs: u64) -> Result<String, FetchError>;
+
+    /// 前回の検証子（ETag / Last-Modified）を付けて条件付きで取得する
+    ///
+    /// `If-None-Match` / `If-Modified-Since`を送信し、304が返された場合は`NotModified`を返す。
+    /// 既定の実装は条件を付けずに`fetch`で取得する（記録・再生用のクライアントは常に本文を扱う）。
+    async fn fetch_conditional(
+        &self,
+        url: &str,
+        timeout_secs: u64,
+        validators: &CacheValidators,
+    ) -> Result<ConditionalFetch, FetchError> {
+        let _ = validators;
+        let body = self.fetch(url, timeout_secs).await?;
+        Ok(ConditionalFetch::Modified {
+            body,
+            validators: CacheValidators::default(),
+        })
+    }
+
+    /// ステータスコード・ヘッダ・リダイレクト後のURLを含めて取得する
+    ///
+    /// 2xx以外のステータスもエラーにせずに返す（404などの判定は呼び出し側で行う）。
+    /// 既定の実装は`fetch`で取得した本文を200として返す（ヘッダは持たない）。
+    async fn fetch_response(
+        &self,
+        url: &str,
+        timeout_secs: u64,
+    ) -> Result<FetchResponse, FetchError> {
+        let body = self.fetch(url, timeout_secs).await?;
+        Ok(FetchResponse {
+            status: StatusCode::OK.as_u16(),
+            headers: HashMap::new(),
+            body,
+            final_url: url.to_string(),
+        })
+    }
+
+    /// 接続プールの統計（統計を持たない実装は`None`）
+    fn pool_stats(&self) -> Option<HttpPoolStats> {
+        None
+    }
+}
+
+// HTTP条件付きリクエストの検証子（前回の応答のETag / Last-Modifiedヘッダ）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    /// 条件を付けられる検証子が無いか
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    fn from_response(response: &FetchResponse) -> Self {
+        Self {
+            etag: response.header(ETAG.as_str()).map(str::to_string),
+            last_modified: response.header(LAST_MODIFIED.as_str()).map(str::to_string),
+        }
+    }
+}
+
+// ステータスコード・ヘッダを含むHTTPの取得結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FetchResponse {
+    pub status: u16,
+    /// 応答ヘッダ（名前は小文字、同じ名前のヘッダは`, `で連結）
+    pub headers: HashMap<String, String>,
+    pub body: String,
+    /// リダイレクトを辿った後のURL
+    pub final_url: String,
+}
+
+impl FetchResponse {
+    /// 2xxのステータスか
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// ヘッダの値（名前の大文字・小文字は区別しない）
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    /// Content-Typeで指定された文字コード（`charset=`、小文字）
+    pub fn charset(&self) -> Option<String> {
+        self.header(CONTENT_TYPE.as_str())?
+            .split(';')
+            .filter_map(|param| param.trim().split_once('='))
+            .find(|(key, _)| key.trim().eq_ignore_ascii_case("charset"))
+            .map(|(_, value)| value.trim().trim_matches('"').to_ascii_lowercase())
+    }
+
+    /// 2xxなら本文を返し、それ以外は`FetchError::Status`にする
+    pub fn into_body(self) -> Result<String, FetchError> {
+        if self.is_success() {
+            Ok(self.body)
+        } else {
+            Err(FetchError::Status {
+                url: self.final_url,
+                status: self.status,
+            })
+        }
+    }
+}
+
+// 条件付きリクエストの結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConditionalFetch {
+    /// 更新されていた（本文と、次回の条件付きリクエストに使う検証子）
+    Modified {
+        body: String,
+        validators: CacheValidators,
+    },
+    /// 304 Not Modified（前回から更新されていない）
+    NotModified,
+}
+
+// HTTPクライアントの接続設定（config/app.yamlのhttpに対応）
+#[derive(Debug, Clone, Deserialize)]
+pub struct HttpClientConfig {
+    /// ホスト毎に保持するアイドル接続数の上限
+    #[serde(default = "default_pool_max_idle_per_host")]
+    pub pool_max_idle_per_host: usize,
+    /// アイドル接続を保持する秒数
+    #[serde(default = "default_pool_idle_timeout_secs")]
+    pub pool_idle_timeout_secs: u64,
+    /// TCP keep-aliveの送信間隔（秒、省略時は送信しない）
+    pub tcp_keepalive_secs: Option<u64>,
+    /// HTTP/2のPINGによるkeep-aliveの送信間隔（秒、省略時は送信しない）
+    pub http2_keep_alive_interval_secs: Option<u64>,
+    /// HTTP/2のフロー制御ウィンドウを帯域に合わせて自動調整する
+    #[serde(default = "default_true")]
+    pub http2_adaptive_window: bool,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            pool_max_idle_per_host: default_pool_max_idle_per_host(),
+            pool_idle_timeout_secs: default_pool_idle_timeout_secs(),
+            tcp_keepalive_secs: Some(60),
+            http2_keep_alive_interval_secs: Some(30),
+            http2_adaptive_window: true,
+        }
+    }
+}
+
+fn default_pool_max_idle_per_host() -> usize {
+    8
+}
+
+fn default_pool_idle_timeout_secs() -> u64 {
+    90
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// 接続プールの統計
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct HttpPoolStats {
+    /// 送信したリクエスト数
+    pub requests: u64,
+    /// 送信・受信に失敗したリクエスト数
+    pub failures: u64,
+    /// HTTP/2で応答したリクエスト数
+    pub http2_responses: u64,
+    /// 接続したホスト数（ホスト毎に接続プールが作られる）
+    pub hosts: usize,
+    /// 同一ホストへの2回目以降のリクエスト数（プール内の確立済み接続を再利用できる）
+    pub reusable_requests: u64,
+    /// 応答までの時間の合計
+    pub total_latency: Duration,
+}
+
+impl HttpPoolStats {
+    /// 応答までの平均時間
+    pub fn average_latency(&self) -> Duration {
+        match u32::try_from(self.requests) {
+            Ok(requests) if requests > 0 => self.total_latency / requests,
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// 表示用の行に整形する
+    pub fn format_lines(&self) -> Vec<String> {
+        vec![
+            format!(
+                "HTTPリクエスト: {}件（失敗{}件、HTTP/2 {}件）",
+                self.requests, self.failures, self.http2_responses
+            ),
+            format!(
+                "接続プール: {}ホスト、接続再利用可能なリクエスト{}件",
+                self.hosts, self.reusable_requests
+            ),
+            format!("平均レイテンシ: {}ms", self.average_latency().as_millis()),
+        ]
+    }
+}
+
+// 接続プールの統計の集計
+#[derive(Debug, Default)]
+struct PoolStatsRecorder {
+    stats: HttpPoolStats,
+    host_requests: HashMap<String, u64>,
+}
+
+impl PoolStatsRecorder {
+    fn record(&mut self, url: &str, result: Option<Version>, latency: Duration) {
+        let host = url::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_default();
+        let count = self.host_requests.entry(host).or_default();
+        if *count > 0 {
+            self.stats.reusable_requests += 1;
+        }
+        *count += 1;
+
+        self.stats.requests += 1;
+        self.stats.hosts = self.host_requests.len();
+        self.stats.total_latency += latency;
+        match result {
+            Some(Version::HTTP_2) => self.stats.http2_responses += 1,
+            Some(_) => {}
+            None => self.stats.failures += 1,
+        }
+    }
 }
 
 /// `reqwest` を使用した本番用のHTTPクライアント実装
+///
+/// 1つのクライアントを使い回すことで、ホスト毎の接続（TLSセッションを含む）を再利用する。
+/// HTTP/2に対応したホストとはALPNでHTTP/2を選択し、1接続上でリクエストを多重化する。
 pub struct ReqwestHttpClient {
     client: Client,
+    stats: Arc<Mutex<PoolStatsRecorder>>,
 }
 
 impl ReqwestHttpClient {
-    /// 新しいHTTPクライアントを作成
+    /// 既定の接続設定でHTTPクライアントを作成
     pub fn new() -> Self {
-        Self {
-            client: Client::new(),
+        Self::with_config(&HttpClientConfig::default())
+            .expect("既定の設定でHTTPクライアントの作成に失敗")
+    }
+
+    /// 接続設定を指定してHTTPクライアントを作成
+    pub fn with_config(config: &HttpClientConfig) -> Result<Self, FetchError> {
+        let mut builder = Client::builder()
+            .pool_max_idle_per_host(config.pool_max_idle_per_host)
+            .pool_idle_timeout(Duration::from_secs(config.pool_idle_timeout_secs))
+            .tcp_keepalive(config.tcp_keepalive_secs.map(Duration::from_secs))
+            .http2_adaptive_window(config.http2_adaptive_window);
+        if let Some(interval) = config.http2_keep_alive_interval_secs {
+            builder = builder
+                .http2_keep_alive_interval(Duration::from_secs(interval))
+                .http2_keep_alive_while_idle(true);
+        }
+        let client = builder.build().map_err(|e| FetchError::Client {
+            client: "HTTPクライアント",
+            source: e.into(),
+        })?;
+
+        Ok(Self {
+            client,
+            stats: Arc::default(),
+        })
+    }
+
+    async fn send(
+        &self,
+        url: &str,
+        timeout_secs: u64,
+        validators: &CacheValidators,
+    ) -> Result<(Version, FetchResponse), FetchError> {
+        let mut request = self
+            .client
+            .get(url)
+            .timeout(Duration::from_secs(timeout_secs));
+        if let Some(etag) = &validators.etag {
+            request = request.header(IF_NONE_MATCH, etag.as_str());
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified.as_str());
+        }
+        let response = request.send().await.map_err(|e| request_error(url, e))?;
+        let version = response.version();
+        let status = response.status().as_u16();
+        let final_url = response.url().to_string();
+        let mut headers: HashMap<String, String> = HashMap::new();
+        for (name, value) in response.headers() {
+            let Ok(value) = value.to_str() else {
+                continue;
+            };
+            headers
+                .entry(name.as_str().to_string())
+                .and_modify(|joined| {
+                    joined.push_str(", ");
+                    joined.push_str(value);
+                })
+                .or_insert_with(|| value.to_string());
+        }
+        if status == StatusCode::NOT_MODIFIED.as_u16() {
+            return Ok((
+                version,
+                FetchResponse {
+                    status,
+                    headers,
+                    body: String::new(),
+                    final_url,
+                },
+            ));
+        }
+
+        // 本文の文字コードはContent-Typeのcharsetに従って変換される（無ければUTF-8）
+        let body = response.text().await.map_err(|e| FetchError::Body {
+            url: url.to_string(),
+            source: e.into(),
+        })?;
+        Ok((
+            version,
+            FetchResponse {
+                status,
+                headers,
+                body,
+                final_url,
+            },
+        ))
+    }
+
+    /// 送信して接続プールの統計に記録する
+    async fn send_recorded(
+        &self,
+        url: &str,
+        timeout_secs: u64,
+        validators: &CacheValidators,
+    ) -> Result<FetchResponse, FetchError> {
+        let started = Instant::now();
+        let result = self.send(url, timeout_secs, validators).await;
+
+        let version = result.as_ref().ok().map(|(version, _)| *version);
+        if let Some(version) = version {
+            tracing::Span::current().record("http.version", tracing::field::debug(version));
+        }
+        self.stats
+            .lock()
+            .expect("接続プール統計のロックに失敗")
+            .record(url, version, started.elapsed());
+
+        result.map(|(_, response)| response)
+    }
+}
+
+/// reqwestの送信エラーを種類毎の`FetchError`に変換する
+pub(crate) fn request_error(url: &str, error: reqwest::Error) -> FetchError {
+    if error.is_timeout() {
+        FetchError::Timeout {
+            url: url.to_string(),
+        }
+    } else {
+        FetchError::Request {
+            url: url.to_string(),
+            source: error.into(),
         }
     }
 }
@@ -40,19 +403,68 @@ impl Default for ReqwestHttpClient {
 
 #[async_trait]
 impl HttpClient for ReqwestHttpClient {
-    async fn fetch(&self, url: &str, timeout_secs: u64) -> Result<String> {
+    async fn fetch(&self, url: &str, timeout_secs: u64) -> Result<String, FetchError> {
+        match self
+            .fetch_conditional(url, timeout_secs, &CacheValidators::default())
+            .await?
+        {
+            ConditionalFetch::Modified { body, .. } => Ok(body),
+            // 検証子を付けずに304が返るのはサーバの誤り
+            ConditionalFetch::NotModified => Err(FetchError::Status {
+                url: url.to_string(),
+                status: StatusCode::NOT_MODIFIED.as_u16(),
+            }),
+        }
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(url = %url, http.version = tracing::field::Empty, http.not_modified = tracing::field::Empty)
+    )]
+    async fn fetch_conditional(
+        &self,
+        url: &str,
+        timeout_secs: u64,
+        validators: &CacheValidators,
+    ) -> Result<ConditionalFetch, FetchError> {
+        let response = self.send_recorded(url, timeout_secs, validators).await?;
+        let not_modified = response.status == StatusCode::NOT_MODIFIED.as_u16();
+        tracing::Span::current().record("http.not_modified", not_modified);
+        if not_modified {
+            return Ok(ConditionalFetch::NotModified);
+        }
+
+        let validators = CacheValidators::from_response(&response);
+        Ok(ConditionalFetch::Modified {
+            body: response.into_body()?,
+            validators,
+        })
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(url = %url, http.version = tracing::field::Empty, http.status = tracing::field::Empty)
+    )]
+    async fn fetch_response(
+        &self,
+        url: &str,
+        timeout_secs: u64,
+    ) -> Result<FetchResponse, FetchError> {
         let response = self
-            .client
-            .get(url)
-            .timeout(Duration::from_secs(timeout_secs))
-            .send()
-            .await
-            .context(format!("HTTPリクエストの送信に失敗: {}", url))?;
+            .send_recorded(url, timeout_secs, &CacheValidators::default())
+            .await?;
+        tracing::Span::current().record("http.status", response.status);
+        Ok(response)
+    }
 
-        response
-            .text()
-            .await
-            .context("レスポンステキストの取得に失敗")
+    fn pool_stats(&self) -> Option<HttpPoolStats> {
+        Some(
+            self.stats
+                .lock()
+                .expect("接続プール統計のロックに失敗")
+                .stats
+                .clone(),
+        )
     }
 }
 
@@ -65,6 +477,10 @@ pub struct MockHttpClient {
     pub simulate_success: bool,
     /// エラー時に返すメッセージ
     pub error_message: Option<String>,
+    /// 成功時でもエラーを返すURL（フォールバックの確認用）
+    pub failing_urls: Vec<String>,
+    /// 検証子付きで取得すると304 Not Modifiedを返すURL（条件付きリクエストの確認用）
+    pub not_modified_urls: Vec<String>,
 }
 
 impl MockHttpClient {
@@ -73,6 +489,8 @@ impl MockHttpClient {
         Self {
             simulate_success: true,
             error_message: None,
+            failing_urls: Vec::new(),
+            not_modified_urls: Vec::new(),
         }
     }
 
@@ -81,17 +499,40 @@ impl MockHttpClient {
         Self {
             simulate_success: false,
             error_message: Some(error_message.to_string()),
+            failing_urls: Vec::new(),
+            not_modified_urls: Vec::new(),
         }
     }
+
+    /// 指定したURLのみエラーを返すようにする
+    pub fn with_failing_url(mut self, url: &str) -> Self {
+        self.failing_urls.push(url.to_string());
+        self
+    }
+
+    /// 指定したURLは検証子付きの取得で304 Not Modifiedを返すようにする
+    pub fn with_not_modified_url(mut self, url: &str) -> Self {
+        self.not_modified_urls.push(url.to_string());
+        self
+    }
 }
 
 #[async_trait]
 impl HttpClient for MockHttpClient {
-    async fn fetch(&self, url: &str, _timeout_secs: u64) -> Result<String> {
+    async fn fetch(&self, url: &str, _timeout_secs: u64) -> Result<String, FetchError> {
         if !self.simulate_success {
             // エラー時のレスポンス
             let error_msg = self.error_message.as_deref().unwrap_or("Mock HTTP error");
-            return Err(anyhow::anyhow!("モックHTTPエラー: {}", error_msg));
+            return Err(FetchError::Unavailable(format!(
+                "モックHTTPエラー: {}",
+                error_msg
+            )));
+        }
+        if self.failing_urls.iter().any(|failing| failing == url) {
+            return Err(FetchError::Unavailable(format!(
+                "モックHTTPエラー: {}",
+                url
+            )));
         }
 
         // URL依存の動的XML生成
@@ -128,6 +569,26 @@ impl HttpClient for MockHttpClient {
             hash, hash, hash, today, hash, hash, yesterday, hash, hash, day_before
         ))
     }
+
+    async fn fetch_conditional(
+        &self,
+        url: &str,
+        timeout_secs: u64,
+        validators: &CacheValidators,
+    ) -> Result<ConditionalFetch, FetchError> {
+        if !validators.is_empty() && self.not_modified_urls.iter().any(|u| u == url) {
+            return Ok(ConditionalFetch::NotModified);
+        }
+        let body = self.fetch(url, timeout_secs).await?;
+        // URL毎に一定のETagを返す
+        Ok(ConditionalFetch::Modified {
+            body,
+            validators: CacheValidators {
+                etag: Some(format!("\"{}\"", generate_mock_rss_id(url))),
+                last_modified: None,
+            },
+        })
+    }
 }
 
 #[cfg(test)]
@@ -159,6 +620,165 @@ mod tests {
         assert!(response.contains(&format!("{}:title:1", hash)));
     }
 
+    #[test]
+    fn test_pool_stats_recorder() {
+        let mut recorder = PoolStatsRecorder::default();
+        let ms = Duration::from_millis;
+        recorder.record("https://a.example.com/rss", Some(Version::HTTP_2), ms(30));
+        recorder.record("https://a.example.com/atom", Some(Version::HTTP_2), ms(10));
+        recorder.record("https://b.example.com/rss", Some(Version::HTTP_11), ms(20));
+        recorder.record("https://b.example.com/rss", None, ms(100));
+
+        let stats = recorder.stats;
+        assert_eq!(stats.requests, 4);
+        assert_eq!(stats.failures, 1);
+        assert_eq!(stats.http2_responses, 2);
+        assert_eq!(stats.hosts, 2);
+        assert_eq!(stats.reusable_requests, 2, "各ホストの初回以外");
+        assert_eq!(stats.average_latency(), ms(40));
+    }
+
+    #[tokio::test]
+    async fn test_reqwest_http_client_pool_stats() {
+        let server = httpmock::MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(httpmock::Method::GET).path("/rss");
+                then.status(200).body("<rss></rss>");
+            })
+            .await;
+
+        let client = ReqwestHttpClient::with_config(&HttpClientConfig::default()).unwrap();
+        for _ in 0..3 {
+            let body = client.fetch(&server.url("/rss"), 5).await.unwrap();
+            assert_eq!(body, "<rss></rss>");
+        }
+        mock.assert_hits_async(3).await;
+
+        let stats = client.pool_stats().unwrap();
+        assert_eq!(stats.requests, 3);
+        assert_eq!(stats.hosts, 1);
+        assert_eq!(stats.reusable_requests, 2);
+        assert!(MockHttpClient::new_success().pool_stats().is_none());
+
+        println!("✅ 接続プール統計テスト成功");
+    }
+
+    #[tokio::test]
+    async fn test_reqwest_http_client_conditional_request() {
+        let server = httpmock::MockServer::start_async().await;
+        let not_modified = server
+            .mock_async(|when, then| {
+                when.method(httpmock::Method::GET)
+                    .path("/rss")
+                    .header("if-none-match", "\"v1\"");
+                then.status(304);
+            })
+            .await;
+        let full = server
+            .mock_async(|when, then| {
+                when.method(httpmock::Method::GET)
+                    .path("/rss")
+                    .matches(|req| {
+                        !req.headers
+                            .iter()
+                            .flatten()
+                            .any(|(name, _)| name.eq_ignore_ascii_case("if-none-match"))
+                    });
+                then.status(200)
+                    .header("ETag", "\"v1\"")
+                    .header("Last-Modified", "Wed, 01 Oct 2025 00:00:00 GMT")
+                    .body("<rss></rss>");
+            })
+            .await;
+
+        let client = ReqwestHttpClient::new();
+        let url = server.url("/rss");
+        let fetched = client
+            .fetch_conditional(&url, 5, &CacheValidators::default())
+            .await
+            .unwrap();
+        let validators = CacheValidators {
+            etag: Some("\"v1\"".to_string()),
+            last_modified: Some("Wed, 01 Oct 2025 00:00:00 GMT".to_string()),
+        };
+        assert_eq!(
+            fetched,
+            ConditionalFetch::Modified {
+                body: "<rss></rss>".to_string(),
+                validators: validators.clone(),
+            }
+        );
+
+        // 前回の検証子を送ると304で本文を受け取らない
+        let fetched = client
+            .fetch_conditional(&url, 5, &validators)
+            .await
+            .unwrap();
+        assert_eq!(fetched, ConditionalFetch::NotModified);
+        full.assert_hits_async(1).await;
+        not_modified.assert_hits_async(1).await;
+    }
+
+    #[tokio::test]
+    async fn test_reqwest_http_client_fetch_response() {
+        let server = httpmock::MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(httpmock::Method::GET).path("/old");
+                then.status(301).header("Location", "/rss");
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(httpmock::Method::GET).path("/rss");
+                then.status(200)
+                    .header("Content-Type", "application/rss+xml; charset=\"UTF-8\"")
+                    .body("<rss></rss>");
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(httpmock::Method::GET).path("/missing");
+                then.status(404).body("not found");
+            })
+            .await;
+
+        let client = ReqwestHttpClient::new();
+        let response = client.fetch_response(&server.url("/old"), 5).await.unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(
+            response.final_url,
+            server.url("/rss"),
+            "リダイレクト後のURL"
+        );
+        assert_eq!(response.charset().as_deref(), Some("utf-8"));
+        assert!(response.header("CONTENT-TYPE").is_some());
+        assert_eq!(response.body, "<rss></rss>");
+
+        // 404もエラーにせずに返し、本文の取得では`FetchError::Status`になる
+        let response = client
+            .fetch_response(&server.url("/missing"), 5)
+            .await
+            .unwrap();
+        assert_eq!(response.status, 404);
+        assert!(!response.is_success());
+        assert_eq!(response.charset(), None);
+        assert!(matches!(
+            client.fetch(&server.url("/missing"), 5).await,
+            Err(FetchError::Status { status: 404, .. })
+        ));
+
+        // 既定の実装は`fetch`の本文を200として返す
+        let response = MockHttpClient::new_success()
+            .fetch_response("https://example.com/rss", 5)
+            .await
+            .unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.final_url, "https://example.com/rss");
+        println!("✅ HTTPレスポンス取得テスト成功");
+    }
+
     #[tokio::test]
     async fn test_mock_http_client_error() {
         let mock_client = MockHttpClient::new_error("接続失敗");
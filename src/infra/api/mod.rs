@@ -1,2 +1,7 @@
 pub mod firecrawl;
+pub mod headless;
 pub mod http;
+pub mod rate_limit;
+pub mod readability;
+pub mod robots;
+pub mod vcr;
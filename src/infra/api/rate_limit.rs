@@ -0,0 +1,66 @@
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// 外部APIへのリクエストを一定間隔に制限するレートリミッタ
+///
+/// リクエスト毎に送出時刻の枠を予約し、枠は`60秒 / requests_per_minute`の間隔で割り当てる。
+/// 予約した時刻まで待ってから送出することで、並列に取得していても上限を超えない。
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    interval: Duration,
+    next_slot: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// 1分あたりのリクエスト数の上限を指定して作成する（0は1として扱う）
+    pub fn per_minute(requests_per_minute: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(60) / requests_per_minute.max(1),
+            next_slot: None,
+        }
+    }
+
+    /// リクエストの間隔
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// 次の送出枠を予約し、送出してよい時刻を返す
+    pub fn reserve(&mut self) -> Instant {
+        let now = Instant::now();
+        let slot = match self.next_slot {
+            Some(next) if next > now => next,
+            _ => now,
+        };
+        self.next_slot = Some(slot + self.interval);
+        slot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limiter_reserve() {
+        let mut limiter = RateLimiter::per_minute(120);
+        assert_eq!(limiter.interval(), Duration::from_millis(500));
+
+        let start = Instant::now();
+        let slots: Vec<Duration> = (0..3).map(|_| limiter.reserve() - start).collect();
+        assert_eq!(
+            slots,
+            vec![
+                Duration::ZERO,
+                Duration::from_millis(500),
+                Duration::from_secs(1)
+            ]
+        );
+
+        // 間隔以上空いた後は即座に送出できる（空いた分をまとめて使わない）
+        tokio::time::advance(Duration::from_secs(10)).await;
+        let now = Instant::now();
+        assert_eq!(limiter.reserve(), now);
+        assert_eq!(limiter.reserve(), now + Duration::from_millis(500));
+    }
+}
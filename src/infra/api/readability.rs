@@ -0,0 +1,175 @@
+use crate::infra::api::http::request_error;
+use crate::types::{FetchError, ParseError};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use url::Url;
+
+// ローカルスクレイパーの設定（config/app.yamlのscraper.localに対応）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadabilityClientConfig {
+    /// 記事ページの取得のタイムアウト（秒）
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// リクエストに付けるUser-Agent
+    #[serde(default = "default_user_agent")]
+    pub user_agent: String,
+}
+
+impl Default for ReadabilityClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: default_timeout_secs(),
+            user_agent: default_user_agent(),
+        }
+    }
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+fn default_user_agent() -> String {
+    concat!("datadoggo/", env!("CARGO_PKG_VERSION")).to_string()
+}
+
+/// 記事ページのHTMLを`reqwest`で取得し、readabilityで本文を抽出するクライアント
+///
+/// Firecrawl APIを使わずに記事本文を取得する。JavaScriptで描画されるページには対応しない。
+pub struct ReadabilityClient {
+    client: Client,
+}
+
+impl ReadabilityClient {
+    /// 既定の設定でクライアントを作成
+    pub fn new() -> Result<Self, FetchError> {
+        Self::with_config(&ReadabilityClientConfig::default())
+    }
+
+    /// 設定を指定してクライアントを作成
+    pub fn with_config(config: &ReadabilityClientConfig) -> Result<Self, FetchError> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .user_agent(config.user_agent.as_str())
+            .build()
+            .map_err(|e| FetchError::Client {
+                client: "ローカルスクレイパーのHTTPクライアント",
+                source: e.into(),
+            })?;
+
+        Ok(Self { client })
+    }
+
+    /// 記事ページを取得し、本文をマークダウンで返す
+    ///
+    /// 2xx以外のステータスはエラーとし、エラーメッセージにステータスを含める。
+    pub async fn fetch_markdown(&self, url: &str) -> Result<String, FetchError> {
+        let (markdown, _) = self.fetch_markdown_with_html(url).await?;
+        Ok(markdown)
+    }
+
+    /// 記事ページを取得し、本文のマークダウンと元のHTMLを返す
+    #[tracing::instrument(skip_all, fields(url = %url))]
+    pub async fn fetch_markdown_with_html(
+        &self,
+        url: &str,
+    ) -> Result<(String, String), FetchError> {
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| request_error(url, e))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(FetchError::Status {
+                url: url.to_string(),
+                status: status.as_u16(),
+            });
+        }
+
+        let html = response.text().await.map_err(|e| FetchError::Body {
+            url: url.to_string(),
+            source: e.into(),
+        })?;
+        let markdown = extract_markdown(&html, url)?;
+        Ok((markdown, html))
+    }
+}
+
+/// # 概要
+/// HTMLからreadabilityで本文を抽出し、マークダウンに変換する。
+///
+/// `url`は本文候補の判定と相対パスの解決に使う。本文が抽出できない場合はエラーを返す。
+pub fn extract_markdown(html: &str, url: &str) -> Result<String, ParseError> {
+    let base = Url::parse(url).map_err(|_| ParseError::Url(url.to_string()))?;
+    let product = ::readability::extractor::extract(&mut html.as_bytes(), &base)
+        .map_err(|e| ParseError::Html(e.to_string()))?;
+
+    let markdown = html2md::parse_html(&product.content).trim().to_string();
+    if markdown.is_empty() {
+        return Err(ParseError::EmptyContent);
+    }
+    Ok(markdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+
+    const ARTICLE_HTML: &str = r#"
+<html>
+  <head><title>新しいコンパイラを公開</title></head>
+  <body>
+    <nav><a href="/">ホーム</a> <a href="/news">ニュース</a></nav>
+    <article>
+      <h1>新しいコンパイラを公開</h1>
+      <p>プロジェクトは本日、新しいコンパイラのバージョンを公開した。ビルド時間が大幅に短縮され、
+      エラーメッセージも読みやすくなったという。開発チームによると、今回のリリースでは
+      数百件の不具合が修正されている。</p>
+      <p>詳細は<a href="/release-notes">リリースノート</a>で確認できる。移行に必要な作業は少なく、
+      既存のプロジェクトの多くはそのままビルドできる見込みだ。</p>
+    </article>
+    <footer>Copyright</footer>
+  </body>
+</html>
+"#;
+
+    #[test]
+    fn test_extract_markdown() {
+        let markdown = extract_markdown(ARTICLE_HTML, "https://example.com/news/1").unwrap();
+
+        assert!(markdown.contains("ビルド時間が大幅に短縮され"));
+        assert!(!markdown.contains("Copyright"), "本文以外は除く");
+        assert!(extract_markdown(ARTICLE_HTML, "not a url").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_markdown() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/news/1");
+            then.status(200)
+                .header("content-type", "text/html; charset=utf-8")
+                .body(ARTICLE_HTML);
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/missing");
+            then.status(404);
+        });
+        let client = ReadabilityClient::new().unwrap();
+
+        let markdown = client.fetch_markdown(&server.url("/news/1")).await.unwrap();
+        assert!(markdown.contains("新しいコンパイラ"));
+
+        let error = client
+            .fetch_markdown(&server.url("/missing"))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, FetchError::Status { status: 404, .. }));
+        assert!(error.to_string().contains("404"));
+
+        println!("✅ ローカルスクレイパー取得テスト成功");
+    }
+}
@@ -0,0 +1,322 @@
+use crate::infra::api::http::HttpClient;
+use crate::types::FetchError;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use url::Url;
+
+// robots.txtの尊重の設定
+#[derive(Debug, Clone, Deserialize)]
+pub struct RobotsConfig {
+    /// robots.txtで禁止されたURLを取得しない
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// 判定に使うUser-agent（robots.txtのグループはこの名前を含むものを優先する）
+    #[serde(default = "default_user_agent")]
+    pub user_agent: String,
+    /// robots.txtの取得のタイムアウト（秒）
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// 取得したrobots.txtを再利用する秒数
+    #[serde(default = "default_cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
+}
+
+impl Default for RobotsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            user_agent: default_user_agent(),
+            timeout_secs: default_timeout_secs(),
+            cache_ttl_secs: default_cache_ttl_secs(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_user_agent() -> String {
+    "datadoggo".to_string()
+}
+
+fn default_timeout_secs() -> u64 {
+    10
+}
+
+fn default_cache_ttl_secs() -> u64 {
+    24 * 60 * 60
+}
+
+// robots.txtの1行のルール
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RobotsRule {
+    allow: bool,
+    /// パスのパターン（`*`は任意の文字列、末尾の`$`は終端）
+    pattern: String,
+}
+
+impl RobotsRule {
+    fn matches(&self, path: &str) -> bool {
+        match self.pattern.strip_suffix('$') {
+            Some(pattern) => wildcard_match(pattern.as_bytes(), path.as_bytes(), true),
+            None => wildcard_match(self.pattern.as_bytes(), path.as_bytes(), false),
+        }
+    }
+}
+
+/// `*`を含むパターンがパスの先頭から一致するか（`anchored`なら末尾まで一致する必要がある）
+fn wildcard_match(pattern: &[u8], path: &[u8], anchored: bool) -> bool {
+    match pattern.split_first() {
+        None => !anchored || path.is_empty(),
+        Some((b'*', rest)) => (0..=path.len()).any(|i| wildcard_match(rest, &path[i..], anchored)),
+        Some((c, rest)) => path
+            .split_first()
+            .is_some_and(|(p, path)| p == c && wildcard_match(rest, path, anchored)),
+    }
+}
+
+/// # 概要
+/// 1つのホストのrobots.txtのうち、自分のUser-agentに適用されるルール。
+///
+/// User-agentの名前を含むグループがあればそれを、無ければ`*`のグループを使う。
+/// 一致するルールのうちパターンが最も長いものを採用し、同じ長さならAllowを優先する。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RobotsRules {
+    rules: Vec<RobotsRule>,
+}
+
+impl RobotsRules {
+    /// すべてのURLを許可するルール（robots.txtが無い場合）
+    pub fn allow_all() -> Self {
+        Self::default()
+    }
+
+    /// robots.txtを解析して`user_agent`に適用されるルールを取り出す
+    pub fn parse(text: &str, user_agent: &str) -> Self {
+        let user_agent = user_agent.to_lowercase();
+        // (グループのUser-agent, ルール)
+        let mut groups: Vec<(Vec<String>, Vec<RobotsRule>)> = Vec::new();
+        let mut in_agent_lines = false;
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim().to_lowercase().as_str() {
+                "user-agent" => {
+                    if !in_agent_lines {
+                        groups.push((Vec::new(), Vec::new()));
+                        in_agent_lines = true;
+                    }
+                    if let Some((agents, _)) = groups.last_mut() {
+                        agents.push(value.to_lowercase());
+                    }
+                }
+                key @ ("allow" | "disallow") => {
+                    in_agent_lines = false;
+                    // 空のDisallowはすべて許可（ルールなし）
+                    if value.is_empty() {
+                        continue;
+                    }
+                    if let Some((_, rules)) = groups.last_mut() {
+                        rules.push(RobotsRule {
+                            allow: key == "allow",
+                            pattern: value.to_string(),
+                        });
+                    }
+                }
+                _ => in_agent_lines = false,
+            }
+        }
+
+        let matches_agent = |agent: &String| agent != "*" && user_agent.contains(agent.as_str());
+        let specific = groups
+            .iter()
+            .any(|(agents, _)| agents.iter().any(matches_agent));
+        let rules = groups
+            .into_iter()
+            .filter(|(agents, _)| {
+                if specific {
+                    agents.iter().any(matches_agent)
+                } else {
+                    agents.iter().any(|agent| agent == "*")
+                }
+            })
+            .flat_map(|(_, rules)| rules)
+            .collect();
+        Self { rules }
+    }
+
+    /// パス（クエリを含む）の取得が許可されているか
+    pub fn is_allowed(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|rule| rule.matches(path))
+            .max_by_key(|rule| (rule.pattern.len(), rule.allow))
+            .is_none_or(|rule| rule.allow)
+    }
+}
+
+/// # 概要
+/// robots.txtをホスト毎に取得・キャッシュし、URLの取得が禁止されていないか判定する。
+///
+/// robots.txtが404などで取得できない場合はすべて許可として扱う。
+/// 通信エラーなどの一時的な失敗も許可として扱い（取得を止めない）、キャッシュはしない。
+pub struct RobotsChecker {
+    client: Arc<dyn HttpClient>,
+    config: RobotsConfig,
+    cache: Mutex<HashMap<String, (Instant, Arc<RobotsRules>)>>,
+}
+
+impl RobotsChecker {
+    pub fn new(client: Arc<dyn HttpClient>, config: &RobotsConfig) -> Self {
+        Self {
+            client,
+            config: config.clone(),
+            cache: Mutex::default(),
+        }
+    }
+
+    /// URLの取得がrobots.txtで許可されているか（http(s)以外のURLは判定せず許可）
+    pub async fn is_allowed(&self, url: &str) -> bool {
+        let Ok(parsed) = Url::parse(url) else {
+            return true;
+        };
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return true;
+        }
+        let path = match parsed.query() {
+            Some(query) => format!("{}?{}", parsed.path(), query),
+            None => parsed.path().to_string(),
+        };
+        self.rules_for(&parsed.origin().ascii_serialization())
+            .await
+            .is_allowed(&path)
+    }
+
+    /// ホスト（オリジン）のルールを返す（期限内のキャッシュが無ければrobots.txtを取得する）
+    async fn rules_for(&self, origin: &str) -> Arc<RobotsRules> {
+        if let Some((fetched_at, rules)) = self.lock().get(origin) {
+            if fetched_at.elapsed() < Duration::from_secs(self.config.cache_ttl_secs) {
+                return rules.clone();
+            }
+        }
+
+        let robots_url = format!("{}/robots.txt", origin);
+        let rules = match self
+            .client
+            .fetch(&robots_url, self.config.timeout_secs)
+            .await
+        {
+            Ok(text) => RobotsRules::parse(&text, &self.config.user_agent),
+            Err(FetchError::Status { status, .. }) if (400..500).contains(&status) => {
+                RobotsRules::allow_all()
+            }
+            Err(e) => {
+                tracing::warn!(url = robots_url, error = %e, "robots.txtの取得に失敗");
+                return Arc::new(RobotsRules::allow_all());
+            }
+        };
+        let rules = Arc::new(rules);
+        self.lock()
+            .insert(origin.to_string(), (Instant::now(), rules.clone()));
+        rules
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Instant, Arc<RobotsRules>)>> {
+        self.cache
+            .lock()
+            .expect("robots.txtキャッシュのロックに失敗")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const ROBOTS_TXT: &str = "\
+# コメント
+User-agent: *
+Disallow: /private/
+Allow: /private/public
+Disallow: /*.pdf$
+
+User-agent: Googlebot
+User-agent: datadoggo
+Disallow: /news/draft
+";
+
+    // 固定のrobots.txtを返し、取得回数を数えるクライアント
+    struct RobotsTxtClient {
+        body: Option<&'static str>,
+        fetches: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl HttpClient for RobotsTxtClient {
+        async fn fetch(&self, url: &str, _timeout_secs: u64) -> Result<String, FetchError> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            self.body.map(str::to_string).ok_or(FetchError::Status {
+                url: url.to_string(),
+                status: 404,
+            })
+        }
+    }
+
+    #[test]
+    fn test_parse_robots_rules() {
+        let generic = RobotsRules::parse(ROBOTS_TXT, "OtherBot/1.0");
+        assert!(!generic.is_allowed("/private/secret"));
+        assert!(
+            generic.is_allowed("/private/public/page"),
+            "長い方のAllowを優先"
+        );
+        assert!(!generic.is_allowed("/files/report.pdf"));
+        assert!(
+            generic.is_allowed("/files/report.pdf?download=1"),
+            "$は終端のみ一致"
+        );
+        assert!(generic.is_allowed("/news/draft/1"));
+
+        // 名前の一致するグループがあれば*のグループは使わない
+        let specific = RobotsRules::parse(ROBOTS_TXT, "datadoggo/0.1");
+        assert!(!specific.is_allowed("/news/draft/1"));
+        assert!(specific.is_allowed("/private/secret"));
+
+        assert!(RobotsRules::parse("User-agent: *\nDisallow:\n", "datadoggo").is_allowed("/"));
+    }
+
+    #[tokio::test]
+    async fn test_robots_checker_caches_rules() {
+        let client = Arc::new(RobotsTxtClient {
+            body: Some(ROBOTS_TXT),
+            fetches: AtomicUsize::new(0),
+        });
+        let checker = RobotsChecker::new(client.clone(), &RobotsConfig::default());
+
+        assert!(!checker.is_allowed("https://example.com/news/draft/1").await);
+        assert!(checker.is_allowed("https://example.com/news/1").await);
+        assert!(checker.is_allowed("https://example.com:8080/news/1").await);
+        assert_eq!(
+            client.fetches.load(Ordering::SeqCst),
+            2,
+            "オリジン毎に1回だけ取得"
+        );
+
+        // robots.txtが無いホストはすべて許可
+        let client = Arc::new(RobotsTxtClient {
+            body: None,
+            fetches: AtomicUsize::new(0),
+        });
+        let checker = RobotsChecker::new(client, &RobotsConfig::default());
+        assert!(checker.is_allowed("https://example.com/news/draft/1").await);
+        println!("✅ robots.txt判定テスト成功");
+    }
+}
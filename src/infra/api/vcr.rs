@@ -0,0 +1,398 @@
+use super::{
+    firecrawl::FirecrawlClient,
+    http::{HttpClient, HttpPoolStats},
+};
+use crate::types::FetchError;
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use firecrawl_sdk::document::Document;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use url::Url;
+
+/// マスキング後の値
+pub const MASKED: &str = "[MASKED]";
+
+/// 値をマスキングするクエリパラメータ名（部分一致・大文字小文字は区別しない）
+const SENSITIVE_PARAMS: &[&str] = &[
+    "key",
+    "token",
+    "secret",
+    "password",
+    "auth",
+    "signature",
+    "session",
+];
+
+/// record/replayの動作モード
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VcrMode {
+    /// 常に実際に通信し、結果をカセットに記録する
+    Record,
+    /// カセットの記録のみを再生する（記録が無い場合はエラー）
+    Replay,
+    /// カセットに記録があれば再生し、無ければ通信して記録する
+    Auto,
+}
+
+impl VcrMode {
+    /// 環境変数`VCR_MODE`（record / replay / auto）からモードを決める（既定はreplay）
+    pub fn from_env() -> Result<Self> {
+        match std::env::var("VCR_MODE").ok().as_deref() {
+            None | Some("replay") => Ok(Self::Replay),
+            Some("record") => Ok(Self::Record),
+            Some("auto") => Ok(Self::Auto),
+            Some(other) => bail!("VCR_MODEの値が不正です: {}", other),
+        }
+    }
+}
+
+/// 記録された1回分の通信結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum RecordedResponse {
+    Ok { body: serde_json::Value },
+    Err { message: String },
+}
+
+/// 記録された1回分の通信（リクエストのキーと結果）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: String,
+    pub response: RecordedResponse,
+}
+
+// カセットファイルの内容
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+/// 通信の記録・再生を行うカセット
+///
+/// 拡張子が`.yaml`/`.yml`の場合はYAML、それ以外はJSONで保存する。
+/// 記録時はURLの秘匿パラメータと、登録した秘匿文字列を`[MASKED]`に置き換えて保存する。
+pub struct VcrCassette {
+    path: PathBuf,
+    mode: VcrMode,
+    secrets: Vec<String>,
+    interactions: Mutex<Vec<Interaction>>,
+}
+
+impl VcrCassette {
+    /// カセットを開く（ファイルが無い場合は空のカセットとして扱う）
+    pub fn open(path: impl AsRef<Path>, mode: VcrMode) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let interactions = if path.exists() {
+            let text = std::fs::read_to_string(&path)
+                .with_context(|| format!("カセットの読み込みに失敗: {}", path.display()))?;
+            let file: CassetteFile = if is_yaml(&path) {
+                serde_yaml::from_str(&text)?
+            } else {
+                serde_json::from_str(&text)?
+            };
+            file.interactions
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            path,
+            mode,
+            secrets: Vec::new(),
+            interactions: Mutex::new(interactions),
+        })
+    }
+
+    /// 記録時にマスキングする秘匿文字列（APIキーなど）を追加する
+    pub fn with_secret(mut self, secret: &str) -> Self {
+        if !secret.is_empty() {
+            self.secrets.push(secret.to_string());
+        }
+        self
+    }
+
+    /// 記録済みの通信数
+    pub fn len(&self) -> usize {
+        self.interactions.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// リクエストに対する結果を再生または記録する
+    ///
+    /// `live`は実際に通信する処理で、記録が必要な場合のみ呼び出す。
+    pub async fn exchange<T, Fut>(&self, request: &str, live: impl FnOnce() -> Fut) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+        Fut: Future<Output = Result<T>>,
+    {
+        let request = self.mask(&mask_url_params(request));
+
+        if self.mode != VcrMode::Record {
+            if let Some(response) = self.find(&request) {
+                return match response {
+                    RecordedResponse::Ok { body } => serde_json::from_value(body)
+                        .with_context(|| format!("記録されたレスポンスの復元に失敗: {}", request)),
+                    RecordedResponse::Err { message } => Err(anyhow!(message)),
+                };
+            }
+            if self.mode == VcrMode::Replay {
+                bail!("カセットに記録がありません: {}", request);
+            }
+        }
+
+        let result = live().await;
+        let response = match &result {
+            Ok(value) => RecordedResponse::Ok {
+                body: self.mask_value(serde_json::to_value(value)?),
+            },
+            Err(e) => RecordedResponse::Err {
+                message: self.mask(&format!("{:#}", e)),
+            },
+        };
+        self.record(Interaction { request, response })?;
+        result
+    }
+
+    fn find(&self, request: &str) -> Option<RecordedResponse> {
+        self.interactions
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|interaction| interaction.request == request)
+            .map(|interaction| interaction.response.clone())
+    }
+
+    /// 記録を追加してファイルに保存する（同じリクエストの記録は置き換える）
+    fn record(&self, interaction: Interaction) -> Result<()> {
+        let mut interactions = self.interactions.lock().unwrap();
+        interactions.retain(|recorded| recorded.request != interaction.request);
+        interactions.push(interaction);
+
+        let file = CassetteFile {
+            interactions: interactions.clone(),
+        };
+        let text = if is_yaml(&self.path) {
+            serde_yaml::to_string(&file)?
+        } else {
+            serde_json::to_string_pretty(&file)?
+        };
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&self.path, text)
+            .with_context(|| format!("カセットの保存に失敗: {}", self.path.display()))
+    }
+
+    fn mask(&self, text: &str) -> String {
+        self.secrets.iter().fold(text.to_string(), |text, secret| {
+            text.replace(secret, MASKED)
+        })
+    }
+
+    fn mask_value(&self, value: serde_json::Value) -> serde_json::Value {
+        use serde_json::Value;
+        match value {
+            Value::String(s) => Value::String(self.mask(&s)),
+            Value::Array(values) => {
+                Value::Array(values.into_iter().map(|v| self.mask_value(v)).collect())
+            }
+            Value::Object(map) => Value::Object(
+                map.into_iter()
+                    .map(|(key, value)| (key, self.mask_value(value)))
+                    .collect(),
+            ),
+            other => other,
+        }
+    }
+}
+
+fn is_yaml(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("yaml") | Some("yml")
+    )
+}
+
+/// リクエスト文字列中のURLの秘匿クエリパラメータの値をマスキングする
+fn mask_url_params(request: &str) -> String {
+    request
+        .split(' ')
+        .map(|part| match Url::parse(part) {
+            Ok(mut url) if url.query().is_some() => {
+                let pairs: Vec<(String, String)> = url
+                    .query_pairs()
+                    .map(|(name, value)| {
+                        let lower = name.to_lowercase();
+                        if SENSITIVE_PARAMS.iter().any(|p| lower.contains(p)) {
+                            (name.into_owned(), MASKED.to_string())
+                        } else {
+                            (name.into_owned(), value.into_owned())
+                        }
+                    })
+                    .collect();
+                url.query_pairs_mut().clear().extend_pairs(pairs);
+                url.to_string()
+            }
+            _ => part.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 実際のHTTP通信を記録・再生するクライアント（テストの再現性向上用）
+pub struct VcrHttpClient<H: HttpClient> {
+    inner: H,
+    cassette: VcrCassette,
+}
+
+impl<H: HttpClient> VcrHttpClient<H> {
+    pub fn new(inner: H, cassette: VcrCassette) -> Self {
+        Self { inner, cassette }
+    }
+}
+
+#[async_trait]
+impl<H: HttpClient + Send + Sync> HttpClient for VcrHttpClient<H> {
+    async fn fetch(&self, url: &str, timeout_secs: u64) -> Result<String, FetchError> {
+        self.cassette
+            .exchange(&format!("GET {}", url), || async {
+                self.inner
+                    .fetch(url, timeout_secs)
+                    .await
+                    .map_err(anyhow::Error::from)
+            })
+            .await
+            .map_err(replayed_error)
+    }
+
+    fn pool_stats(&self) -> Option<HttpPoolStats> {
+        self.inner.pool_stats()
+    }
+}
+
+/// 実際のFirecrawl APIの結果を記録・再生するクライアント（テストの再現性向上用）
+pub struct VcrFirecrawlClient<F: FirecrawlClient> {
+    inner: F,
+    cassette: VcrCassette,
+}
+
+impl<F: FirecrawlClient> VcrFirecrawlClient<F> {
+    pub fn new(inner: F, cassette: VcrCassette) -> Self {
+        Self { inner, cassette }
+    }
+}
+
+#[async_trait]
+impl<F: FirecrawlClient> FirecrawlClient for VcrFirecrawlClient<F> {
+    async fn scrape_url(&self, url: &str) -> Result<Document, FetchError> {
+        self.cassette
+            .exchange::<Document, _>(&format!("SCRAPE {}", url), || async {
+                self.inner
+                    .scrape_url(url)
+                    .await
+                    .map_err(anyhow::Error::from)
+            })
+            .await
+            .map_err(replayed_error)
+    }
+}
+
+/// カセット経由のエラーを`FetchError`に戻す（再生したエラーはメッセージのみ記録されているため`Unavailable`にする）
+fn replayed_error(error: anyhow::Error) -> FetchError {
+    match error.downcast::<FetchError>() {
+        Ok(error) => error,
+        Err(error) => FetchError::Unavailable(format!("{:#}", error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::api::firecrawl::MockFirecrawlClient;
+    use crate::infra::api::http::MockHttpClient;
+
+    fn temp_cassette_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir()
+            .join("datadoggo_vcr_test")
+            .join(format!("{}_{}", std::process::id(), name));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_mask_url_params() {
+        assert_eq!(
+            mask_url_params("GET https://example.com/rss?apiKey=abc&page=2"),
+            "GET https://example.com/rss?apiKey=%5BMASKED%5D&page=2"
+        );
+        assert_eq!(
+            mask_url_params("GET https://example.com/rss"),
+            "GET https://example.com/rss"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_vcr_http_client_record_and_replay() -> Result<(), anyhow::Error> {
+        let path = temp_cassette_path("http.json");
+        let url = "https://example.com/rss.xml?token=secret-token";
+
+        // 記録
+        let recorder = VcrHttpClient::new(
+            MockHttpClient::new_success(),
+            VcrCassette::open(&path, VcrMode::Record)?,
+        );
+        let recorded = recorder.fetch(url, 10).await?;
+        let saved = std::fs::read_to_string(&path)?;
+        assert!(
+            !saved.contains("secret-token"),
+            "秘匿パラメータは保存されない"
+        );
+
+        // 再生（内部クライアントはエラーを返すが、記録が使われる）
+        let player = VcrHttpClient::new(
+            MockHttpClient::new_error("通信してはいけない"),
+            VcrCassette::open(&path, VcrMode::Replay)?,
+        );
+        assert_eq!(player.fetch(url, 10).await?, recorded);
+        assert!(
+            player.fetch("https://example.com/other", 10).await.is_err(),
+            "記録の無いリクエストはエラー"
+        );
+
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_vcr_firecrawl_client_masks_secrets() -> Result<(), anyhow::Error> {
+        let path = temp_cassette_path("firecrawl.yaml");
+        let url = "https://example.com/article";
+
+        let recorder = VcrFirecrawlClient::new(
+            MockFirecrawlClient::new_success("本文 fc-secret-key 本文"),
+            VcrCassette::open(&path, VcrMode::Auto)?.with_secret("fc-secret-key"),
+        );
+        let live = recorder.scrape_url(url).await?;
+        assert_eq!(
+            live.markdown.as_deref(),
+            Some("本文 fc-secret-key 本文"),
+            "呼び出し元には元の値を返す"
+        );
+
+        let player = VcrFirecrawlClient::new(
+            MockFirecrawlClient::new_error("通信してはいけない"),
+            VcrCassette::open(&path, VcrMode::Auto)?,
+        );
+        let replayed = player.scrape_url(url).await?;
+        assert_eq!(replayed.markdown.as_deref(), Some("本文 [MASKED] 本文"));
+
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
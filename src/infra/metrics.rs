@@ -0,0 +1,149 @@
+use crate::infra::storage::file::write_file_atomic;
+use anyhow::{Context, Result};
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+/// `/metrics`のレスポンスのContent-Type（Prometheusのテキスト形式）
+pub const METRICS_CONTENT_TYPE: &str = prometheus::TEXT_FORMAT;
+
+/// エラーを数える処理の段階（`datadoggo_errors_total`の`stage`ラベル）
+pub const STAGE_FEED: &str = "feed";
+pub const STAGE_ARTICLE: &str = "article";
+
+// メトリクスの出力設定（config/app.yamlのmetricsに対応）
+//
+// 常駐プロセスはhealth.listen_addrの`/metrics`で公開する。ワンショット実行（workflow）は
+// `textfile`を指定するとnode_exporterのtextfile collector向けのファイルに書き出す。
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MetricsConfig {
+    /// workflowの終了時にメトリクスを書き出すファイル（未指定なら書き出さない）
+    #[serde(default)]
+    pub textfile: Option<String>,
+}
+
+/// 収集処理のPrometheusメトリクス（独自のレジストリに登録する）
+pub struct Metrics {
+    registry: Registry,
+    /// 保存したRSSのリンク数
+    pub links_collected: IntCounter,
+    /// 本文を取得できた記事数
+    pub articles_fetched: IntCounter,
+    /// 処理の段階（`feed` / `article`）毎のエラー数
+    pub errors: IntCounterVec,
+    /// Firecrawl APIの呼び出しの所要時間（秒）
+    pub firecrawl_latency: Histogram,
+    /// 記事取得の現在の並列度（AIMDで調整した値）
+    pub article_fetch_concurrency: IntGauge,
+}
+
+impl Metrics {
+    /// メトリクスを作成してレジストリに登録する
+    pub fn new() -> Result<Self> {
+        let registry = Registry::new();
+        let links_collected = IntCounter::with_opts(Opts::new(
+            "datadoggo_links_collected_total",
+            "保存したRSSのリンク数",
+        ))?;
+        let articles_fetched = IntCounter::with_opts(Opts::new(
+            "datadoggo_articles_fetched_total",
+            "本文を取得できた記事数",
+        ))?;
+        let errors = IntCounterVec::new(
+            Opts::new("datadoggo_errors_total", "処理の段階毎のエラー数"),
+            &["stage"],
+        )?;
+        let firecrawl_latency = Histogram::with_opts(HistogramOpts::new(
+            "datadoggo_firecrawl_request_duration_seconds",
+            "Firecrawl APIの呼び出しの所要時間（秒）",
+        ))?;
+        let article_fetch_concurrency = IntGauge::with_opts(Opts::new(
+            "datadoggo_article_fetch_concurrency",
+            "記事取得の現在の並列度",
+        ))?;
+
+        registry.register(Box::new(links_collected.clone()))?;
+        registry.register(Box::new(articles_fetched.clone()))?;
+        registry.register(Box::new(errors.clone()))?;
+        registry.register(Box::new(firecrawl_latency.clone()))?;
+        registry.register(Box::new(article_fetch_concurrency.clone()))?;
+        Ok(Self {
+            registry,
+            links_collected,
+            articles_fetched,
+            errors,
+            firecrawl_latency,
+            article_fetch_concurrency,
+        })
+    }
+
+    /// 処理の段階（`STAGE_FEED` / `STAGE_ARTICLE`）のエラーを1件数える
+    pub fn record_error(&self, stage: &str) {
+        self.errors.with_label_values(&[stage]).inc();
+    }
+
+    /// Prometheusのテキスト形式に変換する
+    pub fn encode(&self) -> Result<String> {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .context("メトリクスの変換に失敗")?;
+        String::from_utf8(buffer).context("メトリクスの変換に失敗")
+    }
+
+    /// # 概要
+    /// node_exporterのtextfile collector向けにファイルへ書き出す。
+    ///
+    /// 一時ファイルに書いてから置き換えるため、収集側が書き込み途中の内容を読まない。
+    pub fn write_textfile(&self, path: &str) -> Result<()> {
+        let text = self.encode()?;
+        write_file_atomic(path, &text)
+            .with_context(|| format!("メトリクスの書き出しに失敗: {}", path))
+    }
+}
+
+// プロセス全体で共有するメトリクス（最初の参照時に作成する）
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+/// プロセス全体で共有するメトリクス
+pub fn metrics() -> &'static Metrics {
+    METRICS.get_or_init(|| Metrics::new().expect("メトリクスの登録に失敗"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_encode() {
+        let metrics = Metrics::new().unwrap();
+        metrics.links_collected.inc_by(3);
+        metrics.articles_fetched.inc();
+        metrics.record_error(STAGE_FEED);
+        metrics.record_error(STAGE_FEED);
+        metrics.firecrawl_latency.observe(0.25);
+        metrics.article_fetch_concurrency.set(4);
+
+        let text = metrics.encode().unwrap();
+        assert!(text.contains("datadoggo_links_collected_total 3"));
+        assert!(text.contains("datadoggo_articles_fetched_total 1"));
+        assert!(text.contains(r#"datadoggo_errors_total{stage="feed"} 2"#));
+        assert!(text.contains("datadoggo_firecrawl_request_duration_seconds_count 1"));
+        assert!(text.contains("datadoggo_article_fetch_concurrency 4"));
+
+        let path =
+            std::env::temp_dir().join(format!("datadoggo_metrics_{}.prom", std::process::id()));
+        let path = path.to_str().unwrap();
+        metrics.write_textfile(path).unwrap();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), text);
+        std::fs::remove_file(path).unwrap();
+
+        let config: MetricsConfig = serde_yaml::from_str("textfile: /tmp/datadoggo.prom").unwrap();
+        assert_eq!(config.textfile.as_deref(), Some("/tmp/datadoggo.prom"));
+
+        println!("✅ Prometheusメトリクスの出力テスト成功");
+    }
+}
@@ -1,4 +1,11 @@
+#[cfg(feature = "collector")]
 pub mod api;
 pub mod compute;
+#[cfg(feature = "collector")]
+pub mod metrics;
+#[cfg(feature = "collector")]
+pub mod notify;
 pub mod parser;
 pub mod storage;
+#[cfg(feature = "collector")]
+pub mod telemetry;
@@ -0,0 +1,276 @@
+use crate::infra::api::http::request_error;
+use crate::types::{ConfigError, FetchError};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Mutex;
+use std::time::Duration;
+
+// Webhookの送信先のサービス（送信するJSONの形式が異なる）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookKind {
+    /// SlackのIncoming Webhook（`text`に本文を入れる）
+    #[default]
+    Slack,
+    /// DiscordのWebhook（`content`に本文を入れる）
+    Discord,
+}
+
+impl WebhookKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Slack => "slack",
+            Self::Discord => "discord",
+        }
+    }
+
+    /// 本文を送信先の形式のJSONにする
+    pub fn payload(&self, message: &str) -> Value {
+        match self {
+            Self::Slack => json!({ "text": message }),
+            Self::Discord => json!({ "content": message }),
+        }
+    }
+}
+
+// Webhookの送信先（config/app.yamlのnotify.webhooksの1件）
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookTarget {
+    /// 送信先の名前（ログ・エラーメッセージ用）
+    pub name: String,
+    #[serde(default)]
+    pub kind: WebhookKind,
+    /// WebhookのURLを設定した環境変数の名前（URLは秘密情報のため設定ファイルに書かない）
+    pub url_env: String,
+}
+
+// Webhook通知の設定（config/app.yamlのnotifyに対応）
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotifyConfig {
+    /// 通知の送信先（空の場合は通知しない）
+    #[serde(default)]
+    pub webhooks: Vec<WebhookTarget>,
+    /// ワークフローが完了した場合にもサマリを通知する（`false`なら致命的エラーのアラートのみ）
+    #[serde(default = "default_on_success")]
+    pub on_success: bool,
+    /// 送信のタイムアウト（秒）
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for NotifyConfig {
+    fn default() -> Self {
+        Self {
+            webhooks: Vec::new(),
+            on_success: default_on_success(),
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+fn default_on_success() -> bool {
+    true
+}
+
+fn default_timeout_secs() -> u64 {
+    10
+}
+
+/// バッチ実行の結果を外部に通知するクライアントの抽象化
+///
+/// 実際のWebhook送信とモック実装を統一的に扱うためのインターフェース。
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// 本文を送信先すべてに送る（送信先が無い場合は何もしない）
+    async fn notify(&self, message: &str) -> Result<(), FetchError>;
+
+    /// 正常に完了した場合にもサマリを通知するかどうか（`false`ならエラーのアラートのみ）
+    fn notifies_success(&self) -> bool {
+        true
+    }
+}
+
+/// SlackやDiscordのWebhookに`reqwest`で通知する実装
+pub struct WebhookNotifier {
+    client: Client,
+    /// 送信先とURL
+    endpoints: Vec<(WebhookTarget, String)>,
+    on_success: bool,
+}
+
+impl WebhookNotifier {
+    /// # 概要
+    /// 設定から通知クライアントを作成する。
+    ///
+    /// 送信先のURLは`url_env`の環境変数から読み込み、未設定・空の場合はエラーにする。
+    pub fn from_config(config: &NotifyConfig) -> Result<Self, ConfigError> {
+        let endpoints = config
+            .webhooks
+            .iter()
+            .map(|target| match std::env::var(&target.url_env) {
+                Ok(url) if !url.is_empty() => Ok((target.clone(), url)),
+                _ => Err(ConfigError::invalid(
+                    "notify.webhooks",
+                    format!(
+                        "WebhookのURLの環境変数{}が設定されていません: {}",
+                        target.url_env, target.name
+                    ),
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|e| {
+                ConfigError::invalid("notify", format!("HTTPクライアントの作成に失敗: {}", e))
+            })?;
+        Ok(Self {
+            client,
+            endpoints,
+            on_success: config.on_success,
+        })
+    }
+
+    /// 送信先の数
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    /// 送信先が無いかどうか
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    /// 送信先毎に送り、失敗した送信先があれば最後のエラーを返す（他の送信先への送信は続ける）
+    async fn notify(&self, message: &str) -> Result<(), FetchError> {
+        let mut last_error = None;
+        for (target, url) in &self.endpoints {
+            let result = self
+                .client
+                .post(url)
+                .json(&target.kind.payload(message))
+                .send()
+                .await
+                .map_err(|e| request_error(&target.name, e))
+                .and_then(|response| {
+                    let status = response.status();
+                    if status.is_success() {
+                        Ok(())
+                    } else {
+                        Err(FetchError::Status {
+                            url: target.name.clone(),
+                            status: status.as_u16(),
+                        })
+                    }
+                });
+            if let Err(e) = result {
+                tracing::warn!(
+                    webhook = %target.name,
+                    kind = target.kind.as_str(),
+                    error = %e,
+                    "Webhook通知の送信に失敗"
+                );
+                last_error = Some(e);
+            }
+        }
+        match last_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    fn notifies_success(&self) -> bool {
+        self.on_success
+    }
+}
+
+/// テスト用のモック通知クライアント（送信した本文を記録する）
+#[derive(Debug, Default)]
+pub struct MockNotifier {
+    /// 送信した本文（送信順）
+    messages: Mutex<Vec<String>>,
+    /// 送信時に返すエラーメッセージ（`None`なら成功）
+    error_message: Option<String>,
+}
+
+impl MockNotifier {
+    /// 送信に成功するモックを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 送信時にエラーを返すモックを作成（本文は記録する）
+    pub fn new_error(error_message: &str) -> Self {
+        Self {
+            messages: Mutex::new(Vec::new()),
+            error_message: Some(error_message.to_string()),
+        }
+    }
+
+    /// 送信した本文
+    pub fn messages(&self) -> Vec<String> {
+        self.messages.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Notifier for MockNotifier {
+    async fn notify(&self, message: &str) -> Result<(), FetchError> {
+        self.messages.lock().unwrap().push(message.to_string());
+        match &self.error_message {
+            Some(message) => Err(FetchError::Unavailable(format!(
+                "モックエラー: {}",
+                message
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_webhook_notifier() {
+        assert_eq!(
+            WebhookKind::Slack.payload("完了"),
+            json!({ "text": "完了" })
+        );
+        assert_eq!(
+            WebhookKind::Discord.payload("完了"),
+            json!({ "content": "完了" })
+        );
+
+        let config: NotifyConfig = serde_yaml::from_str(
+            r#"
+            webhooks:
+              - name: ops
+                kind: discord
+                url_env: DATADOGGO_TEST_UNSET_WEBHOOK_URL
+            "#,
+        )
+        .unwrap();
+        assert!(config.on_success);
+        assert_eq!(config.webhooks[0].kind, WebhookKind::Discord);
+        let error = WebhookNotifier::from_config(&config).err().unwrap();
+        assert!(error
+            .to_string()
+            .contains("DATADOGGO_TEST_UNSET_WEBHOOK_URL"));
+
+        let notifier = WebhookNotifier::from_config(&NotifyConfig::default()).unwrap();
+        assert!(notifier.is_empty());
+        assert!(notifier.notify("送信先なし").await.is_ok());
+
+        let mock = MockNotifier::new_error("送信失敗");
+        assert!(mock.notify("アラート").await.is_err());
+        assert_eq!(mock.messages(), vec!["アラート".to_string()]);
+
+        println!("✅ Webhook通知クライアントテスト成功");
+    }
+}
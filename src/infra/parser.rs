@@ -1,45 +1,227 @@
-use anyhow::{anyhow, Context, Result};
-use chrono::{DateTime, Utc};
+use crate::types::ParseError;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use rss::Channel;
 use std::io::{BufRead, BufReader, Cursor};
+use std::sync::RwLock;
+use std::time::Duration;
+use url::Url;
 
-/// 文字列を日付型に変換するヘルパー関数
+/// 日付文字列のフォーマット定義
+///
+/// `parse_date`はフォーマットテーブルを定義順に試し、最初に解析できた結果を返す。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateFormat {
+    /// RFC 2822（RSSで一般的）
+    Rfc2822,
+    /// RFC 3339
+    Rfc3339,
+    /// タイムゾーン（`%z`など）を含むstrftime形式
+    WithOffset(String),
+    /// タイムゾーンを含まないstrftime形式（指定オフセットの時刻とみなす）
+    ///
+    /// 時刻を含まないパターンの場合はその日の0時として扱う。
+    Naive {
+        pattern: String,
+        offset: FixedOffset,
+    },
+}
+
+impl DateFormat {
+    /// strftime形式のパターンからフォーマットを作成する
+    ///
+    /// パターンにタイムゾーン指定子が含まれない場合は`offset`の時刻とみなす。
+    pub fn from_pattern(pattern: &str, offset: FixedOffset) -> Self {
+        if ["%z", "%:z", "%::z", "%#z"]
+            .iter()
+            .any(|tz| pattern.contains(tz))
+        {
+            DateFormat::WithOffset(pattern.to_string())
+        } else {
+            DateFormat::Naive {
+                pattern: pattern.to_string(),
+                offset,
+            }
+        }
+    }
+
+    /// このフォーマットで日付文字列を解析する
+    pub fn parse(&self, date_str: &str) -> Option<DateTime<Utc>> {
+        let parsed = match self {
+            DateFormat::Rfc2822 => DateTime::parse_from_rfc2822(date_str).ok()?,
+            DateFormat::Rfc3339 => DateTime::parse_from_rfc3339(date_str).ok()?,
+            DateFormat::WithOffset(pattern) => DateTime::parse_from_str(date_str, pattern).ok()?,
+            DateFormat::Naive { pattern, offset } => {
+                let naive = NaiveDateTime::parse_from_str(date_str, pattern)
+                    .ok()
+                    .or_else(|| {
+                        NaiveDate::parse_from_str(date_str, pattern)
+                            .ok()
+                            .and_then(|date| date.and_hms_opt(0, 0, 0))
+                    })?;
+                offset.from_local_datetime(&naive).single()?
+            }
+        };
+        Some(parsed.with_timezone(&Utc))
+    }
+}
+
+/// 組み込みの日付フォーマットテーブル（この順に試す）
+pub fn default_date_formats() -> Vec<DateFormat> {
+    let utc = FixedOffset::east_opt(0).expect("UTCオフセットは常に有効");
+    let jst = FixedOffset::east_opt(9 * 3600).expect("JSTオフセットは常に有効");
+
+    let mut formats = vec![DateFormat::Rfc2822, DateFormat::Rfc3339];
+    // ISO 8601 バリアント（タイムゾーン付き）
+    formats.extend(
+        [
+            "%Y-%m-%dT%H:%M:%S%.f%z",
+            "%Y-%m-%d %H:%M:%S%.f%z",
+            "%Y-%m-%dT%H:%M%z",
+        ]
+        .map(|pattern| DateFormat::WithOffset(pattern.to_string())),
+    );
+    // タイムゾーンなしの表記（UTCとみなす）
+    formats.extend(
+        [
+            "%Y-%m-%dT%H:%M:%S%.f",
+            "%Y-%m-%d %H:%M:%S%.f",
+            "%Y-%m-%dT%H:%M",
+            "%Y-%m-%d %H:%M",
+            "%Y/%m/%d %H:%M:%S",
+            "%Y/%m/%d %H:%M",
+            "%Y-%m-%d",
+            "%Y/%m/%d",
+            "%Y%m%d",
+        ]
+        .map(|pattern| DateFormat::from_pattern(pattern, utc)),
+    );
+    // 日本語表記（JSTとみなす）
+    formats.extend(
+        [
+            "%Y年%m月%d日 %H時%M分%S秒",
+            "%Y年%m月%d日 %H時%M分",
+            "%Y年%m月%d日 %H:%M:%S",
+            "%Y年%m月%d日 %H:%M",
+            "%Y年%m月%d日",
+        ]
+        .map(|pattern| DateFormat::from_pattern(pattern, jst)),
+    );
+
+    formats
+}
+
+// set_date_formatsで登録されたフォーマットテーブル（未登録なら組み込みテーブルを使う）
+static DATE_FORMATS: RwLock<Option<Vec<DateFormat>>> = RwLock::new(None);
+
+/// `parse_date`が使うフォーマットテーブルを置き換える
 ///
-/// `dateparser`クレートを利用して、様々な形式の日付文字列を解析し、
-/// `DateTime<Utc>`型に変換する。
+/// アプリ起動時に設定ファイルから構築したテーブルを登録するために使う。
+pub fn set_date_formats(formats: Vec<DateFormat>) {
+    let mut guard = DATE_FORMATS.write().unwrap_or_else(|e| e.into_inner());
+    *guard = Some(formats);
+}
+
+/// 文字列を日付型に変換するヘルパー関数
 ///
-/// **この関数の意義**
-/// `dataparser::parse`で行われないwith_timezoneでUTCへの変換を行なってる。
-/// また`dataparser::parse`で対応できない文字列が来た場合でも、問題をこの関数で吸収できる。
+/// 登録済みのフォーマットテーブル（未登録なら`default_date_formats`）を定義順に試し、
+/// いずれにも一致しない場合は`dateparser`クレートにフォールバックする。
 ///
 /// # サポート形式の例
 /// - "2025-01-15"
 /// - "2025-01-15T10:00:00Z"
 /// - "Sun, 10 Aug 2025 12:00:00 +0000"
+/// - "2025/01/15 10:00"
+/// - "2025年1月15日 10時00分"
 ///
 /// # 引数
 /// - `date_str`: 解析対象の日付文字列
 ///
 /// # 戻り値
 /// - `Ok(DateTime<Utc>)`: 解析が成功した場合
-/// - `Err(anyhow::Error)`: 解析に失敗した場合
-pub fn parse_date(date_str: &str) -> Result<DateTime<Utc>> {
+/// - `Err(ParseError::Date)`: 解析に失敗した場合
+pub fn parse_date(date_str: &str) -> Result<DateTime<Utc>, ParseError> {
+    let guard = DATE_FORMATS.read().unwrap_or_else(|e| e.into_inner());
+    match guard.as_deref() {
+        Some(formats) => parse_date_with_formats(date_str, formats),
+        None => parse_date_with_formats(date_str, &default_date_formats()),
+    }
+}
+
+/// 指定したフォーマットテーブルを定義順に試して日付文字列を解析する
+///
+/// テーブルのいずれにも一致しない場合は`dateparser`で解析を試みる。
+pub fn parse_date_with_formats(
+    date_str: &str,
+    formats: &[DateFormat],
+) -> Result<DateTime<Utc>, ParseError> {
+    let trimmed = date_str.trim();
+    if let Some(dt) = formats.iter().find_map(|format| format.parse(trimmed)) {
+        return Ok(dt);
+    }
+
     // `dateparser`はタイムゾーンを持つ`DateTime`を返すため、UTCに変換する
-    match dateparser::parse(date_str) {
+    match dateparser::parse(trimmed) {
         Ok(dt) => Ok(dt.with_timezone(&Utc)),
-        Err(_) => Err(anyhow!("不正な日付形式: {}", date_str)),
+        Err(_) => Err(ParseError::Date(date_str.to_string())),
     }
 }
 
 /// xml文字列からchannelをパースする
-pub fn parse_channel_from_xml_str(xml: &str) -> Result<Channel> {
-    Channel::read_from(BufReader::new(Cursor::new(xml.as_bytes())))
-        .context("XMLからのRSSチャンネル解析に失敗")
+pub fn parse_channel_from_xml_str(xml: &str) -> Result<Channel, ParseError> {
+    Ok(Channel::read_from(BufReader::new(Cursor::new(
+        xml.as_bytes(),
+    )))?)
 }
 
 /// BufReaderからRSSチャンネルをパースする
-pub fn parse_channel_from_reader<R: BufRead>(reader: R) -> Result<Channel> {
-    Channel::read_from(reader).context("ReaderからのRSSチャンネル解析に失敗")
+pub fn parse_channel_from_reader<R: BufRead>(reader: R) -> Result<Channel, ParseError> {
+    Ok(Channel::read_from(reader)?)
+}
+
+/// 外部から受け取ったURL文字列を検証して正規化する
+///
+/// http/httpsスキームかつホストを持つURLのみ受け付ける。
+///
+/// # 戻り値
+/// - `Ok(String)`: 正規化されたURL文字列
+/// - `Err(ParseError)`: 不正なURLの場合
+pub fn parse_article_url(url_str: &str) -> Result<String, ParseError> {
+    let url = Url::parse(url_str.trim()).map_err(|_| ParseError::Url(url_str.to_string()))?;
+
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(ParseError::UnsupportedScheme(url.scheme().to_string()));
+    }
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err(ParseError::MissingHost(url_str.to_string()));
+    }
+
+    Ok(url.to_string())
+}
+
+/// # 概要
+/// `30s`・`15m`・`2h`・`1d`形式の間隔を解析する（単位を省略した場合は秒）。
+///
+/// 0や負の値、不明な単位はエラーにする。
+pub fn parse_interval(interval: &str) -> Result<Duration, ParseError> {
+    let trimmed = interval.trim();
+    let split = trimmed
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(trimmed.len());
+    let (value, unit) = trimmed.split_at(split);
+    let multiplier = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(ParseError::Interval(interval.to_string())),
+    };
+    match value.parse::<u64>() {
+        Ok(value) if value > 0 => value
+            .checked_mul(multiplier)
+            .map(Duration::from_secs)
+            .ok_or_else(|| ParseError::Interval(interval.to_string())),
+        _ => Err(ParseError::Interval(interval.to_string())),
+    }
 }
 
 #[cfg(test)]
@@ -129,4 +311,111 @@ mod tests {
             );
         }
     }
+
+    // 追加したフォーマットの網羅テスト
+    #[test]
+    fn test_parse_table_formats() {
+        let expected = Utc.with_ymd_and_hms(2025, 8, 10, 12, 30, 0).unwrap();
+        let cases = [
+            "2025-08-10T12:30:00.123Z",
+            "2025-08-10T12:30:00+0000",
+            "2025-08-10 21:30:00+09:00",
+            "2025-08-10T12:30:00",
+            "2025-08-10 12:30",
+            "2025/08/10 12:30",
+            "2025/08/10 12:30:00",
+            "2025年8月10日 21時30分",
+            "2025年08月10日 21:30",
+            "  2025-08-10T12:30:00Z  ",
+        ];
+        for date_str in cases {
+            let parsed = parse_date(date_str).unwrap_or_else(|e| panic!("{}", e));
+            assert_eq!(
+                parsed.timestamp(),
+                expected.timestamp(),
+                "日付文字列 '{}' のパースが期待と異なります",
+                date_str
+            );
+        }
+
+        // 日付のみの表記はその日の0時
+        let midnight = Utc.with_ymd_and_hms(2025, 8, 10, 0, 0, 0).unwrap();
+        assert_eq!(parse_date("2025/08/10").unwrap(), midnight);
+        assert_eq!(parse_date("20250810").unwrap(), midnight);
+        assert_eq!(
+            parse_date("2025年8月10日").unwrap(),
+            midnight - chrono::Duration::hours(9)
+        );
+    }
+
+    // フォーマットテーブルの定義順と追加フォーマットのテスト
+    #[test]
+    fn test_parse_with_custom_formats() {
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let cet = FixedOffset::east_opt(3600).unwrap();
+
+        // 組み込みテーブルでは解析できない表記
+        let custom_str = "10|08|2025 13:30";
+        assert!(parse_date_with_formats(custom_str, &default_date_formats()).is_err());
+
+        let formats = vec![DateFormat::from_pattern("%d|%m|%Y %H:%M", cet)];
+        assert_eq!(
+            parse_date_with_formats(custom_str, &formats).unwrap(),
+            Utc.with_ymd_and_hms(2025, 8, 10, 12, 30, 0).unwrap()
+        );
+
+        // 同じ文字列に一致するフォーマットが複数ある場合は先に定義した方が使われる
+        let ambiguous = "2025/08/10 12:30";
+        let formats = vec![
+            DateFormat::from_pattern("%Y/%m/%d %H:%M", cet),
+            DateFormat::from_pattern("%Y/%m/%d %H:%M", utc),
+        ];
+        assert_eq!(
+            parse_date_with_formats(ambiguous, &formats).unwrap(),
+            Utc.with_ymd_and_hms(2025, 8, 10, 11, 30, 0).unwrap()
+        );
+
+        // タイムゾーン指定子を含むパターンはオフセットを文字列から読む
+        assert_eq!(
+            DateFormat::from_pattern("%d.%m.%Y %H:%M %z", cet),
+            DateFormat::WithOffset("%d.%m.%Y %H:%M %z".to_string())
+        );
+    }
+
+    // URL検証のテスト
+    #[test]
+    fn test_parse_article_url() {
+        assert_eq!(
+            parse_article_url("https://example.com/article").unwrap(),
+            "https://example.com/article"
+        );
+        assert_eq!(
+            parse_article_url("  http://example.com  ").unwrap(),
+            "http://example.com/"
+        );
+
+        assert!(parse_article_url("").is_err());
+        assert!(parse_article_url("example.com/article").is_err());
+        assert!(parse_article_url("ftp://example.com/file").is_err());
+        assert!(parse_article_url("javascript:alert(1)").is_err());
+    }
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("15m").unwrap(), Duration::from_secs(15 * 60));
+        assert_eq!(
+            parse_interval("2h").unwrap(),
+            Duration::from_secs(2 * 60 * 60)
+        );
+        assert_eq!(
+            parse_interval("1d").unwrap(),
+            Duration::from_secs(24 * 60 * 60)
+        );
+        assert_eq!(parse_interval("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_interval(" 30s ").unwrap(), Duration::from_secs(30));
+
+        for invalid in ["", "0m", "-5m", "m", "15 minutes", "1.5h", "10w"] {
+            assert!(parse_interval(invalid).is_err(), "{}はエラー", invalid);
+        }
+    }
 }
@@ -0,0 +1,232 @@
+use crate::types::StoreError;
+use std::fmt::Display;
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+// 書き込みバッチャの設定
+#[derive(Debug, Clone)]
+pub struct WriteBatcherConfig {
+    /// この件数に達したら即座にフラッシュする
+    pub max_batch_size: usize,
+    /// 件数に達しなくてもこの間隔でフラッシュする
+    pub flush_interval: Duration,
+    /// 書き込み待ちキューの容量（満杯時は`push`が待機する）
+    pub channel_capacity: usize,
+}
+
+impl Default for WriteBatcherConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 50,
+            flush_interval: Duration::from_secs(5),
+            channel_capacity: 1000,
+        }
+    }
+}
+
+// 書き込みバッチャの実行統計
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WriteBatcherStats {
+    /// フラッシュを実行した回数
+    pub flush_count: usize,
+    /// 書き込みに成功した件数
+    pub written: usize,
+    /// 書き込みに失敗した件数
+    pub failed: usize,
+}
+
+/// 一定件数または一定時間でまとめて書き込む非同期バッチャ（write-behindキュー）
+///
+/// `push`された値はバックグラウンドのタスクに溜められ、`flush`関数にまとめて渡される。
+/// `shutdown`を呼ぶと残っている値を必ずフラッシュしてから終了する。
+pub struct WriteBatcher<T> {
+    sender: mpsc::Sender<T>,
+    handle: JoinHandle<WriteBatcherStats>,
+}
+
+impl<T: Send + 'static> WriteBatcher<T> {
+    /// バックグラウンドのフラッシュタスクを起動する
+    ///
+    /// `flush`がエラーを返した場合、そのバッチは失敗件数として記録され再試行しない。
+    pub fn spawn<F, Fut, E>(config: WriteBatcherConfig, flush: F) -> Self
+    where
+        F: FnMut(Vec<T>) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(), E>> + Send,
+        E: Display,
+    {
+        let (sender, receiver) = mpsc::channel(config.channel_capacity.max(1));
+        let handle = tokio::spawn(run_batcher(config, receiver, flush));
+        Self { sender, handle }
+    }
+
+    /// 書き込み対象を追加する
+    pub async fn push(&self, item: T) -> Result<(), StoreError> {
+        self.sender
+            .send(item)
+            .await
+            .map_err(|_| StoreError::BatcherClosed)
+    }
+
+    /// 残っている値をフラッシュしてバッチャを停止し、実行統計を返す
+    pub async fn shutdown(self) -> Result<WriteBatcherStats, StoreError> {
+        drop(self.sender);
+        self.handle.await.map_err(StoreError::BatcherJoin)
+    }
+}
+
+async fn run_batcher<T, F, Fut, E>(
+    config: WriteBatcherConfig,
+    mut receiver: mpsc::Receiver<T>,
+    mut flush: F,
+) -> WriteBatcherStats
+where
+    F: FnMut(Vec<T>) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+    E: Display,
+{
+    let max_batch_size = config.max_batch_size.max(1);
+    let mut buffer = Vec::with_capacity(max_batch_size);
+    let mut stats = WriteBatcherStats::default();
+
+    let mut interval = tokio::time::interval(config.flush_interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    // 初回のtickは即座に完了するため読み捨てる
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            received = receiver.recv() => match received {
+                Some(item) => {
+                    buffer.push(item);
+                    if buffer.len() >= max_batch_size {
+                        flush_buffer(&mut buffer, &mut flush, &mut stats).await;
+                        interval.reset();
+                    }
+                }
+                // 全ての送信側がdropされた（shutdown）
+                None => break,
+            },
+            _ = interval.tick() => {
+                flush_buffer(&mut buffer, &mut flush, &mut stats).await;
+            }
+        }
+    }
+
+    flush_buffer(&mut buffer, &mut flush, &mut stats).await;
+    stats
+}
+
+async fn flush_buffer<T, F, Fut, E>(
+    buffer: &mut Vec<T>,
+    flush: &mut F,
+    stats: &mut WriteBatcherStats,
+) where
+    F: FnMut(Vec<T>) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+    E: Display,
+{
+    if buffer.is_empty() {
+        return;
+    }
+    let batch = std::mem::take(buffer);
+    let count = batch.len();
+
+    stats.flush_count += 1;
+    match flush(batch).await {
+        Ok(()) => stats.written += count,
+        Err(e) => {
+            tracing::error!(count, error = format!("{:#}", e), "バッチ書き込みに失敗");
+            stats.failed += count;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn recording_batcher(
+        config: WriteBatcherConfig,
+    ) -> (WriteBatcher<i32>, Arc<Mutex<Vec<Vec<i32>>>>) {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&batches);
+        let batcher = WriteBatcher::spawn(config, move |batch: Vec<i32>| {
+            let recorded = Arc::clone(&recorded);
+            async move {
+                recorded.lock().unwrap().push(batch);
+                Ok::<(), StoreError>(())
+            }
+        });
+        (batcher, batches)
+    }
+
+    #[tokio::test]
+    async fn test_flush_by_size_and_shutdown() -> Result<(), anyhow::Error> {
+        let config = WriteBatcherConfig {
+            max_batch_size: 2,
+            flush_interval: Duration::from_secs(3600),
+            ..Default::default()
+        };
+        let (batcher, batches) = recording_batcher(config);
+
+        for i in 1..=5 {
+            batcher.push(i).await?;
+        }
+        let stats = batcher.shutdown().await?;
+
+        // 2件ずつフラッシュされ、残りの1件はshutdown時にフラッシュされる
+        assert_eq!(
+            *batches.lock().unwrap(),
+            vec![vec![1, 2], vec![3, 4], vec![5]]
+        );
+        assert_eq!(
+            stats,
+            WriteBatcherStats {
+                flush_count: 3,
+                written: 5,
+                failed: 0
+            }
+        );
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_flush_by_interval() -> Result<(), anyhow::Error> {
+        let config = WriteBatcherConfig {
+            max_batch_size: 100,
+            flush_interval: Duration::from_secs(5),
+            ..Default::default()
+        };
+        let (batcher, batches) = recording_batcher(config);
+
+        batcher.push(1).await?;
+        tokio::time::sleep(Duration::from_secs(6)).await;
+        assert_eq!(
+            *batches.lock().unwrap(),
+            vec![vec![1]],
+            "時間経過でフラッシュされるべき"
+        );
+
+        batcher.shutdown().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failed_flush_is_counted() -> Result<(), anyhow::Error> {
+        let batcher =
+            WriteBatcher::spawn(WriteBatcherConfig::default(), |_batch: Vec<i32>| async {
+                Err(anyhow::anyhow!("DB接続エラー"))
+            });
+        batcher.push(1).await?;
+        batcher.push(2).await?;
+
+        let stats = batcher.shutdown().await?;
+        assert_eq!(stats.failed, 2);
+        assert_eq!(stats.written, 0);
+        Ok(())
+    }
+}
@@ -1,29 +1,255 @@
-use anyhow::{Context, Result};
+use crate::types::{InfraError, ParseError, StoreError};
+use serde::Serialize;
+use sqlx::postgres::PgPoolOptions;
 use sqlx::PgPool;
 use std::env;
 
 /// データベース接続プールを作成
 /// .envファイルからDATABASE_URLを読み込みます
-pub async fn create_pool() -> Result<PgPool> {
-    let database_url = env::var("DATABASE_URL")
-        .context("データベースURLの環境変数DATABASE_URLが設定されていません")?;
+pub async fn create_pool() -> Result<PgPool, StoreError> {
+    let database_url = env::var("DATABASE_URL").map_err(|_| StoreError::MissingDatabaseUrl)?;
 
     PgPool::connect(&database_url)
         .await
-        .context("データベースへの接続に失敗しました")
+        .map_err(StoreError::database("データベースへの接続に失敗しました"))
 }
 
 /// データベースの初期化（マイグレーション実行）
-pub async fn initialize_database(pool: &PgPool) -> Result<()> {
-    sqlx::migrate!("./migrations")
-        .run(pool)
-        .await
-        .context("データベースマイグレーションの実行に失敗しました")
+pub async fn initialize_database(pool: &PgPool) -> Result<(), StoreError> {
+    Ok(sqlx::migrate!("./migrations").run(pool).await?)
 }
 
 /// プールの作成とデータベース初期化を一括で行う便利関数
-pub async fn setup_database() -> Result<PgPool> {
+pub async fn setup_database() -> Result<PgPool, StoreError> {
     let pool = create_pool().await?;
     initialize_database(&pool).await?;
     Ok(pool)
 }
+
+/// 本番のテーブルとは別のスキーマにマイグレーション済みのテーブルを作成し、
+/// そのスキーマを参照する接続プールを返す（動作確認などで本番データを汚さないため）
+///
+/// 新着リンク・新着記事のNOTIFYトリガーは本番のワーカー・購読者に通知が届かないよう削除する。
+/// 使い終わったら`drop_isolated_schema`でスキーマを削除すること。
+pub async fn create_isolated_schema_pool(
+    pool: &PgPool,
+    schema: &str,
+) -> Result<PgPool, StoreError> {
+    if !schema
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        return Err(StoreError::InvalidSchemaName(schema.to_string()));
+    }
+
+    sqlx::query(&format!("CREATE SCHEMA {}", schema))
+        .execute(pool)
+        .await
+        .map_err(StoreError::database(format!(
+            "スキーマの作成に失敗しました: {}",
+            schema
+        )))?;
+
+    let options = (*pool.connect_options())
+        .clone()
+        .options([("search_path", schema)]);
+    let isolated = PgPoolOptions::new()
+        .max_connections(2)
+        .connect_with(options)
+        .await
+        .map_err(StoreError::database("分離スキーマへの接続に失敗しました"))?;
+
+    initialize_database(&isolated).await?;
+    for statement in [
+        "DROP TRIGGER IF EXISTS article_links_inserted_notify ON article_links",
+        "DROP TRIGGER IF EXISTS articles_stored_notify ON articles",
+    ] {
+        sqlx::query(statement)
+            .execute(&isolated)
+            .await
+            .map_err(StoreError::database(
+                "分離スキーマの通知トリガーの削除に失敗しました",
+            ))?;
+    }
+
+    Ok(isolated)
+}
+
+/// `create_isolated_schema_pool`で作成したスキーマを削除する
+pub async fn drop_isolated_schema(pool: &PgPool, schema: &str) -> Result<(), StoreError> {
+    sqlx::query(&format!("DROP SCHEMA IF EXISTS {} CASCADE", schema))
+        .execute(pool)
+        .await
+        .map_err(StoreError::database(format!(
+            "スキーマの削除に失敗しました: {}",
+            schema
+        )))?;
+    Ok(())
+}
+
+/// 実行計画の検証対象とする主要クエリ（名前, SQL）
+///
+/// パラメータを取らない形にしたもので、実際のクエリを変更した場合はここも合わせて更新する。
+pub const PLAN_TARGET_QUERIES: &[(&str, &str)] = &[
+    (
+        "backlog_article_links",
+        r#"
+        SELECT al.url, al.title, al.pub_date, al.source
+        FROM article_links al
+        LEFT JOIN articles a ON al.url = a.url
+        WHERE (a.url IS NULL OR (a.status_code != 200 AND a.next_retry_at <= now()))
+            AND al.deleted_at IS NULL
+            AND al.fetch_target
+        ORDER BY al.pub_date DESC
+        LIMIT 100
+        "#,
+    ),
+    (
+        "articles_by_pub_date",
+        r#"
+        SELECT al.url, al.title, al.pub_date, a.timestamp, a.status_code
+        FROM article_links al
+        LEFT JOIN articles a ON al.url = a.url
+        WHERE al.deleted_at IS NULL
+            AND al.pub_date >= now() - interval '7 days'
+        ORDER BY al.pub_date DESC
+        LIMIT 100
+        "#,
+    ),
+    (
+        "links_by_source",
+        r#"
+        SELECT source, COUNT(*) FROM article_links
+        WHERE deleted_at IS NULL
+        GROUP BY source
+        "#,
+    ),
+    (
+        "recent_error_articles",
+        r#"
+        SELECT url, status_code, timestamp FROM articles
+        WHERE status_code <> 200 AND deleted_at IS NULL
+            AND timestamp >= now() - interval '1 day'
+        "#,
+    ),
+];
+
+// 1クエリ分の実行計画の解析結果
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryPlanReport {
+    pub name: String,
+    pub planning_ms: f64,
+    pub execution_ms: f64,
+    /// プランナの推定総コスト
+    pub total_cost: f64,
+    /// シーケンシャルスキャンしたテーブル
+    pub seq_scans: Vec<String>,
+    /// 使用したインデックス
+    pub indexes: Vec<String>,
+    /// `EXPLAIN (FORMAT JSON)`の実行計画
+    pub plan: serde_json::Value,
+}
+
+/// # 概要
+/// 主要クエリ（`PLAN_TARGET_QUERIES`）を`EXPLAIN ANALYZE`で実行し、実行計画を構造化して返す。
+///
+/// # Note
+/// `EXPLAIN ANALYZE`は実際にクエリを実行するため、参照系のクエリのみを対象にすること。
+pub async fn analyze_query_plans(pool: &PgPool) -> Result<Vec<QueryPlanReport>, InfraError> {
+    let mut reports = Vec::with_capacity(PLAN_TARGET_QUERIES.len());
+    for (name, sql) in PLAN_TARGET_QUERIES {
+        let explain: serde_json::Value =
+            sqlx::query_scalar(&format!("EXPLAIN (ANALYZE, FORMAT JSON) {}", sql))
+                .fetch_one(pool)
+                .await
+                .map_err(StoreError::database(format!(
+                    "実行計画の取得に失敗しました: {}",
+                    name
+                )))?;
+        reports.push(parse_query_plan(name, explain)?);
+    }
+    Ok(reports)
+}
+
+/// `EXPLAIN (ANALYZE, FORMAT JSON)`の結果を解析する
+fn parse_query_plan(name: &str, explain: serde_json::Value) -> Result<QueryPlanReport, ParseError> {
+    let root = explain
+        .get(0)
+        .ok_or_else(|| ParseError::QueryPlan(format!("実行計画が空です: {}", name)))?;
+    let plan = root
+        .get("Plan")
+        .ok_or_else(|| ParseError::QueryPlan(format!("実行計画にPlanがありません: {}", name)))?;
+
+    let mut seq_scans = Vec::new();
+    let mut indexes = Vec::new();
+    collect_scans(plan, &mut seq_scans, &mut indexes);
+
+    let number = |value: &serde_json::Value, key: &str| value.get(key).and_then(|v| v.as_f64());
+    Ok(QueryPlanReport {
+        name: name.to_string(),
+        planning_ms: number(root, "Planning Time").unwrap_or_default(),
+        execution_ms: number(root, "Execution Time").unwrap_or_default(),
+        total_cost: number(plan, "Total Cost").unwrap_or_default(),
+        seq_scans,
+        indexes,
+        plan: plan.clone(),
+    })
+}
+
+/// 実行計画のノードを再帰的にたどり、スキャン対象のテーブルとインデックスを集める
+fn collect_scans(node: &serde_json::Value, seq_scans: &mut Vec<String>, indexes: &mut Vec<String>) {
+    let text = |key: &str| node.get(key).and_then(|v| v.as_str()).map(str::to_string);
+    if text("Node Type").as_deref() == Some("Seq Scan") {
+        if let Some(relation) = text("Relation Name") {
+            seq_scans.push(relation);
+        }
+    }
+    if let Some(index) = text("Index Name") {
+        indexes.push(index);
+    }
+    if let Some(children) = node.get("Plans").and_then(|v| v.as_array()) {
+        for child in children {
+            collect_scans(child, seq_scans, indexes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query_plan() {
+        let explain = serde_json::json!([{
+            "Plan": {
+                "Node Type": "Limit",
+                "Total Cost": 12.5,
+                "Plans": [
+                    {"Node Type": "Index Scan", "Relation Name": "article_links", "Index Name": "article_links_backlog_idx"},
+                    {"Node Type": "Seq Scan", "Relation Name": "articles"}
+                ]
+            },
+            "Planning Time": 0.2,
+            "Execution Time": 1.5
+        }]);
+        let report = parse_query_plan("backlog", explain).unwrap();
+        assert_eq!(report.total_cost, 12.5);
+        assert_eq!(report.execution_ms, 1.5);
+        assert_eq!(report.seq_scans, vec!["articles"]);
+        assert_eq!(report.indexes, vec!["article_links_backlog_idx"]);
+
+        assert!(parse_query_plan("empty", serde_json::json!([])).is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_analyze_query_plans(pool: PgPool) -> Result<(), anyhow::Error> {
+        let reports = analyze_query_plans(&pool).await?;
+        assert_eq!(reports.len(), PLAN_TARGET_QUERIES.len());
+        for report in &reports {
+            assert!(report.plan.get("Node Type").is_some(), "{}", report.name);
+        }
+
+        println!("✅ 実行計画の解析テスト成功");
+        Ok(())
+    }
+}
@@ -1,43 +1,174 @@
 use crate::infra::parser::parse_channel_from_reader;
-use anyhow::{Context, Result};
+use crate::types::{InfraError, ParseError, StoreError};
 use rss::Channel;
 use serde::de::DeserializeOwned;
+use std::borrow::Cow;
+use std::ffi::OsString;
 use std::fs::File;
 use std::io::BufReader;
+use std::path::{Component, Path, PathBuf};
+
+/// UTF-8のBOM
+const UTF8_BOM: char = '\u{feff}';
+
+/// # 概要
+/// パスの区切り文字と`.`・`..`を正規化する。
+///
+/// `/`と`\`のどちらで書かれたパスも実行環境の区切り文字に揃える
+/// （設定ファイルのパスをWindows・Unixのどちらで書いても読めるようにするため）。
+/// `..`はファイルシステムを参照せずに字句的に解決し、先頭の`..`はそのまま残す。
+pub fn normalize_path(path: impl AsRef<Path>) -> PathBuf {
+    let path = path.as_ref();
+    // Windowsでは`/`も区切り文字として解釈されるため、Unixでのみ`\`を置き換える
+    let unified: Cow<Path> = match path.to_str() {
+        Some(s) if std::path::MAIN_SEPARATOR == '/' && s.contains('\\') => {
+            Cow::Owned(PathBuf::from(s.replace('\\', "/")))
+        }
+        _ => Cow::Borrowed(path),
+    };
+
+    let mut normalized = PathBuf::new();
+    for component in unified.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    if normalized.as_os_str().is_empty() {
+        normalized.push(".");
+    }
+    normalized
+}
+
+/// # 概要
+/// テキストの先頭のBOMを除き、改行をLFに揃える。
+///
+/// Windowsで編集されたファイル（BOM付きUTF-8・CRLF）や古いMacのCRのみの改行も
+/// 同じように解析できるようにする。変更が無い場合はコピーしない。
+pub fn normalize_text(text: &str) -> Cow<'_, str> {
+    let text = text.strip_prefix(UTF8_BOM).unwrap_or(text);
+    if text.contains('\r') {
+        Cow::Owned(text.replace("\r\n", "\n").replace('\r', "\n"))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
 
 /// ファイルパスからBufReaderを作成する
 /// パースやデータ変換は各ドメインで行う
-pub fn load_file(file_path: &str) -> Result<BufReader<File>> {
-    let file = File::open(file_path)
-        .with_context(|| format!("ファイルの読み込みに失敗しました: {}", file_path))?;
+pub fn load_file(file_path: impl AsRef<Path>) -> Result<BufReader<File>, StoreError> {
+    let file_path = normalize_path(file_path);
+    let file = File::open(&file_path).map_err(|source| StoreError::Read {
+        path: file_path,
+        source,
+    })?;
     let buf_reader = BufReader::new(file);
     Ok(buf_reader)
 }
 
+/// テキストファイルを読み込む（BOMの除去と改行の正規化を行う）
+pub fn load_text_file(file_path: impl AsRef<Path>) -> Result<String, StoreError> {
+    let file_path = normalize_path(file_path);
+    let text = std::fs::read_to_string(&file_path).map_err(|source| StoreError::Read {
+        path: file_path,
+        source,
+    })?;
+    Ok(normalize_text(&text).into_owned())
+}
+
 /// xmlファイルからchannelを読み込む
-pub fn load_channel_from_xml_file(file_path: &str) -> Result<Channel> {
-    let buf_reader = load_file(file_path)?;
-    parse_channel_from_reader(buf_reader)
-        .with_context(|| format!("RSSファイルの解析に失敗: {}", file_path))
+///
+/// XML宣言で文字コードを指定したUTF-8以外のファイルも読めるよう、
+/// 文字列に変換せずにバイト列のままBOMの除去と改行の正規化を行う。
+pub fn load_channel_from_xml_file(file_path: impl AsRef<Path>) -> Result<Channel, InfraError> {
+    let file_path = normalize_path(file_path);
+    let bytes = std::fs::read(&file_path).map_err(|source| StoreError::Read {
+        path: file_path.clone(),
+        source,
+    })?;
+    match parse_channel_from_reader(normalize_bytes(&bytes).as_slice()) {
+        Ok(channel) => Ok(channel),
+        Err(ParseError::Rss(source)) => Err(ParseError::RssFile {
+            path: file_path,
+            source,
+        }
+        .into()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// バイト列の先頭のUTF-8のBOMを除き、改行をLFに揃える
+fn normalize_bytes(bytes: &[u8]) -> Vec<u8> {
+    let bytes = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes);
+    let mut normalized = Vec::with_capacity(bytes.len());
+    let mut iter = bytes.iter().peekable();
+    while let Some(&byte) = iter.next() {
+        if byte == b'\r' {
+            iter.next_if_eq(&&b'\n');
+            normalized.push(b'\n');
+        } else {
+            normalized.push(byte);
+        }
+    }
+    normalized
 }
 
 /// JSONファイルからserde_json::Valueを読み込む
-pub fn load_json_from_file(file_path: &str) -> Result<serde_json::Value> {
-    let buf_reader = load_file(file_path)?;
-    serde_json::from_reader(buf_reader)
-        .with_context(|| format!("JSONファイルの解析に失敗: {}", file_path))
+pub fn load_json_from_file(file_path: impl AsRef<Path>) -> Result<serde_json::Value, InfraError> {
+    let file_path = file_path.as_ref();
+    let text = load_text_file(file_path)?;
+    serde_json::from_str(&text).map_err(|source| {
+        ParseError::Json {
+            path: file_path.to_path_buf(),
+            source,
+        }
+        .into()
+    })
 }
 
 /// YAMLファイルからSerdeでDeserializeできる型を読み込む
-pub fn load_yaml_from_file<T: DeserializeOwned>(file_path: &str) -> Result<T> {
-    let buf_reader = load_file(file_path)?;
-    serde_yaml::from_reader(buf_reader)
-        .with_context(|| format!("YAMLファイルの解析に失敗: {}", file_path))
+pub fn load_yaml_from_file<T: DeserializeOwned>(
+    file_path: impl AsRef<Path>,
+) -> Result<T, InfraError> {
+    let file_path = file_path.as_ref();
+    let text = load_text_file(file_path)?;
+    serde_yaml::from_str(&text).map_err(|source| {
+        ParseError::Yaml {
+            path: file_path.to_path_buf(),
+            source,
+        }
+        .into()
+    })
+}
+
+/// ファイルに書き込む（一時ファイルに書いてから置き換えるため、読み込み側が書き込み途中の内容を読まない）
+pub fn write_file_atomic(file_path: impl AsRef<Path>, content: &str) -> Result<(), StoreError> {
+    let file_path = normalize_path(file_path);
+    let mut temp_path = OsString::from(file_path.as_os_str());
+    temp_path.push(".tmp");
+    let temp_path = PathBuf::from(temp_path);
+
+    std::fs::write(&temp_path, content).map_err(|source| StoreError::Write {
+        path: temp_path.clone(),
+        source,
+    })?;
+    std::fs::rename(&temp_path, &file_path).map_err(|source| StoreError::Write {
+        path: file_path,
+        source,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
 
     #[test]
     fn test_load_existing_file() {
@@ -50,6 +181,66 @@ mod tests {
     fn test_load_non_existing_file() {
         // 存在しないファイルでエラーになることを確認
         let result = load_file("non_existent_file.txt");
-        assert!(result.is_err(), "存在しないファイルでエラーにならなかった");
+        assert!(
+            matches!(result, Err(StoreError::Read { .. })),
+            "存在しないファイルでエラーにならなかった"
+        );
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        let expected: PathBuf = ["config", "feeds.yaml"].iter().collect();
+        assert_eq!(normalize_path("config/feeds.yaml"), expected);
+        assert_eq!(normalize_path("config\\feeds.yaml"), expected);
+        assert_eq!(normalize_path("./config/./tmp/../feeds.yaml"), expected);
+        assert_eq!(
+            normalize_path("../config/feeds.yaml"),
+            ["..", "config", "feeds.yaml"].iter().collect::<PathBuf>()
+        );
+        assert_eq!(normalize_path("./"), PathBuf::from("."));
+        // 既存のファイルは区切り文字を混在させても読み込める
+        assert!(load_file("mock\\fc/bbc.json").is_ok());
+    }
+
+    #[test]
+    fn test_normalize_text() {
+        assert_eq!(normalize_text("\u{feff}a: 1\r\nb: 2\r\n"), "a: 1\nb: 2\n");
+        assert_eq!(normalize_text("a\rb"), "a\nb");
+        assert!(matches!(normalize_text("a\nb"), Cow::Borrowed(_)));
+        assert_eq!(normalize_bytes(b"\xEF\xBB\xBFa\r\nb\rc"), b"a\nb\nc");
+    }
+
+    #[test]
+    fn test_load_files_with_bom_and_crlf() {
+        let dir = std::env::temp_dir().join(format!("datadoggo_file_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let yaml_path = dir.join("windows.yaml");
+        std::fs::write(&yaml_path, "\u{feff}bbc:\r\n  top: 1\r\n").unwrap();
+        let yaml: HashMap<String, HashMap<String, i32>> = load_yaml_from_file(&yaml_path).unwrap();
+        assert_eq!(yaml["bbc"]["top"], 1);
+
+        let json_path = dir.join("windows.json");
+        std::fs::write(&json_path, "\u{feff}{\r\n  \"a\": 1\r\n}").unwrap();
+        assert_eq!(load_json_from_file(&json_path).unwrap()["a"], 1);
+
+        let xml_path = dir.join("windows.xml");
+        std::fs::write(
+            &xml_path,
+            "\u{feff}<?xml version=\"1.0\"?>\r\n<rss version=\"2.0\"><channel>\r\n\
+             <title>Feed</title><link>https://example.com</link><description>d</description>\r\n\
+             </channel></rss>\r\n",
+        )
+        .unwrap();
+        assert_eq!(
+            load_channel_from_xml_file(&xml_path).unwrap().title(),
+            "Feed"
+        );
+
+        write_file_atomic(dir.join("out.yaml"), "a: 1\n").unwrap();
+        assert_eq!(load_text_file(dir.join("out.yaml")).unwrap(), "a: 1\n");
+
+        std::fs::remove_dir_all(&dir).ok();
+        println!("✅ BOM・CRLF付きファイルの読み込みテスト成功");
     }
 }
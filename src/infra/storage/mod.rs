@@ -1,2 +1,3 @@
+pub mod batcher;
 pub mod db;
 pub mod file;
@@ -0,0 +1,217 @@
+use crate::types::ConfigError;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use serde::Deserialize;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+/// トレースのエクスポート先
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TelemetryExporter {
+    /// トレースを送信しない
+    #[default]
+    None,
+    /// OTLP/gRPC（既定のエンドポイント: http://localhost:4317）
+    OtlpGrpc,
+    /// OTLP/HTTP（既定のエンドポイント: http://localhost:4318/v1/traces）
+    OtlpHttp,
+}
+
+/// ログの出力形式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// 人が読むためのテキスト形式
+    #[default]
+    Text,
+    /// 1行1イベントのJSON形式（ログ収集基盤向け）
+    Json,
+}
+
+// ログと分散トレースの設定（config/app.yamlのtelemetryに対応）
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelemetryConfig {
+    #[serde(default)]
+    pub exporter: TelemetryExporter,
+    /// 送信先エンドポイント（省略時はエクスポータ毎の既定値）
+    pub endpoint: Option<String>,
+    #[serde(default = "default_service_name")]
+    pub service_name: String,
+    /// ログレベル（EnvFilterの書式、環境変数`RUST_LOG`があればそちらを優先）
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+    #[serde(default)]
+    pub log_format: LogFormat,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            exporter: TelemetryExporter::default(),
+            endpoint: None,
+            service_name: default_service_name(),
+            log_level: default_log_level(),
+            log_format: LogFormat::default(),
+        }
+    }
+}
+
+fn default_service_name() -> String {
+    "datadoggo".to_string()
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+/// 初期化したトレース基盤
+///
+/// dropされる時に未送信のスパンを送信してエクスポータを停止するため、
+/// プロセス終了まで保持しておく。
+pub struct TelemetryGuard {
+    provider: Option<SdkTracerProvider>,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.provider.take() {
+            if let Err(e) = provider.shutdown() {
+                tracing::warn!(error = %e, "トレースエクスポータの停止に失敗");
+            }
+        }
+    }
+}
+
+/// # 概要
+/// tracingのログを標準エラー出力に出し、設定に従ってスパンをOTLPでエクスポートする
+///
+/// ログは`log_level`（`RUST_LOG`があればそちらを優先）で絞り込み、`log_format`の形式で出力する。
+/// `TelemetryExporter::None`の場合はエクスポートしない。
+/// プロセス全体のsubscriberを登録するため、起動時に1回だけ呼び出す。
+pub fn init_telemetry(config: &TelemetryConfig) -> Result<TelemetryGuard, ConfigError> {
+    let filter = match std::env::var(EnvFilter::DEFAULT_ENV) {
+        Ok(directives) if !directives.is_empty() => EnvFilter::try_new(&directives)
+            .map_err(|e| ConfigError::invalid(EnvFilter::DEFAULT_ENV, e.to_string()))?,
+        _ => EnvFilter::try_new(&config.log_level)
+            .map_err(|e| ConfigError::invalid("telemetry.log_level", e.to_string()))?,
+    };
+    let (text_layer, json_layer) = match config.log_format {
+        LogFormat::Text => (
+            Some(tracing_subscriber::fmt::layer().with_writer(std::io::stderr)),
+            None,
+        ),
+        LogFormat::Json => (
+            None,
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .with_writer(std::io::stderr),
+            ),
+        ),
+    };
+    let (provider, otel_layer) = match build_tracer_provider(config)? {
+        Some(provider) => {
+            let tracer = provider.tracer(config.service_name.clone());
+            let layer = tracing_opentelemetry::layer().with_tracer(tracer);
+            (Some(provider), Some(layer))
+        }
+        None => (None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(text_layer)
+        .with(json_layer)
+        .with(otel_layer)
+        .try_init()
+        .map_err(|e| {
+            ConfigError::invalid(
+                "telemetry",
+                format!("tracing subscriberの登録に失敗: {}", e),
+            )
+        })?;
+
+    Ok(TelemetryGuard { provider })
+}
+
+/// 設定したエクスポータでトレースプロバイダを作成する（`TelemetryExporter::None`ならNone）
+fn build_tracer_provider(
+    config: &TelemetryConfig,
+) -> Result<Option<SdkTracerProvider>, ConfigError> {
+    let exporter = match config.exporter {
+        TelemetryExporter::None => return Ok(None),
+        TelemetryExporter::OtlpGrpc => {
+            let mut builder = SpanExporter::builder().with_tonic();
+            if let Some(ref endpoint) = config.endpoint {
+                builder = builder.with_endpoint(endpoint);
+            }
+            builder.build()
+        }
+        TelemetryExporter::OtlpHttp => {
+            let mut builder = SpanExporter::builder().with_http();
+            if let Some(ref endpoint) = config.endpoint {
+                builder = builder.with_endpoint(endpoint);
+            }
+            builder.build()
+        }
+    }
+    .map_err(|e| {
+        ConfigError::invalid(
+            "telemetry.exporter",
+            format!("OTLPエクスポータの初期化に失敗: {}", e),
+        )
+    })?;
+
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            Resource::builder()
+                .with_service_name(config.service_name.clone())
+                .build(),
+        )
+        .build();
+    Ok(Some(provider))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_telemetry_config_deserialize() {
+        let config: TelemetryConfig = serde_yaml::from_str(
+            r#"
+exporter: otlp_http
+endpoint: "http://collector:4318/v1/traces"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.exporter, TelemetryExporter::OtlpHttp);
+        assert_eq!(
+            config.endpoint.as_deref(),
+            Some("http://collector:4318/v1/traces")
+        );
+        assert_eq!(config.service_name, "datadoggo");
+
+        let config: TelemetryConfig = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(config.exporter, TelemetryExporter::None);
+        assert_eq!(config.log_level, "info");
+        assert_eq!(config.log_format, LogFormat::Text);
+
+        let config: TelemetryConfig =
+            serde_yaml::from_str("log_level: \"datadoggo=debug\"\nlog_format: json").unwrap();
+        assert_eq!(config.log_level, "datadoggo=debug");
+        assert_eq!(config.log_format, LogFormat::Json);
+    }
+
+    #[test]
+    fn test_init_telemetry_disabled() {
+        // エクスポータ無効時はログ出力のみ登録し、トレースプロバイダは作らない
+        let guard = init_telemetry(&TelemetryConfig::default()).unwrap();
+        assert!(guard.provider.is_none());
+    }
+}
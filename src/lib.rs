@@ -1,4 +1,11 @@
+#[cfg(feature = "collector")]
 pub mod app;
 pub mod core;
 pub mod infra;
+#[cfg(feature = "reader")]
+pub mod reader;
+#[cfg(feature = "collector")]
+pub mod server;
+#[cfg(feature = "collector")]
 pub mod task;
+pub mod types;
@@ -1,112 +1,1291 @@
-/// NOTE: main.rsは単なる最小限の動作確認に過ぎないので凝った実装をしないように
-use datadoggo::{app, core, infra};
+/// NOTE: main.rsはCLIの引数解析と各機能の呼び出しのみを行い、処理の実装はapp/task/coreに置くこと
+use datadoggo::{app, core, infra, server, task};
 
-use app::execute_rss_workflow;
-use core::feed::{search_feeds, FeedQuery};
-use core::rss::{get_article_links_from_channel, store_article_links};
+use anyhow::{Context, Result};
+use app::{
+    execute_article_worker, execute_feed_sync_daemon, execute_links_sync, execute_recrawl_daemon,
+    execute_rss_workflow, execute_rss_workflow_multi, execute_smoke_test, execute_workflow_daemon,
+    replay_simulation, shutdown_signal, write_report, CheckReport, ReportFormat, SmokeTestOptions,
+};
+use clap::{Parser, Subcommand};
+use core::api_access::{create_api_key, list_api_keys, revoke_api_key, ServerConfig};
+use core::article::{
+    export_articles, get_article_debug_log, search_articles, search_articles_fulltext,
+    search_articles_page, Article, ArticleCursor, ArticleQuery, ArticleStatus, ErrorCategory,
+    ExportFormat, SnippetOptions,
+};
+use core::collection_error::{search_errors, CollectionEntityType, CollectionErrorQuery};
+use core::config::{apply_app_config, load_app_config, AppConfig};
+use core::exit_code::ExitStatusError;
+use core::feed::{
+    disable_feed, export_feeds_to_opml, feeds_to_opml, feeds_to_yaml, format_feed_stats_table,
+    import_feeds_from_opml, import_feeds_from_yaml, list_feed_records, list_feeds_with_stats,
+    search_feed_fetch_logs, search_feeds, search_feeds_from, source::SharedHttpClient, store_feed,
+    Feed, FeedFetchLogQuery, FeedFetchResult, FeedQuery, FeedStorage, FeedsConfig, FEEDS_PATH,
+};
+use core::health::{CycleTracker, HealthConfig, HealthState};
+use core::maintenance::{migrate_legacy_data, LegacyMigrationOptions};
+use core::ng_word::{add_ng_word, list_ng_words, remove_ng_word};
+use core::replay::{RawScrapeRecordingScraper, ReplayRange, SnapshotRecordingHttpClient};
+use core::rss::{preview_feed, BacklogOrder};
+use core::saved_query::{delete_saved_query, list_saved_queries, run_saved_query, save_query};
+use core::scraper::{DomainRoutingScraper, ScraperBackend};
+use core::series::{backfill_series_keys, search_series};
+use core::storage_quota::{
+    format_storage_usage_lines, list_domain_storage_usage, refresh_domain_storage_usage,
+};
+use core::timeline::get_url_timeline;
 use infra::api::firecrawl::ReqwestFirecrawlClient;
 use infra::api::http::ReqwestHttpClient;
+use infra::metrics::metrics;
+use infra::parser::parse_interval;
 use infra::storage::db::setup_database;
-use infra::storage::file::{load_channel_from_xml_file, load_json_from_file};
+use infra::storage::file::write_file_atomic;
+use infra::telemetry::init_telemetry;
+use server::{serve, serve_health, ServerState};
+use sqlx::PgPool;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+use task::rss::DEFAULT_FEED_CONCURRENCY;
+use task::{
+    task_collect_article_links_with, task_collect_articles_with, task_collect_claimed_articles,
+    task_collect_due_article_links, task_sync_feeds,
+};
+
+#[derive(Debug, Parser)]
+#[command(name = "datadoggo", about = "RSSフィードからニュース記事を収集する")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// RSSフィードから記事リンクを収集してDBに保存する
+    CollectLinks {
+        /// 対象のフィードグループ（省略時は全フィード）
+        #[arg(long)]
+        group: Option<String>,
+        /// 並行して取得するフィード数
+        #[arg(long, default_value_t = DEFAULT_FEED_CONCURRENCY)]
+        concurrency: usize,
+        /// 取得期限の来たフィード（fetch_interval）のみ取得する（config/app.yamlのfeeds.scheduledでも有効）
+        #[arg(long)]
+        scheduled: bool,
+    },
+    /// 未処理・エラーの記事リンクから記事内容を取得してDBに保存する
+    CollectArticles {
+        /// 複数ワーカーでバックログを分担する場合のワーカーID
+        #[arg(long)]
+        worker_id: Option<String>,
+        /// ワーカーIDを指定した場合に1回でクレームする件数
+        #[arg(long, default_value_t = 50)]
+        batch_size: i64,
+        /// 記事本文の取得に使うスクレイパー（firecrawl / local / headless、省略時は設定ファイルの値）
+        #[arg(long)]
+        scraper: Option<ScraperBackend>,
+        /// 処理する取得元（複数指定可、省略時は設定ファイルの値）
+        #[arg(long = "source")]
+        sources: Vec<String>,
+        /// 処理しない取得元（複数指定可、省略時は設定ファイルの値）
+        #[arg(long = "exclude-source")]
+        exclude_sources: Vec<String>,
+        /// 1回に処理するリンクの上限（省略時は設定ファイルの値、ワーカーIDを指定した場合は--batch-sizeずつ全件処理する）
+        #[arg(long, conflicts_with = "worker_id")]
+        limit: Option<u32>,
+        /// 処理する順序（newest / oldest / feed_priority、省略時は設定ファイルの値）
+        #[arg(long)]
+        order: Option<BacklogOrder>,
+    },
+    /// リンク収集から記事取得までのワークフローを実行する（終了コードは全成功0・部分失敗0または2・全失敗1）
+    Workflow {
+        /// 対象のフィードグループ（省略時は全フィード、複数指定するとグループ毎に並列実行）
+        #[arg(long)]
+        group: Vec<String>,
+        /// すべてのフィードグループをグループ毎に並列実行する
+        #[arg(long, conflicts_with = "group")]
+        all_groups: bool,
+        /// 記事本文の取得に使うスクレイパー（firecrawl / local / headless、省略時は設定ファイルの値）
+        #[arg(long)]
+        scraper: Option<ScraperBackend>,
+        /// 実行結果のレポートをJSONで出力する
+        #[arg(long)]
+        json: bool,
+        /// 実行結果を成功/失敗ケースのレポートで出力する（text / junit / github）
+        #[arg(long)]
+        report: Option<ReportFormat>,
+        /// レポートの書き込み先（省略時は標準出力）
+        #[arg(long, requires = "report")]
+        report_file: Option<PathBuf>,
+    },
+    /// 各フィードから1件ずつリンク収集→記事取得→保存を確認する（失敗したフィードがあれば終了コード1）
+    SmokeTest {
+        /// 対象のフィードグループ（省略時は全フィード）
+        #[arg(long)]
+        group: Option<String>,
+        /// 一時スキーマに保存して本番のテーブルを汚さない
+        #[arg(long)]
+        isolated: bool,
+        /// 結果のレポート形式（text / junit / github）
+        #[arg(long, default_value_t = ReportFormat::Text)]
+        report: ReportFormat,
+        /// レポートの書き込み先（省略時は標準出力）
+        #[arg(long)]
+        report_file: Option<PathBuf>,
+    },
+    /// 記事を検索して表示する
+    SearchArticles {
+        /// URLの部分一致
+        #[arg(long)]
+        pattern: Option<String>,
+        /// タイトルの検索条件（簡易クエリ構文）
+        #[arg(long)]
+        title: Option<String>,
+        /// 本文の検索条件（簡易クエリ構文）
+        #[arg(long)]
+        content: Option<String>,
+        /// 本文の全文検索の条件（関連度順に表示する）
+        #[arg(long, conflicts_with = "after")]
+        full_text: Option<String>,
+        /// 全文検索でマッチした箇所周辺の抜粋を表示する
+        #[arg(long, requires = "full_text")]
+        snippet: bool,
+        /// 処理状態（unprocessed / success / 4xx / 5xx / 404 / 500-503）
+        #[arg(long)]
+        status: Option<ArticleStatus>,
+        /// 本文から推定した言語（ISO 639-3、例: jpn / eng）
+        #[arg(long)]
+        language: Option<String>,
+        /// 表示する最大件数（指定すると次のページのカーソルも表示する）
+        #[arg(long)]
+        limit: Option<i64>,
+        /// 先頭から読み飛ばす件数
+        #[arg(long)]
+        offset: Option<i64>,
+        /// 前のページで表示されたカーソルより後の記事を表示する
+        #[arg(long)]
+        after: Option<ArticleCursor>,
+        /// ゴミ箱の記事も含める
+        #[arg(long)]
+        include_deleted: bool,
+    },
+    /// 記事をJSONL / CSV / Markdown（front-matter付き）で書き出す
+    Export {
+        /// 書き出し形式（jsonl / csv / markdown）
+        #[arg(long, default_value_t = ExportFormat::Jsonl)]
+        format: ExportFormat,
+        /// 書き込み先（省略時は標準出力）
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// URLの部分一致
+        #[arg(long)]
+        pattern: Option<String>,
+        /// 書き出す最大件数
+        #[arg(long)]
+        limit: Option<i64>,
+        /// ゴミ箱の記事も含める
+        #[arg(long)]
+        include_deleted: bool,
+    },
+    /// RESTサーバを起動する（SERVER_ADDR, INGEST_TOKEN環境変数を使用）
+    Serve,
+    /// 記事配信のgRPCサーバを起動する（GRPC_ADDR, INGEST_TOKEN環境変数を使用）
+    #[cfg(feature = "grpc")]
+    GrpcServe,
+    /// 新着リンクをLISTEN/NOTIFYで即時処理する記事取得ワーカーを起動する
+    Worker,
+    /// 一定間隔でRSSワークフローを実行し続ける（SIGTERM・Ctrl-Cで実行中のワークフローの完了後に終了）
+    Daemon {
+        /// 実行間隔（例: 30s, 15m, 2h、前回の実行完了から数える）
+        #[arg(long, default_value = "15m", value_parser = parse_interval)]
+        interval: Duration,
+        /// 対象のフィードグループ（省略時は全フィード）
+        #[arg(long)]
+        group: Option<String>,
+        /// 記事本文の取得に使うスクレイパー（firecrawl / local / headless、省略時は設定ファイルの値）
+        #[arg(long)]
+        scraper: Option<ScraperBackend>,
+    },
+    /// 記事本文を取得せず、リンクの収集（タイトル・公開日時の更新）だけを一定間隔毎に繰り返す軽量モード
+    LinksSync {
+        /// 実行間隔（例: 30s, 5m、前回の同期完了から数える）
+        #[arg(long, default_value = "5m", value_parser = parse_interval)]
+        interval: Duration,
+        /// 対象のフィードグループ（省略時は全フィード）
+        #[arg(long)]
+        group: Option<String>,
+    },
+    /// フィード設定を操作する
+    Feeds {
+        #[command(subcommand)]
+        command: FeedsCommand,
+    },
+    /// URLのリンク登録・取得試行・本文リビジョンの履歴（と記録していれば処理ログ）を表示する
+    Inspect { url: String },
+    /// フィードを取得・解析して抽出できるリンクと解析の警告を表示する（DBには書き込まない）
+    PreviewFeed {
+        url: String,
+        /// 表示するリンクの最大件数
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// シリーズ（連載）の記事リンクを新しい順に表示する
+    Series {
+        /// シリーズキー（config/app.yamlのseries.rulesで導出したもの）
+        #[arg(required_unless_present = "backfill")]
+        key: Option<String>,
+        /// 最新話のみ表示する
+        #[arg(long)]
+        latest: bool,
+        /// 保存済みの全リンクにシリーズキーを導出し直す（抽出ルールの変更後に使う）
+        #[arg(long, conflicts_with = "key")]
+        backfill: bool,
+    },
+    /// ドメイン毎の記事本文の保存容量と上限（config/app.yamlのarticle_fetch.storage_quota）を表示する
+    StorageUsage {
+        /// 表示する前に集計し直す
+        #[arg(long)]
+        refresh: bool,
+    },
+    /// 記録したフィード本文とスクレイプ結果で期間内の収集を一時スキーマに再実行し、本番との差分を表示する
+    ReplaySimulation {
+        /// 期間の開始（RFC 3339、例: 2025-01-01T00:00:00Z）
+        #[arg(long)]
+        from: chrono::DateTime<chrono::Utc>,
+        /// 期間の終了（RFC 3339、省略時は現在）
+        #[arg(long)]
+        to: Option<chrono::DateTime<chrono::Utc>>,
+        /// 結果をJSONで出力する
+        #[arg(long)]
+        json: bool,
+    },
+    /// 名前付きで保存した検索条件を操作する
+    SavedQueries {
+        #[command(subcommand)]
+        command: SavedQueriesCommand,
+    },
+    /// RESTサーバのAPIキー（api_keysテーブル）を操作する
+    ApiKeys {
+        #[command(subcommand)]
+        command: ApiKeysCommand,
+    },
+    /// 記事の検閲に使うNGワード（ng_wordsテーブル）を操作する
+    NgWords {
+        #[command(subcommand)]
+        command: NgWordsCommand,
+    },
+    /// 収集処理で記録したエラー（collection_errorsテーブル）を新しい順に表示する
+    Errors {
+        /// 対象の種類（feed / article / task）
+        #[arg(long = "type")]
+        entity_type: Option<CollectionEntityType>,
+        /// フィードのキー（group/name）・記事のURL・処理名
+        #[arg(long)]
+        key: Option<String>,
+        /// エラーカテゴリ（timeout / dns / network / blocked / not_found など）
+        #[arg(long)]
+        category: Option<ErrorCategory>,
+        /// ワークフローの実行ID（job_runs.id）
+        #[arg(long)]
+        job_id: Option<i64>,
+        /// 直近の期間に発生したエラーのみ表示する（例: 30m, 24h）
+        #[arg(long, value_parser = parse_interval)]
+        since: Option<Duration>,
+        /// 表示する最大件数
+        #[arg(long, default_value_t = 50)]
+        limit: i64,
+    },
+    /// 旧スキーマ（rss_articles / firecrawl_articles）のデータをarticle_links / articlesに移行する（検証に失敗したらロールバック）
+    MigrateLegacy {
+        /// 旧テーブルのあるスキーマ
+        #[arg(long, default_value = "public")]
+        schema: String,
+        /// 移行後に旧テーブルと照合する件数
+        #[arg(long, default_value_t = 20)]
+        sample: i64,
+        /// 移行と検証だけ行い、ロールバックする
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum FeedsCommand {
+    /// フィードの一覧を表示する
+    List {
+        /// フィード毎の収集統計も表示する
+        #[arg(long)]
+        stats: bool,
+    },
+    /// 外部ソース（config/app.yamlのfeed_sources）からフィードリストを同期する
+    Sync {
+        /// sync_interval_secs毎に同期し続ける
+        #[arg(long)]
+        daemon: bool,
+    },
+    /// feedsテーブルにフィードを追加する（同じgroup・nameがあればURLを更新）
+    Add {
+        group: String,
+        name: String,
+        url: String,
+        /// 取得に失敗した時に試すURL（複数指定可）
+        #[arg(long = "fallback-url")]
+        fallback_urls: Vec<String>,
+        /// 取得間隔（分）。collect-links --scheduledでこの間隔毎に取得する
+        #[arg(long)]
+        fetch_interval: Option<u32>,
+        /// 記事リンクの期待URLパターン（正規表現、複数指定可）。一致しないリンクは収集しない
+        #[arg(long = "url-pattern")]
+        url_patterns: Vec<String>,
+    },
+    /// feedsテーブルのフィードを無効にする（収集済みのリンクは残す）
+    Disable { group: String, name: String },
+    /// feeds.yaml形式のファイルのフィードをfeedsテーブルに取り込む
+    Import {
+        #[arg(long, default_value = FEEDS_PATH)]
+        path: PathBuf,
+    },
+    /// OPMLファイル（RSSリーダーの購読リスト）をfeeds.yaml形式に変換する
+    ImportOpml {
+        path: PathBuf,
+        /// 書き出し先のファイル（省略時は標準出力）
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// 現在のフィード設定（feeds.storageの保存先）をOPMLとして書き出す
+    ExportOpml {
+        /// 書き出し先のファイル（省略時は標準出力）
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// フィードの取得履歴（feed_fetch_logs）を新しい順に表示する
+    Logs {
+        /// フィードのキー（group/name）
+        #[arg(long)]
+        feed: Option<String>,
+        /// 取得結果（success / not_modified / failed）
+        #[arg(long)]
+        result: Option<FeedFetchResult>,
+        /// 直近の期間の履歴のみ表示する（例: 30m, 24h）
+        #[arg(long, value_parser = parse_interval)]
+        since: Option<Duration>,
+        /// 表示する最大件数
+        #[arg(long, default_value_t = 50)]
+        limit: i64,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum SavedQueriesCommand {
+    /// 検索条件を保存する（同じ名前があれば上書き）
+    Save {
+        name: String,
+        /// 検索条件のJSON（例: '{"title_pattern": "rust", "limit": 20}'）
+        #[arg(long)]
+        json: String,
+    },
+    /// 保存済みの検索条件で記事を検索して表示する
+    Run {
+        name: String,
+        /// 一部の条件を上書きするJSON（例: '{"limit": 5}'）
+        #[arg(long)]
+        overrides: Option<String>,
+    },
+    /// 保存済みの検索条件の一覧を表示する
+    List,
+    /// 保存済みの検索条件を削除する
+    Delete { name: String },
+}
+
+#[derive(Debug, Subcommand)]
+enum ApiKeysCommand {
+    /// APIキーを発行する（キーは発行時にのみ表示される）
+    Create {
+        name: String,
+        /// 1分あたりのリクエスト数の上限（省略時はserver.rate_limit_per_minute、0は無制限）
+        #[arg(long)]
+        rate_limit: Option<u32>,
+    },
+    /// APIキーの一覧を表示する
+    List,
+    /// APIキーを失効させる
+    Revoke { name: String },
+}
+
+#[derive(Debug, Subcommand)]
+enum NgWordsCommand {
+    /// NGワードを登録する（登録済みの場合は理由を更新する）
+    Add {
+        word: String,
+        #[arg(long, default_value = "")]
+        reason: String,
+    },
+    /// 登録したNGワードの一覧を表示する（config/app.yamlの語は含まない）
+    List,
+    /// NGワードの登録を削除する
+    Remove { word: String },
+}
 
 #[tokio::main]
-async fn main() {
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
     // 環境変数を読み込み（.envファイルがあれば使用）
     let _ = dotenvy::dotenv();
 
-    // フィード設定を読み込み
-    println!("=== フィード設定の読み込み ===");
-    match search_feeds(None) {
-        Ok(feeds) => {
-            println!("全フィード数: {}", feeds.len());
-
-            // BBCフィードの例
-            let bbc_query = Some(FeedQuery::from_group("bbc"));
-            match search_feeds(bbc_query) {
-                Ok(bbc_feeds) => {
-                    println!("BBCフィード数: {}", bbc_feeds.len());
-                    for feed in bbc_feeds.iter().take(3) {
-                        println!("  - {}", feed);
-                    }
-                }
-                Err(e) => eprintln!("BBCフィード検索エラー: {}", e),
+    // アプリ設定を読み込んで反映
+    let config = match load_app_config().and_then(|config| {
+        apply_app_config(&config)?;
+        Ok(config)
+    }) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!(
+                "アプリ設定の読み込みに失敗しました: {:#}",
+                anyhow::Error::from(e)
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+    // 分散トレースの初期化（guardはmain終了時に未送信のスパンを送信する）
+    let _telemetry = match init_telemetry(&config.telemetry) {
+        Ok(guard) => guard,
+        Err(e) => {
+            eprintln!(
+                "分散トレースの初期化に失敗しました: {:#}",
+                anyhow::Error::from(e)
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(cli.command, &config).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("エラー: {:#}", e);
+            match e.downcast_ref::<ExitStatusError>() {
+                Some(status) => ExitCode::from(status.code),
+                None => ExitCode::FAILURE,
             }
+        }
+    }
+}
 
-            // Yahoo Japanフィードの例
-            let yahoo_query = Some(FeedQuery::from_group("yahoo_japan"));
-            match search_feeds(yahoo_query) {
-                Ok(yahoo_feeds) => {
-                    println!("Yahoo Japanフィード数: {}", yahoo_feeds.len());
-                    for feed in yahoo_feeds.iter().take(3) {
-                        println!("  - {}", feed);
-                    }
+async fn run(command: Command, config: &AppConfig) -> Result<()> {
+    match command {
+        Command::CollectLinks {
+            group,
+            concurrency,
+            scheduled,
+        } => {
+            let pool = connect_database().await?;
+            let feeds = search_feeds_from(
+                &config.feeds,
+                group.as_deref().map(FeedQuery::from_group),
+                &pool,
+            )
+            .await
+            .context("フィード設定の読み込みに失敗しました")?;
+            let client = http_client(config)?;
+            if scheduled || config.feeds.scheduled {
+                task_collect_due_article_links(
+                    &client,
+                    &feeds,
+                    &config.topic_filter,
+                    &config.series,
+                    concurrency,
+                    &pool,
+                )
+                .await?;
+            } else {
+                task_collect_article_links_with(
+                    &client,
+                    &feeds,
+                    &config.topic_filter,
+                    &config.series,
+                    concurrency,
+                    &pool,
+                )
+                .await?;
+            }
+            Ok(())
+        }
+        Command::CollectArticles {
+            worker_id,
+            batch_size,
+            scraper,
+            sources,
+            exclude_sources,
+            limit,
+            order,
+        } => {
+            let mut fetch_config = config.article_fetch.clone();
+            if !sources.is_empty() {
+                fetch_config.backlog.sources = Some(sources);
+            }
+            if !exclude_sources.is_empty() {
+                fetch_config.backlog.exclude_sources = Some(exclude_sources);
+            }
+            if let Some(limit) = limit {
+                fetch_config.backlog_options.limit = limit;
+            }
+            if let Some(order) = order {
+                fetch_config.backlog_options.order = order;
+            }
+            let pool = connect_database().await?;
+            let scraper = article_scraper(config, scraper, &pool).await?;
+            match worker_id {
+                Some(worker_id) => {
+                    task_collect_claimed_articles(
+                        &scraper,
+                        &fetch_config,
+                        &pool,
+                        &worker_id,
+                        batch_size,
+                    )
+                    .await
                 }
-                Err(e) => eprintln!("Yahoo Japanフィード検索エラー: {}", e),
+                None => task_collect_articles_with(&scraper, &fetch_config, &pool).await,
             }
         }
-        Err(e) => {
-            eprintln!("フィード設定の読み込みに失敗しました: {}", e);
+        Command::Workflow {
+            group,
+            all_groups,
+            scraper,
+            json,
+            report: report_format,
+            report_file,
+        } => {
+            let pool = connect_database().await?;
+            // リプレイ用の記録（replay.record_snapshots）が無効な場合はそのまま取得する
+            let recorder = config.replay.record_snapshots.then(|| pool.clone());
+            let http_client =
+                SnapshotRecordingHttpClient::new(http_client(config)?, recorder.clone());
+            let scraper = RawScrapeRecordingScraper::new(
+                article_scraper(config, scraper, &pool).await?,
+                recorder,
+            );
+            // 複数グループはグループ毎に並列実行して結果を集約する
+            if all_groups || group.len() > 1 {
+                let report = execute_rss_workflow_multi(
+                    Arc::new(http_client),
+                    Arc::new(scraper),
+                    &pool,
+                    &group,
+                )
+                .await?;
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&report)?);
+                }
+                if let Some(format) = report_format {
+                    write_report_output(&report, format, report_file.as_deref())?;
+                }
+                if let Some(path) = &config.metrics.textfile {
+                    metrics().write_textfile(path)?;
+                }
+                report.check_exit_code(&config.exit_code)?;
+                return Ok(());
+            }
+            let group = group.first().map(String::as_str);
+            let report = execute_rss_workflow(&http_client, &scraper, &pool, group).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            }
+            if let Some(format) = report_format {
+                write_report_output(&report, format, report_file.as_deref())?;
+            }
+            // ワンショット実行は/metricsを公開できないため、textfile collector向けに書き出す
+            if let Some(path) = &config.metrics.textfile {
+                metrics().write_textfile(path)?;
+            }
+            // 失敗したフィードの割合からexit_codeの設定に従って終了コードを決める
+            report.check_exit_code(&config.exit_code)?;
+            Ok(())
+        }
+        Command::SmokeTest {
+            group,
+            isolated,
+            report: report_format,
+            report_file,
+        } => {
+            let pool = connect_database().await?;
+            let report = execute_smoke_test(
+                &http_client(config)?,
+                &firecrawl_client()?,
+                &pool,
+                group.as_deref(),
+                SmokeTestOptions {
+                    isolated_schema: isolated,
+                },
+            )
+            .await?;
+            output_report(&report, report_format, report_file.as_deref())
+        }
+        Command::SearchArticles {
+            pattern,
+            title,
+            content,
+            full_text,
+            snippet,
+            status,
+            language,
+            limit,
+            offset,
+            after,
+            include_deleted,
+        } => {
+            let pool = connect_database().await?;
+            if full_text.is_some() {
+                let query = ArticleQuery {
+                    link_pattern: pattern,
+                    title_pattern: title,
+                    content_query: content,
+                    full_text,
+                    article_status: status,
+                    language,
+                    limit,
+                    offset,
+                    include_deleted,
+                    snippet: snippet.then(SnippetOptions::default),
+                    ..Default::default()
+                };
+                print_articles(&search_articles_fulltext(query, &pool).await?);
+                return Ok(());
+            }
+            let paged = limit.is_some() || after.is_some();
+            let query = ArticleQuery {
+                link_pattern: pattern,
+                title_pattern: title,
+                content_query: content,
+                article_status: status,
+                language,
+                limit,
+                offset,
+                after,
+                include_deleted,
+                ..Default::default()
+            };
+            if !paged {
+                print_articles(&search_articles(Some(query), &pool).await?);
+                return Ok(());
+            }
+            let page = search_articles_page(query, &pool).await?;
+            print_articles(&page.articles);
+            if let Some(cursor) = page.next_cursor {
+                println!("次のページ: --after '{}'", cursor);
+            }
+            Ok(())
+        }
+        Command::Serve => run_server(&config.health, &config.server).await,
+        #[cfg(feature = "grpc")]
+        Command::GrpcServe => run_grpc_server(config).await,
+        Command::Worker => run_worker(config).await,
+        Command::Daemon {
+            interval,
+            group,
+            scraper,
+        } => {
+            let pool = connect_database().await?;
+            let cycles = spawn_health_server(&pool, &config.health);
+            let recorder = config.replay.record_snapshots.then(|| pool.clone());
+            let http_client =
+                SnapshotRecordingHttpClient::new(http_client(config)?, recorder.clone());
+            let scraper = RawScrapeRecordingScraper::new(
+                article_scraper(config, scraper, &pool).await?,
+                recorder,
+            );
+            // 再クロール（article_fetch.recrawl）はワークフローと並行して別の間隔・並列度で回す
+            let (workflow, recrawl) = tokio::join!(
+                execute_workflow_daemon(
+                    &http_client,
+                    &scraper,
+                    &pool,
+                    group.as_deref(),
+                    interval,
+                    &cycles,
+                    shutdown_signal(),
+                ),
+                execute_recrawl_daemon(&scraper, &pool, &config.article_fetch, shutdown_signal()),
+            );
+            workflow?;
+            recrawl?;
+            Ok(())
+        }
+        Command::LinksSync { interval, group } => {
+            let pool = connect_database().await?;
+            execute_links_sync(
+                &http_client(config)?,
+                &pool,
+                group.as_deref(),
+                interval,
+                shutdown_signal(),
+            )
+            .await?;
+            Ok(())
+        }
+        Command::Export {
+            format,
+            out,
+            pattern,
+            limit,
+            include_deleted,
+        } => {
+            let pool = connect_database().await?;
+            let query = ArticleQuery {
+                link_pattern: pattern,
+                limit,
+                include_deleted,
+                ..Default::default()
+            };
+            match out {
+                Some(path) => {
+                    let mut buffer = Vec::new();
+                    let count = export_articles(Some(query), format, &mut buffer, &pool).await?;
+                    write_file_atomic(&path, &String::from_utf8(buffer)?)?;
+                    eprintln!("{}件の記事を書き出しました: {}", count, path.display());
+                }
+                None => {
+                    export_articles(Some(query), format, std::io::stdout(), &pool).await?;
+                }
+            }
+            Ok(())
+        }
+        Command::Feeds { command } => run_feeds(command, config).await,
+        Command::SavedQueries { command } => run_saved_queries(command).await,
+        Command::ApiKeys { command } => run_api_keys(command).await,
+        Command::NgWords { command } => run_ng_words(command).await,
+        Command::Errors {
+            entity_type,
+            key,
+            category,
+            job_id,
+            since,
+            limit,
+        } => {
+            let pool = connect_database().await?;
+            let occurred_from = since
+                .map(|since| chrono::Duration::from_std(since).map(|d| chrono::Utc::now() - d))
+                .transpose()
+                .context("期間が長すぎます")?;
+            let query = CollectionErrorQuery {
+                entity_type,
+                entity_key: key,
+                error_category: category,
+                job_id,
+                occurred_from,
+                limit: Some(limit),
+                ..Default::default()
+            };
+            for error in search_errors(Some(query), &pool).await? {
+                let job = error
+                    .job_id
+                    .map_or(String::new(), |job_id| format!(" job={}", job_id));
+                println!(
+                    "{} [{}] {} {}{}: {}",
+                    error.occurred_at.to_rfc3339(),
+                    error.entity_type,
+                    error.entity_key,
+                    error.error_category,
+                    job,
+                    error.message
+                );
+            }
+            Ok(())
+        }
+        Command::ReplaySimulation { from, to, json } => {
+            let pool = connect_database().await?;
+            let range = ReplayRange::new(from, to.unwrap_or_else(chrono::Utc::now))?;
+            let report = replay_simulation(&range, &pool).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                for line in report.format_lines() {
+                    println!("{}", line);
+                }
+            }
+            Ok(())
+        }
+        Command::MigrateLegacy {
+            schema,
+            sample,
+            dry_run,
+        } => {
+            let pool = connect_database().await?;
+            let options = LegacyMigrationOptions {
+                legacy_schema: schema,
+                sample_size: sample,
+                dry_run,
+            };
+            let report = migrate_legacy_data(&pool, &options).await?;
+            for line in report.format_lines() {
+                println!("{}", line);
+            }
+            if !report.is_verified() {
+                anyhow::bail!(
+                    "移行の検証で{}件の問題が見つかりました",
+                    report.issues.len()
+                );
+            }
+            Ok(())
+        }
+        Command::Series {
+            key,
+            latest,
+            backfill,
+        } => {
+            let pool = connect_database().await?;
+            if backfill {
+                let count = backfill_series_keys(&config.series, &pool).await?;
+                println!("{}件のリンクにシリーズキーを設定しました", count);
+                return Ok(());
+            }
+            let key = key.context("シリーズキーを指定してください")?;
+            for link in search_series(&key, latest, &pool).await? {
+                println!(
+                    "{} {} {}",
+                    link.pub_date.format("%Y-%m-%d %H:%M"),
+                    link.title,
+                    link.url
+                );
+            }
+            Ok(())
+        }
+        Command::StorageUsage { refresh } => {
+            let pool = connect_database().await?;
+            if refresh {
+                refresh_domain_storage_usage(&pool).await?;
+            }
+            let usage = list_domain_storage_usage(&pool).await?;
+            for line in format_storage_usage_lines(&usage, &config.article_fetch.storage_quota) {
+                println!("{}", line);
+            }
+            Ok(())
+        }
+        Command::PreviewFeed { url, limit } => {
+            let preview = preview_feed(&http_client(config)?, &url)
+                .await
+                .with_context(|| format!("フィードのプレビューに失敗しました: {}", url))?;
+            for line in preview.format_lines(limit) {
+                println!("{}", line);
+            }
+            Ok(())
+        }
+        Command::Inspect { url } => {
+            let pool = connect_database().await?;
+            let timeline = get_url_timeline(&url, &pool)
+                .await
+                .context("処理履歴の取得に失敗しました")?;
+            timeline
+                .format_lines()
+                .iter()
+                .for_each(|line| println!("{}", line));
+            if let Some(debug_log) = get_article_debug_log(&url, &pool)
+                .await
+                .context("処理ログの取得に失敗しました")?
+            {
+                println!("[処理ログ]");
+                debug_log
+                    .format_lines()
+                    .iter()
+                    .for_each(|line| println!("  {}", line));
+            }
+            Ok(())
         }
     }
+}
 
-    // データベースプールを1回だけ作成
-    let pool = match setup_database().await {
-        Ok(pool) => pool,
-        Err(e) => {
-            eprintln!("データベースの初期化に失敗しました: {}", e);
-            return;
+fn print_articles(articles: &[Article]) {
+    for article in articles {
+        let status = match article.status_code {
+            None => "未処理".to_string(),
+            Some(200) => "取得済み".to_string(),
+            Some(code) => format!("エラー({})", code),
+        };
+        println!(
+            "[{}] {} {}",
+            status,
+            article.pub_date.to_rfc3339(),
+            article.title
+        );
+        println!("  {}", article.url);
+        if let Some(ref snippet) = article.snippet {
+            println!("  {}", snippet);
         }
-    };
+    }
+    println!("{}件", articles.len());
+}
 
-    // RSS処理
-    println!("=== RSS処理を開始 ===");
-    match load_channel_from_xml_file("mock/rss/bbc.rss") {
-        Ok(channel) => {
-            let links = get_article_links_from_channel(&channel);
-            println!("BBCのRSSから{}件のリンクを抽出しました。", links.len());
+/// レポートを標準出力またはファイルに書き出し、失敗ケースがあればエラーにする（終了コード1）
+fn output_report<R: CheckReport>(
+    report: &R,
+    format: ReportFormat,
+    file: Option<&Path>,
+) -> Result<()> {
+    write_report_output(report, format, file)?;
+    match report.failure_count() {
+        0 => Ok(()),
+        failures => anyhow::bail!("{}件のケースが失敗しました", failures),
+    }
+}
 
-            match store_article_links(&links, &pool).await {
-                Ok(_) => {
-                    println!("データベースへの保存が完了しました: {}件", links.len());
-                }
-                Err(e) => eprintln!("データベースへの保存中にエラーが発生しました: {}", e),
+fn write_report_output<R: CheckReport>(
+    report: &R,
+    format: ReportFormat,
+    file: Option<&Path>,
+) -> Result<()> {
+    match file {
+        Some(path) => {
+            let mut out = Vec::new();
+            write_report(report, format, &mut out)?;
+            write_file_atomic(path, &String::from_utf8(out)?)?;
+        }
+        None => write_report(report, format, &mut std::io::stdout().lock())?,
+    }
+    Ok(())
+}
+
+async fn run_saved_queries(command: SavedQueriesCommand) -> Result<()> {
+    let pool = connect_database().await?;
+    match command {
+        SavedQueriesCommand::Save { name, json } => {
+            let query: ArticleQuery =
+                serde_json::from_str(&json).context("検索条件のJSONが不正です")?;
+            save_query(&name, &query, &pool).await?;
+            println!("保存しました: {}", name);
+        }
+        SavedQueriesCommand::Run { name, overrides } => {
+            let overrides = overrides
+                .map(|json| serde_json::from_str(&json))
+                .transpose()
+                .context("上書きする検索条件のJSONが不正です")?;
+            let articles = run_saved_query(&name, overrides.as_ref(), &pool).await?;
+            print_articles(&articles);
+        }
+        SavedQueriesCommand::List => {
+            for saved in list_saved_queries(&pool).await? {
+                println!("{}: {}", saved.name, serde_json::to_string(&saved.query)?);
             }
         }
-        Err(e) => {
-            eprintln!("RSSの読み込み中にエラーが発生しました: {}", e);
+        SavedQueriesCommand::Delete { name } => {
+            if !delete_saved_query(&name, &pool).await? {
+                anyhow::bail!("保存済みクエリが見つかりません: {}", name);
+            }
+            println!("削除しました: {}", name);
         }
     }
+    Ok(())
+}
 
-    // Firecrawl処理（簡易確認）
-    println!("\n=== Firecrawl処理を開始 ===");
-    match load_json_from_file("mock/fc/bbc.json") {
-        Ok(json_value) => {
-            println!("BBCのFirecrawlデータを読み込みました。");
-            if let Some(metadata) = json_value.get("metadata") {
-                if let Some(url) = metadata.get("url").and_then(|v| v.as_str()) {
-                    println!("URL: {}", url);
-                }
+async fn run_api_keys(command: ApiKeysCommand) -> Result<()> {
+    let pool = connect_database().await?;
+    match command {
+        ApiKeysCommand::Create { name, rate_limit } => {
+            let key = create_api_key(&name, rate_limit, &pool).await?;
+            eprintln!("APIキーを発行しました: {}（X-API-Keyヘッダで送信）", name);
+            println!("{}", key);
+        }
+        ApiKeysCommand::List => {
+            for key in list_api_keys(&pool).await? {
+                let rate_limit = key
+                    .rate_limit_per_minute
+                    .map_or("既定".to_string(), |limit| format!("{}回/分", limit));
+                let state = match key.revoked_at {
+                    Some(revoked_at) => format!("失効 {}", revoked_at.to_rfc3339()),
+                    None => "有効".to_string(),
+                };
+                println!(
+                    "{}: {} 上限={} 発行={}",
+                    key.name,
+                    state,
+                    rate_limit,
+                    key.created_at.to_rfc3339()
+                );
             }
-            println!("JSONデータの読み込み完了");
         }
-        Err(e) => {
-            eprintln!("Firecrawlデータの読み込み中にエラーが発生しました: {}", e);
+        ApiKeysCommand::Revoke { name } => {
+            if !revoke_api_key(&name, &pool).await? {
+                anyhow::bail!("有効なAPIキーが見つかりません: {}", name);
+            }
+            println!("失効させました: {}", name);
         }
     }
+    Ok(())
+}
 
-    // RSSワークフローのデモンストレーション（BBCグループのみ）
-    println!("\n=== RSSワークフロー（デモ実行） ===");
-    // 本番用のクライアントをインスタンス化
-    let http_client = ReqwestHttpClient::new();
-    let firecrawl_client =
-        ReqwestFirecrawlClient::new().expect("Firecrawlクライアントの初期化に失敗");
+async fn run_ng_words(command: NgWordsCommand) -> Result<()> {
+    let pool = connect_database().await?;
+    match command {
+        NgWordsCommand::Add { word, reason } => {
+            add_ng_word(&word, &reason, &pool).await?;
+            println!("NGワードを登録しました: {}", word.trim());
+        }
+        NgWordsCommand::List => {
+            for word in list_ng_words(&pool).await? {
+                println!(
+                    "{}: 理由={} 登録={}",
+                    word.word,
+                    word.reason,
+                    word.added_at.to_rfc3339()
+                );
+            }
+        }
+        NgWordsCommand::Remove { word } => {
+            if !remove_ng_word(&word, &pool).await? {
+                anyhow::bail!("NGワードが登録されていません: {}", word);
+            }
+            println!("削除しました: {}", word);
+        }
+    }
+    Ok(())
+}
 
-    match execute_rss_workflow(&http_client, &firecrawl_client, &pool, Some("bbc")).await {
-        Ok(()) => {
-            println!("RSSワークフローが正常に完了しました");
+async fn run_feeds(command: FeedsCommand, config: &AppConfig) -> Result<()> {
+    match command {
+        FeedsCommand::List { stats } => return run_feeds_list(&config.feeds, stats).await,
+        FeedsCommand::ImportOpml { path, out } => {
+            let feeds = import_feeds_from_opml(&path)?;
+            let yaml = feeds_to_yaml(&feeds)?;
+            match out {
+                Some(out) => {
+                    write_file_atomic(&out, &yaml)?;
+                    eprintln!(
+                        "{}件のフィードを書き出しました: {}",
+                        feeds.len(),
+                        out.display()
+                    );
+                }
+                None => print!("{}", yaml),
+            }
+            return Ok(());
         }
-        Err(e) => {
-            eprintln!("RSSワークフローでエラーが発生しました: {}", e);
+        FeedsCommand::ExportOpml { out } => {
+            let feeds = match config.feeds.storage {
+                FeedStorage::Yaml => {
+                    search_feeds(None).context("フィード設定の読み込みに失敗しました")?
+                }
+                FeedStorage::Db => {
+                    let pool = connect_database().await?;
+                    search_feeds_from(&config.feeds, None, &pool).await?
+                }
+            };
+            match out {
+                Some(out) => {
+                    export_feeds_to_opml(&feeds, &out)?;
+                    eprintln!(
+                        "{}件のフィードを書き出しました: {}",
+                        feeds.len(),
+                        out.display()
+                    );
+                }
+                None => print!("{}", feeds_to_opml(&feeds)),
+            }
+            return Ok(());
         }
+        FeedsCommand::Sync { daemon } => {
+            let client: SharedHttpClient = Arc::new(http_client(config)?);
+            return if daemon {
+                execute_feed_sync_daemon(&config.feed_sources, &client).await
+            } else {
+                task_sync_feeds(&config.feed_sources, &client).await
+            };
+        }
+        _ => {}
     }
+
+    let pool = connect_database().await?;
+    match command {
+        FeedsCommand::Add {
+            group,
+            name,
+            url,
+            fallback_urls,
+            fetch_interval,
+            url_patterns,
+        } => {
+            let feed = Feed {
+                group,
+                name,
+                rss_link: url,
+                fallback_urls,
+                fetch_interval,
+                url_patterns,
+                last_fetched_at: None,
+            };
+            store_feed(&feed, &pool).await?;
+            println!("保存しました: {}", feed.key());
+        }
+        FeedsCommand::Disable { group, name } => {
+            if !disable_feed(&group, &name, &pool).await? {
+                anyhow::bail!("フィードが見つかりません: {}/{}", group, name);
+            }
+            println!("無効にしました: {}/{}", group, name);
+        }
+        FeedsCommand::Import { path } => {
+            let count = import_feeds_from_yaml(&path, &pool).await?;
+            println!("{}件のフィードを取り込みました: {}", count, path.display());
+        }
+        FeedsCommand::Logs {
+            feed,
+            result,
+            since,
+            limit,
+        } => {
+            let fetched_from = since
+                .map(|since| chrono::Duration::from_std(since).map(|d| chrono::Utc::now() - d))
+                .transpose()
+                .context("期間が長すぎます")?;
+            let query = FeedFetchLogQuery {
+                feed,
+                result,
+                fetched_from,
+                limit: Some(limit),
+                ..Default::default()
+            };
+            for log in search_feed_fetch_logs(Some(query), &pool).await? {
+                let error = match (log.error_category, &log.error_message) {
+                    (Some(category), Some(message)) => format!(" [{}] {}", category, message),
+                    _ => String::new(),
+                };
+                println!(
+                    "{} {} {} {}件 {}{}",
+                    log.fetched_at.to_rfc3339(),
+                    log.feed,
+                    log.result,
+                    log.link_count,
+                    log.url,
+                    error
+                );
+            }
+        }
+        FeedsCommand::List { .. }
+        | FeedsCommand::Sync { .. }
+        | FeedsCommand::ImportOpml { .. }
+        | FeedsCommand::ExportOpml { .. } => {}
+    }
+    Ok(())
+}
+
+async fn connect_database() -> Result<PgPool> {
+    setup_database()
+        .await
+        .context("データベースの初期化に失敗しました")
+}
+
+fn http_client(config: &AppConfig) -> Result<ReqwestHttpClient> {
+    ReqwestHttpClient::with_config(&config.http).context("HTTPクライアントの初期化に失敗しました")
+}
+
+fn firecrawl_client() -> Result<ReqwestFirecrawlClient> {
+    ReqwestFirecrawlClient::new().context("Firecrawlクライアントの初期化に失敗しました")
+}
+
+/// 記事本文の取得に使うスクレイパー（`--scraper`省略時はconfig/app.yamlのscraper.backend、
+/// fetch_backendsテーブル・scraper.domainsに記載したドメインは指定のバックエンド）
+async fn article_scraper(
+    config: &AppConfig,
+    backend: Option<ScraperBackend>,
+    pool: &PgPool,
+) -> Result<DomainRoutingScraper> {
+    let scraper = DomainRoutingScraper::from_config(
+        &config.scraper,
+        backend.unwrap_or(config.scraper.backend),
+    )
+    .await
+    .context("スクレイパーの初期化に失敗しました")?
+    .with_fetch_backends(
+        pool.clone(),
+        Duration::from_secs(config.scraper.fetch_backends_refresh_secs),
+    );
+    scraper
+        .reload_fetch_backends()
+        .await
+        .context("記事取得バックエンドの対応表の読み込みに失敗しました")?;
+    Ok(scraper)
+}
+
+async fn run_server(health_config: &HealthConfig, server_config: &ServerConfig) -> Result<()> {
+    let pool = connect_database().await?;
+    let health = HealthState::new(pool.clone(), health_config.readiness.clone(), None);
+    health.mark_config_loaded();
+    let state = ServerState::from_env(pool, firecrawl_client()?, server_config)
+        .context("サーバ設定の読み込みに失敗しました")?;
+
+    let addr = std::env::var("SERVER_ADDR").unwrap_or_else(|_| "127.0.0.1:8080".to_string());
+    serve(&addr, state, health).await
+}
+
+#[cfg(feature = "grpc")]
+async fn run_grpc_server(config: &AppConfig) -> Result<()> {
+    use app::grpc::{serve_grpc, ArticleGrpcService};
+
+    let pool = connect_database().await?;
+    let service = ArticleGrpcService::from_env(pool, &config.server, &config.grpc)
+        .context("サーバ設定の読み込みに失敗しました")?;
+
+    let addr = std::env::var("GRPC_ADDR").unwrap_or_else(|_| "127.0.0.1:50051".to_string());
+    serve_grpc(&addr, service).await
+}
+
+async fn run_worker(config: &AppConfig) -> Result<()> {
+    let pool = connect_database().await?;
+    let scraper = article_scraper(config, None, &pool).await?;
+    let cycles = spawn_health_server(&pool, &config.health);
+
+    execute_article_worker(
+        &scraper,
+        &config.article_fetch,
+        &pool,
+        config.article_fetch.worker_poll_interval(),
+        &cycles,
+    )
+    .await
+}
+
+/// 常駐プロセスのヘルスチェック（health.listen_addrの設定時のみ）を起動し、処理結果の記録先を返す
+fn spawn_health_server(pool: &PgPool, health_config: &HealthConfig) -> CycleTracker {
+    let cycles = CycleTracker::default();
+    if let Some(addr) = health_config.listen_addr.clone() {
+        let health = HealthState::new(
+            pool.clone(),
+            health_config.readiness.clone(),
+            Some(cycles.clone()),
+        );
+        health.mark_config_loaded();
+        tokio::spawn(async move {
+            if let Err(e) = serve_health(&addr, health).await {
+                eprintln!("ヘルスチェックでエラーが発生しました: {}", e);
+            }
+        });
+    }
+    cycles
+}
+
+async fn run_feeds_list(config: &FeedsConfig, stats: bool) -> Result<()> {
+    if !stats {
+        match config.storage {
+            FeedStorage::Yaml => {
+                let feeds = search_feeds(None).context("フィード設定の読み込みに失敗しました")?;
+                feeds.iter().for_each(|feed| println!("{}", feed));
+            }
+            FeedStorage::Db => {
+                let pool = connect_database().await?;
+                for record in list_feed_records(&pool).await? {
+                    let state = if record.enabled { "" } else { "（無効）" };
+                    println!("{}{}", record.feed, state);
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    let pool = connect_database().await?;
+    let feeds = list_feeds_with_stats(config, &pool)
+        .await
+        .context("フィード統計の取得に失敗しました")?;
+    format_feed_stats_table(&feeds)
+        .iter()
+        .for_each(|line| println!("{}", line));
+    Ok(())
 }
@@ -0,0 +1,163 @@
+//! 収集機能に依存しない読み取り専用のAPI
+//!
+//! `default-features = false, features = ["reader"]`で依存に追加すると、
+//! Firecrawl SDKやHTTPクライアントなどの収集系の依存なしで記事の検索だけを利用できる。
+//!
+//! ```no_run
+//! use datadoggo::reader::{ArticleQuery, Client};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let client = Client::connect("postgres://localhost/datadoggo").await?;
+//! let articles = client
+//!     .search_articles(ArticleQuery {
+//!         title_pattern: Some("rust -tokio".to_string()),
+//!         limit: Some(10),
+//!         ..Default::default()
+//!     })
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::{article, rss, timeline};
+use anyhow::{Context, Result};
+use futures::Stream;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+pub use crate::core::article::{
+    Article, ArticleCacheConfig, ArticleContent, ArticleContentQuery, ArticleCursor,
+    ArticleMetadata, ArticleQuery, ArticleStatus, SearchArticlesPage,
+};
+pub use crate::core::rss::{ArticleLink, ArticleLinkQuery};
+pub use crate::core::timeline::UrlTimeline;
+
+/// 記事データベースの読み取り用クライアント
+///
+/// マイグレーションは実行しないため、スキーマは収集側で作成済みである必要がある。
+#[derive(Debug, Clone)]
+pub struct Client {
+    pool: PgPool,
+    /// `search_articles`の読み取りキャッシュ（`with_cache`で有効にする）
+    cache: Option<Arc<article::ArticleQueryCache>>,
+}
+
+impl Client {
+    /// データベースURLに接続してクライアントを作成する
+    pub async fn connect(database_url: &str) -> Result<Self> {
+        let pool = PgPool::connect(database_url)
+            .await
+            .context("データベースへの接続に失敗しました")?;
+        Ok(Self::from_pool(pool))
+    }
+
+    /// 環境変数`DATABASE_URL`の接続先でクライアントを作成する
+    pub async fn from_env() -> Result<Self> {
+        let database_url = std::env::var("DATABASE_URL")
+            .context("データベースURLの環境変数DATABASE_URLが設定されていません")?;
+        Self::connect(&database_url).await
+    }
+
+    /// 既存の接続プールからクライアントを作成する
+    pub fn from_pool(pool: PgPool) -> Self {
+        Self { pool, cache: None }
+    }
+
+    /// `search_articles`の結果をTTLの間再利用する（同じ検索が繰り返されるWeb UIなど向け）
+    ///
+    /// 収集側の書き込みはTTLが切れるまで反映されない。
+    pub fn with_cache(mut self, config: &ArticleCacheConfig) -> Self {
+        self.cache = Some(Arc::new(article::ArticleQueryCache::new(config)));
+        self
+    }
+
+    /// 記事リンクと取得状態を検索する
+    pub async fn search_articles(&self, query: ArticleQuery) -> Result<Vec<Article>> {
+        match &self.cache {
+            Some(cache) => cache.search_articles(Some(query), &self.pool).await,
+            None => article::search_articles(Some(query), &self.pool).await,
+        }
+    }
+
+    /// 記事リンクと取得状態を1ページ分検索する（`next_cursor`を次の`after`に指定して続きを取得）
+    pub async fn search_articles_page(&self, query: ArticleQuery) -> Result<SearchArticlesPage> {
+        article::search_articles_page(query, &self.pool).await
+    }
+
+    /// 記事本文を全文検索し、関連度の高い順に返す（`full_text`の指定が必要）
+    pub async fn search_articles_fulltext(&self, query: ArticleQuery) -> Result<Vec<Article>> {
+        article::search_articles_fulltext(query, &self.pool).await
+    }
+
+    /// 取得済みの記事内容を検索する
+    pub async fn search_article_contents(
+        &self,
+        query: ArticleContentQuery,
+    ) -> Result<Vec<ArticleContent>> {
+        article::search_article_contents(Some(query), &self.pool).await
+    }
+
+    /// 記事リンクを検索する
+    pub async fn search_article_links(&self, query: ArticleLinkQuery) -> Result<Vec<ArticleLink>> {
+        rss::search_article_links(Some(query), &self.pool).await
+    }
+
+    /// URLの記事内容を取得する（未取得・ゴミ箱の記事は`None`）
+    pub async fn get_article_content(&self, url: &str) -> Result<Option<ArticleContent>> {
+        let query = ArticleContentQuery {
+            url_pattern: Some(url.to_string()),
+            ..Default::default()
+        };
+        let contents = article::search_article_contents(Some(query), &self.pool).await?;
+        Ok(contents.into_iter().find(|content| content.url == url))
+    }
+
+    /// URLの記事ページの元のHTMLを取得する（`scraper.raw_html`で保存した記事のみ）
+    pub async fn get_article_raw_html(&self, url: &str) -> Result<Option<String>> {
+        article::get_article_raw_html(url, &self.pool).await
+    }
+
+    /// URLの処理履歴を取得する
+    pub async fn url_timeline(&self, url: &str) -> Result<UrlTimeline> {
+        timeline::get_url_timeline(url, &self.pool).await
+    }
+
+    /// 本文を取得できた新着記事を購読する（LISTEN/NOTIFY、`core::article::subscribe_new_articles`）
+    pub async fn subscribe_new_articles(
+        &self,
+    ) -> Result<impl Stream<Item = ArticleMetadata> + Send + 'static> {
+        article::subscribe_new_articles(&self.pool).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(fixtures("../fixtures/article_query_filter.sql"))]
+    async fn test_reader_client(pool: PgPool) -> Result<(), anyhow::Error> {
+        let client = Client::from_pool(pool.clone());
+
+        let all = client.search_articles(ArticleQuery::default()).await?;
+        let expected = article::search_articles(None, &pool).await?;
+        assert_eq!(all.len(), expected.len());
+
+        let links = client
+            .search_article_links(ArticleLinkQuery::default())
+            .await?;
+        assert!(!links.is_empty());
+
+        let content = client
+            .get_article_content("https://example.com/news1")
+            .await?
+            .expect("取得済みの記事");
+        assert_eq!(content.status_code, 200);
+        assert!(client
+            .get_article_content("https://example.com/news")
+            .await?
+            .is_none());
+
+        println!("✅ 読み取り専用クライアントテスト成功");
+        Ok(())
+    }
+}
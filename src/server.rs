@@ -0,0 +1,1030 @@
+use crate::{
+    core::{
+        api_access::{
+            constant_time_eq, find_api_client, load_configured_api_keys, ApiClient, RateLimiter,
+            ServerConfig,
+        },
+        article::{
+            get_article, get_article_chunks, get_article_content_with_client,
+            normalize_article_url, store_article_content, ArticleKey, ChunkOptions,
+        },
+        blocklist::find_blocked_domain,
+        fetch_backend::{self, FetchBackendInput},
+        health::{check_readiness, HealthState},
+        rss::{store_article_link_if_absent, ArticleLink, UNTITLED},
+        schema::Versioned,
+    },
+    infra::{
+        api::firecrawl::FirecrawlClient,
+        metrics::{metrics, METRICS_CONTENT_TYPE},
+        parser::parse_article_url,
+    },
+};
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Path, Query, Request, State},
+    http::{
+        header::AUTHORIZATION, header::CONTENT_TYPE, header::RETRY_AFTER, HeaderMap, StatusCode,
+    },
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post, put},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::PgPool;
+use std::{env, sync::Arc};
+use tokio::net::TcpListener;
+
+/// Webhookで投入されたリンクに付与するsource
+pub const WEBHOOK_SOURCE: &str = "webhook";
+
+/// APIキーを渡すヘッダ
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// `INGEST_TOKEN`で認証したリクエストのクライアント名（レート制限の単位）
+pub const INGEST_TOKEN_CLIENT: &str = "ingest_token";
+
+/// RESTサーバの共有状態
+pub struct ServerState<F: FirecrawlClient> {
+    pub pool: PgPool,
+    pub firecrawl_client: Arc<F>,
+    /// AuthorizationヘッダのBearerトークンとして受け付けるトークン
+    pub ingest_token: String,
+    /// 設定で管理するAPIキーとそのクライアント（DBのapi_keysより先に照合する）
+    pub api_keys: Arc<Vec<(String, ApiClient)>>,
+    /// クライアント毎の1分あたりのリクエスト数の既定の上限（0は無制限）
+    pub rate_limit_per_minute: u32,
+    pub rate_limiter: Arc<RateLimiter>,
+}
+
+// deriveだと`F: Clone`が要求されるため手動で実装する
+impl<F: FirecrawlClient> Clone for ServerState<F> {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            firecrawl_client: Arc::clone(&self.firecrawl_client),
+            ingest_token: self.ingest_token.clone(),
+            api_keys: Arc::clone(&self.api_keys),
+            rate_limit_per_minute: self.rate_limit_per_minute,
+            rate_limiter: Arc::clone(&self.rate_limiter),
+        }
+    }
+}
+
+impl<F: FirecrawlClient> ServerState<F> {
+    /// 環境変数INGEST_TOKENと設定のAPIキー（`server.api_keys`）を読み込んで状態を作成
+    pub fn from_env(pool: PgPool, firecrawl_client: F, config: &ServerConfig) -> Result<Self> {
+        let ingest_token = env::var("INGEST_TOKEN")
+            .context("認証トークンの環境変数INGEST_TOKENが設定されていません")?;
+        if ingest_token.is_empty() {
+            anyhow::bail!("環境変数INGEST_TOKENが空です");
+        }
+
+        Ok(Self {
+            pool,
+            firecrawl_client: Arc::new(firecrawl_client),
+            ingest_token,
+            api_keys: Arc::new(load_configured_api_keys(config)?),
+            rate_limit_per_minute: config.rate_limit_per_minute,
+            rate_limiter: Arc::new(RateLimiter::default()),
+        })
+    }
+
+    /// リクエストのクライアントを認証する（認証できなければ`None`）
+    ///
+    /// `X-API-Key`ヘッダを設定のAPIキー、DBのapi_keysの順に照合し、
+    /// 無ければAuthorizationヘッダのBearerトークンを`INGEST_TOKEN`と照合する。
+    async fn authenticate(&self, headers: &HeaderMap) -> Result<Option<ApiClient>> {
+        if let Some(key) = headers
+            .get(API_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+        {
+            let configured = self
+                .api_keys
+                .iter()
+                .find(|(expected, _)| constant_time_eq(key, expected));
+            if let Some((_, client)) = configured {
+                return Ok(Some(client.clone()));
+            }
+            return find_api_client(key, &self.pool).await;
+        }
+
+        Ok(
+            is_authorized(headers, &self.ingest_token).then(|| ApiClient {
+                name: INGEST_TOKEN_CLIENT.to_string(),
+                rate_limit_per_minute: None,
+            }),
+        )
+    }
+}
+
+// POST /ingest のリクエストボディ
+#[derive(Debug, Deserialize)]
+pub struct IngestRequest {
+    pub url: String,
+    pub title: Option<String>,
+    /// trueの場合、登録後すぐに記事内容を取得する
+    #[serde(default)]
+    pub fetch_now: bool,
+}
+
+// POST /ingest のレスポンスボディ
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IngestResponse {
+    pub url: String,
+    /// 新規登録された場合はtrue（既存URLの場合はfalse）
+    pub created: bool,
+    /// 即時取得を行った場合の記事のステータスコード
+    pub status_code: Option<i32>,
+}
+
+/// RESTサーバのルーティングを構築する
+pub fn build_router<F>(state: ServerState<F>) -> Router
+where
+    F: FirecrawlClient + 'static,
+{
+    Router::new()
+        .route("/ingest", post(ingest::<F>))
+        .route("/articles/{key}", get(get_article_by_key::<F>))
+        .route(
+            "/articles/{key}/chunks",
+            get(get_article_chunks_by_key::<F>),
+        )
+        .route(
+            "/fetch-backends",
+            get(list_fetch_backends::<F>).post(create_fetch_backend::<F>),
+        )
+        .route(
+            "/fetch-backends/{id}",
+            put(update_fetch_backend::<F>).delete(delete_fetch_backend::<F>),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            authorize::<F>,
+        ))
+        .with_state(state)
+}
+
+/// # 概要
+/// APIキー・トークンでクライアントを認証し、クライアント毎のレート制限を適用するミドルウェア。
+///
+/// 認証できなければ401、上限を超えたら`Retry-After`ヘッダ付きの429を返す。
+async fn authorize<F>(State(state): State<ServerState<F>>, request: Request, next: Next) -> Response
+where
+    F: FirecrawlClient + 'static,
+{
+    let client = match state.authenticate(request.headers()).await {
+        Ok(Some(client)) => client,
+        Ok(None) => return error_response(StatusCode::UNAUTHORIZED, "APIキーが不正です"),
+        Err(e) => {
+            tracing::error!(
+                path = %request.uri().path(),
+                error = format!("{:#}", e),
+                "APIキーの照合に失敗"
+            );
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "APIキーの照合に失敗しました",
+            );
+        }
+    };
+
+    let limit = client
+        .rate_limit_per_minute
+        .unwrap_or(state.rate_limit_per_minute);
+    if let Err(retry_after) = state.rate_limiter.check(&client.name, limit) {
+        tracing::warn!(client = %client.name, limit, "リクエスト数の上限を超過");
+        let mut response = error_response(
+            StatusCode::TOO_MANY_REQUESTS,
+            "リクエスト数の上限を超えました",
+        );
+        let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+        response
+            .headers_mut()
+            .insert(RETRY_AFTER, secs.max(1).into());
+        return response;
+    }
+
+    next.run(request).await
+}
+
+/// ヘルスチェック（`GET /healthz`, `GET /readyz`）とメトリクス（`GET /metrics`）のルーティングを構築する
+pub fn health_router(health: HealthState) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .route("/metrics", get(prometheus_metrics))
+        .with_state(health)
+}
+
+/// 指定アドレスでRESTサーバを起動する（ヘルスチェックも同じアドレスで提供する）
+pub async fn serve<F>(addr: &str, state: ServerState<F>, health: HealthState) -> Result<()>
+where
+    F: FirecrawlClient + 'static,
+{
+    let listener = TcpListener::bind(addr)
+        .await
+        .context(format!("アドレスのバインドに失敗: {}", addr))?;
+    tracing::info!(addr, "RESTサーバを起動");
+
+    axum::serve(listener, build_router(state).merge(health_router(health)))
+        .await
+        .context("RESTサーバの実行中にエラーが発生しました")
+}
+
+/// 指定アドレスでヘルスチェック専用のHTTPサーバを起動する（ワーカーモード用）
+pub async fn serve_health(addr: &str, health: HealthState) -> Result<()> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .context(format!("アドレスのバインドに失敗: {}", addr))?;
+    tracing::info!(addr, "ヘルスチェックを起動");
+
+    axum::serve(listener, health_router(health))
+        .await
+        .context("ヘルスチェックの実行中にエラーが発生しました")
+}
+
+/// プロセスの生存確認（リクエストに応答できれば常に200）
+async fn healthz() -> Response {
+    (StatusCode::OK, Json(json!({ "status": "ok" }))).into_response()
+}
+
+/// 処理を受け付けられる状態かどうか（条件を満たさない場合は503）
+async fn readyz(State(health): State<HealthState>) -> Response {
+    let report = check_readiness(&health).await;
+    let status = if report.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report)).into_response()
+}
+
+/// 収集処理のメトリクス（Prometheusのテキスト形式）
+async fn prometheus_metrics() -> Response {
+    match metrics().encode() {
+        Ok(text) => ([(CONTENT_TYPE, METRICS_CONTENT_TYPE)], text).into_response(),
+        Err(e) => {
+            tracing::error!(error = format!("{:#}", e), "メトリクスの変換に失敗");
+            error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "メトリクスの変換に失敗しました",
+            )
+        }
+    }
+}
+
+/// IDまたはURL（パーセントエンコード）で記事を取得する
+async fn get_article_by_key<F>(
+    State(state): State<ServerState<F>>,
+    Path(key): Path<String>,
+) -> Response
+where
+    F: FirecrawlClient + 'static,
+{
+    match get_article(&ArticleKey::parse(&key), &state.pool).await {
+        Ok(Some(article)) => (StatusCode::OK, Json(Versioned::new(article))).into_response(),
+        Ok(None) => error_response(StatusCode::NOT_FOUND, "記事が見つかりません"),
+        Err(e) => {
+            tracing::error!(key, error = format!("{:#}", e), "記事の取得に失敗");
+            error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "記事の取得に失敗しました",
+            )
+        }
+    }
+}
+
+// GET /articles/{key}/chunks のクエリ（省略した値は`ChunkOptions`の既定値）
+#[derive(Debug, Default, Deserialize)]
+pub struct ChunkParams {
+    pub max_tokens: Option<usize>,
+    pub overlap_tokens: Option<usize>,
+    /// 指定した番号のチャンクのみ返す
+    pub index: Option<usize>,
+}
+
+/// IDまたはURLで記事を取得し、本文をチャンクに分割して返す
+async fn get_article_chunks_by_key<F>(
+    State(state): State<ServerState<F>>,
+    Path(key): Path<String>,
+    Query(params): Query<ChunkParams>,
+) -> Response
+where
+    F: FirecrawlClient + 'static,
+{
+    let defaults = ChunkOptions::default();
+    let options = ChunkOptions {
+        max_tokens: params.max_tokens.unwrap_or(defaults.max_tokens),
+        overlap_tokens: params.overlap_tokens.unwrap_or(defaults.overlap_tokens),
+    };
+    let chunks = match get_article_chunks(&ArticleKey::parse(&key), &options, &state.pool).await {
+        Ok(Some(chunks)) => chunks,
+        Ok(None) => return error_response(StatusCode::NOT_FOUND, "記事が見つかりません"),
+        Err(e) => {
+            tracing::error!(
+                key,
+                error = format!("{:#}", e),
+                "記事のチャンクの取得に失敗"
+            );
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "記事の取得に失敗しました",
+            );
+        }
+    };
+    let total = chunks.len();
+    let chunks = match params.index {
+        Some(index) => match chunks.into_iter().nth(index) {
+            Some(chunk) => vec![chunk],
+            None => return error_response(StatusCode::NOT_FOUND, "チャンクが見つかりません"),
+        },
+        None => chunks,
+    };
+    (
+        StatusCode::OK,
+        Json(json!({ "total": total, "chunks": chunks })),
+    )
+        .into_response()
+}
+
+/// 記事取得バックエンドの対応表を、振り分けで優先する順に返す
+async fn list_fetch_backends<F>(State(state): State<ServerState<F>>) -> Response
+where
+    F: FirecrawlClient + 'static,
+{
+    match fetch_backend::list_fetch_backends(&state.pool).await {
+        Ok(rules) => (StatusCode::OK, Json(rules)).into_response(),
+        Err(e) => {
+            tracing::error!(
+                error = format!("{:#}", e),
+                "記事取得バックエンドの対応表の取得に失敗"
+            );
+            error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "記事取得バックエンドの対応表の取得に失敗しました",
+            )
+        }
+    }
+}
+
+/// # 概要
+/// 記事取得バックエンドの対応表に行を追加する。
+///
+/// 入力が不正なら400、同じドメインの行が既にあれば409を返す。
+/// 記事を取得するプロセスは`scraper.fetch_backends_refresh_secs`以内に読み込み直して振り分けに使う。
+async fn create_fetch_backend<F>(
+    State(state): State<ServerState<F>>,
+    Json(input): Json<FetchBackendInput>,
+) -> Response
+where
+    F: FirecrawlClient + 'static,
+{
+    let input = match input.validate() {
+        Ok(input) => input,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &format!("{:#}", e)),
+    };
+    let domain_pattern = input.domain_pattern.clone();
+    match fetch_backend::create_fetch_backend(input, &state.pool).await {
+        Ok(rule) => (StatusCode::CREATED, Json(rule)).into_response(),
+        Err(e) => fetch_backend_write_error(e, None, &domain_pattern),
+    }
+}
+
+/// 記事取得バックエンドの対応表の行を置き換える（行が無ければ404）
+async fn update_fetch_backend<F>(
+    State(state): State<ServerState<F>>,
+    Path(id): Path<i64>,
+    Json(input): Json<FetchBackendInput>,
+) -> Response
+where
+    F: FirecrawlClient + 'static,
+{
+    let input = match input.validate() {
+        Ok(input) => input,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &format!("{:#}", e)),
+    };
+    let domain_pattern = input.domain_pattern.clone();
+    match fetch_backend::update_fetch_backend(id, input, &state.pool).await {
+        Ok(Some(rule)) => (StatusCode::OK, Json(rule)).into_response(),
+        Ok(None) => error_response(
+            StatusCode::NOT_FOUND,
+            "記事取得バックエンドが見つかりません",
+        ),
+        Err(e) => fetch_backend_write_error(e, Some(id), &domain_pattern),
+    }
+}
+
+/// 記事取得バックエンドの対応表の行を削除する（行が無ければ404）
+async fn delete_fetch_backend<F>(
+    State(state): State<ServerState<F>>,
+    Path(id): Path<i64>,
+) -> Response
+where
+    F: FirecrawlClient + 'static,
+{
+    match fetch_backend::delete_fetch_backend(id, &state.pool).await {
+        Ok(true) => StatusCode::NO_CONTENT.into_response(),
+        Ok(false) => error_response(
+            StatusCode::NOT_FOUND,
+            "記事取得バックエンドが見つかりません",
+        ),
+        Err(e) => {
+            tracing::error!(
+                id,
+                error = format!("{:#}", e),
+                "記事取得バックエンドの削除に失敗"
+            );
+            error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "記事取得バックエンドの削除に失敗しました",
+            )
+        }
+    }
+}
+
+/// 記事取得バックエンドの追加・更新のエラーのレスポンス（同じドメインの行がある場合は409）
+fn fetch_backend_write_error(
+    error: anyhow::Error,
+    id: Option<i64>,
+    domain_pattern: &str,
+) -> Response {
+    let duplicated = error
+        .downcast_ref::<sqlx::Error>()
+        .and_then(|e| e.as_database_error())
+        .is_some_and(|e| e.is_unique_violation());
+    if duplicated {
+        return error_response(
+            StatusCode::CONFLICT,
+            "同じドメインの記事取得バックエンドが既にあります",
+        );
+    }
+    tracing::error!(
+        id,
+        domain_pattern,
+        error = format!("{:#}", error),
+        "記事取得バックエンドの保存に失敗"
+    );
+    error_response(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "記事取得バックエンドの保存に失敗しました",
+    )
+}
+
+/// 外部システムから収集対象のURLを受け付ける
+async fn ingest<F>(
+    State(state): State<ServerState<F>>,
+    Json(request): Json<IngestRequest>,
+) -> Response
+where
+    F: FirecrawlClient + 'static,
+{
+    // RSS由来のリンクと同じ記事を指す場合に重複させないよう、URLを正規化してから登録する
+    let url = match parse_article_url(&request.url) {
+        Ok(url) => normalize_article_url(&url),
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &e.to_string()),
+    };
+
+    match find_blocked_domain(&url, &state.pool).await {
+        Ok(None) => {}
+        Ok(Some(blocked)) => {
+            return error_response(
+                StatusCode::FORBIDDEN,
+                &format!("収集禁止ドメインのURLです: {}", blocked.domain),
+            );
+        }
+        Err(e) => {
+            tracing::error!(
+                url,
+                error = format!("{:#}", e),
+                "収集禁止ドメインの確認に失敗"
+            );
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "収集禁止ドメインの確認に失敗しました",
+            );
+        }
+    }
+
+    let article_link = ArticleLink {
+        url: url.clone(),
+        title: request
+            .title
+            .filter(|title| !title.trim().is_empty())
+            .unwrap_or_else(|| UNTITLED.to_string()),
+        pub_date: chrono::Utc::now(),
+        source: WEBHOOK_SOURCE.to_string(),
+        ..Default::default()
+    };
+
+    let created = match store_article_link_if_absent(&article_link, &state.pool).await {
+        Ok(created) => created,
+        Err(e) => {
+            tracing::error!(url, error = format!("{:#}", e), "Webhookリンクの登録に失敗");
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "リンクの登録に失敗しました",
+            );
+        }
+    };
+
+    let mut status_code = None;
+    if request.fetch_now {
+        let fetch_result =
+            get_article_content_with_client(&url, state.firecrawl_client.as_ref()).await;
+        let stored = match fetch_result {
+            Ok(article) => store_article_content(&article, &state.pool)
+                .await
+                .map(|_| article.status_code),
+            Err(e) => Err(e),
+        };
+        match stored {
+            Ok(code) => status_code = Some(code),
+            Err(e) => {
+                tracing::error!(
+                    url,
+                    error = format!("{:#}", e),
+                    "Webhook記事の即時取得に失敗"
+                );
+                return error_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "記事の即時取得に失敗しました",
+                );
+            }
+        }
+    }
+
+    let status = if created {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    let body = IngestResponse {
+        url,
+        created,
+        status_code,
+    };
+    (status, Json(body)).into_response()
+}
+
+/// AuthorizationヘッダのBearerトークンを検証する
+fn is_authorized(headers: &HeaderMap, expected_token: &str) -> bool {
+    let Some(token) = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+
+    constant_time_eq(token, expected_token)
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({ "error": message }))).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::blocklist::block_domain;
+    use crate::infra::api::firecrawl::MockFirecrawlClient;
+    use axum::{body::Body, http::Request};
+    use tower::ServiceExt;
+
+    const TEST_TOKEN: &str = "test-token";
+
+    fn test_state(
+        pool: PgPool,
+        firecrawl_client: MockFirecrawlClient,
+    ) -> ServerState<MockFirecrawlClient> {
+        ServerState {
+            pool,
+            firecrawl_client: Arc::new(firecrawl_client),
+            ingest_token: TEST_TOKEN.to_string(),
+            api_keys: Arc::new(Vec::new()),
+            rate_limit_per_minute: 0,
+            rate_limiter: Arc::new(RateLimiter::default()),
+        }
+    }
+
+    fn test_router(pool: PgPool, firecrawl_client: MockFirecrawlClient) -> Router {
+        build_router(test_state(pool, firecrawl_client))
+    }
+
+    fn ingest_request(token: Option<&str>, body: serde_json::Value) -> Request<Body> {
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri("/ingest")
+            .header("content-type", "application/json");
+        if let Some(token) = token {
+            builder = builder.header(AUTHORIZATION, format!("Bearer {}", token));
+        }
+        builder.body(Body::from(body.to_string())).unwrap()
+    }
+
+    async fn read_json(response: Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    fn get_request_with_token(uri: &str) -> Request<Body> {
+        Request::builder()
+            .uri(uri)
+            .header(AUTHORIZATION, format!("Bearer {}", TEST_TOKEN))
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[sqlx::test]
+    async fn test_get_article_by_id_or_url(pool: PgPool) -> Result<(), anyhow::Error> {
+        let router = test_router(pool.clone(), MockFirecrawlClient::new_success("内容"));
+        let url = "https://example.com/news/1?lang=ja";
+        router
+            .clone()
+            .oneshot(ingest_request(Some(TEST_TOKEN), json!({ "url": url })))
+            .await?;
+        let id = sqlx::query_scalar!("SELECT id FROM article_links WHERE url = $1", url)
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(id.len(), 26);
+
+        let response = router
+            .clone()
+            .oneshot(get_request_with_token(&format!("/articles/{}", id)))
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(read_json(response).await["url"], url);
+
+        let encoded: String = url::form_urlencoded::byte_serialize(url.as_bytes()).collect();
+        let response = router
+            .clone()
+            .oneshot(get_request_with_token(&format!("/articles/{}", encoded)))
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(read_json(response).await["id"], id);
+
+        let response = router
+            .clone()
+            .oneshot(get_request_with_token(
+                "/articles/01ARZ3NDEKTSV4RRFFQ69G5FAV",
+            ))
+            .await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        println!("✅ IDとURLによる記事取得テスト成功");
+        Ok(())
+    }
+
+    #[test]
+    fn test_article_key_parse() {
+        assert_eq!(
+            ArticleKey::parse("01arz3ndektsv4rrffq69g5fav"),
+            ArticleKey::Id("01ARZ3NDEKTSV4RRFFQ69G5FAV".to_string())
+        );
+        assert!(matches!(
+            ArticleKey::parse("https://example.com/a"),
+            ArticleKey::Url(_)
+        ));
+    }
+
+    #[test]
+    fn test_is_authorized() {
+        let mut headers = HeaderMap::new();
+        assert!(!is_authorized(&headers, TEST_TOKEN), "ヘッダなしは拒否");
+
+        headers.insert(AUTHORIZATION, "Bearer test-token".parse().unwrap());
+        assert!(is_authorized(&headers, TEST_TOKEN));
+
+        headers.insert(AUTHORIZATION, "Bearer test-tokem".parse().unwrap());
+        assert!(!is_authorized(&headers, TEST_TOKEN), "異なるトークンは拒否");
+
+        headers.insert(AUTHORIZATION, "test-token".parse().unwrap());
+        assert!(!is_authorized(&headers, TEST_TOKEN), "Bearerなしは拒否");
+    }
+
+    #[sqlx::test]
+    async fn test_api_key_auth_and_rate_limit(pool: PgPool) -> Result<(), anyhow::Error> {
+        use crate::core::api_access::{create_api_key, revoke_api_key};
+
+        let state = ServerState {
+            api_keys: Arc::new(vec![(
+                "configured-key".to_string(),
+                ApiClient {
+                    name: "configured".to_string(),
+                    rate_limit_per_minute: Some(1),
+                },
+            )]),
+            rate_limit_per_minute: 2,
+            ..test_state(pool.clone(), MockFirecrawlClient::new_success("内容"))
+        };
+        let router = build_router(state);
+        let db_key = create_api_key("partner", None, &pool).await?;
+        let request = |key: &str| {
+            Request::builder()
+                .uri("/articles/01ARZ3NDEKTSV4RRFFQ69G5FAV")
+                .header(API_KEY_HEADER, key)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        // DBのキーは既定の上限（2回/分）
+        for _ in 0..2 {
+            let response = router.clone().oneshot(request(&db_key)).await?;
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+        let response = router.clone().oneshot(request(&db_key)).await?;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(response.headers().contains_key(RETRY_AFTER));
+
+        // 設定のキーはキー毎の上限（1回/分）で、クライアント毎に数える
+        let response = router.clone().oneshot(request("configured-key")).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = router.clone().oneshot(request("configured-key")).await?;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // 不正なキー・失効したキーは401
+        let response = router.clone().oneshot(request("unknown-key")).await?;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        revoke_api_key("partner", &pool).await?;
+        let response = router.oneshot(request(&db_key)).await?;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        println!("✅ APIキー認証・レート制限テスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_ingest_rejects_invalid_requests(pool: PgPool) -> Result<(), anyhow::Error> {
+        let router = test_router(pool.clone(), MockFirecrawlClient::new_success("内容"));
+
+        // 認証トークンなし
+        let response = router
+            .clone()
+            .oneshot(ingest_request(
+                None,
+                json!({ "url": "https://example.com/a" }),
+            ))
+            .await?;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        // 不正なURL
+        let response = router
+            .clone()
+            .oneshot(ingest_request(
+                Some(TEST_TOKEN),
+                json!({ "url": "ftp://example.com/a" }),
+            ))
+            .await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // 収集禁止ドメイン
+        block_domain("blocked.example.com", "テスト", &pool).await?;
+        let response = router
+            .oneshot(ingest_request(
+                Some(TEST_TOKEN),
+                json!({ "url": "https://blocked.example.com/a" }),
+            ))
+            .await?;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let count = sqlx::query_scalar!("SELECT COUNT(*) FROM article_links")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(count, Some(0), "拒否されたリクエストが登録されました");
+
+        println!("✅ Webhook不正リクエスト拒否テスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_ingest_registers_webhook_link(pool: PgPool) -> Result<(), anyhow::Error> {
+        let router = test_router(pool.clone(), MockFirecrawlClient::new_success("内容"));
+
+        let response = router
+            .clone()
+            .oneshot(ingest_request(
+                Some(TEST_TOKEN),
+                json!({ "url": "https://example.com/webhook", "title": "Webhook記事" }),
+            ))
+            .await?;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = read_json(response).await;
+        assert_eq!(body["created"], true);
+        assert!(body["status_code"].is_null(), "即時取得していないはず");
+
+        let source = sqlx::query_scalar!(
+            "SELECT source FROM article_links WHERE url = $1",
+            "https://example.com/webhook"
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(source, WEBHOOK_SOURCE);
+
+        // 同じURLの再投入は200で既存扱い
+        let response = router
+            .oneshot(ingest_request(
+                Some(TEST_TOKEN),
+                json!({ "url": "https://example.com/webhook" }),
+            ))
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(read_json(response).await["created"], false);
+
+        println!("✅ Webhookリンク登録テスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_health_endpoints(pool: PgPool) -> Result<(), anyhow::Error> {
+        use crate::core::health::{CycleTracker, ReadinessConfig};
+
+        let get_request = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let cycles = CycleTracker::default();
+        let health = HealthState::new(pool, ReadinessConfig::default(), Some(cycles.clone()));
+        let router = health_router(health.clone());
+
+        let response = router.clone().oneshot(get_request("/healthz")).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = router.clone().oneshot(get_request("/readyz")).await?;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(read_json(response).await["ready"], false);
+
+        health.mark_config_loaded();
+        cycles.record_success();
+        let response = router.clone().oneshot(get_request("/readyz")).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        metrics().links_collected.inc();
+        let response = router.oneshot(get_request("/metrics")).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], METRICS_CONTENT_TYPE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        assert!(String::from_utf8(body.to_vec())?.contains("datadoggo_links_collected_total"));
+
+        println!("✅ ヘルスチェックエンドポイントテスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_ingest_fetch_now(pool: PgPool) -> Result<(), anyhow::Error> {
+        let router = test_router(
+            pool.clone(),
+            MockFirecrawlClient::new_success("即時取得内容"),
+        );
+
+        let response = router
+            .oneshot(ingest_request(
+                Some(TEST_TOKEN),
+                json!({ "url": "https://example.com/now", "fetch_now": true }),
+            ))
+            .await?;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(read_json(response).await["status_code"], 200);
+
+        let content = sqlx::query_scalar!(
+            "SELECT content FROM articles WHERE url = $1",
+            "https://example.com/now"
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(content, "即時取得内容");
+
+        println!("✅ Webhook即時取得テスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_get_article_chunks(pool: PgPool) -> Result<(), anyhow::Error> {
+        let router = test_router(
+            pool.clone(),
+            MockFirecrawlClient::new_success("# 見出し1\n\n本文1\n\n# 見出し2\n\n本文2"),
+        );
+        let url = "https://example.com/chunks";
+        router
+            .clone()
+            .oneshot(ingest_request(
+                Some(TEST_TOKEN),
+                json!({ "url": url, "fetch_now": true }),
+            ))
+            .await?;
+        let encoded: String = url::form_urlencoded::byte_serialize(url.as_bytes()).collect();
+
+        let response = router
+            .clone()
+            .oneshot(get_request_with_token(&format!(
+                "/articles/{}/chunks",
+                encoded
+            )))
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = read_json(response).await;
+        assert_eq!(body["total"], 2);
+        assert_eq!(body["chunks"][1]["heading"], "見出し2");
+        assert_eq!(body["chunks"][1]["text"], "# 見出し2\n\n本文2");
+
+        let response = router
+            .clone()
+            .oneshot(get_request_with_token(&format!(
+                "/articles/{}/chunks?index=0&max_tokens=100",
+                encoded
+            )))
+            .await?;
+        let body = read_json(response).await;
+        assert_eq!(body["chunks"].as_array().map(Vec::len), Some(1));
+        assert_eq!(body["chunks"][0]["heading"], "見出し1");
+
+        let response = router
+            .oneshot(get_request_with_token(&format!(
+                "/articles/{}/chunks?index=5",
+                encoded
+            )))
+            .await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        println!("✅ 記事のチャンク取得テスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_fetch_backends_crud(pool: PgPool) -> Result<(), anyhow::Error> {
+        let router = test_router(pool, MockFirecrawlClient::new_success("内容"));
+        let request = |method: &str, uri: &str, body: Option<serde_json::Value>| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .header(AUTHORIZATION, format!("Bearer {}", TEST_TOKEN))
+                .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+                .unwrap()
+        };
+
+        let response = router
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/fetch-backends",
+                Some(json!({ "domain_pattern": "Example.com", "backend": "local", "options": { "timeout_secs": 10 } })),
+            ))
+            .await?;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let created = read_json(response).await;
+        assert_eq!(created["domain_pattern"], "example.com");
+        assert_eq!(created["options"]["timeout_secs"], 10);
+        let id = created["id"].as_i64().unwrap();
+
+        let response = router
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/fetch-backends",
+                Some(json!({ "domain_pattern": "example.com", "backend": "firecrawl" })),
+            ))
+            .await?;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let response = router
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/fetch-backends",
+                Some(json!({ "domain_pattern": "other.com", "backend": "firecrawl", "options": { "timeout_secs": 10 } })),
+            ))
+            .await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = router
+            .clone()
+            .oneshot(request(
+                "PUT",
+                &format!("/fetch-backends/{}", id),
+                Some(json!({ "domain_pattern": "example.com", "backend": "headless", "priority": 5 })),
+            ))
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = router
+            .clone()
+            .oneshot(request("GET", "/fetch-backends", None))
+            .await?;
+        let rules = read_json(response).await;
+        assert_eq!(rules[0]["backend"], "headless");
+        assert_eq!(rules[0]["priority"], 5);
+
+        let response = router
+            .clone()
+            .oneshot(request("DELETE", &format!("/fetch-backends/{}", id), None))
+            .await?;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = router
+            .oneshot(request("DELETE", &format!("/fetch-backends/{}", id), None))
+            .await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        println!("✅ 記事取得バックエンドのAPIテスト成功");
+        Ok(())
+    }
+}
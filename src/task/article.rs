@@ -1,66 +1,1156 @@
 use crate::{
     core::{
-        article::{get_article_content_with_client, store_article_content, ArticleContent},
-        rss::search_backlog_article_links,
+        article::{
+            debug_log::count_fetch_attempts, get_article_content_with_retry,
+            mark_articles_recrawled, schedule_article_reprocessing, search_recrawl_targets,
+            store_article_contents_batch, store_article_debug_logs, ArticleContent,
+            ArticleDebugLog, ContentPipeline, FetchOutcome, PipelineReport, RetryPolicy,
+        },
+        backlog::{claim_backlog_batch_with, release_backlog_claims},
+        blocklist::load_domain_blocklist,
+        circuit_breaker::{load_domain_circuit_breakers, url_domain, CircuitBreakerConfig},
+        collection_error::{record_collection_errors, NewCollectionError},
+        config::ArticleFetchConfig,
+        ng_word::{load_ng_word_filter, NgWordConfig, NG_WORD_EXCLUDED_STATUS_CODE},
+        rss::{search_backlog_article_links_with_options, ArticleLink},
+        scraper::ScraperClient,
+        storage_quota::load_domain_storage_quotas,
     },
-    infra::api::firecrawl::FirecrawlClient,
+    infra::{
+        api::{
+            http::ReqwestHttpClient,
+            rate_limit::RateLimiter,
+            robots::{RobotsChecker, RobotsConfig},
+        },
+        metrics::{metrics, STAGE_ARTICLE},
+        storage::batcher::{WriteBatcher, WriteBatcherConfig},
+    },
+    task::progress::Progress,
 };
 use anyhow::Result;
+use chrono::Utc;
+use futures::stream::{FuturesUnordered, StreamExt};
 use sqlx::PgPool;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use url::Url;
+
+/// バックログをドメイン毎に均等に取り出すスケジューラ
+///
+/// ドメイン毎のキューからラウンドロビンで取り出す。重みを設定したドメインは
+/// 1巡あたり重みの件数だけ取り出される（未設定のドメインは`default_weight`）。
+#[derive(Debug, Clone)]
+pub struct FairBacklogScheduler {
+    weights: HashMap<String, usize>,
+    default_weight: usize,
+}
+
+impl Default for FairBacklogScheduler {
+    fn default() -> Self {
+        Self {
+            weights: HashMap::new(),
+            default_weight: 1,
+        }
+    }
+}
+
+impl FairBacklogScheduler {
+    /// 指定ドメインの1巡あたりの取り出し件数を設定する（0は1として扱う）
+    pub fn with_weight(mut self, domain: &str, weight: usize) -> Self {
+        self.weights.insert(domain.to_string(), weight.max(1));
+        self
+    }
+
+    /// 未設定ドメインの1巡あたりの取り出し件数を設定する（0は1として扱う）
+    pub fn with_default_weight(mut self, weight: usize) -> Self {
+        self.default_weight = weight.max(1);
+        self
+    }
+
+    /// バックログを処理順に並べ替える
+    ///
+    /// 同一ドメイン内の順序と、ドメインの初出順は元の並びを維持する。
+    pub fn schedule(&self, links: Vec<ArticleLink>) -> Vec<ArticleLink> {
+        let total = links.len();
+        let mut queues: Vec<(String, VecDeque<ArticleLink>)> = Vec::new();
+        for link in links {
+            let domain = link_domain(&link.url);
+            match queues.iter_mut().find(|(d, _)| *d == domain) {
+                Some((_, queue)) => queue.push_back(link),
+                None => queues.push((domain, VecDeque::from([link]))),
+            }
+        }
+
+        let mut scheduled = Vec::with_capacity(total);
+        while scheduled.len() < total {
+            for (domain, queue) in queues.iter_mut() {
+                let weight = self
+                    .weights
+                    .get(domain.as_str())
+                    .copied()
+                    .unwrap_or(self.default_weight);
+                let take = weight.min(queue.len());
+                scheduled.extend(queue.drain(..take));
+            }
+        }
+        scheduled
+    }
+}
 
-/// バックログ対象リンクから処理待ちの記事を収集してDBに保存する
-pub async fn task_collect_articles<F: FirecrawlClient>(
-    firecrawl_client: &F,
+/// URLからスケジューリング用のドメインを取り出す（解析できない場合は空文字）
+fn link_domain(url: &str) -> String {
+    Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+// 適応的並列度制御の設定
+#[derive(Debug, Clone)]
+pub struct AdaptiveConcurrencyConfig {
+    /// 並列度の下限
+    pub min: usize,
+    /// 並列度の上限
+    pub max: usize,
+    /// 開始時の並列度
+    pub initial: usize,
+    /// これを超えるレスポンスタイムは過負荷とみなす
+    pub target_latency: Duration,
+    /// 直近の取得結果のエラー率がこれを超えたら過負荷とみなす
+    pub max_error_rate: f64,
+    /// エラー率を計算する直近の取得件数
+    pub window: usize,
+    /// 過負荷時に並列度に掛ける係数
+    pub decrease_factor: f64,
+}
+
+impl Default for AdaptiveConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            min: 1,
+            max: 8,
+            initial: 2,
+            target_latency: Duration::from_secs(10),
+            max_error_rate: 0.2,
+            window: 20,
+            decrease_factor: 0.5,
+        }
+    }
+}
+
+/// レスポンスタイムとエラー率から並列度を増減する制御（AIMD方式）
+///
+/// 過負荷でなければ現在の並列度と同じ件数の完了毎に1ずつ増やし（加算増加）、
+/// 過負荷を検知したら`decrease_factor`倍に減らす（乗算減少）。
+/// 減少直後は、減少前から実行中だった取得の結果で続けて減らさないよう、
+/// 減少前の並列度の件数が完了するまで減少を保留する。
+#[derive(Debug, Clone)]
+pub struct AdaptiveConcurrency {
+    config: AdaptiveConcurrencyConfig,
+    limit: usize,
+    completed_since_increase: usize,
+    decrease_cooldown: usize,
+    recent_errors: VecDeque<bool>,
+}
+
+impl AdaptiveConcurrency {
+    pub fn new(config: AdaptiveConcurrencyConfig) -> Self {
+        let min = config.min.max(1);
+        let max = config.max.max(min);
+        let config = AdaptiveConcurrencyConfig { min, max, ..config };
+        Self {
+            limit: config.initial.clamp(min, max),
+            config,
+            completed_since_increase: 0,
+            decrease_cooldown: 0,
+            recent_errors: VecDeque::new(),
+        }
+    }
+
+    /// 現在の並列度
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// 直近の取得結果のエラー率
+    pub fn error_rate(&self) -> f64 {
+        if self.recent_errors.is_empty() {
+            return 0.0;
+        }
+        let errors = self.recent_errors.iter().filter(|e| **e).count();
+        errors as f64 / self.recent_errors.len() as f64
+    }
+
+    /// 取得の完了を記録し、更新後の並列度を返す
+    pub fn on_complete(&mut self, latency: Duration, failed: bool) -> usize {
+        self.recent_errors.push_back(failed);
+        while self.recent_errors.len() > self.config.window.max(1) {
+            self.recent_errors.pop_front();
+        }
+        self.decrease_cooldown = self.decrease_cooldown.saturating_sub(1);
+
+        let overloaded = failed && self.error_rate() > self.config.max_error_rate
+            || latency > self.config.target_latency;
+
+        if overloaded {
+            if self.decrease_cooldown == 0 {
+                let decreased = (self.limit as f64 * self.config.decrease_factor) as usize;
+                self.decrease_cooldown = self.limit;
+                self.limit = decreased.clamp(self.config.min, self.config.max);
+                self.completed_since_increase = 0;
+            }
+        } else if !failed {
+            self.completed_since_increase += 1;
+            if self.completed_since_increase >= self.limit {
+                self.limit = (self.limit + 1).min(self.config.max);
+                self.completed_since_increase = 0;
+            }
+        }
+        self.limit
+    }
+}
+
+impl AdaptiveConcurrencyConfig {
+    /// 記事取得の設定から並列度制御の設定を作成する
+    ///
+    /// 自動調整しない場合は下限・上限・開始時をすべて`max_concurrency`にして並列度を固定する。
+    pub fn from_fetch_config(config: &ArticleFetchConfig) -> Self {
+        let max = config.max_concurrency.max(1);
+        let defaults = Self::default();
+        if config.adaptive {
+            Self {
+                max,
+                initial: defaults.initial.min(max),
+                ..defaults
+            }
+        } else {
+            Self {
+                min: max,
+                max,
+                initial: max,
+                ..defaults
+            }
+        }
+    }
+}
+
+/// # 概要
+/// 記事取得のレート制限と同時に取得する件数の上限（複数の収集処理で共有できる）。
+///
+/// `requests_per_minute`のレートリミッタと`max_concurrency`件の取得枠を持ち、
+/// クローンは同じレートリミッタと取得枠を共有する。複数グループのワークフローを並行実行する場合は
+/// 1つを全グループに渡し、グループ数に関わらずスクレイパーへの負荷を設定の範囲に収める。
+#[derive(Debug, Clone)]
+pub struct FetchBudget {
+    rate_limiter: Option<Arc<Mutex<RateLimiter>>>,
+    permits: Arc<Semaphore>,
+}
+
+impl FetchBudget {
+    /// 記事取得の設定の`requests_per_minute` / `max_concurrency`から作成する
+    pub fn new(config: &ArticleFetchConfig) -> Self {
+        Self {
+            rate_limiter: config
+                .requests_per_minute
+                .map(|rpm| Arc::new(Mutex::new(RateLimiter::per_minute(rpm)))),
+            permits: Arc::new(Semaphore::new(config.max_concurrency.max(1))),
+        }
+    }
+
+    /// レート制限の次の送出枠を予約する（レート制限しない場合は`None`）
+    fn reserve(&self) -> Option<tokio::time::Instant> {
+        self.rate_limiter
+            .as_ref()
+            .map(|limiter| limiter.lock().unwrap_or_else(|e| e.into_inner()).reserve())
+    }
+
+    /// 取得枠が空くまで待って確保する
+    async fn acquire(&self) -> OwnedSemaphorePermit {
+        self.permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("記事取得の枠のセマフォは閉じない")
+    }
+
+    /// 空いている取得枠があれば確保する
+    fn try_acquire(&self) -> Option<OwnedSemaphorePermit> {
+        self.permits.clone().try_acquire_owned().ok()
+    }
+}
+
+/// バックログ対象リンクから処理待ちの記事を収集してDBに保存する（既定の取得設定）
+pub async fn task_collect_articles<S: ScraperClient>(scraper: &S, pool: &PgPool) -> Result<()> {
+    task_collect_articles_with(scraper, &ArticleFetchConfig::default(), pool).await
+}
+
+/// # 概要
+/// バックログ対象リンクから処理待ちの記事を収集してDBに保存する。
+///
+/// 記事は最大`max_concurrency`件を並列に取得し、`adaptive`の場合は`AdaptiveConcurrency`で
+/// 並列度を自動調整する。`requests_per_minute`を指定するとスクレイパーへのリクエストを
+/// その間隔に制限する。`debug`で対象にした記事は処理ログを記事に添付して保存する。
+/// `backlog`を指定すると対象のリンクを取得元で絞り込み、`backlog_options`で1回に処理する件数と順序を決める。
+/// 現在の並列度はスパンの`concurrency`フィールドに、
+/// 取得失敗率が高く遮断したドメイン数は`open_circuits`フィールドに記録する。
+pub async fn task_collect_articles_with<S: ScraperClient>(
+    scraper: &S,
+    config: &ArticleFetchConfig,
+    pool: &PgPool,
+) -> Result<()> {
+    task_collect_articles_with_budget(scraper, config, &FetchBudget::new(config), pool).await
+}
+
+/// # 概要
+/// 他の収集処理と共有する`budget`のレート制限・取得枠の範囲で、バックログ対象リンクから記事を収集する。
+///
+/// `budget`以外は`task_collect_articles_with`と同じ（`config`の`max_concurrency` /
+/// `requests_per_minute`は`budget`の作成時に使う）。
+#[tracing::instrument(
+    skip_all,
+    fields(concurrency = tracing::field::Empty, open_circuits = tracing::field::Empty)
+)]
+pub async fn task_collect_articles_with_budget<S: ScraperClient>(
+    scraper: &S,
+    config: &ArticleFetchConfig,
+    budget: &FetchBudget,
+    pool: &PgPool,
+) -> Result<()> {
+    collect_articles(scraper, config, budget, None::<fn(Progress)>, pool).await
+}
+
+/// # 概要
+/// バックログ対象リンクから処理待ちの記事を収集してDBに保存し、リンクを1件処理する毎に`progress`へ進捗を通知する。
+///
+/// 総数は収集禁止ドメインを除いたバックログの件数で、取得を終えた記事のほか、
+/// robots.txtで禁止された記事とサーキットブレーカで取得しなかったリンクも処理済みとして数える。
+/// それ以外は`task_collect_articles_with`と同じ。
+pub async fn task_collect_articles_with_progress<S: ScraperClient>(
+    scraper: &S,
+    config: &ArticleFetchConfig,
+    progress: Option<impl Fn(Progress)>,
+    pool: &PgPool,
+) -> Result<()> {
+    collect_articles(scraper, config, &FetchBudget::new(config), progress, pool).await
+}
+
+/// バックログ対象リンクを検索して`budget`の範囲で記事を収集する
+async fn collect_articles<S: ScraperClient>(
+    scraper: &S,
+    config: &ArticleFetchConfig,
+    budget: &FetchBudget,
+    progress: Option<impl Fn(Progress)>,
     pool: &PgPool,
 ) -> Result<()> {
-    println!("--- 記事内容取得開始 ---");
     // 未処理のリンクを取得（articleテーブルに存在しないarticle_linkを取得）
-    let unprocessed_links = search_backlog_article_links(pool).await?;
-    println!("未処理リンク数: {}件", unprocessed_links.len());
+    let unprocessed_links =
+        search_backlog_article_links_with_options(&config.backlog, &config.backlog_options, pool)
+            .await?;
+    tracing::info!(links = unprocessed_links.len(), "記事内容取得開始");
+    let robots = robots_checker(&config.robots);
+    collect_backlog_links(
+        unprocessed_links,
+        scraper,
+        config,
+        budget,
+        robots.as_ref(),
+        progress.as_ref(),
+        false,
+        pool,
+    )
+    .await?;
+    tracing::info!("記事内容取得完了");
+    Ok(())
+}
+
+/// 複数ワーカーでバックログを分担して記事を収集する
+///
+/// 並列度・レート制限・再処理などの取得の設定は`task_collect_articles_with`と同じく`config`を使う。
+/// `claim_backlog_batch_with`で`config.backlog`の絞り込みに合う、他のワーカーと重複しないリンクを
+/// `config.backlog_options.order`の順に`batch_size`件ずつクレームして取得・保存し、
+/// クレームできるリンクが無くなったら終了する（`backlog_options.limit`は使わない）。
+/// 取得に失敗したリンクを同じ実行中に再クレームしないよう、クレームは終了時にまとめて解放する。
+/// 処理中に落ちた場合のクレームは期限切れ後に他のワーカーが取り直す。
+#[tracing::instrument(
+    skip_all,
+    fields(
+        worker_id = %worker_id,
+        concurrency = tracing::field::Empty,
+        open_circuits = tracing::field::Empty
+    )
+)]
+pub async fn task_collect_claimed_articles<S: ScraperClient>(
+    scraper: &S,
+    config: &ArticleFetchConfig,
+    pool: &PgPool,
+    worker_id: &str,
+    batch_size: i64,
+) -> Result<()> {
+    let robots = robots_checker(&config.robots);
+    collect_claimed_articles(
+        scraper,
+        config,
+        robots.as_ref(),
+        pool,
+        worker_id,
+        batch_size,
+    )
+    .await
+}
+
+/// バックログをクレームしながら記事を収集する（`robots`は`config.robots`から作成したチェッカ）
+async fn collect_claimed_articles<S: ScraperClient>(
+    scraper: &S,
+    config: &ArticleFetchConfig,
+    robots: Option<&RobotsChecker>,
+    pool: &PgPool,
+    worker_id: &str,
+    batch_size: i64,
+) -> Result<()> {
+    tracing::info!("記事内容取得開始");
+    // レート制限と取得枠はバッチをまたいで共有する
+    let budget = FetchBudget::new(config);
+    let mut claimed_urls: Vec<String> = Vec::new();
+    let result = async {
+        loop {
+            let claimed = claim_backlog_batch_with(
+                pool,
+                worker_id,
+                batch_size,
+                &config.backlog,
+                &config.backlog_options,
+            )
+            .await?;
+            if claimed.is_empty() {
+                return Ok(());
+            }
+            tracing::info!(links = claimed.len(), "バックログをクレーム");
+            claimed_urls.extend(claimed.iter().map(|link| link.url.clone()));
+            collect_backlog_links(
+                claimed,
+                scraper,
+                config,
+                &budget,
+                robots,
+                None::<&fn(Progress)>,
+                false,
+                pool,
+            )
+            .await?;
+        }
+    }
+    .await;
+
+    release_backlog_claims(pool, worker_id, &claimed_urls).await?;
+    tracing::info!(links = claimed_urls.len(), "記事内容取得完了");
+    result
+}
+
+/// # 概要
+/// `article_fetch.recrawl`のポリシーに従って取得済みの記事を再クロールし、対象にした記事数を返す。
+///
+/// 並列度とレート制限は`recrawl.max_concurrency` / `requests_per_minute`を使い、
+/// それ以外（再試行・保存・robots.txt・NGワードなど）は通常の記事取得と同じ設定で取得する。
+/// 本文が変わった記事だけを更新し、取得できなかった記事は保存済みの本文を残す。
+/// 対象にした記事は取得の成否に関わらず`recrawled_at`を更新し、次の間隔まで対象から外す。
+#[tracing::instrument(
+    skip_all,
+    fields(concurrency = tracing::field::Empty, open_circuits = tracing::field::Empty)
+)]
+pub async fn task_recrawl_articles<S: ScraperClient>(
+    scraper: &S,
+    config: &ArticleFetchConfig,
+    pool: &PgPool,
+) -> Result<usize> {
+    let policy = &config.recrawl;
+    let targets = search_recrawl_targets(policy, pool).await?;
+    if targets.is_empty() {
+        tracing::info!("再クロール対象の記事なし");
+        return Ok(0);
+    }
+    tracing::info!(articles = targets.len(), "再クロール開始");
+    let urls: Vec<String> = targets.iter().map(|link| link.url.clone()).collect();
+    let recrawl_config = ArticleFetchConfig {
+        max_concurrency: policy.max_concurrency.max(1),
+        requests_per_minute: policy.requests_per_minute,
+        ..config.clone()
+    };
+    let robots = robots_checker(&recrawl_config.robots);
+    let result = collect_backlog_links(
+        targets,
+        scraper,
+        &recrawl_config,
+        &FetchBudget::new(&recrawl_config),
+        robots.as_ref(),
+        None::<&fn(Progress)>,
+        true,
+        pool,
+    )
+    .await;
+
+    mark_articles_recrawled(&urls, pool).await?;
+    result?;
+    tracing::info!(articles = urls.len(), "再クロール完了");
+    Ok(urls.len())
+}
+
+/// robots.txtの判定を有効にしている場合に、判定に使うチェッカを作成する
+pub(crate) fn robots_checker(config: &RobotsConfig) -> Option<RobotsChecker> {
+    config
+        .enabled
+        .then(|| RobotsChecker::new(Arc::new(ReqwestHttpClient::new()), config))
+}
+
+/// 指定したバックログのリンクから記事を並列に取得してDBに保存する
+///
+/// 取得は`budget`のレート制限と取得枠の範囲で行い、並列度は`config`に従って調整する。
+/// 取得失敗率の高いドメインはサーキットブレーカで遮断し、そのリンクは取得せずに残す。
+/// `robots`を指定するとrobots.txtで禁止されたリンクは取得せず、
+/// `ROBOTS_DISALLOWED_STATUS_CODE`の記事として記録する。
+/// 取得に失敗した記事は`reprocess`のポリシーに従って次の再処理時刻を設定する。
+/// `storage_quota`で保存容量の上限を超えたドメインの記事は抜粋するか保存を拒否する。
+/// `progress`にはリンクを1件処理する毎に進捗を通知する。
+/// `keep_existing`（再クロール）の場合は取得できなかった記事を保存せず、保存済みの本文を残す。
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn collect_backlog_links<S: ScraperClient, P: Fn(Progress)>(
+    unprocessed_links: Vec<ArticleLink>,
+    scraper: &S,
+    config: &ArticleFetchConfig,
+    budget: &FetchBudget,
+    robots: Option<&RobotsChecker>,
+    progress: Option<&P>,
+    keep_existing: bool,
+    pool: &PgPool,
+) -> Result<()> {
+    // 収集禁止ドメインのリンクは取得しない
+    let (unprocessed_links, blocked) = load_domain_blocklist(pool)
+        .await?
+        .retain_allowed(unprocessed_links, |link| &link.url);
+    if blocked > 0 {
+        tracing::info!(blocked, "収集禁止ドメインのため除外");
+    }
+    // 特定ドメインに偏らないよう、ドメイン毎に交互に処理する
+    let unprocessed_links = FairBacklogScheduler::default().schedule(unprocessed_links);
+    let total = unprocessed_links.len();
+    let mut completed = 0;
+    let mut notify_progress = |url: &str| {
+        completed += 1;
+        if let Some(progress) = progress {
+            progress(Progress {
+                completed,
+                total,
+                url: url.to_string(),
+            });
+        }
+    };
+
+    // 取得した本文の加工（トラッキングリンク除去など）とNGワードの検出
+    let pipeline = load_content_pipeline(&config.ng_words, pool).await?;
+    let mut report = PipelineReport::default();
+
+    // 記事の保存は一定件数・一定時間毎にまとめて行う（1バッチ1回のUPSERT）
+    let write_pool = pool.clone();
+    let batch_size = config.write_batch.batch_size.max(1);
+    let batcher = WriteBatcher::spawn(
+        WriteBatcherConfig {
+            max_batch_size: batch_size,
+            flush_interval: Duration::from_millis(config.write_batch.flush_interval_ms.max(1)),
+            ..Default::default()
+        },
+        move |articles: Vec<ArticleContent>| {
+            let pool = write_pool.clone();
+            async move {
+                store_article_contents_batch(&articles, batch_size, &pool)
+                    .await
+                    .map(|_| ())
+            }
+        },
+    );
+
+    let mut concurrency =
+        AdaptiveConcurrency::new(AdaptiveConcurrencyConfig::from_fetch_config(config));
+    let span = tracing::Span::current();
+    span.record("concurrency", concurrency.limit());
+    metrics()
+        .article_fetch_concurrency
+        .set(concurrency.limit() as i64);
+
+    // 前回までの取得履歴から、失敗の続いているドメインの遮断状態を引き継ぐ
+    let mut breakers = load_domain_circuit_breakers(CircuitBreakerConfig::default(), pool).await?;
+    span.record("open_circuits", breakers.open_count(Utc::now()));
 
-    for article_link in unprocessed_links {
-        println!("記事処理中: {}", article_link.url);
+    // ドメイン毎の保存容量の上限（超えた記事は抜粋保存または保存拒否）
+    let mut quotas = load_domain_storage_quotas(&config.storage_quota, pool).await?;
 
-        let article_result =
-            get_article_content_with_client(&article_link.url, firecrawl_client).await;
+    // デバッグモードの対象記事の処理ログ（取得中はURL毎に保持し、保存後にまとめて添付する）
+    let mut debug_logs: HashMap<String, ArticleDebugLog> = HashMap::new();
+    let mut finished_debug_logs: Vec<(String, ArticleDebugLog)> = Vec::new();
+    // 保存後に再処理の予定を更新するための取得結果
+    let mut outcomes: Vec<FetchOutcome> = Vec::new();
+    // 取得に失敗した記事（保存後にまとめてcollection_errorsに記録する）
+    let mut errors: Vec<NewCollectionError> = Vec::new();
+    let mut robots_disallowed = 0;
 
-        match article_result {
-            Ok(article) => match store_article_content(&article, pool).await {
-                Ok(_) => {
-                    println!("  記事保存完了");
+    let mut pending = unprocessed_links.into_iter().peekable();
+    let mut in_flight = FuturesUnordered::new();
+    // 確保した取得枠（robots.txtやサーキットブレーカで取得しなかったリンクの分は次のリンクに使う）
+    let mut permit = None;
+    loop {
+        while in_flight.len() < concurrency.limit() {
+            if pending.peek().is_none() {
+                permit = None;
+                break;
+            }
+            if permit.is_none() {
+                // 取得中の記事がある間は枠を待たずに完了を処理する（枠は取得の完了時に返る）
+                permit = if in_flight.is_empty() {
+                    Some(budget.acquire().await)
+                } else {
+                    budget.try_acquire()
+                };
+                if permit.is_none() {
+                    break;
                 }
-                Err(e) => {
-                    eprintln!("  記事保存エラー: {}", e);
+            }
+            let Some(article_link) = pending.next() else {
+                break;
+            };
+            if !breakers.allow(&url_domain(&article_link.url), Utc::now()) {
+                notify_progress(&article_link.url);
+                continue;
+            }
+            if let Some(robots) = robots {
+                if !robots.is_allowed(&article_link.url).await {
+                    tracing::info!(url = %article_link.url, "robots.txtで禁止されているため取得しない");
+                    if keep_existing {
+                        notify_progress(&article_link.url);
+                        continue;
+                    }
+                    let article = ArticleContent::robots_disallowed(&article_link.url);
+                    outcomes.push(FetchOutcome::from(&article));
+                    batcher.push(article).await?;
+                    robots_disallowed += 1;
+                    notify_progress(&article_link.url);
+                    continue;
                 }
-            },
-            Err(e) => {
-                eprintln!("  記事取得エラー: {}", e);
-
-                // エラーが発生した場合も、status_codeを記録してスキップ
-                let error_article = ArticleContent {
-                    url: article_link.url,
-                    timestamp: chrono::Utc::now(),
-                    status_code: 500, // エラー用のステータスコード
-                    content: format!("取得エラー: {}", e),
-                };
+            }
+            let start_at = budget.reserve();
+            if config.debug.should_trace(&article_link.url) {
+                let log = start_debug_log(&article_link.url, start_at, scraper, pool).await;
+                debug_logs.insert(article_link.url.clone(), log);
+            }
+            let slot = permit.take();
+            let fetch = fetch_article(article_link.url, start_at, scraper, &config.retry);
+            in_flight.push(async move {
+                let fetched = fetch.await;
+                drop(slot);
+                fetched
+            });
+        }
+        let Some((url, result, latency)) = in_flight.next().await else {
+            break;
+        };
+        let mut debug_log = debug_logs.remove(&url);
+        if let Some(log) = debug_log.as_mut() {
+            record_fetch_result(log, &result, latency);
+        }
 
-                if let Err(store_err) = store_article_content(&error_article, pool).await {
-                    eprintln!("  エラー記事の保存に失敗: {}", store_err);
-                }
+        let failed = !matches!(&result, Ok(article) if article.is_success());
+        let domain = url_domain(&url);
+        if let Some(state) = breakers.record(&domain, !failed, Utc::now()) {
+            let open_circuits = breakers.open_count(Utc::now());
+            span.record("open_circuits", open_circuits);
+            tracing::warn!(
+                domain = %domain,
+                state = state.as_str(),
+                failure_rate = breakers.failure_rate(&domain),
+                open_circuits,
+                "サーキットブレーカの状態を変更"
+            );
+            if let Some(log) = debug_log.as_mut() {
+                log.warn(
+                    "circuit_breaker",
+                    format!("{}のサーキットブレーカ: {}", domain, state.as_str()),
+                );
             }
         }
+        let previous = concurrency.limit();
+        let current = concurrency.on_complete(latency, failed);
+        if current != previous {
+            span.record("concurrency", current);
+            metrics().article_fetch_concurrency.set(current as i64);
+            tracing::info!(previous, concurrency = current, "並列度を変更");
+        }
+
+        let article = match debug_log {
+            Some(mut log) => {
+                // 対象記事はパイプラインの統計を記事単位で記録してから全体に加算する
+                let mut article_report = PipelineReport::default();
+                let article = finish_article(&url, result, &pipeline, &mut article_report);
+                log.record_pipeline(&article_report);
+                report.merge(article_report);
+                finished_debug_logs.push((url, log));
+                article
+            }
+            None => finish_article(&url, result, &pipeline, &mut report),
+        };
+        if let Some(category) = article.error_category() {
+            errors.push(NewCollectionError::article(
+                &article.url,
+                category,
+                &article.content,
+            ));
+        }
+        if keep_existing && !article.is_success() {
+            notify_progress(&article.url);
+            continue;
+        }
+        let article = quotas.apply(article);
+        outcomes.push(FetchOutcome::from(&article));
+        notify_progress(&article.url);
+        batcher.push(article).await?;
     }
 
-    println!("--- 記事内容取得完了 ---");
+    let stats = batcher.shutdown().await?;
+    if stats.failed > 0 {
+        errors.push(NewCollectionError::task(
+            "store_article_contents_batch",
+            &anyhow::anyhow!("記事の保存に失敗: {}件", stats.failed),
+        ));
+    }
+    if !finished_debug_logs.is_empty() {
+        // 処理ログの保存に失敗しても記事の収集は失敗扱いにしない
+        match store_article_debug_logs(&finished_debug_logs, pool).await {
+            Ok(()) => tracing::info!(articles = finished_debug_logs.len(), "処理ログを記録"),
+            Err(e) => {
+                tracing::error!(error = format!("{:#}", e), "処理ログの保存に失敗");
+                errors.push(NewCollectionError::task("store_article_debug_logs", &e));
+            }
+        }
+    }
+    // 再処理の予定の更新に失敗しても記事の収集は失敗扱いにしない（次回はすぐに再処理される）
+    match schedule_article_reprocessing(&outcomes, &config.reprocess, pool).await {
+        Ok(summary) if summary.scheduled + summary.exhausted > 0 => tracing::info!(
+            scheduled = summary.scheduled,
+            exhausted = summary.exhausted,
+            "エラー記事の再処理を予定"
+        ),
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!(error = format!("{:#}", e), "再処理予定の更新に失敗");
+            errors.push(NewCollectionError::task(
+                "schedule_article_reprocessing",
+                &e,
+            ));
+        }
+    }
+    record_collection_errors(&errors, pool).await;
+    let (excerpted, rejected) = quotas.counts();
+    tracing::info!(
+        written = stats.written,
+        failed = stats.failed,
+        flush_count = stats.flush_count,
+        excerpted,
+        rejected,
+        robots_disallowed,
+        concurrency = concurrency.limit(),
+        "記事保存完了"
+    );
+    for line in breakers.format_lines(Utc::now()) {
+        tracing::info!("{}", line);
+    }
+    for line in report.format_lines() {
+        tracing::info!("{}", line);
+    }
     Ok(())
 }
 
+/// デバッグモードの対象記事の処理ログを、取得前の情報（取得試行の回数・レートリミットの待ち）で開始する
+async fn start_debug_log<S: ScraperClient>(
+    url: &str,
+    start_at: Option<tokio::time::Instant>,
+    scraper: &S,
+    pool: &PgPool,
+) -> ArticleDebugLog {
+    let mut log = ArticleDebugLog::default();
+    match count_fetch_attempts(url, pool).await {
+        Ok(0) => log.info("attempt", "初回の取得"),
+        Ok(count) => log.warn(
+            "attempt",
+            format!("リトライ（過去の取得試行: {}回）", count),
+        ),
+        Err(e) => log.warn("attempt", format!("取得試行回数の集計に失敗: {:#}", e)),
+    }
+    let wait = start_at
+        .map(|at| at.saturating_duration_since(tokio::time::Instant::now()))
+        .unwrap_or_default();
+    log.info(
+        "request",
+        format!(
+            "{}にリクエスト（レートリミットの待ち: {}ms）",
+            scraper.backend_name_for(url),
+            wait.as_millis()
+        ),
+    );
+    log
+}
+
+/// 取得結果を処理ログに記録する
+fn record_fetch_result(
+    log: &mut ArticleDebugLog,
+    result: &Result<ArticleContent>,
+    latency: Duration,
+) {
+    match result {
+        Ok(article) if article.is_success() => log.info(
+            "response",
+            format!(
+                "取得成功（{}ms、本文{}文字）",
+                latency.as_millis(),
+                article.content.chars().count()
+            ),
+        ),
+        Ok(article) => log.error(
+            "response",
+            format!(
+                "取得失敗（{}ms、status_code={}）: {}",
+                latency.as_millis(),
+                article.status_code,
+                article.content
+            ),
+        ),
+        Err(e) => log.error(
+            "response",
+            format!("取得エラー（{}ms）: {:#}", latency.as_millis(), e),
+        ),
+    }
+}
+
+/// 記事本文の加工パイプライン（標準のステップに、NGワードがあればNGワードの検出を加える）
+async fn load_content_pipeline(config: &NgWordConfig, pool: &PgPool) -> Result<ContentPipeline> {
+    let filter = load_ng_word_filter(config, pool).await?;
+    let pipeline = ContentPipeline::default();
+    Ok(if filter.is_empty() {
+        pipeline
+    } else {
+        pipeline.with_step(filter)
+    })
+}
+
+/// 1件の記事を取得し、レスポンスタイムと合わせて返す
+///
+/// `start_at`を指定した場合はその時刻まで待ってから取得する（レートリミットの予約枠）。
+/// 待ち時間はレスポンスタイムに含めない。
+/// 一時的なエラーは`retry`に従って再試行する（再試行の待ちはレスポンスタイムに含める）。
+#[tracing::instrument(skip_all, fields(url = %url))]
+async fn fetch_article<S: ScraperClient>(
+    url: String,
+    start_at: Option<tokio::time::Instant>,
+    scraper: &S,
+    retry: &RetryPolicy,
+) -> (String, Result<ArticleContent>, Duration) {
+    if let Some(start_at) = start_at {
+        tokio::time::sleep_until(start_at).await;
+    }
+    tracing::debug!("記事処理中");
+    let started = Instant::now();
+    let result = get_article_content_with_retry(&url, scraper, retry).await;
+    (url, result, started.elapsed())
+}
+
+/// 取得結果を加工して保存用の記事内容にする
+fn finish_article(
+    url: &str,
+    result: Result<ArticleContent>,
+    pipeline: &ContentPipeline,
+    report: &mut PipelineReport,
+) -> ArticleContent {
+    match result {
+        Ok(mut article) => {
+            if article.is_success() {
+                article.content = pipeline.run(&article.content, report);
+                if let Some(reason) = pipeline.exclusion_reason(&article.content) {
+                    tracing::info!(url, reason = %reason, "保存対象から除外");
+                    report.excluded += 1;
+                    return ArticleContent {
+                        status_code: NG_WORD_EXCLUDED_STATUS_CODE,
+                        content: reason,
+                        raw_html: None,
+                        ..article
+                    };
+                }
+                metrics().articles_fetched.inc();
+            }
+            article
+        }
+        Err(e) => {
+            tracing::warn!(url, error = format!("{:#}", e), "記事取得エラー");
+            metrics().record_error(STAGE_ARTICLE);
+
+            // エラーが発生した場合も、status_codeを記録してスキップ
+            ArticleContent::error_placeholder(url, format!("取得エラー: {}", e))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::article::{RecrawlPolicy, ROBOTS_DISALLOWED_STATUS_CODE};
+    use crate::core::rss::search_backlog_article_links_with;
     use crate::infra::api::firecrawl::MockFirecrawlClient;
     use sqlx::PgPool;
 
+    fn backlog_link(url: &str) -> ArticleLink {
+        ArticleLink {
+            url: url.to_string(),
+            title: url.to_string(),
+            pub_date: chrono::Utc::now(),
+            source: "test".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn test_concurrency() -> AdaptiveConcurrency {
+        AdaptiveConcurrency::new(AdaptiveConcurrencyConfig {
+            min: 1,
+            max: 4,
+            initial: 2,
+            target_latency: Duration::from_secs(1),
+            max_error_rate: 0.2,
+            window: 10,
+            decrease_factor: 0.5,
+        })
+    }
+
+    fn scheduled_urls(scheduler: &FairBacklogScheduler, urls: &[&str]) -> Vec<String> {
+        let links = urls.iter().map(|url| backlog_link(url)).collect();
+        scheduler
+            .schedule(links)
+            .into_iter()
+            .map(|link| link.url)
+            .collect()
+    }
+
+    #[test]
+    fn test_fair_backlog_scheduler_round_robin() {
+        // a.comに偏ったバックログ
+        let urls = [
+            "https://a.com/1",
+            "https://a.com/2",
+            "https://a.com/3",
+            "https://a.com/4",
+            "https://b.com/1",
+            "https://c.com/1",
+            "https://b.com/2",
+        ];
+        let scheduled = scheduled_urls(&FairBacklogScheduler::default(), &urls);
+        assert_eq!(
+            scheduled,
+            vec![
+                "https://a.com/1",
+                "https://b.com/1",
+                "https://c.com/1",
+                "https://a.com/2",
+                "https://b.com/2",
+                "https://a.com/3",
+                "https://a.com/4",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fair_backlog_scheduler_weighted() {
+        let urls = [
+            "https://a.com/1",
+            "https://a.com/2",
+            "https://a.com/3",
+            "https://b.com/1",
+            "https://b.com/2",
+            "not a url",
+        ];
+        let scheduler = FairBacklogScheduler::default().with_weight("a.com", 2);
+        let scheduled = scheduled_urls(&scheduler, &urls);
+        assert_eq!(
+            scheduled,
+            vec![
+                "https://a.com/1",
+                "https://a.com/2",
+                "https://b.com/1",
+                "not a url",
+                "https://a.com/3",
+                "https://b.com/2",
+            ]
+        );
+
+        // 空のバックログ
+        assert!(scheduled_urls(&scheduler, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_adaptive_concurrency_additive_increase() {
+        let mut concurrency = test_concurrency();
+        let fast = Duration::from_millis(100);
+
+        // 現在の並列度と同じ件数が完了する毎に1ずつ増え、上限で止まる
+        assert_eq!(concurrency.on_complete(fast, false), 2);
+        assert_eq!(concurrency.on_complete(fast, false), 3);
+        for _ in 0..3 {
+            concurrency.on_complete(fast, false);
+        }
+        assert_eq!(concurrency.limit(), 4);
+        for _ in 0..8 {
+            concurrency.on_complete(fast, false);
+        }
+        assert_eq!(concurrency.limit(), 4, "上限を超えない");
+    }
+
+    #[test]
+    fn test_adaptive_concurrency_multiplicative_decrease() {
+        let mut concurrency = test_concurrency();
+        let fast = Duration::from_millis(100);
+        let slow = Duration::from_secs(5);
+        for _ in 0..5 {
+            concurrency.on_complete(fast, false);
+        }
+        assert_eq!(concurrency.limit(), 4);
+
+        // レスポンスタイム超過で半減し、減少前から実行中だった分では続けて減らさない
+        assert_eq!(concurrency.on_complete(slow, false), 2);
+        for _ in 0..3 {
+            assert_eq!(concurrency.on_complete(slow, false), 2);
+        }
+        assert_eq!(concurrency.on_complete(slow, false), 1);
+        assert_eq!(concurrency.on_complete(slow, false), 1, "下限を下回らない");
+
+        // エラー率の超過でも減少する
+        let mut concurrency = test_concurrency();
+        assert_eq!(concurrency.on_complete(fast, true), 1);
+        assert!(concurrency.error_rate() > 0.2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fetch_budget_shared() {
+        let config = ArticleFetchConfig {
+            max_concurrency: 2,
+            requests_per_minute: Some(60),
+            ..Default::default()
+        };
+        let budget = FetchBudget::new(&config);
+        let other = budget.clone();
+
+        // クローンは取得枠を共有する
+        let first = budget.acquire().await;
+        let _second = other.acquire().await;
+        assert!(budget.try_acquire().is_none());
+        drop(first);
+        assert!(other.try_acquire().is_some());
+
+        // クローンはレート制限の送出枠も共有する
+        let start = budget.reserve().unwrap();
+        assert_eq!(other.reserve().unwrap() - start, Duration::from_secs(1));
+
+        println!("✅ 記事取得の予算の共有テスト成功");
+    }
+
+    #[test]
+    fn test_concurrency_config_from_fetch_config() {
+        let adaptive = AdaptiveConcurrencyConfig::from_fetch_config(&ArticleFetchConfig {
+            max_concurrency: 16,
+            ..Default::default()
+        });
+        assert_eq!((adaptive.min, adaptive.initial, adaptive.max), (1, 2, 16));
+
+        // 自動調整しない場合は並列度を固定する
+        let mut fixed = AdaptiveConcurrency::new(AdaptiveConcurrencyConfig::from_fetch_config(
+            &ArticleFetchConfig {
+                max_concurrency: 4,
+                adaptive: false,
+                requests_per_minute: None,
+                ..Default::default()
+            },
+        ));
+        assert_eq!(fixed.limit(), 4);
+        assert_eq!(fixed.on_complete(Duration::from_secs(60), true), 4);
+    }
+
+    #[sqlx::test(fixtures("../../fixtures/workflow.sql"))]
+    async fn test_collect_articles_with_rate_limit(pool: PgPool) -> Result<(), anyhow::Error> {
+        let mock_client = MockFirecrawlClient::new_success("レートリミットテスト記事の内容です");
+        let config = ArticleFetchConfig {
+            max_concurrency: 4,
+            adaptive: false,
+            requests_per_minute: Some(600),
+            ..Default::default()
+        };
+
+        let started = Instant::now();
+        task_collect_articles_with(&mock_client, &config, &pool).await?;
+
+        // 6件を100ms間隔で送出するため、最後の送出まで500ms以上かかる
+        assert!(started.elapsed() >= Duration::from_millis(500));
+        let success = sqlx::query_scalar!("SELECT COUNT(*) FROM articles WHERE status_code = 200")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(success.unwrap_or(0), 8);
+
+        println!("✅ レートリミット付き並列取得テスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("../../fixtures/workflow.sql"))]
+    async fn test_collect_articles_with_debug_log(pool: PgPool) -> Result<(), anyhow::Error> {
+        use crate::core::article::{get_article_debug_log, ArticleDebugConfig};
+        use crate::infra::api::firecrawl::MockScrapeResult;
+
+        let target = "https://news.example.com/article1";
+        let mock_client = MockFirecrawlClient::new_success("```\nlet x = 1;")
+            .with_response(target, MockScrapeResult::error("operation timed out"));
+        let config = ArticleFetchConfig {
+            retry: RetryPolicy::none(),
+            debug: ArticleDebugConfig {
+                urls: vec![target.to_string()],
+                sample_rate: 0.0,
+            },
+            ..Default::default()
+        };
+        task_collect_articles_with(&mock_client, &config, &pool).await?;
+
+        let log = get_article_debug_log(target, &pool)
+            .await?
+            .expect("対象記事の処理ログが保存されるべき");
+        let kinds: Vec<&str> = log.entries.iter().map(|e| e.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["attempt", "request", "response"]);
+        assert!(log.entries[2].message.contains("operation timed out"));
+        assert!(
+            get_article_debug_log("https://news.example.com/article2", &pool)
+                .await?
+                .is_none(),
+            "対象外の記事は記録しない"
+        );
+
+        // 2回目の取得はリトライとして記録され、パイプラインの修復も記録される
+        sqlx::query!(
+            "UPDATE articles SET next_retry_at = now() WHERE url = $1",
+            target
+        )
+        .execute(&pool)
+        .await?;
+        let retry_client = MockFirecrawlClient::new_success("```\nlet x = 1;");
+        task_collect_articles_with(&retry_client, &config, &pool).await?;
+        let log = get_article_debug_log(target, &pool).await?.unwrap();
+        assert!(log.entries[0].message.contains("リトライ"));
+        assert!(log
+            .entries
+            .iter()
+            .any(|e| e.kind == "pipeline" && e.message.contains("closed_code_fence")));
+
+        println!("✅ 記事の処理ログ記録テスト成功");
+        Ok(())
+    }
+
     #[sqlx::test(fixtures("../../fixtures/workflow.sql"))]
     async fn test_process_collect_articles(pool: PgPool) -> Result<(), anyhow::Error> {
         // fixtureから6件の未処理RSSリンクと3件の処理済み記事が読み込まれる（archiveも再処理される）
@@ -176,4 +1266,244 @@ mod tests {
         println!("✅ 混在シナリオworkflow統合テスト完了: 11件すべて成功処理しました");
         Ok(())
     }
+
+    #[sqlx::test(fixtures("../../fixtures/workflow.sql"))]
+    async fn test_process_collect_articles_cleans_tracking_links(
+        pool: PgPool,
+    ) -> Result<(), anyhow::Error> {
+        let mock_client =
+            MockFirecrawlClient::new_success("[関連](https://example.com/r?id=1&utm_source=rss)");
+        task_collect_articles(&mock_client, &pool).await?;
+
+        let article_content: String = sqlx::query_scalar!(
+            "SELECT content FROM articles WHERE url = $1",
+            "https://news.example.com/article1"
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(
+            article_content, "[関連](https://example.com/r?id=1)",
+            "トラッキングパラメータが除去されていません"
+        );
+
+        println!("✅ トラッキングリンク除去workflowテスト完了");
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("../../fixtures/workflow.sql"))]
+    async fn test_collect_articles_with_progress(pool: PgPool) -> Result<(), anyhow::Error> {
+        let mock_client = MockFirecrawlClient::new_success("進捗テスト記事の内容です");
+        let config = ArticleFetchConfig {
+            robots: RobotsConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let notified = std::sync::Mutex::new(Vec::new());
+        task_collect_articles_with_progress(
+            &mock_client,
+            &config,
+            Some(|progress: Progress| notified.lock().unwrap().push(progress)),
+            &pool,
+        )
+        .await?;
+
+        let notified = notified.into_inner().unwrap();
+        assert_eq!(notified.len(), 6, "バックログの全件を通知する");
+        assert!(notified
+            .iter()
+            .enumerate()
+            .all(|(i, p)| p.completed == i + 1 && p.total == 6));
+        assert!(notified
+            .iter()
+            .any(|p| p.url == "https://news.example.com/article1"));
+        assert_eq!(notified.last().map(Progress::ratio), Some(1.0));
+
+        println!("✅ 記事収集の進捗通知テスト完了");
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("../../fixtures/workflow.sql"))]
+    async fn test_collect_articles_excludes_ng_words(pool: PgPool) -> Result<(), anyhow::Error> {
+        use crate::core::ng_word::{NgWordAction, NgWordConfig};
+
+        let mock_client = MockFirecrawlClient::new_success("機密情報を含む記事の内容です");
+        let config = ArticleFetchConfig {
+            robots: RobotsConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            ng_words: NgWordConfig {
+                words: vec!["機密情報".to_string()],
+                action: NgWordAction::Exclude,
+            },
+            ..Default::default()
+        };
+        task_collect_articles_with(&mock_client, &config, &pool).await?;
+
+        let stored: Vec<(i32, String)> = sqlx::query!("SELECT status_code, content FROM articles")
+            .fetch_all(&pool)
+            .await?
+            .into_iter()
+            .map(|row| (row.status_code, row.content))
+            .collect();
+        assert!(!stored.is_empty());
+        assert!(stored.iter().all(|(status_code, content)| {
+            *status_code == NG_WORD_EXCLUDED_STATUS_CODE && content.contains("機密情報")
+        }));
+
+        println!("✅ NGワードを含む記事の除外テスト完了");
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("../../fixtures/workflow.sql"))]
+    async fn test_collect_articles_respects_robots(pool: PgPool) -> Result<(), anyhow::Error> {
+        // news.example.comの/article*だけを禁止するrobots.txtを返すクライアント
+        struct RobotsTxtClient;
+
+        #[async_trait::async_trait]
+        impl crate::infra::api::http::HttpClient for RobotsTxtClient {
+            async fn fetch(
+                &self,
+                url: &str,
+                _timeout_secs: u64,
+            ) -> Result<String, crate::types::FetchError> {
+                Ok(if url.starts_with("https://news.example.com/") {
+                    "User-agent: *\nDisallow: /article\n".to_string()
+                } else {
+                    String::new()
+                })
+            }
+        }
+
+        let robots = RobotsChecker::new(Arc::new(RobotsTxtClient), &RobotsConfig::default());
+        let links = search_backlog_article_links_with(&Default::default(), &pool).await?;
+        let mock_client = MockFirecrawlClient::new_success("robots.txtテスト記事の内容です");
+        let config = ArticleFetchConfig::default();
+        collect_backlog_links(
+            links,
+            &mock_client,
+            &config,
+            &FetchBudget::new(&config),
+            Some(&robots),
+            None::<&fn(Progress)>,
+            false,
+            &pool,
+        )
+        .await?;
+
+        async fn assert_robots_respected(pool: &PgPool) -> Result<(), anyhow::Error> {
+            let disallowed: Vec<String> = sqlx::query_scalar!(
+                "SELECT url FROM articles WHERE status_code = $1 ORDER BY url",
+                ROBOTS_DISALLOWED_STATUS_CODE
+            )
+            .fetch_all(pool)
+            .await?;
+            assert_eq!(
+                disallowed,
+                vec![
+                    "https://news.example.com/article1".to_string(),
+                    "https://news.example.com/article2".to_string()
+                ]
+            );
+            let breaking_status: i32 = sqlx::query_scalar!(
+                "SELECT status_code FROM articles WHERE url = $1",
+                "https://news.example.com/breaking"
+            )
+            .fetch_one(pool)
+            .await?;
+            assert_eq!(breaking_status, 200, "禁止されていないパスは取得する");
+            Ok(())
+        }
+        assert_robots_respected(&pool).await?;
+
+        // ワーカーモード（クレームしながらの取得）でもrobots.txtを尊重する
+        sqlx::query!("DELETE FROM articles WHERE url LIKE 'https://news.example.com/%'")
+            .execute(&pool)
+            .await?;
+        collect_claimed_articles(&mock_client, &config, Some(&robots), &pool, "worker-a", 2)
+            .await?;
+        assert_robots_respected(&pool).await?;
+
+        println!("✅ robots.txt尊重workflowテスト完了");
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("../../fixtures/workflow.sql"))]
+    async fn test_task_collect_claimed_articles(pool: PgPool) -> Result<(), anyhow::Error> {
+        // 失敗したリンクを同じ実行中に再クレームし続けないこと（終了すること）を確認する
+        let error_client = MockFirecrawlClient::new_error("取得失敗");
+        let config = ArticleFetchConfig::default();
+        task_collect_claimed_articles(&error_client, &config, &pool, "worker-a", 2).await?;
+        // 失敗した記事は再処理時刻までクレームされないため、時刻を過ぎたものとする
+        sqlx::query!("UPDATE articles SET next_retry_at = now() WHERE status_code <> 200")
+            .execute(&pool)
+            .await?;
+
+        let success_client = MockFirecrawlClient::new_success("分担テスト記事の内容です");
+        let (a, b) = tokio::join!(
+            task_collect_claimed_articles(&success_client, &config, &pool, "worker-a", 2),
+            task_collect_claimed_articles(&success_client, &config, &pool, "worker-b", 2)
+        );
+        a?;
+        b?;
+
+        let error_articles = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM articles WHERE status_code <> 200"#
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(error_articles, 0, "バックログはすべて処理される");
+        let claims = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM backlog_claims"#)
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(claims, 0, "終了時にクレームは解放される");
+
+        println!("✅ バックログ分担処理テスト完了");
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("../../fixtures/workflow.sql"))]
+    async fn test_task_recrawl_articles(pool: PgPool) -> Result<(), anyhow::Error> {
+        let config = ArticleFetchConfig {
+            recrawl: RecrawlPolicy {
+                max_article_age_days: 36500,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let url = "https://old.example.com/processed1";
+
+        // 取得に失敗しても保存済みの本文は残す
+        let error_client = MockFirecrawlClient::new_error("再クロール失敗");
+        let recrawled = task_recrawl_articles(&error_client, &config, &pool).await?;
+        assert_eq!(recrawled, 2, "取得済み（200）の記事のみ対象");
+        let content = sqlx::query_scalar!("SELECT content FROM articles WHERE url = $1", url)
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(content, "処理済み記事1の内容です。");
+        assert_eq!(
+            task_recrawl_articles(&error_client, &config, &pool).await?,
+            0,
+            "再クロールした記事は次の間隔まで対象にしない"
+        );
+
+        // 間隔が過ぎたら再クロールし、変わった本文で更新する
+        sqlx::query!("UPDATE articles SET recrawled_at = now() - interval '2 days'")
+            .execute(&pool)
+            .await?;
+        let success_client = MockFirecrawlClient::new_success("再クロール後の本文です");
+        assert_eq!(
+            task_recrawl_articles(&success_client, &config, &pool).await?,
+            2
+        );
+        let content = sqlx::query_scalar!("SELECT content FROM articles WHERE url = $1", url)
+            .fetch_one(&pool)
+            .await?;
+        assert!(content.contains("再クロール後の本文です"));
+
+        println!("✅ 記事の再クロールテスト完了");
+        Ok(())
+    }
 }
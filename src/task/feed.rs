@@ -0,0 +1,21 @@
+use crate::core::feed::source::{sync_feeds, FeedSourcesConfig, SharedHttpClient};
+use anyhow::Result;
+
+/// # 概要
+/// 設定した同期元からフィードリストを取得してマージし、書き込み先に反映する。
+///
+/// 同期元毎の件数と追加・削除・変更したフィードを表示する。
+pub async fn task_sync_feeds(config: &FeedSourcesConfig, client: &SharedHttpClient) -> Result<()> {
+    let sources = config.build_sources(client)?;
+    let report = sync_feeds(&sources, &config.output).await?;
+
+    for line in report.format_lines() {
+        tracing::info!("{}", line);
+    }
+    if report.has_changes() {
+        tracing::info!(output = %config.output.display(), "フィードリストを更新しました");
+    } else {
+        tracing::info!("フィードリストに変更はありません");
+    }
+    Ok(())
+}
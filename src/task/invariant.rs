@@ -0,0 +1,40 @@
+use crate::core::collection_error::{record_collection_errors, NewCollectionError};
+use crate::core::invariant::{check_invariants, InvariantConfig, ViolationAction, WorkflowStage};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+/// # 概要
+/// 段階の終了時に不変条件を検証し、違反を警告として表示する。
+///
+/// `on_violation: abort`の設定では違反があればエラーを返してワークフローを中断する。
+/// 検証自体の失敗（DBエラー等）はワークフローを中断しない。
+pub async fn task_check_invariants(
+    stage: WorkflowStage,
+    since: DateTime<Utc>,
+    config: &InvariantConfig,
+    pool: &PgPool,
+) -> Result<()> {
+    let violations = match check_invariants(stage, since, config, pool).await {
+        Ok(violations) => violations,
+        Err(e) => {
+            tracing::error!(error = format!("{:#}", e), "不変条件の検証に失敗");
+            record_collection_errors(&[NewCollectionError::task("check_invariants", &e)], pool)
+                .await;
+            return Ok(());
+        }
+    };
+    for violation in &violations {
+        tracing::warn!(
+            invariant = violation.invariant.name(),
+            message = %violation.message,
+            "不変条件違反"
+        );
+    }
+
+    if config.on_violation == ViolationAction::Abort && !violations.is_empty() {
+        let names: Vec<&str> = violations.iter().map(|v| v.invariant.name()).collect();
+        anyhow::bail!("不変条件違反のためワークフローを中断: {}", names.join(", "));
+    }
+    Ok(())
+}
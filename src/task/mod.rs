@@ -1,5 +1,19 @@
 pub mod article;
+pub mod feed;
+pub mod invariant;
+pub mod notify;
+pub mod progress;
 pub mod rss;
 
-pub use article::task_collect_articles;
-pub use rss::task_collect_article_links;
+pub use article::{
+    task_collect_articles, task_collect_articles_with, task_collect_articles_with_budget,
+    task_collect_articles_with_progress, task_collect_claimed_articles, task_recrawl_articles,
+};
+pub use feed::task_sync_feeds;
+pub use invariant::task_check_invariants;
+pub use notify::task_listen_new_article_links;
+pub use progress::Progress;
+pub use rss::{
+    task_collect_article_links, task_collect_article_links_with,
+    task_collect_article_links_with_progress, task_collect_due_article_links,
+};
@@ -0,0 +1,219 @@
+use super::article::{collect_backlog_links, robots_checker, FetchBudget};
+use crate::core::{
+    collection_error::{record_collection_errors, NewCollectionError},
+    config::ArticleFetchConfig,
+    rss::find_backlog_article_link,
+    scraper::ScraperClient,
+};
+use crate::task::progress::Progress;
+use anyhow::{Context, Result};
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+
+/// article_linksへのINSERTを通知するチャンネル名（migrations/004と対応）
+pub const ARTICLE_LINKS_CHANNEL: &str = "article_links_inserted";
+
+/// 新着リンクの通知をLISTENし、届いたリンクの記事を即座に取得してDBに保存する
+///
+/// キーワード選別で対象外にしたリンクや`config.backlog`の絞り込みに合わないリンクなど、
+/// バックログの処理対象でないリンクは取得しない。
+/// 届いたリンクはバックログの処理と同じ経路で`config`に従って取得する（収集禁止ドメイン・
+/// robots.txt・サーキットブレーカ・保存容量の上限・NGワード・再試行と再処理の予定）。
+/// LISTEN接続が切断された場合（通知を取りこぼした可能性がある場合）は`Ok(())`で戻る。
+/// 接続や受信に失敗した場合はエラーを返す。
+pub async fn task_listen_new_article_links<S: ScraperClient>(
+    scraper: &S,
+    config: &ArticleFetchConfig,
+    pool: &PgPool,
+) -> Result<()> {
+    let mut listener = PgListener::connect_with(pool)
+        .await
+        .context("LISTEN用の接続に失敗")?;
+    listener
+        .listen(ARTICLE_LINKS_CHANNEL)
+        .await
+        .context(format!(
+            "チャンネルのLISTENに失敗: {}",
+            ARTICLE_LINKS_CHANNEL
+        ))?;
+    tracing::info!(channel = ARTICLE_LINKS_CHANNEL, "新着リンクの待ち受け開始");
+
+    // レート制限と取得枠は通知をまたいで共有する
+    let budget = FetchBudget::new(config);
+    let robots = robots_checker(&config.robots);
+
+    loop {
+        match listener.try_recv().await.context("通知の受信に失敗")? {
+            Some(notification) => {
+                let url = notification.payload();
+                let link = match find_backlog_article_link(url, &config.backlog, pool).await {
+                    Ok(Some(link)) => link,
+                    Ok(None) => {
+                        tracing::info!(url, "記事取得対象外のためスキップ");
+                        continue;
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            url,
+                            error = format!("{:#}", e),
+                            "記事取得対象の確認エラー"
+                        );
+                        let error = e.context("記事取得対象の確認エラー");
+                        let error = NewCollectionError::article_error(url, &error);
+                        record_collection_errors(&[error], pool).await;
+                        continue;
+                    }
+                };
+                // 通知駆動では1件ずつ届くため、届いたリンクだけを取得して保存する
+                let result = collect_backlog_links(
+                    vec![link],
+                    scraper,
+                    config,
+                    &budget,
+                    robots.as_ref(),
+                    None::<&fn(Progress)>,
+                    false,
+                    pool,
+                )
+                .await;
+                match result {
+                    Ok(()) => tracing::info!(url, "記事保存完了"),
+                    Err(e) => {
+                        tracing::error!(url, error = format!("{:#}", e), "記事保存エラー");
+                        let error = e.context("記事保存エラー");
+                        let error = NewCollectionError::article_error(url, &error);
+                        record_collection_errors(&[error], pool).await;
+                    }
+                }
+            }
+            None => {
+                tracing::warn!("LISTEN接続が切断されました");
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::rss::{store_article_links, store_article_links_with_executor, ArticleLink};
+    use crate::core::topic::{mark_fetch_targets_with_executor, TopicFilter};
+    use crate::infra::api::firecrawl::MockFirecrawlClient;
+    use std::time::Duration;
+
+    fn new_link(url: &str) -> ArticleLink {
+        ArticleLink {
+            url: url.to_string(),
+            title: "新着記事".to_string(),
+            pub_date: chrono::Utc::now(),
+            source: "test".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_insert_notifies_article_link(pool: PgPool) -> Result<(), anyhow::Error> {
+        let mut listener = PgListener::connect_with(&pool).await?;
+        listener.listen(ARTICLE_LINKS_CHANNEL).await?;
+
+        store_article_links(&[new_link("https://notify.example.com/1")], &pool).await?;
+
+        let notification = tokio::time::timeout(Duration::from_secs(5), listener.recv())
+            .await
+            .context("通知が届きませんでした")??;
+        assert_eq!(notification.payload(), "https://notify.example.com/1");
+
+        println!("✅ INSERT通知テスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_listen_processes_new_link(pool: PgPool) -> Result<(), anyhow::Error> {
+        let worker_pool = pool.clone();
+        let worker = tokio::spawn(async move {
+            let client = MockFirecrawlClient::new_success("即時処理された記事");
+            task_listen_new_article_links(&client, &ArticleFetchConfig::default(), &worker_pool)
+                .await
+        });
+
+        // LISTEN開始前のINSERTは通知されないため、記事が保存されるまで再投入する
+        let url = "https://notify.example.com/worker";
+        let mut content = None;
+        for _ in 0..50 {
+            sqlx::query!("DELETE FROM article_links WHERE url = $1", url)
+                .execute(&pool)
+                .await?;
+            store_article_links(&[new_link(url)], &pool).await?;
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            content = sqlx::query_scalar!("SELECT content FROM articles WHERE url = $1", url)
+                .fetch_optional(&pool)
+                .await?;
+            if content.is_some() {
+                break;
+            }
+        }
+        worker.abort();
+
+        assert_eq!(content.as_deref(), Some("即時処理された記事"));
+        println!("✅ 新着リンク即時処理テスト成功");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_listen_skips_excluded_link(pool: PgPool) -> Result<(), anyhow::Error> {
+        let worker_pool = pool.clone();
+        let worker = tokio::spawn(async move {
+            let client = MockFirecrawlClient::new_success("即時処理された記事");
+            task_listen_new_article_links(&client, &ArticleFetchConfig::default(), &worker_pool)
+                .await
+        });
+
+        // キーワード選別の対象外のリンクは、リンク収集と同じく保存と同じトランザクションで対象外にする
+        let filter = TopicFilter {
+            exclude: vec!["広告".to_string()],
+            ..Default::default()
+        };
+        let excluded = ArticleLink {
+            title: "広告記事".to_string(),
+            ..new_link("https://notify.example.com/excluded")
+        };
+        let target = new_link("https://notify.example.com/target");
+        let mut fetched = None;
+        for _ in 0..50 {
+            sqlx::query!(
+                "DELETE FROM article_links WHERE url = ANY($1)",
+                &[excluded.url.clone(), target.url.clone()]
+            )
+            .execute(&pool)
+            .await?;
+            let mut tx = pool.begin().await?;
+            store_article_links_with_executor(std::slice::from_ref(&excluded), &mut *tx).await?;
+            mark_fetch_targets_with_executor(std::slice::from_ref(&excluded), &filter, &mut *tx)
+                .await?;
+            tx.commit().await?;
+            // 対象外のリンクの後に通知される対象のリンクが処理されれば、対象外のリンクの通知も処理済み
+            store_article_links(std::slice::from_ref(&target), &pool).await?;
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            fetched = sqlx::query_scalar!("SELECT url FROM articles WHERE url = $1", target.url)
+                .fetch_optional(&pool)
+                .await?;
+            if fetched.is_some() {
+                break;
+            }
+        }
+        worker.abort();
+
+        assert!(fetched.is_some(), "対象のリンクは取得する");
+        let excluded_article =
+            sqlx::query_scalar!("SELECT url FROM articles WHERE url = $1", excluded.url)
+                .fetch_optional(&pool)
+                .await?;
+        assert!(excluded_article.is_none(), "対象外のリンクは取得しない");
+
+        println!("✅ 対象外リンクの通知スキップテスト成功");
+        Ok(())
+    }
+}
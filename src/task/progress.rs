@@ -0,0 +1,20 @@
+// 収集処理の進捗（フィード・記事を1件処理する毎に進捗コールバックへ通知する）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progress {
+    /// 処理を終えた件数（今回の通知分を含む）
+    pub completed: usize,
+    /// 処理対象の総数
+    pub total: usize,
+    /// 処理を終えたフィード・記事のURL
+    pub url: String,
+}
+
+impl Progress {
+    /// 完了した割合（0.0〜1.0、対象が無い場合は1.0）
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        self.completed as f64 / self.total as f64
+    }
+}
@@ -1,45 +1,320 @@
 use crate::{
     core::{
-        feed::Feed,
-        rss::{get_article_links_from_feed, store_article_links},
+        blocklist::{load_domain_blocklist, DomainBlocklist},
+        collection_error::{record_collection_errors, NewCollectionError},
+        feed::{
+            load_last_fetched_at, record_feed_fetch_log, record_feed_fetched, Feed,
+            FeedUrlPatterns, NewFeedFetchLog,
+        },
+        rss::{
+            assign_article_links_feed, get_article_links_from_feed_conditional,
+            load_feed_validators, store_article_links_with_executor, store_feed_validators,
+            ArticleLink, FeedFetch, FeedFetchStats,
+        },
+        series::{mark_series_keys, SeriesConfig},
+        topic::{mark_fetch_targets_with_executor, TopicFilter, TopicFilterConfig},
     },
-    infra::api::http::HttpClient,
+    infra::{
+        api::http::HttpClient,
+        metrics::{metrics, STAGE_FEED},
+    },
+    task::progress::Progress,
 };
-use anyhow::Result;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use futures::stream::{self, StreamExt};
 use sqlx::PgPool;
 
-/// RSSフィードからリンクを収集してDBに保存する
+/// フィード取得の既定の並列度
+pub const DEFAULT_FEED_CONCURRENCY: usize = 8;
+
+/// RSSフィードからリンクを収集してDBに保存する（既定の並列度）
 pub async fn task_collect_article_links<H: HttpClient>(
     client: &H,
     feeds: &[Feed],
+    topic_filter: &TopicFilterConfig,
+    series: &SeriesConfig,
     pool: &PgPool,
-) -> Result<()> {
-    println!("--- RSSフィードからリンク取得開始 ---");
-
-    for feed in feeds {
-        println!("フィード処理中: {}", feed);
-
-        match get_article_links_from_feed(client, feed).await {
-            Ok(article_links) => {
-                println!("  {}件のリンクを抽出", article_links.len());
-
-                match store_article_links(&article_links, pool).await {
-                    Ok(_) => {
-                        println!("  DB保存完了: {}件処理", article_links.len());
-                    }
-                    Err(e) => {
-                        eprintln!("  DB保存エラー: {}", e);
-                    }
-                }
+) -> Result<FeedFetchStats> {
+    task_collect_article_links_with(
+        client,
+        feeds,
+        topic_filter,
+        series,
+        DEFAULT_FEED_CONCURRENCY,
+        pool,
+    )
+    .await
+}
+
+/// # 概要
+/// RSSフィードからリンクを収集してDBに保存する。
+///
+/// 最大`concurrency`件のフィードを並行して取得・保存する（0は1として扱う）。
+/// 保存したリンクはタイトルのキーワードで評価して記事取得の対象可否を記録し、
+/// シリーズの抽出ルールに一致すればシリーズキーを記録する。
+/// フィード毎の取得結果（取得に失敗したフィードなど）を返す。
+pub async fn task_collect_article_links_with<H: HttpClient>(
+    client: &H,
+    feeds: &[Feed],
+    topic_filter: &TopicFilterConfig,
+    series: &SeriesConfig,
+    concurrency: usize,
+    pool: &PgPool,
+) -> Result<FeedFetchStats> {
+    task_collect_article_links_with_progress(
+        client,
+        feeds,
+        topic_filter,
+        series,
+        concurrency,
+        None::<fn(Progress)>,
+        pool,
+    )
+    .await
+}
+
+/// # 概要
+/// RSSフィードからリンクを収集してDBに保存し、フィードを1件処理する毎に`progress`へ進捗を通知する。
+///
+/// 通知はフィードの取得・保存が終わった順（並行して取得するため`feeds`の順とは限らない）。
+/// 進捗のURLはフィードの`rss_link`。それ以外は`task_collect_article_links_with`と同じ。
+#[tracing::instrument(skip_all, fields(feeds = feeds.len(), concurrency = concurrency))]
+pub async fn task_collect_article_links_with_progress<H: HttpClient>(
+    client: &H,
+    feeds: &[Feed],
+    topic_filter: &TopicFilterConfig,
+    series: &SeriesConfig,
+    concurrency: usize,
+    progress: Option<impl Fn(Progress)>,
+    pool: &PgPool,
+) -> Result<FeedFetchStats> {
+    tracing::info!(
+        concurrency = concurrency.max(1),
+        "RSSフィードからリンク取得開始"
+    );
+    let blocklist = load_domain_blocklist(pool).await?;
+
+    let blocklist = &blocklist;
+    let mut results = stream::iter(feeds)
+        .map(|feed| async move {
+            let stats =
+                collect_feed_links(client, feed, blocklist, topic_filter, series, pool).await;
+            (feed, stats)
+        })
+        .buffer_unordered(concurrency.max(1));
+    let mut fetch_stats = FeedFetchStats::default();
+    let mut completed = 0;
+    while let Some((feed, stats)) = results.next().await {
+        fetch_stats.merge(stats);
+        completed += 1;
+        if let Some(progress) = &progress {
+            progress(Progress {
+                completed,
+                total: feeds.len(),
+                url: feed.rss_link.clone(),
+            });
+        }
+    }
+
+    tracing::info!(
+        primary = fetch_stats.primary,
+        fallbacks = fetch_stats.fallbacks.len(),
+        failed = fetch_stats.failed.len(),
+        "RSSフィードからリンク取得完了"
+    );
+    for (feed, url) in &fetch_stats.fallbacks {
+        tracing::info!(feed = %feed, url = %url, "フォールバックURLで取得");
+    }
+    if let Some(pool_stats) = client.pool_stats() {
+        tracing::info!(
+            requests = pool_stats.requests,
+            failures = pool_stats.failures,
+            http2_responses = pool_stats.http2_responses,
+            hosts = pool_stats.hosts,
+            reusable_requests = pool_stats.reusable_requests,
+            average_latency_ms = pool_stats.average_latency().as_millis() as u64,
+            "http_pool_stats"
+        );
+    }
+    Ok(fetch_stats)
+}
+
+/// # 概要
+/// 取得期限の来たフィードのみリンクを収集する（スケジューリングモード）。
+///
+/// フィード毎の前回の取得日時を読み込み、`fetch_interval`（分）が経過していないフィードは取得せず
+/// `FeedFetchStats::not_due`に数える。取得間隔が未設定のフィードと未取得のフィードは毎回取得する。
+#[tracing::instrument(skip_all, fields(feeds = feeds.len(), concurrency = concurrency))]
+pub async fn task_collect_due_article_links<H: HttpClient>(
+    client: &H,
+    feeds: &[Feed],
+    topic_filter: &TopicFilterConfig,
+    series: &SeriesConfig,
+    concurrency: usize,
+    pool: &PgPool,
+) -> Result<FeedFetchStats> {
+    let mut feeds = feeds.to_vec();
+    load_last_fetched_at(&mut feeds, pool).await?;
+
+    let now = Utc::now();
+    let (due_feeds, not_due_feeds): (Vec<Feed>, Vec<Feed>) =
+        feeds.into_iter().partition(|feed| feed.is_due(now));
+    tracing::info!(
+        due = due_feeds.len(),
+        not_due = not_due_feeds.len(),
+        "取得期限の来たフィードを選択"
+    );
+
+    let mut fetch_stats = task_collect_article_links_with(
+        client,
+        &due_feeds,
+        topic_filter,
+        series,
+        concurrency,
+        pool,
+    )
+    .await?;
+    fetch_stats.not_due = not_due_feeds.len();
+    Ok(fetch_stats)
+}
+
+/// 1つのフィードからリンクを取得して保存する
+///
+/// 並行して処理されるため、ログはフィードのスパン内で出力して区別できるようにする。
+#[tracing::instrument(skip_all, fields(feed = %feed.key(), url = %feed.rss_link))]
+async fn collect_feed_links<H: HttpClient>(
+    client: &H,
+    feed: &Feed,
+    blocklist: &DomainBlocklist,
+    topic_filter: &TopicFilterConfig,
+    series: &SeriesConfig,
+    pool: &PgPool,
+) -> FeedFetchStats {
+    let mut fetch_stats = FeedFetchStats::default();
+    // 期待URLパターンが不正なフィードは、想定外のリンクを保存しないよう取得しない
+    let url_patterns = match FeedUrlPatterns::for_feed(feed) {
+        Ok(url_patterns) => url_patterns,
+        Err(e) => {
+            tracing::error!(error = %e, "期待URLパターンの読み込みエラー");
+            record_feed_error(feed, "期待URLパターンの読み込みエラー", e.into(), pool).await;
+            return fetch_stats;
+        }
+    };
+    // 検証子が読めない場合は条件を付けずに取得する
+    let validators = match load_feed_validators(feed, pool).await {
+        Ok(validators) => validators,
+        Err(e) => {
+            tracing::warn!(error = format!("{:#}", e), "検証子の読み込みエラー");
+            record_feed_error(feed, "検証子の読み込みエラー", e, pool).await;
+            Default::default()
+        }
+    };
+    let fetched =
+        get_article_links_from_feed_conditional(client, feed, &validators, &mut fetch_stats).await;
+    let (article_links, fetched_url, new_validators) = match fetched {
+        Ok(FeedFetch::Modified {
+            links,
+            url,
+            validators,
+        }) => (links, url, validators),
+        Ok(FeedFetch::NotModified { url }) => {
+            tracing::info!("前回から更新なし（304）");
+            record_feed_fetch_log(&NewFeedFetchLog::not_modified(feed, &url), pool).await;
+            if let Err(e) = record_feed_fetched(feed, Utc::now(), pool).await {
+                tracing::error!(error = format!("{:#}", e), "取得日時の記録エラー");
+                record_feed_error(feed, "取得日時の記録エラー", e, pool).await;
             }
-            Err(e) => {
-                eprintln!("  フィード取得エラー: {}", e);
+            return fetch_stats;
+        }
+        Err(e) => {
+            tracing::error!(error = format!("{:#}", e), "フィード取得エラー");
+            let log = NewFeedFetchLog::failed(feed, &feed.rss_link, 0, &e);
+            record_feed_fetch_log(&log, pool).await;
+            record_feed_error(feed, "フィード取得エラー", e, pool).await;
+            return fetch_stats;
+        }
+    };
+    tracing::info!(links = article_links.len(), "リンクを抽出");
+    let link_count = article_links.len();
+
+    let (article_links, blocked) = blocklist.retain_allowed(article_links, |link| &link.url);
+    if blocked > 0 {
+        tracing::info!(blocked, "収集禁止ドメインのため除外");
+    }
+    let (article_links, unexpected) = url_patterns.retain_matching(article_links, |link| &link.url);
+    if let Some(sample) = unexpected.first() {
+        tracing::warn!(
+            unexpected = unexpected.len(),
+            sample = %sample,
+            "期待URLパターン外のリンクを除外"
+        );
+        fetch_stats
+            .unexpected_links
+            .push((feed.key(), unexpected.len()));
+    }
+
+    // 記事取得対象フラグは保存と同じトランザクションで更新し、新着通知（LISTENワーカー）が届いた時点で
+    // キーワード選別の対象外のリンクを判別できるようにする
+    let filter = topic_filter.filter_for(feed);
+    match store_and_mark_article_links(&article_links, &filter, pool).await {
+        Ok(skipped) => {
+            tracing::info!(links = article_links.len(), "DB保存完了");
+            if skipped > 0 {
+                tracing::info!(skipped, "キーワード選別で対象外");
             }
+            metrics().links_collected.inc_by(article_links.len() as u64);
+            let log = NewFeedFetchLog::success(feed, &fetched_url, link_count);
+            record_feed_fetch_log(&log, pool).await;
         }
+        Err(e) => {
+            tracing::error!(error = format!("{:#}", e), "DB保存エラー");
+            let log = NewFeedFetchLog::failed(feed, &fetched_url, link_count, &e);
+            record_feed_fetch_log(&log, pool).await;
+            record_feed_error(feed, "DB保存エラー", e, pool).await;
+            return fetch_stats;
+        }
+    }
+
+    if let Err(e) = assign_article_links_feed(&article_links, feed, pool).await {
+        tracing::error!(error = format!("{:#}", e), "フィードの記録エラー");
+        record_feed_error(feed, "フィードの記録エラー", e, pool).await;
+    }
+    // 保存まで完了したフィードのみ取得日時と検証子を記録する（失敗したフィードは次回も全件取得する）
+    if let Err(e) = record_feed_fetched(feed, Utc::now(), pool).await {
+        tracing::error!(error = format!("{:#}", e), "取得日時の記録エラー");
+        record_feed_error(feed, "取得日時の記録エラー", e, pool).await;
+    }
+    if let Err(e) = store_feed_validators(&fetched_url, &new_validators, pool).await {
+        tracing::error!(error = format!("{:#}", e), "検証子の記録エラー");
+        record_feed_error(feed, "検証子の記録エラー", e, pool).await;
     }
 
-    println!("--- RSSフィードからリンク取得完了 ---");
-    Ok(())
+    if let Err(e) = mark_series_keys(&article_links, series, pool).await {
+        tracing::error!(error = format!("{:#}", e), "シリーズキーの更新エラー");
+        record_feed_error(feed, "シリーズキーの更新エラー", e, pool).await;
+    }
+    fetch_stats
+}
+
+/// リンクの保存と記事取得対象フラグの更新を1つのトランザクションで行い、対象外にしたリンク数を返す
+async fn store_and_mark_article_links(
+    article_links: &[ArticleLink],
+    filter: &TopicFilter,
+    pool: &PgPool,
+) -> Result<usize> {
+    let mut tx = pool.begin().await.context("トランザクションの開始に失敗")?;
+    store_article_links_with_executor(article_links, &mut *tx).await?;
+    let skipped = mark_fetch_targets_with_executor(article_links, filter, &mut *tx).await?;
+    tx.commit().await.context("リンクの保存のコミットに失敗")?;
+    Ok(skipped)
+}
+
+/// フィードの処理のエラーをcollection_errorsに記録する（`step`はエラーの発生した処理）
+async fn record_feed_error(feed: &Feed, step: &'static str, error: anyhow::Error, pool: &PgPool) {
+    metrics().record_error(STAGE_FEED);
+    let error = NewCollectionError::feed(feed, &error.context(step));
+    record_collection_errors(&[error], pool).await;
 }
 
 #[cfg(test)]
@@ -58,16 +333,19 @@ mod tests {
                 group: "news".to_string(),
                 name: "tech_news".to_string(),
                 rss_link: "https://technews.example.com/rss.xml".to_string(),
+                ..Default::default()
             },
             Feed {
                 group: "blog".to_string(),
                 name: "dev_blog".to_string(),
                 rss_link: "https://devblog.example.com/feed.xml".to_string(),
+                ..Default::default()
             },
             Feed {
                 group: "updates".to_string(),
                 name: "product_updates".to_string(),
                 rss_link: "https://updates.example.com/rss".to_string(),
+                ..Default::default()
             },
         ];
 
@@ -85,7 +363,14 @@ mod tests {
         );
 
         // task_collect_article_linksを実行
-        let result = task_collect_article_links(&mock_client, &test_feeds, &pool).await;
+        let result = task_collect_article_links(
+            &mock_client,
+            &test_feeds,
+            &TopicFilterConfig::default(),
+            &SeriesConfig::default(),
+            &pool,
+        )
+        .await;
         assert!(
             result.is_ok(),
             "RSS収集処理が失敗しました: {:?}",
@@ -169,28 +454,276 @@ mod tests {
     }
 
     #[sqlx::test]
-    async fn test_task_collect_article_links_with_errors(
+    async fn test_task_collect_article_links_concurrently(
         pool: PgPool,
     ) -> Result<(), anyhow::Error> {
         use crate::core::feed::Feed;
         use crate::infra::api::http::MockHttpClient;
 
+        let feeds: Vec<Feed> = (0..20)
+            .map(|i| Feed {
+                group: "parallel".to_string(),
+                name: format!("feed_{}", i),
+                rss_link: format!("https://parallel{}.example.com/rss", i),
+                ..Default::default()
+            })
+            .collect();
+        let failing = feeds[3].rss_link.clone();
+        let client = MockHttpClient::new_success().with_failing_url(&failing);
+
+        let stats = task_collect_article_links_with(
+            &client,
+            &feeds,
+            &TopicFilterConfig::default(),
+            &SeriesConfig::default(),
+            5,
+            &pool,
+        )
+        .await?;
+        assert_eq!(stats.failed, vec![feeds[3].key()], "失敗したフィードを返す");
+
+        let count = sqlx::query_scalar!("SELECT COUNT(*) FROM article_links")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(
+            count.unwrap_or(0),
+            19 * 3,
+            "失敗したフィード以外は保存される"
+        );
+        let unassigned =
+            sqlx::query_scalar!("SELECT COUNT(*) FROM article_links WHERE feed IS NULL")
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!(unassigned.unwrap_or(0), 0);
+
+        println!("✅ フィードの並列収集テスト完了");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_task_collect_article_links_url_patterns(
+        pool: PgPool,
+    ) -> Result<(), anyhow::Error> {
+        use crate::core::feed::Feed;
+        use crate::infra::api::http::MockHttpClient;
+
+        // モックのリンクは https://{hash}.example.com/1〜3
+        let feeds = vec![
+            Feed {
+                group: "pattern".to_string(),
+                name: "checked".to_string(),
+                rss_link: "https://checked.example.com/rss".to_string(),
+                url_patterns: vec![r"\.example\.com/[12]$".to_string()],
+                ..Default::default()
+            },
+            Feed {
+                group: "pattern".to_string(),
+                name: "invalid".to_string(),
+                rss_link: "https://invalid.example.com/rss".to_string(),
+                url_patterns: vec!["(unclosed".to_string()],
+                ..Default::default()
+            },
+        ];
+        let stats = task_collect_article_links(
+            &MockHttpClient::new_success(),
+            &feeds,
+            &TopicFilterConfig::default(),
+            &SeriesConfig::default(),
+            &pool,
+        )
+        .await?;
+        assert_eq!(stats.unexpected_links, vec![(feeds[0].key(), 1)]);
+
+        let count = sqlx::query_scalar!("SELECT COUNT(*) FROM article_links")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(
+            count.unwrap_or(0),
+            2,
+            "パターン外のリンクと不正なパターンのフィードは保存しない"
+        );
+        let errors = sqlx::query_scalar!(
+            "SELECT COUNT(*) FROM collection_errors WHERE message LIKE '%期待URLパターン%'"
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(errors.unwrap_or(0), 1, "不正なパターンはエラーとして記録");
+
+        println!("✅ 期待URLパターンによるリンク検証テスト完了");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_task_collect_article_links_progress(pool: PgPool) -> Result<(), anyhow::Error> {
+        use crate::core::feed::Feed;
+        use crate::infra::api::http::MockHttpClient;
+        use std::sync::Mutex;
+
+        let feeds: Vec<Feed> = (0..3)
+            .map(|i| Feed {
+                group: "progress".to_string(),
+                name: format!("feed_{}", i),
+                rss_link: format!("https://progress{}.example.com/rss", i),
+                ..Default::default()
+            })
+            .collect();
+        let client = MockHttpClient::new_success().with_failing_url(&feeds[1].rss_link);
+        let notified = Mutex::new(Vec::new());
+
+        task_collect_article_links_with_progress(
+            &client,
+            &feeds,
+            &TopicFilterConfig::default(),
+            &SeriesConfig::default(),
+            2,
+            Some(|progress: Progress| notified.lock().unwrap().push(progress)),
+            &pool,
+        )
+        .await?;
+
+        let notified = notified.into_inner().unwrap();
+        assert_eq!(
+            notified
+                .iter()
+                .map(|p| (p.completed, p.total))
+                .collect::<Vec<_>>(),
+            vec![(1, 3), (2, 3), (3, 3)],
+            "失敗したフィードも処理済みとして通知する"
+        );
+        let mut urls: Vec<&str> = notified.iter().map(|p| p.url.as_str()).collect();
+        urls.sort();
+        assert_eq!(
+            urls,
+            feeds
+                .iter()
+                .map(|f| f.rss_link.as_str())
+                .collect::<Vec<_>>()
+        );
+
+        println!("✅ フィード収集の進捗通知テスト完了");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_task_collect_due_article_links(pool: PgPool) -> Result<(), anyhow::Error> {
+        use crate::infra::api::http::MockHttpClient;
+
+        let feeds = vec![
+            Feed {
+                group: "schedule".to_string(),
+                name: "hourly".to_string(),
+                rss_link: "https://hourly.example.com/rss".to_string(),
+                fetch_interval: Some(60),
+                ..Default::default()
+            },
+            Feed {
+                group: "schedule".to_string(),
+                name: "every_run".to_string(),
+                rss_link: "https://every-run.example.com/rss".to_string(),
+                ..Default::default()
+            },
+        ];
+        let client = MockHttpClient::new_success();
+        let topic_filter = TopicFilterConfig::default();
+        let series = SeriesConfig::default();
+        let collect =
+            || task_collect_due_article_links(&client, &feeds, &topic_filter, &series, 2, &pool);
+
+        // 未取得のフィードはすべて取得する
+        let stats = collect().await?;
+        assert_eq!((stats.primary, stats.not_due), (2, 0));
+
+        // 取得間隔が経過していないフィードは取得しない
+        let stats = collect().await?;
+        assert_eq!((stats.primary, stats.not_due), (1, 1));
+        assert!(stats
+            .format_lines()
+            .contains(&"取得期限前のため取得せず: 1件".to_string()));
+
+        // 取得間隔が経過したら再び取得する
+        sqlx::query!(
+            "UPDATE feed_fetch_times SET last_fetched_at = last_fetched_at - INTERVAL '2 hours'"
+        )
+        .execute(&pool)
+        .await?;
+        let stats = collect().await?;
+        assert_eq!((stats.primary, stats.not_due), (2, 0));
+
+        println!("✅ フィードのスケジューリングテスト完了");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_task_collect_article_links_conditional(
+        pool: PgPool,
+    ) -> Result<(), anyhow::Error> {
+        use crate::infra::api::http::MockHttpClient;
+        use crate::infra::compute::generate_mock_rss_id;
+
+        let url = "https://conditional.example.com/rss";
+        let feeds = vec![Feed {
+            group: "conditional".to_string(),
+            name: "etag".to_string(),
+            rss_link: url.to_string(),
+            ..Default::default()
+        }];
+        let client = MockHttpClient::new_success().with_not_modified_url(url);
+        let topic_filter = TopicFilterConfig::default();
+        let series = SeriesConfig::default();
+        let collect = || task_collect_article_links(&client, &feeds, &topic_filter, &series, &pool);
+
+        // 初回は検証子が無いため全件取得し、応答のETagを記録する
+        let stats = collect().await?;
+        assert_eq!((stats.primary, stats.not_modified), (1, 0));
+        let etag = sqlx::query_scalar!("SELECT etag FROM feed_http_validators WHERE url = $1", url)
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(etag, Some(format!("\"{}\"", generate_mock_rss_id(url))));
+
+        // 2回目は304のため解析を省略するが、取得日時は更新する
+        let stats = collect().await?;
+        assert_eq!((stats.primary, stats.not_modified), (1, 1));
+        assert!(stats
+            .format_lines()
+            .contains(&"更新なし（304）: 1件".to_string()));
+        let fetched = sqlx::query_scalar!(
+            "SELECT COUNT(*) FROM feed_fetch_times WHERE feed = 'conditional/etag'"
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(fetched, Some(1));
+
+        println!("✅ 条件付きリクエストでのリンク収集テスト完了");
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_task_collect_article_links_with_errors(
+        pool: PgPool,
+    ) -> Result<(), anyhow::Error> {
+        use crate::core::collection_error::{search_errors, CollectionEntityType};
+        use crate::core::feed::{search_feed_fetch_logs, Feed, FeedFetchLogQuery, FeedFetchResult};
+        use crate::infra::api::http::MockHttpClient;
+
         // 成功フィード1つ + エラーフィード2つを準備
         let test_feeds = vec![
             Feed {
                 group: "success".to_string(),
                 name: "working_feed".to_string(),
                 rss_link: "https://working.example.com/rss.xml".to_string(),
+                ..Default::default()
             },
             Feed {
                 group: "error1".to_string(),
                 name: "timeout_feed".to_string(),
                 rss_link: "https://timeout.example.com/rss.xml".to_string(),
+                ..Default::default()
             },
             Feed {
                 group: "error2".to_string(),
                 name: "server_error_feed".to_string(),
                 rss_link: "https://servererror.example.com/rss.xml".to_string(),
+                ..Default::default()
             },
         ];
 
@@ -202,7 +735,14 @@ mod tests {
 
         // 1. 成功フィードのテスト
         let success_feeds = vec![test_feeds[0].clone()];
-        let result = task_collect_article_links(&success_client, &success_feeds, &pool).await;
+        let result = task_collect_article_links(
+            &success_client,
+            &success_feeds,
+            &TopicFilterConfig::default(),
+            &SeriesConfig::default(),
+            &pool,
+        )
+        .await;
         assert!(result.is_ok(), "成功フィードの処理が失敗しました");
 
         // 成功フィードからの3件のリンクが保存されることを確認
@@ -219,7 +759,14 @@ mod tests {
         let error_client = MockHttpClient::new_error("接続タイムアウト");
 
         // エラークライアントでも処理自体は成功する（内部でエラーハンドリング）
-        let all_result = task_collect_article_links(&error_client, &test_feeds, &pool).await;
+        let all_result = task_collect_article_links(
+            &error_client,
+            &test_feeds,
+            &TopicFilterConfig::default(),
+            &SeriesConfig::default(),
+            &pool,
+        )
+        .await;
         assert!(
             all_result.is_ok(),
             "エラーハンドリングが正しく動作していません"
@@ -235,6 +782,44 @@ mod tests {
             "エラーフィードから新たなリンクが追加されるべきではありません"
         );
 
+        // 取得エラーはフィード毎にcollection_errorsに記録される
+        let errors = search_errors(None, &pool).await?;
+        let mut keys: Vec<&str> = errors.iter().map(|e| e.entity_key.as_str()).collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![
+                "error1/timeout_feed",
+                "error2/server_error_feed",
+                "success/working_feed"
+            ]
+        );
+        assert!(errors
+            .iter()
+            .all(|e| e.entity_type == CollectionEntityType::Feed
+                && e.message.contains("フィード取得エラー")));
+
+        // 取得結果は成功・失敗ともにfeed_fetch_logsに記録される
+        let logs = search_feed_fetch_logs(
+            Some(FeedFetchLogQuery {
+                feed: Some("success/working_feed".to_string()),
+                ..Default::default()
+            }),
+            &pool,
+        )
+        .await?;
+        let results: Vec<FeedFetchResult> = logs.iter().map(|log| log.result).collect();
+        assert_eq!(
+            results,
+            vec![FeedFetchResult::Failed, FeedFetchResult::Success]
+        );
+        assert_eq!(logs[1].link_count, 3);
+        assert_eq!(logs[1].url, "https://working.example.com/rss.xml");
+        assert!(logs[0]
+            .error_message
+            .as_deref()
+            .is_some_and(|message| message.contains("接続タイムアウト")));
+
         // 3. 成功・エラー混在での処理確認
         // 新しいテーブル状態でテスト
         sqlx::query!("DELETE FROM article_links")
@@ -244,7 +829,14 @@ mod tests {
         // 混在処理では各フィードが個別に処理される
         // この関数は現在の実装ではクライアント固定なので、実際の混在テストは困難
         // その代わりに、成功ケースが正しく処理されることを再確認
-        let final_result = task_collect_article_links(&success_client, &success_feeds, &pool).await;
+        let final_result = task_collect_article_links(
+            &success_client,
+            &success_feeds,
+            &TopicFilterConfig::default(),
+            &SeriesConfig::default(),
+            &pool,
+        )
+        .await;
         assert!(
             final_result.is_ok(),
             "最終的な成功フィード処理が失敗しました"
@@ -279,16 +871,19 @@ mod tests {
                 group: "group1".to_string(),
                 name: "shared_feed_1".to_string(),
                 rss_link: same_rss_url.to_string(),
+                ..Default::default()
             },
             Feed {
                 group: "group2".to_string(),
                 name: "shared_feed_2".to_string(),
                 rss_link: same_rss_url.to_string(),
+                ..Default::default()
             },
             Feed {
                 group: "group3".to_string(),
                 name: "shared_feed_3".to_string(),
                 rss_link: same_rss_url.to_string(),
+                ..Default::default()
             },
         ];
 
@@ -307,7 +902,14 @@ mod tests {
 
         // 1回目の実行：最初のフィードを処理
         let first_feed = vec![duplicate_feeds[0].clone()];
-        let result1 = task_collect_article_links(&mock_client, &first_feed, &pool).await;
+        let result1 = task_collect_article_links(
+            &mock_client,
+            &first_feed,
+            &TopicFilterConfig::default(),
+            &SeriesConfig::default(),
+            &pool,
+        )
+        .await;
         assert!(result1.is_ok(), "1回目のRSS収集処理が失敗しました");
 
         // 1回目実行後の件数確認（3件のリンクが挿入されるはず）
@@ -336,7 +938,14 @@ mod tests {
 
         // 2回目の実行：同一URLのフィードを再度処理（重複発生）
         let second_feed = vec![duplicate_feeds[1].clone()];
-        let result2 = task_collect_article_links(&mock_client, &second_feed, &pool).await;
+        let result2 = task_collect_article_links(
+            &mock_client,
+            &second_feed,
+            &TopicFilterConfig::default(),
+            &SeriesConfig::default(),
+            &pool,
+        )
+        .await;
         assert!(result2.is_ok(), "2回目のRSS収集処理が失敗しました");
 
         // 2回目実行後の件数確認（重複により件数は変わらず3件のまま）
@@ -384,7 +993,14 @@ mod tests {
         }
 
         // 3回目の実行：全ての重複フィードを一度に処理
-        let all_result = task_collect_article_links(&mock_client, &duplicate_feeds, &pool).await;
+        let all_result = task_collect_article_links(
+            &mock_client,
+            &duplicate_feeds,
+            &TopicFilterConfig::default(),
+            &SeriesConfig::default(),
+            &pool,
+        )
+        .await;
         assert!(all_result.is_ok(), "全重複フィードの処理が失敗しました");
 
         // 最終的な件数確認（依然として3件のまま）
@@ -426,9 +1042,17 @@ mod tests {
             group: "unique".to_string(),
             name: "unique_feed".to_string(),
             rss_link: "https://unique.example.com/different.xml".to_string(),
+            ..Default::default()
         }];
 
-        let unique_result = task_collect_article_links(&mock_client, &unique_feed, &pool).await;
+        let unique_result = task_collect_article_links(
+            &mock_client,
+            &unique_feed,
+            &TopicFilterConfig::default(),
+            &SeriesConfig::default(),
+            &pool,
+        )
+        .await;
         assert!(
             unique_result.is_ok(),
             "ユニークフィードの処理が失敗しました"
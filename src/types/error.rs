@@ -0,0 +1,237 @@
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// 外部ライブラリのエラーをそのまま保持するための型
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// エラーと原因のエラーを`: `で連結して表示用の文字列にする（anyhowの`{:#}`と同じ形式）
+pub fn format_error_chain(error: &(dyn std::error::Error + 'static)) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+/// infra層のエラー
+///
+/// 種類毎のエラー（取得・保存・解析・設定）をまとめたもので、
+/// 複数の種類のエラーを返し得る関数の戻り値に使う。種類で分岐する場合は各バリアントをmatchする。
+#[derive(Debug, Error)]
+pub enum InfraError {
+    #[error(transparent)]
+    Fetch(#[from] FetchError),
+    #[error(transparent)]
+    Store(#[from] StoreError),
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+}
+
+/// 外部サービス（RSSフィード・記事ページ・スクレイピングAPI）からの取得のエラー
+#[derive(Debug, Error)]
+pub enum FetchError {
+    /// クライアントの初期化に失敗
+    #[error("{client}の初期化に失敗")]
+    Client {
+        client: &'static str,
+        #[source]
+        source: BoxError,
+    },
+    /// リクエストの送信・接続に失敗
+    #[error("HTTPリクエストの送信に失敗: {url}")]
+    Request {
+        url: String,
+        #[source]
+        source: BoxError,
+    },
+    /// 応答がタイムアウトした
+    #[error("HTTPリクエストがタイムアウト: {url}")]
+    Timeout { url: String },
+    /// 2xx以外のステータスが返された
+    #[error("HTTPステータス {status}: {url}")]
+    Status { url: String, status: u16 },
+    /// レスポンス本文の受信に失敗
+    #[error("レスポンステキストの取得に失敗: {url}")]
+    Body {
+        url: String,
+        #[source]
+        source: BoxError,
+    },
+    /// 外部APIがエラーを返した
+    #[error("{service} エラー: {message}")]
+    Api {
+        service: &'static str,
+        message: String,
+    },
+    /// 取得した内容の解析に失敗
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    /// モックや記録の再生など、実際に通信しない取得元のエラー
+    #[error("{0}")]
+    Unavailable(String),
+}
+
+impl FetchError {
+    /// 再試行で回復し得るエラーかどうか（接続失敗・タイムアウト・5xx・429）
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Request { .. } | Self::Timeout { .. } | Self::Body { .. } => true,
+            Self::Status { status, .. } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+}
+
+/// DB・ファイルへの読み書きのエラー
+#[derive(Debug, Error)]
+pub enum StoreError {
+    #[error("データベースURLの環境変数DATABASE_URLが設定されていません")]
+    MissingDatabaseUrl,
+    /// DBの操作に失敗（`context`は失敗した操作の説明）
+    #[error("{context}")]
+    Database {
+        context: String,
+        #[source]
+        source: sqlx::Error,
+    },
+    #[error("データベースマイグレーションの実行に失敗しました")]
+    Migrate(#[from] sqlx::migrate::MigrateError),
+    #[error("スキーマ名は英小文字・数字・_のみ使用できます: {0}")]
+    InvalidSchemaName(String),
+    #[error("書き込みバッチャは既に停止しています")]
+    BatcherClosed,
+    #[error("書き込みバッチャの終了待ちに失敗")]
+    BatcherJoin(#[source] tokio::task::JoinError),
+    #[error("ファイルの読み込みに失敗しました: {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("ファイルの書き込みに失敗しました: {}", path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+impl StoreError {
+    /// DBエラーに失敗した操作の説明を付ける（`map_err`に渡して使う）
+    pub fn database(context: impl Into<String>) -> impl FnOnce(sqlx::Error) -> Self {
+        let context = context.into();
+        move |source| Self::Database { context, source }
+    }
+}
+
+impl From<sqlx::Error> for StoreError {
+    fn from(source: sqlx::Error) -> Self {
+        Self::Database {
+            context: "データベースの操作に失敗しました".to_string(),
+            source,
+        }
+    }
+}
+
+/// 日付・URL・RSS・JSON/YAML・実行計画・HTMLの解析のエラー
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("不正な日付形式: {0}")]
+    Date(String),
+    #[error("不正な間隔の形式: {0}（例: 30s, 15m, 2h, 1d）")]
+    Interval(String),
+    #[error("不正なURL形式: {0}")]
+    Url(String),
+    #[error("非対応のスキーム: {0}")]
+    UnsupportedScheme(String),
+    #[error("ホストが含まれていないURL: {0}")]
+    MissingHost(String),
+    #[error("RSSチャンネルの解析に失敗")]
+    Rss(#[from] rss::Error),
+    #[error("RSSファイルの解析に失敗: {}", path.display())]
+    RssFile {
+        path: PathBuf,
+        #[source]
+        source: rss::Error,
+    },
+    #[error("JSONファイルの解析に失敗: {}", path.display())]
+    Json {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("YAMLファイルの解析に失敗: {}", path.display())]
+    Yaml {
+        path: PathBuf,
+        #[source]
+        source: serde_yaml::Error,
+    },
+    #[error("実行計画の解析に失敗: {0}")]
+    QueryPlan(String),
+    #[error("本文の解析に失敗: {0}")]
+    Html(String),
+    #[error("本文の解析に失敗: 抽出結果が空です")]
+    EmptyContent,
+}
+
+/// 設定ファイル・環境変数の読み込みと検証のエラー
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    /// 設定ファイルの読み込み・解析に失敗
+    #[error("設定ファイルの読み込みに失敗: {}", path.display())]
+    Load {
+        path: PathBuf,
+        #[source]
+        source: Box<InfraError>,
+    },
+    /// 設定値が不正（`key`は設定項目のパス）
+    #[error("{key}の値が不正です: {message}")]
+    Invalid { key: String, message: String },
+}
+
+impl ConfigError {
+    pub fn invalid(key: &str, message: impl Into<String>) -> Self {
+        Self::Invalid {
+            key: key.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_hierarchy() {
+        // 種類毎のエラーはInfraErrorに変換でき、表示は元のエラーのまま
+        let error: InfraError = ParseError::Date("2025-13-40".to_string()).into();
+        assert!(matches!(error, InfraError::Parse(ParseError::Date(_))));
+        assert_eq!(error.to_string(), "不正な日付形式: 2025-13-40");
+
+        // 原因のエラーはsourceで辿れる（anyhowの`{:#}`で連結して表示される）
+        let error_for_chain = || ConfigError::Load {
+            path: PathBuf::from("config/app.yaml"),
+            source: Box::new(StoreError::MissingDatabaseUrl.into()),
+        };
+        let chain = format!("{:#}", anyhow::Error::from(error_for_chain()));
+        assert!(chain.contains("config/app.yaml"));
+        assert!(chain.contains("DATABASE_URL"));
+        assert_eq!(format_error_chain(&error_for_chain()), chain);
+
+        let status = |status| FetchError::Status {
+            url: "https://example.com".to_string(),
+            status,
+        };
+        assert!(status(503).is_transient());
+        assert!(status(429).is_transient());
+        assert!(!status(404).is_transient());
+        assert!(!FetchError::Unavailable("記録無し".to_string()).is_transient());
+        println!("✅ エラー型の階層テスト成功");
+    }
+}
@@ -0,0 +1,5 @@
+pub mod error;
+
+pub use error::{
+    format_error_chain, BoxError, ConfigError, FetchError, InfraError, ParseError, StoreError,
+};